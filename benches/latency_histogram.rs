@@ -0,0 +1,111 @@
+//! Latency histogram cost at market data rates
+//!
+//! `record_one_second_10k` replays one second of a 10k msg/s feed, so its
+//! time must stay well under one second of CPU for recording to keep up.
+
+use criterion::{BatchSize, Criterion, Throughput, black_box, criterion_group, criterion_main};
+use xtrade::metrics::{LatencyHistogram, LatencyWindow, RollingHistogram};
+
+const MESSAGES_PER_SECOND: u64 = 10_000;
+const START_MS: u64 = 1_700_000_000_000;
+
+/// Deterministic latency pattern: mostly fast, with a long tail
+fn latency(i: u64) -> u64 {
+    let jitter = i.wrapping_mul(2_654_435_761) % 1_000;
+    match jitter {
+        0..=899 => 5 + jitter % 40,
+        900..=989 => 50 + jitter,
+        _ => 2_000 + jitter * 7,
+    }
+}
+
+/// An hour of traffic sampled once per 100 ms at 10k msg/s volume
+fn filled_hour() -> RollingHistogram {
+    let mut rolling = RollingHistogram::new();
+    for i in 0..3_600_000 / 100 {
+        let now = START_MS + i * 100;
+        for j in 0..MESSAGES_PER_SECOND / 10 {
+            rolling.record(latency(i * 1_000 + j), now);
+        }
+    }
+    rolling
+}
+
+fn bench_record(c: &mut Criterion) {
+    let mut group = c.benchmark_group("record");
+    group.throughput(Throughput::Elements(MESSAGES_PER_SECOND));
+
+    group.bench_function("record_one_second_10k", |b| {
+        b.iter_batched_ref(
+            RollingHistogram::new,
+            |rolling| {
+                for i in 0..MESSAGES_PER_SECOND {
+                    rolling.record(black_box(latency(i)), START_MS + i / 10);
+                }
+            },
+            BatchSize::SmallInput,
+        )
+    });
+
+    // The previous implementation: bounded Vec with front eviction
+    group.bench_function("vec_remove_front_one_second_10k", |b| {
+        b.iter_batched_ref(
+            || Vec::<u64>::with_capacity(1_000),
+            |samples| {
+                for i in 0..MESSAGES_PER_SECOND {
+                    if samples.len() >= 1_000 {
+                        samples.remove(0);
+                    }
+                    samples.push(black_box(latency(i)));
+                }
+            },
+            BatchSize::SmallInput,
+        )
+    });
+    group.finish();
+}
+
+fn bench_query(c: &mut Criterion) {
+    let rolling = filled_hour();
+    let now = START_MS + 3_600_000 - 1;
+
+    let mut group = c.benchmark_group("summary");
+    for window in LatencyWindow::ALL {
+        group.bench_function(window.label(), |b| {
+            b.iter(|| black_box(rolling.summary(window, black_box(now))))
+        });
+    }
+    group.finish();
+
+    let symbols: Vec<LatencyHistogram> = (0..20)
+        .map(|s| {
+            let mut histogram = LatencyHistogram::new();
+            for i in 0..10_000 {
+                histogram.record(latency(s * 10_000 + i));
+            }
+            histogram
+        })
+        .collect();
+    c.bench_function("merge_20_symbols", |b| {
+        b.iter(|| {
+            let mut merged = LatencyHistogram::new();
+            for histogram in &symbols {
+                merged.merge(histogram);
+            }
+            black_box(merged.summary())
+        })
+    });
+
+    // The previous implementation: clone and sort on every refresh
+    let samples: Vec<u64> = (0..1_000).map(latency).collect();
+    c.bench_function("vec_sort_1000_samples", |b| {
+        b.iter(|| {
+            let mut sorted = samples.clone();
+            sorted.sort_unstable();
+            black_box(sorted[sorted.len() * 99 / 100])
+        })
+    });
+}
+
+criterion_group!(benches, bench_record, bench_query);
+criterion_main!(benches);
@@ -0,0 +1,536 @@
+//! Authenticated Binance account: balances, open orders and execution reports
+//!
+//! A REST snapshot seeds [`AccountState`]; the listen-key user data stream run
+//! by [`stream::spawn_account_stream`] keeps it current. The stream is opened
+//! before the snapshot is taken so no update between the two is lost; updates
+//! that predate the snapshot are simply applied again.
+
+pub mod stream;
+
+use std::collections::{BTreeMap, VecDeque};
+
+use anyhow::{Result, anyhow};
+use serde::{Deserialize, Serialize};
+
+use crate::binance::types::{
+    ExecutionReportMessage, RestAccountInfo, RestOpenOrder, UserDataMessage,
+};
+
+pub use stream::{AccountEvent, AccountStreamHandle, spawn_account_stream};
+
+/// Execution reports kept for the account panel
+pub const EXECUTION_HISTORY: usize = 100;
+
+/// Order statuses after which an order no longer rests on the book
+const TERMINAL_STATUSES: [&str; 5] = [
+    "FILLED",
+    "CANCELED",
+    "REJECTED",
+    "EXPIRED",
+    "EXPIRED_IN_MATCH",
+];
+
+/// Free and locked amount of one asset
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AccountBalance {
+    pub asset: String,
+    pub free: f64,
+    pub locked: f64,
+}
+
+impl AccountBalance {
+    pub fn total(&self) -> f64 {
+        self.free + self.locked
+    }
+}
+
+/// An order resting on the exchange
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AccountOrder {
+    pub symbol: String,
+    pub order_id: u64,
+    pub client_order_id: String,
+    pub side: String,
+    pub order_type: String,
+    pub price: f64,
+    pub stop_price: f64,
+    pub quantity: f64,
+    pub filled_qty: f64,
+    pub status: String,
+    pub time_ms: u64,
+}
+
+/// One `executionReport`: an order was accepted, traded, cancelled, rejected or expired
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Execution {
+    pub symbol: String,
+    pub order_id: u64,
+    pub client_order_id: String,
+    pub side: String,
+    pub order_type: String,
+    /// `NEW`, `TRADE`, `CANCELED`, `REJECTED`, `EXPIRED`, ...
+    pub execution_type: String,
+    /// Order status after this execution
+    pub status: String,
+    pub price: f64,
+    pub stop_price: f64,
+    pub quantity: f64,
+    pub last_qty: f64,
+    pub last_price: f64,
+    pub filled_qty: f64,
+    pub commission: f64,
+    pub commission_asset: Option<String>,
+    pub reject_reason: String,
+    pub is_maker: bool,
+    pub order_time_ms: u64,
+    pub time_ms: u64,
+}
+
+impl Execution {
+    /// Whether the order stopped resting on the book with this execution
+    pub fn is_terminal(&self) -> bool {
+        TERMINAL_STATUSES.contains(&self.status.as_str())
+    }
+}
+
+/// Connection state of the user data stream
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub enum StreamStatus {
+    #[default]
+    Connecting,
+    /// Snapshot loaded and stream connected
+    Live,
+    /// Connection lost; a new listen key will be requested
+    Reconnecting { reason: String },
+    /// Gave up, e.g. because the credentials were rejected
+    Failed { reason: String },
+}
+
+impl StreamStatus {
+    pub fn describe(&self) -> String {
+        match self {
+            StreamStatus::Connecting => "connecting".to_string(),
+            StreamStatus::Live => "live".to_string(),
+            StreamStatus::Reconnecting { reason } => format!("reconnecting ({})", reason),
+            StreamStatus::Failed { reason } => format!("failed ({})", reason),
+        }
+    }
+}
+
+/// Account update decoded from the user data stream
+#[derive(Debug, Clone, PartialEq)]
+pub enum AccountUpdate {
+    /// New balances of the assets that changed
+    Balances {
+        balances: Vec<AccountBalance>,
+        time_ms: u64,
+    },
+    /// Deposit, withdrawal or transfer of one asset
+    BalanceDelta {
+        asset: String,
+        delta: f64,
+        time_ms: u64,
+    },
+    /// Order lifecycle event
+    Execution(Box<Execution>),
+}
+
+impl AccountUpdate {
+    /// Decode a stream message; `None` for `listenKeyExpired`, which the stream handles itself
+    pub fn from_message(message: UserDataMessage) -> Option<Result<Self>> {
+        let update = match message {
+            UserDataMessage::AccountPosition(position) => position
+                .balances
+                .into_iter()
+                .map(|balance| {
+                    Ok(AccountBalance {
+                        free: parse_amount(&balance.free, "free")?,
+                        locked: parse_amount(&balance.locked, "locked")?,
+                        asset: balance.asset,
+                    })
+                })
+                .collect::<Result<Vec<_>>>()
+                .map(|balances| AccountUpdate::Balances {
+                    balances,
+                    time_ms: position.event_time,
+                }),
+            UserDataMessage::BalanceUpdate(update) => {
+                parse_amount(&update.delta, "delta").map(|delta| AccountUpdate::BalanceDelta {
+                    asset: update.asset,
+                    delta,
+                    time_ms: update.event_time,
+                })
+            }
+            UserDataMessage::ExecutionReport(report) => Execution::try_from(*report)
+                .map(|execution| AccountUpdate::Execution(Box::new(execution))),
+            UserDataMessage::ListenKeyExpired { .. } => return None,
+        };
+        Some(update)
+    }
+}
+
+impl TryFrom<ExecutionReportMessage> for Execution {
+    type Error = anyhow::Error;
+
+    fn try_from(report: ExecutionReportMessage) -> Result<Self> {
+        Ok(Self {
+            price: parse_amount(&report.price, "price")?,
+            stop_price: parse_optional_amount(&report.stop_price, "stop price")?,
+            quantity: parse_amount(&report.quantity, "quantity")?,
+            last_qty: parse_amount(&report.last_quantity, "last quantity")?,
+            last_price: parse_amount(&report.last_price, "last price")?,
+            filled_qty: parse_amount(&report.cumulative_quantity, "cumulative quantity")?,
+            commission: parse_optional_amount(&report.commission, "commission")?,
+            symbol: report.symbol,
+            order_id: report.order_id,
+            // Cancels carry the cancel request's id in `c` and the order's own in `C`
+            client_order_id: report
+                .original_client_order_id
+                .filter(|id| !id.is_empty())
+                .unwrap_or(report.client_order_id),
+            side: report.side,
+            order_type: report.order_type,
+            execution_type: report.execution_type,
+            status: report.status,
+            commission_asset: report.commission_asset,
+            reject_reason: report.reject_reason,
+            is_maker: report.is_maker,
+            order_time_ms: report.order_time,
+            time_ms: report.transaction_time,
+        })
+    }
+}
+
+impl TryFrom<RestOpenOrder> for AccountOrder {
+    type Error = anyhow::Error;
+
+    fn try_from(order: RestOpenOrder) -> Result<Self> {
+        Ok(Self {
+            price: parse_amount(&order.price, "price")?,
+            stop_price: parse_optional_amount(&order.stop_price, "stop price")?,
+            quantity: parse_amount(&order.orig_qty, "quantity")?,
+            filled_qty: parse_amount(&order.executed_qty, "executed quantity")?,
+            symbol: order.symbol,
+            order_id: order.order_id,
+            client_order_id: order.client_order_id,
+            side: order.side,
+            order_type: order.order_type,
+            status: order.status,
+            time_ms: order.time,
+        })
+    }
+}
+
+/// Convert the REST account and open orders into snapshot parts
+pub fn snapshot_from_rest(
+    account: RestAccountInfo,
+    open_orders: Vec<RestOpenOrder>,
+) -> Result<(Vec<AccountBalance>, Vec<AccountOrder>)> {
+    let balances = account
+        .balances
+        .into_iter()
+        .map(|balance| {
+            Ok(AccountBalance {
+                free: parse_amount(&balance.free, "free")?,
+                locked: parse_amount(&balance.locked, "locked")?,
+                asset: balance.asset,
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let orders = open_orders
+        .into_iter()
+        .map(AccountOrder::try_from)
+        .collect::<Result<Vec<_>>>()?;
+    Ok((balances, orders))
+}
+
+/// Balances, open orders and recent executions shown in the account panel
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AccountSnapshot {
+    pub balances: Vec<AccountBalance>,
+    pub open_orders: Vec<AccountOrder>,
+    /// Most recent first
+    pub executions: Vec<Execution>,
+    pub status: StreamStatus,
+    pub updated_ms: u64,
+}
+
+/// Live view of the account, fed by REST snapshots and stream updates
+#[derive(Debug, Clone, Default)]
+pub struct AccountState {
+    balances: BTreeMap<String, AccountBalance>,
+    open_orders: BTreeMap<u64, AccountOrder>,
+    executions: VecDeque<Execution>,
+    status: StreamStatus,
+    updated_ms: u64,
+}
+
+impl AccountState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Replace balances and open orders with a freshly loaded snapshot
+    pub fn apply_snapshot(
+        &mut self,
+        balances: Vec<AccountBalance>,
+        open_orders: Vec<AccountOrder>,
+        time_ms: u64,
+    ) {
+        self.balances = balances
+            .into_iter()
+            .filter(|balance| balance.total() > 0.0)
+            .map(|balance| (balance.asset.clone(), balance))
+            .collect();
+        self.open_orders = open_orders
+            .into_iter()
+            .map(|order| (order.order_id, order))
+            .collect();
+        self.updated_ms = time_ms;
+    }
+
+    pub fn apply(&mut self, update: &AccountUpdate) {
+        match update {
+            AccountUpdate::Balances { balances, time_ms } => {
+                for balance in balances {
+                    if balance.total() > 0.0 {
+                        self.balances.insert(balance.asset.clone(), balance.clone());
+                    } else {
+                        self.balances.remove(&balance.asset);
+                    }
+                }
+                self.updated_ms = self.updated_ms.max(*time_ms);
+            }
+            AccountUpdate::BalanceDelta {
+                asset,
+                delta,
+                time_ms,
+            } => {
+                // An outboundAccountPosition with the settled amounts follows
+                let balance =
+                    self.balances
+                        .entry(asset.clone())
+                        .or_insert_with(|| AccountBalance {
+                            asset: asset.clone(),
+                            free: 0.0,
+                            locked: 0.0,
+                        });
+                balance.free = (balance.free + delta).max(0.0);
+                self.updated_ms = self.updated_ms.max(*time_ms);
+            }
+            AccountUpdate::Execution(execution) => {
+                if execution.is_terminal() {
+                    self.open_orders.remove(&execution.order_id);
+                } else {
+                    self.open_orders.insert(
+                        execution.order_id,
+                        AccountOrder {
+                            symbol: execution.symbol.clone(),
+                            order_id: execution.order_id,
+                            client_order_id: execution.client_order_id.clone(),
+                            side: execution.side.clone(),
+                            order_type: execution.order_type.clone(),
+                            price: execution.price,
+                            stop_price: execution.stop_price,
+                            quantity: execution.quantity,
+                            filled_qty: execution.filled_qty,
+                            status: execution.status.clone(),
+                            time_ms: execution.order_time_ms,
+                        },
+                    );
+                }
+                self.executions.push_front((**execution).clone());
+                self.executions.truncate(EXECUTION_HISTORY);
+                self.updated_ms = self.updated_ms.max(execution.time_ms);
+            }
+        }
+    }
+
+    pub fn set_status(&mut self, status: StreamStatus) {
+        self.status = status;
+    }
+
+    pub fn status(&self) -> &StreamStatus {
+        &self.status
+    }
+
+    pub fn balance(&self, asset: &str) -> Option<&AccountBalance> {
+        self.balances.get(asset)
+    }
+
+    pub fn open_orders(&self) -> impl Iterator<Item = &AccountOrder> {
+        self.open_orders.values()
+    }
+
+    pub fn snapshot(&self) -> AccountSnapshot {
+        AccountSnapshot {
+            balances: self.balances.values().cloned().collect(),
+            open_orders: self.open_orders.values().cloned().collect(),
+            executions: self.executions.iter().cloned().collect(),
+            status: self.status.clone(),
+            updated_ms: self.updated_ms,
+        }
+    }
+}
+
+fn parse_amount(raw: &str, field: &str) -> Result<f64> {
+    raw.parse::<f64>()
+        .map_err(|e| anyhow!("Invalid {} '{}': {}", field, raw, e))
+}
+
+/// Like [`parse_amount`], but an absent value counts as zero
+fn parse_optional_amount(raw: &str, field: &str) -> Result<f64> {
+    if raw.is_empty() {
+        Ok(0.0)
+    } else {
+        parse_amount(raw, field)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{Value, json};
+
+    fn execution_report(order_id: u64, execution_type: &str, status: &str, filled: &str) -> Value {
+        json!({
+            "e": "executionReport",
+            "E": 1_700_000_000_100u64,
+            "s": "BTCUSDT",
+            "c": "web_1",
+            "C": "",
+            "S": "BUY",
+            "o": "LIMIT",
+            "f": "GTC",
+            "q": "0.50000000",
+            "p": "65000.00",
+            "P": "0.00",
+            "x": execution_type,
+            "X": status,
+            "r": "NONE",
+            "i": order_id,
+            "l": "0.25000000",
+            "z": filled,
+            "L": "65000.00",
+            "n": "0.00025",
+            "N": "BTC",
+            "T": 1_700_000_000_050u64,
+            "t": 42,
+            "O": 1_700_000_000_000u64,
+            "m": true,
+            "M": true
+        })
+    }
+
+    fn decode(value: Value) -> AccountUpdate {
+        let message: UserDataMessage = serde_json::from_value(value).unwrap();
+        AccountUpdate::from_message(message).unwrap().unwrap()
+    }
+
+    #[test]
+    fn decodes_user_data_events() {
+        let update = decode(json!({
+            "e": "outboundAccountPosition",
+            "E": 1_564_034_571_105u64,
+            "u": 1_564_034_571_073u64,
+            "B": [{"a": "ETH", "f": "10000.000000", "l": "0.000000"}]
+        }));
+        assert_eq!(
+            update,
+            AccountUpdate::Balances {
+                balances: vec![AccountBalance {
+                    asset: "ETH".to_string(),
+                    free: 10_000.0,
+                    locked: 0.0,
+                }],
+                time_ms: 1_564_034_571_105,
+            }
+        );
+
+        let AccountUpdate::Execution(execution) =
+            decode(execution_report(7, "TRADE", "PARTIALLY_FILLED", "0.25"))
+        else {
+            panic!("expected an execution");
+        };
+        assert_eq!(execution.order_id, 7);
+        assert_eq!(execution.client_order_id, "web_1");
+        assert_eq!(execution.last_qty, 0.25);
+        assert_eq!(execution.commission_asset.as_deref(), Some("BTC"));
+        assert!(execution.is_maker);
+        assert!(!execution.is_terminal());
+
+        let expired: UserDataMessage = serde_json::from_value(json!({
+            "e": "listenKeyExpired",
+            "E": 1_576_653_824_250u64,
+            "listenKey": "abc"
+        }))
+        .unwrap();
+        assert!(AccountUpdate::from_message(expired).is_none());
+    }
+
+    #[test]
+    fn executions_track_open_orders() {
+        let mut state = AccountState::new();
+        state.apply_snapshot(Vec::new(), Vec::new(), 1);
+
+        state.apply(&decode(execution_report(7, "NEW", "NEW", "0")));
+        assert_eq!(state.open_orders().count(), 1);
+
+        state.apply(&decode(execution_report(
+            7,
+            "TRADE",
+            "PARTIALLY_FILLED",
+            "0.25",
+        )));
+        let order = state.open_orders().next().unwrap();
+        assert_eq!(order.filled_qty, 0.25);
+        assert_eq!(order.status, "PARTIALLY_FILLED");
+
+        state.apply(&decode(execution_report(7, "TRADE", "FILLED", "0.5")));
+        assert_eq!(state.open_orders().count(), 0);
+
+        let snapshot = state.snapshot();
+        assert_eq!(snapshot.executions.len(), 3);
+        assert_eq!(snapshot.executions[0].status, "FILLED");
+    }
+
+    #[test]
+    fn balance_updates_replace_and_drop_empty_assets() {
+        let mut state = AccountState::new();
+        state.apply_snapshot(
+            vec![
+                AccountBalance {
+                    asset: "USDT".to_string(),
+                    free: 100.0,
+                    locked: 0.0,
+                },
+                AccountBalance {
+                    asset: "BNB".to_string(),
+                    free: 0.0,
+                    locked: 0.0,
+                },
+            ],
+            Vec::new(),
+            1,
+        );
+        assert!(state.balance("BNB").is_none());
+
+        state.apply(&AccountUpdate::BalanceDelta {
+            asset: "USDT".to_string(),
+            delta: 25.0,
+            time_ms: 2,
+        });
+        assert_eq!(state.balance("USDT").unwrap().free, 125.0);
+
+        state.apply(&AccountUpdate::Balances {
+            balances: vec![AccountBalance {
+                asset: "USDT".to_string(),
+                free: 0.0,
+                locked: 0.0,
+            }],
+            time_ms: 3,
+        });
+        assert!(state.balance("USDT").is_none());
+        assert_eq!(state.snapshot().updated_ms, 3);
+    }
+}
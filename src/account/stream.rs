@@ -0,0 +1,264 @@
+//! Listen-key user data stream task
+//!
+//! Each connection opens a listen key, connects to `<ws_url>/ws/<listenKey>`,
+//! loads a REST snapshot and then forwards stream updates. The key is kept
+//! alive on a timer; when it expires or the connection drops, a new key is
+//! requested with exponential back-off. Rejected credentials end the task.
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use anyhow::{Result, anyhow};
+use futures_util::{SinkExt, StreamExt};
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tokio::time::{Instant, interval_at};
+use tokio_tungstenite::{connect_async, tungstenite::protocol::Message};
+use tracing::{debug, error, info, warn};
+
+use super::{AccountBalance, AccountOrder, AccountUpdate, StreamStatus, snapshot_from_rest};
+use crate::binance::BinanceRestClient;
+use crate::binance::auth::redact;
+use crate::binance::types::{RestApiError, UserDataMessage};
+
+/// Keeps quiet connections open; the stream may go minutes without an event
+const PING_INTERVAL: Duration = Duration::from_secs(60);
+
+/// First reconnect delay, doubled after every failed attempt
+const INITIAL_RECONNECT_DELAY: Duration = Duration::from_secs(1);
+
+/// Upper bound for the reconnect delay
+const MAX_RECONNECT_DELAY: Duration = Duration::from_secs(60);
+
+/// Output of the account stream task
+#[derive(Debug, Clone, PartialEq)]
+pub enum AccountEvent {
+    /// Balances and open orders loaded over REST after (re)connecting
+    Snapshot {
+        balances: Vec<AccountBalance>,
+        open_orders: Vec<AccountOrder>,
+        time_ms: u64,
+    },
+    /// Update received on the user data stream
+    Update(AccountUpdate),
+    /// Stream connected, lost or given up
+    Status(StreamStatus),
+}
+
+/// Handle for the account stream task
+pub struct AccountStreamHandle {
+    pub task: JoinHandle<()>,
+    stop_tx: mpsc::UnboundedSender<()>,
+    rest: BinanceRestClient,
+}
+
+impl AccountStreamHandle {
+    /// Ask the task to close its listen key and exit
+    pub fn stop(&self) {
+        let _ = self.stop_tx.send(());
+    }
+
+    /// Apply a newly measured exchange clock offset to signed requests
+    pub fn set_time_offset_ms(&self, offset_ms: i64) {
+        self.rest.set_time_offset_ms(offset_ms);
+    }
+}
+
+/// Why a single listen key session ended without an error
+enum StreamEnd {
+    Stopped,
+    Expired,
+}
+
+/// Stream account updates for the credentials `rest` was built with
+///
+/// `ws_url` is the stream base URL, e.g. `wss://stream.binance.com:9443`.
+pub fn spawn_account_stream(
+    rest: BinanceRestClient,
+    ws_url: impl Into<String>,
+    keepalive_interval: Duration,
+    events: mpsc::UnboundedSender<AccountEvent>,
+) -> AccountStreamHandle {
+    let (stop_tx, mut stop_rx) = mpsc::unbounded_channel();
+    let ws_url = ws_url.into();
+    let task_rest = rest.clone();
+
+    let task = tokio::spawn(async move {
+        let rest = task_rest;
+        let mut delay = INITIAL_RECONNECT_DELAY;
+
+        loop {
+            let result =
+                run_listen_key(&rest, &ws_url, keepalive_interval, &events, &mut stop_rx).await;
+
+            let reason = match result {
+                Ok(StreamEnd::Stopped) => break,
+                Ok(StreamEnd::Expired) => {
+                    delay = INITIAL_RECONNECT_DELAY;
+                    "listen key expired".to_string()
+                }
+                Err(e) if is_fatal(&e) => {
+                    error!("User data stream stopped: {:#}", e);
+                    let _ = events.send(AccountEvent::Status(StreamStatus::Failed {
+                        reason: e.to_string(),
+                    }));
+                    break;
+                }
+                Err(e) => {
+                    warn!("User data stream dropped: {:#}; retrying in {:?}", e, delay);
+                    e.to_string()
+                }
+            };
+
+            if events
+                .send(AccountEvent::Status(StreamStatus::Reconnecting { reason }))
+                .is_err()
+            {
+                break;
+            }
+            tokio::select! {
+                _ = tokio::time::sleep(delay) => {}
+                _ = stop_rx.recv() => break,
+            }
+            delay = (delay * 2).min(MAX_RECONNECT_DELAY);
+        }
+
+        info!("User data stream stopped");
+    });
+
+    AccountStreamHandle {
+        task,
+        stop_tx,
+        rest,
+    }
+}
+
+/// Open a listen key, stream it until it ends, and close it unless it expired
+async fn run_listen_key(
+    rest: &BinanceRestClient,
+    ws_url: &str,
+    keepalive_interval: Duration,
+    events: &mpsc::UnboundedSender<AccountEvent>,
+    stop_rx: &mut mpsc::UnboundedReceiver<()>,
+) -> Result<StreamEnd> {
+    let listen_key = rest.create_listen_key().await?;
+    let result = stream_listen_key(
+        rest,
+        ws_url,
+        &listen_key,
+        keepalive_interval,
+        events,
+        stop_rx,
+    )
+    .await;
+
+    if !matches!(result, Ok(StreamEnd::Expired)) {
+        if let Err(e) = rest.close_listen_key(&listen_key).await {
+            debug!(
+                "Failed to close user data stream {}: {}",
+                redact(&listen_key),
+                e
+            );
+        }
+    }
+
+    result
+}
+
+async fn stream_listen_key(
+    rest: &BinanceRestClient,
+    ws_url: &str,
+    listen_key: &str,
+    keepalive_interval: Duration,
+    events: &mpsc::UnboundedSender<AccountEvent>,
+    stop_rx: &mut mpsc::UnboundedReceiver<()>,
+) -> Result<StreamEnd> {
+    let url = format!("{}/ws/{}", ws_url.trim_end_matches('/'), listen_key);
+    let (mut ws, _) = connect_async(&url)
+        .await
+        .map_err(|e| anyhow!("Failed to connect user data stream: {}", e))?;
+    info!("Connected user data stream {}", redact(listen_key));
+
+    // Snapshot after connecting, so updates in between are not lost
+    let account = rest.get_account().await?;
+    let open_orders = rest.get_open_orders(None).await?;
+    let (balances, open_orders) = snapshot_from_rest(account, open_orders)?;
+    let snapshot = AccountEvent::Snapshot {
+        balances,
+        open_orders,
+        time_ms: local_time_ms(),
+    };
+    if events.send(snapshot).is_err()
+        || events
+            .send(AccountEvent::Status(StreamStatus::Live))
+            .is_err()
+    {
+        return Ok(StreamEnd::Stopped);
+    }
+
+    let mut keepalive = interval_at(Instant::now() + keepalive_interval, keepalive_interval);
+    let mut ping = interval_at(Instant::now() + PING_INTERVAL, PING_INTERVAL);
+
+    loop {
+        tokio::select! {
+            _ = stop_rx.recv() => {
+                if let Err(e) = ws.close(None).await {
+                    debug!("Failed to close user data stream connection: {}", e);
+                }
+                return Ok(StreamEnd::Stopped);
+            }
+            _ = keepalive.tick() => {
+                rest.keepalive_listen_key(listen_key).await?;
+            }
+            _ = ping.tick() => {
+                ws.send(Message::Ping(Vec::new()))
+                    .await
+                    .map_err(|e| anyhow!("Failed to ping user data stream: {}", e))?;
+            }
+            message = ws.next() => match message {
+                Some(Ok(Message::Text(text))) => {
+                    match serde_json::from_str::<UserDataMessage>(&text) {
+                        Ok(UserDataMessage::ListenKeyExpired { .. }) => {
+                            info!("User data stream {} expired", redact(listen_key));
+                            return Ok(StreamEnd::Expired);
+                        }
+                        Ok(message) => {
+                            let update = match AccountUpdate::from_message(message) {
+                                Some(Ok(update)) => update,
+                                Some(Err(e)) => {
+                                    warn!("Ignoring malformed user data event: {}", e);
+                                    continue;
+                                }
+                                None => continue,
+                            };
+                            if events.send(AccountEvent::Update(update)).is_err() {
+                                return Ok(StreamEnd::Stopped);
+                            }
+                        }
+                        Err(e) => debug!("Ignoring unrecognised user data message: {}", e),
+                    }
+                }
+                Some(Ok(Message::Close(_))) => {
+                    return Err(anyhow!("User data stream closed by the server"));
+                }
+                Some(Ok(_)) => {}
+                Some(Err(e)) => return Err(anyhow!("User data stream error: {}", e)),
+                None => return Err(anyhow!("User data stream ended")),
+            }
+        }
+    }
+}
+
+/// Errors retrying cannot fix
+fn is_fatal(error: &anyhow::Error) -> bool {
+    matches!(
+        error.downcast_ref::<RestApiError>(),
+        Some(RestApiError::Unauthorized(_) | RestApiError::MissingCredentials)
+    )
+}
+
+fn local_time_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
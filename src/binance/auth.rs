@@ -0,0 +1,207 @@
+//! API credentials and request signing for Binance `USER_DATA` endpoints
+//!
+//! Credentials come from `XTRADE_BINANCE_API_KEY` / `XTRADE_BINANCE_API_SECRET`
+//! or from a TOML credentials file that must not be readable by group or
+//! others. They are deliberately kept out of [`crate::config::Config`] so they
+//! never end up in `config show` output, and `Debug` only prints a key prefix.
+
+use std::fmt;
+use std::path::Path;
+
+use anyhow::{Context, Result, anyhow};
+use hmac::{Hmac, Mac};
+use serde::Deserialize;
+use sha2::Sha256;
+
+use crate::config::AccountConfig;
+
+/// Environment variable holding the API key
+pub const API_KEY_ENV: &str = "XTRADE_BINANCE_API_KEY";
+/// Environment variable holding the API secret
+pub const API_SECRET_ENV: &str = "XTRADE_BINANCE_API_SECRET";
+
+/// Binance API key and secret
+#[derive(Clone, PartialEq, Eq)]
+pub struct ApiCredentials {
+    api_key: String,
+    api_secret: String,
+}
+
+#[derive(Deserialize)]
+struct CredentialsFile {
+    api_key: String,
+    api_secret: String,
+}
+
+impl ApiCredentials {
+    pub fn new(api_key: impl Into<String>, api_secret: impl Into<String>) -> Result<Self> {
+        let api_key = api_key.into().trim().to_string();
+        let api_secret = api_secret.into().trim().to_string();
+        if api_key.is_empty() || api_secret.is_empty() {
+            anyhow::bail!("API key and secret must both be set");
+        }
+        Ok(Self {
+            api_key,
+            api_secret,
+        })
+    }
+
+    /// Load credentials from the environment, falling back to `account.credentials_file`
+    pub fn load(config: &AccountConfig) -> Result<Self> {
+        if let Some(credentials) = Self::from_env()? {
+            return Ok(credentials);
+        }
+        if config.credentials_file.trim().is_empty() {
+            return Err(anyhow!(
+                "No API credentials: set {} and {} or account.credentials_file",
+                API_KEY_ENV,
+                API_SECRET_ENV
+            ));
+        }
+        Self::from_file(&config.credentials_file)
+    }
+
+    /// Credentials from `XTRADE_BINANCE_API_KEY` and `XTRADE_BINANCE_API_SECRET`
+    ///
+    /// Returns `Ok(None)` when neither is set and an error when only one is.
+    pub fn from_env() -> Result<Option<Self>> {
+        let key = std::env::var(API_KEY_ENV).ok();
+        let secret = std::env::var(API_SECRET_ENV).ok();
+        match (key, secret) {
+            (None, None) => Ok(None),
+            (Some(key), Some(secret)) => Self::new(key, secret).map(Some),
+            _ => Err(anyhow!(
+                "{} and {} must be set together",
+                API_KEY_ENV,
+                API_SECRET_ENV
+            )),
+        }
+    }
+
+    /// Read `api_key` and `api_secret` from a TOML file only the owner can read
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        check_permissions(path)?;
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read credentials file: {}", path.display()))?;
+        // The parse error could quote the secret, so it is not passed on
+        let file: CredentialsFile = toml::from_str(&content).map_err(|_| {
+            anyhow!(
+                "Failed to parse credentials file {}: expected api_key and api_secret",
+                path.display()
+            )
+        })?;
+        Self::new(file.api_key, file.api_secret)
+    }
+
+    pub fn api_key(&self) -> &str {
+        &self.api_key
+    }
+
+    /// Hex-encoded HMAC-SHA256 of `payload` keyed with the API secret
+    pub fn sign(&self, payload: &str) -> String {
+        let mut mac = Hmac::<Sha256>::new_from_slice(self.api_secret.as_bytes())
+            .expect("HMAC accepts keys of any length");
+        mac.update(payload.as_bytes());
+        hex::encode(mac.finalize().into_bytes())
+    }
+}
+
+impl fmt::Debug for ApiCredentials {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ApiCredentials")
+            .field("api_key", &redact(&self.api_key))
+            .field("api_secret", &"<redacted>")
+            .finish()
+    }
+}
+
+/// First four characters followed by an ellipsis, for logs
+pub fn redact(value: &str) -> String {
+    let prefix: String = value.chars().take(4).collect();
+    format!("{}…", prefix)
+}
+
+#[cfg(unix)]
+fn check_permissions(path: &Path) -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let metadata = std::fs::metadata(path)
+        .with_context(|| format!("Failed to read credentials file: {}", path.display()))?;
+    let mode = metadata.permissions().mode() & 0o777;
+    if mode & 0o077 != 0 {
+        anyhow::bail!(
+            "Credentials file {} is accessible by other users (mode {:o}); run chmod 600 on it",
+            path.display(),
+            mode
+        );
+    }
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn check_permissions(_path: &Path) -> Result<()> {
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn signs_binance_documentation_example() {
+        let credentials = ApiCredentials::new(
+            "vmPUZE6mv9SD5VNHk4HlWFsOr6aKE2zvsw0MuIgwCIPy6utIco14y7Ju91duEh8A",
+            "NhqPtmdSJYdKjVHjA7PZj4Mge3R5YNiP1e3UZjInClVN65XAbvqqM6A7H5fATj0j",
+        )
+        .unwrap();
+        let payload = "symbol=LTCBTC&side=BUY&type=LIMIT&timeInForce=GTC&quantity=1&price=0.1&recvWindow=5000&timestamp=1499827319559";
+
+        assert_eq!(
+            credentials.sign(payload),
+            "c8db56825ae71d6d79447849e617115f4a920fa2acdcab2b053c4b2838bd6b71"
+        );
+    }
+
+    #[test]
+    fn debug_output_hides_the_secret() {
+        let credentials = ApiCredentials::new("abcdefgh", "topsecret").unwrap();
+        let debug = format!("{:?}", credentials);
+
+        assert!(debug.contains("abcd…"));
+        assert!(!debug.contains("abcdefgh"));
+        assert!(!debug.contains("topsecret"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn credentials_file_must_be_private() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("credentials.toml");
+        std::fs::write(&path, "api_key = \"key\"\napi_secret = \"secret\"\n").unwrap();
+
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o644)).unwrap();
+        let err = ApiCredentials::from_file(&path).unwrap_err();
+        assert!(err.to_string().contains("chmod 600"));
+
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o600)).unwrap();
+        let credentials = ApiCredentials::from_file(&path).unwrap();
+        assert_eq!(credentials.api_key(), "key");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn parse_errors_do_not_echo_the_file() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("credentials.toml");
+        std::fs::write(&path, "api_secret = \"leaky\" oops").unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o600)).unwrap();
+
+        let err = format!("{:#}", ApiCredentials::from_file(&path).unwrap_err());
+        assert!(!err.contains("leaky"));
+    }
+}
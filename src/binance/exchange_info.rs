@@ -0,0 +1,459 @@
+//! Exchange information cache with per-symbol trading rules
+
+use anyhow::{Result, anyhow};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Arc, RwLock};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tracing::{debug, info};
+
+use super::rest::BinanceRestClient;
+use super::types::{Symbol, SymbolFilter};
+
+/// Decimal places used when a symbol's tick size is unknown
+pub const DEFAULT_PRICE_DECIMALS: usize = 2;
+
+/// Maximum decimal places derived from a filter increment
+const MAX_DECIMALS: usize = 12;
+
+/// Trading status reported by Binance for a symbol
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SymbolStatus {
+    Trading,
+    PreTrading,
+    PostTrading,
+    EndOfDay,
+    Halt,
+    AuctionMatch,
+    Break,
+    /// Symbol no longer listed in exchange info
+    Delisted,
+    Other(String),
+}
+
+impl SymbolStatus {
+    /// Parse the raw `status` field from exchange info
+    pub fn parse(raw: &str) -> Self {
+        match raw {
+            "TRADING" => Self::Trading,
+            "PRE_TRADING" => Self::PreTrading,
+            "POST_TRADING" => Self::PostTrading,
+            "END_OF_DAY" => Self::EndOfDay,
+            "HALT" => Self::Halt,
+            "AUCTION_MATCH" => Self::AuctionMatch,
+            "BREAK" => Self::Break,
+            other => Self::Other(other.to_string()),
+        }
+    }
+
+    /// Whether the symbol is currently open for trading
+    pub fn is_trading(&self) -> bool {
+        matches!(self, Self::Trading)
+    }
+
+    /// Wire representation of the status
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Trading => "TRADING",
+            Self::PreTrading => "PRE_TRADING",
+            Self::PostTrading => "POST_TRADING",
+            Self::EndOfDay => "END_OF_DAY",
+            Self::Halt => "HALT",
+            Self::AuctionMatch => "AUCTION_MATCH",
+            Self::Break => "BREAK",
+            Self::Delisted => "DELISTED",
+            Self::Other(raw) => raw.as_str(),
+        }
+    }
+}
+
+impl fmt::Display for SymbolStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Parsed trading rules for a single symbol
+#[derive(Debug, Clone, PartialEq)]
+pub struct SymbolInfo {
+    pub symbol: String,
+    pub base_asset: String,
+    pub quote_asset: String,
+    pub status: SymbolStatus,
+    pub tick_size: Option<f64>,
+    pub min_price: Option<f64>,
+    pub max_price: Option<f64>,
+    pub step_size: Option<f64>,
+    pub min_qty: Option<f64>,
+    pub max_qty: Option<f64>,
+    pub min_notional: Option<f64>,
+}
+
+impl SymbolInfo {
+    /// Build parsed trading rules from the raw exchange info payload
+    pub fn from_symbol(symbol: &Symbol) -> Self {
+        let mut info = Self {
+            symbol: symbol.symbol.clone(),
+            base_asset: symbol.base_asset.clone(),
+            quote_asset: symbol.quote_asset.clone(),
+            status: SymbolStatus::parse(&symbol.status),
+            tick_size: None,
+            min_price: None,
+            max_price: None,
+            step_size: None,
+            min_qty: None,
+            max_qty: None,
+            min_notional: None,
+        };
+
+        for filter in &symbol.filters {
+            match filter {
+                SymbolFilter::PriceFilter {
+                    min_price,
+                    max_price,
+                    tick_size,
+                } => {
+                    info.tick_size = parse_positive(tick_size);
+                    info.min_price = parse_positive(min_price);
+                    info.max_price = parse_positive(max_price);
+                }
+                SymbolFilter::LotSize {
+                    min_qty,
+                    max_qty,
+                    step_size,
+                } => {
+                    info.step_size = parse_positive(step_size);
+                    info.min_qty = parse_positive(min_qty);
+                    info.max_qty = parse_positive(max_qty);
+                }
+                SymbolFilter::MinNotional { min_notional }
+                | SymbolFilter::Notional { min_notional } => {
+                    // Newer symbols only carry NOTIONAL; keep whichever is present.
+                    if info.min_notional.is_none() {
+                        info.min_notional = parse_positive(min_notional);
+                    }
+                }
+                SymbolFilter::Other => {}
+            }
+        }
+
+        info
+    }
+
+    /// Number of decimals implied by the price tick size
+    pub fn price_decimals(&self) -> usize {
+        self.tick_size
+            .map(decimals_for_increment)
+            .unwrap_or(DEFAULT_PRICE_DECIMALS)
+    }
+
+    /// Number of decimals implied by the lot step size
+    pub fn quantity_decimals(&self) -> usize {
+        self.step_size.map(decimals_for_increment).unwrap_or(0)
+    }
+
+    /// Format a price with the symbol's tick precision
+    pub fn format_price(&self, price: f64) -> String {
+        format!("{:.*}", self.price_decimals(), price)
+    }
+
+    /// Round a price down to the nearest valid tick
+    pub fn round_price_to_tick(&self, price: f64) -> f64 {
+        round_to_increment(price, self.tick_size)
+    }
+
+    /// Round a quantity down to the nearest valid lot step
+    pub fn round_quantity_to_step(&self, quantity: f64) -> f64 {
+        round_to_increment(quantity, self.step_size)
+    }
+}
+
+/// Status transition detected between two exchange info refreshes
+#[derive(Debug, Clone, PartialEq)]
+pub struct SymbolStatusChange {
+    pub symbol: String,
+    pub previous: SymbolStatus,
+    pub current: SymbolStatus,
+}
+
+#[derive(Debug, Default)]
+struct CacheState {
+    symbols: HashMap<String, SymbolInfo>,
+    last_refresh_ms: Option<u64>,
+}
+
+/// Shared, cheaply cloneable cache of exchange trading rules
+#[derive(Debug, Clone, Default)]
+pub struct ExchangeInfoCache {
+    inner: Arc<RwLock<CacheState>>,
+}
+
+impl ExchangeInfoCache {
+    /// Create an empty cache
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Fetch exchange info and replace the cached rules, returning status changes
+    pub async fn refresh(
+        &self,
+        rest_client: &BinanceRestClient,
+    ) -> Result<Vec<SymbolStatusChange>> {
+        let exchange_info = rest_client.get_exchange_info().await?;
+        let symbols: Vec<SymbolInfo> = exchange_info
+            .symbols
+            .iter()
+            .map(SymbolInfo::from_symbol)
+            .collect();
+
+        info!(
+            "Exchange info cache refreshed with {} symbols",
+            symbols.len()
+        );
+        Ok(self.replace(symbols))
+    }
+
+    /// Replace cached rules and report status changes for previously known symbols
+    pub fn replace(&self, symbols: Vec<SymbolInfo>) -> Vec<SymbolStatusChange> {
+        let mut state = self.write();
+        let mut next: HashMap<String, SymbolInfo> = symbols
+            .into_iter()
+            .map(|info| (info.symbol.clone(), info))
+            .collect();
+
+        let mut changes = Vec::new();
+        let had_previous = state.last_refresh_ms.is_some();
+
+        if had_previous {
+            for (symbol, previous) in &state.symbols {
+                match next.get(symbol) {
+                    Some(current) if current.status != previous.status => {
+                        changes.push(SymbolStatusChange {
+                            symbol: symbol.clone(),
+                            previous: previous.status.clone(),
+                            current: current.status.clone(),
+                        });
+                    }
+                    Some(_) => {}
+                    None if previous.status != SymbolStatus::Delisted => {
+                        changes.push(SymbolStatusChange {
+                            symbol: symbol.clone(),
+                            previous: previous.status.clone(),
+                            current: SymbolStatus::Delisted,
+                        });
+                        // Keep a tombstone so later lookups explain why the symbol vanished
+                        let mut tombstone = previous.clone();
+                        tombstone.status = SymbolStatus::Delisted;
+                        next.insert(symbol.clone(), tombstone);
+                    }
+                    None => {
+                        next.insert(symbol.clone(), previous.clone());
+                    }
+                }
+            }
+        }
+
+        changes.sort_by(|a, b| a.symbol.cmp(&b.symbol));
+        debug!(
+            "Exchange info replace produced {} status changes",
+            changes.len()
+        );
+
+        state.symbols = next;
+        state.last_refresh_ms = Some(now_ms());
+        changes
+    }
+
+    /// Whether exchange info has been loaded at least once
+    pub fn is_loaded(&self) -> bool {
+        self.read().last_refresh_ms.is_some()
+    }
+
+    /// Timestamp of the last successful refresh
+    pub fn last_refresh_ms(&self) -> Option<u64> {
+        self.read().last_refresh_ms
+    }
+
+    /// Look up the trading rules for a symbol
+    pub fn get(&self, symbol: &str) -> Option<SymbolInfo> {
+        self.read().symbols.get(symbol).cloned()
+    }
+
+    /// Snapshot of all cached symbols
+    pub fn symbols(&self) -> Vec<SymbolInfo> {
+        self.read().symbols.values().cloned().collect()
+    }
+
+    /// Number of cached symbols
+    pub fn len(&self) -> usize {
+        self.read().symbols.len()
+    }
+
+    /// Whether the cache holds no symbols
+    pub fn is_empty(&self) -> bool {
+        self.read().symbols.is_empty()
+    }
+
+    /// Validate that a symbol exists and is trading. Passes when the cache is not loaded yet.
+    pub fn validate_symbol(&self, symbol: &str) -> Result<()> {
+        let state = self.read();
+        if state.last_refresh_ms.is_none() {
+            return Ok(());
+        }
+
+        match state.symbols.get(symbol) {
+            Some(info) if info.status.is_trading() => Ok(()),
+            Some(info) => Err(anyhow!(
+                "Symbol {} is not trading (status {})",
+                symbol,
+                info.status
+            )),
+            None => Err(anyhow!("Unknown symbol: {}", symbol)),
+        }
+    }
+
+    /// Decimal places for displaying prices of a symbol
+    pub fn price_decimals(&self, symbol: &str) -> usize {
+        self.read()
+            .symbols
+            .get(symbol)
+            .map(SymbolInfo::price_decimals)
+            .unwrap_or(DEFAULT_PRICE_DECIMALS)
+    }
+
+    /// Format a price using the symbol's tick precision
+    pub fn format_price(&self, symbol: &str, price: f64) -> String {
+        format!("{:.*}", self.price_decimals(symbol), price)
+    }
+
+    fn read(&self) -> std::sync::RwLockReadGuard<'_, CacheState> {
+        self.inner
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn write(&self) -> std::sync::RwLockWriteGuard<'_, CacheState> {
+        self.inner
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// Number of decimals needed to represent multiples of an increment such as `0.001`
+pub fn decimals_for_increment(increment: f64) -> usize {
+    if !increment.is_finite() || increment <= 0.0 {
+        return DEFAULT_PRICE_DECIMALS;
+    }
+
+    for decimals in 0..=MAX_DECIMALS {
+        let scaled = increment * 10f64.powi(decimals as i32);
+        if scaled.round() >= 1.0 && (scaled - scaled.round()).abs() < 1e-6 {
+            return decimals;
+        }
+    }
+
+    MAX_DECIMALS
+}
+
+fn round_to_increment(value: f64, increment: Option<f64>) -> f64 {
+    match increment {
+        Some(step) if step > 0.0 && value.is_finite() => {
+            let decimals = decimals_for_increment(step);
+            let units = (value / step + 1e-9).floor();
+            let rounded = units * step;
+            let factor = 10f64.powi(decimals as i32);
+            (rounded * factor).round() / factor
+        }
+        _ => value,
+    }
+}
+
+fn parse_positive(raw: &str) -> Option<f64> {
+    raw.parse::<f64>()
+        .ok()
+        .filter(|v| v.is_finite() && *v > 0.0)
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn sample_symbol(symbol: &str, status: &str, tick: &str) -> Symbol {
+        serde_json::from_value(json!({
+            "symbol": symbol,
+            "status": status,
+            "baseAsset": "PEPE",
+            "quoteAsset": "USDT",
+            "filters": [
+                {"filterType": "PRICE_FILTER", "minPrice": tick, "maxPrice": "1.00000000", "tickSize": tick},
+                {"filterType": "LOT_SIZE", "minQty": "1.00", "maxQty": "9000000.00", "stepSize": "1.00"},
+                {"filterType": "NOTIONAL", "minNotional": "5.00000000", "applyMinToMarket": true},
+                {"filterType": "MAX_NUM_ORDERS", "maxNumOrders": 200}
+            ]
+        }))
+        .expect("symbol should deserialize")
+    }
+
+    #[test]
+    fn parses_filters_from_exchange_info_payload() {
+        let info = SymbolInfo::from_symbol(&sample_symbol("PEPEUSDT", "TRADING", "0.00000001"));
+
+        assert_eq!(info.status, SymbolStatus::Trading);
+        assert_eq!(info.tick_size, Some(0.00000001));
+        assert_eq!(info.step_size, Some(1.0));
+        assert_eq!(info.min_notional, Some(5.0));
+        assert_eq!(info.price_decimals(), 8);
+        assert_eq!(info.quantity_decimals(), 0);
+        assert_eq!(info.format_price(0.0000123), "0.00001230");
+    }
+
+    #[test]
+    fn decimals_for_common_increments() {
+        assert_eq!(decimals_for_increment(0.01), 2);
+        assert_eq!(decimals_for_increment(0.00001), 5);
+        assert_eq!(decimals_for_increment(1.0), 0);
+        assert_eq!(decimals_for_increment(0.5), 1);
+    }
+
+    #[test]
+    fn rounds_to_tick_and_step() {
+        let info = SymbolInfo::from_symbol(&sample_symbol("PEPEUSDT", "TRADING", "0.01"));
+        assert!((info.round_price_to_tick(12.3456) - 12.34).abs() < 1e-12);
+        assert!((info.round_quantity_to_step(10.9) - 10.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn replace_reports_status_changes_and_delistings() {
+        let cache = ExchangeInfoCache::new();
+        assert!(cache.validate_symbol("ANYTHING").is_ok());
+
+        let first = vec![
+            SymbolInfo::from_symbol(&sample_symbol("AAAUSDT", "TRADING", "0.01")),
+            SymbolInfo::from_symbol(&sample_symbol("BBBUSDT", "TRADING", "0.01")),
+        ];
+        assert!(cache.replace(first).is_empty());
+        assert!(cache.validate_symbol("AAAUSDT").is_ok());
+        assert!(cache.validate_symbol("ZZZUSDT").is_err());
+
+        let second = vec![SymbolInfo::from_symbol(&sample_symbol(
+            "AAAUSDT", "BREAK", "0.01",
+        ))];
+        let changes = cache.replace(second);
+
+        assert_eq!(changes.len(), 2);
+        assert_eq!(changes[0].symbol, "AAAUSDT");
+        assert_eq!(changes[0].current, SymbolStatus::Break);
+        assert_eq!(changes[1].symbol, "BBBUSDT");
+        assert_eq!(changes[1].current, SymbolStatus::Delisted);
+        assert!(cache.validate_symbol("AAAUSDT").is_err());
+        assert!(cache.validate_symbol("BBBUSDT").is_err());
+    }
+}
@@ -2,12 +2,16 @@
 //!
 //! Handles WebSocket connections, REST API calls, and data parsing for Binance.
 
+pub mod auth;
 pub mod demo;
+pub mod exchange_info;
 pub mod rest;
 pub mod types;
 pub mod websocket;
 
 // Re-export commonly used types
+pub use auth::ApiCredentials;
+pub use exchange_info::{ExchangeInfoCache, SymbolInfo, SymbolStatus, SymbolStatusChange};
 pub use rest::BinanceRestClient;
 pub use types::*;
 pub use websocket::BinanceWebSocket;
@@ -1,17 +1,37 @@
 //! Binance REST API client implementation
 
+use std::sync::Arc;
+use std::sync::atomic::{AtomicI64, Ordering};
+
 use anyhow::{Result, anyhow};
+use reqwest::Method;
 use serde::Deserialize;
 use serde::de::{Error as DeError, IgnoredAny};
 use tracing::{debug, info, warn};
 
-use super::types::{DepthSnapshot, Symbol, Ticker24hr};
+use super::auth::{ApiCredentials, redact};
+use super::types::{
+    DepthSnapshot, Kline, ListenKeyResponse, RestAccountInfo, RestAggTrade, RestApiError,
+    RestCancelAck, RestCancelReplaceResponse, RestOpenOrder, RestOrderAck, RestTicker24hr, Symbol,
+};
 use crate::market_data::DailyCandle;
+use crate::metrics::exporter;
+
+/// Header carrying the API key on `USER_DATA` and `USER_STREAM` requests
+const API_KEY_HEADER: &str = "X-MBX-APIKEY";
+
+/// Validity window used for signed requests unless configured otherwise
+const DEFAULT_RECV_WINDOW_MS: u64 = 5_000;
 
 /// Binance REST API client
+#[derive(Clone)]
 pub struct BinanceRestClient {
     base_url: String,
     client: reqwest::Client,
+    credentials: Option<Arc<ApiCredentials>>,
+    recv_window_ms: u64,
+    /// Exchange clock minus local clock, applied to signed request timestamps
+    time_offset_ms: Arc<AtomicI64>,
 }
 
 impl BinanceRestClient {
@@ -19,9 +39,44 @@ impl BinanceRestClient {
         Self {
             base_url,
             client: reqwest::Client::new(),
+            credentials: None,
+            recv_window_ms: DEFAULT_RECV_WINDOW_MS,
+            time_offset_ms: Arc::new(AtomicI64::new(0)),
         }
     }
 
+    /// Sign account requests with these credentials
+    pub fn with_credentials(mut self, credentials: ApiCredentials) -> Self {
+        self.credentials = Some(Arc::new(credentials));
+        self
+    }
+
+    /// Milliseconds a signed request stays valid after its timestamp
+    pub fn with_recv_window(mut self, recv_window_ms: u64) -> Self {
+        self.recv_window_ms = recv_window_ms;
+        self
+    }
+
+    pub fn has_credentials(&self) -> bool {
+        self.credentials.is_some()
+    }
+
+    /// Correct signed request timestamps by the measured exchange clock offset
+    ///
+    /// Clones of this client share the offset.
+    pub fn set_time_offset_ms(&self, offset_ms: i64) {
+        self.time_offset_ms.store(offset_ms, Ordering::Relaxed);
+    }
+
+    /// Local time shifted onto the exchange clock
+    fn exchange_time_ms(&self) -> u64 {
+        let local_ms = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis() as i64;
+        (local_ms + self.time_offset_ms.load(Ordering::Relaxed)).max(0) as u64
+    }
+
     /// Get orderbook depth snapshot for a symbol
     pub async fn get_depth_snapshot(
         &self,
@@ -44,11 +99,10 @@ impl BinanceRestClient {
             .send()
             .await
             .map_err(|e| anyhow!("Failed to send HTTP request: {}", e))?;
+        record_response_metrics("/api/v3/depth", &response);
 
         if !response.status().is_success() {
-            let status = response.status();
-            let body = response.text().await.unwrap_or_default();
-            return Err(anyhow!("HTTP error {}: {}", status, body));
+            return Err(status_error(response).await.into());
         }
 
         let snapshot: DepthSnapshot = response
@@ -73,7 +127,7 @@ impl BinanceRestClient {
     }
 
     /// Get 24hr ticker information for a symbol
-    pub async fn get_24hr_ticker(&self, symbol: &str) -> Result<Ticker24hr> {
+    pub async fn get_24hr_ticker(&self, symbol: &str) -> Result<RestTicker24hr> {
         let url = format!("{}/api/v3/ticker/24hr?symbol={}", self.base_url, symbol);
 
         debug!("Fetching 24hr ticker from: {}", url);
@@ -85,14 +139,13 @@ impl BinanceRestClient {
             .send()
             .await
             .map_err(|e| anyhow!("Failed to send HTTP request: {}", e))?;
+        record_response_metrics("/api/v3/ticker/24hr", &response);
 
         if !response.status().is_success() {
-            let status = response.status();
-            let body = response.text().await.unwrap_or_default();
-            return Err(anyhow!("HTTP error {}: {}", status, body));
+            return Err(status_error(response).await.into());
         }
 
-        let ticker: Ticker24hr = response
+        let ticker: RestTicker24hr = response
             .json()
             .await
             .map_err(|e| anyhow!("Failed to parse 24hr ticker: {}", e))?;
@@ -102,6 +155,37 @@ impl BinanceRestClient {
         Ok(ticker)
     }
 
+    /// Get 24hr ticker information for several symbols in one request
+    pub async fn get_24hr_tickers(&self, symbols: &[String]) -> Result<Vec<RestTicker24hr>> {
+        let url = format!("{}/api/v3/ticker/24hr", self.base_url);
+        let symbols_param = serde_json::to_string(symbols)?;
+
+        debug!("Fetching 24hr tickers for {} from: {}", symbols_param, url);
+
+        let response = self
+            .client
+            .get(&url)
+            .query(&[("symbols", symbols_param.as_str())])
+            .timeout(std::time::Duration::from_secs(10))
+            .send()
+            .await
+            .map_err(|e| anyhow!("Failed to send HTTP request: {}", e))?;
+        record_response_metrics("/api/v3/ticker/24hr", &response);
+
+        if !response.status().is_success() {
+            return Err(status_error(response).await.into());
+        }
+
+        let tickers: Vec<RestTicker24hr> = response
+            .json()
+            .await
+            .map_err(|e| anyhow!("Failed to parse 24hr tickers: {}", e))?;
+
+        debug!("Fetched {} 24hr tickers", tickers.len());
+
+        Ok(tickers)
+    }
+
     /// Get exchange information
     pub async fn get_exchange_info(&self) -> Result<ExchangeInfo> {
         let url = format!("{}/api/v3/exchangeInfo", self.base_url);
@@ -115,6 +199,7 @@ impl BinanceRestClient {
             .send()
             .await
             .map_err(|e| anyhow!("Failed to send HTTP request: {}", e))?;
+        record_response_metrics("/api/v3/exchangeInfo", &response);
 
         if !response.status().is_success() {
             let status = response.status();
@@ -145,6 +230,7 @@ impl BinanceRestClient {
             .send()
             .await
             .map_err(|e| anyhow!("Failed to send HTTP request: {}", e))?;
+        record_response_metrics("/api/v3/time", &response);
 
         if !response.status().is_success() {
             let status = response.status();
@@ -166,12 +252,6 @@ impl BinanceRestClient {
         Ok(exchange_info.symbols)
     }
 
-    /// Validate symbol exists
-    pub async fn validate_symbol(&self, symbol: &str) -> Result<bool> {
-        let symbols = self.get_all_symbols().await?;
-        Ok(symbols.iter().any(|s| s.symbol == symbol))
-    }
-
     /// Get current price for a symbol
     pub async fn get_price(&self, symbol: &str) -> Result<f64> {
         let ticker = self.get_24hr_ticker(symbol).await?;
@@ -205,16 +285,56 @@ impl BinanceRestClient {
         symbol: &str,
         limit: Option<u16>,
     ) -> Result<Vec<DailyCandle>> {
-        let clamped_limit = limit.unwrap_or(90).clamp(1, 1000);
-        let url = format!(
-            "{}/api/v3/klines?symbol={}&interval=1d&limit={}",
-            self.base_url, symbol, clamped_limit
+        self.get_klines(symbol, "1d", None, limit).await
+    }
+
+    /// Get klines for a symbol and interval, optionally starting at `start_time_ms`
+    pub async fn get_klines(
+        &self,
+        symbol: &str,
+        interval: &str,
+        start_time_ms: Option<u64>,
+        limit: Option<u16>,
+    ) -> Result<Vec<DailyCandle>> {
+        let page = self
+            .get_kline_page(symbol, interval, start_time_ms, None, limit.or(Some(90)))
+            .await?;
+
+        info!(
+            "Successfully fetched {} {} klines for {}",
+            page.klines.len(),
+            interval,
+            symbol
         );
 
-        debug!(
-            "Fetching daily klines from: {} (limit={})",
-            url, clamped_limit
+        Ok(page.klines.into_iter().map(DailyCandle::from).collect())
+    }
+
+    /// Get one page of klines between `start_time_ms` and `end_time_ms` (both inclusive)
+    ///
+    /// HTTP 429 and 418 responses surface as [`RestApiError::RateLimited`] and
+    /// [`RestApiError::IpBanned`] so callers can honour `Retry-After`.
+    pub async fn get_kline_page(
+        &self,
+        symbol: &str,
+        interval: &str,
+        start_time_ms: Option<u64>,
+        end_time_ms: Option<u64>,
+        limit: Option<u16>,
+    ) -> Result<KlinePage> {
+        let clamped_limit = limit.unwrap_or(500).clamp(1, 1000);
+        let mut url = format!(
+            "{}/api/v3/klines?symbol={}&interval={}&limit={}",
+            self.base_url, symbol, interval, clamped_limit
         );
+        if let Some(start_time_ms) = start_time_ms {
+            url.push_str(&format!("&startTime={}", start_time_ms));
+        }
+        if let Some(end_time_ms) = end_time_ms {
+            url.push_str(&format!("&endTime={}", end_time_ms));
+        }
+
+        debug!("Fetching klines from: {} (limit={})", url, clamped_limit);
 
         let response = self
             .client
@@ -223,6 +343,12 @@ impl BinanceRestClient {
             .send()
             .await
             .map_err(|e| anyhow!("Failed to send HTTP request: {}", e))?;
+        record_response_metrics("/api/v3/klines", &response);
+
+        if let Some(error) = rate_limit_error(&response) {
+            warn!("Kline request for {} rejected: {}", symbol, error);
+            return Err(error.into());
+        }
 
         if !response.status().is_success() {
             let status = response.status();
@@ -230,23 +356,427 @@ impl BinanceRestClient {
             return Err(anyhow!("HTTP error {}: {}", status, body));
         }
 
+        let used_weight_1m = used_weight_1m(&response);
         let rows: Vec<RestKlineRow> = response
             .json()
             .await
-            .map_err(|e| anyhow!("Failed to parse daily klines: {}", e))?;
+            .map_err(|e| anyhow!("Failed to parse klines: {}", e))?;
+
+        Ok(KlinePage {
+            klines: rows.into_iter().map(Kline::from).collect(),
+            used_weight_1m,
+        })
+    }
+
+    /// Get aggregate trades, starting at an aggregate trade id or a time
+    ///
+    /// `from_id` takes precedence over `start_time_ms`; with neither the most
+    /// recent trades are returned.
+    pub async fn get_agg_trades(
+        &self,
+        symbol: &str,
+        from_id: Option<u64>,
+        start_time_ms: Option<u64>,
+        limit: Option<u16>,
+    ) -> Result<Vec<RestAggTrade>> {
+        let clamped_limit = limit.unwrap_or(500).clamp(1, 1000);
+        let mut url = format!(
+            "{}/api/v3/aggTrades?symbol={}&limit={}",
+            self.base_url, symbol, clamped_limit
+        );
+        if let Some(from_id) = from_id {
+            url.push_str(&format!("&fromId={}", from_id));
+        } else if let Some(start_time_ms) = start_time_ms {
+            url.push_str(&format!("&startTime={}", start_time_ms));
+        }
+
+        debug!("Fetching aggregate trades from: {}", url);
+
+        let response = self
+            .client
+            .get(&url)
+            .timeout(std::time::Duration::from_secs(10))
+            .send()
+            .await
+            .map_err(|e| anyhow!("Failed to send HTTP request: {}", e))?;
+        record_response_metrics("/api/v3/aggTrades", &response);
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(anyhow!("HTTP error {}: {}", status, body));
+        }
+
+        let trades: Vec<RestAggTrade> = response
+            .json()
+            .await
+            .map_err(|e| anyhow!("Failed to parse aggregate trades: {}", e))?;
+
+        debug!("Fetched {} aggregate trades for {}", trades.len(), symbol);
+
+        Ok(trades)
+    }
+
+    /// Get balances of the account the API key belongs to
+    pub async fn get_account(&self) -> Result<RestAccountInfo> {
+        let response = self
+            .send_signed(
+                Method::GET,
+                "/api/v3/account",
+                &[("omitZeroBalances", "true".into())],
+            )
+            .await?;
+
+        let account: RestAccountInfo = response
+            .json()
+            .await
+            .map_err(|e| anyhow!("Failed to parse account information: {}", e))?;
+
+        debug!("Fetched account with {} balances", account.balances.len());
+
+        Ok(account)
+    }
+
+    /// Get open orders, for one symbol or across the whole account
+    pub async fn get_open_orders(&self, symbol: Option<&str>) -> Result<Vec<RestOpenOrder>> {
+        let params: Vec<(&str, String)> = symbol
+            .map(|symbol| ("symbol", symbol.to_string()))
+            .into_iter()
+            .collect();
+        let response = self
+            .send_signed(Method::GET, "/api/v3/openOrders", &params)
+            .await?;
+
+        let orders: Vec<RestOpenOrder> = response
+            .json()
+            .await
+            .map_err(|e| anyhow!("Failed to parse open orders: {}", e))?;
+
+        debug!("Fetched {} open orders", orders.len());
+
+        Ok(orders)
+    }
+
+    /// Place an order, or only have Binance validate it when `test` is set
+    ///
+    /// `params` are the order fields without `recvWindow`, `timestamp` or the
+    /// signature. Test orders go to `/api/v3/order/test` and return `None`.
+    pub async fn place_order(
+        &self,
+        params: &[(&str, String)],
+        test: bool,
+    ) -> Result<Option<RestOrderAck>> {
+        let endpoint = if test {
+            "/api/v3/order/test"
+        } else {
+            "/api/v3/order"
+        };
+        let response = self
+            .send_signed(Method::POST, endpoint, params)
+            .await
+            .map_err(order_error)?;
+
+        if test {
+            debug!("Test order accepted");
+            return Ok(None);
+        }
+
+        let ack: RestOrderAck = response
+            .json()
+            .await
+            .map_err(|e| anyhow!("Failed to parse order response: {}", e))?;
+
+        info!("Placed order {} on {}", ack.order_id, ack.symbol);
+
+        Ok(Some(ack))
+    }
+
+    /// Cancel an open order
+    pub async fn cancel_order(&self, symbol: &str, order_id: u64) -> Result<RestCancelAck> {
+        let params = [
+            ("symbol", symbol.to_string()),
+            ("orderId", order_id.to_string()),
+        ];
+        let response = self
+            .send_signed(Method::DELETE, "/api/v3/order", &params)
+            .await
+            .map_err(order_error)?;
+
+        let ack: RestCancelAck = response
+            .json()
+            .await
+            .map_err(|e| anyhow!("Failed to parse cancel response: {}", e))?;
+
+        info!("Cancelled order {} on {}", ack.order_id, ack.symbol);
+
+        Ok(ack)
+    }
+
+    /// Cancel `cancel_order_id` and place a new order, leaving the old one in
+    /// place if the cancel fails
+    pub async fn cancel_replace_order(
+        &self,
+        cancel_order_id: u64,
+        params: &[(&str, String)],
+    ) -> Result<RestOrderAck> {
+        let mut params = params.to_vec();
+        params.push(("cancelReplaceMode", "STOP_ON_FAILURE".to_string()));
+        params.push(("cancelOrderId", cancel_order_id.to_string()));
+        let response = self
+            .send_signed(Method::POST, "/api/v3/order/cancelReplace", &params)
+            .await
+            .map_err(order_error)?;
 
-        let candles: Vec<DailyCandle> = rows.into_iter().map(DailyCandle::from).collect();
+        let body: RestCancelReplaceResponse = response
+            .json()
+            .await
+            .map_err(|e| anyhow!("Failed to parse cancel-replace response: {}", e))?;
+        let ack = body.new_order_response.ok_or_else(|| {
+            anyhow!(
+                "Order {} cancelled ({}) but the new order was not placed ({})",
+                cancel_order_id,
+                body.cancel_result,
+                body.new_order_result
+            )
+        })?;
 
         info!(
-            "Successfully fetched {} daily klines for {}",
-            candles.len(),
-            symbol
+            "Replaced order {} with {} on {}",
+            cancel_order_id, ack.order_id, ack.symbol
         );
 
-        Ok(candles)
+        Ok(ack)
+    }
+
+    /// Open a user data stream and return its listen key
+    pub async fn create_listen_key(&self) -> Result<String> {
+        let response = self
+            .send_with_api_key(Method::POST, "/api/v3/userDataStream", None)
+            .await?;
+
+        let body: ListenKeyResponse = response
+            .json()
+            .await
+            .map_err(|e| anyhow!("Failed to parse listen key: {}", e))?;
+
+        info!("Opened user data stream {}", redact(&body.listen_key));
+
+        Ok(body.listen_key)
+    }
+
+    /// Extend a listen key's validity by another 60 minutes
+    pub async fn keepalive_listen_key(&self, listen_key: &str) -> Result<()> {
+        self.send_with_api_key(Method::PUT, "/api/v3/userDataStream", Some(listen_key))
+            .await?;
+        debug!("Kept user data stream {} alive", redact(listen_key));
+        Ok(())
+    }
+
+    /// Close a user data stream
+    pub async fn close_listen_key(&self, listen_key: &str) -> Result<()> {
+        self.send_with_api_key(Method::DELETE, "/api/v3/userDataStream", Some(listen_key))
+            .await?;
+        info!("Closed user data stream {}", redact(listen_key));
+        Ok(())
+    }
+
+    /// Send a `USER_DATA` request with a timestamp, `recvWindow` and HMAC-SHA256 signature
+    async fn send_signed(
+        &self,
+        method: Method,
+        endpoint: &'static str,
+        params: &[(&str, String)],
+    ) -> Result<reqwest::Response> {
+        let credentials = self
+            .credentials
+            .as_ref()
+            .ok_or(RestApiError::MissingCredentials)?;
+        let query = signed_query(
+            credentials,
+            params,
+            self.recv_window_ms,
+            self.exchange_time_ms(),
+        );
+        let url = format!("{}{}?{}", self.base_url, endpoint, query);
+
+        // The URL carries the signature, so only the endpoint is logged
+        debug!("Sending signed {} {}", method, endpoint);
+
+        let response = self
+            .client
+            .request(method, &url)
+            .header(API_KEY_HEADER, credentials.api_key())
+            .timeout(std::time::Duration::from_secs(10))
+            .send()
+            .await
+            .map_err(|e| anyhow!("Failed to send HTTP request: {}", e.without_url()))?;
+        record_response_metrics(endpoint, &response);
+
+        if !response.status().is_success() {
+            return Err(status_error(response).await.into());
+        }
+
+        Ok(response)
+    }
+
+    /// Send a `USER_STREAM` request, which carries the API key but no signature
+    async fn send_with_api_key(
+        &self,
+        method: Method,
+        endpoint: &'static str,
+        listen_key: Option<&str>,
+    ) -> Result<reqwest::Response> {
+        let credentials = self
+            .credentials
+            .as_ref()
+            .ok_or(RestApiError::MissingCredentials)?;
+        let url = format!("{}{}", self.base_url, endpoint);
+
+        debug!("Sending {} {}", method, endpoint);
+
+        let mut request = self
+            .client
+            .request(method, &url)
+            .header(API_KEY_HEADER, credentials.api_key())
+            .timeout(std::time::Duration::from_secs(10));
+        if let Some(listen_key) = listen_key {
+            request = request.query(&[("listenKey", listen_key)]);
+        }
+        let response = request
+            .send()
+            .await
+            .map_err(|e| anyhow!("Failed to send HTTP request: {}", e.without_url()))?;
+        record_response_metrics(endpoint, &response);
+
+        if !response.status().is_success() {
+            return Err(status_error(response).await.into());
+        }
+
+        Ok(response)
+    }
+}
+
+/// Query string for a signed request: the parameters, `recvWindow` and
+/// `timestamp`, followed by the signature over everything before it
+fn signed_query(
+    credentials: &ApiCredentials,
+    params: &[(&str, String)],
+    recv_window_ms: u64,
+    timestamp_ms: u64,
+) -> String {
+    let mut query = params
+        .iter()
+        .map(|(key, value)| format!("{}={}", key, value))
+        .chain([
+            format!("recvWindow={}", recv_window_ms),
+            format!("timestamp={}", timestamp_ms),
+        ])
+        .collect::<Vec<_>>()
+        .join("&");
+    let signature = credentials.sign(&query);
+    query.push_str("&signature=");
+    query.push_str(&signature);
+    query
+}
+
+/// Count a REST response and track the request weight Binance reports for the current minute
+fn record_response_metrics(endpoint: &'static str, response: &reqwest::Response) {
+    metrics::counter!(
+        exporter::REST_REQUESTS_TOTAL,
+        1,
+        "endpoint" => endpoint,
+        "status" => response.status().as_u16().to_string()
+    );
+
+    if let Some(weight) = used_weight_1m(response) {
+        metrics::gauge!(exporter::REST_USED_WEIGHT_1M, weight as f64);
+    }
+}
+
+/// Request weight Binance reports as used in the current minute
+fn used_weight_1m(response: &reqwest::Response) -> Option<u32> {
+    response
+        .headers()
+        .get("x-mbx-used-weight-1m")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse::<u32>().ok())
+}
+
+/// Map 429 (rate limited) and 418 (IP banned) responses to typed errors
+fn rate_limit_error(response: &reqwest::Response) -> Option<RestApiError> {
+    let retry_after_secs = response
+        .headers()
+        .get(reqwest::header::RETRY_AFTER)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.trim().parse::<u64>().ok())
+        .unwrap_or(DEFAULT_RETRY_AFTER_SECS);
+
+    match response.status().as_u16() {
+        429 => Some(RestApiError::RateLimited { retry_after_secs }),
+        418 => Some(RestApiError::IpBanned { retry_after_secs }),
+        _ => None,
+    }
+}
+
+/// Turn an unsuccessful response into a typed error, recognising Binance's invalid symbol code
+async fn status_error(response: reqwest::Response) -> RestApiError {
+    if let Some(error) = rate_limit_error(&response) {
+        return error;
+    }
+
+    let status = response.status().as_u16();
+    let body = response.text().await.unwrap_or_default();
+    match serde_json::from_str::<BinanceErrorBody>(&body) {
+        Ok(error) if error.code == INVALID_SYMBOL_CODE => RestApiError::InvalidSymbol(error.msg),
+        Ok(error) if AUTH_ERROR_CODES.contains(&error.code) => {
+            RestApiError::Unauthorized(error.msg)
+        }
+        _ if status == 401 => RestApiError::Unauthorized(body),
+        _ => RestApiError::HttpStatusError(status, body),
+    }
+}
+
+/// Surface Binance's error code and message for order requests instead of the raw body
+fn order_error(error: anyhow::Error) -> anyhow::Error {
+    let rejection = match error.downcast_ref::<RestApiError>() {
+        Some(RestApiError::HttpStatusError(_, body)) => {
+            serde_json::from_str::<BinanceErrorBody>(body).ok()
+        }
+        _ => None,
+    };
+    match rejection {
+        Some(body) => RestApiError::OrderRejected {
+            code: body.code,
+            msg: body.msg,
+        }
+        .into(),
+        None => error,
     }
 }
 
+/// Error payload Binance returns alongside 4xx responses
+#[derive(Debug, Deserialize)]
+struct BinanceErrorBody {
+    code: i64,
+    msg: String,
+}
+
+/// Binance error code for an unknown or malformed symbol
+const INVALID_SYMBOL_CODE: i64 = -1121;
+
+/// Binance error codes for a bad signature, malformed key or key without permission
+const AUTH_ERROR_CODES: [i64; 3] = [-1022, -2014, -2015];
+
+/// Back-off used when a rate-limit response carries no `Retry-After` header
+const DEFAULT_RETRY_AFTER_SECS: u64 = 60;
+
+/// One page of klines plus the request weight used so far this minute
+#[derive(Debug, Clone)]
+pub struct KlinePage {
+    pub klines: Vec<Kline>,
+    pub used_weight_1m: Option<u32>,
+}
+
 /// Exchange information response
 #[derive(Debug, serde::Deserialize)]
 pub struct ExchangeInfo {
@@ -256,6 +786,7 @@ pub struct ExchangeInfo {
 /// Server time response
 #[derive(Debug, serde::Deserialize)]
 pub struct TimeResponse {
+    #[serde(rename = "serverTime")]
     pub server_time: u64,
 }
 
@@ -268,24 +799,61 @@ struct RestKlineRow(
     #[serde(deserialize_with = "deserialize_f64_from_any")] f64,
     #[serde(deserialize_with = "deserialize_f64_from_any")] f64,
     #[serde(deserialize_with = "deserialize_u64_from_any")] u64,
-    IgnoredAny,
-    IgnoredAny,
-    IgnoredAny,
-    IgnoredAny,
+    #[serde(deserialize_with = "deserialize_f64_from_any")] f64,
+    #[serde(deserialize_with = "deserialize_u64_from_any")] u64,
+    #[serde(deserialize_with = "deserialize_f64_from_any")] f64,
+    #[serde(deserialize_with = "deserialize_f64_from_any")] f64,
     IgnoredAny,
 );
 
-impl From<RestKlineRow> for DailyCandle {
+impl From<RestKlineRow> for Kline {
     fn from(row: RestKlineRow) -> Self {
-        let RestKlineRow(open_time_ms, open, high, low, close, volume, close_time_ms, ..) = row;
-        DailyCandle::new(
+        let RestKlineRow(
             open_time_ms,
+            open,
+            high,
+            low,
+            close,
+            volume,
             close_time_ms,
+            quote_volume,
+            trades,
+            taker_buy_base_volume,
+            taker_buy_quote_volume,
+            _,
+        ) = row;
+        Kline {
+            open_time_ms,
             open,
             high,
             low,
             close,
             volume,
+            close_time_ms,
+            quote_volume,
+            trades,
+            taker_buy_base_volume,
+            taker_buy_quote_volume,
+        }
+    }
+}
+
+impl From<RestKlineRow> for DailyCandle {
+    fn from(row: RestKlineRow) -> Self {
+        Kline::from(row).into()
+    }
+}
+
+impl From<Kline> for DailyCandle {
+    fn from(kline: Kline) -> Self {
+        DailyCandle::new(
+            kline.open_time_ms,
+            kline.close_time_ms,
+            kline.open,
+            kline.high,
+            kline.low,
+            kline.close,
+            kline.volume,
             true,
         )
     }
@@ -358,4 +926,57 @@ mod tests {
         assert!((candle.volume - 1234.56).abs() < f64::EPSILON);
         assert!(candle.is_closed);
     }
+
+    #[test]
+    fn rest_kline_row_keeps_volume_breakdown() {
+        let payload = json!([
+            1_700_000_000_000u64,
+            "100.0",
+            "101.0",
+            "99.0",
+            "100.5",
+            "12.5",
+            1_700_003_599_999u64,
+            "1256.25",
+            42,
+            "7.5",
+            "753.75",
+            "0"
+        ]);
+
+        let row: RestKlineRow =
+            serde_json::from_value(payload).expect("row should deserialize from payload");
+        let kline = Kline::from(row);
+
+        assert_eq!(kline.trades, 42);
+        assert!((kline.quote_volume - 1256.25).abs() < f64::EPSILON);
+        assert!((kline.taker_buy_base_volume - 7.5).abs() < f64::EPSILON);
+        assert!((kline.taker_buy_quote_volume - 753.75).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn signed_query_appends_window_timestamp_and_signature() {
+        let credentials = ApiCredentials::new(
+            "vmPUZE6mv9SD5VNHk4HlWFsOr6aKE2zvsw0MuIgwCIPy6utIco14y7Ju91duEh8A",
+            "NhqPtmdSJYdKjVHjA7PZj4Mge3R5YNiP1e3UZjInClVN65XAbvqqM6A7H5fATj0j",
+        )
+        .unwrap();
+        let params = [
+            ("symbol", "LTCBTC".to_string()),
+            ("side", "BUY".to_string()),
+            ("type", "LIMIT".to_string()),
+            ("timeInForce", "GTC".to_string()),
+            ("quantity", "1".to_string()),
+            ("price", "0.1".to_string()),
+        ];
+
+        let query = signed_query(&credentials, &params, 5_000, 1_499_827_319_559);
+
+        assert_eq!(
+            query,
+            "symbol=LTCBTC&side=BUY&type=LIMIT&timeInForce=GTC&quantity=1&price=0.1\
+             &recvWindow=5000&timestamp=1499827319559\
+             &signature=c8db56825ae71d6d79447849e617115f4a920fa2acdcab2b053c4b2838bd6b71"
+        );
+    }
 }
@@ -25,10 +25,43 @@ pub struct BinanceMessage {
 
 /// Trading symbol information
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct Symbol {
     pub symbol: String,
+    #[serde(default = "default_symbol_status")]
+    pub status: String,
     pub base_asset: String,
     pub quote_asset: String,
+    #[serde(default)]
+    pub filters: Vec<SymbolFilter>,
+}
+
+fn default_symbol_status() -> String {
+    "TRADING".to_string()
+}
+
+/// Trading rule filter attached to a symbol in exchange info
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "filterType")]
+pub enum SymbolFilter {
+    #[serde(rename = "PRICE_FILTER", rename_all = "camelCase")]
+    PriceFilter {
+        min_price: String,
+        max_price: String,
+        tick_size: String,
+    },
+    #[serde(rename = "LOT_SIZE", rename_all = "camelCase")]
+    LotSize {
+        min_qty: String,
+        max_qty: String,
+        step_size: String,
+    },
+    #[serde(rename = "MIN_NOTIONAL", rename_all = "camelCase")]
+    MinNotional { min_notional: String },
+    #[serde(rename = "NOTIONAL", rename_all = "camelCase")]
+    Notional { min_notional: String },
+    #[serde(other)]
+    Other,
 }
 
 /// OrderBook structure for managing bid/ask data
@@ -55,6 +88,21 @@ impl OrderBook {
         }
     }
 
+    /// Book holding the given `(price, quantity)` levels, for tests
+    #[cfg(test)]
+    pub fn from_levels(symbol: &str, bids: &[(f64, f64)], asks: &[(f64, f64)]) -> Self {
+        let mut book = Self::new(symbol.to_string());
+        for &(price, quantity) in bids {
+            book.bids
+                .insert(ordered_float::OrderedFloat(price), quantity);
+        }
+        for &(price, quantity) in asks {
+            book.asks
+                .insert(ordered_float::OrderedFloat(price), quantity);
+        }
+        book
+    }
+
     /// Get the best bid price
     pub fn best_bid(&self) -> Option<f64> {
         self.bids.keys().next_back().map(|k| k.0)
@@ -73,6 +121,14 @@ impl OrderBook {
         }
     }
 
+    /// Midpoint between best bid and ask
+    pub fn mid_price(&self) -> Option<f64> {
+        match (self.best_bid(), self.best_ask()) {
+            (Some(bid), Some(ask)) => Some((bid + ask) / 2.0),
+            _ => None,
+        }
+    }
+
     /// Fetch orderbook snapshot from Binance REST API
     pub async fn fetch_snapshot(&mut self, rest_client: &BinanceRestClient) -> Result<()> {
         let snapshot = rest_client
@@ -425,6 +481,66 @@ pub struct TradeMessage {
     pub is_buyer_maker: bool,
 }
 
+/// Aggregate trade message from Binance: fills at one price and taker side merged
+#[derive(Debug, Deserialize, Serialize)]
+pub struct AggTradeMessage {
+    #[serde(rename = "e")]
+    pub event_type: String,
+    #[serde(rename = "E")]
+    pub event_time: u64,
+    #[serde(rename = "s")]
+    pub symbol: String,
+    #[serde(rename = "a")]
+    pub agg_trade_id: u64,
+    #[serde(rename = "p")]
+    pub price: String,
+    #[serde(rename = "q")]
+    pub quantity: String,
+    #[serde(rename = "f")]
+    pub first_trade_id: u64,
+    #[serde(rename = "l")]
+    pub last_trade_id: u64,
+    #[serde(rename = "T")]
+    pub trade_time: u64,
+    #[serde(rename = "m")]
+    pub is_buyer_maker: bool,
+}
+
+/// Aggregate trade as returned by the REST `aggTrades` endpoint
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct RestAggTrade {
+    #[serde(rename = "a")]
+    pub agg_trade_id: u64,
+    #[serde(rename = "p")]
+    pub price: String,
+    #[serde(rename = "q")]
+    pub quantity: String,
+    #[serde(rename = "f")]
+    pub first_trade_id: u64,
+    #[serde(rename = "l")]
+    pub last_trade_id: u64,
+    #[serde(rename = "T")]
+    pub trade_time: u64,
+    #[serde(rename = "m")]
+    pub is_buyer_maker: bool,
+}
+
+/// Kline row as returned by the REST `klines` endpoint, with every column kept
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+pub struct Kline {
+    pub open_time_ms: u64,
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+    pub volume: f64,
+    pub close_time_ms: u64,
+    pub quote_volume: f64,
+    pub trades: u64,
+    pub taker_buy_base_volume: f64,
+    pub taker_buy_quote_volume: f64,
+}
+
 /// 24hr ticker message from Binance
 #[derive(Debug, Deserialize, Serialize)]
 pub struct Ticker24hr {
@@ -466,6 +582,76 @@ pub struct Ticker24hr {
     pub total_trades: u64,
 }
 
+/// 24hr rolling statistics as returned by the REST `ticker/24hr` endpoint
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RestTicker24hr {
+    pub symbol: String,
+    pub price_change: String,
+    pub price_change_percent: String,
+    pub weighted_avg_price: String,
+    pub last_price: String,
+    pub last_qty: String,
+    pub bid_price: String,
+    pub bid_qty: String,
+    pub ask_price: String,
+    pub ask_qty: String,
+    pub open_price: String,
+    pub high_price: String,
+    pub low_price: String,
+    pub volume: String,
+    pub quote_volume: String,
+    pub open_time: u64,
+    pub close_time: u64,
+    pub count: u64,
+}
+
+/// Best bid/offer update from `<symbol>@bookTicker`
+///
+/// Spot book tickers carry no event type or time; `u` is the same order book
+/// update id used by the diff-depth stream.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct BookTickerMessage {
+    #[serde(rename = "u")]
+    pub update_id: u64,
+    #[serde(rename = "s")]
+    pub symbol: String,
+    #[serde(rename = "b")]
+    pub bid_price: String,
+    #[serde(rename = "B")]
+    pub bid_qty: String,
+    #[serde(rename = "a")]
+    pub ask_price: String,
+    #[serde(rename = "A")]
+    pub ask_qty: String,
+}
+
+/// Rolling 24hr mini ticker, delivered for every symbol by `!miniTicker@arr`
+///
+/// The full `!ticker@arr` payload carries the same fields, so either stream
+/// deserializes into this type.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct MiniTicker {
+    #[serde(rename = "e")]
+    pub event_type: String,
+    #[serde(rename = "E")]
+    pub event_time: u64,
+    #[serde(rename = "s")]
+    pub symbol: String,
+    #[serde(rename = "c")]
+    pub close_price: String,
+    #[serde(rename = "o")]
+    pub open_price: String,
+    #[serde(rename = "h")]
+    pub high_price: String,
+    #[serde(rename = "l")]
+    pub low_price: String,
+    #[serde(rename = "v")]
+    pub volume: String,
+    #[serde(rename = "q")]
+    pub quote_volume: String,
+}
+
 /// WebSocket kline stream event wrapper
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct KlineStreamEvent {
@@ -536,6 +722,193 @@ pub enum WebSocketError {
     JsonError(#[from] serde_json::Error),
 }
 
+/// Spot account as returned by the signed REST `account` endpoint
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RestAccountInfo {
+    #[serde(default)]
+    pub can_trade: bool,
+    #[serde(default)]
+    pub account_type: String,
+    #[serde(default)]
+    pub update_time: u64,
+    pub balances: Vec<RestBalance>,
+}
+
+/// One asset balance of [`RestAccountInfo`]
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct RestBalance {
+    pub asset: String,
+    pub free: String,
+    pub locked: String,
+}
+
+/// Order as returned by the signed REST `openOrders` endpoint
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RestOpenOrder {
+    pub symbol: String,
+    pub order_id: u64,
+    pub client_order_id: String,
+    pub price: String,
+    pub orig_qty: String,
+    pub executed_qty: String,
+    pub status: String,
+    #[serde(rename = "type")]
+    pub order_type: String,
+    pub side: String,
+    #[serde(default)]
+    pub stop_price: String,
+    pub time: u64,
+    #[serde(default)]
+    pub update_time: u64,
+}
+
+/// Response of `POST /api/v3/order` and the new order leg of `cancelReplace`
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RestOrderAck {
+    pub symbol: String,
+    pub order_id: u64,
+    pub client_order_id: String,
+    #[serde(default)]
+    pub transact_time: u64,
+    #[serde(default)]
+    pub status: String,
+    #[serde(default)]
+    pub executed_qty: String,
+}
+
+/// Response of `DELETE /api/v3/order`
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RestCancelAck {
+    pub symbol: String,
+    pub order_id: u64,
+    #[serde(default)]
+    pub status: String,
+}
+
+/// Response of `POST /api/v3/order/cancelReplace`
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RestCancelReplaceResponse {
+    pub cancel_result: String,
+    pub new_order_result: String,
+    pub new_order_response: Option<RestOrderAck>,
+}
+
+/// Response of `POST /api/v3/userDataStream`
+#[derive(Debug, Clone, Deserialize)]
+pub struct ListenKeyResponse {
+    #[serde(rename = "listenKey")]
+    pub listen_key: String,
+}
+
+/// Event delivered on a user data stream, tagged by its `e` field
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(tag = "e")]
+pub enum UserDataMessage {
+    /// Balances of every asset that changed in an account update
+    #[serde(rename = "outboundAccountPosition")]
+    AccountPosition(AccountPositionMessage),
+    /// Deposit, withdrawal or transfer moving one asset
+    #[serde(rename = "balanceUpdate")]
+    BalanceUpdate(BalanceUpdateMessage),
+    /// Order placed, filled, cancelled, rejected or expired
+    #[serde(rename = "executionReport")]
+    ExecutionReport(Box<ExecutionReportMessage>),
+    /// The listen key is no longer valid; a new one is needed
+    #[serde(rename = "listenKeyExpired")]
+    ListenKeyExpired {
+        #[serde(rename = "E")]
+        event_time: u64,
+    },
+}
+
+/// `outboundAccountPosition` payload
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct AccountPositionMessage {
+    #[serde(rename = "E")]
+    pub event_time: u64,
+    #[serde(rename = "u")]
+    pub last_update_time: u64,
+    #[serde(rename = "B")]
+    pub balances: Vec<StreamBalance>,
+}
+
+/// One asset of an `outboundAccountPosition` event
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct StreamBalance {
+    #[serde(rename = "a")]
+    pub asset: String,
+    #[serde(rename = "f")]
+    pub free: String,
+    #[serde(rename = "l")]
+    pub locked: String,
+}
+
+/// `balanceUpdate` payload
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct BalanceUpdateMessage {
+    #[serde(rename = "E")]
+    pub event_time: u64,
+    #[serde(rename = "a")]
+    pub asset: String,
+    #[serde(rename = "d")]
+    pub delta: String,
+    #[serde(rename = "T")]
+    pub clear_time: u64,
+}
+
+/// `executionReport` payload
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ExecutionReportMessage {
+    #[serde(rename = "E")]
+    pub event_time: u64,
+    #[serde(rename = "s")]
+    pub symbol: String,
+    #[serde(rename = "c")]
+    pub client_order_id: String,
+    /// Client id of the order a cancel refers to, empty otherwise
+    #[serde(rename = "C", default)]
+    pub original_client_order_id: Option<String>,
+    #[serde(rename = "S")]
+    pub side: String,
+    #[serde(rename = "o")]
+    pub order_type: String,
+    #[serde(rename = "q")]
+    pub quantity: String,
+    #[serde(rename = "p")]
+    pub price: String,
+    #[serde(rename = "P", default)]
+    pub stop_price: String,
+    #[serde(rename = "x")]
+    pub execution_type: String,
+    #[serde(rename = "X")]
+    pub status: String,
+    #[serde(rename = "r", default)]
+    pub reject_reason: String,
+    #[serde(rename = "i")]
+    pub order_id: u64,
+    #[serde(rename = "l")]
+    pub last_quantity: String,
+    #[serde(rename = "z")]
+    pub cumulative_quantity: String,
+    #[serde(rename = "L")]
+    pub last_price: String,
+    #[serde(rename = "n", default)]
+    pub commission: String,
+    #[serde(rename = "N", default)]
+    pub commission_asset: Option<String>,
+    #[serde(rename = "T")]
+    pub transaction_time: u64,
+    #[serde(rename = "O", default)]
+    pub order_time: u64,
+    #[serde(rename = "m", default)]
+    pub is_maker: bool,
+}
+
 /// Error types for REST API operations
 #[derive(Debug, thiserror::Error)]
 pub enum RestApiError {
@@ -551,6 +924,16 @@ pub enum RestApiError {
     NetworkError(String),
     #[error("Invalid symbol: {0}")]
     InvalidSymbol(String),
+    #[error("Rate limited by Binance, retry after {retry_after_secs}s")]
+    RateLimited { retry_after_secs: u64 },
+    #[error("IP banned by Binance for {retry_after_secs}s after ignoring rate limits")]
+    IpBanned { retry_after_secs: u64 },
+    #[error("API key and secret are required for this request")]
+    MissingCredentials,
+    #[error("Binance rejected the API credentials: {0}")]
+    Unauthorized(String),
+    #[error("Binance rejected the order: {msg} ({code})")]
+    OrderRejected { code: i64, msg: String },
 }
 
 /// Error types for OrderBook operations
@@ -570,6 +953,8 @@ pub enum OrderBookError {
     SymbolMismatch { expected: String, actual: String },
     #[error("Invalid update: {0}")]
     InvalidUpdate(String),
+    #[error("Top of book diverged from book ticker at update {update_id}: {detail}")]
+    TopOfBookMismatch { update_id: u64, detail: String },
 }
 
 impl OrderBookError {
@@ -582,6 +967,7 @@ impl OrderBookError {
             OrderBookError::SequenceValidationFailed { .. } => false, // Need to re-sync
             OrderBookError::SymbolMismatch { .. } => true, // Wrong symbol, can ignore
             OrderBookError::InvalidUpdate(_) => false,   // Data integrity issue
+            OrderBookError::TopOfBookMismatch { .. } => false, // Local book has drifted
         }
     }
 
@@ -593,6 +979,7 @@ impl OrderBookError {
                 | OrderBookError::PriceParseError(_)
                 | OrderBookError::QuantityParseError(_)
                 | OrderBookError::InvalidUpdate(_)
+                | OrderBookError::TopOfBookMismatch { .. }
         )
     }
 
@@ -605,6 +992,7 @@ impl OrderBookError {
             OrderBookError::PriceParseError(_) => ErrorSeverity::Critical,
             OrderBookError::QuantityParseError(_) => ErrorSeverity::Critical,
             OrderBookError::InvalidUpdate(_) => ErrorSeverity::Error,
+            OrderBookError::TopOfBookMismatch { .. } => ErrorSeverity::Error,
         }
     }
 }
@@ -631,6 +1019,8 @@ pub enum BinanceEventType {
     Kline,
     #[serde(rename = "aggTrade")]
     AggregatedTrade,
+    /// Book ticker payloads have no `e` field; classified by shape
+    BookTicker,
 }
 
 // Additional types will be added in subsequent days
@@ -17,10 +17,16 @@ use tokio_tungstenite::{
 use tracing::{debug, error, info, warn};
 
 use super::types::{
-    BinanceEventType, BinanceMessage, ConnectionStatus, KlineStreamEvent, OrderBookUpdate,
-    SubscribeRequest, Ticker24hr, TradeMessage, UnsubscribeRequest, WebSocketError,
+    AggTradeMessage, BinanceEventType, BinanceMessage, BookTickerMessage, ConnectionStatus,
+    KlineStreamEvent, OrderBookUpdate, SubscribeRequest, Ticker24hr, TradeMessage,
+    UnsubscribeRequest, WebSocketError,
 };
 
+/// Stream carrying a mini ticker for every symbol, once per second
+pub const ALL_MARKET_MINI_TICKER_STREAM: &str = "!miniTicker@arr";
+/// Stream carrying the full 24hr ticker for every symbol, once per second
+pub const ALL_MARKET_TICKER_STREAM: &str = "!ticker@arr";
+
 /// Binance WebSocket client
 pub struct BinanceWebSocket {
     url: String,
@@ -174,6 +180,13 @@ impl BinanceWebSocket {
         Ok(())
     }
 
+    /// Subscribe to aggregate trade stream for a symbol
+    pub async fn subscribe_agg_trade(&self, symbol: &str) -> Result<()> {
+        self.subscribe(symbol, "aggTrade").await?;
+        info!("Subscribed to aggregate trade stream for {}", symbol);
+        Ok(())
+    }
+
     /// Subscribe to 24hr ticker stream for a symbol
     pub async fn subscribe_ticker(&self, symbol: &str) -> Result<()> {
         self.subscribe(symbol, "ticker").await?;
@@ -181,6 +194,13 @@ impl BinanceWebSocket {
         Ok(())
     }
 
+    /// Subscribe to best bid/offer stream for a symbol
+    pub async fn subscribe_book_ticker(&self, symbol: &str) -> Result<()> {
+        self.subscribe(symbol, "bookTicker").await?;
+        info!("Subscribed to book ticker stream for {}", symbol);
+        Ok(())
+    }
+
     /// Subscribe to kline stream for a symbol with the given interval
     pub async fn subscribe_kline(&self, symbol: &str, interval: &str) -> Result<()> {
         let stream_type = format!("kline_{}", interval);
@@ -351,17 +371,30 @@ impl BinanceWebSocket {
 
     /// Classify message based on event type
     fn classify_message(value: &serde_json::Value) -> Option<BinanceEventType> {
-        value
-            .get("e")
-            .and_then(|v| v.as_str())
-            .and_then(|event_type| match event_type {
-                "depthUpdate" => Some(BinanceEventType::DepthUpdate),
-                "trade" => Some(BinanceEventType::Trade),
-                "24hrTicker" => Some(BinanceEventType::Ticker24hr),
-                "kline" => Some(BinanceEventType::Kline),
-                "aggTrade" => Some(BinanceEventType::AggregatedTrade),
-                _ => None,
-            })
+        let Some(event_type) = value.get("e") else {
+            let is_book_ticker = ["u", "s", "b", "a"]
+                .iter()
+                .all(|key| value.get(key).is_some());
+            return is_book_ticker.then_some(BinanceEventType::BookTicker);
+        };
+        match event_type.as_str()? {
+            "depthUpdate" => Some(BinanceEventType::DepthUpdate),
+            "trade" => Some(BinanceEventType::Trade),
+            "24hrTicker" => Some(BinanceEventType::Ticker24hr),
+            "kline" => Some(BinanceEventType::Kline),
+            "aggTrade" => Some(BinanceEventType::AggregatedTrade),
+            _ => None,
+        }
+    }
+
+    /// Stream name for an all-market ticker array, `None` for any other payload
+    fn all_market_stream(value: &serde_json::Value) -> Option<&'static str> {
+        let event_type = value.as_array()?.first()?.get("e")?.as_str()?;
+        match event_type {
+            "24hrMiniTicker" => Some(ALL_MARKET_MINI_TICKER_STREAM),
+            "24hrTicker" => Some(ALL_MARKET_TICKER_STREAM),
+            _ => None,
+        }
     }
 
     /// Process incoming WebSocket message
@@ -375,6 +408,15 @@ impl BinanceWebSocket {
                     WebSocketError::ParseError(format!("Failed to parse JSON: {}", e))
                 })?;
 
+                // All-market streams deliver one array of tickers per update
+                if let Some(stream) = Self::all_market_stream(&value) {
+                    debug!("Received all-market ticker array on {}", stream);
+                    return Ok(BinanceMessage {
+                        stream: stream.to_string(),
+                        data: value,
+                    });
+                }
+
                 // Classify message based on event type
                 match Self::classify_message(&value) {
                     Some(BinanceEventType::DepthUpdate) => {
@@ -467,12 +509,37 @@ impl BinanceWebSocket {
 
                         Ok(BinanceMessage { stream, data })
                     }
+                    Some(BinanceEventType::BookTicker) => {
+                        let book_ticker: BookTickerMessage = serde_json::from_value(value)
+                            .map_err(|e| {
+                                WebSocketError::ParseError(format!(
+                                    "Failed to parse book ticker: {}",
+                                    e
+                                ))
+                            })?;
+
+                        Ok(BinanceMessage {
+                            stream: format!("{}@bookTicker", book_ticker.symbol.to_lowercase()),
+                            data: serde_json::json!(book_ticker),
+                        })
+                    }
                     Some(BinanceEventType::AggregatedTrade) => {
-                        // Aggregated trade messages are not yet fully supported
-                        debug!("Received aggregated trade message (not fully supported)");
+                        let agg_trade: AggTradeMessage =
+                            serde_json::from_value(value).map_err(|e| {
+                                WebSocketError::ParseError(format!(
+                                    "Failed to parse aggregate trade message: {}",
+                                    e
+                                ))
+                            })?;
+
+                        debug!(
+                            "Received aggregate trade message: symbol={}, price={}, quantity={}",
+                            agg_trade.symbol, agg_trade.price, agg_trade.quantity
+                        );
+
                         Ok(BinanceMessage {
-                            stream: "aggTrade".to_string(),
-                            data: value,
+                            stream: format!("{}@aggTrade", agg_trade.symbol.to_lowercase()),
+                            data: serde_json::json!(agg_trade),
                         })
                     }
                     None => {
@@ -744,6 +811,51 @@ mod tests {
         assert_eq!(result, Some(BinanceEventType::Ticker24hr));
     }
 
+    #[test]
+    fn test_classify_message_book_ticker() {
+        let json_data = serde_json::json!({
+            "u": 400900217,
+            "s": "BNBUSDT",
+            "b": "25.35190000",
+            "B": "31.21000000",
+            "a": "25.36520000",
+            "A": "40.66000000"
+        });
+        let result = BinanceWebSocket::classify_message(&json_data);
+        assert_eq!(result, Some(BinanceEventType::BookTicker));
+
+        let msg = Message::Text(json_data.to_string());
+        let result = BinanceWebSocket::process_message(msg).unwrap();
+        assert_eq!(result.stream, "bnbusdt@bookTicker");
+    }
+
+    #[test]
+    fn test_process_message_agg_trade() {
+        let json_data = serde_json::json!({
+            "e": "aggTrade",
+            "E": 1672515782136u64,
+            "s": "BNBBTC",
+            "a": 12345,
+            "p": "0.001",
+            "q": "100",
+            "f": 100,
+            "l": 105,
+            "T": 1672515782136u64,
+            "m": true,
+            "M": true
+        });
+        let result = BinanceWebSocket::classify_message(&json_data);
+        assert_eq!(result, Some(BinanceEventType::AggregatedTrade));
+
+        let msg = Message::Text(json_data.to_string());
+        let result = BinanceWebSocket::process_message(msg).unwrap();
+        assert_eq!(result.stream, "bnbbtc@aggTrade");
+        let parsed: AggTradeMessage = serde_json::from_value(result.data).unwrap();
+        assert_eq!(parsed.agg_trade_id, 12345);
+        assert_eq!(parsed.last_trade_id - parsed.first_trade_id, 5);
+        assert!(parsed.is_buyer_maker);
+    }
+
     #[test]
     fn test_classify_message_unknown() {
         let json_data = serde_json::json!({
@@ -764,6 +876,20 @@ mod tests {
         assert_eq!(result, None);
     }
 
+    #[test]
+    fn test_process_message_all_market_array() {
+        let msg = Message::Text(
+            r#"[{"e":"24hrMiniTicker","E":1,"s":"BTCUSDT","c":"1","o":"1","h":"1","l":"1","v":"1","q":"1"}]"#
+                .to_string(),
+        );
+        let result = BinanceWebSocket::process_message(msg).unwrap();
+        assert_eq!(result.stream, ALL_MARKET_MINI_TICKER_STREAM);
+        assert!(result.data.is_array());
+
+        let other = serde_json::json!([{ "e": "trade" }]);
+        assert_eq!(BinanceWebSocket::all_market_stream(&other), None);
+    }
+
     #[tokio::test]
     async fn test_reconnect_logic() {
         let (ws, _rx) = BinanceWebSocket::new("wss://invalid-test-url");
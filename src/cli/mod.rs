@@ -2,7 +2,12 @@
 //!
 //! Implements the CLI commands and argument parsing for XTrade.
 
-use clap::{Parser, Subcommand};
+use std::path::PathBuf;
+
+use clap::{Args, Parser, Subcommand};
+
+use crate::fetch::ExportFormat;
+use crate::watch::{DEFAULT_WATCH_EVENTS, WatchEventKind, WatchFormat};
 
 #[derive(Parser, Debug, Clone)]
 #[command(name = "xtrade")]
@@ -28,6 +33,10 @@ pub struct Cli {
     /// Dry-run mode: show welcome page and configuration without starting UI
     #[arg(long)]
     pub dry_run: bool,
+
+    /// Send orders to Binance's test endpoint, which validates them without trading
+    #[arg(long)]
+    pub testnet: bool,
 }
 
 #[derive(Subcommand, Debug, Clone)]
@@ -35,17 +44,29 @@ pub enum Commands {
     /// Start interactive terminal session
     #[command(hide = true)]
     Interactive {
-        /// Use simple CLI output instead of full TUI
+        /// Stream the configured symbols as plain text instead of starting the TUI
         #[arg(long)]
         simple: bool,
     },
 
+    /// Stream market events and alert triggers to stdout for piping
+    Watch(WatchArgs),
+
     /// Configuration management
     Config {
         #[command(subcommand)]
         action: Option<ConfigAction>,
     },
 
+    #[command(flatten)]
+    Query(QueryCommand),
+
+    /// Download historical market data over REST
+    Fetch {
+        #[command(subcommand)]
+        target: FetchTarget,
+    },
+
     /// Demo WebSocket functionality (for testing)
     Demo,
 }
@@ -73,6 +94,151 @@ pub enum ConfigAction {
     Reset,
 }
 
+/// One-shot REST queries that print a table or JSON and exit
+#[derive(Subcommand, Debug, Clone)]
+pub enum QueryCommand {
+    /// Print bid, ask and last price for one or more symbols
+    Quote {
+        /// Symbols to quote, e.g. BTCUSDT ETHUSDT
+        #[arg(required = true)]
+        symbols: Vec<String>,
+
+        #[command(flatten)]
+        output: OutputArgs,
+    },
+
+    /// Print the top of the order book for a symbol
+    Book {
+        /// Symbol to query, e.g. BTCUSDT
+        symbol: String,
+
+        /// Number of levels per side (1-5000)
+        #[arg(long, default_value_t = 10, value_parser = clap::value_parser!(u16).range(1..=5000))]
+        depth: u16,
+
+        #[command(flatten)]
+        output: OutputArgs,
+    },
+
+    /// Print 24h rolling statistics for one or more symbols
+    Ticker {
+        /// Symbols to query, e.g. BTCUSDT ETHUSDT
+        #[arg(required = true)]
+        symbols: Vec<String>,
+
+        #[command(flatten)]
+        output: OutputArgs,
+    },
+
+    /// List exchange symbols, optionally filtered by base or quote asset
+    Symbols {
+        /// Only symbols quoted in this asset, e.g. USDT
+        #[arg(long)]
+        quote: Option<String>,
+
+        /// Only symbols with this base asset, e.g. BTC
+        #[arg(long)]
+        base: Option<String>,
+
+        /// Include symbols that are not currently trading
+        #[arg(long)]
+        all: bool,
+
+        #[command(flatten)]
+        output: OutputArgs,
+    },
+}
+
+#[derive(Args, Debug, Clone, Copy, Default)]
+pub struct OutputArgs {
+    /// Print JSON instead of a table
+    #[arg(long)]
+    pub json: bool,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct WatchArgs {
+    /// Symbols to watch, e.g. BTCUSDT ETHUSDT
+    #[arg(required = true)]
+    pub symbols: Vec<String>,
+
+    /// Output format
+    #[arg(long, value_enum, default_value_t = WatchFormat::Ndjson)]
+    pub format: WatchFormat,
+
+    /// Comma separated event families to print
+    #[arg(long, value_enum, value_delimiter = ',', default_values_t = DEFAULT_WATCH_EVENTS)]
+    pub events: Vec<WatchEventKind>,
+
+    /// Order book levels per side in depth events
+    #[arg(long, default_value_t = 5)]
+    pub depth: usize,
+
+    /// Alert to evaluate, written like `/alert:add` arguments (repeatable),
+    /// e.g. --alert "BTCUSDT above 70000"
+    #[arg(long = "alert", value_name = "SPEC")]
+    pub alerts: Vec<String>,
+
+    /// Exit after printing this many records
+    #[arg(long)]
+    pub limit: Option<u64>,
+}
+
+impl WatchArgs {
+    /// Default settings for the given symbols and format
+    pub fn for_symbols(symbols: Vec<String>, format: WatchFormat) -> Self {
+        Self {
+            symbols,
+            format,
+            events: DEFAULT_WATCH_EVENTS.to_vec(),
+            depth: 5,
+            alerts: Vec::new(),
+            limit: None,
+        }
+    }
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum FetchTarget {
+    /// Download klines for a symbol and time range into a CSV, NDJSON or Parquet file
+    Klines(KlineFetchArgs),
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct KlineFetchArgs {
+    /// Trading pair symbol, e.g. BTCUSDT
+    #[arg(long)]
+    pub symbol: String,
+
+    /// Kline interval (1s, 1m, 3m, 5m, 15m, 30m, 1h, 2h, 4h, 6h, 8h, 12h, 1d, 3d, 1w, 1M)
+    #[arg(long, default_value = "1h")]
+    pub interval: String,
+
+    /// Start of the range: YYYY-MM-DD, RFC 3339 or epoch milliseconds (UTC)
+    #[arg(long)]
+    pub from: String,
+
+    /// End of the range, exclusive; defaults to now
+    #[arg(long)]
+    pub to: Option<String>,
+
+    /// Output file
+    #[arg(long)]
+    pub out: PathBuf,
+
+    /// Output format; inferred from the --out extension when omitted
+    #[arg(long, value_enum)]
+    pub format: Option<ExportFormat>,
+
+    /// Continue an interrupted download after the last row already in --out
+    #[arg(long, conflicts_with = "force")]
+    pub resume: bool,
+
+    /// Overwrite --out if it already exists
+    #[arg(long)]
+    pub force: bool,
+}
+
 impl Cli {
     /// Parse command line arguments
     pub fn parse_args() -> Self {
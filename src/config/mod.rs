@@ -4,9 +4,17 @@
 
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::env;
 use std::path::Path;
 
+use crate::market_data::{
+    BarSpec, DEFAULT_BAR_HISTORY, DEFAULT_HEATMAP_INTERVAL_MS, DEFAULT_HEATMAP_LEVELS,
+    DEFAULT_HEATMAP_SAMPLES, HeatmapSettings,
+};
+use crate::portfolio::{Holding, PortfolioAlertKind};
+use crate::storage::RetentionPolicy;
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Config {
     /// List of trading symbols to monitor
@@ -32,6 +40,42 @@ pub struct Config {
 
     /// UI-specific configuration
     pub ui: UiConfig,
+
+    /// Alert journal configuration
+    #[serde(default)]
+    pub alerts: AlertsConfig,
+
+    /// Prometheus exporter configuration
+    #[serde(default)]
+    pub metrics: MetricsConfig,
+
+    /// Exchange clock synchronisation
+    #[serde(default)]
+    pub clock: ClockConfig,
+
+    /// Bars aggregated locally from the trade stream
+    #[serde(default)]
+    pub candles: CandlesConfig,
+
+    /// Local history store for candles and trades
+    #[serde(default)]
+    pub storage: StorageConfig,
+
+    /// Simulated trading against the live order book
+    #[serde(default)]
+    pub paper: PaperConfig,
+
+    /// Authenticated account data (balances, orders, user data stream)
+    #[serde(default)]
+    pub account: AccountConfig,
+
+    /// Order entry against the exchange account
+    #[serde(default)]
+    pub orders: OrdersConfig,
+
+    /// Manually entered holdings valued from live prices
+    #[serde(default)]
+    pub portfolio: PortfolioConfig,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -50,6 +94,49 @@ pub struct BinanceConfig {
 
     /// Maximum reconnection attempts
     pub max_reconnect_attempts: u32,
+
+    /// Seconds between exchange info refreshes (symbol filters and status)
+    #[serde(default = "default_exchange_info_refresh_secs")]
+    pub exchange_info_refresh_secs: u64,
+
+    /// Public trade feed: raw `trade` events or exchange-aggregated `aggTrade`
+    #[serde(default)]
+    pub trade_stream: TradeStreamKind,
+}
+
+fn default_exchange_info_refresh_secs() -> u64 {
+    300
+}
+
+/// Which Binance trade stream feeds prices and trade analytics
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
+pub enum TradeStreamKind {
+    /// Every individual fill (`<symbol>@trade`)
+    #[default]
+    #[serde(rename = "trade")]
+    Trade,
+    /// Fills at the same price and taker side merged (`<symbol>@aggTrade`)
+    #[serde(rename = "aggTrade")]
+    AggTrade,
+}
+
+impl TradeStreamKind {
+    /// Stream name suffix used when subscribing
+    pub fn stream_name(self) -> &'static str {
+        match self {
+            TradeStreamKind::Trade => "trade",
+            TradeStreamKind::AggTrade => "aggTrade",
+        }
+    }
+
+    /// Parse a config or environment value, accepting `aggtrade` and `agg_trade`
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw.trim().to_ascii_lowercase().replace('_', "").as_str() {
+            "trade" => Some(TradeStreamKind::Trade),
+            "aggtrade" => Some(TradeStreamKind::AggTrade),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -66,6 +153,195 @@ pub struct UiConfig {
 
     /// Minimum seconds between kline redraws from streaming updates
     pub kline_refresh_secs: u64,
+
+    /// File used to persist command palette history across sessions
+    pub history_file: String,
+
+    /// Maximum number of commands kept in history
+    pub history_size: usize,
+
+    /// Book levels per side sampled for the liquidity heatmap
+    pub heatmap_levels: usize,
+
+    /// Minimum milliseconds between liquidity heatmap samples
+    pub heatmap_interval_ms: u64,
+
+    /// Liquidity heatmap samples kept per symbol
+    pub heatmap_samples: usize,
+}
+
+impl UiConfig {
+    /// Book sampling settings for the liquidity heatmap
+    pub fn heatmap_settings(&self) -> HeatmapSettings {
+        HeatmapSettings {
+            levels: self.heatmap_levels,
+            interval_ms: self.heatmap_interval_ms,
+            samples: self.heatmap_samples,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub struct AlertsConfig {
+    /// Append-only journal of fired alerts (NDJSON)
+    pub history_file: String,
+
+    /// Maximum number of fired alerts kept in memory for display and export
+    pub history_limit: usize,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub struct MetricsConfig {
+    /// Serve Prometheus metrics over HTTP
+    pub enabled: bool,
+
+    /// Address the `/metrics` endpoint listens on
+    pub bind_address: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub struct ClockConfig {
+    /// Periodically sample Binance server time to correct latency metrics
+    pub sync_enabled: bool,
+
+    /// Seconds between server time sampling rounds
+    pub sync_interval_secs: u64,
+
+    /// Requests per sampling round; slow round trips are discarded
+    pub sync_samples: usize,
+
+    /// Warn when the local clock is further than this from the exchange
+    pub warn_skew_ms: u64,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub struct CandlesConfig {
+    /// Bar specs to build per symbol: time (`1s`, `5m`), volume (`v10`) or tick (`t100`)
+    pub bars: Vec<BarSpec>,
+
+    /// Bars kept per spec for the chart
+    pub history: usize,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub struct StorageConfig {
+    /// Persist candles (and optionally trades) between runs
+    pub enabled: bool,
+
+    /// Directory holding the segment files
+    pub dir: String,
+
+    /// Also store every trade print; large on busy symbols
+    pub record_trades: bool,
+
+    /// Days of exchange daily candles to keep, 0 to keep forever
+    pub daily_candle_retention_days: u64,
+
+    /// Days of locally aggregated bars to keep, 0 to keep forever
+    pub bar_retention_days: u64,
+
+    /// Days of trades to keep, 0 to keep forever
+    pub trade_retention_days: u64,
+}
+
+impl StorageConfig {
+    pub fn retention(&self) -> RetentionPolicy {
+        RetentionPolicy {
+            daily_candle_days: self.daily_candle_retention_days,
+            bar_days: self.bar_retention_days,
+            trade_days: self.trade_retention_days,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub struct PaperConfig {
+    /// Accept paper orders; also streams individual trades for fill simulation
+    pub enabled: bool,
+
+    /// JSON document holding balances, orders, fills and positions
+    pub state_file: String,
+
+    /// Balances a new or reset paper account starts with, by asset
+    pub starting_balances: BTreeMap<String, f64>,
+
+    /// Fee on fills that rested in the book, in basis points
+    pub maker_fee_bps: f64,
+
+    /// Fee on fills that took liquidity, in basis points
+    pub taker_fee_bps: f64,
+
+    /// Closed orders and fills kept in the state file
+    pub history_limit: usize,
+}
+
+/// Settings for the authenticated account view
+///
+/// The API key and secret are never stored here; they are read from
+/// `XTRADE_BINANCE_API_KEY` / `XTRADE_BINANCE_API_SECRET` or `credentials_file`.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub struct AccountConfig {
+    /// Load balances and open orders and stream account updates
+    pub enabled: bool,
+
+    /// TOML file with `api_key` and `api_secret`, readable by the owner only
+    pub credentials_file: String,
+
+    /// Milliseconds a signed request stays valid after its timestamp
+    pub recv_window_ms: u64,
+
+    /// Seconds between listen key keepalives; Binance expires keys after 60 minutes
+    pub keepalive_interval_secs: u64,
+}
+
+/// Settings for live order entry
+///
+/// Orders are signed with the same credentials as the account view.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub struct OrdersConfig {
+    /// Accept `/order` commands
+    pub enabled: bool,
+
+    /// Send orders to `/api/v3/order/test`, which validates them without trading
+    pub test_mode: bool,
+
+    /// Largest order value accepted per quote asset, e.g. `{ USDT = 100.0 }`;
+    /// orders on quote assets without an entry are rejected
+    pub max_notional: BTreeMap<String, f64>,
+}
+
+/// Settings for the manual portfolio
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub struct PortfolioConfig {
+    /// Track holdings and accept `/position` commands
+    pub enabled: bool,
+
+    /// JSON document holding the current holdings, thresholds and peak value
+    pub state_file: String,
+
+    /// Asset every holding is valued in
+    pub currency: String,
+
+    /// Alert when the total value rises to this amount
+    pub value_alert_above: Option<f64>,
+
+    /// Alert when the total value falls to this amount
+    pub value_alert_below: Option<f64>,
+
+    /// Alert when the value is this many percent below its peak
+    pub drawdown_alert_pct: Option<f64>,
+
+    /// Holdings a new or reset portfolio starts with
+    pub holdings: Vec<Holding>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -85,6 +361,15 @@ impl Default for Config {
             log: LogConfig::default(),
             binance: BinanceConfig::default(),
             ui: UiConfig::default(),
+            alerts: AlertsConfig::default(),
+            metrics: MetricsConfig::default(),
+            clock: ClockConfig::default(),
+            candles: CandlesConfig::default(),
+            storage: StorageConfig::default(),
+            paper: PaperConfig::default(),
+            account: AccountConfig::default(),
+            orders: OrdersConfig::default(),
+            portfolio: PortfolioConfig::default(),
         }
     }
 }
@@ -97,6 +382,8 @@ impl Default for BinanceConfig {
             timeout_seconds: 10,
             reconnect_interval_ms: 1000,
             max_reconnect_attempts: 10,
+            exchange_info_refresh_secs: default_exchange_info_refresh_secs(),
+            trade_stream: TradeStreamKind::default(),
         }
     }
 }
@@ -108,6 +395,116 @@ impl Default for UiConfig {
             update_rate_fps: 20,
             sparkline_points: 60,
             kline_refresh_secs: 60,
+            history_file: ".xtrade_history".to_string(),
+            history_size: 500,
+            heatmap_levels: DEFAULT_HEATMAP_LEVELS,
+            heatmap_interval_ms: DEFAULT_HEATMAP_INTERVAL_MS,
+            heatmap_samples: DEFAULT_HEATMAP_SAMPLES,
+        }
+    }
+}
+
+impl Default for AlertsConfig {
+    fn default() -> Self {
+        Self {
+            history_file: ".xtrade_alerts.ndjson".to_string(),
+            history_limit: 1_000,
+        }
+    }
+}
+
+impl Default for MetricsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            bind_address: "127.0.0.1:9184".to_string(),
+        }
+    }
+}
+
+impl Default for ClockConfig {
+    fn default() -> Self {
+        Self {
+            sync_enabled: true,
+            sync_interval_secs: 60,
+            sync_samples: 4,
+            warn_skew_ms: 1_000,
+        }
+    }
+}
+
+impl Default for CandlesConfig {
+    fn default() -> Self {
+        Self {
+            bars: vec![
+                BarSpec::Time { interval_ms: 1_000 },
+                BarSpec::Time { interval_ms: 5_000 },
+                BarSpec::Time {
+                    interval_ms: 60_000,
+                },
+            ],
+            history: DEFAULT_BAR_HISTORY,
+        }
+    }
+}
+
+impl Default for StorageConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            dir: "data".to_string(),
+            record_trades: false,
+            daily_candle_retention_days: 0,
+            bar_retention_days: 7,
+            trade_retention_days: 2,
+        }
+    }
+}
+
+impl Default for PaperConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            state_file: ".xtrade_paper.json".to_string(),
+            starting_balances: BTreeMap::from([("USDT".to_string(), 10_000.0)]),
+            maker_fee_bps: 10.0,
+            taker_fee_bps: 10.0,
+            history_limit: 500,
+        }
+    }
+}
+
+impl Default for AccountConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            credentials_file: String::new(),
+            recv_window_ms: 5_000,
+            keepalive_interval_secs: 1_800,
+        }
+    }
+}
+
+impl Default for OrdersConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            test_mode: true,
+            max_notional: BTreeMap::from([("USDT".to_string(), 100.0)]),
+        }
+    }
+}
+
+impl Default for PortfolioConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            state_file: ".xtrade_portfolio.json".to_string(),
+            currency: "USDT".to_string(),
+            value_alert_above: None,
+            value_alert_below: None,
+            drawdown_alert_pct: None,
+            holdings: Vec::new(),
         }
     }
 }
@@ -210,6 +607,24 @@ impl Config {
             }
         }
 
+        // XTRADE_BINANCE_EXCHANGE_INFO_REFRESH_SECS - exchange info refresh interval
+        if let Ok(refresh) = env::var("XTRADE_BINANCE_EXCHANGE_INFO_REFRESH_SECS") {
+            if let Ok(value) = refresh.parse::<u64>() {
+                self.binance.exchange_info_refresh_secs = value;
+            }
+        }
+
+        // XTRADE_BINANCE_TRADE_STREAM - trade feed ("trade" or "aggTrade")
+        if let Ok(stream) = env::var("XTRADE_BINANCE_TRADE_STREAM") {
+            match TradeStreamKind::parse(&stream) {
+                Some(kind) => self.binance.trade_stream = kind,
+                None => tracing::warn!(
+                    "Ignoring XTRADE_BINANCE_TRADE_STREAM='{}': expected 'trade' or 'aggTrade'",
+                    stream
+                ),
+            }
+        }
+
         // UI-specific environment variables
         // XTRADE_UI_ENABLE_COLORS - enable colors
         if let Ok(enable_colors) = env::var("XTRADE_UI_ENABLE_COLORS") {
@@ -236,6 +651,276 @@ impl Config {
                 self.ui.kline_refresh_secs = value.max(1);
             }
         }
+
+        // XTRADE_UI_HISTORY_FILE - command history file
+        if let Ok(path) = env::var("XTRADE_UI_HISTORY_FILE") {
+            self.ui.history_file = path;
+        }
+
+        // XTRADE_UI_HISTORY_SIZE - command history length
+        if let Ok(size) = env::var("XTRADE_UI_HISTORY_SIZE") {
+            if let Ok(value) = size.parse::<usize>() {
+                self.ui.history_size = value;
+            }
+        }
+
+        // XTRADE_UI_HEATMAP_LEVELS - heatmap levels per side
+        if let Ok(levels) = env::var("XTRADE_UI_HEATMAP_LEVELS") {
+            if let Ok(value) = levels.parse::<usize>() {
+                self.ui.heatmap_levels = value;
+            }
+        }
+
+        // XTRADE_UI_HEATMAP_INTERVAL_MS - heatmap sampling cadence
+        if let Ok(interval) = env::var("XTRADE_UI_HEATMAP_INTERVAL_MS") {
+            if let Ok(value) = interval.parse::<u64>() {
+                self.ui.heatmap_interval_ms = value;
+            }
+        }
+
+        // XTRADE_UI_HEATMAP_SAMPLES - heatmap buffer length
+        if let Ok(samples) = env::var("XTRADE_UI_HEATMAP_SAMPLES") {
+            if let Ok(value) = samples.parse::<usize>() {
+                self.ui.heatmap_samples = value;
+            }
+        }
+
+        // XTRADE_ALERTS_HISTORY_FILE - fired alert journal
+        if let Ok(path) = env::var("XTRADE_ALERTS_HISTORY_FILE") {
+            self.alerts.history_file = path;
+        }
+
+        // XTRADE_ALERTS_HISTORY_LIMIT - fired alerts kept in memory
+        if let Ok(limit) = env::var("XTRADE_ALERTS_HISTORY_LIMIT") {
+            if let Ok(value) = limit.parse::<usize>() {
+                self.alerts.history_limit = value;
+            }
+        }
+
+        // XTRADE_METRICS_ENABLED - serve Prometheus metrics
+        if let Ok(enabled) = env::var("XTRADE_METRICS_ENABLED") {
+            self.metrics.enabled = enabled.parse().unwrap_or(self.metrics.enabled);
+        }
+
+        // XTRADE_METRICS_BIND_ADDRESS - Prometheus listen address
+        if let Ok(address) = env::var("XTRADE_METRICS_BIND_ADDRESS") {
+            self.metrics.bind_address = address;
+        }
+
+        // XTRADE_CLOCK_SYNC_ENABLED - sample exchange server time
+        if let Ok(enabled) = env::var("XTRADE_CLOCK_SYNC_ENABLED") {
+            self.clock.sync_enabled = enabled.parse().unwrap_or(self.clock.sync_enabled);
+        }
+
+        // XTRADE_CLOCK_SYNC_INTERVAL_SECS - seconds between sampling rounds
+        if let Ok(interval) = env::var("XTRADE_CLOCK_SYNC_INTERVAL_SECS") {
+            if let Ok(value) = interval.parse::<u64>() {
+                self.clock.sync_interval_secs = value;
+            }
+        }
+
+        // XTRADE_CLOCK_SYNC_SAMPLES - requests per sampling round
+        if let Ok(samples) = env::var("XTRADE_CLOCK_SYNC_SAMPLES") {
+            if let Ok(value) = samples.parse::<usize>() {
+                self.clock.sync_samples = value;
+            }
+        }
+
+        // XTRADE_CLOCK_WARN_SKEW_MS - clock skew warning threshold
+        if let Ok(threshold) = env::var("XTRADE_CLOCK_WARN_SKEW_MS") {
+            if let Ok(value) = threshold.parse::<u64>() {
+                self.clock.warn_skew_ms = value;
+            }
+        }
+
+        // XTRADE_CANDLES_BARS - comma-separated bar specs, empty to disable
+        if let Ok(bars) = env::var("XTRADE_CANDLES_BARS") {
+            match bars
+                .split(',')
+                .map(str::trim)
+                .filter(|spec| !spec.is_empty())
+                .map(BarSpec::parse)
+                .collect::<Result<Vec<_>>>()
+            {
+                Ok(specs) => self.candles.bars = specs,
+                Err(e) => tracing::warn!("Ignoring XTRADE_CANDLES_BARS='{}': {}", bars, e),
+            }
+        }
+
+        // XTRADE_CANDLES_HISTORY - bars kept per spec
+        if let Ok(history) = env::var("XTRADE_CANDLES_HISTORY") {
+            if let Ok(value) = history.parse::<usize>() {
+                self.candles.history = value;
+            }
+        }
+
+        // XTRADE_STORAGE_ENABLED - persist candles and trades
+        if let Ok(enabled) = env::var("XTRADE_STORAGE_ENABLED") {
+            self.storage.enabled = enabled.parse().unwrap_or(self.storage.enabled);
+        }
+
+        // XTRADE_STORAGE_DIR - history store directory
+        if let Ok(dir) = env::var("XTRADE_STORAGE_DIR") {
+            self.storage.dir = dir;
+        }
+
+        // XTRADE_STORAGE_RECORD_TRADES - also store trade prints
+        if let Ok(record) = env::var("XTRADE_STORAGE_RECORD_TRADES") {
+            self.storage.record_trades = record.parse().unwrap_or(self.storage.record_trades);
+        }
+
+        // XTRADE_STORAGE_DAILY_CANDLE_RETENTION_DAYS - daily candle retention
+        if let Ok(days) = env::var("XTRADE_STORAGE_DAILY_CANDLE_RETENTION_DAYS") {
+            if let Ok(value) = days.parse::<u64>() {
+                self.storage.daily_candle_retention_days = value;
+            }
+        }
+
+        // XTRADE_STORAGE_BAR_RETENTION_DAYS - local bar retention
+        if let Ok(days) = env::var("XTRADE_STORAGE_BAR_RETENTION_DAYS") {
+            if let Ok(value) = days.parse::<u64>() {
+                self.storage.bar_retention_days = value;
+            }
+        }
+
+        // XTRADE_STORAGE_TRADE_RETENTION_DAYS - trade retention
+        if let Ok(days) = env::var("XTRADE_STORAGE_TRADE_RETENTION_DAYS") {
+            if let Ok(value) = days.parse::<u64>() {
+                self.storage.trade_retention_days = value;
+            }
+        }
+
+        // XTRADE_PAPER_ENABLED - accept paper orders
+        if let Ok(enabled) = env::var("XTRADE_PAPER_ENABLED") {
+            self.paper.enabled = enabled.to_lowercase() == "true";
+        }
+
+        // XTRADE_PAPER_STATE_FILE - paper account state document
+        if let Ok(path) = env::var("XTRADE_PAPER_STATE_FILE") {
+            self.paper.state_file = path;
+        }
+
+        // XTRADE_PAPER_STARTING_BALANCES - e.g. "USDT=10000,BTC=0.5"
+        if let Ok(balances) = env::var("XTRADE_PAPER_STARTING_BALANCES") {
+            match parse_balances(&balances) {
+                Ok(parsed) => self.paper.starting_balances = parsed,
+                Err(e) => tracing::warn!(
+                    "Ignoring XTRADE_PAPER_STARTING_BALANCES='{}': {}",
+                    balances,
+                    e
+                ),
+            }
+        }
+
+        // XTRADE_PAPER_MAKER_FEE_BPS - maker fee in basis points
+        if let Ok(fee) = env::var("XTRADE_PAPER_MAKER_FEE_BPS") {
+            if let Ok(value) = fee.parse::<f64>() {
+                self.paper.maker_fee_bps = value;
+            }
+        }
+
+        // XTRADE_PAPER_TAKER_FEE_BPS - taker fee in basis points
+        if let Ok(fee) = env::var("XTRADE_PAPER_TAKER_FEE_BPS") {
+            if let Ok(value) = fee.parse::<f64>() {
+                self.paper.taker_fee_bps = value;
+            }
+        }
+
+        // XTRADE_PAPER_HISTORY_LIMIT - closed orders and fills kept
+        if let Ok(limit) = env::var("XTRADE_PAPER_HISTORY_LIMIT") {
+            if let Ok(value) = limit.parse::<usize>() {
+                self.paper.history_limit = value;
+            }
+        }
+
+        // XTRADE_ACCOUNT_ENABLED - stream authenticated account data
+        if let Ok(enabled) = env::var("XTRADE_ACCOUNT_ENABLED") {
+            self.account.enabled = enabled.to_lowercase() == "true";
+        }
+
+        // XTRADE_ACCOUNT_CREDENTIALS_FILE - API key and secret file
+        if let Ok(path) = env::var("XTRADE_ACCOUNT_CREDENTIALS_FILE") {
+            self.account.credentials_file = path;
+        }
+
+        // XTRADE_ACCOUNT_RECV_WINDOW_MS - signed request validity
+        if let Ok(window) = env::var("XTRADE_ACCOUNT_RECV_WINDOW_MS") {
+            if let Ok(value) = window.parse::<u64>() {
+                self.account.recv_window_ms = value;
+            }
+        }
+
+        // XTRADE_ACCOUNT_KEEPALIVE_INTERVAL_SECS - listen key keepalive interval
+        if let Ok(interval) = env::var("XTRADE_ACCOUNT_KEEPALIVE_INTERVAL_SECS") {
+            if let Ok(value) = interval.parse::<u64>() {
+                self.account.keepalive_interval_secs = value;
+            }
+        }
+
+        // XTRADE_ORDERS_ENABLED - accept live order commands
+        if let Ok(enabled) = env::var("XTRADE_ORDERS_ENABLED") {
+            self.orders.enabled = enabled.to_lowercase() == "true";
+        }
+
+        // XTRADE_ORDERS_TEST_MODE - validate orders without trading
+        if let Ok(test_mode) = env::var("XTRADE_ORDERS_TEST_MODE") {
+            self.orders.test_mode = test_mode.to_lowercase() == "true";
+        }
+
+        // XTRADE_ORDERS_MAX_NOTIONAL - largest order value per quote asset (e.g., "USDT=100,BTC=0.002")
+        if let Ok(max) = env::var("XTRADE_ORDERS_MAX_NOTIONAL") {
+            match parse_balances(&max) {
+                Ok(caps) => self.orders.max_notional = caps,
+                Err(e) => tracing::warn!("Ignoring XTRADE_ORDERS_MAX_NOTIONAL='{}': {}", max, e),
+            }
+        }
+
+        // XTRADE_PORTFOLIO_ENABLED - track manual holdings
+        if let Ok(enabled) = env::var("XTRADE_PORTFOLIO_ENABLED") {
+            self.portfolio.enabled = enabled.to_lowercase() == "true";
+        }
+
+        // XTRADE_PORTFOLIO_STATE_FILE - portfolio state document
+        if let Ok(path) = env::var("XTRADE_PORTFOLIO_STATE_FILE") {
+            self.portfolio.state_file = path;
+        }
+
+        // XTRADE_PORTFOLIO_CURRENCY - asset holdings are valued in
+        if let Ok(currency) = env::var("XTRADE_PORTFOLIO_CURRENCY") {
+            self.portfolio.currency = currency.trim().to_ascii_uppercase();
+        }
+
+        // XTRADE_PORTFOLIO_HOLDINGS - e.g. "BTCUSDT=0.5@42000,ETHUSDT=2@2500"
+        if let Ok(holdings) = env::var("XTRADE_PORTFOLIO_HOLDINGS") {
+            match parse_holdings(&holdings) {
+                Ok(parsed) => self.portfolio.holdings = parsed,
+                Err(e) => {
+                    tracing::warn!("Ignoring XTRADE_PORTFOLIO_HOLDINGS='{}': {}", holdings, e)
+                }
+            }
+        }
+
+        // XTRADE_PORTFOLIO_VALUE_ALERT_ABOVE / _BELOW / XTRADE_PORTFOLIO_DRAWDOWN_ALERT_PCT
+        for (name, threshold) in [
+            (
+                "XTRADE_PORTFOLIO_VALUE_ALERT_ABOVE",
+                &mut self.portfolio.value_alert_above,
+            ),
+            (
+                "XTRADE_PORTFOLIO_VALUE_ALERT_BELOW",
+                &mut self.portfolio.value_alert_below,
+            ),
+            (
+                "XTRADE_PORTFOLIO_DRAWDOWN_ALERT_PCT",
+                &mut self.portfolio.drawdown_alert_pct,
+            ),
+        ] {
+            if let Ok(raw) = env::var(name) {
+                if let Ok(value) = raw.parse::<f64>() {
+                    *threshold = Some(value);
+                }
+            }
+        }
     }
 
     /// Save configuration to file
@@ -274,6 +959,10 @@ impl Config {
             anyhow::bail!("Timeout must be greater than 0");
         }
 
+        if self.binance.exchange_info_refresh_secs == 0 {
+            anyhow::bail!("binance.exchange_info_refresh_secs must be greater than 0");
+        }
+
         if self.log.file_path.trim().is_empty() {
             anyhow::bail!("Log file path must not be empty");
         }
@@ -282,6 +971,155 @@ impl Config {
             anyhow::bail!("ui.kline_refresh_secs must be greater than 0");
         }
 
+        if self.ui.history_size == 0 {
+            anyhow::bail!("ui.history_size must be greater than 0");
+        }
+
+        if self.ui.heatmap_levels == 0 {
+            anyhow::bail!("ui.heatmap_levels must be greater than 0");
+        }
+
+        if self.ui.heatmap_interval_ms == 0 {
+            anyhow::bail!("ui.heatmap_interval_ms must be greater than 0");
+        }
+
+        if self.ui.heatmap_samples == 0 {
+            anyhow::bail!("ui.heatmap_samples must be greater than 0");
+        }
+
+        if self.alerts.history_limit == 0 {
+            anyhow::bail!("alerts.history_limit must be greater than 0");
+        }
+
+        if self.metrics.enabled
+            && self
+                .metrics
+                .bind_address
+                .parse::<std::net::SocketAddr>()
+                .is_err()
+        {
+            anyhow::bail!(
+                "metrics.bind_address must be a socket address like 127.0.0.1:9184, got '{}'",
+                self.metrics.bind_address
+            );
+        }
+
+        if self.clock.sync_enabled {
+            if self.clock.sync_interval_secs == 0 {
+                anyhow::bail!("clock.sync_interval_secs must be greater than 0");
+            }
+            if self.clock.sync_samples == 0 || self.clock.sync_samples > 10 {
+                anyhow::bail!("clock.sync_samples must be between 1 and 10");
+            }
+        }
+
+        if self.candles.history == 0 {
+            anyhow::bail!("candles.history must be greater than 0");
+        }
+        if self.candles.bars.len() > 8 {
+            anyhow::bail!("candles.bars supports at most 8 specs");
+        }
+        for (idx, spec) in self.candles.bars.iter().enumerate() {
+            if self.candles.bars[..idx].contains(spec) {
+                anyhow::bail!("candles.bars lists '{}' more than once", spec);
+            }
+        }
+
+        if self.storage.enabled && self.storage.dir.trim().is_empty() {
+            anyhow::bail!("storage.dir must not be empty when storage is enabled");
+        }
+
+        if self.paper.enabled {
+            if self.paper.state_file.trim().is_empty() {
+                anyhow::bail!("paper.state_file must not be empty when paper trading is enabled");
+            }
+            for (name, fee) in [
+                ("maker_fee_bps", self.paper.maker_fee_bps),
+                ("taker_fee_bps", self.paper.taker_fee_bps),
+            ] {
+                if !(0.0..=1_000.0).contains(&fee) {
+                    anyhow::bail!("paper.{} must be between 0 and 1000, got {}", name, fee);
+                }
+            }
+            if self.paper.history_limit == 0 {
+                anyhow::bail!("paper.history_limit must be greater than 0");
+            }
+            for (asset, amount) in &self.paper.starting_balances {
+                if asset.trim().is_empty() || !amount.is_finite() || *amount < 0.0 {
+                    anyhow::bail!(
+                        "paper.starting_balances must map asset names to non-negative amounts, got {} = {}",
+                        asset,
+                        amount
+                    );
+                }
+            }
+        }
+
+        if self.account.enabled {
+            if self.account.recv_window_ms == 0 || self.account.recv_window_ms > 60_000 {
+                anyhow::bail!("account.recv_window_ms must be between 1 and 60000");
+            }
+            if !(60..=3_540).contains(&self.account.keepalive_interval_secs) {
+                anyhow::bail!(
+                    "account.keepalive_interval_secs must be between 60 and 3540 so the listen key never expires"
+                );
+            }
+        }
+
+        if self.orders.enabled {
+            if self.orders.max_notional.is_empty() {
+                anyhow::bail!("orders.max_notional needs a cap for at least one quote asset");
+            }
+            for (asset, max) in &self.orders.max_notional {
+                if !max.is_finite() || *max <= 0.0 {
+                    anyhow::bail!(
+                        "orders.max_notional.{} must be greater than 0, got {}",
+                        asset,
+                        max
+                    );
+                }
+            }
+            if self.account.recv_window_ms == 0 || self.account.recv_window_ms > 60_000 {
+                anyhow::bail!("account.recv_window_ms must be between 1 and 60000");
+            }
+        }
+
+        if self.portfolio.enabled {
+            if self.portfolio.state_file.trim().is_empty() {
+                anyhow::bail!(
+                    "portfolio.state_file must not be empty when the portfolio is enabled"
+                );
+            }
+            if self.portfolio.currency.trim().is_empty() {
+                anyhow::bail!("portfolio.currency must not be empty");
+            }
+            for holding in &self.portfolio.holdings {
+                holding
+                    .validate(None)
+                    .with_context(|| format!("Invalid portfolio holding {}", holding.symbol))?;
+            }
+            for (kind, threshold) in [
+                (
+                    PortfolioAlertKind::ValueAbove,
+                    self.portfolio.value_alert_above,
+                ),
+                (
+                    PortfolioAlertKind::ValueBelow,
+                    self.portfolio.value_alert_below,
+                ),
+                (
+                    PortfolioAlertKind::Drawdown,
+                    self.portfolio.drawdown_alert_pct,
+                ),
+            ] {
+                if let Some(threshold) = threshold {
+                    kind.validate_threshold(threshold).with_context(|| {
+                        format!("Invalid portfolio {} alert threshold", kind.label())
+                    })?;
+                }
+            }
+        }
+
         // Validate symbol format (basic check)
         for symbol in &self.symbols {
             if symbol.is_empty() || symbol.len() < 3 {
@@ -343,6 +1181,51 @@ impl Config {
     }
 }
 
+/// Parse `ASSET=amount` pairs separated by commas, e.g. `USDT=10000,BTC=0.5`
+fn parse_balances(raw: &str) -> Result<BTreeMap<String, f64>> {
+    raw.split(',')
+        .map(str::trim)
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (asset, amount) = pair
+                .split_once('=')
+                .with_context(|| format!("expected ASSET=amount, got '{}'", pair))?;
+            let amount = amount
+                .trim()
+                .parse::<f64>()
+                .with_context(|| format!("invalid amount in '{}'", pair))?;
+            Ok((asset.trim().to_ascii_uppercase(), amount))
+        })
+        .collect()
+}
+
+/// Parse `SYMBOL=quantity@cost,...` into holdings
+fn parse_holdings(raw: &str) -> Result<Vec<Holding>> {
+    raw.split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .map(|entry| {
+            let (symbol, amounts) = entry
+                .split_once('=')
+                .with_context(|| format!("expected SYMBOL=quantity@cost, got '{}'", entry))?;
+            let (quantity, cost_basis) = amounts
+                .split_once('@')
+                .with_context(|| format!("expected SYMBOL=quantity@cost, got '{}'", entry))?;
+            Ok(Holding {
+                symbol: Config::normalize_symbol(symbol),
+                quantity: quantity
+                    .trim()
+                    .parse()
+                    .with_context(|| format!("invalid quantity in '{}'", entry))?,
+                cost_basis: cost_basis
+                    .trim()
+                    .parse()
+                    .with_context(|| format!("invalid cost in '{}'", entry))?,
+            })
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -382,4 +1265,263 @@ mod tests {
         let loaded_config = Config::load_from_file(temp_file.path()).unwrap();
         assert_eq!(config.symbols, loaded_config.symbols);
     }
+
+    #[test]
+    fn test_exchange_info_refresh_defaults_when_missing() {
+        let content = r#"
+            symbols = ["BTCUSDT"]
+            refresh_rate_ms = 100
+            orderbook_depth = 20
+            enable_sparkline = true
+            log_level = "info"
+
+            [log]
+            file_path = "logs/xtrade.log"
+
+            [binance]
+            ws_url = "wss://stream.binance.com:9443"
+            rest_url = "https://api.binance.com"
+            timeout_seconds = 10
+            reconnect_interval_ms = 1000
+            max_reconnect_attempts = 10
+
+            [ui]
+        "#;
+
+        let config: Config = toml::from_str(content).unwrap();
+        assert_eq!(config.binance.exchange_info_refresh_secs, 300);
+        assert_eq!(config.binance.trade_stream, TradeStreamKind::Trade);
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_trade_stream_kind_parsing() {
+        let binance: BinanceConfig = toml::from_str(
+            r#"
+            ws_url = "wss://stream.binance.com:9443"
+            rest_url = "https://api.binance.com"
+            timeout_seconds = 10
+            reconnect_interval_ms = 1000
+            max_reconnect_attempts = 10
+            trade_stream = "aggTrade"
+        "#,
+        )
+        .unwrap();
+        assert_eq!(binance.trade_stream, TradeStreamKind::AggTrade);
+        assert_eq!(binance.trade_stream.stream_name(), "aggTrade");
+
+        assert_eq!(
+            TradeStreamKind::parse("agg_trade"),
+            Some(TradeStreamKind::AggTrade)
+        );
+        assert_eq!(
+            TradeStreamKind::parse("TRADE"),
+            Some(TradeStreamKind::Trade)
+        );
+        assert_eq!(TradeStreamKind::parse("bookTicker"), None);
+    }
+
+    #[test]
+    fn test_metrics_bind_address_validated_when_enabled() {
+        let mut config = Config::default();
+        assert!(!config.metrics.enabled);
+
+        config.metrics.bind_address = "localhost".to_string();
+        assert!(config.validate().is_ok());
+
+        config.metrics.enabled = true;
+        assert!(config.validate().is_err());
+
+        config.metrics.bind_address = "0.0.0.0:9184".to_string();
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_clock_sync_settings_validated() {
+        let mut config = Config::default();
+        assert!(config.clock.sync_enabled);
+
+        config.clock.sync_samples = 0;
+        assert!(config.validate().is_err());
+
+        config.clock.sync_enabled = false;
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_candle_bars_parse_from_toml_and_validate() {
+        let toml_str = r#"
+            symbols = ["BTCUSDT"]
+            refresh_rate_ms = 100
+            orderbook_depth = 20
+            enable_sparkline = true
+            log_level = "info"
+
+            [log]
+            file_path = "logs/xtrade.log"
+
+            [binance]
+            ws_url = "wss://stream.binance.com:9443"
+            rest_url = "https://api.binance.com"
+            timeout_seconds = 10
+            reconnect_interval_ms = 1000
+            max_reconnect_attempts = 10
+
+            [ui]
+            enable_colors = true
+            update_rate_fps = 20
+            sparkline_points = 60
+            kline_refresh_secs = 60
+            history_file = ".xtrade_history"
+            history_size = 500
+
+            [candles]
+            bars = ["15s", "v10", "t100"]
+        "#;
+        let mut config: Config = toml::from_str(toml_str).unwrap();
+        assert_eq!(
+            config.candles.bars,
+            vec![
+                BarSpec::Time {
+                    interval_ms: 15_000
+                },
+                BarSpec::Volume { size: 10.0 },
+                BarSpec::Tick { trades: 100 },
+            ]
+        );
+        assert_eq!(config.candles.history, DEFAULT_BAR_HISTORY);
+        assert!(config.validate().is_ok());
+
+        config.candles.bars.push(BarSpec::Tick { trades: 100 });
+        assert!(config.validate().is_err());
+
+        let bad = toml_str.replace(r#""t100""#, r#""7x""#);
+        assert!(toml::from_str::<Config>(&bad).is_err());
+    }
+
+    #[test]
+    fn test_paper_settings_parse_and_validate() {
+        let toml_str = toml::to_string(&Config::default()).unwrap();
+        let config: Config = toml::from_str(&toml_str).unwrap();
+        assert_eq!(config.paper.starting_balances.get("USDT"), Some(&10_000.0));
+
+        assert_eq!(
+            parse_balances("usdt=2500, BTC=0.5").unwrap(),
+            BTreeMap::from([("BTC".to_string(), 0.5), ("USDT".to_string(), 2_500.0)])
+        );
+        assert!(parse_balances("USDT").is_err());
+
+        let mut config = Config::default();
+        config.paper.taker_fee_bps = -1.0;
+        assert!(config.validate().is_err());
+
+        let mut config = Config::default();
+        config
+            .paper
+            .starting_balances
+            .insert("BTC".to_string(), -0.1);
+        assert!(config.validate().is_err());
+        config.paper.enabled = false;
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_account_settings_validate_only_when_enabled() {
+        let mut config = Config::default();
+        assert!(!config.account.enabled);
+        config.account.keepalive_interval_secs = 3_600;
+        assert!(config.validate().is_ok());
+
+        config.account.enabled = true;
+        assert!(config.validate().is_err());
+
+        config.account.keepalive_interval_secs = 1_800;
+        config.account.recv_window_ms = 90_000;
+        assert!(config.validate().is_err());
+
+        config.account.recv_window_ms = 5_000;
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_portfolio_holdings_parse_and_validate() {
+        let toml_str = r#"
+            symbols = ["BTCUSDT"]
+            refresh_rate_ms = 100
+            orderbook_depth = 20
+            enable_sparkline = true
+            log_level = "info"
+
+            [log]
+            file_path = "logs/xtrade.log"
+
+            [binance]
+            ws_url = "wss://stream.binance.com:9443"
+            rest_url = "https://api.binance.com"
+            timeout_seconds = 10
+            reconnect_interval_ms = 1000
+            max_reconnect_attempts = 10
+
+            [ui]
+            enable_colors = true
+            update_rate_fps = 20
+            sparkline_points = 60
+            kline_refresh_secs = 60
+            history_file = ".xtrade_history"
+            history_size = 500
+
+            [portfolio]
+            drawdown_alert_pct = 15.0
+            holdings = [{ symbol = "BTCUSDT", quantity = 0.5, cost_basis = 42000.0 }]
+        "#;
+        let config: Config = toml::from_str(toml_str).unwrap();
+        assert_eq!(config.portfolio.holdings.len(), 1);
+        assert_eq!(config.portfolio.currency, "USDT");
+        assert!(config.validate().is_ok());
+
+        let holdings = parse_holdings("btc-usdt=0.5@42000, ETHUSDT=2@2500").unwrap();
+        assert_eq!(holdings[0].symbol, "BTCUSDT");
+        assert_eq!(holdings[1].cost_basis, 2_500.0);
+        assert!(parse_holdings("BTCUSDT=0.5").is_err());
+
+        let mut config = config;
+        config.portfolio.drawdown_alert_pct = Some(120.0);
+        assert!(config.validate().is_err());
+        config.portfolio.drawdown_alert_pct = None;
+        config.portfolio.holdings[0].quantity = 0.0;
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_orders_default_to_test_mode_with_a_positive_cap() {
+        let mut config = Config::default();
+        assert!(!config.orders.enabled);
+        assert!(config.orders.test_mode);
+
+        assert_eq!(config.orders.max_notional.get("USDT"), Some(&100.0));
+
+        config.orders.enabled = true;
+        config.orders.max_notional.insert("BTC".to_string(), 0.0);
+        assert!(config.validate().is_err());
+
+        config.orders.max_notional.insert("BTC".to_string(), 0.002);
+        assert!(config.validate().is_ok());
+
+        config.orders.max_notional.clear();
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_heatmap_settings_default_and_validate() {
+        let mut config = Config::default();
+        assert_eq!(config.ui.heatmap_settings(), HeatmapSettings::default());
+
+        config.ui.heatmap_samples = 0;
+        assert!(config.validate().is_err());
+
+        config.ui.heatmap_samples = 600;
+        config.ui.heatmap_interval_ms = 250;
+        assert!(config.validate().is_ok());
+        assert_eq!(config.ui.heatmap_settings().samples, 600);
+    }
 }
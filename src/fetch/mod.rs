@@ -0,0 +1,601 @@
+//! Historical market data downloads
+//!
+//! Backs the `xtrade fetch` subcommands: klines are paged out of the REST
+//! API in 1000-row requests, paced against the request weight Binance
+//! reports, and streamed into CSV, NDJSON or Parquet files that can be
+//! resumed after an interruption.
+
+#[cfg(feature = "parquet")]
+mod parquet;
+
+use std::fs::{File, OpenOptions};
+use std::io::{BufWriter, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use anyhow::{Context, Result, anyhow, bail};
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+use tracing::{info, warn};
+
+use crate::binance::rest::BinanceRestClient;
+use crate::binance::types::{Kline, RestApiError};
+use crate::cli::{FetchTarget, KlineFetchArgs};
+use crate::config::Config;
+
+/// Largest page the `klines` endpoint serves per request
+pub const MAX_KLINES_PER_REQUEST: u16 = 1000;
+
+/// Intervals accepted by the Binance `klines` endpoint
+pub const KLINE_INTERVALS: &[&str] = &[
+    "1s", "1m", "3m", "5m", "15m", "30m", "1h", "2h", "4h", "6h", "8h", "12h", "1d", "3d", "1w",
+    "1M",
+];
+
+/// Pause once the per-minute weight gets this close to Binance's 6000 limit
+pub const WEIGHT_SOFT_LIMIT_1M: u32 = 5000;
+
+/// Give up after this many consecutive 429 responses
+const MAX_RATE_LIMIT_RETRIES: u32 = 5;
+
+/// How far back from the end of an output file to look for the last row
+const RESUME_TAIL_BYTES: u64 = 64 * 1024;
+
+const CSV_HEADER: &str = "open_time,open,high,low,close,volume,close_time,quote_volume,trades,taker_buy_base_volume,taker_buy_quote_volume";
+
+/// Output file format for downloaded klines
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ExportFormat {
+    Csv,
+    Ndjson,
+    Parquet,
+}
+
+impl ExportFormat {
+    /// Infer the format from an output path's extension
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+        match extension.as_str() {
+            "csv" => Some(Self::Csv),
+            "ndjson" | "jsonl" => Some(Self::Ndjson),
+            "parquet" | "pq" => Some(Self::Parquet),
+            _ => None,
+        }
+    }
+}
+
+/// A fully resolved kline download
+#[derive(Debug, Clone)]
+pub struct KlineFetchRequest {
+    pub symbol: String,
+    pub interval: String,
+    /// Inclusive start of the range, in UTC milliseconds
+    pub start_ms: u64,
+    /// Exclusive end of the range, in UTC milliseconds
+    pub end_ms: u64,
+    pub out: PathBuf,
+    pub format: ExportFormat,
+    /// Append after the last row already in `out`
+    pub resume: bool,
+    /// Replace `out` if it already exists
+    pub force: bool,
+}
+
+impl KlineFetchRequest {
+    /// Resolve CLI arguments into a validated request, using `now_ms` as the default end
+    pub fn from_args(args: &KlineFetchArgs, now_ms: u64) -> Result<Self> {
+        let symbol = args.symbol.trim().to_uppercase();
+        if symbol.is_empty() {
+            bail!("--symbol must not be empty");
+        }
+        if !KLINE_INTERVALS.contains(&args.interval.as_str()) {
+            bail!(
+                "Unsupported interval '{}'; expected one of {}",
+                args.interval,
+                KLINE_INTERVALS.join(", ")
+            );
+        }
+
+        let start_ms = parse_time_arg(&args.from).context("Invalid --from")?;
+        let end_ms = match &args.to {
+            Some(to) => parse_time_arg(to).context("Invalid --to")?,
+            None => now_ms,
+        };
+        if start_ms >= end_ms {
+            bail!("--from must be earlier than --to");
+        }
+
+        let format = match args.format {
+            Some(format) => format,
+            None => ExportFormat::from_path(&args.out).ok_or_else(|| {
+                anyhow!(
+                    "Cannot infer the format of {}; pass --format csv, ndjson or parquet",
+                    args.out.display()
+                )
+            })?,
+        };
+
+        Ok(Self {
+            symbol,
+            interval: args.interval.clone(),
+            start_ms,
+            end_ms,
+            out: args.out.clone(),
+            format,
+            resume: args.resume,
+            force: args.force,
+        })
+    }
+}
+
+/// Running totals reported after every page and returned when a download finishes
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct KlineFetchSummary {
+    pub rows_written: usize,
+    pub pages: usize,
+    /// Open time the download resumed after, when continuing an earlier file
+    pub resumed_after_ms: Option<u64>,
+    pub last_open_time_ms: Option<u64>,
+}
+
+/// Parse a range bound: epoch milliseconds, `YYYY-MM-DD`, `YYYY-MM-DDTHH:MM[:SS]` or RFC 3339
+///
+/// Bounds without an offset are read as UTC.
+pub fn parse_time_arg(value: &str) -> Result<u64> {
+    let value = value.trim();
+    if !value.is_empty() && value.chars().all(|c| c.is_ascii_digit()) {
+        return value
+            .parse::<u64>()
+            .map_err(|e| anyhow!("Invalid timestamp '{}': {}", value, e));
+    }
+
+    let timestamp = if let Ok(date) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        date.and_hms_opt(0, 0, 0)
+            .map(|naive| naive.and_utc().timestamp_millis())
+    } else if let Ok(datetime) = DateTime::parse_from_rfc3339(value) {
+        Some(datetime.timestamp_millis())
+    } else if let Ok(naive) = NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S")
+        .or_else(|_| NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M"))
+    {
+        Some(naive.and_utc().timestamp_millis())
+    } else {
+        None
+    };
+
+    timestamp
+        .and_then(|ms| u64::try_from(ms).ok())
+        .ok_or_else(|| {
+            anyhow!(
+                "Unrecognised time '{}'; use YYYY-MM-DD, RFC 3339 or epoch milliseconds",
+                value
+            )
+        })
+}
+
+/// How long to wait before the next request given the weight used this minute
+pub fn weight_pause(used_weight_1m: Option<u32>, soft_limit: u32, now_ms: u64) -> Option<Duration> {
+    let used = used_weight_1m?;
+    if used < soft_limit {
+        return None;
+    }
+    // Binance resets the weight counter on minute boundaries.
+    let until_next_minute = 60_000 - now_ms % 60_000;
+    Some(Duration::from_millis(until_next_minute + 250))
+}
+
+/// Entry point for `xtrade fetch`
+pub async fn run(target: FetchTarget, config: &Config) -> Result<()> {
+    match target {
+        FetchTarget::Klines(args) => {
+            let request =
+                KlineFetchRequest::from_args(&args, Utc::now().timestamp_millis() as u64)?;
+            let client = BinanceRestClient::new(config.binance.rest_url.clone());
+            eprintln!(
+                "Fetching {} {} klines from {} to {} into {}",
+                request.symbol,
+                request.interval,
+                format_utc(request.start_ms),
+                format_utc(request.end_ms),
+                request.out.display()
+            );
+
+            let summary = fetch_klines(&client, &request, |progress| {
+                if let Some(last) = progress.last_open_time_ms {
+                    eprintln!(
+                        "  page {:>4}: {:>8} rows, up to {}",
+                        progress.pages,
+                        progress.rows_written,
+                        format_utc(last)
+                    );
+                }
+            })
+            .await?;
+
+            if let Some(resumed_after) = summary.resumed_after_ms {
+                println!(
+                    "Resumed after {}; wrote {} new rows to {}",
+                    format_utc(resumed_after),
+                    summary.rows_written,
+                    request.out.display()
+                );
+            } else {
+                println!(
+                    "Wrote {} rows to {}",
+                    summary.rows_written,
+                    request.out.display()
+                );
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Download every kline in the request's range, page by page, into its output file
+///
+/// `on_page` is called after each page has been written. Rows already on disk
+/// stay valid if the download is interrupted, so a later run with `resume`
+/// continues after the last complete row.
+pub async fn fetch_klines<F>(
+    client: &BinanceRestClient,
+    request: &KlineFetchRequest,
+    mut on_page: F,
+) -> Result<KlineFetchSummary>
+where
+    F: FnMut(&KlineFetchSummary),
+{
+    let (mut writer, resumed_after_ms) = KlineWriter::open(request)?;
+    let mut summary = KlineFetchSummary {
+        resumed_after_ms,
+        ..KlineFetchSummary::default()
+    };
+
+    let mut next_start_ms = match resumed_after_ms {
+        Some(last_open) => request.start_ms.max(last_open + 1),
+        None => request.start_ms,
+    };
+    let mut rate_limit_retries = 0;
+
+    while next_start_ms < request.end_ms {
+        let page = match client
+            .get_kline_page(
+                &request.symbol,
+                &request.interval,
+                Some(next_start_ms),
+                Some(request.end_ms - 1),
+                Some(MAX_KLINES_PER_REQUEST),
+            )
+            .await
+        {
+            Ok(page) => page,
+            Err(err) => {
+                if let Some(RestApiError::RateLimited { retry_after_secs }) =
+                    err.downcast_ref::<RestApiError>()
+                {
+                    if rate_limit_retries < MAX_RATE_LIMIT_RETRIES {
+                        rate_limit_retries += 1;
+                        warn!(
+                            "Rate limited while fetching {} klines; retrying in {}s ({}/{})",
+                            request.symbol,
+                            retry_after_secs,
+                            rate_limit_retries,
+                            MAX_RATE_LIMIT_RETRIES
+                        );
+                        tokio::time::sleep(Duration::from_secs(*retry_after_secs)).await;
+                        continue;
+                    }
+                }
+                writer.finish()?;
+                return Err(err.context(format!(
+                    "Kline download stopped after {} rows; rerun with --resume to continue",
+                    summary.rows_written
+                )));
+            }
+        };
+        rate_limit_retries = 0;
+
+        let full_page = page.klines.len() >= usize::from(MAX_KLINES_PER_REQUEST);
+        let rows: Vec<Kline> = page
+            .klines
+            .into_iter()
+            .filter(|kline| {
+                kline.open_time_ms >= next_start_ms && kline.open_time_ms < request.end_ms
+            })
+            .collect();
+        let Some(last) = rows.last().copied() else {
+            break;
+        };
+
+        writer.write(&rows)?;
+        summary.rows_written += rows.len();
+        summary.pages += 1;
+        summary.last_open_time_ms = Some(last.open_time_ms);
+        next_start_ms = last.open_time_ms + 1;
+        on_page(&summary);
+
+        if !full_page {
+            break;
+        }
+        if let Some(pause) = weight_pause(
+            page.used_weight_1m,
+            WEIGHT_SOFT_LIMIT_1M,
+            Utc::now().timestamp_millis() as u64,
+        ) {
+            info!(
+                "Used weight {:?} is near the limit; pausing {:?}",
+                page.used_weight_1m, pause
+            );
+            tokio::time::sleep(pause).await;
+        }
+    }
+
+    writer.finish()?;
+    info!(
+        "Fetched {} {} klines for {} in {} pages",
+        summary.rows_written, request.interval, request.symbol, summary.pages
+    );
+    Ok(summary)
+}
+
+/// Destination for downloaded rows
+enum KlineWriter {
+    Csv(BufWriter<File>),
+    Ndjson(BufWriter<File>),
+    #[cfg(feature = "parquet")]
+    Parquet(parquet::ParquetKlineWriter),
+}
+
+impl KlineWriter {
+    /// Open the output file, returning the open time of the last row kept when resuming
+    fn open(request: &KlineFetchRequest) -> Result<(Self, Option<u64>)> {
+        let exists = request
+            .out
+            .metadata()
+            .map(|meta| meta.len() > 0)
+            .unwrap_or(false);
+        if exists && !request.resume && !request.force {
+            bail!(
+                "{} already exists; pass --resume to continue it or --force to overwrite it",
+                request.out.display()
+            );
+        }
+        let resume = exists && request.resume;
+
+        if let Some(parent) = request.out.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+
+        match request.format {
+            ExportFormat::Csv | ExportFormat::Ndjson => {
+                let (file, last_open) = if resume {
+                    open_for_append(&request.out, request.format)?
+                } else {
+                    let file = File::create(&request.out)
+                        .with_context(|| format!("Failed to create {}", request.out.display()))?;
+                    (file, None)
+                };
+                let mut out = BufWriter::new(file);
+                let writer = if request.format == ExportFormat::Csv {
+                    if !resume {
+                        writeln!(out, "{}", CSV_HEADER)?;
+                    }
+                    KlineWriter::Csv(out)
+                } else {
+                    KlineWriter::Ndjson(out)
+                };
+                Ok((writer, last_open))
+            }
+            #[cfg(feature = "parquet")]
+            ExportFormat::Parquet => {
+                let writer = parquet::ParquetKlineWriter::open(&request.out, resume)?;
+                let last_open = writer.last_open_time_ms();
+                Ok((KlineWriter::Parquet(writer), last_open))
+            }
+            #[cfg(not(feature = "parquet"))]
+            ExportFormat::Parquet => bail!(
+                "Parquet output needs a build with the `parquet` feature (cargo build --features parquet)"
+            ),
+        }
+    }
+
+    fn write(&mut self, rows: &[Kline]) -> Result<()> {
+        match self {
+            KlineWriter::Csv(out) => {
+                for k in rows {
+                    writeln!(
+                        out,
+                        "{},{},{},{},{},{},{},{},{},{},{}",
+                        k.open_time_ms,
+                        k.open,
+                        k.high,
+                        k.low,
+                        k.close,
+                        k.volume,
+                        k.close_time_ms,
+                        k.quote_volume,
+                        k.trades,
+                        k.taker_buy_base_volume,
+                        k.taker_buy_quote_volume
+                    )?;
+                }
+                out.flush()?;
+            }
+            KlineWriter::Ndjson(out) => {
+                for kline in rows {
+                    serde_json::to_writer(&mut *out, kline)?;
+                    out.write_all(b"\n")?;
+                }
+                out.flush()?;
+            }
+            #[cfg(feature = "parquet")]
+            KlineWriter::Parquet(writer) => writer.extend(rows),
+        }
+        Ok(())
+    }
+
+    fn finish(self) -> Result<()> {
+        match self {
+            KlineWriter::Csv(mut out) | KlineWriter::Ndjson(mut out) => {
+                out.flush()?;
+                Ok(())
+            }
+            #[cfg(feature = "parquet")]
+            KlineWriter::Parquet(writer) => writer.finish(),
+        }
+    }
+}
+
+/// Open a line-based file for appending, dropping any partially written last line
+fn open_for_append(path: &Path, format: ExportFormat) -> Result<(File, Option<u64>)> {
+    let mut file = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open(path)
+        .with_context(|| format!("Failed to open {}", path.display()))?;
+    let len = file.metadata()?.len();
+
+    let tail_start = len.saturating_sub(RESUME_TAIL_BYTES);
+    file.seek(SeekFrom::Start(tail_start))?;
+    let mut tail = Vec::new();
+    file.read_to_end(&mut tail)?;
+
+    let complete_len = match tail.iter().rposition(|b| *b == b'\n') {
+        Some(pos) => pos + 1,
+        None if tail_start == 0 => 0,
+        None => bail!(
+            "Cannot find the last complete row of {}; the file does not look like a kline export",
+            path.display()
+        ),
+    };
+    let kept_len = tail_start + complete_len as u64;
+    if kept_len < len {
+        warn!(
+            "Dropping {} bytes of a partially written row at the end of {}",
+            len - kept_len,
+            path.display()
+        );
+        file.set_len(kept_len)?;
+    }
+    file.seek(SeekFrom::End(0))?;
+
+    let complete = String::from_utf8_lossy(&tail[..complete_len]);
+    let last_line = complete.lines().rev().find(|line| !line.trim().is_empty());
+    let last_open = match last_line {
+        Some(line) => last_open_time(line, format)
+            .with_context(|| format!("Failed to read the last row of {}", path.display()))?,
+        None => None,
+    };
+
+    if format == ExportFormat::Csv && kept_len == 0 {
+        writeln!(file, "{}", CSV_HEADER)?;
+    }
+
+    Ok((file, last_open))
+}
+
+/// Open time of a written row; `None` for the CSV header
+fn last_open_time(line: &str, format: ExportFormat) -> Result<Option<u64>> {
+    match format {
+        ExportFormat::Csv => {
+            if line.starts_with("open_time") {
+                return Ok(None);
+            }
+            let first = line.split(',').next().unwrap_or_default();
+            first
+                .trim()
+                .parse::<u64>()
+                .map(Some)
+                .map_err(|e| anyhow!("Invalid open_time '{}': {}", first, e))
+        }
+        ExportFormat::Ndjson => {
+            let kline: Kline = serde_json::from_str(line)?;
+            Ok(Some(kline.open_time_ms))
+        }
+        ExportFormat::Parquet => Err(anyhow!("Parquet files are not line based")),
+    }
+}
+
+fn format_utc(ms: u64) -> String {
+    DateTime::<Utc>::from_timestamp_millis(ms as i64)
+        .map(|dt| dt.format("%Y-%m-%d %H:%M:%S UTC").to_string())
+        .unwrap_or_else(|| ms.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn time_args_accept_dates_rfc3339_and_epoch_ms() {
+        assert_eq!(parse_time_arg("2024-01-01").unwrap(), 1_704_067_200_000);
+        assert_eq!(
+            parse_time_arg("2024-01-01T01:00:00Z").unwrap(),
+            1_704_070_800_000
+        );
+        assert_eq!(
+            parse_time_arg("2024-01-01T02:00:00+01:00").unwrap(),
+            1_704_070_800_000
+        );
+        assert_eq!(
+            parse_time_arg("2024-01-01T01:00").unwrap(),
+            1_704_070_800_000
+        );
+        assert_eq!(parse_time_arg("1704067200000").unwrap(), 1_704_067_200_000);
+        assert!(parse_time_arg("yesterday").is_err());
+    }
+
+    #[test]
+    fn format_is_inferred_from_extension() {
+        assert_eq!(
+            ExportFormat::from_path(Path::new("btc.CSV")),
+            Some(ExportFormat::Csv)
+        );
+        assert_eq!(
+            ExportFormat::from_path(Path::new("out/btc.jsonl")),
+            Some(ExportFormat::Ndjson)
+        );
+        assert_eq!(
+            ExportFormat::from_path(Path::new("btc.parquet")),
+            Some(ExportFormat::Parquet)
+        );
+        assert_eq!(ExportFormat::from_path(Path::new("btc")), None);
+    }
+
+    #[test]
+    fn weight_pause_waits_for_next_minute_near_the_limit() {
+        assert_eq!(weight_pause(Some(100), 5000, 30_000), None);
+        assert_eq!(weight_pause(None, 5000, 30_000), None);
+        assert_eq!(
+            weight_pause(Some(5200), 5000, 120_000 + 45_000),
+            Some(Duration::from_millis(15_250))
+        );
+    }
+
+    #[test]
+    fn resume_drops_partial_csv_row_and_reports_last_open_time() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("btc.csv");
+        std::fs::write(
+            &path,
+            format!(
+                "{}\n1000,1,1,1,1,1,1999,1,1,1,1\n2000,1,1,1,1,1,2999,1,1,1,1\n3000,1,1",
+                CSV_HEADER
+            ),
+        )
+        .unwrap();
+
+        let (_, last_open) = open_for_append(&path, ExportFormat::Csv).unwrap();
+        assert_eq!(last_open, Some(2000));
+        let contents = std::fs::read_to_string(&path).unwrap();
+        assert!(contents.ends_with("2999,1,1,1,1\n"));
+    }
+
+    #[test]
+    fn resume_of_header_only_csv_starts_from_scratch() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("btc.csv");
+        std::fs::write(&path, format!("{}\n", CSV_HEADER)).unwrap();
+
+        let (_, last_open) = open_for_append(&path, ExportFormat::Csv).unwrap();
+        assert_eq!(last_open, None);
+    }
+}
@@ -0,0 +1,186 @@
+//! Parquet output for kline downloads
+//!
+//! Parquet files cannot be appended to, so rows are buffered and written in
+//! one go when the download finishes. Resuming reads the existing file back
+//! and rewrites it with the new rows appended.
+
+use std::fs::File;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use anyhow::{Context, Result};
+use parquet::data_type::{DoubleType, Int64Type};
+use parquet::file::properties::WriterProperties;
+use parquet::file::reader::SerializedFileReader;
+use parquet::file::writer::SerializedFileWriter;
+use parquet::record::RowAccessor;
+use parquet::schema::parser::parse_message_type;
+
+use crate::binance::types::Kline;
+
+const KLINE_SCHEMA: &str = "
+message kline {
+    REQUIRED INT64 open_time (TIMESTAMP(MILLIS,true));
+    REQUIRED DOUBLE open;
+    REQUIRED DOUBLE high;
+    REQUIRED DOUBLE low;
+    REQUIRED DOUBLE close;
+    REQUIRED DOUBLE volume;
+    REQUIRED INT64 close_time (TIMESTAMP(MILLIS,true));
+    REQUIRED DOUBLE quote_volume;
+    REQUIRED INT64 trades;
+    REQUIRED DOUBLE taker_buy_base_volume;
+    REQUIRED DOUBLE taker_buy_quote_volume;
+}
+";
+
+/// Buffers klines and writes them as a single Parquet row group on finish
+pub(super) struct ParquetKlineWriter {
+    path: PathBuf,
+    rows: Vec<Kline>,
+}
+
+impl ParquetKlineWriter {
+    pub(super) fn open(path: &Path, resume: bool) -> Result<Self> {
+        let rows = if resume {
+            read_klines(path)?
+        } else {
+            Vec::new()
+        };
+        Ok(Self {
+            path: path.to_path_buf(),
+            rows,
+        })
+    }
+
+    pub(super) fn last_open_time_ms(&self) -> Option<u64> {
+        self.rows.last().map(|kline| kline.open_time_ms)
+    }
+
+    pub(super) fn extend(&mut self, rows: &[Kline]) {
+        self.rows.extend_from_slice(rows);
+    }
+
+    /// Write to a sibling temp file and rename it over the output
+    pub(super) fn finish(self) -> Result<()> {
+        let tmp_path = self.path.with_extension("parquet.partial");
+        write_klines(&tmp_path, &self.rows)?;
+        std::fs::rename(&tmp_path, &self.path)
+            .with_context(|| format!("Failed to move Parquet output to {}", self.path.display()))
+    }
+}
+
+fn write_klines(path: &Path, rows: &[Kline]) -> Result<()> {
+    let schema = Arc::new(parse_message_type(KLINE_SCHEMA)?);
+    let properties = Arc::new(WriterProperties::builder().build());
+    let file =
+        File::create(path).with_context(|| format!("Failed to create {}", path.display()))?;
+    let mut writer = SerializedFileWriter::new(file, schema, properties)?;
+
+    let mut row_group = writer.next_row_group()?;
+    let mut index = 0;
+    while let Some(mut column) = row_group.next_column()? {
+        match index {
+            0 | 6 | 8 => {
+                let values: Vec<i64> = rows
+                    .iter()
+                    .map(|k| match index {
+                        0 => k.open_time_ms as i64,
+                        6 => k.close_time_ms as i64,
+                        _ => k.trades as i64,
+                    })
+                    .collect();
+                column
+                    .typed::<Int64Type>()
+                    .write_batch(&values, None, None)?;
+            }
+            _ => {
+                let values: Vec<f64> = rows
+                    .iter()
+                    .map(|k| match index {
+                        1 => k.open,
+                        2 => k.high,
+                        3 => k.low,
+                        4 => k.close,
+                        5 => k.volume,
+                        7 => k.quote_volume,
+                        9 => k.taker_buy_base_volume,
+                        _ => k.taker_buy_quote_volume,
+                    })
+                    .collect();
+                column
+                    .typed::<DoubleType>()
+                    .write_batch(&values, None, None)?;
+            }
+        }
+        column.close()?;
+        index += 1;
+    }
+    row_group.close()?;
+    writer.close()?;
+    Ok(())
+}
+
+fn read_klines(path: &Path) -> Result<Vec<Kline>> {
+    let file = File::open(path).with_context(|| format!("Failed to open {}", path.display()))?;
+    let reader = SerializedFileReader::new(file)
+        .with_context(|| format!("{} is not a readable Parquet file", path.display()))?;
+
+    let mut rows = Vec::new();
+    for row in reader {
+        let row = row?;
+        rows.push(Kline {
+            open_time_ms: row.get_timestamp_millis(0)? as u64,
+            open: row.get_double(1)?,
+            high: row.get_double(2)?,
+            low: row.get_double(3)?,
+            close: row.get_double(4)?,
+            volume: row.get_double(5)?,
+            close_time_ms: row.get_timestamp_millis(6)? as u64,
+            quote_volume: row.get_double(7)?,
+            trades: row.get_long(8)? as u64,
+            taker_buy_base_volume: row.get_double(9)?,
+            taker_buy_quote_volume: row.get_double(10)?,
+        });
+    }
+    Ok(rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kline(open_time_ms: u64) -> Kline {
+        Kline {
+            open_time_ms,
+            open: 1.0,
+            high: 2.0,
+            low: 0.5,
+            close: 1.5,
+            volume: 10.0,
+            close_time_ms: open_time_ms + 999,
+            quote_volume: 15.0,
+            trades: 7,
+            taker_buy_base_volume: 4.0,
+            taker_buy_quote_volume: 6.0,
+        }
+    }
+
+    #[test]
+    fn parquet_round_trips_and_resumes() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("btc.parquet");
+
+        let mut writer = ParquetKlineWriter::open(&path, false).unwrap();
+        writer.extend(&[kline(1_000), kline(2_000)]);
+        writer.finish().unwrap();
+
+        let mut resumed = ParquetKlineWriter::open(&path, true).unwrap();
+        assert_eq!(resumed.last_open_time_ms(), Some(2_000));
+        resumed.extend(&[kline(3_000)]);
+        resumed.finish().unwrap();
+
+        let rows = read_klines(&path).unwrap();
+        assert_eq!(rows, vec![kline(1_000), kline(2_000), kline(3_000)]);
+    }
+}
@@ -3,14 +3,22 @@
 //! A high-performance cryptocurrency market data monitoring system
 //! built with Rust, focusing on real-time data processing and display.
 
+pub mod account;
 pub mod binance;
 pub mod cli;
 pub mod config;
+pub mod fetch;
 pub mod market_data;
 pub mod metrics;
 pub mod notify;
+pub mod paper;
+pub mod portfolio;
+pub mod query;
 pub mod session;
+pub mod storage;
+pub mod trading;
 pub mod ui;
+pub mod watch;
 
 use anyhow::{Context, Result};
 use std::collections::VecDeque;
@@ -1,10 +1,11 @@
 use xtrade::{
     AppResult,
     binance::demo,
-    cli::{Cli, Commands},
+    cli::{Cli, Commands, WatchArgs},
     config::Config,
-    init_logging,
+    fetch, init_logging, query,
     session::SessionManager,
+    watch::{self, WatchFormat},
 };
 
 #[tokio::main]
@@ -25,6 +26,16 @@ async fn main() -> AppResult<()> {
             Config::handle_command(&action)?;
             Ok(())
         }
+        Commands::Watch(args) => std::process::exit(watch::run(args, &config).await),
+        Commands::Interactive { simple: true } => {
+            let args = WatchArgs::for_symbols(config.symbols.clone(), WatchFormat::Table);
+            std::process::exit(watch::run(args, &config).await)
+        }
+        Commands::Query(command) => std::process::exit(query::run(command, &config).await),
+        Commands::Fetch { target } => {
+            fetch::run(target, &config).await?;
+            Ok(())
+        }
         Commands::Demo => demo::demo_websocket().await,
         _ => {
             let mut session_manager = SessionManager::new(&cli, config)?;
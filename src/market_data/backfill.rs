@@ -0,0 +1,238 @@
+//! REST backfill for trades and candles missed while the stream was down
+//!
+//! After a reconnect the subscription asks `aggTrades` for everything after
+//! the last trade it saw and `klines` from the open of its latest candle. The
+//! trade cursor records the newest exchange trade id handled so far, so
+//! backfilled prints and the live messages queued behind the reconnect never
+//! count the same fill twice.
+
+use anyhow::Result;
+use tracing::{debug, warn};
+
+use crate::binance::{BinanceRestClient, RestAggTrade};
+use crate::market_data::{DailyCandle, TradePrint};
+
+/// Aggregate trades requested per REST page
+pub const AGG_TRADES_PAGE_LIMIT: u16 = 1_000;
+
+/// Upper bound on pages per backfill so a long outage cannot stall the stream
+pub const MAX_BACKFILL_PAGES: usize = 10;
+
+const DAY_MS: u64 = 86_400_000;
+
+/// Position in the trade stream reached so far
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TradeCursor {
+    /// Newest exchange fill id handled
+    pub last_trade_id: Option<u64>,
+    /// Newest aggregate trade id handled, known on the `aggTrade` stream only
+    pub last_agg_id: Option<u64>,
+    pub last_time_ms: Option<u64>,
+}
+
+impl TradeCursor {
+    /// Whether a print carries fills newer than the cursor
+    pub fn is_new(&self, trade: &TradePrint) -> bool {
+        self.last_trade_id
+            .map_or(true, |last| trade.trade_id > last)
+    }
+
+    /// Whether every fill of an aggregate is newer than the cursor. On the raw
+    /// trade stream an aggregate can straddle the cursor; its fills cannot be
+    /// split, so it is dropped rather than counted twice.
+    pub fn is_new_aggregate(&self, first_trade_id: u64) -> bool {
+        self.last_trade_id
+            .map_or(true, |last| first_trade_id > last)
+    }
+
+    /// Move the cursor past a handled print
+    pub fn advance(&mut self, trade: &TradePrint, agg_id: Option<u64>) {
+        self.last_trade_id = Some(
+            self.last_trade_id
+                .map_or(trade.trade_id, |last| last.max(trade.trade_id)),
+        );
+        if let Some(agg_id) = agg_id {
+            self.last_agg_id = Some(self.last_agg_id.map_or(agg_id, |last| last.max(agg_id)));
+        }
+        self.last_time_ms = Some(
+            self.last_time_ms
+                .map_or(trade.time_ms, |last| last.max(trade.time_ms)),
+        );
+    }
+
+    /// Nothing seen yet, so there is no gap to fill
+    pub fn is_empty(&self) -> bool {
+        self.last_trade_id.is_none()
+    }
+
+    /// First REST request: continue from the aggregate id when known, else from the last trade time
+    fn first_query(&self) -> (Option<u64>, Option<u64>) {
+        match self.last_agg_id {
+            Some(agg_id) => (Some(agg_id + 1), None),
+            None => (None, self.last_time_ms),
+        }
+    }
+}
+
+/// Trades recovered over REST
+#[derive(Debug, Default)]
+pub struct TradeBackfill {
+    /// New prints, oldest first, already filtered against the cursor
+    pub trades: Vec<TradePrint>,
+    /// Aggregate id of the newest print returned
+    pub last_agg_id: Option<u64>,
+    /// The page limit was hit before reaching the live stream
+    pub truncated: bool,
+}
+
+/// Fetch aggregate trades newer than `cursor`
+pub async fn backfill_trades(
+    rest: &BinanceRestClient,
+    symbol: &str,
+    cursor: &TradeCursor,
+) -> Result<TradeBackfill> {
+    let mut backfill = TradeBackfill::default();
+    if cursor.is_empty() {
+        return Ok(backfill);
+    }
+
+    let (mut from_id, start_time) = cursor.first_query();
+    for page in 0..MAX_BACKFILL_PAGES {
+        let start_time = if page == 0 { start_time } else { None };
+        let rows = rest
+            .get_agg_trades(symbol, from_id, start_time, Some(AGG_TRADES_PAGE_LIMIT))
+            .await?;
+        let full_page = rows.len() >= AGG_TRADES_PAGE_LIMIT as usize;
+
+        backfill.trades.extend(new_prints(&rows, cursor, symbol));
+
+        let Some(last) = rows.last() else {
+            return Ok(backfill);
+        };
+        backfill.last_agg_id = Some(last.agg_trade_id);
+        from_id = Some(last.agg_trade_id + 1);
+
+        if !full_page {
+            return Ok(backfill);
+        }
+        debug!(
+            "Backfill page {} for {} was full; requesting more",
+            page + 1,
+            symbol
+        );
+    }
+
+    backfill.truncated = backfill.last_agg_id.is_some();
+    Ok(backfill)
+}
+
+/// Parse REST aggregates, keeping only those entirely after the cursor
+fn new_prints(rows: &[RestAggTrade], cursor: &TradeCursor, symbol: &str) -> Vec<TradePrint> {
+    rows.iter()
+        .filter(|row| cursor.is_new_aggregate(row.first_trade_id))
+        .filter_map(|row| match TradePrint::from_rest_agg_trade(row) {
+            Ok(trade) => Some(trade),
+            Err(e) => {
+                warn!("Skipping malformed aggregate trade for {}: {}", symbol, e);
+                None
+            }
+        })
+        .collect()
+}
+
+/// Fetch daily candles from the open of `last_open_time_ms` up to now
+pub async fn backfill_daily_candles(
+    rest: &BinanceRestClient,
+    symbol: &str,
+    last_open_time_ms: u64,
+    now_ms: u64,
+) -> Result<Vec<DailyCandle>> {
+    let days = now_ms.saturating_sub(last_open_time_ms) / DAY_MS;
+    let limit = (days + 2).min(1_000) as u16;
+    let mut candles = rest
+        .get_klines(symbol, "1d", Some(last_open_time_ms), Some(limit))
+        .await?;
+    mark_open_candles(&mut candles, now_ms);
+    Ok(candles)
+}
+
+/// REST klines include the candle still in progress; it must not be treated as closed
+pub(crate) fn mark_open_candles(candles: &mut [DailyCandle], now_ms: u64) {
+    for candle in candles {
+        candle.is_closed = candle.close_time_ms < now_ms;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::market_data::TakerSide;
+
+    fn print(trade_id: u64, time_ms: u64) -> TradePrint {
+        TradePrint {
+            trade_id,
+            ..TradePrint::single_fill(time_ms, 100.0, 1.0, TakerSide::Buy)
+        }
+    }
+
+    #[test]
+    fn cursor_drops_fills_already_handled() {
+        let mut cursor = TradeCursor::default();
+        assert!(cursor.is_empty());
+        assert!(cursor.is_new(&print(10, 1_000)));
+
+        cursor.advance(&print(10, 1_000), None);
+        assert!(!cursor.is_new(&print(10, 1_000)));
+        assert!(!cursor.is_new(&print(9, 900)));
+        assert!(cursor.is_new(&print(11, 1_100)));
+
+        // Out-of-order input never moves the cursor backwards
+        cursor.advance(&print(8, 800), None);
+        assert_eq!(cursor.last_trade_id, Some(10));
+        assert_eq!(cursor.last_time_ms, Some(1_000));
+    }
+
+    #[test]
+    fn partially_overlapping_aggregate_is_not_counted_twice() {
+        let row = |agg_trade_id: u64, first_trade_id: u64, last_trade_id: u64| RestAggTrade {
+            agg_trade_id,
+            price: "100.0".to_string(),
+            quantity: "1.0".to_string(),
+            first_trade_id,
+            last_trade_id,
+            trade_time: 1_000 + last_trade_id,
+            is_buyer_maker: false,
+        };
+        // Raw trade stream: the cursor holds a fill id but no aggregate id
+        let mut cursor = TradeCursor::default();
+        cursor.advance(&print(10, 1_010), None);
+
+        let rows = [row(1, 5, 7), row(2, 8, 12), row(3, 11, 13)];
+        let prints = new_prints(&rows, &cursor, "BTCUSDT");
+        assert_eq!(prints.len(), 1);
+        assert_eq!(prints[0].trade_id, 13);
+        assert_eq!(prints[0].fills, 3);
+        assert!(prints[0].backfilled);
+    }
+
+    #[test]
+    fn first_query_prefers_aggregate_ids() {
+        let mut cursor = TradeCursor::default();
+        cursor.advance(&print(10, 1_000), None);
+        assert_eq!(cursor.first_query(), (None, Some(1_000)));
+
+        cursor.advance(&print(12, 1_200), Some(55));
+        assert_eq!(cursor.first_query(), (Some(56), None));
+    }
+
+    #[test]
+    fn in_progress_candle_stays_open() {
+        let mut candles = vec![
+            DailyCandle::new(0, DAY_MS - 1, 1.0, 1.0, 1.0, 1.0, 1.0, true),
+            DailyCandle::new(DAY_MS, 2 * DAY_MS - 1, 1.0, 1.0, 1.0, 1.0, 1.0, true),
+        ];
+        mark_open_candles(&mut candles, DAY_MS + 5);
+        assert!(candles[0].is_closed);
+        assert!(!candles[1].is_closed);
+    }
+}
@@ -0,0 +1,221 @@
+//! Real-time best bid/offer from the book ticker stream
+//!
+//! The diff-depth book only moves every 100ms, while `@bookTicker` pushes
+//! every change to the top of book. Both streams share the order book update
+//! id, so when the local book sits at the same id as a book ticker their best
+//! levels must match exactly; a difference means the local book has drifted.
+
+use anyhow::{Result, anyhow};
+
+use crate::binance::types::{BookTickerMessage, OrderBook, OrderBookError};
+
+/// How long the local book may stay crossed against newer book tickers
+/// before it is treated as desynced rather than lagging
+pub const DEFAULT_CROSSED_TOLERANCE_MS: u64 = 3_000;
+
+/// Best bid and offer at one order book update
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BookTicker {
+    pub update_id: u64,
+    pub bid_price: f64,
+    pub bid_qty: f64,
+    pub ask_price: f64,
+    pub ask_qty: f64,
+}
+
+impl BookTicker {
+    pub fn from_message(message: &BookTickerMessage) -> Result<Self> {
+        let parse = |value: &str, field: &str| {
+            value
+                .parse::<f64>()
+                .map_err(|e| anyhow!("failed to parse {} value '{}': {}", field, value, e))
+        };
+        Ok(Self {
+            update_id: message.update_id,
+            bid_price: parse(&message.bid_price, "bid_price")?,
+            bid_qty: parse(&message.bid_qty, "bid_qty")?,
+            ask_price: parse(&message.ask_price, "ask_price")?,
+            ask_qty: parse(&message.ask_qty, "ask_qty")?,
+        })
+    }
+
+    pub fn mid(&self) -> f64 {
+        (self.bid_price + self.ask_price) / 2.0
+    }
+
+    pub fn spread(&self) -> f64 {
+        self.ask_price - self.bid_price
+    }
+
+    /// Spread in basis points of the mid price
+    pub fn spread_bps(&self) -> f64 {
+        let mid = self.mid();
+        if mid > 0.0 {
+            self.spread() / mid * 10_000.0
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Cross-checks the local order book against book tickers
+#[derive(Debug)]
+pub struct TopOfBookMonitor {
+    crossed_tolerance_ms: u64,
+    /// When the local book was first seen crossed against a newer book ticker
+    crossed_since_ms: Option<u64>,
+}
+
+impl Default for TopOfBookMonitor {
+    fn default() -> Self {
+        Self::new(DEFAULT_CROSSED_TOLERANCE_MS)
+    }
+}
+
+impl TopOfBookMonitor {
+    pub fn new(crossed_tolerance_ms: u64) -> Self {
+        Self {
+            crossed_tolerance_ms,
+            crossed_since_ms: None,
+        }
+    }
+
+    /// Compare the local book with a book ticker, returning an error when the book has drifted
+    pub fn check(
+        &mut self,
+        book: &OrderBook,
+        ticker: &BookTicker,
+        now_ms: u64,
+    ) -> Result<(), OrderBookError> {
+        let (Some(best_bid), Some(best_ask)) = (book.best_bid(), book.best_ask()) else {
+            // No snapshot yet
+            self.crossed_since_ms = None;
+            return Ok(());
+        };
+
+        if ticker.update_id == book.last_update_id {
+            self.crossed_since_ms = None;
+            if best_bid != ticker.bid_price || best_ask != ticker.ask_price {
+                return Err(OrderBookError::TopOfBookMismatch {
+                    update_id: ticker.update_id,
+                    detail: format!(
+                        "book {} / {} vs ticker {} / {}",
+                        best_bid, best_ask, ticker.bid_price, ticker.ask_price
+                    ),
+                });
+            }
+            return Ok(());
+        }
+
+        // A lagging book can briefly cross a newer ticker while prices move;
+        // only a cross that outlives the depth stream cadence is a desync
+        let crossed = ticker.update_id > book.last_update_id
+            && (best_bid >= ticker.ask_price || best_ask <= ticker.bid_price);
+        if !crossed {
+            self.crossed_since_ms = None;
+            return Ok(());
+        }
+
+        let since = *self.crossed_since_ms.get_or_insert(now_ms);
+        if now_ms.saturating_sub(since) >= self.crossed_tolerance_ms {
+            self.crossed_since_ms = None;
+            return Err(OrderBookError::TopOfBookMismatch {
+                update_id: ticker.update_id,
+                detail: format!(
+                    "book {} / {} crossed ticker {} / {} for {} ms",
+                    best_bid,
+                    best_ask,
+                    ticker.bid_price,
+                    ticker.ask_price,
+                    now_ms - since
+                ),
+            });
+        }
+        Ok(())
+    }
+
+    /// Forget any pending cross, e.g. after the book was resynced
+    pub fn reset(&mut self) {
+        self.crossed_since_ms = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn book(update_id: u64, bid: f64, ask: f64) -> OrderBook {
+        let mut book = OrderBook::from_levels("BTCUSDT", &[(bid, 1.0)], &[(ask, 1.0)]);
+        book.last_update_id = update_id;
+        book
+    }
+
+    fn ticker(update_id: u64, bid: f64, ask: f64) -> BookTicker {
+        BookTicker {
+            update_id,
+            bid_price: bid,
+            bid_qty: 2.0,
+            ask_price: ask,
+            ask_qty: 3.0,
+        }
+    }
+
+    #[test]
+    fn derives_mid_and_spread() {
+        let t = ticker(1, 99.99, 100.01);
+        assert!((t.mid() - 100.0).abs() < 1e-9);
+        assert!((t.spread() - 0.02).abs() < 1e-9);
+        assert!((t.spread_bps() - 2.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn same_update_id_must_match_exactly() {
+        let mut monitor = TopOfBookMonitor::default();
+        let local = book(10, 100.0, 101.0);
+
+        assert!(monitor.check(&local, &ticker(10, 100.0, 101.0), 0).is_ok());
+        // Newer book, older ticker: nothing to compare
+        assert!(monitor.check(&local, &ticker(9, 99.0, 100.5), 0).is_ok());
+
+        let err = monitor
+            .check(&local, &ticker(10, 100.5, 101.0), 0)
+            .unwrap_err();
+        assert!(err.requires_resync());
+    }
+
+    #[test]
+    fn persistent_cross_against_newer_tickers_is_a_desync() {
+        let mut monitor = TopOfBookMonitor::new(1_000);
+        let local = book(10, 100.0, 101.0);
+
+        // Price jumped above the stale ask; tolerated while the depth stream catches up
+        assert!(monitor.check(&local, &ticker(12, 101.5, 102.0), 0).is_ok());
+        assert!(
+            monitor
+                .check(&local, &ticker(13, 101.5, 102.0), 500)
+                .is_ok()
+        );
+        assert!(
+            monitor
+                .check(&local, &ticker(14, 101.5, 102.0), 1_000)
+                .is_err()
+        );
+
+        // Reporting restarts the tolerance window, and an uncrossed update clears it
+        assert!(
+            monitor
+                .check(&local, &ticker(15, 101.5, 102.0), 1_100)
+                .is_ok()
+        );
+        assert!(
+            monitor
+                .check(&local, &ticker(16, 100.2, 100.8), 1_200)
+                .is_ok()
+        );
+        assert!(
+            monitor
+                .check(&local, &ticker(17, 101.5, 102.0), 1_300)
+                .is_ok()
+        );
+    }
+}
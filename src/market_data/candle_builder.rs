@@ -0,0 +1,448 @@
+//! Local OHLCV bars aggregated from the trade stream
+//!
+//! Exchange klines stop at one minute and only come in fixed time intervals.
+//! The builder folds trades into bars of any length, including sub-minute
+//! time bars, volume bars and tick bars. Time bars are bucketed by exchange
+//! trade time, so they roll over on the same boundaries as Binance klines no
+//! matter how late a message arrives locally.
+
+use std::fmt;
+
+use anyhow::{Result, anyhow, bail};
+use serde::{Deserialize, Serialize};
+
+use crate::market_data::{TakerSide, TradePrint};
+
+/// Bars kept per series when no limit is configured
+pub const DEFAULT_BAR_HISTORY: usize = 120;
+
+const MAX_TIME_BAR_MS: u64 = 86_400_000;
+
+/// Smallest volume bar size; Binance quantities carry at most 8 decimals
+const MIN_VOLUME_BAR_SIZE: f64 = 1e-8;
+
+/// Volume bars one trade may close; the rest of a larger trade stays in the last one
+const MAX_VOLUME_BARS_PER_TRADE: usize = 100;
+
+/// How a bar decides it is complete
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum BarSpec {
+    /// Fixed wall-clock interval aligned to the Unix epoch (`1s`, `15s`, `5m`, `1h`)
+    Time { interval_ms: u64 },
+    /// Closes once this much base asset has traded (`v10`, `v0.5`)
+    Volume { size: f64 },
+    /// Closes after this many exchange fills (`t100`)
+    Tick { trades: u64 },
+}
+
+impl BarSpec {
+    /// Parse `<n>s|m|h` time bars, `v<size>` volume bars or `t<count>` tick bars
+    pub fn parse(raw: &str) -> Result<Self> {
+        let raw = raw.trim();
+        if let Some(size) = raw.strip_prefix('v') {
+            let size: f64 = size
+                .parse()
+                .map_err(|_| anyhow!("invalid volume bar size in '{}'", raw))?;
+            if !size.is_finite() || size <= 0.0 {
+                bail!("volume bar size must be positive, got '{}'", raw);
+            }
+            if size < MIN_VOLUME_BAR_SIZE {
+                bail!(
+                    "volume bar size must be at least {}, got '{}'",
+                    MIN_VOLUME_BAR_SIZE,
+                    raw
+                );
+            }
+            return Ok(BarSpec::Volume { size });
+        }
+        if let Some(count) = raw.strip_prefix('t') {
+            let trades: u64 = count
+                .parse()
+                .map_err(|_| anyhow!("invalid tick bar count in '{}'", raw))?;
+            if trades == 0 {
+                bail!("tick bar count must be positive, got '{}'", raw);
+            }
+            return Ok(BarSpec::Tick { trades });
+        }
+
+        let unit_ms = match raw.chars().last() {
+            Some('s') => 1_000,
+            Some('m') => 60_000,
+            Some('h') => 3_600_000,
+            _ => bail!("unknown bar '{}': expected e.g. 5s, 1m, v10 or t100", raw),
+        };
+        let count: u64 = raw[..raw.len() - 1]
+            .parse()
+            .map_err(|_| anyhow!("invalid bar interval '{}'", raw))?;
+        let interval_ms = count
+            .checked_mul(unit_ms)
+            .filter(|interval_ms| (1..=MAX_TIME_BAR_MS).contains(interval_ms))
+            .ok_or_else(|| anyhow!("bar interval must be between 1s and 24h, got '{}'", raw))?;
+        Ok(BarSpec::Time { interval_ms })
+    }
+
+    /// Short label in the same form `parse` accepts
+    pub fn label(&self) -> String {
+        match *self {
+            BarSpec::Time { interval_ms } => {
+                if interval_ms % 3_600_000 == 0 {
+                    format!("{}h", interval_ms / 3_600_000)
+                } else if interval_ms % 60_000 == 0 {
+                    format!("{}m", interval_ms / 60_000)
+                } else {
+                    format!("{}s", interval_ms / 1_000)
+                }
+            }
+            BarSpec::Volume { size } => format!("v{}", size),
+            BarSpec::Tick { trades } => format!("t{}", trades),
+        }
+    }
+
+    pub fn is_time_based(&self) -> bool {
+        matches!(self, BarSpec::Time { .. })
+    }
+}
+
+impl fmt::Display for BarSpec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.label())
+    }
+}
+
+impl TryFrom<String> for BarSpec {
+    type Error = anyhow::Error;
+
+    fn try_from(value: String) -> Result<Self> {
+        BarSpec::parse(&value)
+    }
+}
+
+impl From<BarSpec> for String {
+    fn from(value: BarSpec) -> Self {
+        value.label()
+    }
+}
+
+/// One OHLCV bar
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Bar {
+    /// Interval start for time bars, first trade time otherwise
+    pub open_time_ms: u64,
+    /// Last millisecond of the interval for time bars, latest trade time otherwise
+    pub close_time_ms: u64,
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+    pub volume: f64,
+    /// Volume where the taker bought
+    pub buy_volume: f64,
+    /// Exchange fills folded into the bar
+    pub trades: u64,
+    pub is_closed: bool,
+}
+
+impl Bar {
+    fn open_at(open_time_ms: u64, close_time_ms: u64, price: f64) -> Self {
+        Self {
+            open_time_ms,
+            close_time_ms,
+            open: price,
+            high: price,
+            low: price,
+            close: price,
+            volume: 0.0,
+            buy_volume: 0.0,
+            trades: 0,
+            is_closed: false,
+        }
+    }
+
+    fn fold(&mut self, price: f64, quantity: f64, side: TakerSide, fills: u64) {
+        self.high = self.high.max(price);
+        self.low = self.low.min(price);
+        self.close = price;
+        self.volume += quantity;
+        if side == TakerSide::Buy {
+            self.buy_volume += quantity;
+        }
+        self.trades += fills;
+    }
+}
+
+/// Aggregates trades into bars of one spec
+#[derive(Debug, Clone)]
+pub struct CandleBuilder {
+    spec: BarSpec,
+    current: Option<Bar>,
+    /// Open time of the newest closed time bar
+    last_closed_open_ms: Option<u64>,
+    /// Trades older than the open bar, dropped rather than rewriting closed bars
+    late_trades: u64,
+}
+
+impl CandleBuilder {
+    pub fn new(spec: BarSpec) -> Self {
+        Self {
+            spec,
+            current: None,
+            last_closed_open_ms: None,
+            late_trades: 0,
+        }
+    }
+
+    pub fn spec(&self) -> BarSpec {
+        self.spec
+    }
+
+    /// The bar still being built
+    pub fn current(&self) -> Option<&Bar> {
+        self.current.as_ref()
+    }
+
+    pub fn late_trades(&self) -> u64 {
+        self.late_trades
+    }
+
+    /// Fold a trade in, returning any bars it closed, oldest first
+    pub fn push(&mut self, trade: &TradePrint) -> Vec<Bar> {
+        match self.spec {
+            BarSpec::Time { interval_ms } => self.push_time(trade, interval_ms),
+            BarSpec::Volume { size } => self.push_volume(trade, size),
+            BarSpec::Tick { trades } => self.push_tick(trade, trades),
+        }
+    }
+
+    /// Close a time bar whose interval ended before `exchange_time_ms`
+    ///
+    /// Lets quiet markets roll over without waiting for the next trade.
+    /// Volume and tick bars only close on trades.
+    pub fn advance_to(&mut self, exchange_time_ms: u64) -> Option<Bar> {
+        if !self.spec.is_time_based() {
+            return None;
+        }
+        let current = self.current.as_ref()?;
+        if exchange_time_ms <= current.close_time_ms {
+            return None;
+        }
+        let mut closed = self.current.take()?;
+        closed.is_closed = true;
+        self.last_closed_open_ms = Some(closed.open_time_ms);
+        Some(closed)
+    }
+
+    fn push_time(&mut self, trade: &TradePrint, interval_ms: u64) -> Vec<Bar> {
+        let open_time_ms = trade.time_ms - trade.time_ms % interval_ms;
+        let mut closed = Vec::new();
+
+        if self
+            .last_closed_open_ms
+            .is_some_and(|last| open_time_ms <= last)
+        {
+            self.late_trades += 1;
+            return closed;
+        }
+        if let Some(current) = self.current.as_mut() {
+            if open_time_ms < current.open_time_ms {
+                self.late_trades += 1;
+                return closed;
+            }
+            if open_time_ms > current.open_time_ms {
+                let mut finished = self.current.take().expect("current bar checked above");
+                finished.is_closed = true;
+                self.last_closed_open_ms = Some(finished.open_time_ms);
+                closed.push(finished);
+            }
+        }
+
+        let bar = self.current.get_or_insert_with(|| {
+            Bar::open_at(open_time_ms, open_time_ms + interval_ms - 1, trade.price)
+        });
+        bar.fold(trade.price, trade.quantity, trade.side, trade.fills);
+        closed
+    }
+
+    fn push_volume(&mut self, trade: &TradePrint, size: f64) -> Vec<Bar> {
+        // A trade larger than the room left spills into as many bars as it fills,
+        // up to a cap; past it the last bar takes the whole remainder
+        let mut closed = Vec::new();
+        let mut remaining = trade.quantity;
+        let mut fills = trade.fills;
+        loop {
+            let bar = self
+                .current
+                .get_or_insert_with(|| Bar::open_at(trade.time_ms, trade.time_ms, trade.price));
+            let take = if closed.len() + 1 >= MAX_VOLUME_BARS_PER_TRADE {
+                remaining
+            } else {
+                remaining.min(size - bar.volume)
+            };
+            bar.fold(trade.price, take, trade.side, fills);
+            bar.close_time_ms = trade.time_ms;
+            // Fills are counted once, in the bar the trade starts in
+            fills = 0;
+            remaining -= take;
+
+            if bar.volume >= size - size * 1e-9 {
+                let mut finished = self.current.take().expect("bar inserted above");
+                finished.is_closed = true;
+                closed.push(finished);
+            }
+            if remaining <= size * 1e-9 {
+                return closed;
+            }
+        }
+    }
+
+    fn push_tick(&mut self, trade: &TradePrint, trades: u64) -> Vec<Bar> {
+        let bar = self
+            .current
+            .get_or_insert_with(|| Bar::open_at(trade.time_ms, trade.time_ms, trade.price));
+        bar.fold(trade.price, trade.quantity, trade.side, trade.fills);
+        bar.close_time_ms = trade.time_ms;
+
+        // An aggregate trade is indivisible, so it may overshoot the count
+        if bar.trades >= trades {
+            let mut finished = self.current.take().expect("bar inserted above");
+            finished.is_closed = true;
+            return vec![finished];
+        }
+        Vec::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn trade(time_ms: u64, price: f64, quantity: f64) -> TradePrint {
+        TradePrint::single_fill(time_ms, price, quantity, TakerSide::Buy)
+    }
+
+    #[test]
+    fn parses_and_labels_specs() {
+        assert_eq!(
+            BarSpec::parse("5s").unwrap(),
+            BarSpec::Time { interval_ms: 5_000 }
+        );
+        assert_eq!(
+            BarSpec::parse("15m").unwrap(),
+            BarSpec::Time {
+                interval_ms: 900_000
+            }
+        );
+        assert_eq!(
+            BarSpec::parse("v2.5").unwrap(),
+            BarSpec::Volume { size: 2.5 }
+        );
+        assert_eq!(
+            BarSpec::parse("t100").unwrap(),
+            BarSpec::Tick { trades: 100 }
+        );
+        assert_eq!(BarSpec::parse("60s").unwrap().label(), "1m");
+        assert_eq!(BarSpec::parse("v2.5").unwrap().label(), "v2.5");
+
+        for bad in [
+            "",
+            "0s",
+            "25h",
+            "5x",
+            "v0",
+            "v-1",
+            "v1e-9",
+            "t0",
+            "tx",
+            "99999999999999d",
+            "99999999999999999h",
+        ] {
+            assert!(BarSpec::parse(bad).is_err(), "{} should be rejected", bad);
+        }
+    }
+
+    #[test]
+    fn time_bars_roll_over_on_exchange_boundaries() {
+        let mut builder = CandleBuilder::new(BarSpec::Time { interval_ms: 5_000 });
+        assert!(builder.push(&trade(10_100, 100.0, 1.0)).is_empty());
+        assert!(builder.push(&trade(14_999, 102.0, 2.0)).is_empty());
+
+        let closed = builder.push(&trade(15_000, 101.0, 1.0));
+        assert_eq!(closed.len(), 1);
+        let bar = closed[0];
+        assert_eq!((bar.open_time_ms, bar.close_time_ms), (10_000, 14_999));
+        assert_eq!(
+            (bar.open, bar.high, bar.low, bar.close),
+            (100.0, 102.0, 100.0, 102.0)
+        );
+        assert_eq!(bar.volume, 3.0);
+        assert_eq!(bar.trades, 2);
+        assert!(bar.is_closed);
+
+        let current = builder.current().unwrap();
+        assert_eq!(current.open_time_ms, 15_000);
+        assert!(!current.is_closed);
+
+        // A trade from the closed interval does not reopen it
+        assert!(builder.push(&trade(14_000, 90.0, 1.0)).is_empty());
+        assert_eq!(builder.late_trades(), 1);
+        assert_eq!(builder.current().unwrap().low, 101.0);
+    }
+
+    #[test]
+    fn time_bars_close_on_exchange_clock_without_trades() {
+        let mut builder = CandleBuilder::new(BarSpec::Time { interval_ms: 1_000 });
+        builder.push(&trade(1_200, 100.0, 1.0));
+        assert!(builder.advance_to(1_999).is_none());
+        let closed = builder.advance_to(2_050).unwrap();
+        assert_eq!(closed.open_time_ms, 1_000);
+        assert!(closed.is_closed);
+        assert!(builder.current().is_none());
+
+        // A straggler from the closed second must not reopen it
+        assert!(builder.push(&trade(1_900, 100.0, 1.0)).is_empty());
+        assert!(builder.current().is_none());
+        assert_eq!(builder.late_trades(), 1);
+    }
+
+    #[test]
+    fn volume_bars_split_large_trades() {
+        let mut builder = CandleBuilder::new(BarSpec::Volume { size: 2.0 });
+        assert!(builder.push(&trade(1_000, 100.0, 1.5)).is_empty());
+
+        let closed = builder.push(&trade(2_000, 101.0, 5.0));
+        assert_eq!(closed.len(), 3);
+        assert!(closed.iter().all(|bar| (bar.volume - 2.0).abs() < 1e-9));
+        assert_eq!(closed[0].open, 100.0);
+        assert_eq!(closed[0].close, 101.0);
+        assert_eq!(closed[0].trades, 2);
+        assert_eq!(closed[1].trades, 0);
+
+        let current = builder.current().unwrap();
+        assert!((current.volume - 0.5).abs() < 1e-9);
+        assert_eq!(current.open_time_ms, 2_000);
+    }
+
+    #[test]
+    fn volume_bars_cap_bars_closed_by_one_trade() {
+        let mut builder = CandleBuilder::new(BarSpec::Volume { size: 1e-8 });
+        let closed = builder.push(&trade(1_000, 100.0, 1.0));
+        assert_eq!(closed.len(), MAX_VOLUME_BARS_PER_TRADE);
+        let last = closed.last().unwrap();
+        assert!(last.volume > 0.99);
+        let total: f64 = closed.iter().map(|bar| bar.volume).sum();
+        assert!((total - 1.0).abs() < 1e-9);
+        assert!(builder.current().is_none());
+    }
+
+    #[test]
+    fn tick_bars_close_after_fill_count() {
+        let mut builder = CandleBuilder::new(BarSpec::Tick { trades: 3 });
+        assert!(builder.push(&trade(1, 100.0, 1.0)).is_empty());
+        assert!(builder.push(&trade(2, 99.0, 1.0)).is_empty());
+        let closed = builder.push(&trade(3, 101.0, 1.0));
+        assert_eq!(closed.len(), 1);
+        assert_eq!(closed[0].trades, 3);
+        assert_eq!((closed[0].open_time_ms, closed[0].close_time_ms), (1, 3));
+        assert!(builder.current().is_none());
+    }
+}
@@ -0,0 +1,279 @@
+//! Sampled history of the top of the order book for liquidity heatmaps
+//!
+//! Each sample keeps the best levels of one book state together with the last
+//! trade price at that moment. Samples are taken at most once per interval and
+//! held in a fixed-size ring, so the heatmap shows how resting size moved over
+//! the last few minutes rather than only the current book.
+
+use std::collections::VecDeque;
+
+use ordered_float::OrderedFloat;
+
+use crate::binance::types::OrderBook;
+
+/// Levels per side kept in each sample
+pub const DEFAULT_HEATMAP_LEVELS: usize = 20;
+
+/// Minimum time between samples
+pub const DEFAULT_HEATMAP_INTERVAL_MS: u64 = 500;
+
+/// Samples kept per symbol
+pub const DEFAULT_HEATMAP_SAMPLES: usize = 240;
+
+/// How often and how much of the book to sample
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HeatmapSettings {
+    pub levels: usize,
+    pub interval_ms: u64,
+    pub samples: usize,
+}
+
+impl Default for HeatmapSettings {
+    fn default() -> Self {
+        Self {
+            levels: DEFAULT_HEATMAP_LEVELS,
+            interval_ms: DEFAULT_HEATMAP_INTERVAL_MS,
+            samples: DEFAULT_HEATMAP_SAMPLES,
+        }
+    }
+}
+
+/// Top levels of one book state, best price first on each side
+#[derive(Debug, Clone, PartialEq)]
+pub struct BookSample {
+    pub time_ms: u64,
+    pub bids: Vec<(f64, f64)>,
+    pub asks: Vec<(f64, f64)>,
+    pub last_price: Option<f64>,
+}
+
+/// Resting size that fell into one price bucket of a heatmap column
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct HeatCell {
+    pub bid: f64,
+    pub ask: f64,
+}
+
+/// One sample bucketed into price rows, highest price first
+#[derive(Debug, Clone, PartialEq)]
+pub struct HeatmapColumn {
+    pub cells: Vec<HeatCell>,
+    /// Row holding the last trade price, if it falls inside the range
+    pub trade_row: Option<usize>,
+}
+
+/// Samples bucketed onto a price grid, oldest column first
+#[derive(Debug, Clone, PartialEq)]
+pub struct HeatmapGrid {
+    pub low: f64,
+    pub high: f64,
+    pub columns: Vec<HeatmapColumn>,
+    /// Largest bucket size in the grid, for scaling intensity
+    pub max_size: f64,
+}
+
+impl HeatmapGrid {
+    /// Price at the middle of a row
+    pub fn row_price(&self, row: usize, rows: usize) -> f64 {
+        let step = (self.high - self.low) / rows.max(1) as f64;
+        self.high - step * (row as f64 + 0.5)
+    }
+}
+
+/// Ring buffer of book samples for one symbol
+#[derive(Debug, Clone, Default)]
+pub struct LiquidityHistory {
+    samples: VecDeque<BookSample>,
+}
+
+impl LiquidityHistory {
+    /// Sample the book if the interval has passed since the last sample
+    pub fn record(
+        &mut self,
+        book: &OrderBook,
+        last_price: Option<f64>,
+        time_ms: u64,
+        settings: HeatmapSettings,
+    ) -> bool {
+        if let Some(latest) = self.samples.back() {
+            if time_ms < latest.time_ms.saturating_add(settings.interval_ms) {
+                return false;
+            }
+        }
+        if book.bids.is_empty() && book.asks.is_empty() {
+            return false;
+        }
+
+        let level = |(price, quantity): (&OrderedFloat<f64>, &f64)| (price.0, *quantity);
+        self.samples.push_back(BookSample {
+            time_ms,
+            bids: book
+                .bids
+                .iter()
+                .rev()
+                .take(settings.levels)
+                .map(level)
+                .collect(),
+            asks: book.asks.iter().take(settings.levels).map(level).collect(),
+            last_price: last_price.filter(|price| *price > 0.0),
+        });
+        while self.samples.len() > settings.samples.max(1) {
+            self.samples.pop_front();
+        }
+        true
+    }
+
+    pub fn samples(&self) -> &VecDeque<BookSample> {
+        &self.samples
+    }
+
+    pub fn len(&self) -> usize {
+        self.samples.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+
+    /// Bucket the newest `columns` samples into `rows` price rows spanning their levels
+    pub fn grid(&self, columns: usize, rows: usize) -> Option<HeatmapGrid> {
+        if columns == 0 || rows == 0 {
+            return None;
+        }
+        let start = self.samples.len().saturating_sub(columns);
+        let visible = self.samples.range(start..);
+
+        let (low, high) = visible
+            .clone()
+            .flat_map(|sample| {
+                let levels = sample
+                    .bids
+                    .iter()
+                    .chain(&sample.asks)
+                    .map(|(price, _)| *price);
+                levels.chain(sample.last_price)
+            })
+            .fold(None, |range: Option<(f64, f64)>, price| match range {
+                None => Some((price, price)),
+                Some((low, high)) => Some((low.min(price), high.max(price))),
+            })?;
+        // A flat range still needs some height to place prices in
+        let (low, high) = if high > low {
+            (low, high)
+        } else {
+            let pad = (low.abs() * 1e-4).max(f64::EPSILON);
+            (low - pad, high + pad)
+        };
+        let row_of = |price: f64| {
+            let offset = (high - price) / (high - low) * rows as f64;
+            (offset.max(0.0) as usize).min(rows - 1)
+        };
+
+        let mut max_size: f64 = 0.0;
+        let columns = visible
+            .map(|sample| {
+                let mut cells = vec![HeatCell::default(); rows];
+                for (price, quantity) in &sample.bids {
+                    let cell = &mut cells[row_of(*price)];
+                    cell.bid += quantity;
+                    max_size = max_size.max(cell.bid);
+                }
+                for (price, quantity) in &sample.asks {
+                    let cell = &mut cells[row_of(*price)];
+                    cell.ask += quantity;
+                    max_size = max_size.max(cell.ask);
+                }
+                HeatmapColumn {
+                    cells,
+                    trade_row: sample.last_price.map(row_of),
+                }
+            })
+            .collect();
+
+        Some(HeatmapGrid {
+            low,
+            high,
+            columns,
+            max_size,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn samples_respect_interval_levels_and_capacity() {
+        let settings = HeatmapSettings {
+            levels: 2,
+            interval_ms: 500,
+            samples: 3,
+        };
+        let book = OrderBook::from_levels(
+            "BTCUSDT",
+            &[(99.0, 1.0), (98.0, 2.0), (97.0, 3.0)],
+            &[(101.0, 1.0), (102.0, 2.0), (103.0, 3.0)],
+        );
+        let mut history = LiquidityHistory::default();
+
+        assert!(history.record(&book, Some(100.0), 1_000, settings));
+        assert!(!history.record(&book, Some(100.0), 1_200, settings));
+        for time_ms in [1_500, 2_000, 2_500] {
+            assert!(history.record(&book, Some(100.0), time_ms, settings));
+        }
+
+        assert_eq!(history.len(), 3);
+        let oldest = &history.samples()[0];
+        assert_eq!(oldest.time_ms, 1_500);
+        assert_eq!(oldest.bids, vec![(99.0, 1.0), (98.0, 2.0)]);
+        assert_eq!(oldest.asks, vec![(101.0, 1.0), (102.0, 2.0)]);
+    }
+
+    #[test]
+    fn grid_buckets_levels_by_price() {
+        let settings = HeatmapSettings {
+            levels: 5,
+            interval_ms: 1,
+            samples: 10,
+        };
+        let mut history = LiquidityHistory::default();
+        history.record(
+            &OrderBook::from_levels(
+                "BTCUSDT",
+                &[(99.0, 2.0), (96.0, 8.0)],
+                &[(101.0, 1.0), (104.0, 3.0)],
+            ),
+            Some(100.0),
+            0,
+            settings,
+        );
+        history.record(
+            &OrderBook::from_levels("BTCUSDT", &[(99.0, 4.0)], &[(101.0, 1.0)]),
+            Some(101.0),
+            10,
+            settings,
+        );
+
+        // 96 to 104 over 4 rows: [104, 102), [102, 100), [100, 98), [98, 96]
+        let grid = history.grid(10, 4).unwrap();
+        assert_eq!((grid.low, grid.high), (96.0, 104.0));
+        assert_eq!(grid.columns.len(), 2);
+        assert_eq!(grid.max_size, 8.0);
+
+        let first = &grid.columns[0];
+        assert_eq!(first.cells[0].ask, 3.0);
+        assert_eq!(first.cells[1].ask, 1.0);
+        assert_eq!(first.cells[2].bid, 2.0);
+        assert_eq!(first.cells[3].bid, 8.0);
+        assert_eq!(first.trade_row, Some(2));
+        assert_eq!(grid.columns[1].trade_row, Some(1));
+        assert_eq!(grid.row_price(0, 4), 103.0);
+
+        // Only the newest column when the view is one column wide
+        let narrow = history.grid(1, 4).unwrap();
+        assert_eq!(narrow.columns.len(), 1);
+        assert_eq!((narrow.low, narrow.high), (99.0, 101.0));
+        assert!(LiquidityHistory::default().grid(10, 4).is_none());
+    }
+}
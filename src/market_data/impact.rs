@@ -0,0 +1,201 @@
+//! Market impact of a hypothetical market order against the local book
+//!
+//! The order walks the opposite side level by level, the way a taker order
+//! would, using only the depth currently held in the book. Hidden liquidity,
+//! other traders and the exchange's own matching are not modelled.
+
+use ordered_float::OrderedFloat;
+
+use crate::binance::types::OrderBook;
+
+use super::trade_analytics::TakerSide;
+
+/// Remaining size at or below this fraction of the request counts as filled
+const FILL_TOLERANCE: f64 = 1e-9;
+
+/// Size of a hypothetical market order
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ImpactSize {
+    /// Quantity in the base asset
+    Base(f64),
+    /// Notional in the quote asset
+    Quote(f64),
+}
+
+impl ImpactSize {
+    pub fn amount(self) -> f64 {
+        match self {
+            ImpactSize::Base(amount) | ImpactSize::Quote(amount) => amount,
+        }
+    }
+}
+
+/// A market order to estimate on a symbol's live book
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImpactRequest {
+    pub symbol: String,
+    pub side: TakerSide,
+    pub size: ImpactSize,
+}
+
+/// Result of walking the book with a hypothetical market order
+#[derive(Debug, Clone, PartialEq)]
+pub struct MarketImpact {
+    pub side: TakerSide,
+    pub size: ImpactSize,
+    /// Base quantity the visible book can fill
+    pub filled_quantity: f64,
+    /// Quote notional paid or received for the filled quantity
+    pub filled_notional: f64,
+    pub average_price: Option<f64>,
+    /// Price of the last level touched
+    pub worst_price: Option<f64>,
+    pub mid_price: Option<f64>,
+    /// Levels touched, including a partially consumed last one
+    pub levels_consumed: usize,
+    /// Part of the request left over once the book ran out, in the request's unit
+    pub unfilled: f64,
+}
+
+impl MarketImpact {
+    /// Whether the visible book covers the whole order
+    pub fn sufficient_depth(&self) -> bool {
+        self.unfilled <= self.size.amount() * FILL_TOLERANCE
+    }
+
+    /// Cost of the average fill against the mid, in basis points; positive is adverse
+    pub fn slippage_bps(&self) -> Option<f64> {
+        self.bps_from_mid(self.average_price?)
+    }
+
+    /// Cost of the last level touched against the mid, in basis points
+    pub fn worst_slippage_bps(&self) -> Option<f64> {
+        self.bps_from_mid(self.worst_price?)
+    }
+
+    fn bps_from_mid(&self, price: f64) -> Option<f64> {
+        let mid = self.mid_price.filter(|mid| *mid > 0.0)?;
+        let adverse = match self.side {
+            TakerSide::Buy => price - mid,
+            TakerSide::Sell => mid - price,
+        };
+        Some(adverse / mid * 10_000.0)
+    }
+}
+
+impl OrderBook {
+    /// Walk the opposite side of the book as a market order of `size` would
+    pub fn market_impact(&self, side: TakerSide, size: ImpactSize) -> MarketImpact {
+        // Best price first on the side a taker order consumes
+        let levels: Box<dyn Iterator<Item = _> + '_> = match side {
+            TakerSide::Buy => Box::new(self.asks.iter()),
+            TakerSide::Sell => Box::new(self.bids.iter().rev()),
+        };
+
+        let target = size.amount().max(0.0);
+        let mut remaining = target;
+        let mut filled_quantity = 0.0;
+        let mut filled_notional = 0.0;
+        let mut worst_price = None;
+        let mut levels_consumed = 0;
+
+        for (&OrderedFloat(price), &available) in levels {
+            if remaining <= target * FILL_TOLERANCE {
+                break;
+            }
+            let take = match size {
+                ImpactSize::Base(_) => available.min(remaining),
+                ImpactSize::Quote(_) => available.min(remaining / price),
+            };
+            filled_quantity += take;
+            filled_notional += take * price;
+            remaining -= match size {
+                ImpactSize::Base(_) => take,
+                ImpactSize::Quote(_) => take * price,
+            };
+            worst_price = Some(price);
+            levels_consumed += 1;
+        }
+
+        MarketImpact {
+            side,
+            size,
+            filled_quantity,
+            filled_notional,
+            average_price: (filled_quantity > 0.0).then(|| filled_notional / filled_quantity),
+            worst_price,
+            mid_price: self.mid_price(),
+            levels_consumed,
+            unfilled: remaining.max(0.0),
+        }
+    }
+
+    /// Volume-weighted price to fill `quantity` base units, if the book is deep enough
+    pub fn average_fill_price(&self, side: TakerSide, quantity: f64) -> Option<f64> {
+        let impact = self.market_impact(side, ImpactSize::Base(quantity));
+        impact.average_price.filter(|_| impact.sufficient_depth())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn close(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-9
+    }
+
+    fn book() -> OrderBook {
+        OrderBook::from_levels(
+            "BTCUSDT",
+            &[(99.0, 1.0), (98.0, 2.0)],
+            &[(101.0, 1.0), (102.0, 2.0), (104.0, 5.0)],
+        )
+    }
+
+    #[test]
+    fn buy_walks_asks_and_measures_slippage_from_mid() {
+        let impact = book().market_impact(TakerSide::Buy, ImpactSize::Base(2.0));
+
+        assert!(close(impact.filled_quantity, 2.0));
+        assert!(close(impact.filled_notional, 203.0));
+        assert!(close(impact.average_price.unwrap(), 101.5));
+        assert_eq!(impact.worst_price, Some(102.0));
+        assert_eq!(impact.levels_consumed, 2);
+        assert!(impact.sufficient_depth());
+        // Mid is 100, so 101.5 is 150 bps adverse
+        assert!(close(impact.slippage_bps().unwrap(), 150.0));
+        assert!(close(impact.worst_slippage_bps().unwrap(), 200.0));
+    }
+
+    #[test]
+    fn sell_by_notional_reports_missing_depth() {
+        let impact = book().market_impact(TakerSide::Sell, ImpactSize::Quote(500.0));
+
+        // 99 + 196 is all the bid side holds
+        assert!(close(impact.filled_quantity, 3.0));
+        assert!(close(impact.filled_notional, 295.0));
+        assert!(close(impact.unfilled, 205.0));
+        assert!(!impact.sufficient_depth());
+        assert_eq!(impact.worst_price, Some(98.0));
+        assert!(impact.slippage_bps().unwrap() > 0.0);
+
+        assert!(book().average_fill_price(TakerSide::Sell, 4.0).is_none());
+        assert_eq!(book().average_fill_price(TakerSide::Sell, 1.0), Some(99.0));
+    }
+
+    #[test]
+    fn quote_size_fills_a_partial_level() {
+        let impact = book().market_impact(TakerSide::Buy, ImpactSize::Quote(152.0));
+
+        assert!(close(impact.filled_quantity, 1.5));
+        assert_eq!(impact.levels_consumed, 2);
+        assert!(impact.sufficient_depth());
+
+        let empty = OrderBook::new("BTCUSDT".to_string());
+        let impact = empty.market_impact(TakerSide::Buy, ImpactSize::Base(1.0));
+        assert!(impact.average_price.is_none());
+        assert!(impact.slippage_bps().is_none());
+        assert!(!impact.sufficient_depth());
+    }
+}
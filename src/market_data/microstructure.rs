@@ -0,0 +1,658 @@
+//! Order book microstructure analytics from successive depth updates
+//!
+//! Level figures (microprice, weighted mid, depth imbalance) describe the
+//! latest book. Flow figures need two books: order flow imbalance compares the
+//! best levels of consecutive books, and add/cancel rates come from the
+//! quantity changes a diff-depth update makes near the touch. Quantity that
+//! leaves the book is counted as cancelled once the taker volume seen on that
+//! side has been accounted for, so fills are not mistaken for cancels. The
+//! trade and depth streams are not synchronized, which makes that split an
+//! estimate.
+
+use std::collections::VecDeque;
+
+use ordered_float::OrderedFloat;
+
+use crate::binance::types::{OrderBook, OrderBookUpdate};
+
+use super::trade_analytics::{TakerSide, TradePrint};
+
+/// Levels per side used for depth, imbalance and the weighted mid
+pub const DEFAULT_MICROSTRUCTURE_LEVELS: usize = 10;
+
+/// Span of exchange time covered by order flow imbalance and add/cancel rates
+pub const DEFAULT_MICROSTRUCTURE_WINDOW_MS: u64 = 10_000;
+
+/// A large trade whose side has not recovered after this long counts as unrecovered
+const RESILIENCE_TIMEOUT_MS: u64 = 30_000;
+
+/// Share of the pre-trade depth that counts as recovered
+const RECOVERY_FRACTION: f64 = 0.9;
+
+/// Recovery times kept for the average
+const RESILIENCE_HISTORY: usize = 20;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BookSide {
+    Bid,
+    Ask,
+}
+
+impl BookSide {
+    /// Resting side a taker order consumes
+    fn consumed_by(side: TakerSide) -> Self {
+        match side {
+            TakerSide::Buy => BookSide::Ask,
+            TakerSide::Sell => BookSide::Bid,
+        }
+    }
+}
+
+/// Quantity change at one price level made by a depth update
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LevelChange {
+    pub side: BookSide,
+    pub price: f64,
+    pub previous: f64,
+    pub current: f64,
+}
+
+/// Changes an update will make to `book`; call before applying it
+pub fn level_changes(book: &OrderBook, update: &OrderBookUpdate) -> Vec<LevelChange> {
+    let sides = [
+        (BookSide::Bid, &update.bids, &book.bids),
+        (BookSide::Ask, &update.asks, &book.asks),
+    ];
+    sides
+        .into_iter()
+        .flat_map(|(side, levels, current)| {
+            levels.iter().filter_map(move |[price, quantity]| {
+                let price = price.parse::<f64>().ok()?;
+                let quantity = quantity.parse::<f64>().ok()?;
+                let previous = current.get(&OrderedFloat(price)).copied().unwrap_or(0.0);
+                (previous != quantity).then_some(LevelChange {
+                    side,
+                    price,
+                    previous,
+                    current: quantity,
+                })
+            })
+        })
+        .collect()
+}
+
+/// Recovery of the book after large trades
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ResilienceStats {
+    /// Trades at least as large as the best level they hit
+    pub large_trades: u64,
+    pub last_recovery_ms: Option<u64>,
+    /// Mean of the most recent recovery times
+    pub average_recovery_ms: Option<f64>,
+    /// Large trades whose side did not refill within the timeout
+    pub unrecovered: u64,
+    /// A large trade is waiting for its side to refill
+    pub recovering: bool,
+}
+
+/// Point-in-time microstructure figures for one symbol
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MicrostructureSnapshot {
+    pub levels: usize,
+    pub window_ms: u64,
+    pub mid: Option<f64>,
+    /// Best bid and ask weighted by the opposite side's size
+    pub microprice: Option<f64>,
+    /// Average of the bid and ask VWAPs over the top levels
+    pub weighted_mid: Option<f64>,
+    /// Bid minus ask depth over their sum for the top levels, from -1 to 1
+    pub depth_imbalance: Option<f64>,
+    /// Base quantity resting in the top levels
+    pub bid_depth: f64,
+    pub ask_depth: f64,
+    /// Order flow imbalance summed over the window, in the base asset
+    pub order_flow_imbalance: f64,
+    /// Quantity added and cancelled near the touch, base asset per second
+    pub bid_add_rate: f64,
+    pub bid_cancel_rate: f64,
+    pub ask_add_rate: f64,
+    pub ask_cancel_rate: f64,
+    pub resilience: ResilienceStats,
+}
+
+impl MicrostructureSnapshot {
+    /// Distance of the microprice from the mid in basis points; positive leans to the ask
+    pub fn microprice_offset_bps(&self) -> Option<f64> {
+        match (self.microprice, self.mid) {
+            (Some(micro), Some(mid)) if mid > 0.0 => Some((micro - mid) / mid * 10_000.0),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct TopOfBook {
+    bid: f64,
+    bid_qty: f64,
+    ask: f64,
+    ask_qty: f64,
+}
+
+impl TopOfBook {
+    fn of(book: &OrderBook) -> Option<Self> {
+        let (bid, bid_qty) = book.bids.iter().next_back()?;
+        let (ask, ask_qty) = book.asks.iter().next()?;
+        Some(Self {
+            bid: bid.0,
+            bid_qty: *bid_qty,
+            ask: ask.0,
+            ask_qty: *ask_qty,
+        })
+    }
+
+    fn spread(&self) -> f64 {
+        self.ask - self.bid
+    }
+
+    fn best_qty(&self, side: BookSide) -> f64 {
+        match side {
+            BookSide::Bid => self.bid_qty,
+            BookSide::Ask => self.ask_qty,
+        }
+    }
+
+    /// Order flow imbalance contribution of moving from `previous` to `self`
+    fn flow_since(&self, previous: &TopOfBook) -> f64 {
+        let mut flow = 0.0;
+        if self.bid >= previous.bid {
+            flow += self.bid_qty;
+        }
+        if self.bid <= previous.bid {
+            flow -= previous.bid_qty;
+        }
+        if self.ask <= previous.ask {
+            flow -= self.ask_qty;
+        }
+        if self.ask >= previous.ask {
+            flow += previous.ask_qty;
+        }
+        flow
+    }
+}
+
+/// Figures of the latest book over the top levels
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+struct LevelFigures {
+    bid_depth: f64,
+    ask_depth: f64,
+    bid_vwap: Option<f64>,
+    ask_vwap: Option<f64>,
+}
+
+impl LevelFigures {
+    fn of(book: &OrderBook, levels: usize) -> Self {
+        let side = |levels: &mut dyn Iterator<Item = (&OrderedFloat<f64>, &f64)>| {
+            let (depth, notional) = levels.fold((0.0, 0.0), |(depth, notional), (price, qty)| {
+                (depth + qty, notional + price.0 * qty)
+            });
+            (depth, (depth > 0.0).then(|| notional / depth))
+        };
+        let (bid_depth, bid_vwap) = side(&mut book.bids.iter().rev().take(levels));
+        let (ask_depth, ask_vwap) = side(&mut book.asks.iter().take(levels));
+        Self {
+            bid_depth,
+            ask_depth,
+            bid_vwap,
+            ask_vwap,
+        }
+    }
+
+    fn depth(&self, side: BookSide) -> f64 {
+        match side {
+            BookSide::Bid => self.bid_depth,
+            BookSide::Ask => self.ask_depth,
+        }
+    }
+}
+
+/// Quantity added and cancelled near the touch by one depth update
+#[derive(Debug, Clone, Copy, Default)]
+struct LevelFlow {
+    time_ms: u64,
+    bid_added: f64,
+    bid_cancelled: f64,
+    ask_added: f64,
+    ask_cancelled: f64,
+}
+
+/// A large trade waiting for its side of the book to refill
+#[derive(Debug, Clone, Copy)]
+struct PendingRecovery {
+    started_ms: u64,
+    side: BookSide,
+    depth_before: f64,
+    spread_before: f64,
+    /// A book after the trade showed the side thinned out
+    depleted: bool,
+}
+
+/// Accumulates depth updates and trades for one symbol
+#[derive(Debug)]
+pub struct BookMicrostructure {
+    levels: usize,
+    window_ms: u64,
+    top: Option<TopOfBook>,
+    figures: LevelFigures,
+    ofi_window: VecDeque<(u64, f64)>,
+    ofi_sum: f64,
+    flow_window: VecDeque<LevelFlow>,
+    flow_sum: LevelFlow,
+    first_update_ms: Option<u64>,
+    latest_ms: u64,
+    /// Taker volume per side not yet matched against removed quantity,
+    /// split into what arrived before and after the previous update
+    traded_carried: [f64; 2],
+    traded_fresh: [f64; 2],
+    pending_recovery: Option<PendingRecovery>,
+    recoveries: VecDeque<u64>,
+    resilience: ResilienceStats,
+}
+
+impl Default for BookMicrostructure {
+    fn default() -> Self {
+        Self::new(
+            DEFAULT_MICROSTRUCTURE_LEVELS,
+            DEFAULT_MICROSTRUCTURE_WINDOW_MS,
+        )
+    }
+}
+
+impl BookMicrostructure {
+    pub fn new(levels: usize, window_ms: u64) -> Self {
+        Self {
+            levels: levels.max(1),
+            window_ms: window_ms.max(1),
+            top: None,
+            figures: LevelFigures::default(),
+            ofi_window: VecDeque::new(),
+            ofi_sum: 0.0,
+            flow_window: VecDeque::new(),
+            flow_sum: LevelFlow::default(),
+            first_update_ms: None,
+            latest_ms: 0,
+            traded_carried: [0.0; 2],
+            traded_fresh: [0.0; 2],
+            pending_recovery: None,
+            recoveries: VecDeque::new(),
+            resilience: ResilienceStats::default(),
+        }
+    }
+
+    /// Fold in a book after a depth update; `changes` come from [`level_changes`]
+    pub fn on_book(&mut self, book: &OrderBook, changes: &[LevelChange], time_ms: u64) {
+        let Some(top) = TopOfBook::of(book) else {
+            return;
+        };
+        self.figures = LevelFigures::of(book, self.levels);
+        self.first_update_ms.get_or_insert(time_ms);
+        self.latest_ms = self.latest_ms.max(time_ms);
+
+        if let Some(previous) = self.top {
+            let flow = top.flow_since(&previous);
+            self.ofi_window.push_back((time_ms, flow));
+            self.ofi_sum += flow;
+        }
+        self.top = Some(top);
+
+        let flow = self.level_flow(book, changes, time_ms);
+        self.flow_sum.bid_added += flow.bid_added;
+        self.flow_sum.bid_cancelled += flow.bid_cancelled;
+        self.flow_sum.ask_added += flow.ask_added;
+        self.flow_sum.ask_cancelled += flow.ask_cancelled;
+        self.flow_window.push_back(flow);
+
+        self.check_recovery(&top, time_ms);
+        self.evict();
+    }
+
+    /// Track taker volume and watch for trades that clear the best level
+    pub fn on_trade(&mut self, trade: &TradePrint) {
+        if !trade.quantity.is_finite() || trade.quantity <= 0.0 {
+            return;
+        }
+        let side = BookSide::consumed_by(trade.side);
+        self.traded_fresh[side as usize] += trade.quantity;
+
+        let Some(top) = self.top else {
+            return;
+        };
+        if self.pending_recovery.is_none() && trade.quantity >= top.best_qty(side) {
+            self.resilience.large_trades += 1;
+            self.pending_recovery = Some(PendingRecovery {
+                started_ms: trade.time_ms,
+                side,
+                depth_before: self.figures.depth(side),
+                spread_before: top.spread(),
+                depleted: false,
+            });
+        }
+    }
+
+    /// Forget the previous book after a resync, so no flow spans the gap
+    pub fn reset_book(&mut self) {
+        self.top = None;
+        self.pending_recovery = None;
+        self.traded_carried = [0.0; 2];
+        self.traded_fresh = [0.0; 2];
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.top.is_none() && self.first_update_ms.is_none()
+    }
+
+    pub fn snapshot(&self) -> MicrostructureSnapshot {
+        let figures = self.figures;
+        let top = self.top;
+        let total_depth = figures.bid_depth + figures.ask_depth;
+        let rate = |quantity: f64| quantity / self.span_secs();
+
+        let mut resilience = self.resilience.clone();
+        resilience.recovering = self.pending_recovery.is_some();
+
+        MicrostructureSnapshot {
+            levels: self.levels,
+            window_ms: self.window_ms,
+            mid: top.map(|top| (top.bid + top.ask) / 2.0),
+            microprice: top
+                .filter(|top| top.bid_qty + top.ask_qty > 0.0)
+                .map(|top| {
+                    (top.bid * top.ask_qty + top.ask * top.bid_qty) / (top.bid_qty + top.ask_qty)
+                }),
+            weighted_mid: match (figures.bid_vwap, figures.ask_vwap) {
+                (Some(bid), Some(ask)) => Some((bid + ask) / 2.0),
+                _ => None,
+            },
+            depth_imbalance: (total_depth > 0.0)
+                .then(|| (figures.bid_depth - figures.ask_depth) / total_depth),
+            bid_depth: figures.bid_depth,
+            ask_depth: figures.ask_depth,
+            order_flow_imbalance: self.ofi_sum,
+            bid_add_rate: rate(self.flow_sum.bid_added),
+            bid_cancel_rate: rate(self.flow_sum.bid_cancelled),
+            ask_add_rate: rate(self.flow_sum.ask_added),
+            ask_cancel_rate: rate(self.flow_sum.ask_cancelled),
+            resilience,
+        }
+    }
+
+    /// Split the near-touch changes of one update into adds and cancels
+    fn level_flow(&mut self, book: &OrderBook, changes: &[LevelChange], time_ms: u64) -> LevelFlow {
+        // Only levels at or inside the last tracked level on each side count
+        let bid_floor = book
+            .bids
+            .keys()
+            .rev()
+            .take(self.levels)
+            .next_back()
+            .map(|price| price.0);
+        let ask_ceiling = book
+            .asks
+            .keys()
+            .take(self.levels)
+            .next_back()
+            .map(|price| price.0);
+
+        let mut added = [0.0; 2];
+        let mut removed = [0.0; 2];
+        for change in changes {
+            let near_touch = match change.side {
+                BookSide::Bid => bid_floor.map_or(true, |floor| change.price >= floor),
+                BookSide::Ask => ask_ceiling.map_or(true, |ceiling| change.price <= ceiling),
+            };
+            if !near_touch {
+                continue;
+            }
+            let delta = change.current - change.previous;
+            if delta > 0.0 {
+                added[change.side as usize] += delta;
+            } else {
+                removed[change.side as usize] -= delta;
+            }
+        }
+
+        let mut cancelled = [0.0; 2];
+        for side in [BookSide::Bid, BookSide::Ask] {
+            let idx = side as usize;
+            let from_carried = removed[idx].min(self.traded_carried[idx]);
+            let from_fresh = (removed[idx] - from_carried).min(self.traded_fresh[idx]);
+            cancelled[idx] = removed[idx] - from_carried - from_fresh;
+            // Trades may land just after the update that removed their quantity
+            self.traded_carried[idx] = self.traded_fresh[idx] - from_fresh;
+            self.traded_fresh[idx] = 0.0;
+        }
+
+        LevelFlow {
+            time_ms,
+            bid_added: added[BookSide::Bid as usize],
+            bid_cancelled: cancelled[BookSide::Bid as usize],
+            ask_added: added[BookSide::Ask as usize],
+            ask_cancelled: cancelled[BookSide::Ask as usize],
+        }
+    }
+
+    fn check_recovery(&mut self, top: &TopOfBook, time_ms: u64) {
+        let Some(pending) = self.pending_recovery.as_mut() else {
+            return;
+        };
+        let refilled = self.figures.depth(pending.side) >= pending.depth_before * RECOVERY_FRACTION
+            && top.spread() <= pending.spread_before * (1.0 + 1e-9);
+        let elapsed = time_ms.saturating_sub(pending.started_ms);
+
+        if !refilled {
+            pending.depleted = true;
+            if elapsed > RESILIENCE_TIMEOUT_MS {
+                self.resilience.unrecovered += 1;
+                self.pending_recovery = None;
+            }
+            return;
+        }
+        // A book that never thinned out had not caught up with the trade yet
+        if pending.depleted {
+            self.recoveries.push_back(elapsed);
+            if self.recoveries.len() > RESILIENCE_HISTORY {
+                self.recoveries.pop_front();
+            }
+            self.resilience.last_recovery_ms = Some(elapsed);
+            self.resilience.average_recovery_ms =
+                Some(self.recoveries.iter().sum::<u64>() as f64 / self.recoveries.len() as f64);
+            self.pending_recovery = None;
+        } else if elapsed > RESILIENCE_TIMEOUT_MS {
+            self.resilience.large_trades -= 1;
+            self.pending_recovery = None;
+        }
+    }
+
+    fn evict(&mut self) {
+        let cutoff = self.latest_ms.saturating_sub(self.window_ms);
+        while let Some((time_ms, flow)) = self.ofi_window.front().copied() {
+            if time_ms > cutoff {
+                break;
+            }
+            self.ofi_sum -= flow;
+            self.ofi_window.pop_front();
+        }
+        while let Some(oldest) = self.flow_window.front().copied() {
+            if oldest.time_ms > cutoff {
+                break;
+            }
+            self.flow_sum.bid_added -= oldest.bid_added;
+            self.flow_sum.bid_cancelled -= oldest.bid_cancelled;
+            self.flow_sum.ask_added -= oldest.ask_added;
+            self.flow_sum.ask_cancelled -= oldest.ask_cancelled;
+            self.flow_window.pop_front();
+        }
+
+        // Running sums drift slightly off zero from float error once emptied
+        if self.ofi_window.is_empty() {
+            self.ofi_sum = 0.0;
+        }
+        if self.flow_window.is_empty() {
+            self.flow_sum = LevelFlow::default();
+        }
+    }
+
+    /// Seconds covered by the window, or by the session until the window has filled
+    fn span_secs(&self) -> f64 {
+        let Some(first) = self.first_update_ms else {
+            return 1.0;
+        };
+        let span_ms = self
+            .latest_ms
+            .saturating_sub(first)
+            .clamp(1_000, self.window_ms.max(1_000));
+        span_ms as f64 / 1_000.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn close(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-9
+    }
+
+    fn update(bids: &[(&str, &str)], asks: &[(&str, &str)]) -> OrderBookUpdate {
+        let levels = |levels: &[(&str, &str)]| {
+            levels
+                .iter()
+                .map(|(price, qty)| [price.to_string(), qty.to_string()])
+                .collect()
+        };
+        OrderBookUpdate {
+            event_type: "depthUpdate".to_string(),
+            event_time: 0,
+            symbol: "BTCUSDT".to_string(),
+            first_update_id: 1,
+            final_update_id: 1,
+            bids: levels(bids),
+            asks: levels(asks),
+        }
+    }
+
+    fn trade(side: TakerSide, quantity: f64, time_ms: u64) -> TradePrint {
+        TradePrint::single_fill(time_ms, 100.0, quantity, side)
+    }
+
+    #[test]
+    fn level_figures_lean_toward_the_heavier_side() {
+        let mut micro = BookMicrostructure::new(2, 10_000);
+        let book = OrderBook::from_levels(
+            "BTCUSDT",
+            &[(99.0, 3.0), (98.0, 1.0)],
+            &[(101.0, 1.0), (102.0, 1.0)],
+        );
+        micro.on_book(&book, &[], 1_000);
+
+        let snapshot = micro.snapshot();
+        assert_eq!(snapshot.mid, Some(100.0));
+        // 99 * 1 + 101 * 3 over 4
+        assert!(close(snapshot.microprice.unwrap(), 100.5));
+        assert!(snapshot.microprice_offset_bps().unwrap() > 0.0);
+        // Bid VWAP 98.75, ask VWAP 101.5
+        assert!(close(snapshot.weighted_mid.unwrap(), 100.125));
+        assert!(close(snapshot.depth_imbalance.unwrap(), 2.0 / 6.0));
+    }
+
+    #[test]
+    fn order_flow_imbalance_follows_best_level_changes() {
+        let mut micro = BookMicrostructure::default();
+        micro.on_book(
+            &OrderBook::from_levels("BTCUSDT", &[(99.0, 1.0)], &[(101.0, 1.0)]),
+            &[],
+            1_000,
+        );
+        // Bid size grows at the same price: +2 of buying pressure
+        micro.on_book(
+            &OrderBook::from_levels("BTCUSDT", &[(99.0, 3.0)], &[(101.0, 1.0)]),
+            &[],
+            1_100,
+        );
+        assert!(close(micro.snapshot().order_flow_imbalance, 2.0));
+
+        // Ask steps down with 4 resting: -4
+        micro.on_book(
+            &OrderBook::from_levels("BTCUSDT", &[(99.0, 3.0)], &[(100.5, 4.0)]),
+            &[],
+            1_200,
+        );
+        assert!(close(micro.snapshot().order_flow_imbalance, -2.0));
+
+        // Both contributions leave the window
+        micro.on_book(
+            &OrderBook::from_levels("BTCUSDT", &[(99.0, 3.0)], &[(100.5, 4.0)]),
+            &[],
+            20_000,
+        );
+        assert!(close(micro.snapshot().order_flow_imbalance, 0.0));
+    }
+
+    #[test]
+    fn traded_quantity_is_not_counted_as_cancelled() {
+        let mut micro = BookMicrostructure::default();
+        let before = OrderBook::from_levels("BTCUSDT", &[(99.0, 2.0)], &[(101.0, 5.0)]);
+        micro.on_book(&before, &[], 0);
+
+        micro.on_trade(&trade(TakerSide::Buy, 1.0, 500));
+        let depth = update(&[("99.0", "4.0")], &[("101.0", "2.0")]);
+        let changes = level_changes(&before, &depth);
+        assert_eq!(changes.len(), 2);
+        let after = OrderBook::from_levels("BTCUSDT", &[(99.0, 4.0)], &[(101.0, 2.0)]);
+        micro.on_book(&after, &changes, 1_000);
+
+        let snapshot = micro.snapshot();
+        // 3 left the ask, 1 of it was bought
+        assert!(close(snapshot.ask_cancel_rate, 2.0));
+        assert!(close(snapshot.bid_add_rate, 2.0));
+        assert!(close(snapshot.ask_add_rate, 0.0));
+    }
+
+    #[test]
+    fn resilience_measures_refill_after_a_sweep() {
+        let mut micro = BookMicrostructure::default();
+        micro.on_book(
+            &OrderBook::from_levels("BTCUSDT", &[(99.0, 1.0)], &[(101.0, 2.0), (102.0, 2.0)]),
+            &[],
+            0,
+        );
+
+        micro.on_trade(&trade(TakerSide::Buy, 2.0, 100));
+        assert!(micro.snapshot().resilience.recovering);
+
+        // Book still shows the pre-trade state, then thins out, then refills
+        micro.on_book(
+            &OrderBook::from_levels("BTCUSDT", &[(99.0, 1.0)], &[(101.0, 2.0), (102.0, 2.0)]),
+            &[],
+            150,
+        );
+        micro.on_book(
+            &OrderBook::from_levels("BTCUSDT", &[(99.0, 1.0)], &[(102.0, 2.0)]),
+            &[],
+            200,
+        );
+        micro.on_book(
+            &OrderBook::from_levels("BTCUSDT", &[(99.0, 1.0)], &[(101.0, 3.0), (102.0, 2.0)]),
+            &[],
+            900,
+        );
+
+        let resilience = micro.snapshot().resilience;
+        assert_eq!(resilience.large_trades, 1);
+        assert_eq!(resilience.last_recovery_ms, Some(800));
+        assert!(!resilience.recovering);
+
+        // A small trade does not start a new measurement
+        micro.on_trade(&trade(TakerSide::Sell, 0.5, 1_000));
+        assert!(!micro.snapshot().resilience.recovering);
+    }
+}
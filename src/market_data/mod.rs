@@ -8,13 +8,47 @@ use tokio::sync::{Mutex, RwLock, mpsc};
 use tokio::task::JoinHandle;
 use tracing::{debug, error, info, warn};
 
-use crate::binance::BinanceRestClient;
-use crate::binance::types::{BinanceMessage, ConnectionStatus, OrderBook};
-
+use crate::binance::types::{
+    BinanceMessage, ConnectionStatus, MiniTicker, OrderBook, WebSocketError,
+};
+use crate::binance::websocket::ALL_MARKET_MINI_TICKER_STREAM;
+use crate::binance::{BinanceRestClient, BinanceWebSocket, ExchangeInfoCache, SymbolStatus};
+use crate::config::TradeStreamKind;
+use crate::metrics::{ClockEstimate, ClockSample, ClockSync};
+use crate::storage::HistoryHandle;
+
+mod backfill;
+mod book_ticker;
+mod candle_builder;
 mod daily_candle;
+mod heatmap;
+mod impact;
+mod microstructure;
+mod scanner;
 mod symbol_subscription;
+mod trade_analytics;
+pub use backfill::{
+    AGG_TRADES_PAGE_LIMIT, MAX_BACKFILL_PAGES, TradeBackfill, TradeCursor, backfill_daily_candles,
+    backfill_trades,
+};
+pub use book_ticker::{BookTicker, DEFAULT_CROSSED_TOLERANCE_MS, TopOfBookMonitor};
+pub use candle_builder::{Bar, BarSpec, CandleBuilder, DEFAULT_BAR_HISTORY};
 pub use daily_candle::{DEFAULT_DAILY_CANDLE_LIMIT, DailyCandle};
-pub use symbol_subscription::SymbolSubscription;
+pub use heatmap::{
+    BookSample, DEFAULT_HEATMAP_INTERVAL_MS, DEFAULT_HEATMAP_LEVELS, DEFAULT_HEATMAP_SAMPLES,
+    HeatCell, HeatmapColumn, HeatmapGrid, HeatmapSettings, LiquidityHistory,
+};
+pub use impact::{ImpactRequest, ImpactSize, MarketImpact};
+pub use microstructure::{
+    BookMicrostructure, BookSide, DEFAULT_MICROSTRUCTURE_LEVELS, DEFAULT_MICROSTRUCTURE_WINDOW_MS,
+    LevelChange, MicrostructureSnapshot, ResilienceStats, level_changes,
+};
+pub use scanner::{MarketScanner, ScannerFilter, ScannerRow, ScannerSort, ScannerTicker};
+pub use symbol_subscription::{SubscriptionOptions, SymbolSubscription};
+pub use trade_analytics::{
+    DEFAULT_TRADE_WINDOW_MS, LARGEST_TRADES, TakerSide, TradeAnalytics, TradeAnalyticsSnapshot,
+    TradePrint,
+};
 
 /// Subscription status for a symbol
 #[derive(Debug, Clone)]
@@ -55,6 +89,26 @@ pub enum MarketEvent {
         symbol: String,
         orderbook: OrderBook,
     },
+    /// Real-time best bid/offer from the book ticker stream
+    BookTicker {
+        symbol: String,
+        ticker: BookTicker,
+    },
+    /// Individual print; only published when trade events are enabled
+    Trade {
+        symbol: String,
+        trade: TradePrint,
+    },
+    /// Rolling trade flow figures, published at most every few hundred milliseconds
+    TradeAnalytics {
+        symbol: String,
+        analytics: TradeAnalyticsSnapshot,
+    },
+    /// Order book microstructure figures, published at most every few hundred milliseconds
+    Microstructure {
+        symbol: String,
+        snapshot: MicrostructureSnapshot,
+    },
     ConnectionStatus {
         symbol: String,
         status: ConnectionStatus,
@@ -68,6 +122,41 @@ pub enum MarketEvent {
         candles: Vec<DailyCandle>,
         is_snapshot: bool,
     },
+    /// Locally aggregated bar; sent when a bar closes and periodically while it builds
+    Bar {
+        symbol: String,
+        spec: BarSpec,
+        bar: Bar,
+    },
+    /// Stored bars of one spec loaded when the subscription starts
+    BarHistory {
+        symbol: String,
+        spec: BarSpec,
+        bars: Vec<Bar>,
+    },
+    /// Trades and candles recovered over REST for the gap left by a reconnect
+    Backfill {
+        symbol: String,
+        /// Prints missed by the stream, oldest first, all marked `backfilled`
+        trades: Vec<TradePrint>,
+        /// Daily candles touched by the gap
+        candles: Vec<DailyCandle>,
+        /// Not every missed trade could be recovered
+        truncated: bool,
+    },
+    SymbolStatusChanged {
+        symbol: String,
+        previous: SymbolStatus,
+        current: SymbolStatus,
+    },
+    /// Updated estimate of the exchange clock offset
+    ClockOffset {
+        estimate: ClockEstimate,
+    },
+    /// Batch of tickers from the all-market scanner stream
+    ScannerTickers {
+        tickers: Vec<ScannerTicker>,
+    },
 }
 
 /// Control message for managing subscription tasks
@@ -85,11 +174,27 @@ pub struct SubscriptionHandle {
     pub symbol: String,
 }
 
+/// Handle for the all-market scanner stream
+pub struct ScannerHandle {
+    pub task: JoinHandle<()>,
+    pub control_tx: mpsc::UnboundedSender<ControlMessage>,
+}
+
+impl ScannerHandle {
+    /// Ask the scanner task to close its connection and exit
+    pub fn stop(&self) {
+        let _ = self.control_tx.send(ControlMessage::Shutdown);
+    }
+}
+
 /// Market data manager for handling multiple symbol subscriptions
 pub struct MarketDataManager {
-    subscriptions: RwLock<HashMap<String, SubscriptionHandle>>,
+    subscriptions: Arc<RwLock<HashMap<String, SubscriptionHandle>>>,
     orderbooks: RwLock<HashMap<String, OrderBook>>,
-    _rest_client: BinanceRestClient,
+    book_tickers: RwLock<HashMap<String, BookTicker>>,
+    rest_client: BinanceRestClient,
+    exchange_info: ExchangeInfoCache,
+    subscription_options: SubscriptionOptions,
     event_tx: mpsc::UnboundedSender<MarketEvent>,
     event_rx: Arc<Mutex<mpsc::UnboundedReceiver<MarketEvent>>>,
 }
@@ -100,14 +205,43 @@ impl MarketDataManager {
         let (event_tx, event_rx) = mpsc::unbounded_channel();
 
         Self {
-            subscriptions: RwLock::new(HashMap::new()),
+            subscriptions: Arc::new(RwLock::new(HashMap::new())),
             orderbooks: RwLock::new(HashMap::new()),
-            _rest_client: BinanceRestClient::new("https://api.binance.com".to_string()),
+            book_tickers: RwLock::new(HashMap::new()),
+            rest_client: BinanceRestClient::new("https://api.binance.com".to_string()),
+            exchange_info: ExchangeInfoCache::new(),
+            subscription_options: SubscriptionOptions::default(),
             event_tx,
             event_rx: Arc::new(Mutex::new(event_rx)),
         }
     }
 
+    /// Use the given trade stream for new subscriptions
+    pub fn with_trade_stream(mut self, trade_stream: TradeStreamKind) -> Self {
+        self.subscription_options.trade_stream = trade_stream;
+        self
+    }
+
+    /// Aggregate local bars of these specs for new subscriptions,
+    /// reloading up to `bar_history` stored bars per spec on start
+    pub fn with_bar_specs(mut self, bar_specs: Vec<BarSpec>, bar_history: usize) -> Self {
+        self.subscription_options.bar_specs = bar_specs;
+        self.subscription_options.bar_history = bar_history;
+        self
+    }
+
+    /// Publish every trade as [`MarketEvent::Trade`] in addition to price updates
+    pub fn with_trade_events(mut self) -> Self {
+        self.subscription_options.trade_events = true;
+        self
+    }
+
+    /// Persist candles and trades, and preload charts from the store
+    pub fn with_history(mut self, history: HistoryHandle) -> Self {
+        self.subscription_options.history = Some(history);
+        self
+    }
+
     /// Subscribe to a symbol with concurrent WebSocket connection
     pub async fn subscribe(&self, symbol: String) -> Result<()> {
         // Acquire write lock briefly to validate and capture state
@@ -138,6 +272,12 @@ impl MarketDataManager {
             return Err(anyhow::anyhow!("Invalid symbol format: {}", symbol));
         }
 
+        // Reject unknown or non-trading symbols once exchange info is loaded
+        if let Err(e) = self.exchange_info.validate_symbol(&symbol) {
+            warn!("Rejecting subscription for {}: {}", symbol, e);
+            return Err(e);
+        }
+
         let should_delay = Self::is_subscribing_too_fast(&subscriptions);
         drop(subscriptions);
 
@@ -155,8 +295,13 @@ impl MarketDataManager {
         let event_tx = self.event_tx.clone();
 
         // Create symbol subscription outside of the lock to avoid blocking other readers
-        let mut symbol_subscription =
-            SymbolSubscription::new(symbol.clone(), control_rx, event_tx).await?;
+        let mut symbol_subscription = SymbolSubscription::new(
+            symbol.clone(),
+            self.subscription_options.clone(),
+            control_rx,
+            event_tx,
+        )
+        .await?;
 
         // Initialize the subscription (network calls)
         if let Err(e) = symbol_subscription.initialize().await {
@@ -196,6 +341,195 @@ impl MarketDataManager {
         Ok(())
     }
 
+    /// Shared exchange info cache with per-symbol trading rules
+    pub fn exchange_info(&self) -> ExchangeInfoCache {
+        self.exchange_info.clone()
+    }
+
+    /// Load exchange info now and refresh it periodically in the background.
+    ///
+    /// Status changes for subscribed symbols are published as
+    /// [`MarketEvent::SymbolStatusChanged`].
+    pub fn spawn_exchange_info_refresh(&self, interval: std::time::Duration) -> JoinHandle<()> {
+        let cache = self.exchange_info.clone();
+        let rest_client = self.rest_client.clone();
+        let subscriptions = self.subscriptions.clone();
+        let event_tx = self.event_tx.clone();
+
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+            loop {
+                ticker.tick().await;
+
+                let changes = match cache.refresh(&rest_client).await {
+                    Ok(changes) => changes,
+                    Err(e) => {
+                        warn!("Failed to refresh exchange info: {}", e);
+                        continue;
+                    }
+                };
+
+                if changes.is_empty() {
+                    continue;
+                }
+
+                let watched = subscriptions.read().await;
+                for change in changes {
+                    if !watched.contains_key(&change.symbol) {
+                        continue;
+                    }
+
+                    info!(
+                        "Symbol {} status changed: {} -> {}",
+                        change.symbol, change.previous, change.current
+                    );
+                    if event_tx
+                        .send(MarketEvent::SymbolStatusChanged {
+                            symbol: change.symbol,
+                            previous: change.previous,
+                            current: change.current,
+                        })
+                        .is_err()
+                    {
+                        debug!("Market event channel closed; stopping exchange info refresh");
+                        return;
+                    }
+                }
+            }
+        })
+    }
+
+    /// Periodically sample the exchange server time and publish offset estimates
+    ///
+    /// Each round takes `burst` back-to-back samples so the estimator has
+    /// enough round trips to discard slow ones.
+    pub fn spawn_clock_sync(&self, interval: std::time::Duration, burst: usize) -> JoinHandle<()> {
+        let rest_client = self.rest_client.clone();
+        let event_tx = self.event_tx.clone();
+
+        tokio::spawn(async move {
+            let mut sync = ClockSync::default();
+            let mut ticker = tokio::time::interval(interval);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+            loop {
+                ticker.tick().await;
+
+                let mut estimate = None;
+                for _ in 0..burst.max(1) {
+                    let send_ms = local_time_ms();
+                    match rest_client.get_server_time().await {
+                        Ok(server_ms) => {
+                            let receive_ms = local_time_ms();
+                            let sample = ClockSample::from_exchange(send_ms, server_ms, receive_ms);
+                            estimate = Some(sync.add_sample(sample, receive_ms));
+                        }
+                        Err(e) => {
+                            warn!("Failed to sample exchange server time: {}", e);
+                            break;
+                        }
+                    }
+                }
+
+                let Some(estimate) = estimate else {
+                    continue;
+                };
+                debug!(
+                    "Exchange clock offset {} ms (rtt {} ms, {} samples)",
+                    estimate.offset_ms, estimate.rtt_ms, estimate.samples
+                );
+                if event_tx
+                    .send(MarketEvent::ClockOffset { estimate })
+                    .is_err()
+                {
+                    debug!("Market event channel closed; stopping clock sync");
+                    return;
+                }
+            }
+        })
+    }
+
+    /// Stream mini tickers for every symbol on the exchange
+    ///
+    /// Each update is published as [`MarketEvent::ScannerTickers`]. The task
+    /// reconnects on connection errors and exits on
+    /// [`ControlMessage::Shutdown`].
+    pub fn spawn_scanner(&self) -> ScannerHandle {
+        let (control_tx, mut control_rx) = mpsc::unbounded_channel();
+        let event_tx = self.event_tx.clone();
+
+        let task = tokio::spawn(async move {
+            let url = format!(
+                "wss://stream.binance.com:9443/ws/{}",
+                ALL_MARKET_MINI_TICKER_STREAM
+            );
+            let (mut ws, mut message_rx) = BinanceWebSocket::new(url);
+
+            if let Err(e) = ws.connect().await {
+                warn!("Failed to connect scanner stream: {}", e);
+                if let Err(e) = ws.reconnect().await {
+                    error!("Scanner stream unavailable: {}", e);
+                    return;
+                }
+            }
+            if let Err(e) = ws.start_listening().await {
+                error!("Failed to start scanner listener: {}", e);
+                return;
+            }
+            info!("Scanner stream started");
+
+            loop {
+                tokio::select! {
+                    Some(control) = control_rx.recv() => {
+                        if matches!(control, ControlMessage::Shutdown) {
+                            break;
+                        }
+                    }
+                    Some(message) = message_rx.recv() => match message {
+                        Ok(message) if message.stream == ALL_MARKET_MINI_TICKER_STREAM => {
+                            let tickers = parse_scanner_tickers(message);
+                            if !tickers.is_empty()
+                                && event_tx.send(MarketEvent::ScannerTickers { tickers }).is_err()
+                            {
+                                debug!("Market event channel closed; stopping scanner");
+                                break;
+                            }
+                        }
+                        Ok(_) => {}
+                        Err(
+                            e @ (WebSocketError::ConnectionError(_)
+                            | WebSocketError::MessageError(_)),
+                        ) => {
+                            warn!("Scanner stream dropped: {}; reconnecting", e);
+                            if let Err(e) = ws.reconnect().await {
+                                error!("Scanner stream unavailable: {}", e);
+                                break;
+                            }
+                            if let Err(e) = ws.start_listening().await {
+                                error!("Failed to restart scanner listener: {}", e);
+                                break;
+                            }
+                        }
+                        Err(e) => debug!("Ignoring scanner stream error: {}", e),
+                    },
+                    else => break,
+                }
+            }
+
+            if let Err(e) = ws.shutdown().await {
+                debug!("Scanner listener shutdown failed: {}", e);
+            }
+            if let Err(e) = ws.disconnect().await {
+                debug!("Scanner disconnect failed: {}", e);
+            }
+            info!("Scanner stream stopped");
+        });
+
+        ScannerHandle { task, control_tx }
+    }
+
     /// Check if we're subscribing too fast (rate limiting)
     fn is_subscribing_too_fast(subscriptions: &HashMap<String, SubscriptionHandle>) -> bool {
         // Simple rate limiting: if we have more than 5 subscriptions, slow down
@@ -367,6 +701,12 @@ impl MarketDataManager {
         orderbooks.get(symbol).cloned()
     }
 
+    /// Latest best bid/offer for a symbol
+    pub async fn get_book_ticker(&self, symbol: &str) -> Option<BookTicker> {
+        let book_tickers = self.book_tickers.read().await;
+        book_tickers.get(symbol).copied()
+    }
+
     /// Recover subscription state after reconnection
     pub async fn recover_subscription(&self, symbol: &str) -> Result<()> {
         info!("Recovering subscription for symbol: {}", symbol);
@@ -481,9 +821,16 @@ impl MarketDataManager {
 
     /// Update internal state for a processed market event.
     pub async fn process_market_event(&self, event: &MarketEvent) {
-        if let MarketEvent::OrderBookUpdate { symbol, orderbook } = event {
-            let mut orderbooks = self.orderbooks.write().await;
-            orderbooks.insert(symbol.clone(), orderbook.clone());
+        match event {
+            MarketEvent::OrderBookUpdate { symbol, orderbook } => {
+                let mut orderbooks = self.orderbooks.write().await;
+                orderbooks.insert(symbol.clone(), orderbook.clone());
+            }
+            MarketEvent::BookTicker { symbol, ticker } => {
+                let mut book_tickers = self.book_tickers.write().await;
+                book_tickers.insert(symbol.clone(), *ticker);
+            }
+            _ => {}
         }
     }
 }
@@ -494,6 +841,25 @@ impl Default for MarketDataManager {
     }
 }
 
+/// Parse the ticker array of an all-market update, skipping malformed entries
+fn parse_scanner_tickers(message: BinanceMessage) -> Vec<ScannerTicker> {
+    let serde_json::Value::Array(items) = message.data else {
+        return Vec::new();
+    };
+    items
+        .into_iter()
+        .filter_map(|item| serde_json::from_value::<MiniTicker>(item).ok())
+        .filter_map(|ticker| ScannerTicker::from_mini_ticker(&ticker))
+        .collect()
+}
+
+fn local_time_ms() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
+
 /// Connection quality metrics
 #[derive(Debug)]
 pub struct ConnectionQuality {
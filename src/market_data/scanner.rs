@@ -0,0 +1,373 @@
+//! Market-wide scanner fed by the all-market ticker stream
+//!
+//! Every update from `!miniTicker@arr` carries the rolling 24h window for the
+//! symbols that changed in the last second. The scanner keeps the latest
+//! ticker per symbol plus a short history of closes, which is enough to rank
+//! the whole market by change, volume, range or short-term volatility.
+
+use std::collections::{HashMap, VecDeque};
+
+use crate::binance::ExchangeInfoCache;
+use crate::binance::types::MiniTicker;
+
+/// Close prices kept per symbol for the volatility column
+pub const VOLATILITY_SAMPLES: usize = 120;
+
+/// Parsed 24h ticker for one symbol
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScannerTicker {
+    pub symbol: String,
+    pub last_price: f64,
+    pub open_price: f64,
+    pub high_price: f64,
+    pub low_price: f64,
+    pub volume: f64,
+    pub quote_volume: f64,
+    pub event_time: u64,
+}
+
+impl ScannerTicker {
+    /// Parse the string fields of a mini ticker, `None` if any price is malformed
+    pub fn from_mini_ticker(ticker: &MiniTicker) -> Option<Self> {
+        Some(Self {
+            symbol: ticker.symbol.clone(),
+            last_price: ticker.close_price.parse().ok()?,
+            open_price: ticker.open_price.parse().ok()?,
+            high_price: ticker.high_price.parse().ok()?,
+            low_price: ticker.low_price.parse().ok()?,
+            volume: ticker.volume.parse().ok()?,
+            quote_volume: ticker.quote_volume.parse().ok()?,
+            event_time: ticker.event_time,
+        })
+    }
+}
+
+/// Column the scanner is ranked by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ScannerSort {
+    #[default]
+    Change,
+    Volume,
+    Range,
+    Volatility,
+}
+
+impl ScannerSort {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ScannerSort::Change => "24h change",
+            ScannerSort::Volume => "quote volume",
+            ScannerSort::Range => "24h range",
+            ScannerSort::Volatility => "volatility",
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        match self {
+            ScannerSort::Change => ScannerSort::Volume,
+            ScannerSort::Volume => ScannerSort::Range,
+            ScannerSort::Range => ScannerSort::Volatility,
+            ScannerSort::Volatility => ScannerSort::Change,
+        }
+    }
+}
+
+/// Which symbols the scanner shows
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ScannerFilter {
+    /// Only symbols quoted in this asset, e.g. `USDT`
+    pub quote_asset: Option<String>,
+    /// Minimum 24h volume in the quote asset
+    pub min_quote_volume: f64,
+}
+
+impl ScannerFilter {
+    /// Whether `ticker` passes; the quote asset comes from exchange info, so
+    /// symbols the cache does not know are hidden while a quote filter is set
+    pub fn matches(&self, ticker: &ScannerTicker, exchange_info: &ExchangeInfoCache) -> bool {
+        let quote_ok = self.quote_asset.as_deref().map_or(true, |quote| {
+            exchange_info
+                .get(&ticker.symbol)
+                .is_some_and(|info| info.quote_asset == quote)
+        });
+        quote_ok && ticker.quote_volume >= self.min_quote_volume
+    }
+}
+
+/// One ranked scanner row
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScannerRow {
+    pub ticker: ScannerTicker,
+    /// Percent change from the 24h open
+    pub change_percent: f64,
+    /// 24h high-low range as a percent of the low
+    pub range_percent: f64,
+    /// Realized volatility of recent updates in percent
+    pub volatility_percent: f64,
+}
+
+impl ScannerRow {
+    fn sort_key(&self, sort: ScannerSort) -> f64 {
+        match sort {
+            ScannerSort::Change => self.change_percent,
+            ScannerSort::Volume => self.ticker.quote_volume,
+            ScannerSort::Range => self.range_percent,
+            ScannerSort::Volatility => self.volatility_percent,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ScannerEntry {
+    ticker: ScannerTicker,
+    closes: VecDeque<f64>,
+}
+
+impl ScannerEntry {
+    fn row(&self) -> ScannerRow {
+        let ticker = &self.ticker;
+        ScannerRow {
+            ticker: ticker.clone(),
+            change_percent: percent_of(ticker.last_price - ticker.open_price, ticker.open_price),
+            range_percent: percent_of(ticker.high_price - ticker.low_price, ticker.low_price),
+            volatility_percent: realized_volatility(&self.closes),
+        }
+    }
+}
+
+/// Latest ticker state for every symbol on the exchange
+#[derive(Debug, Clone, Default)]
+pub struct MarketScanner {
+    entries: HashMap<String, ScannerEntry>,
+}
+
+impl MarketScanner {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Merge one all-market update; symbols missing from it keep their last ticker
+    pub fn apply(&mut self, tickers: &[ScannerTicker]) {
+        for ticker in tickers {
+            let entry = self
+                .entries
+                .entry(ticker.symbol.clone())
+                .or_insert_with(|| ScannerEntry {
+                    ticker: ticker.clone(),
+                    closes: VecDeque::with_capacity(VOLATILITY_SAMPLES),
+                });
+
+            if entry.closes.back() != Some(&ticker.last_price) {
+                if entry.closes.len() >= VOLATILITY_SAMPLES {
+                    entry.closes.pop_front();
+                }
+                entry.closes.push_back(ticker.last_price);
+            }
+            entry.ticker = ticker.clone();
+        }
+    }
+
+    /// Symbols passing `filter`, ranked by `sort`; ties are broken by symbol
+    pub fn ranked(
+        &self,
+        filter: &ScannerFilter,
+        exchange_info: &ExchangeInfoCache,
+        sort: ScannerSort,
+        descending: bool,
+    ) -> Vec<ScannerRow> {
+        let mut rows: Vec<ScannerRow> = self
+            .entries
+            .values()
+            .filter(|entry| filter.matches(&entry.ticker, exchange_info))
+            .map(ScannerEntry::row)
+            .collect();
+
+        rows.sort_by(|a, b| {
+            let ordering = a.sort_key(sort).total_cmp(&b.sort_key(sort));
+            let ordering = if descending {
+                ordering.reverse()
+            } else {
+                ordering
+            };
+            ordering.then_with(|| a.ticker.symbol.cmp(&b.ticker.symbol))
+        });
+        rows
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
+
+fn percent_of(delta: f64, base: f64) -> f64 {
+    if base > 0.0 {
+        delta / base * 100.0
+    } else {
+        0.0
+    }
+}
+
+/// Square root of the summed squared log returns, in percent
+fn realized_volatility(closes: &VecDeque<f64>) -> f64 {
+    let sum: f64 = closes
+        .iter()
+        .zip(closes.iter().skip(1))
+        .filter(|(prev, _)| **prev > 0.0)
+        .map(|(prev, next)| (next / prev).ln().powi(2))
+        .sum();
+    sum.sqrt() * 100.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::binance::{SymbolInfo, SymbolStatus};
+
+    fn ticker(symbol: &str, open: f64, last: f64, quote_volume: f64) -> ScannerTicker {
+        ScannerTicker {
+            symbol: symbol.to_string(),
+            last_price: last,
+            open_price: open,
+            high_price: open.max(last) * 1.1,
+            low_price: open.min(last),
+            volume: quote_volume / last,
+            quote_volume,
+            event_time: 0,
+        }
+    }
+
+    fn exchange_info(symbols: &[(&str, &str)]) -> ExchangeInfoCache {
+        let cache = ExchangeInfoCache::new();
+        cache.replace(
+            symbols
+                .iter()
+                .map(|(base, quote)| SymbolInfo {
+                    symbol: format!("{}{}", base, quote),
+                    base_asset: base.to_string(),
+                    quote_asset: quote.to_string(),
+                    status: SymbolStatus::Trading,
+                    tick_size: None,
+                    min_price: None,
+                    max_price: None,
+                    step_size: None,
+                    min_qty: None,
+                    max_qty: None,
+                    min_notional: None,
+                })
+                .collect(),
+        );
+        cache
+    }
+
+    #[test]
+    fn ranks_and_filters_symbols() {
+        let exchange_info = exchange_info(&[("BTC", "USDT"), ("ETH", "USDT"), ("ETH", "BTC")]);
+        let mut scanner = MarketScanner::new();
+        scanner.apply(&[
+            ticker("BTCUSDT", 100.0, 105.0, 50_000_000.0),
+            ticker("ETHUSDT", 100.0, 90.0, 20_000_000.0),
+            ticker("ETHBTC", 100.0, 120.0, 500.0),
+        ]);
+        assert_eq!(scanner.len(), 3);
+
+        let all = scanner.ranked(
+            &ScannerFilter::default(),
+            &exchange_info,
+            ScannerSort::Change,
+            true,
+        );
+        let symbols: Vec<&str> = all.iter().map(|r| r.ticker.symbol.as_str()).collect();
+        assert_eq!(symbols, ["ETHBTC", "BTCUSDT", "ETHUSDT"]);
+        assert!((all[1].change_percent - 5.0).abs() < 1e-9);
+
+        let filter = ScannerFilter {
+            quote_asset: Some("USDT".to_string()),
+            min_quote_volume: 30_000_000.0,
+        };
+        let rows = scanner.ranked(&filter, &exchange_info, ScannerSort::Volume, false);
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].ticker.symbol, "BTCUSDT");
+
+        let losers = scanner.ranked(
+            &ScannerFilter::default(),
+            &exchange_info,
+            ScannerSort::Change,
+            false,
+        );
+        assert_eq!(losers[0].ticker.symbol, "ETHUSDT");
+    }
+
+    #[test]
+    fn quote_filter_compares_exchange_info_quote_asset() {
+        let exchange_info = exchange_info(&[("BTC", "USDT"), ("ETH", "TUSD"), ("BTC", "USD")]);
+        let mut scanner = MarketScanner::new();
+        scanner.apply(&[
+            ticker("BTCUSDT", 100.0, 101.0, 1.0),
+            ticker("ETHTUSD", 100.0, 102.0, 1.0),
+            ticker("BTCUSD", 100.0, 103.0, 1.0),
+            // Not in exchange info yet, so its quote asset is unknown
+            ticker("NEWUSD", 100.0, 104.0, 1.0),
+        ]);
+
+        let filter = ScannerFilter {
+            quote_asset: Some("USD".to_string()),
+            min_quote_volume: 0.0,
+        };
+        let rows = scanner.ranked(&filter, &exchange_info, ScannerSort::Change, true);
+        let symbols: Vec<&str> = rows.iter().map(|r| r.ticker.symbol.as_str()).collect();
+        assert_eq!(symbols, ["BTCUSD"]);
+
+        let unfiltered = scanner.ranked(
+            &ScannerFilter::default(),
+            &exchange_info,
+            ScannerSort::Change,
+            true,
+        );
+        assert_eq!(unfiltered.len(), 4);
+    }
+
+    #[test]
+    fn volatility_tracks_price_updates() {
+        let exchange_info = ExchangeInfoCache::new();
+        let mut scanner = MarketScanner::new();
+        for last in [100.0, 101.0, 99.0, 102.0] {
+            scanner.apply(&[ticker("SOLUSDT", 100.0, last, 1.0)]);
+            // A repeated close carries no new information
+            scanner.apply(&[ticker("SOLUSDT", 100.0, last, 1.0)]);
+        }
+        scanner.apply(&[ticker("XRPUSDT", 1.0, 1.0, 1.0)]);
+
+        let rows = scanner.ranked(
+            &ScannerFilter::default(),
+            &exchange_info,
+            ScannerSort::Volatility,
+            true,
+        );
+        assert_eq!(rows[0].ticker.symbol, "SOLUSDT");
+        assert!(rows[0].volatility_percent > 3.0 && rows[0].volatility_percent < 5.0);
+        assert_eq!(rows[1].volatility_percent, 0.0);
+        assert!(rows[0].range_percent > 0.0);
+    }
+
+    #[test]
+    fn parses_mini_ticker_payload() {
+        let raw: MiniTicker = serde_json::from_value(serde_json::json!({
+            "e": "24hrMiniTicker", "E": 1_700_000_000_000u64, "s": "BNBUSDT",
+            "c": "600.5", "o": "590.0", "h": "610.0", "l": "585.0",
+            "v": "1000", "q": "600000"
+        }))
+        .unwrap();
+        let parsed = ScannerTicker::from_mini_ticker(&raw).unwrap();
+        assert_eq!(parsed.symbol, "BNBUSDT");
+        assert_eq!(parsed.last_price, 600.5);
+        assert_eq!(parsed.quote_volume, 600_000.0);
+    }
+}
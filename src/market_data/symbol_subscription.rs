@@ -4,13 +4,53 @@ use anyhow::{Result, anyhow};
 use tokio::sync::mpsc;
 use tracing::{debug, error, info, warn};
 
+use super::backfill::mark_open_candles;
 use super::{ControlMessage, MarketEvent};
 use crate::binance::types::{
-    BinanceMessage, ErrorSeverity, KlineStreamEvent, OrderBook, OrderBookError, Ticker24hr,
-    WebSocketError,
+    AggTradeMessage, BinanceMessage, BookTickerMessage, ErrorSeverity, KlineStreamEvent, OrderBook,
+    OrderBookError, Ticker24hr, TradeMessage, WebSocketError,
 };
 use crate::binance::{BinanceRestClient, BinanceWebSocket};
-use crate::market_data::{DEFAULT_DAILY_CANDLE_LIMIT, DailyCandle};
+use crate::config::TradeStreamKind;
+use crate::market_data::{
+    Bar, BarSpec, BookMicrostructure, BookTicker, CandleBuilder, DEFAULT_BAR_HISTORY,
+    DEFAULT_DAILY_CANDLE_LIMIT, DailyCandle, TopOfBookMonitor, TradeAnalytics, TradeCursor,
+    TradePrint, backfill_daily_candles, backfill_trades, level_changes,
+};
+use crate::metrics::exporter;
+use crate::storage::{self, DAILY_INTERVAL, HistoryHandle};
+
+/// Minimum interval between trade analytics events for one symbol
+const TRADE_ANALYTICS_EMIT_MS: u64 = 250;
+
+/// Minimum interval between microstructure events for one symbol
+const MICROSTRUCTURE_EMIT_MS: u64 = 250;
+
+/// Per-subscription settings shared by every symbol the manager subscribes
+#[derive(Debug, Clone)]
+pub struct SubscriptionOptions {
+    pub trade_stream: TradeStreamKind,
+    /// Local bars to aggregate from trades
+    pub bar_specs: Vec<BarSpec>,
+    /// Stored bars per spec to reload on start
+    pub bar_history: usize,
+    /// Store for candles and trades, when persistence is enabled
+    pub history: Option<HistoryHandle>,
+    /// Publish every print as [`MarketEvent::Trade`]
+    pub trade_events: bool,
+}
+
+impl Default for SubscriptionOptions {
+    fn default() -> Self {
+        Self {
+            trade_stream: TradeStreamKind::default(),
+            bar_specs: Vec::new(),
+            bar_history: DEFAULT_BAR_HISTORY,
+            history: None,
+            trade_events: false,
+        }
+    }
+}
 
 /// Symbol subscription manager for individual trading pairs
 pub struct SymbolSubscription {
@@ -18,6 +58,17 @@ pub struct SymbolSubscription {
     orderbook: OrderBook,
     daily_candles: Vec<DailyCandle>,
     daily_candle_limit: usize,
+    top_of_book: TopOfBookMonitor,
+    trade_stream: TradeStreamKind,
+    trade_analytics: TradeAnalytics,
+    trade_analytics_emitted_ms: u64,
+    microstructure: BookMicrostructure,
+    microstructure_emitted_ms: u64,
+    trade_cursor: TradeCursor,
+    bar_builders: Vec<CandleBuilder>,
+    bar_history: usize,
+    history: Option<HistoryHandle>,
+    trade_events: bool,
     control_rx: mpsc::UnboundedReceiver<ControlMessage>,
     event_tx: mpsc::UnboundedSender<MarketEvent>,
     ws: BinanceWebSocket,
@@ -29,6 +80,7 @@ impl SymbolSubscription {
     /// Create a new SymbolSubscription
     pub async fn new(
         symbol: String,
+        options: SubscriptionOptions,
         control_rx: mpsc::UnboundedReceiver<ControlMessage>,
         event_tx: mpsc::UnboundedSender<MarketEvent>,
     ) -> Result<Self> {
@@ -47,6 +99,22 @@ impl SymbolSubscription {
             orderbook,
             daily_candles: Vec::new(),
             daily_candle_limit: DEFAULT_DAILY_CANDLE_LIMIT,
+            top_of_book: TopOfBookMonitor::default(),
+            trade_stream: options.trade_stream,
+            trade_analytics: TradeAnalytics::default(),
+            trade_analytics_emitted_ms: 0,
+            microstructure: BookMicrostructure::default(),
+            microstructure_emitted_ms: 0,
+            trade_cursor: TradeCursor::default(),
+            bar_builders: options
+                .bar_specs
+                .iter()
+                .copied()
+                .map(CandleBuilder::new)
+                .collect(),
+            bar_history: options.bar_history,
+            history: options.history,
+            trade_events: options.trade_events,
             control_rx,
             event_tx,
             ws,
@@ -103,10 +171,25 @@ impl SymbolSubscription {
             return Err(e);
         }
 
-        // Subscribe to trade stream for latency/price metrics
-        if let Err(e) = self.ws.subscribe_trade(&self.symbol).await {
+        // Subscribe to trade stream for latency/price metrics and trade analytics
+        let subscribed = match self.trade_stream {
+            TradeStreamKind::Trade => self.ws.subscribe_trade(&self.symbol).await,
+            TradeStreamKind::AggTrade => self.ws.subscribe_agg_trade(&self.symbol).await,
+        };
+        if let Err(e) = subscribed {
             error!(
-                "Failed to subscribe to trade stream for {}: {}",
+                "Failed to subscribe to {} stream for {}: {}",
+                self.trade_stream.stream_name(),
+                self.symbol,
+                e
+            );
+            return Err(e);
+        }
+
+        // Subscribe to book ticker stream for real-time top of book
+        if let Err(e) = self.ws.subscribe_book_ticker(&self.symbol).await {
+            error!(
+                "Failed to subscribe to book ticker stream for {}: {}",
                 self.symbol, e
             );
             return Err(e);
@@ -145,6 +228,9 @@ impl SymbolSubscription {
             }
         }
 
+        self.load_stored_bars().await;
+        self.load_stored_trades().await;
+
         // Fetch initial snapshot
         match self.orderbook.fetch_snapshot(&self.rest_client).await {
             Ok(_) => {
@@ -185,10 +271,43 @@ impl SymbolSubscription {
 
     async fn load_initial_daily_candles(&mut self) -> Result<()> {
         let limit = self.daily_candle_limit as u16;
-        let candles = self
-            .rest_client
-            .get_daily_klines(&self.symbol, Some(limit))
-            .await?;
+        let now_ms = Self::now_ms();
+        let stored = self.load_stored_daily_candles(now_ms).await;
+
+        // With a local history only the candles since the newest stored one are downloaded
+        let mut candles = match stored.last() {
+            Some(last) => {
+                let mut fetched = self
+                    .rest_client
+                    .get_klines(&self.symbol, "1d", Some(last.open_time_ms), Some(limit))
+                    .await?;
+                mark_open_candles(&mut fetched, now_ms);
+                let mut merged: Vec<DailyCandle> = stored
+                    .into_iter()
+                    .filter(|candle| {
+                        fetched
+                            .first()
+                            .map_or(true, |first| candle.open_time_ms < first.open_time_ms)
+                    })
+                    .collect();
+                merged.extend(fetched);
+                merged
+            }
+            None => {
+                let mut fetched = self
+                    .rest_client
+                    .get_daily_klines(&self.symbol, Some(limit))
+                    .await?;
+                mark_open_candles(&mut fetched, now_ms);
+                fetched
+            }
+        };
+        if candles.len() > self.daily_candle_limit {
+            candles.drain(0..candles.len() - self.daily_candle_limit);
+        }
+        for candle in candles.iter().filter(|candle| candle.is_closed) {
+            self.record_daily_candle(candle);
+        }
 
         if candles.is_empty() {
             return Err(anyhow!("no candles returned for {}", self.symbol));
@@ -297,15 +416,41 @@ impl SymbolSubscription {
 
     /// Process WebSocket message and update orderbook
     async fn process_websocket_message(&mut self, binance_msg: BinanceMessage) {
+        self.record_message_metrics(&binance_msg.stream);
+
         match binance_msg.stream.as_str() {
+            // Checked first: "bookTicker" would otherwise fall through to other arms
+            stream if stream.ends_with("@bookTicker") => {
+                match serde_json::from_value::<BookTickerMessage>(binance_msg.data) {
+                    Ok(message) => match BookTicker::from_message(&message) {
+                        Ok(ticker) => self.handle_book_ticker(ticker).await,
+                        Err(e) => {
+                            warn!(
+                                "Failed to convert book ticker payload for {}: {}",
+                                self.symbol, e
+                            );
+                        }
+                    },
+                    Err(e) => {
+                        error!("Failed to parse book ticker for {}: {}", self.symbol, e);
+                    }
+                }
+            }
             stream if stream.contains("depth") => {
                 // Parse depth update
                 if let Ok(depth_update) = serde_json::from_value::<
                     crate::binance::types::OrderBookUpdate,
                 >(binance_msg.data)
                 {
+                    // Depth events tick every 100ms, keeping time bars rolling in quiet markets
+                    self.advance_bars(depth_update.event_time);
+                    let event_time = depth_update.event_time;
+                    let changes = level_changes(&self.orderbook, &depth_update);
                     match self.orderbook.apply_depth_update(depth_update) {
                         Ok(_) => {
+                            self.microstructure
+                                .on_book(&self.orderbook, &changes, event_time);
+                            self.emit_microstructure();
                             if let Err(e) = self.event_tx.send(MarketEvent::OrderBookUpdate {
                                 symbol: self.symbol.clone(),
                                 orderbook: self.orderbook.clone(),
@@ -322,25 +467,34 @@ impl SymbolSubscription {
                     }
                 }
             }
+            stream if stream.ends_with("@aggTrade") => {
+                match serde_json::from_value::<AggTradeMessage>(binance_msg.data) {
+                    Ok(message) => match TradePrint::from_agg_trade(&message) {
+                        Ok(trade) => {
+                            self.handle_trade(trade, Some(message.agg_trade_id), message.event_time)
+                        }
+                        Err(e) => {
+                            warn!(
+                                "Failed to convert aggregate trade for {}: {}",
+                                self.symbol, e
+                            );
+                        }
+                    },
+                    Err(e) => {
+                        error!("Failed to parse aggregate trade for {}: {}", self.symbol, e);
+                    }
+                }
+            }
             stream if stream.contains("trade") => {
-                // Parse trade message
-                if let Ok(trade_msg) =
-                    serde_json::from_value::<crate::binance::types::TradeMessage>(binance_msg.data)
-                {
-                    // Send price update
-                    if let Ok(price) = trade_msg.price.parse::<f64>() {
-                        if let Err(e) = self.event_tx.send(MarketEvent::PriceUpdate {
-                            symbol: self.symbol.clone(),
-                            price,
-                            time: trade_msg.event_time,
-                        }) {
-                            error!("Failed to send price update for {}: {}", self.symbol, e);
+                match serde_json::from_value::<TradeMessage>(binance_msg.data) {
+                    Ok(message) => match TradePrint::from_trade(&message) {
+                        Ok(trade) => self.handle_trade(trade, None, message.event_time),
+                        Err(e) => {
+                            warn!("Failed to convert trade for {}: {}", self.symbol, e);
                         }
-                    } else {
-                        error!(
-                            "Failed to parse price for {}: {}",
-                            self.symbol, trade_msg.price
-                        );
+                    },
+                    Err(e) => {
+                        error!("Failed to parse trade for {}: {}", self.symbol, e);
                     }
                 }
             }
@@ -390,6 +544,296 @@ impl SymbolSubscription {
         }
     }
 
+    /// Stored daily candles covering the preload window, oldest first
+    async fn load_stored_daily_candles(&self, now_ms: u64) -> Vec<DailyCandle> {
+        let Some(history) = &self.history else {
+            return Vec::new();
+        };
+        let since_ms = now_ms.saturating_sub(self.daily_candle_limit as u64 * 86_400_000);
+        match history
+            .load_candles(&self.symbol, DAILY_INTERVAL, since_ms)
+            .await
+        {
+            Ok(bars) => bars.iter().map(storage::bar_to_daily_candle).collect(),
+            Err(e) => {
+                warn!(
+                    "Failed to load stored daily candles for {}: {}",
+                    self.symbol, e
+                );
+                Vec::new()
+            }
+        }
+    }
+
+    /// Publish stored local bars so charts start with history
+    async fn load_stored_bars(&mut self) {
+        let Some(history) = &self.history else {
+            return;
+        };
+        let now_ms = Self::now_ms();
+        for builder in &self.bar_builders {
+            let spec = builder.spec();
+            // Volume and tick bars have no fixed length; look back a day
+            let lookback_ms = match spec {
+                BarSpec::Time { interval_ms } => {
+                    interval_ms.saturating_mul(self.bar_history as u64)
+                }
+                _ => 86_400_000,
+            };
+            let mut bars = match history
+                .load_candles(
+                    &self.symbol,
+                    &spec.label(),
+                    now_ms.saturating_sub(lookback_ms),
+                )
+                .await
+            {
+                Ok(bars) => bars,
+                Err(e) => {
+                    warn!(
+                        "Failed to load stored {} bars for {}: {}",
+                        spec, self.symbol, e
+                    );
+                    continue;
+                }
+            };
+            if bars.is_empty() {
+                continue;
+            }
+            if bars.len() > self.bar_history {
+                bars.drain(0..bars.len() - self.bar_history);
+            }
+            debug!(
+                "Loaded {} stored {} bars for {}",
+                bars.len(),
+                spec,
+                self.symbol
+            );
+            if let Err(e) = self.event_tx.send(MarketEvent::BarHistory {
+                symbol: self.symbol.clone(),
+                spec,
+                bars,
+            }) {
+                error!("Failed to send stored bars for {}: {}", self.symbol, e);
+            }
+        }
+    }
+
+    /// Seed the trade analytics with recorded prints from the last analytics window
+    async fn load_stored_trades(&mut self) {
+        let Some(history) = &self.history else {
+            return;
+        };
+        let window_ms = self.trade_analytics.snapshot().window_ms;
+        let since_ms = Self::now_ms().saturating_sub(window_ms);
+        let trades = match history.load_trades(&self.symbol, since_ms).await {
+            Ok(trades) => trades,
+            Err(e) => {
+                warn!("Failed to load stored trades for {}: {}", self.symbol, e);
+                return;
+            }
+        };
+        if trades.is_empty() {
+            return;
+        }
+        debug!(
+            "Seeding trade analytics for {} with {} stored trades",
+            self.symbol,
+            trades.len()
+        );
+        for trade in trades {
+            self.trade_analytics.record(trade);
+        }
+        self.emit_trade_analytics();
+    }
+
+    fn record_daily_candle(&self, candle: &DailyCandle) {
+        if let Some(history) = &self.history {
+            history.record_candle(
+                &self.symbol,
+                DAILY_INTERVAL,
+                &storage::daily_candle_to_bar(candle),
+            );
+        }
+    }
+
+    fn now_ms() -> u64 {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis() as u64
+    }
+
+    /// Publish the best bid/offer and cross-check it against the local book
+    async fn handle_book_ticker(&mut self, ticker: BookTicker) {
+        if let Err(e) = self.event_tx.send(MarketEvent::BookTicker {
+            symbol: self.symbol.clone(),
+            ticker,
+        }) {
+            error!("Failed to send book ticker for {}: {}", self.symbol, e);
+        }
+
+        let now_ms = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis() as u64;
+        if let Err(e) = self.top_of_book.check(&self.orderbook, &ticker, now_ms) {
+            self.handle_orderbook_error(e).await;
+        }
+    }
+
+    /// Publish the trade price and fold the print into the trade analytics
+    fn handle_trade(&mut self, trade: TradePrint, agg_id: Option<u64>, event_time: u64) {
+        // Live messages queued behind a reconnect may already have been backfilled
+        if !self.trade_cursor.is_new(&trade) {
+            debug!(
+                "Dropping trade {} for {}: already handled",
+                trade.trade_id, self.symbol
+            );
+            return;
+        }
+        self.trade_cursor.advance(&trade, agg_id);
+
+        if let Err(e) = self.event_tx.send(MarketEvent::PriceUpdate {
+            symbol: self.symbol.clone(),
+            price: trade.price,
+            time: event_time,
+        }) {
+            error!("Failed to send price update for {}: {}", self.symbol, e);
+        }
+        if self.trade_events {
+            if let Err(e) = self.event_tx.send(MarketEvent::Trade {
+                symbol: self.symbol.clone(),
+                trade,
+            }) {
+                error!("Failed to send trade for {}: {}", self.symbol, e);
+            }
+        }
+
+        self.trade_analytics.record(trade);
+        self.microstructure.on_trade(&trade);
+        self.feed_bars(&trade);
+        if let Some(history) = &self.history {
+            history.record_trade(&self.symbol, &trade);
+        }
+
+        let now_ms = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis() as u64;
+        if now_ms.saturating_sub(self.trade_analytics_emitted_ms) < TRADE_ANALYTICS_EMIT_MS {
+            return;
+        }
+        self.trade_analytics_emitted_ms = now_ms;
+        self.emit_trade_analytics();
+        self.emit_building_bars();
+    }
+
+    /// Fold a trade into every bar builder, publishing the bars it closes
+    fn feed_bars(&mut self, trade: &TradePrint) {
+        let mut closed = Vec::new();
+        for builder in &mut self.bar_builders {
+            let spec = builder.spec();
+            closed.extend(builder.push(trade).into_iter().map(|bar| (spec, bar)));
+        }
+        for (spec, bar) in closed {
+            self.record_bar(spec, &bar);
+            self.emit_bar(spec, bar);
+        }
+    }
+
+    fn record_bar(&self, spec: BarSpec, bar: &Bar) {
+        if let Some(history) = &self.history {
+            history.record_candle(&self.symbol, &spec.label(), bar);
+        }
+    }
+
+    /// Close time bars whose interval ended before the given exchange time
+    fn advance_bars(&mut self, exchange_time_ms: u64) {
+        let closed: Vec<(BarSpec, Bar)> = self
+            .bar_builders
+            .iter_mut()
+            .filter_map(|builder| {
+                builder
+                    .advance_to(exchange_time_ms)
+                    .map(|bar| (builder.spec(), bar))
+            })
+            .collect();
+        for (spec, bar) in closed {
+            self.record_bar(spec, &bar);
+            self.emit_bar(spec, bar);
+        }
+    }
+
+    /// Publish the bars still being built; throttled with the trade analytics
+    fn emit_building_bars(&self) {
+        for builder in &self.bar_builders {
+            if let Some(bar) = builder.current() {
+                self.emit_bar(builder.spec(), *bar);
+            }
+        }
+    }
+
+    fn emit_bar(&self, spec: BarSpec, bar: Bar) {
+        if let Err(e) = self.event_tx.send(MarketEvent::Bar {
+            symbol: self.symbol.clone(),
+            spec,
+            bar,
+        }) {
+            error!("Failed to send {} bar for {}: {}", spec, self.symbol, e);
+        }
+    }
+
+    /// Publish the microstructure figures, throttled to wall-clock time
+    fn emit_microstructure(&mut self) {
+        let now_ms = Self::now_ms();
+        if now_ms.saturating_sub(self.microstructure_emitted_ms) < MICROSTRUCTURE_EMIT_MS {
+            return;
+        }
+        self.microstructure_emitted_ms = now_ms;
+        if let Err(e) = self.event_tx.send(MarketEvent::Microstructure {
+            symbol: self.symbol.clone(),
+            snapshot: self.microstructure.snapshot(),
+        }) {
+            error!("Failed to send microstructure for {}: {}", self.symbol, e);
+        }
+    }
+
+    fn emit_trade_analytics(&mut self) {
+        if let Err(e) = self.event_tx.send(MarketEvent::TradeAnalytics {
+            symbol: self.symbol.clone(),
+            analytics: self.trade_analytics.snapshot(),
+        }) {
+            error!("Failed to send trade analytics for {}: {}", self.symbol, e);
+        }
+    }
+
+    fn record_message_metrics(&self, stream: &str) {
+        let kind = [
+            "bookTicker",
+            "depth",
+            "aggTrade",
+            "trade",
+            "ticker",
+            "kline",
+        ]
+        .into_iter()
+        .find(|kind| stream.contains(kind))
+        .unwrap_or("other");
+        metrics::counter!(
+            exporter::WS_MESSAGES_TOTAL,
+            1,
+            "symbol" => self.symbol.clone(),
+            "stream" => kind
+        );
+        metrics::gauge!(
+            exporter::CHANNEL_QUEUE_DEPTH,
+            self.message_rx.len() as f64,
+            "channel" => "ws_messages",
+            "symbol" => self.symbol.clone()
+        );
+    }
+
     async fn handle_kline_event(&mut self, event: KlineStreamEvent) {
         if event.kline.interval != "1d" {
             debug!(
@@ -402,6 +846,9 @@ impl SymbolSubscription {
         match Self::build_daily_candle(&event) {
             Ok(candle) => {
                 let updated = self.upsert_daily_candle(candle);
+                if updated.is_closed {
+                    self.record_daily_candle(&updated);
+                }
 
                 if let Err(e) = self.event_tx.send(MarketEvent::DailyCandleUpdate {
                     symbol: self.symbol.clone(),
@@ -525,7 +972,14 @@ impl SymbolSubscription {
     }
 
     async fn resync_orderbook(&mut self) -> Result<()> {
+        metrics::counter!(
+            exporter::ORDERBOOK_RESYNCS_TOTAL,
+            1,
+            "symbol" => self.symbol.clone()
+        );
         self.orderbook.fetch_snapshot(&self.rest_client).await?;
+        self.top_of_book.reset();
+        self.microstructure.reset_book();
 
         if let Err(e) = self.event_tx.send(MarketEvent::OrderBookUpdate {
             symbol: self.symbol.clone(),
@@ -543,6 +997,11 @@ impl SymbolSubscription {
     /// Reconnect the WebSocket connection
     async fn reconnect(&mut self) -> Result<()> {
         info!("Reconnecting WebSocket for: {}", self.symbol);
+        metrics::counter!(
+            exporter::WS_RECONNECTS_TOTAL,
+            1,
+            "symbol" => self.symbol.clone()
+        );
 
         // Send reconnecting status
         if let Err(e) = self.event_tx.send(MarketEvent::ConnectionStatus {
@@ -589,6 +1048,10 @@ impl SymbolSubscription {
             );
         }
 
+        // Runs before the queued live messages are processed, so the trade
+        // cursor drops anything the stream delivers again
+        self.backfill_after_reconnect().await;
+
         // Send connection reestablished event
         if let Err(e) = self.event_tx.send(MarketEvent::ConnectionStatus {
             symbol: self.symbol.clone(),
@@ -604,6 +1067,99 @@ impl SymbolSubscription {
         Ok(())
     }
 
+    /// Recover trades and daily candles missed while the stream was down
+    async fn backfill_after_reconnect(&mut self) {
+        let mut trades = Vec::new();
+        let mut truncated = false;
+        match backfill_trades(&self.rest_client, &self.symbol, &self.trade_cursor).await {
+            Ok(backfill) => {
+                for trade in &backfill.trades {
+                    self.trade_cursor.advance(trade, None);
+                    self.trade_analytics.record(*trade);
+                    self.feed_bars(trade);
+                    if let Some(history) = &self.history {
+                        history.record_trade(&self.symbol, trade);
+                    }
+                }
+                if let Some(agg_id) = backfill.last_agg_id {
+                    self.trade_cursor.last_agg_id = Some(
+                        self.trade_cursor
+                            .last_agg_id
+                            .map_or(agg_id, |last| last.max(agg_id)),
+                    );
+                }
+                trades = backfill.trades;
+                truncated = backfill.truncated;
+            }
+            Err(e) => {
+                warn!("Failed to backfill trades for {}: {}", self.symbol, e);
+            }
+        }
+
+        let now_ms = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis() as u64;
+        let mut candles = Vec::new();
+        if let Some(last_open_time_ms) = self.daily_candles.last().map(|c| c.open_time_ms) {
+            match backfill_daily_candles(&self.rest_client, &self.symbol, last_open_time_ms, now_ms)
+                .await
+            {
+                Ok(fetched) => {
+                    candles = fetched
+                        .into_iter()
+                        .map(|candle| self.upsert_daily_candle(candle))
+                        .collect();
+                    for candle in candles.iter().filter(|candle| candle.is_closed) {
+                        self.record_daily_candle(candle);
+                    }
+                }
+                Err(e) => {
+                    warn!("Failed to backfill candles for {}: {}", self.symbol, e);
+                }
+            }
+        }
+
+        if trades.is_empty() && candles.is_empty() {
+            return;
+        }
+        if truncated {
+            // Pages run oldest first, so the cap cuts off the newest part of the gap
+            let missing_from = trades
+                .last()
+                .map_or("the last backfilled trade".to_string(), |trade| {
+                    format_utc_ms(trade.time_ms)
+                });
+            warn!(
+                "Trade backfill for {} stopped after {} trades; trades from {} to the reconnect at {} are missing",
+                self.symbol,
+                trades.len(),
+                missing_from,
+                format_utc_ms(now_ms)
+            );
+        }
+        info!(
+            "Backfilled {} trades and {} daily candles for {}",
+            trades.len(),
+            candles.len(),
+            self.symbol
+        );
+
+        let recovered_trades = !trades.is_empty();
+        if let Err(e) = self.event_tx.send(MarketEvent::Backfill {
+            symbol: self.symbol.clone(),
+            trades,
+            candles,
+            truncated,
+        }) {
+            error!("Failed to send backfill for {}: {}", self.symbol, e);
+        }
+        if recovered_trades {
+            self.emit_trade_analytics();
+            self.emit_building_bars();
+        }
+    }
+
     /// Get the symbol
     pub fn symbol(&self) -> &str {
         &self.symbol
@@ -675,6 +1231,13 @@ struct ParsedTickerStats {
     volume: f64,
 }
 
+/// Exchange time for log messages, e.g. `2024-06-10 14:03:22.517 UTC`
+fn format_utc_ms(time_ms: u64) -> String {
+    chrono::DateTime::<chrono::Utc>::from_timestamp_millis(time_ms as i64)
+        .map(|time| time.format("%Y-%m-%d %H:%M:%S%.3f UTC").to_string())
+        .unwrap_or_else(|| time_ms.to_string())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
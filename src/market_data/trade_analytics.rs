@@ -0,0 +1,374 @@
+//! Rolling trade flow analytics from the public trade stream
+//!
+//! Every print carries the taker side (`m` is true when the buyer was the
+//! maker, i.e. a taker sell), so the stream alone is enough to split volume
+//! into aggressive buys and sells. Window figures cover the last
+//! `window_ms` of exchange time; the cumulative volume delta and VWAP run
+//! for the whole session.
+
+use std::collections::VecDeque;
+
+use anyhow::{Result, anyhow};
+use serde::{Deserialize, Serialize};
+
+use crate::binance::types::{AggTradeMessage, RestAggTrade, TradeMessage};
+
+/// Span of exchange time covered by the rolling window figures
+pub const DEFAULT_TRADE_WINDOW_MS: u64 = 60_000;
+
+/// Number of largest trades reported per snapshot
+pub const LARGEST_TRADES: usize = 5;
+
+/// Side of the aggressor that took liquidity
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TakerSide {
+    Buy,
+    Sell,
+}
+
+impl TakerSide {
+    fn from_buyer_maker(is_buyer_maker: bool) -> Self {
+        if is_buyer_maker {
+            TakerSide::Sell
+        } else {
+            TakerSide::Buy
+        }
+    }
+}
+
+/// One print from either the raw or the aggregate trade stream
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct TradePrint {
+    pub price: f64,
+    pub quantity: f64,
+    pub time_ms: u64,
+    pub side: TakerSide,
+    /// Exchange fills behind this print; more than one for aggregate trades
+    pub fills: u64,
+    /// Id of the last exchange fill in this print, used to drop duplicates
+    pub trade_id: u64,
+    /// Recovered over REST after a reconnect rather than streamed
+    pub backfilled: bool,
+}
+
+impl TradePrint {
+    /// Streamed print of a single exchange fill, with `time_ms` as its trade id, for tests
+    #[cfg(test)]
+    pub fn single_fill(time_ms: u64, price: f64, quantity: f64, side: TakerSide) -> Self {
+        Self {
+            price,
+            quantity,
+            time_ms,
+            side,
+            fills: 1,
+            trade_id: time_ms,
+            backfilled: false,
+        }
+    }
+
+    pub fn from_trade(message: &TradeMessage) -> Result<Self> {
+        Ok(Self {
+            price: parse_f64(&message.price, "price")?,
+            quantity: parse_f64(&message.quantity, "quantity")?,
+            time_ms: message.trade_time,
+            side: TakerSide::from_buyer_maker(message.is_buyer_maker),
+            fills: 1,
+            trade_id: message.trade_id,
+            backfilled: false,
+        })
+    }
+
+    pub fn from_agg_trade(message: &AggTradeMessage) -> Result<Self> {
+        Ok(Self {
+            price: parse_f64(&message.price, "price")?,
+            quantity: parse_f64(&message.quantity, "quantity")?,
+            time_ms: message.trade_time,
+            side: TakerSide::from_buyer_maker(message.is_buyer_maker),
+            fills: message
+                .last_trade_id
+                .saturating_sub(message.first_trade_id)
+                .saturating_add(1),
+            trade_id: message.last_trade_id,
+            backfilled: false,
+        })
+    }
+
+    pub fn from_rest_agg_trade(trade: &RestAggTrade) -> Result<Self> {
+        Ok(Self {
+            price: parse_f64(&trade.price, "price")?,
+            quantity: parse_f64(&trade.quantity, "quantity")?,
+            time_ms: trade.trade_time,
+            side: TakerSide::from_buyer_maker(trade.is_buyer_maker),
+            fills: trade
+                .last_trade_id
+                .saturating_sub(trade.first_trade_id)
+                .saturating_add(1),
+            trade_id: trade.last_trade_id,
+            backfilled: true,
+        })
+    }
+
+    /// Traded value in the quote asset
+    pub fn notional(&self) -> f64 {
+        self.price * self.quantity
+    }
+}
+
+/// Point-in-time view of the trade flow for one symbol
+#[derive(Debug, Clone, PartialEq)]
+pub struct TradeAnalyticsSnapshot {
+    pub window_ms: u64,
+    /// Taker buy volume in the window (base asset)
+    pub buy_volume: f64,
+    /// Taker sell volume in the window (base asset)
+    pub sell_volume: f64,
+    /// Exchange fills in the window
+    pub trade_count: u64,
+    pub trades_per_sec: f64,
+    /// Taker buy minus taker sell volume since the session started
+    pub cumulative_delta: f64,
+    /// Volume weighted average price since the session started
+    pub session_vwap: Option<f64>,
+    pub session_volume: f64,
+    pub last_price: Option<f64>,
+    /// Prints in the window recovered over REST after a reconnect
+    pub backfilled_trades: u64,
+    /// Largest prints in the window by notional, biggest first
+    pub largest_trades: Vec<TradePrint>,
+}
+
+impl TradeAnalyticsSnapshot {
+    /// Taker buy minus taker sell volume in the window
+    pub fn window_delta(&self) -> f64 {
+        self.buy_volume - self.sell_volume
+    }
+
+    /// Share of window volume bought by takers, in percent
+    pub fn buy_ratio_percent(&self) -> Option<f64> {
+        let total = self.buy_volume + self.sell_volume;
+        (total > 0.0).then(|| self.buy_volume / total * 100.0)
+    }
+
+    /// Distance of the last price from the session VWAP, in percent
+    pub fn vwap_deviation_percent(&self) -> Option<f64> {
+        match (self.last_price, self.session_vwap) {
+            (Some(last), Some(vwap)) if vwap > 0.0 => Some((last - vwap) / vwap * 100.0),
+            _ => None,
+        }
+    }
+}
+
+/// Accumulates trade prints for one symbol
+#[derive(Debug)]
+pub struct TradeAnalytics {
+    window_ms: u64,
+    window: VecDeque<TradePrint>,
+    buy_volume: f64,
+    sell_volume: f64,
+    trade_count: u64,
+    backfilled_trades: u64,
+    cumulative_delta: f64,
+    session_notional: f64,
+    session_volume: f64,
+    first_trade_ms: Option<u64>,
+    last_price: Option<f64>,
+}
+
+impl Default for TradeAnalytics {
+    fn default() -> Self {
+        Self::new(DEFAULT_TRADE_WINDOW_MS)
+    }
+}
+
+impl TradeAnalytics {
+    pub fn new(window_ms: u64) -> Self {
+        Self {
+            window_ms: window_ms.max(1),
+            window: VecDeque::new(),
+            buy_volume: 0.0,
+            sell_volume: 0.0,
+            trade_count: 0,
+            backfilled_trades: 0,
+            cumulative_delta: 0.0,
+            session_notional: 0.0,
+            session_volume: 0.0,
+            first_trade_ms: None,
+            last_price: None,
+        }
+    }
+
+    /// Add a print and drop the ones that fell out of the window
+    pub fn record(&mut self, trade: TradePrint) {
+        if !trade.price.is_finite() || !trade.quantity.is_finite() || trade.quantity <= 0.0 {
+            return;
+        }
+
+        match trade.side {
+            TakerSide::Buy => {
+                self.buy_volume += trade.quantity;
+                self.cumulative_delta += trade.quantity;
+            }
+            TakerSide::Sell => {
+                self.sell_volume += trade.quantity;
+                self.cumulative_delta -= trade.quantity;
+            }
+        }
+        self.trade_count += trade.fills;
+        self.backfilled_trades += u64::from(trade.backfilled);
+        self.session_notional += trade.notional();
+        self.session_volume += trade.quantity;
+        self.first_trade_ms.get_or_insert(trade.time_ms);
+        self.last_price = Some(trade.price);
+        self.window.push_back(trade);
+
+        self.evict(trade.time_ms);
+    }
+
+    /// Summarize the flow as of the most recent print
+    pub fn snapshot(&self) -> TradeAnalyticsSnapshot {
+        let mut largest_trades: Vec<TradePrint> = self.window.iter().copied().collect();
+        largest_trades.sort_by(|a, b| b.notional().total_cmp(&a.notional()));
+        largest_trades.truncate(LARGEST_TRADES);
+
+        TradeAnalyticsSnapshot {
+            window_ms: self.window_ms,
+            buy_volume: self.buy_volume,
+            sell_volume: self.sell_volume,
+            trade_count: self.trade_count,
+            trades_per_sec: self.trades_per_sec(),
+            cumulative_delta: self.cumulative_delta,
+            session_vwap: (self.session_volume > 0.0)
+                .then(|| self.session_notional / self.session_volume),
+            session_volume: self.session_volume,
+            last_price: self.last_price,
+            backfilled_trades: self.backfilled_trades,
+            largest_trades,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.last_price.is_none()
+    }
+
+    fn evict(&mut self, latest_ms: u64) {
+        let cutoff = latest_ms.saturating_sub(self.window_ms);
+        while let Some(oldest) = self.window.front() {
+            if oldest.time_ms > cutoff {
+                break;
+            }
+            match oldest.side {
+                TakerSide::Buy => self.buy_volume -= oldest.quantity,
+                TakerSide::Sell => self.sell_volume -= oldest.quantity,
+            }
+            self.trade_count -= oldest.fills;
+            self.backfilled_trades -= u64::from(oldest.backfilled);
+            self.window.pop_front();
+        }
+
+        // Running sums drift slightly below zero from float error once emptied
+        if self.window.is_empty() {
+            self.buy_volume = 0.0;
+            self.sell_volume = 0.0;
+        }
+    }
+
+    /// Fills per second over the window, or over the session until the window has filled
+    fn trades_per_sec(&self) -> f64 {
+        let (Some(first), Some(latest)) = (self.first_trade_ms, self.window.back()) else {
+            return 0.0;
+        };
+        let span_ms = latest
+            .time_ms
+            .saturating_sub(first)
+            .clamp(1_000, self.window_ms.max(1_000));
+        self.trade_count as f64 / (span_ms as f64 / 1_000.0)
+    }
+}
+
+fn parse_f64(value: &str, field: &str) -> Result<f64> {
+    value
+        .parse::<f64>()
+        .map_err(|e| anyhow!("failed to parse {} value '{}': {}", field, value, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_volume_by_taker_side() {
+        let mut analytics = TradeAnalytics::new(10_000);
+        analytics.record(TradePrint::single_fill(1_000, 100.0, 2.0, TakerSide::Buy));
+        analytics.record(TradePrint::single_fill(2_000, 101.0, 1.0, TakerSide::Sell));
+        analytics.record(TradePrint::single_fill(3_000, 102.0, 1.0, TakerSide::Buy));
+
+        let snapshot = analytics.snapshot();
+        assert_eq!(snapshot.buy_volume, 3.0);
+        assert_eq!(snapshot.sell_volume, 1.0);
+        assert_eq!(snapshot.window_delta(), 2.0);
+        assert_eq!(snapshot.cumulative_delta, 2.0);
+        assert_eq!(snapshot.buy_ratio_percent(), Some(75.0));
+
+        let vwap = snapshot.session_vwap.unwrap();
+        assert!((vwap - 100.75).abs() < 1e-9);
+        let deviation = snapshot.vwap_deviation_percent().unwrap();
+        assert!((deviation - (102.0 - 100.75) / 100.75 * 100.0).abs() < 1e-9);
+
+        assert_eq!(snapshot.largest_trades.len(), 3);
+        assert_eq!(snapshot.largest_trades[0].quantity, 2.0);
+    }
+
+    #[test]
+    fn window_expires_while_session_figures_persist() {
+        let mut analytics = TradeAnalytics::new(5_000);
+        analytics.record(TradePrint::single_fill(0, 100.0, 4.0, TakerSide::Sell));
+        analytics.record(TradePrint::single_fill(2_000, 100.0, 1.0, TakerSide::Buy));
+        analytics.record(TradePrint::single_fill(6_000, 110.0, 1.0, TakerSide::Buy));
+
+        let snapshot = analytics.snapshot();
+        // The 4.0 sell left the window but still counts toward CVD and VWAP
+        assert_eq!(snapshot.sell_volume, 0.0);
+        assert_eq!(snapshot.buy_volume, 2.0);
+        assert_eq!(snapshot.trade_count, 2);
+        assert_eq!(snapshot.cumulative_delta, -2.0);
+        assert!((snapshot.session_vwap.unwrap() - 610.0 / 6.0).abs() < 1e-9);
+        assert!((snapshot.trades_per_sec - 0.4).abs() < 1e-9);
+        assert!(snapshot.largest_trades.iter().all(|t| t.time_ms >= 2_000));
+    }
+
+    #[test]
+    fn counts_backfilled_prints_in_window() {
+        let mut analytics = TradeAnalytics::new(5_000);
+        let recovered = TradePrint {
+            backfilled: true,
+            ..TradePrint::single_fill(1_000, 100.0, 1.0, TakerSide::Buy)
+        };
+        analytics.record(recovered);
+        analytics.record(TradePrint::single_fill(2_000, 100.0, 1.0, TakerSide::Sell));
+        assert_eq!(analytics.snapshot().backfilled_trades, 1);
+
+        analytics.record(TradePrint::single_fill(7_000, 100.0, 1.0, TakerSide::Sell));
+        assert_eq!(analytics.snapshot().backfilled_trades, 0);
+    }
+
+    #[test]
+    fn aggregate_trades_count_underlying_fills() {
+        let raw: AggTradeMessage = serde_json::from_value(serde_json::json!({
+            "e": "aggTrade", "E": 1_000u64, "s": "BTCUSDT", "a": 7,
+            "p": "50000.0", "q": "0.5", "f": 100, "l": 103, "T": 1_000u64, "m": true
+        }))
+        .unwrap();
+        let trade = TradePrint::from_agg_trade(&raw).unwrap();
+        assert_eq!(trade.side, TakerSide::Sell);
+        assert_eq!(trade.fills, 4);
+        assert_eq!(trade.trade_id, 103);
+        assert_eq!(trade.notional(), 25_000.0);
+
+        let mut analytics = TradeAnalytics::default();
+        assert!(analytics.is_empty());
+        analytics.record(trade);
+        assert_eq!(analytics.snapshot().trade_count, 4);
+        assert_eq!(analytics.snapshot().trades_per_sec, 4.0);
+    }
+}
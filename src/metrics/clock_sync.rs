@@ -0,0 +1,160 @@
+//! Exchange clock offset estimation
+//!
+//! Each sample brackets a `/api/v3/time` request with local timestamps. As in
+//! NTP, the server is assumed to stamp the response halfway through the round
+//! trip, so `offset = server_time - (send + receive) / 2`. Samples with the
+//! slowest round trips carry the most asymmetry error and are discarded; the
+//! estimate is the median offset of the rest.
+
+use std::collections::VecDeque;
+
+/// Samples kept for the rolling estimate
+pub const DEFAULT_CLOCK_SAMPLES: usize = 16;
+
+/// A single server time measurement
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClockSample {
+    /// Exchange clock minus local clock, in milliseconds
+    pub offset_ms: i64,
+    pub rtt_ms: u64,
+}
+
+impl ClockSample {
+    /// Build a sample from local send/receive times and the server timestamp
+    pub fn from_exchange(send_ms: u64, server_ms: u64, receive_ms: u64) -> Self {
+        let receive_ms = receive_ms.max(send_ms);
+        let midpoint = send_ms + (receive_ms - send_ms) / 2;
+        Self {
+            offset_ms: server_ms as i64 - midpoint as i64,
+            rtt_ms: receive_ms - send_ms,
+        }
+    }
+}
+
+/// Current offset estimate between the local and exchange clocks
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClockEstimate {
+    /// Add to local time to get exchange time
+    pub offset_ms: i64,
+    /// Median round trip of the samples used
+    pub rtt_ms: u64,
+    /// Samples the estimate was derived from
+    pub samples: usize,
+    /// Local time the estimate was produced
+    pub updated_at_ms: u64,
+}
+
+impl ClockEstimate {
+    /// Whether the local clock is further than `threshold_ms` from the exchange
+    pub fn exceeds(&self, threshold_ms: u64) -> bool {
+        self.offset_ms.unsigned_abs() > threshold_ms
+    }
+
+    /// Convert a local timestamp to exchange time
+    pub fn exchange_time(&self, local_ms: u64) -> u64 {
+        (local_ms as i64 + self.offset_ms).max(0) as u64
+    }
+}
+
+/// Rolling offset estimator over the most recent samples
+#[derive(Debug, Clone)]
+pub struct ClockSync {
+    samples: VecDeque<ClockSample>,
+    max_samples: usize,
+}
+
+impl Default for ClockSync {
+    fn default() -> Self {
+        Self::new(DEFAULT_CLOCK_SAMPLES)
+    }
+}
+
+impl ClockSync {
+    pub fn new(max_samples: usize) -> Self {
+        Self {
+            samples: VecDeque::with_capacity(max_samples.max(1)),
+            max_samples: max_samples.max(1),
+        }
+    }
+
+    /// Add a sample and return the updated estimate
+    pub fn add_sample(&mut self, sample: ClockSample, now_ms: u64) -> ClockEstimate {
+        if self.samples.len() >= self.max_samples {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(sample);
+        self.estimate(now_ms)
+            .expect("estimate exists after adding a sample")
+    }
+
+    /// Median offset of the faster half of the samples, `None` before the first sample
+    pub fn estimate(&self, now_ms: u64) -> Option<ClockEstimate> {
+        if self.samples.is_empty() {
+            return None;
+        }
+
+        let mut by_rtt: Vec<ClockSample> = self.samples.iter().copied().collect();
+        by_rtt.sort_by_key(|sample| sample.rtt_ms);
+        by_rtt.truncate(by_rtt.len().div_ceil(2));
+
+        let mut offsets: Vec<i64> = by_rtt.iter().map(|s| s.offset_ms).collect();
+        offsets.sort_unstable();
+
+        Some(ClockEstimate {
+            offset_ms: offsets[offsets.len() / 2],
+            rtt_ms: by_rtt[by_rtt.len() / 2].rtt_ms,
+            samples: by_rtt.len(),
+            updated_at_ms: now_ms,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sample_uses_round_trip_midpoint() {
+        // Local clock 250ms behind the exchange, 100ms round trip
+        let sample = ClockSample::from_exchange(1_000, 1_300, 1_100);
+        assert_eq!(sample.offset_ms, 250);
+        assert_eq!(sample.rtt_ms, 100);
+
+        let ahead = ClockSample::from_exchange(5_000, 4_000, 5_040);
+        assert_eq!(ahead.offset_ms, -1_020);
+    }
+
+    #[test]
+    fn slow_round_trips_are_discarded() {
+        let mut sync = ClockSync::new(8);
+        assert!(sync.estimate(0).is_none());
+
+        for (offset, rtt) in [(100, 20), (104, 22), (98, 18), (102, 25)] {
+            sync.add_sample(
+                ClockSample {
+                    offset_ms: offset,
+                    rtt_ms: rtt,
+                },
+                0,
+            );
+        }
+        // A congested request skews the midpoint badly
+        let estimate = sync.add_sample(
+            ClockSample {
+                offset_ms: 900,
+                rtt_ms: 1_600,
+            },
+            42,
+        );
+
+        assert_eq!(estimate.samples, 3);
+        assert!((98..=104).contains(&estimate.offset_ms));
+        assert!(estimate.rtt_ms <= 25);
+        assert_eq!(estimate.updated_at_ms, 42);
+        assert!(!estimate.exceeds(500));
+        assert_eq!(
+            estimate.exchange_time(1_000),
+            1_000 + estimate.offset_ms as u64
+        );
+    }
+}
@@ -0,0 +1,204 @@
+//! Prometheus exporter serving the `metrics` crate recorder over HTTP
+//!
+//! Instrumented code records through the `metrics` macros using the names
+//! below; when the exporter is disabled no recorder is installed and those
+//! calls are no-ops.
+
+use anyhow::{Context, Result};
+use metrics::{Unit, describe_counter, describe_gauge, describe_histogram};
+use metrics_exporter_prometheus::{Matcher, PrometheusBuilder, PrometheusHandle};
+use std::net::SocketAddr;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::task::JoinHandle;
+use tracing::{debug, info, warn};
+
+/// WebSocket messages received, labelled by `symbol` and `stream`
+pub const WS_MESSAGES_TOTAL: &str = "xtrade_ws_messages_total";
+/// Exchange event time to local receipt latency, labelled by `symbol`
+pub const MESSAGE_LATENCY_MS: &str = "xtrade_message_latency_ms";
+/// WebSocket reconnect attempts, labelled by `symbol`
+pub const WS_RECONNECTS_TOTAL: &str = "xtrade_ws_reconnects_total";
+/// Order book snapshot resyncs, labelled by `symbol`
+pub const ORDERBOOK_RESYNCS_TOTAL: &str = "xtrade_orderbook_resyncs_total";
+/// REST requests, labelled by `endpoint` and `status`
+pub const REST_REQUESTS_TOTAL: &str = "xtrade_rest_requests_total";
+/// Request weight used in the current minute as reported by Binance
+pub const REST_USED_WEIGHT_1M: &str = "xtrade_rest_used_weight_1m";
+/// Price alerts fired, labelled by `symbol`
+pub const ALERT_TRIGGERS_TOTAL: &str = "xtrade_alert_triggers_total";
+/// Messages waiting in an internal channel, labelled by `channel`
+pub const CHANNEL_QUEUE_DEPTH: &str = "xtrade_channel_queue_depth";
+
+const LATENCY_BUCKETS_MS: [f64; 12] = [
+    1.0, 5.0, 10.0, 25.0, 50.0, 100.0, 250.0, 500.0, 1_000.0, 2_500.0, 5_000.0, 10_000.0,
+];
+
+/// Maximum request head accepted by the `/metrics` listener
+const MAX_REQUEST_BYTES: usize = 8 * 1024;
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Running exporter with the address it is bound to
+pub struct MetricsExporter {
+    pub local_addr: SocketAddr,
+    pub task: JoinHandle<()>,
+}
+
+/// Install the global Prometheus recorder and serve `/metrics` on `bind_address`
+pub async fn start_exporter(bind_address: &str) -> Result<MetricsExporter> {
+    let addr: SocketAddr = bind_address
+        .parse()
+        .with_context(|| format!("Invalid metrics bind address: {}", bind_address))?;
+
+    let handle = PrometheusBuilder::new()
+        .set_buckets_for_metric(
+            Matcher::Full(MESSAGE_LATENCY_MS.to_string()),
+            &LATENCY_BUCKETS_MS,
+        )
+        .context("Failed to configure latency buckets")?
+        .install_recorder()
+        .context("Failed to install Prometheus recorder")?;
+    describe_metrics();
+
+    let listener = TcpListener::bind(addr)
+        .await
+        .with_context(|| format!("Failed to bind metrics endpoint on {}", addr))?;
+    let local_addr = listener.local_addr()?;
+    info!(
+        "Prometheus metrics available at http://{}/metrics",
+        local_addr
+    );
+
+    let task = tokio::spawn(serve(listener, handle));
+    Ok(MetricsExporter { local_addr, task })
+}
+
+/// Accept connections and answer `GET /metrics` with the rendered registry
+pub async fn serve(listener: TcpListener, handle: PrometheusHandle) {
+    loop {
+        match listener.accept().await {
+            Ok((stream, peer)) => {
+                let handle = handle.clone();
+                tokio::spawn(async move {
+                    if let Err(e) = handle_connection(stream, &handle).await {
+                        debug!("Metrics request from {} failed: {}", peer, e);
+                    }
+                });
+            }
+            Err(e) => {
+                warn!("Failed to accept metrics connection: {}", e);
+                tokio::time::sleep(Duration::from_millis(100)).await;
+            }
+        }
+    }
+}
+
+async fn handle_connection(mut stream: TcpStream, handle: &PrometheusHandle) -> Result<()> {
+    let head = tokio::time::timeout(REQUEST_TIMEOUT, read_request_head(&mut stream))
+        .await
+        .context("Timed out reading request")??;
+
+    let mut parts = head.lines().next().unwrap_or_default().split_whitespace();
+    let method = parts.next().unwrap_or_default();
+    let path = parts
+        .next()
+        .unwrap_or_default()
+        .split('?')
+        .next()
+        .unwrap_or_default();
+
+    let (status, body) = match (method, path) {
+        ("GET" | "HEAD", "/metrics") => ("200 OK", handle.render()),
+        ("GET" | "HEAD", _) => ("404 Not Found", "Not Found\n".to_string()),
+        _ => ("405 Method Not Allowed", "Method Not Allowed\n".to_string()),
+    };
+
+    let mut response = format!(
+        "HTTP/1.1 {}\r\nContent-Type: text/plain; version=0.0.4\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        status,
+        body.len()
+    );
+    if method != "HEAD" {
+        response.push_str(&body);
+    }
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await?;
+    Ok(())
+}
+
+async fn read_request_head(stream: &mut TcpStream) -> Result<String> {
+    let mut buffer = Vec::with_capacity(1024);
+    let mut chunk = [0u8; 1024];
+
+    loop {
+        let read = stream.read(&mut chunk).await?;
+        if read == 0 {
+            break;
+        }
+        buffer.extend_from_slice(&chunk[..read]);
+        if buffer.windows(4).any(|w| w == b"\r\n\r\n") {
+            break;
+        }
+        if buffer.len() > MAX_REQUEST_BYTES {
+            anyhow::bail!("Request head too large");
+        }
+    }
+
+    Ok(String::from_utf8_lossy(&buffer).into_owned())
+}
+
+fn describe_metrics() {
+    describe_counter!(WS_MESSAGES_TOTAL, "WebSocket messages received");
+    describe_histogram!(
+        MESSAGE_LATENCY_MS,
+        Unit::Milliseconds,
+        "Latency between exchange event time and local receipt"
+    );
+    describe_counter!(WS_RECONNECTS_TOTAL, "WebSocket reconnect attempts");
+    describe_counter!(ORDERBOOK_RESYNCS_TOTAL, "Order book snapshot resyncs");
+    describe_counter!(REST_REQUESTS_TOTAL, "REST API requests");
+    describe_gauge!(
+        REST_USED_WEIGHT_1M,
+        "Request weight used in the current minute (X-MBX-USED-WEIGHT-1M)"
+    );
+    describe_counter!(ALERT_TRIGGERS_TOTAL, "Price alerts fired");
+    describe_gauge!(
+        CHANNEL_QUEUE_DEPTH,
+        "Messages waiting in an internal channel"
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn request(addr: SocketAddr, head: &str) -> String {
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        stream.write_all(head.as_bytes()).await.unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+        response
+    }
+
+    #[tokio::test]
+    async fn serves_metrics_path_only() {
+        let recorder = PrometheusBuilder::new().build_recorder();
+        let handle = recorder.handle();
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let task = tokio::spawn(serve(listener, handle));
+
+        let ok = request(addr, "GET /metrics HTTP/1.1\r\nHost: localhost\r\n\r\n").await;
+        assert!(ok.starts_with("HTTP/1.1 200 OK"));
+        assert!(ok.contains("Content-Type: text/plain; version=0.0.4"));
+
+        let missing = request(addr, "GET / HTTP/1.1\r\n\r\n").await;
+        assert!(missing.starts_with("HTTP/1.1 404"));
+
+        let rejected = request(addr, "POST /metrics HTTP/1.1\r\n\r\n").await;
+        assert!(rejected.starts_with("HTTP/1.1 405"));
+
+        task.abort();
+    }
+}
@@ -0,0 +1,407 @@
+//! Constant-memory latency histograms
+//!
+//! `LatencyHistogram` uses HDR-style log-linear buckets: values below 64 ms
+//! are exact, larger values land in one of 32 sub-buckets per power of two,
+//! bounding the relative error to about 3%. Recording is O(1) and a quantile
+//! query walks a fixed number of buckets, independent of the sample count.
+//!
+//! `RollingHistogram` keeps a ring of per-slot histograms so percentiles can
+//! be read over the last minute, five minutes or hour.
+
+/// Sub-buckets per power of two (2^5 = 32)
+const SUB_BUCKET_BITS: u32 = 5;
+const SUB_BUCKET_COUNT: usize = 1 << SUB_BUCKET_BITS;
+/// Values at or above 2^24 ms (~4.6 hours) share a final overflow bucket
+const MAX_MAGNITUDE: u32 = 24;
+const BUCKET_COUNT: usize =
+    SUB_BUCKET_COUNT + (MAX_MAGNITUDE - SUB_BUCKET_BITS) as usize * SUB_BUCKET_COUNT + 1;
+
+/// Fine ring: 10 second slots covering five minutes
+const FINE_SLOT_MS: u64 = 10_000;
+const FINE_SLOTS: usize = 30;
+/// Coarse ring: five minute slots covering one hour
+const COARSE_SLOT_MS: u64 = 300_000;
+const COARSE_SLOTS: usize = 12;
+
+/// Percentile summary of a latency distribution, in milliseconds
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LatencySummary {
+    pub count: u64,
+    pub p50: u64,
+    pub p90: u64,
+    pub p95: u64,
+    pub p99: u64,
+    pub p999: u64,
+    pub max: u64,
+}
+
+/// Rolling window a latency summary is computed over
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LatencyWindow {
+    OneMinute,
+    FiveMinutes,
+    OneHour,
+}
+
+impl LatencyWindow {
+    pub const ALL: [LatencyWindow; 3] = [
+        LatencyWindow::OneMinute,
+        LatencyWindow::FiveMinutes,
+        LatencyWindow::OneHour,
+    ];
+
+    pub fn duration_ms(&self) -> u64 {
+        match self {
+            LatencyWindow::OneMinute => 60_000,
+            LatencyWindow::FiveMinutes => 300_000,
+            LatencyWindow::OneHour => 3_600_000,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            LatencyWindow::OneMinute => "1m",
+            LatencyWindow::FiveMinutes => "5m",
+            LatencyWindow::OneHour => "1h",
+        }
+    }
+}
+
+/// Fixed-bucket latency histogram
+#[derive(Debug, Clone)]
+pub struct LatencyHistogram {
+    counts: Box<[u32; BUCKET_COUNT]>,
+    total: u64,
+    max: u64,
+}
+
+impl Default for LatencyHistogram {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LatencyHistogram {
+    pub fn new() -> Self {
+        Self {
+            counts: Box::new([0; BUCKET_COUNT]),
+            total: 0,
+            max: 0,
+        }
+    }
+
+    /// Record one latency sample
+    pub fn record(&mut self, value_ms: u64) {
+        let bucket = &mut self.counts[bucket_index(value_ms)];
+        *bucket = bucket.saturating_add(1);
+        self.total += 1;
+        self.max = self.max.max(value_ms);
+    }
+
+    /// Add every sample from another histogram
+    pub fn merge(&mut self, other: &LatencyHistogram) {
+        if other.total == 0 {
+            return;
+        }
+        for (count, other) in self.counts.iter_mut().zip(other.counts.iter()) {
+            *count = count.saturating_add(*other);
+        }
+        self.total += other.total;
+        self.max = self.max.max(other.max);
+    }
+
+    /// Number of recorded samples
+    pub fn len(&self) -> u64 {
+        self.total
+    }
+
+    /// Whether no samples were recorded
+    pub fn is_empty(&self) -> bool {
+        self.total == 0
+    }
+
+    /// Largest recorded value
+    pub fn max(&self) -> u64 {
+        self.max
+    }
+
+    /// Value at a quantile in `0.0..=1.0`, reported as the bucket's upper bound
+    pub fn value_at_quantile(&self, quantile: f64) -> u64 {
+        let [value] = self.values_at_quantiles([quantile]);
+        value
+    }
+
+    /// Forget all samples
+    pub fn clear(&mut self) {
+        self.counts.fill(0);
+        self.total = 0;
+        self.max = 0;
+    }
+
+    /// p50/p90/p95/p99/p99.9/max in a single pass over the buckets
+    pub fn summary(&self) -> LatencySummary {
+        let [p50, p90, p95, p99, p999] = self.values_at_quantiles([0.50, 0.90, 0.95, 0.99, 0.999]);
+        LatencySummary {
+            count: self.total,
+            p50,
+            p90,
+            p95,
+            p99,
+            p999,
+            max: self.max,
+        }
+    }
+
+    /// Quantiles must be given in ascending order
+    fn values_at_quantiles<const N: usize>(&self, quantiles: [f64; N]) -> [u64; N] {
+        let mut values = [self.max; N];
+        if self.total == 0 {
+            return values;
+        }
+
+        let ranks =
+            quantiles.map(|q| ((q.clamp(0.0, 1.0) * self.total as f64).ceil() as u64).max(1));
+        let mut next = 0;
+        let mut seen = 0u64;
+
+        for (index, count) in self.counts.iter().enumerate() {
+            seen += u64::from(*count);
+            while next < N && seen >= ranks[next] {
+                values[next] = bucket_upper_bound(index).min(self.max);
+                next += 1;
+            }
+            if next == N {
+                break;
+            }
+        }
+        values
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Slot {
+    epoch: u64,
+    histogram: LatencyHistogram,
+}
+
+/// Ring of histograms, one per fixed-length time slot
+#[derive(Debug, Clone)]
+struct SlotRing {
+    slot_ms: u64,
+    slots: Vec<Option<Slot>>,
+}
+
+impl SlotRing {
+    fn new(slot_ms: u64, slot_count: usize) -> Self {
+        Self {
+            slot_ms,
+            slots: vec![None; slot_count],
+        }
+    }
+
+    fn record(&mut self, value_ms: u64, now_ms: u64) {
+        let epoch = now_ms / self.slot_ms;
+        let index = (epoch % self.slots.len() as u64) as usize;
+        let slot = self.slots[index].get_or_insert_with(|| Slot {
+            epoch,
+            histogram: LatencyHistogram::new(),
+        });
+        if slot.epoch != epoch {
+            slot.epoch = epoch;
+            slot.histogram.clear();
+        }
+        slot.histogram.record(value_ms);
+    }
+
+    /// Merge the slots overlapping the last `window_ms` into `into`
+    fn merge_window(&self, window_ms: u64, now_ms: u64, into: &mut LatencyHistogram) {
+        let current = now_ms / self.slot_ms;
+        let span = window_ms
+            .div_ceil(self.slot_ms)
+            .clamp(1, self.slots.len() as u64);
+        let oldest = current.saturating_sub(span - 1);
+        for slot in self.slots.iter().flatten() {
+            if slot.epoch >= oldest && slot.epoch <= current {
+                into.merge(&slot.histogram);
+            }
+        }
+    }
+
+    fn covers(&self, window_ms: u64) -> bool {
+        window_ms <= self.slot_ms * self.slots.len() as u64
+    }
+}
+
+/// Latency histogram over rolling 1m/5m/1h windows
+///
+/// Windows up to five minutes are served from 10 second slots, the hour
+/// window from five minute slots, so its edge is accurate to one slot.
+#[derive(Debug, Clone)]
+pub struct RollingHistogram {
+    fine: SlotRing,
+    coarse: SlotRing,
+}
+
+impl Default for RollingHistogram {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RollingHistogram {
+    pub fn new() -> Self {
+        Self {
+            fine: SlotRing::new(FINE_SLOT_MS, FINE_SLOTS),
+            coarse: SlotRing::new(COARSE_SLOT_MS, COARSE_SLOTS),
+        }
+    }
+
+    /// Record a sample observed at `now_ms`
+    pub fn record(&mut self, value_ms: u64, now_ms: u64) {
+        self.fine.record(value_ms, now_ms);
+        self.coarse.record(value_ms, now_ms);
+    }
+
+    /// Merge this window's samples into an existing histogram
+    pub fn merge_window_into(
+        &self,
+        window: LatencyWindow,
+        now_ms: u64,
+        into: &mut LatencyHistogram,
+    ) {
+        let window_ms = window.duration_ms();
+        let ring = if self.fine.covers(window_ms) {
+            &self.fine
+        } else {
+            &self.coarse
+        };
+        ring.merge_window(window_ms, now_ms, into);
+    }
+
+    /// Histogram of the samples in a window
+    pub fn window(&self, window: LatencyWindow, now_ms: u64) -> LatencyHistogram {
+        let mut histogram = LatencyHistogram::new();
+        self.merge_window_into(window, now_ms, &mut histogram);
+        histogram
+    }
+
+    /// Percentile summary of a window
+    pub fn summary(&self, window: LatencyWindow, now_ms: u64) -> LatencySummary {
+        self.window(window, now_ms).summary()
+    }
+
+    /// Forget all samples
+    pub fn clear(&mut self) {
+        *self = Self::new();
+    }
+}
+
+fn bucket_index(value: u64) -> usize {
+    if value < (SUB_BUCKET_COUNT as u64) << 1 {
+        return value as usize;
+    }
+    let magnitude = 63 - value.leading_zeros();
+    if magnitude >= MAX_MAGNITUDE {
+        return BUCKET_COUNT - 1;
+    }
+    let shift = magnitude - SUB_BUCKET_BITS;
+    let sub_bucket = (value >> shift) as usize - SUB_BUCKET_COUNT;
+    SUB_BUCKET_COUNT + shift as usize * SUB_BUCKET_COUNT + sub_bucket
+}
+
+/// Largest value that maps to a bucket
+fn bucket_upper_bound(index: usize) -> u64 {
+    if index < SUB_BUCKET_COUNT << 1 {
+        return index as u64;
+    }
+    if index == BUCKET_COUNT - 1 {
+        return u64::MAX;
+    }
+    let shift = ((index - SUB_BUCKET_COUNT) / SUB_BUCKET_COUNT) as u32;
+    let sub_bucket = ((index - SUB_BUCKET_COUNT) % SUB_BUCKET_COUNT + SUB_BUCKET_COUNT) as u64;
+    ((sub_bucket + 1) << shift) - 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn buckets_are_contiguous_and_bounded() {
+        let mut previous = 0;
+        for value in 0..200_000u64 {
+            let index = bucket_index(value);
+            assert!(index >= previous);
+            previous = index;
+            let upper = bucket_upper_bound(index);
+            assert!(upper >= value);
+            // Relative error stays within one sub-bucket
+            assert!(upper - value <= value / SUB_BUCKET_COUNT as u64);
+        }
+        assert_eq!(bucket_index(u64::MAX), BUCKET_COUNT - 1);
+    }
+
+    #[test]
+    fn quantiles_match_uniform_distribution() {
+        let mut histogram = LatencyHistogram::new();
+        for value in 1..=10_000 {
+            histogram.record(value);
+        }
+
+        let summary = histogram.summary();
+        assert_eq!(summary.count, 10_000);
+        assert_eq!(summary.max, 10_000);
+        for (actual, expected) in [
+            (summary.p50, 5_000),
+            (summary.p90, 9_000),
+            (summary.p99, 9_900),
+            (summary.p999, 9_990),
+        ] {
+            assert!(actual >= expected);
+            assert!(
+                actual - expected <= expected / 32,
+                "{} vs {}",
+                actual,
+                expected
+            );
+        }
+        assert_eq!(LatencyHistogram::new().summary(), LatencySummary::default());
+    }
+
+    #[test]
+    fn merge_combines_symbols() {
+        let mut fast = LatencyHistogram::new();
+        let mut slow = LatencyHistogram::new();
+        for _ in 0..90 {
+            fast.record(5);
+        }
+        for _ in 0..10 {
+            slow.record(500);
+        }
+
+        fast.merge(&slow);
+        assert_eq!(fast.len(), 100);
+        assert_eq!(fast.value_at_quantile(0.5), 5);
+        assert!(fast.value_at_quantile(0.95) >= 500);
+        assert_eq!(fast.max(), 500);
+    }
+
+    #[test]
+    fn rolling_windows_expire_old_slots() {
+        let mut rolling = RollingHistogram::new();
+        let start = 1_700_000_000_000;
+        rolling.record(900, start);
+        rolling.record(10, start + 4 * 60_000);
+        rolling.record(20, start + 10 * 60_000);
+
+        let now = start + 10 * 60_000;
+        assert_eq!(rolling.summary(LatencyWindow::OneMinute, now).count, 1);
+        assert_eq!(rolling.summary(LatencyWindow::FiveMinutes, now).max, 20);
+        let hour = rolling.summary(LatencyWindow::OneHour, now);
+        assert_eq!(hour.count, 3);
+        assert_eq!(hour.max, 900);
+
+        let later = start + 2 * 3_600_000;
+        assert_eq!(rolling.summary(LatencyWindow::OneHour, later).count, 0);
+    }
+}
@@ -2,6 +2,15 @@
 //!
 //! Provides performance metrics, latency measurement, and connection monitoring.
 
+mod clock_sync;
+pub mod exporter;
+mod histogram;
+mod symbol_metrics;
+
+pub use clock_sync::{ClockEstimate, ClockSample, ClockSync, DEFAULT_CLOCK_SAMPLES};
+pub use histogram::{LatencyHistogram, LatencySummary, LatencyWindow, RollingHistogram};
+pub use symbol_metrics::{StreamKind, StreamMetrics, SymbolMetrics, SymbolMetricsTracker};
+
 use std::collections::VecDeque;
 use std::time::{SystemTime, UNIX_EPOCH};
 
@@ -15,6 +24,21 @@ pub enum ConnectionStatus {
     Error(String),
 }
 
+impl From<crate::binance::types::ConnectionStatus> for ConnectionStatus {
+    fn from(status: crate::binance::types::ConnectionStatus) -> Self {
+        match status {
+            crate::binance::types::ConnectionStatus::Disconnected => Self::Disconnected,
+            crate::binance::types::ConnectionStatus::Connecting => Self::Connecting,
+            crate::binance::types::ConnectionStatus::Connected => Self::Connected,
+            crate::binance::types::ConnectionStatus::Reconnecting => Self::Reconnecting,
+            crate::binance::types::ConnectionStatus::Error(err) => Self::Error(err),
+        }
+    }
+}
+
+/// Default clock offset, in milliseconds, beyond which skew is reported
+pub const DEFAULT_CLOCK_SKEW_WARN_MS: u64 = 1_000;
+
 /// Connection quality levels
 #[derive(Debug, Clone, PartialEq)]
 pub enum ConnectionQualityLevel {
@@ -39,6 +63,14 @@ pub struct ConnectionMetrics {
     pub uptime_seconds: u64,
     pub total_messages: u64,
     pub error_count: u32,
+    /// Latency summaries over the rolling 1m/5m/1h windows
+    pub latency_windows: Vec<(LatencyWindow, LatencySummary)>,
+    /// Exchange clock offset applied to latencies, once measured
+    pub clock: Option<ClockEstimate>,
+    /// Whether the clock offset exceeds the configured warning threshold
+    pub clock_skewed: bool,
+    /// Breakdown by symbol, ordered by symbol name
+    pub symbols: Vec<SymbolMetrics>,
 }
 
 impl Default for ConnectionMetrics {
@@ -55,37 +87,70 @@ impl Default for ConnectionMetrics {
             uptime_seconds: 0,
             total_messages: 0,
             error_count: 0,
+            latency_windows: Vec::new(),
+            clock: None,
+            clock_skewed: false,
+            symbols: Vec::new(),
         }
     }
 }
 
 /// Metrics collector for performance monitoring
 pub struct MetricsCollector {
-    latency_samples: Vec<u64>,
+    latency: RollingHistogram,
     message_count: u64,
     last_reset: SystemTime,
-    max_samples: usize,
     connection_start_time: SystemTime,
     error_count: u32,
     reconnect_count: u32,
     message_history: VecDeque<u64>, // Timestamps of last 1000 messages
+    symbols: SymbolMetricsTracker,
+    clock: Option<ClockEstimate>,
+    clock_skew_warn_ms: u64,
+}
+
+impl Default for MetricsCollector {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl MetricsCollector {
     /// Create a new metrics collector
-    pub fn new(max_samples: usize) -> Self {
+    pub fn new() -> Self {
         Self {
-            latency_samples: Vec::with_capacity(max_samples),
+            latency: RollingHistogram::new(),
             message_count: 0,
             last_reset: SystemTime::now(),
-            max_samples,
             connection_start_time: SystemTime::now(),
             error_count: 0,
             reconnect_count: 0,
             message_history: VecDeque::with_capacity(1000),
+            symbols: SymbolMetricsTracker::new(),
+            clock: None,
+            clock_skew_warn_ms: DEFAULT_CLOCK_SKEW_WARN_MS,
         }
     }
 
+    /// Offset beyond which the clock is reported as skewed
+    pub fn set_clock_skew_warning(&mut self, threshold_ms: u64) {
+        self.clock_skew_warn_ms = threshold_ms;
+    }
+
+    /// Apply a new exchange clock offset to subsequent latency samples
+    pub fn set_clock_estimate(&mut self, estimate: ClockEstimate) {
+        self.clock = Some(estimate);
+    }
+
+    /// Latency from an exchange event time to now, corrected for clock offset
+    fn latency_since(&self, event_time: u64, now: u64) -> u64 {
+        let exchange_now = match &self.clock {
+            Some(clock) => clock.exchange_time(now),
+            None => now,
+        };
+        exchange_now.saturating_sub(event_time)
+    }
+
     /// Record connection start time
     pub fn record_connection_start(&mut self) {
         self.connection_start_time = SystemTime::now();
@@ -99,17 +164,26 @@ impl MetricsCollector {
 
     /// Record message latency
     pub fn record_message_latency(&mut self, event_time: u64) {
-        let now = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap()
-            .as_millis() as u64;
-        let latency = now.saturating_sub(event_time);
+        let latency = self.record_latency_sample(event_time);
+        metrics::histogram!(exporter::MESSAGE_LATENCY_MS, latency as f64);
+    }
 
-        // Add to samples, maintain max size
-        if self.latency_samples.len() >= self.max_samples {
-            self.latency_samples.remove(0);
-        }
-        self.latency_samples.push(latency);
+    /// Record message latency for a specific symbol, returning the corrected latency
+    pub fn record_symbol_latency(&mut self, symbol: &str, event_time: u64) -> u64 {
+        let latency = self.record_latency_sample(event_time);
+        metrics::histogram!(
+            exporter::MESSAGE_LATENCY_MS,
+            latency as f64,
+            "symbol" => symbol.to_string()
+        );
+        latency
+    }
+
+    fn record_latency_sample(&mut self, event_time: u64) -> u64 {
+        let now = now_ms();
+        let latency = self.latency_since(event_time, now);
+
+        self.latency.record(latency, now);
         self.message_count += 1;
 
         // Record message timestamp for rate calculation
@@ -118,30 +192,18 @@ impl MetricsCollector {
         }
         self.message_history.push_back(now);
 
-        // Record metrics (placeholder for actual metrics implementation)
-        // metrics::histogram!("message_latency_ms").record(latency as f64);
-        // metrics::counter!("messages_received").increment(1);
+        latency
     }
 
-    /// Calculate latency percentiles
+    /// Calculate latency percentiles over the last minute
     pub fn calculate_percentiles(&self) -> (u64, u64, u64) {
-        if self.latency_samples.is_empty() {
-            return (0, 0, 0);
-        }
-
-        let mut sorted = self.latency_samples.clone();
-        sorted.sort_unstable();
-
-        let len = sorted.len();
-        let p50_idx = (len * 50) / 100;
-        let p95_idx = (len * 95) / 100;
-        let p99_idx = (len * 99) / 100;
-
-        let p50 = sorted.get(p50_idx).copied().unwrap_or(0);
-        let p95 = sorted.get(p95_idx.min(len - 1)).copied().unwrap_or(0);
-        let p99 = sorted.get(p99_idx.min(len - 1)).copied().unwrap_or(0);
+        let summary = self.latency_summary(LatencyWindow::OneMinute);
+        (summary.p50, summary.p95, summary.p99)
+    }
 
-        (p50, p95, p99)
+    /// Latency percentiles over a rolling window
+    pub fn latency_summary(&self, window: LatencyWindow) -> LatencySummary {
+        self.latency.summary(window, now_ms())
     }
 
     /// Calculate messages per second
@@ -155,7 +217,7 @@ impl MetricsCollector {
 
     /// Reset metrics
     pub fn reset(&mut self) {
-        self.latency_samples.clear();
+        self.latency.clear();
         self.message_count = 0;
         self.message_history.clear();
         self.last_reset = SystemTime::now();
@@ -204,7 +266,12 @@ impl MetricsCollector {
 
     /// Get comprehensive connection metrics
     pub fn get_connection_metrics(&self, status: ConnectionStatus) -> ConnectionMetrics {
-        let (p50, p95, p99) = self.calculate_percentiles();
+        let now = now_ms();
+        let latency_windows: Vec<(LatencyWindow, LatencySummary)> = LatencyWindow::ALL
+            .iter()
+            .map(|window| (*window, self.latency.summary(*window, now)))
+            .collect();
+        let one_minute = latency_windows[0].1;
         let uptime = self
             .connection_start_time
             .elapsed()
@@ -213,9 +280,9 @@ impl MetricsCollector {
 
         ConnectionMetrics {
             status,
-            latency_p50: p50,
-            latency_p95: p95,
-            latency_p99: p99,
+            latency_p50: one_minute.p50,
+            latency_p95: one_minute.p95,
+            latency_p99: one_minute.p99,
             reconnect_count: self.reconnect_count,
             last_message_time: self.message_history.back().copied().unwrap_or(0),
             messages_per_second: self.messages_per_second(),
@@ -223,6 +290,12 @@ impl MetricsCollector {
             uptime_seconds: uptime,
             total_messages: self.message_count,
             error_count: self.error_count,
+            latency_windows,
+            clock: self.clock,
+            clock_skewed: self
+                .clock
+                .is_some_and(|clock| clock.exceeds(self.clock_skew_warn_ms)),
+            symbols: self.symbols.snapshot(now),
         }
     }
 
@@ -243,17 +316,64 @@ impl MetricsCollector {
         Ok(())
     }
 
+    /// Combined connection status across all tracked symbols
+    pub fn overall_status(&self) -> ConnectionStatus {
+        self.symbols.overall_status()
+    }
+
+    /// Stop tracking a symbol after it is unsubscribed
+    pub fn remove_symbol(&mut self, symbol: &str) {
+        self.symbols.remove(symbol);
+    }
+
     /// Handle market event for metrics collection
     pub async fn handle_market_event(
         &mut self,
         event: crate::market_data::MarketEvent,
     ) -> anyhow::Result<()> {
+        use crate::market_data::MarketEvent;
+
+        let now = now_ms();
         match event {
-            crate::market_data::MarketEvent::PriceUpdate { time, .. } => {
-                self.record_message_latency(time);
+            MarketEvent::PriceUpdate { symbol, time, .. } => {
+                let latency = self.record_symbol_latency(&symbol, time);
+                self.symbols
+                    .record_message(&symbol, StreamKind::Trade, Some(latency), now);
+            }
+            MarketEvent::OrderBookUpdate { symbol, orderbook } => {
+                // Snapshots carry a local timestamp rather than an exchange event time
+                let latency = (orderbook.last_update_time != orderbook.snapshot_time
+                    && orderbook.last_update_time > 0)
+                    .then(|| self.latency_since(orderbook.last_update_time, now));
+                self.symbols
+                    .record_message(&symbol, StreamKind::Depth, latency, now);
+            }
+            MarketEvent::TickerUpdate { symbol, .. } => {
+                self.symbols
+                    .record_message(&symbol, StreamKind::Ticker, None, now);
+            }
+            MarketEvent::BookTicker { symbol, .. } => {
+                // Spot book tickers carry no event time
+                self.symbols
+                    .record_message(&symbol, StreamKind::BookTicker, None, now);
+            }
+            MarketEvent::DailyCandleUpdate {
+                symbol,
+                is_snapshot: false,
+                ..
+            } => {
+                self.symbols
+                    .record_message(&symbol, StreamKind::Kline, None, now);
+            }
+            MarketEvent::ConnectionStatus { symbol, status } => {
+                self.symbols.set_status(&symbol, status.into());
             }
-            crate::market_data::MarketEvent::Error { .. } => {
+            MarketEvent::ClockOffset { estimate } => {
+                self.set_clock_estimate(estimate);
+            }
+            MarketEvent::Error { symbol, .. } => {
                 self.record_error();
+                self.symbols.record_error(&symbol);
             }
             _ => {
                 // Other events don't affect metrics directly
@@ -269,13 +389,20 @@ impl MetricsCollector {
     }
 }
 
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn test_metrics_collector() {
-        let mut collector = MetricsCollector::new(100);
+        let mut collector = MetricsCollector::new();
 
         // Record some latencies
         let now = SystemTime::now()
@@ -293,6 +420,27 @@ mod tests {
         assert!(p99 >= p95);
     }
 
+    #[test]
+    fn latency_is_corrected_for_clock_offset() {
+        let mut collector = MetricsCollector::new();
+        collector.set_clock_skew_warning(100);
+        // Local clock runs 500ms ahead of the exchange
+        collector.set_clock_estimate(ClockEstimate {
+            offset_ms: -500,
+            rtt_ms: 30,
+            samples: 4,
+            updated_at_ms: 0,
+        });
+
+        collector.record_message_latency(now_ms() - 510);
+        let (p50, _, _) = collector.calculate_percentiles();
+        assert!((10..100).contains(&p50), "p50 {}", p50);
+
+        let metrics = collector.get_connection_metrics(ConnectionStatus::Connected);
+        assert_eq!(metrics.clock.map(|c| c.offset_ms), Some(-500));
+        assert!(metrics.clock_skewed);
+    }
+
     #[test]
     fn test_connection_metrics_default() {
         let metrics = ConnectionMetrics::default();
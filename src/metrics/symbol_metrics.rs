@@ -0,0 +1,341 @@
+//! Per-symbol and per-stream message statistics
+
+use std::collections::{BTreeMap, VecDeque};
+
+use super::ConnectionStatus;
+use super::histogram::{LatencyHistogram, LatencySummary, LatencyWindow, RollingHistogram};
+
+/// Window used for message rate calculation
+const RATE_WINDOW_MS: u64 = 10_000;
+/// Window the per-symbol and per-stream latency columns are computed over
+const TABLE_LATENCY_WINDOW: LatencyWindow = LatencyWindow::OneMinute;
+
+/// Market data stream a message arrived on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum StreamKind {
+    Depth,
+    BookTicker,
+    Trade,
+    Ticker,
+    Kline,
+}
+
+impl StreamKind {
+    pub const ALL: [StreamKind; 5] = [
+        StreamKind::Depth,
+        StreamKind::BookTicker,
+        StreamKind::Trade,
+        StreamKind::Ticker,
+        StreamKind::Kline,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            StreamKind::Depth => "depth",
+            StreamKind::BookTicker => "bookTicker",
+            StreamKind::Trade => "trade",
+            StreamKind::Ticker => "ticker",
+            StreamKind::Kline => "kline",
+        }
+    }
+}
+
+/// Snapshot of a single stream for one symbol
+#[derive(Debug, Clone, PartialEq)]
+pub struct StreamMetrics {
+    pub kind: StreamKind,
+    pub total_messages: u64,
+    pub messages_per_second: f64,
+    /// Latency over the last minute
+    pub latency: LatencySummary,
+    /// Milliseconds since the last message, `None` if nothing arrived yet
+    pub last_message_age_ms: Option<u64>,
+}
+
+/// Snapshot of one symbol across all of its streams
+#[derive(Debug, Clone, PartialEq)]
+pub struct SymbolMetrics {
+    pub symbol: String,
+    pub status: ConnectionStatus,
+    pub total_messages: u64,
+    pub messages_per_second: f64,
+    /// Latency over the last minute, merged across streams
+    pub latency: LatencySummary,
+    pub last_message_age_ms: Option<u64>,
+    pub reconnect_count: u32,
+    pub error_count: u32,
+    pub streams: Vec<StreamMetrics>,
+}
+
+#[derive(Debug, Default)]
+struct StreamTracker {
+    total_messages: u64,
+    message_times: VecDeque<u64>,
+    /// Allocated on the first sample; ticker and kline streams carry no event time
+    latency: Option<RollingHistogram>,
+    last_message_ms: Option<u64>,
+}
+
+impl StreamTracker {
+    fn record(&mut self, now_ms: u64, latency_ms: Option<u64>) {
+        self.total_messages += 1;
+        self.last_message_ms = Some(now_ms);
+        self.message_times.push_back(now_ms);
+        self.prune(now_ms);
+
+        if let Some(latency) = latency_ms {
+            self.latency
+                .get_or_insert_with(RollingHistogram::new)
+                .record(latency, now_ms);
+        }
+    }
+
+    fn prune(&mut self, now_ms: u64) {
+        let cutoff = now_ms.saturating_sub(RATE_WINDOW_MS);
+        while self.message_times.front().is_some_and(|&t| t < cutoff) {
+            self.message_times.pop_front();
+        }
+    }
+
+    fn recent_count(&self, now_ms: u64) -> usize {
+        let cutoff = now_ms.saturating_sub(RATE_WINDOW_MS);
+        self.message_times.iter().filter(|&&t| t >= cutoff).count()
+    }
+
+    fn merge_latency(&self, window: LatencyWindow, now_ms: u64, into: &mut LatencyHistogram) {
+        if let Some(latency) = &self.latency {
+            latency.merge_window_into(window, now_ms, into);
+        }
+    }
+
+    fn snapshot(&self, kind: StreamKind, now_ms: u64) -> StreamMetrics {
+        let mut latency = LatencyHistogram::new();
+        self.merge_latency(TABLE_LATENCY_WINDOW, now_ms, &mut latency);
+        StreamMetrics {
+            kind,
+            total_messages: self.total_messages,
+            messages_per_second: rate(self.recent_count(now_ms)),
+            latency: latency.summary(),
+            last_message_age_ms: self.last_message_ms.map(|t| now_ms.saturating_sub(t)),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct SymbolTracker {
+    status: ConnectionStatus,
+    streams: BTreeMap<StreamKind, StreamTracker>,
+    reconnect_count: u32,
+    error_count: u32,
+}
+
+impl Default for SymbolTracker {
+    fn default() -> Self {
+        Self {
+            status: ConnectionStatus::Connecting,
+            streams: BTreeMap::new(),
+            reconnect_count: 0,
+            error_count: 0,
+        }
+    }
+}
+
+impl SymbolTracker {
+    fn merge_latency(&self, window: LatencyWindow, now_ms: u64, into: &mut LatencyHistogram) {
+        for stream in self.streams.values() {
+            stream.merge_latency(window, now_ms, into);
+        }
+    }
+
+    fn snapshot(&self, symbol: &str, now_ms: u64) -> SymbolMetrics {
+        let streams: Vec<StreamMetrics> = self
+            .streams
+            .iter()
+            .map(|(kind, tracker)| tracker.snapshot(*kind, now_ms))
+            .collect();
+
+        let mut latency = LatencyHistogram::new();
+        self.merge_latency(TABLE_LATENCY_WINDOW, now_ms, &mut latency);
+        let recent: usize = self
+            .streams
+            .values()
+            .map(|tracker| tracker.recent_count(now_ms))
+            .sum();
+        let last_message = self
+            .streams
+            .values()
+            .filter_map(|tracker| tracker.last_message_ms)
+            .max();
+
+        SymbolMetrics {
+            symbol: symbol.to_string(),
+            status: self.status.clone(),
+            total_messages: streams.iter().map(|s| s.total_messages).sum(),
+            messages_per_second: rate(recent),
+            latency: latency.summary(),
+            last_message_age_ms: last_message.map(|t| now_ms.saturating_sub(t)),
+            reconnect_count: self.reconnect_count,
+            error_count: self.error_count,
+            streams,
+        }
+    }
+}
+
+/// Tracks message statistics keyed by symbol and stream
+#[derive(Debug, Default)]
+pub struct SymbolMetricsTracker {
+    symbols: BTreeMap<String, SymbolTracker>,
+}
+
+impl SymbolMetricsTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a message; `latency_ms` is present when the stream carries an exchange event time
+    pub fn record_message(
+        &mut self,
+        symbol: &str,
+        kind: StreamKind,
+        latency_ms: Option<u64>,
+        now_ms: u64,
+    ) {
+        self.symbol_mut(symbol)
+            .streams
+            .entry(kind)
+            .or_default()
+            .record(now_ms, latency_ms);
+    }
+
+    /// Update the connection status for a symbol, counting reconnect attempts
+    pub fn set_status(&mut self, symbol: &str, status: ConnectionStatus) {
+        let tracker = self.symbol_mut(symbol);
+        if status == ConnectionStatus::Reconnecting && tracker.status != status {
+            tracker.reconnect_count += 1;
+        }
+        tracker.status = status;
+    }
+
+    /// Count an error reported for a symbol
+    pub fn record_error(&mut self, symbol: &str) {
+        self.symbol_mut(symbol).error_count += 1;
+    }
+
+    /// Forget a symbol after it is unsubscribed
+    pub fn remove(&mut self, symbol: &str) {
+        self.symbols.remove(symbol);
+    }
+
+    /// Combined status: healthy only when every symbol is connected
+    pub fn overall_status(&self) -> ConnectionStatus {
+        let statuses: Vec<&ConnectionStatus> = self.symbols.values().map(|t| &t.status).collect();
+        if statuses.is_empty() {
+            return ConnectionStatus::Disconnected;
+        }
+
+        if let Some(error) = statuses
+            .iter()
+            .find(|s| matches!(s, ConnectionStatus::Error(_)))
+        {
+            return (*error).clone();
+        }
+
+        for candidate in [
+            ConnectionStatus::Reconnecting,
+            ConnectionStatus::Disconnected,
+            ConnectionStatus::Connecting,
+        ] {
+            if statuses.iter().any(|s| **s == candidate) {
+                return candidate;
+            }
+        }
+
+        ConnectionStatus::Connected
+    }
+
+    /// Latency across every symbol and stream over a window
+    pub fn latency_summary(&self, window: LatencyWindow, now_ms: u64) -> LatencySummary {
+        let mut merged = LatencyHistogram::new();
+        for tracker in self.symbols.values() {
+            tracker.merge_latency(window, now_ms, &mut merged);
+        }
+        merged.summary()
+    }
+
+    /// Snapshot every tracked symbol, ordered by symbol name
+    pub fn snapshot(&self, now_ms: u64) -> Vec<SymbolMetrics> {
+        self.symbols
+            .iter()
+            .map(|(symbol, tracker)| tracker.snapshot(symbol, now_ms))
+            .collect()
+    }
+
+    fn symbol_mut(&mut self, symbol: &str) -> &mut SymbolTracker {
+        self.symbols.entry(symbol.to_string()).or_default()
+    }
+}
+
+fn rate(recent: usize) -> f64 {
+    recent as f64 / (RATE_WINDOW_MS as f64 / 1_000.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn breaks_down_by_symbol_and_stream() {
+        let mut tracker = SymbolMetricsTracker::new();
+        let now = 1_000_000;
+        tracker.set_status("BTCUSDT", ConnectionStatus::Connected);
+        tracker.record_message("BTCUSDT", StreamKind::Trade, Some(20), now);
+        tracker.record_message("BTCUSDT", StreamKind::Trade, Some(40), now);
+        tracker.record_message("BTCUSDT", StreamKind::Ticker, None, now - 5_000);
+        tracker.record_message("ETHUSDT", StreamKind::Depth, Some(5), now - 20_000);
+
+        let snapshot = tracker.snapshot(now);
+        assert_eq!(snapshot.len(), 2);
+
+        let btc = &snapshot[0];
+        assert_eq!(btc.symbol, "BTCUSDT");
+        assert_eq!(btc.total_messages, 3);
+        assert!((btc.messages_per_second - 0.3).abs() < 1e-9);
+        assert_eq!(btc.latency.p99, 40);
+        assert_eq!(btc.latency.count, 2);
+        assert_eq!(btc.last_message_age_ms, Some(0));
+        assert_eq!(btc.streams.len(), 2);
+        let ticker = btc
+            .streams
+            .iter()
+            .find(|s| s.kind == StreamKind::Ticker)
+            .unwrap();
+        assert_eq!(ticker.latency, LatencySummary::default());
+        assert_eq!(ticker.last_message_age_ms, Some(5_000));
+
+        let eth = &snapshot[1];
+        assert_eq!(eth.messages_per_second, 0.0);
+        assert_eq!(eth.last_message_age_ms, Some(20_000));
+
+        let merged = tracker.latency_summary(LatencyWindow::OneMinute, now);
+        assert_eq!(merged.count, 3);
+        assert_eq!(merged.max, 40);
+    }
+
+    #[test]
+    fn overall_status_reflects_worst_symbol() {
+        let mut tracker = SymbolMetricsTracker::new();
+        assert_eq!(tracker.overall_status(), ConnectionStatus::Disconnected);
+
+        tracker.set_status("BTCUSDT", ConnectionStatus::Connected);
+        tracker.set_status("ETHUSDT", ConnectionStatus::Connected);
+        assert_eq!(tracker.overall_status(), ConnectionStatus::Connected);
+
+        tracker.set_status("ETHUSDT", ConnectionStatus::Reconnecting);
+        tracker.set_status("ETHUSDT", ConnectionStatus::Reconnecting);
+        assert_eq!(tracker.overall_status(), ConnectionStatus::Reconnecting);
+        assert_eq!(tracker.snapshot(0)[1].reconnect_count, 1);
+
+        tracker.remove("ETHUSDT");
+        assert_eq!(tracker.overall_status(), ConnectionStatus::Connected);
+    }
+}
@@ -0,0 +1,1298 @@
+//! Paper trading against the live order book
+//!
+//! Orders never leave the process. Market orders and triggered stops walk the
+//! local `OrderBook`; resting limit orders join the back of the queue at their
+//! price and fill once the trade stream has printed through the quantity that
+//! was displayed ahead of them, or at once when the market trades through the
+//! price. Balances, orders, fills and positions are written to one JSON
+//! document after every change so the next session picks up where this one
+//! ended.
+//!
+//! Simulated fills do not remove liquidity from the local book, so two market
+//! orders in quick succession see the same depth.
+
+use anyhow::{Context, Result};
+use ordered_float::OrderedFloat;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, VecDeque};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tracing::warn;
+
+use crate::binance::SymbolInfo;
+use crate::binance::types::OrderBook;
+use crate::config::PaperConfig;
+use crate::market_data::{TakerSide, TradePrint};
+
+/// Quote assets recognised in a symbol name before exchange info has loaded
+const KNOWN_QUOTE_ASSETS: [&str; 10] = [
+    "USDT", "FDUSD", "USDC", "TUSD", "BUSD", "BTC", "ETH", "BNB", "EUR", "TRY",
+];
+
+/// Quantities at or below this are treated as nothing left to fill
+const QTY_EPSILON: f64 = 1e-12;
+
+/// Relative tolerance when matching a print to an order price
+const PRICE_EPSILON: f64 = 1e-9;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OrderSide {
+    Buy,
+    Sell,
+}
+
+impl OrderSide {
+    pub fn label(self) -> &'static str {
+        match self {
+            OrderSide::Buy => "BUY",
+            OrderSide::Sell => "SELL",
+        }
+    }
+
+    /// Whether a level at `price` is acceptable for an order limited to `limit`
+    fn accepts(self, price: f64, limit: f64) -> bool {
+        match self {
+            OrderSide::Buy => price <= limit || same_price(price, limit),
+            OrderSide::Sell => price >= limit || same_price(price, limit),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum OrderType {
+    Market,
+    Limit {
+        price: f64,
+    },
+    /// Stop-market: executes against the book once the last trade reaches `trigger`
+    Stop {
+        trigger: f64,
+    },
+}
+
+impl OrderType {
+    /// Short description such as `market`, `limit 65000` or `stop 70000`
+    pub fn describe(&self) -> String {
+        match self {
+            OrderType::Market => "market".to_string(),
+            OrderType::Limit { price } => format!("limit {}", price),
+            OrderType::Stop { trigger } => format!("stop {}", trigger),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OrderStatus {
+    Open,
+    Filled,
+    Cancelled,
+    Rejected,
+}
+
+impl OrderStatus {
+    pub fn label(self) -> &'static str {
+        match self {
+            OrderStatus::Open => "open",
+            OrderStatus::Filled => "filled",
+            OrderStatus::Cancelled => "cancelled",
+            OrderStatus::Rejected => "rejected",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Liquidity {
+    Maker,
+    Taker,
+}
+
+impl Liquidity {
+    pub fn label(self) -> &'static str {
+        match self {
+            Liquidity::Maker => "maker",
+            Liquidity::Taker => "taker",
+        }
+    }
+}
+
+/// A paper order as entered by the user
+#[derive(Debug, Clone, PartialEq)]
+pub struct OrderRequest {
+    pub symbol: String,
+    pub side: OrderSide,
+    pub order_type: OrderType,
+    pub quantity: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PaperOrder {
+    pub id: u64,
+    pub symbol: String,
+    pub base_asset: String,
+    pub quote_asset: String,
+    pub side: OrderSide,
+    pub order_type: OrderType,
+    pub quantity: f64,
+    pub filled_qty: f64,
+    /// Volume-weighted fill price, 0 until the first fill
+    pub avg_fill_price: f64,
+    pub status: OrderStatus,
+    /// Why the order was rejected or ended before filling completely
+    #[serde(default)]
+    pub note: Option<String>,
+    /// Displayed quantity estimated to be ahead of a resting limit order
+    #[serde(default)]
+    pub queue_ahead: f64,
+    /// Funds locked for the unfilled part: quote for buys, base for sells
+    #[serde(default)]
+    pub reserved: f64,
+    pub created_ms: u64,
+    pub updated_ms: u64,
+}
+
+impl PaperOrder {
+    pub fn remaining(&self) -> f64 {
+        (self.quantity - self.filled_qty).max(0.0)
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.status == OrderStatus::Open
+    }
+
+    /// Asset that funds the order: quote for buys, base for sells
+    fn funding_asset(&self) -> &str {
+        match self.side {
+            OrderSide::Buy => &self.quote_asset,
+            OrderSide::Sell => &self.base_asset,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PaperFill {
+    pub order_id: u64,
+    pub symbol: String,
+    pub side: OrderSide,
+    pub price: f64,
+    pub quantity: f64,
+    /// Fee charged in the quote asset
+    pub fee: f64,
+    pub quote_asset: String,
+    pub liquidity: Liquidity,
+    /// PnL realized by a sell, net of fees; 0 for buys
+    pub realized_pnl: f64,
+    pub time_ms: u64,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct Balance {
+    pub free: f64,
+    pub locked: f64,
+}
+
+impl Balance {
+    pub fn total(&self) -> f64 {
+        self.free + self.locked
+    }
+}
+
+/// Holdings bought through paper fills, with their cost basis
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Position {
+    pub symbol: String,
+    pub base_asset: String,
+    pub quote_asset: String,
+    pub quantity: f64,
+    /// Average cost per unit, including buy fees
+    pub avg_entry: f64,
+    pub realized_pnl: f64,
+    pub fees_paid: f64,
+    /// Last traded or mid price seen for the symbol
+    #[serde(default)]
+    pub mark_price: Option<f64>,
+}
+
+impl Position {
+    fn new(order: &PaperOrder) -> Self {
+        Self {
+            symbol: order.symbol.clone(),
+            base_asset: order.base_asset.clone(),
+            quote_asset: order.quote_asset.clone(),
+            quantity: 0.0,
+            avg_entry: 0.0,
+            realized_pnl: 0.0,
+            fees_paid: 0.0,
+            mark_price: None,
+        }
+    }
+
+    /// PnL of the open quantity if it were sold at `mark`, before exit fees
+    pub fn unrealized_pnl(&self, mark: f64) -> f64 {
+        (mark - self.avg_entry) * self.quantity
+    }
+}
+
+/// Everything the positions panel needs, newest fills first
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PaperSnapshot {
+    pub balances: Vec<(String, Balance)>,
+    pub positions: Vec<Position>,
+    pub open_orders: Vec<PaperOrder>,
+    pub recent_fills: Vec<PaperFill>,
+}
+
+/// Persisted account document
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct PaperState {
+    balances: BTreeMap<String, Balance>,
+    positions: BTreeMap<String, Position>,
+    open_orders: Vec<PaperOrder>,
+    /// Closed orders, oldest first
+    closed_orders: VecDeque<PaperOrder>,
+    /// Fills, oldest first
+    fills: VecDeque<PaperFill>,
+    next_order_id: u64,
+}
+
+impl PaperState {
+    fn fresh(starting_balances: &BTreeMap<String, f64>) -> Self {
+        Self {
+            balances: starting_balances
+                .iter()
+                .map(|(asset, amount)| {
+                    (
+                        asset.to_ascii_uppercase(),
+                        Balance {
+                            free: *amount,
+                            locked: 0.0,
+                        },
+                    )
+                })
+                .collect(),
+            next_order_id: 1,
+            ..Self::default()
+        }
+    }
+
+    fn free(&self, asset: &str) -> f64 {
+        self.balances.get(asset).map_or(0.0, |balance| balance.free)
+    }
+
+    /// Move `amount` of an asset between free and locked
+    fn lock(&mut self, asset: &str, amount: f64) {
+        let balance = self.balances.entry(asset.to_string()).or_default();
+        balance.free -= amount;
+        balance.locked += amount;
+    }
+
+    fn unlock(&mut self, asset: &str, amount: f64) {
+        self.lock(asset, -amount);
+    }
+
+    /// Release the order's whole reservation back to free balance
+    fn release_reservation(&mut self, order: &mut PaperOrder) {
+        if order.reserved > 0.0 {
+            let asset = order.funding_asset().to_string();
+            self.unlock(&asset, order.reserved);
+            order.reserved = 0.0;
+        }
+    }
+
+    /// Book one fill against balances and the position, returning the fill record
+    fn apply_fill(
+        &mut self,
+        order: &mut PaperOrder,
+        price: f64,
+        quantity: f64,
+        liquidity: Liquidity,
+        fee_rate: f64,
+        now_ms: u64,
+    ) -> PaperFill {
+        // Release the part of the reservation that covered this quantity
+        if order.reserved > 0.0 {
+            let share = (quantity / order.remaining()).min(1.0);
+            let release = order.reserved * share;
+            let asset = order.funding_asset().to_string();
+            self.unlock(&asset, release);
+            order.reserved -= release;
+        }
+
+        let notional = price * quantity;
+        let fee = notional * fee_rate;
+        let position = self
+            .positions
+            .entry(order.symbol.clone())
+            .or_insert_with(|| Position::new(order));
+        position.fees_paid += fee;
+        position.mark_price = Some(price);
+
+        let realized_pnl = match order.side {
+            OrderSide::Buy => {
+                let cost = notional + fee;
+                let held = position.quantity;
+                position.avg_entry = (position.avg_entry * held + cost) / (held + quantity);
+                position.quantity += quantity;
+                self.balances
+                    .entry(order.quote_asset.clone())
+                    .or_default()
+                    .free -= cost;
+                self.balances
+                    .entry(order.base_asset.clone())
+                    .or_default()
+                    .free += quantity;
+                0.0
+            }
+            OrderSide::Sell => {
+                // Only quantity bought on paper has a cost basis; selling
+                // starting balances realizes nothing.
+                let covered = quantity.min(position.quantity);
+                let proceeds = notional - fee;
+                let realized = proceeds * (covered / quantity) - position.avg_entry * covered;
+                position.quantity -= covered;
+                if position.quantity <= QTY_EPSILON {
+                    position.quantity = 0.0;
+                    position.avg_entry = 0.0;
+                }
+                position.realized_pnl += realized;
+                self.balances
+                    .entry(order.base_asset.clone())
+                    .or_default()
+                    .free -= quantity;
+                self.balances
+                    .entry(order.quote_asset.clone())
+                    .or_default()
+                    .free += proceeds;
+                realized
+            }
+        };
+
+        let filled = order.filled_qty + quantity;
+        order.avg_fill_price = (order.avg_fill_price * order.filled_qty + notional) / filled;
+        order.filled_qty = filled;
+        order.updated_ms = now_ms;
+        if order.remaining() <= QTY_EPSILON {
+            order.status = OrderStatus::Filled;
+            self.release_reservation(order);
+        }
+
+        PaperFill {
+            order_id: order.id,
+            symbol: order.symbol.clone(),
+            side: order.side,
+            price,
+            quantity,
+            fee,
+            quote_asset: order.quote_asset.clone(),
+            liquidity,
+            realized_pnl,
+            time_ms: now_ms,
+        }
+    }
+}
+
+/// Simulated exchange account
+#[derive(Debug, Clone)]
+pub struct PaperEngine {
+    config: PaperConfig,
+    state: PaperState,
+    path: Option<PathBuf>,
+}
+
+impl PaperEngine {
+    /// Create an in-memory account funded with the configured starting balances
+    pub fn new(config: &PaperConfig) -> Self {
+        Self {
+            config: config.clone(),
+            state: PaperState::fresh(&config.starting_balances),
+            path: None,
+        }
+    }
+
+    /// Load the account from `config.state_file`, starting fresh if it does not exist.
+    /// An unreadable document is moved aside to `<file>.bak` rather than overwritten.
+    pub fn load(config: &PaperConfig) -> Self {
+        let path = PathBuf::from(&config.state_file);
+        let mut engine = Self::new(config);
+
+        match fs::read_to_string(&path) {
+            Ok(content) => match serde_json::from_str::<PaperState>(&content) {
+                Ok(state) => engine.state = state,
+                Err(e) => {
+                    let backup = backup_path(&path);
+                    warn!(
+                        "Paper account {} is unreadable ({}); starting fresh and keeping it as {}",
+                        path.display(),
+                        e,
+                        backup.display()
+                    );
+                    if let Err(e) = fs::rename(&path, &backup) {
+                        warn!("Failed to move aside {}: {}", path.display(), e);
+                    }
+                }
+            },
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => warn!("Failed to read paper account {}: {}", path.display(), e),
+        }
+
+        engine.path = Some(path);
+        engine
+    }
+
+    /// Write the account document, replacing the previous one atomically
+    pub fn save(&self) -> Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+
+        let content =
+            serde_json::to_vec_pretty(&self.state).context("Failed to serialize paper account")?;
+        let tmp = path.with_extension("tmp");
+        fs::write(&tmp, content).with_context(|| format!("Failed to write {}", tmp.display()))?;
+        fs::rename(&tmp, path).with_context(|| format!("Failed to replace {}", path.display()))?;
+        Ok(())
+    }
+
+    /// Drop all orders, fills and positions and restore the starting balances
+    pub fn reset(&mut self) {
+        self.state = PaperState::fresh(&self.config.starting_balances);
+    }
+
+    /// Place an order. Market orders and marketable limits fill against `book` at once;
+    /// the rest wait for trades and book updates. Returns the order and any fills.
+    pub fn place_order(
+        &mut self,
+        request: OrderRequest,
+        info: Option<&SymbolInfo>,
+        book: Option<&OrderBook>,
+        now_ms: u64,
+    ) -> Result<(PaperOrder, Vec<PaperFill>)> {
+        let symbol = request.symbol.to_ascii_uppercase();
+        let (base_asset, quote_asset) = resolve_assets(&symbol, info)?;
+
+        let mut quantity = request.quantity;
+        if !quantity.is_finite() || quantity <= 0.0 {
+            anyhow::bail!("Quantity must be greater than 0");
+        }
+        if let Some(info) = info {
+            if !info.status.is_trading() {
+                anyhow::bail!("{} is not trading ({})", symbol, info.status.as_str());
+            }
+            quantity = info.round_quantity_to_step(quantity);
+            if let Some(min_qty) = info.min_qty.filter(|min| quantity < *min) {
+                anyhow::bail!("Quantity is below the {} minimum of {}", symbol, min_qty);
+            }
+            if quantity <= 0.0 {
+                anyhow::bail!("Quantity rounds to 0 at the {} lot size", symbol);
+            }
+        }
+
+        match request.order_type {
+            OrderType::Market => {}
+            OrderType::Limit { price } | OrderType::Stop { trigger: price } => {
+                if !price.is_finite() || price <= 0.0 {
+                    anyhow::bail!("Price must be greater than 0");
+                }
+            }
+        }
+
+        let mut order = PaperOrder {
+            id: self.state.next_order_id,
+            symbol,
+            base_asset,
+            quote_asset,
+            side: request.side,
+            order_type: request.order_type,
+            quantity,
+            filled_qty: 0.0,
+            avg_fill_price: 0.0,
+            status: OrderStatus::Open,
+            note: None,
+            queue_ahead: 0.0,
+            reserved: 0.0,
+            created_ms: now_ms,
+            updated_ms: now_ms,
+        };
+
+        let fills = match order.order_type {
+            OrderType::Market => {
+                let book = book.with_context(|| {
+                    format!("No order book for {} yet; subscribe first", order.symbol)
+                })?;
+                let levels = take_liquidity(book, order.side, quantity, None);
+                if levels.is_empty() {
+                    anyhow::bail!("The {} book has no liquidity on that side", order.symbol);
+                }
+                self.check_funds(&order, &levels)?;
+                self.execute(&mut order, &levels, now_ms)
+            }
+            OrderType::Limit { price } => {
+                let reserve = match order.side {
+                    OrderSide::Buy => {
+                        let fee_rate = self
+                            .fee_rate(Liquidity::Maker)
+                            .max(self.fee_rate(Liquidity::Taker));
+                        quantity * price * (1.0 + fee_rate)
+                    }
+                    OrderSide::Sell => quantity,
+                };
+                let asset = order.funding_asset().to_string();
+                let free = self.state.free(&asset);
+                if reserve > free + QTY_EPSILON {
+                    anyhow::bail!(
+                        "Insufficient {} balance: need {:.8}, have {:.8}",
+                        asset,
+                        reserve,
+                        free
+                    );
+                }
+                self.state.lock(&asset, reserve);
+                order.reserved = reserve;
+
+                let levels = book
+                    .map(|book| take_liquidity(book, order.side, quantity, Some(price)))
+                    .unwrap_or_default();
+                let fills = self.execute_levels(&mut order, &levels, Liquidity::Taker, now_ms);
+                if order.is_open() {
+                    order.queue_ahead =
+                        book.map_or(0.0, |book| displayed_at(book, order.side, price));
+                }
+                fills
+            }
+            OrderType::Stop { trigger } => {
+                let last = book.and_then(|book| match order.side {
+                    OrderSide::Buy => book.best_ask(),
+                    OrderSide::Sell => book.best_bid(),
+                });
+                if let Some(last) = last {
+                    let immediate = match order.side {
+                        OrderSide::Buy => last >= trigger,
+                        OrderSide::Sell => last <= trigger,
+                    };
+                    if immediate {
+                        anyhow::bail!(
+                            "Stop at {} would trigger immediately (market at {})",
+                            trigger,
+                            last
+                        );
+                    }
+                }
+                Vec::new()
+            }
+        };
+
+        self.state.next_order_id += 1;
+        let placed = order.clone();
+        if order.is_open() {
+            self.state.open_orders.push(order);
+        } else {
+            self.archive(order);
+        }
+        self.record_fills(&fills);
+        Ok((placed, fills))
+    }
+
+    /// Cancel one open order and release its reservation
+    pub fn cancel_order(&mut self, id: u64, now_ms: u64) -> Result<PaperOrder> {
+        let index = self
+            .state
+            .open_orders
+            .iter()
+            .position(|order| order.id == id)
+            .with_context(|| format!("Paper order #{} is not open", id))?;
+        let mut order = self.state.open_orders.remove(index);
+        self.close(&mut order, OrderStatus::Cancelled, None, now_ms);
+        let cancelled = order.clone();
+        self.archive(order);
+        Ok(cancelled)
+    }
+
+    /// Cancel every open order, returning them
+    pub fn cancel_all(&mut self, now_ms: u64) -> Vec<PaperOrder> {
+        let orders = std::mem::take(&mut self.state.open_orders);
+        orders
+            .into_iter()
+            .map(|mut order| {
+                self.close(&mut order, OrderStatus::Cancelled, None, now_ms);
+                self.archive(order.clone());
+                order
+            })
+            .collect()
+    }
+
+    /// Match a live print against resting orders and stops on its symbol
+    pub fn on_trade(
+        &mut self,
+        symbol: &str,
+        trade: &TradePrint,
+        book: Option<&OrderBook>,
+    ) -> Vec<PaperFill> {
+        self.mark(symbol, trade.price);
+        if !self.has_open_orders(symbol) {
+            return Vec::new();
+        }
+
+        let now_ms = trade.time_ms;
+        let mut fills = Vec::new();
+        let mut orders = std::mem::take(&mut self.state.open_orders);
+        for order in orders.iter_mut().filter(|order| order.symbol == symbol) {
+            match order.order_type {
+                OrderType::Limit { price } => {
+                    let through = match order.side {
+                        OrderSide::Buy => trade.price < price && !same_price(trade.price, price),
+                        OrderSide::Sell => trade.price > price && !same_price(trade.price, price),
+                    };
+                    // Only aggressors on the other side consume our queue
+                    let hits_queue = same_price(trade.price, price)
+                        && matches!(
+                            (order.side, trade.side),
+                            (OrderSide::Buy, TakerSide::Sell) | (OrderSide::Sell, TakerSide::Buy)
+                        );
+
+                    let quantity = if through {
+                        order.remaining()
+                    } else if hits_queue {
+                        let past_queue = trade.quantity - order.queue_ahead;
+                        order.queue_ahead = (order.queue_ahead - trade.quantity).max(0.0);
+                        past_queue.min(order.remaining())
+                    } else {
+                        0.0
+                    };
+                    if quantity > QTY_EPSILON {
+                        fills.extend(self.execute_levels(
+                            order,
+                            &[(price, quantity)],
+                            Liquidity::Maker,
+                            now_ms,
+                        ));
+                    }
+                }
+                OrderType::Stop { trigger } => {
+                    let triggered = match order.side {
+                        OrderSide::Buy => trade.price >= trigger,
+                        OrderSide::Sell => trade.price <= trigger,
+                    };
+                    if triggered {
+                        // Without a book the stop fills at the print that triggered it
+                        let levels = book
+                            .map(|book| take_liquidity(book, order.side, order.remaining(), None))
+                            .filter(|levels| !levels.is_empty())
+                            .unwrap_or_else(|| vec![(trade.price, order.remaining())]);
+                        match self.check_funds(order, &levels) {
+                            Ok(()) => fills.extend(self.execute(order, &levels, now_ms)),
+                            Err(e) => self.close(
+                                order,
+                                OrderStatus::Rejected,
+                                Some(format!("{} when triggered", e)),
+                                now_ms,
+                            ),
+                        }
+                    }
+                }
+                OrderType::Market => {}
+            }
+        }
+
+        self.settle(orders);
+        self.record_fills(&fills);
+        fills
+    }
+
+    /// Follow book changes: fill limits the book has moved through and shrink
+    /// queue estimates when displayed quantity ahead is cancelled
+    pub fn on_book(&mut self, symbol: &str, book: &OrderBook, now_ms: u64) -> Vec<PaperFill> {
+        if let (Some(bid), Some(ask)) = (book.best_bid(), book.best_ask()) {
+            self.mark(symbol, (bid + ask) / 2.0);
+        }
+        if !self.has_open_orders(symbol) {
+            return Vec::new();
+        }
+
+        let mut fills = Vec::new();
+        let mut orders = std::mem::take(&mut self.state.open_orders);
+        for order in orders.iter_mut().filter(|order| order.symbol == symbol) {
+            let OrderType::Limit { price } = order.order_type else {
+                continue;
+            };
+            let opposite = match order.side {
+                OrderSide::Buy => book.best_ask(),
+                OrderSide::Sell => book.best_bid(),
+            };
+            let crossed = opposite.is_some_and(|best| {
+                !same_price(best, price)
+                    && match order.side {
+                        OrderSide::Buy => best < price,
+                        OrderSide::Sell => best > price,
+                    }
+            });
+            if crossed {
+                let remaining = order.remaining();
+                fills.extend(self.execute_levels(
+                    order,
+                    &[(price, remaining)],
+                    Liquidity::Maker,
+                    now_ms,
+                ));
+            } else {
+                order.queue_ahead = order.queue_ahead.min(displayed_at(book, order.side, price));
+            }
+        }
+
+        self.settle(orders);
+        self.record_fills(&fills);
+        fills
+    }
+
+    pub fn has_open_orders(&self, symbol: &str) -> bool {
+        self.state
+            .open_orders
+            .iter()
+            .any(|order| order.symbol == symbol)
+    }
+
+    pub fn open_orders(&self) -> &[PaperOrder] {
+        &self.state.open_orders
+    }
+
+    pub fn balance(&self, asset: &str) -> Balance {
+        self.state
+            .balances
+            .get(&asset.to_ascii_uppercase())
+            .copied()
+            .unwrap_or_default()
+    }
+
+    pub fn position(&self, symbol: &str) -> Option<&Position> {
+        self.state.positions.get(symbol)
+    }
+
+    /// State for the positions panel; `fill_limit` caps the recent fills returned
+    pub fn snapshot(&self, fill_limit: usize) -> PaperSnapshot {
+        PaperSnapshot {
+            balances: self
+                .state
+                .balances
+                .iter()
+                .filter(|(_, balance)| balance.total().abs() > QTY_EPSILON)
+                .map(|(asset, balance)| (asset.clone(), *balance))
+                .collect(),
+            positions: self
+                .state
+                .positions
+                .values()
+                .filter(|position| {
+                    position.quantity > QTY_EPSILON || position.realized_pnl.abs() > QTY_EPSILON
+                })
+                .cloned()
+                .collect(),
+            open_orders: self.state.open_orders.clone(),
+            recent_fills: self
+                .state
+                .fills
+                .iter()
+                .rev()
+                .take(fill_limit)
+                .cloned()
+                .collect(),
+        }
+    }
+
+    fn fee_rate(&self, liquidity: Liquidity) -> f64 {
+        let bps = match liquidity {
+            Liquidity::Maker => self.config.maker_fee_bps,
+            Liquidity::Taker => self.config.taker_fee_bps,
+        };
+        bps / 10_000.0
+    }
+
+    /// Reject a taking order the free balance cannot pay for
+    fn check_funds(&self, order: &PaperOrder, levels: &[(f64, f64)]) -> Result<()> {
+        let (needed, asset) = match order.side {
+            OrderSide::Buy => {
+                let notional: f64 = levels.iter().map(|(price, qty)| price * qty).sum();
+                (
+                    notional * (1.0 + self.fee_rate(Liquidity::Taker)),
+                    &order.quote_asset,
+                )
+            }
+            OrderSide::Sell => (levels.iter().map(|(_, qty)| qty).sum(), &order.base_asset),
+        };
+        let free = self.state.free(asset);
+        if needed > free + QTY_EPSILON {
+            anyhow::bail!(
+                "Insufficient {} balance: need {:.8}, have {:.8}",
+                asset,
+                needed,
+                free
+            );
+        }
+        Ok(())
+    }
+
+    /// Fill a taking order level by level; whatever the book could not cover is cancelled
+    fn execute(
+        &mut self,
+        order: &mut PaperOrder,
+        levels: &[(f64, f64)],
+        now_ms: u64,
+    ) -> Vec<PaperFill> {
+        let fills = self.execute_levels(order, levels, Liquidity::Taker, now_ms);
+        if order.is_open() {
+            self.close(
+                order,
+                OrderStatus::Cancelled,
+                Some("book depth exhausted".to_string()),
+                now_ms,
+            );
+        }
+        fills
+    }
+
+    /// Fill at the given price levels, in order
+    fn execute_levels(
+        &mut self,
+        order: &mut PaperOrder,
+        levels: &[(f64, f64)],
+        liquidity: Liquidity,
+        now_ms: u64,
+    ) -> Vec<PaperFill> {
+        let fee_rate = self.fee_rate(liquidity);
+        levels
+            .iter()
+            .filter(|(_, qty)| *qty > QTY_EPSILON)
+            .map(|&(price, qty)| {
+                let qty = qty.min(order.remaining());
+                self.state
+                    .apply_fill(order, price, qty, liquidity, fee_rate, now_ms)
+            })
+            .collect()
+    }
+
+    fn close(
+        &mut self,
+        order: &mut PaperOrder,
+        status: OrderStatus,
+        note: Option<String>,
+        now_ms: u64,
+    ) {
+        self.state.release_reservation(order);
+        order.status = status;
+        order.note = note;
+        order.updated_ms = now_ms;
+    }
+
+    /// Put still-open orders back and archive the rest
+    fn settle(&mut self, orders: Vec<PaperOrder>) {
+        let (open, closed): (Vec<_>, Vec<_>) = orders.into_iter().partition(PaperOrder::is_open);
+        self.state.open_orders = open;
+        for order in closed {
+            self.archive(order);
+        }
+    }
+
+    fn archive(&mut self, order: PaperOrder) {
+        self.state.closed_orders.push_back(order);
+        while self.state.closed_orders.len() > self.config.history_limit.max(1) {
+            self.state.closed_orders.pop_front();
+        }
+    }
+
+    fn record_fills(&mut self, fills: &[PaperFill]) {
+        self.state.fills.extend(fills.iter().cloned());
+        while self.state.fills.len() > self.config.history_limit.max(1) {
+            self.state.fills.pop_front();
+        }
+    }
+
+    fn mark(&mut self, symbol: &str, price: f64) {
+        if let Some(position) = self.state.positions.get_mut(symbol) {
+            position.mark_price = Some(price);
+        }
+    }
+}
+
+/// Split a symbol into base and quote assets, preferring exchange info
+pub fn resolve_assets(symbol: &str, info: Option<&SymbolInfo>) -> Result<(String, String)> {
+    if let Some(info) = info {
+        return Ok((info.base_asset.clone(), info.quote_asset.clone()));
+    }
+    KNOWN_QUOTE_ASSETS
+        .iter()
+        .find_map(|quote| {
+            symbol
+                .strip_suffix(quote)
+                .filter(|base| !base.is_empty())
+                .map(|base| (base.to_string(), quote.to_string()))
+        })
+        .with_context(|| {
+            format!(
+                "Cannot tell the base and quote assets of {} before exchange info loads",
+                symbol
+            )
+        })
+}
+
+/// Walk the opposite side of the book for up to `quantity`, stopping at `limit`
+fn take_liquidity(
+    book: &OrderBook,
+    side: OrderSide,
+    quantity: f64,
+    limit: Option<f64>,
+) -> Vec<(f64, f64)> {
+    let levels: Box<dyn Iterator<Item = (&OrderedFloat<f64>, &f64)>> = match side {
+        OrderSide::Buy => Box::new(book.asks.iter()),
+        OrderSide::Sell => Box::new(book.bids.iter().rev()),
+    };
+
+    let mut remaining = quantity;
+    let mut taken = Vec::new();
+    for (price, available) in levels {
+        if remaining <= QTY_EPSILON {
+            break;
+        }
+        if limit.is_some_and(|limit| !side.accepts(price.0, limit)) {
+            break;
+        }
+        let qty = available.min(remaining);
+        if qty > 0.0 {
+            taken.push((price.0, qty));
+            remaining -= qty;
+        }
+    }
+    taken
+}
+
+/// Quantity displayed on the order's own side at `price`
+fn displayed_at(book: &OrderBook, side: OrderSide, price: f64) -> f64 {
+    let levels = match side {
+        OrderSide::Buy => &book.bids,
+        OrderSide::Sell => &book.asks,
+    };
+    levels
+        .range(
+            OrderedFloat(price * (1.0 - PRICE_EPSILON))
+                ..=OrderedFloat(price * (1.0 + PRICE_EPSILON)),
+        )
+        .map(|(_, qty)| *qty)
+        .sum()
+}
+
+fn same_price(a: f64, b: f64) -> bool {
+    (a - b).abs() <= PRICE_EPSILON * a.abs().max(b.abs())
+}
+
+pub(crate) fn backup_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(".bak");
+    PathBuf::from(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> PaperConfig {
+        PaperConfig {
+            starting_balances: BTreeMap::from([("USDT".to_string(), 10_000.0)]),
+            maker_fee_bps: 0.0,
+            taker_fee_bps: 10.0,
+            ..PaperConfig::default()
+        }
+    }
+
+    fn request(side: OrderSide, order_type: OrderType, quantity: f64) -> OrderRequest {
+        OrderRequest {
+            symbol: "BTCUSDT".to_string(),
+            side,
+            order_type,
+            quantity,
+        }
+    }
+
+    fn trade(price: f64, quantity: f64, side: TakerSide) -> TradePrint {
+        TradePrint::single_fill(2_000, price, quantity, side)
+    }
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!(
+            (actual - expected).abs() < 1e-6,
+            "expected {}, got {}",
+            expected,
+            actual
+        );
+    }
+
+    #[test]
+    fn market_order_walks_the_book_and_charges_taker_fees() {
+        let mut engine = PaperEngine::new(&config());
+        let shallow =
+            OrderBook::from_levels("BTCUSDT", &[(99.0, 5.0)], &[(100.0, 1.0), (101.0, 2.0)]);
+
+        let (order, fills) = engine
+            .place_order(
+                request(OrderSide::Buy, OrderType::Market, 2.0),
+                None,
+                Some(&shallow),
+                1_000,
+            )
+            .unwrap();
+
+        assert_eq!(order.status, OrderStatus::Filled);
+        assert_eq!(fills.len(), 2);
+        assert_close(order.avg_fill_price, 100.5);
+        assert!(fills.iter().all(|fill| fill.liquidity == Liquidity::Taker));
+        // 201 notional plus 0.1% fee
+        assert_close(engine.balance("USDT").free, 10_000.0 - 201.201);
+        assert_close(engine.balance("BTC").free, 2.0);
+        let position = engine.position("BTCUSDT").unwrap();
+        assert_close(position.avg_entry, 201.201 / 2.0);
+        assert_close(position.unrealized_pnl(110.0), 220.0 - 201.201);
+
+        // Whatever the book cannot cover is cancelled
+        let (partial, _) = engine
+            .place_order(
+                request(OrderSide::Buy, OrderType::Market, 5.0),
+                None,
+                Some(&shallow),
+                1_000,
+            )
+            .unwrap();
+        assert_eq!(partial.status, OrderStatus::Cancelled);
+        assert_close(partial.filled_qty, 3.0);
+
+        let deep = OrderBook::from_levels("BTCUSDT", &[(99.0, 5.0)], &[(100.0, 500.0)]);
+        let err = engine
+            .place_order(
+                request(OrderSide::Buy, OrderType::Market, 200.0),
+                None,
+                Some(&deep),
+                1_000,
+            )
+            .unwrap_err();
+        assert!(err.to_string().contains("Insufficient USDT"));
+        assert!(
+            engine
+                .place_order(
+                    request(OrderSide::Sell, OrderType::Market, 1.0),
+                    None,
+                    None,
+                    1_000
+                )
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn resting_limit_waits_for_the_queue_ahead() {
+        let mut engine = PaperEngine::new(&config());
+        let book = OrderBook::from_levels("BTCUSDT", &[(99.0, 3.0)], &[(100.0, 1.0)]);
+
+        let (order, fills) = engine
+            .place_order(
+                request(OrderSide::Buy, OrderType::Limit { price: 99.0 }, 2.0),
+                None,
+                Some(&book),
+                1_000,
+            )
+            .unwrap();
+        assert!(fills.is_empty());
+        assert_close(order.queue_ahead, 3.0);
+        assert_close(engine.balance("USDT").locked, 198.0 * 1.001);
+
+        // Buyer-initiated prints at our price do not reach the bid queue
+        assert!(
+            engine
+                .on_trade("BTCUSDT", &trade(99.0, 5.0, TakerSide::Buy), None)
+                .is_empty()
+        );
+        // Sells eat the 3.0 ahead of us, then 0.5 of ours
+        let fills = engine.on_trade("BTCUSDT", &trade(99.0, 3.5, TakerSide::Sell), None);
+        assert_eq!(fills.len(), 1);
+        assert_close(fills[0].quantity, 0.5);
+        assert_eq!(fills[0].liquidity, Liquidity::Maker);
+        assert_close(fills[0].fee, 0.0);
+
+        // Trading through the price fills the rest at our limit
+        let fills = engine.on_trade("BTCUSDT", &trade(98.5, 0.1, TakerSide::Sell), None);
+        assert_close(fills[0].quantity, 1.5);
+        assert_close(fills[0].price, 99.0);
+        assert!(engine.open_orders().is_empty());
+        assert_close(engine.balance("USDT").locked, 0.0);
+        assert_close(engine.balance("USDT").free, 10_000.0 - 198.0);
+    }
+
+    #[test]
+    fn book_updates_shrink_the_queue_and_fill_crossed_limits() {
+        let mut engine = PaperEngine::new(&config());
+        engine.state.balances.insert(
+            "BTC".to_string(),
+            Balance {
+                free: 1.0,
+                locked: 0.0,
+            },
+        );
+
+        engine
+            .place_order(
+                request(OrderSide::Sell, OrderType::Limit { price: 101.0 }, 1.0),
+                None,
+                Some(&OrderBook::from_levels(
+                    "BTCUSDT",
+                    &[(99.0, 1.0)],
+                    &[(100.0, 1.0), (101.0, 4.0)],
+                )),
+                1_000,
+            )
+            .unwrap();
+        assert_close(engine.balance("BTC").locked, 1.0);
+
+        // Orders ahead of us were cancelled
+        engine.on_book(
+            "BTCUSDT",
+            &OrderBook::from_levels("BTCUSDT", &[(99.0, 1.0)], &[(100.0, 1.0), (101.0, 0.5)]),
+            1_500,
+        );
+        assert_close(engine.open_orders()[0].queue_ahead, 0.5);
+
+        let fills = engine.on_book(
+            "BTCUSDT",
+            &OrderBook::from_levels("BTCUSDT", &[(102.0, 1.0)], &[(103.0, 1.0)]),
+            2_000,
+        );
+        assert_eq!(fills.len(), 1);
+        assert_close(fills[0].price, 101.0);
+        assert_close(engine.balance("BTC").total(), 0.0);
+        assert_close(engine.balance("USDT").free, 10_101.0);
+        // Starting balances carry no cost basis, so nothing is realized
+        assert_close(fills[0].realized_pnl, 0.0);
+    }
+
+    #[test]
+    fn stop_triggers_on_the_last_trade_and_realizes_pnl() {
+        let mut engine = PaperEngine::new(&config());
+        let entry_book = OrderBook::from_levels("BTCUSDT", &[(99.0, 5.0)], &[(100.0, 5.0)]);
+        engine
+            .place_order(
+                request(OrderSide::Buy, OrderType::Market, 1.0),
+                None,
+                Some(&entry_book),
+                1_000,
+            )
+            .unwrap();
+
+        assert!(
+            engine
+                .place_order(
+                    request(OrderSide::Sell, OrderType::Stop { trigger: 99.5 }, 1.0),
+                    None,
+                    Some(&entry_book),
+                    1_000,
+                )
+                .is_err(),
+            "a sell stop above the bid would trigger immediately"
+        );
+        let (stop, _) = engine
+            .place_order(
+                request(OrderSide::Sell, OrderType::Stop { trigger: 95.0 }, 1.0),
+                None,
+                Some(&entry_book),
+                1_000,
+            )
+            .unwrap();
+
+        assert!(
+            engine
+                .on_trade("BTCUSDT", &trade(96.0, 1.0, TakerSide::Sell), None)
+                .is_empty()
+        );
+        let falling =
+            OrderBook::from_levels("BTCUSDT", &[(94.0, 0.4), (93.0, 1.0)], &[(95.0, 1.0)]);
+        let fills = engine.on_trade(
+            "BTCUSDT",
+            &trade(95.0, 1.0, TakerSide::Sell),
+            Some(&falling),
+        );
+
+        assert_eq!(fills.len(), 2);
+        assert!(fills.iter().all(|fill| fill.order_id == stop.id));
+        let position = engine.position("BTCUSDT").unwrap();
+        assert_close(position.quantity, 0.0);
+        // Bought at 100.1 including fees, sold 0.4 @ 94 and 0.6 @ 93 less 0.1%
+        let proceeds = (0.4 * 94.0 + 0.6 * 93.0) * 0.999;
+        assert_close(position.realized_pnl, proceeds - 100.1);
+        assert!(engine.open_orders().is_empty());
+    }
+
+    #[test]
+    fn cancel_releases_the_reservation() {
+        let mut engine = PaperEngine::new(&config());
+        let (order, _) = engine
+            .place_order(
+                request(OrderSide::Buy, OrderType::Limit { price: 50.0 }, 10.0),
+                None,
+                None,
+                1_000,
+            )
+            .unwrap();
+        assert!(engine.balance("USDT").locked > 0.0);
+
+        let cancelled = engine.cancel_order(order.id, 2_000).unwrap();
+        assert_eq!(cancelled.status, OrderStatus::Cancelled);
+        assert_close(engine.balance("USDT").free, 10_000.0);
+        assert_close(engine.balance("USDT").locked, 0.0);
+        assert!(engine.cancel_order(order.id, 2_000).is_err());
+    }
+
+    #[test]
+    fn state_survives_a_restart() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = PaperConfig {
+            state_file: dir.path().join("paper.json").display().to_string(),
+            ..config()
+        };
+
+        let mut engine = PaperEngine::load(&config);
+        engine
+            .place_order(
+                request(OrderSide::Buy, OrderType::Limit { price: 90.0 }, 1.0),
+                None,
+                None,
+                1_000,
+            )
+            .unwrap();
+        engine.save().unwrap();
+
+        let reloaded = PaperEngine::load(&config);
+        assert_eq!(reloaded.open_orders().len(), 1);
+        assert_close(
+            reloaded.balance("USDT").locked,
+            engine.balance("USDT").locked,
+        );
+
+        fs::write(&config.state_file, "{ not json").unwrap();
+        let fresh = PaperEngine::load(&config);
+        assert!(fresh.open_orders().is_empty());
+        assert_close(fresh.balance("USDT").free, 10_000.0);
+        assert!(backup_path(Path::new(&config.state_file)).exists());
+    }
+
+    #[test]
+    fn resolves_assets_from_the_symbol_name() {
+        assert_eq!(
+            resolve_assets("ETHBTC", None).unwrap(),
+            ("ETH".to_string(), "BTC".to_string())
+        );
+        assert_eq!(
+            resolve_assets("BTCFDUSD", None).unwrap(),
+            ("BTC".to_string(), "FDUSD".to_string())
+        );
+        assert!(resolve_assets("USDT", None).is_err());
+    }
+}
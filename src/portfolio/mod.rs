@@ -0,0 +1,861 @@
+//! Manually entered holdings valued from live prices
+//!
+//! A holding is a symbol, a quantity and a cost basis per unit in the symbol's
+//! quote asset; no API keys are involved. Holdings are valued in one reporting
+//! currency (`portfolio.currency`). One quoted in another asset is converted
+//! through whichever of `<quote><currency>` or `<currency><quote>` is listed,
+//! so `ETHBTC` is valued through `BTCUSDT` and `SOLBRL` through `USDTBRL` when
+//! reporting in USDT.
+//!
+//! Holdings from the configuration seed a new portfolio. After that the state
+//! file is authoritative, like the paper account: `/position:add` and
+//! `/position:remove` change it and `/position:reset` goes back to the
+//! configured holdings.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+use std::fs;
+use std::path::PathBuf;
+use tracing::warn;
+
+use crate::binance::{ExchangeInfoCache, SymbolInfo, SymbolStatus};
+use crate::config::PortfolioConfig;
+use crate::paper::{backup_path, resolve_assets};
+
+/// A position entered by hand
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Holding {
+    pub symbol: String,
+    pub quantity: f64,
+    /// Average price paid per unit, in the symbol's quote asset
+    pub cost_basis: f64,
+}
+
+impl Holding {
+    /// Check the symbol resolves to a quote asset and the amounts are usable;
+    /// without exchange info the quote asset is guessed from the symbol suffix
+    pub fn validate(&self, info: Option<&SymbolInfo>) -> Result<()> {
+        if !self.quantity.is_finite() || self.quantity <= 0.0 {
+            anyhow::bail!(
+                "Quantity for {} must be greater than 0, got {}",
+                self.symbol,
+                self.quantity
+            );
+        }
+        if !self.cost_basis.is_finite() || self.cost_basis < 0.0 {
+            anyhow::bail!(
+                "Cost basis for {} must not be negative, got {}",
+                self.symbol,
+                self.cost_basis
+            );
+        }
+        resolve_assets(&self.symbol.to_ascii_uppercase(), info)?;
+        Ok(())
+    }
+}
+
+/// Portfolio-wide alert thresholds; `None` disables a threshold
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PortfolioAlerts {
+    /// Total value in the reporting currency
+    pub value_above: Option<f64>,
+    pub value_below: Option<f64>,
+    /// Percent below the peak value
+    pub drawdown_pct: Option<f64>,
+}
+
+impl PortfolioAlerts {
+    pub fn get(&self, kind: PortfolioAlertKind) -> Option<f64> {
+        match kind {
+            PortfolioAlertKind::ValueAbove => self.value_above,
+            PortfolioAlertKind::ValueBelow => self.value_below,
+            PortfolioAlertKind::Drawdown => self.drawdown_pct,
+        }
+    }
+
+    fn set(&mut self, kind: PortfolioAlertKind, threshold: Option<f64>) {
+        match kind {
+            PortfolioAlertKind::ValueAbove => self.value_above = threshold,
+            PortfolioAlertKind::ValueBelow => self.value_below = threshold,
+            PortfolioAlertKind::Drawdown => self.drawdown_pct = threshold,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum PortfolioAlertKind {
+    ValueAbove,
+    ValueBelow,
+    Drawdown,
+}
+
+impl PortfolioAlertKind {
+    pub const ALL: [PortfolioAlertKind; 3] = [
+        PortfolioAlertKind::ValueAbove,
+        PortfolioAlertKind::ValueBelow,
+        PortfolioAlertKind::Drawdown,
+    ];
+
+    /// Keyword used in commands
+    pub fn label(self) -> &'static str {
+        match self {
+            PortfolioAlertKind::ValueAbove => "above",
+            PortfolioAlertKind::ValueBelow => "below",
+            PortfolioAlertKind::Drawdown => "drawdown",
+        }
+    }
+
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw.to_ascii_lowercase().as_str() {
+            "above" => Some(PortfolioAlertKind::ValueAbove),
+            "below" => Some(PortfolioAlertKind::ValueBelow),
+            "drawdown" | "dd" => Some(PortfolioAlertKind::Drawdown),
+            _ => None,
+        }
+    }
+
+    /// Values are positive amounts; drawdowns are percentages under 100
+    pub fn validate_threshold(self, threshold: f64) -> Result<()> {
+        if !threshold.is_finite() || threshold <= 0.0 {
+            anyhow::bail!("Threshold must be a positive, finite number");
+        }
+        if self == PortfolioAlertKind::Drawdown && threshold >= 100.0 {
+            anyhow::bail!("Drawdown threshold must be below 100%");
+        }
+        Ok(())
+    }
+}
+
+/// A portfolio threshold that was crossed
+#[derive(Debug, Clone, PartialEq)]
+pub struct PortfolioTrigger {
+    pub kind: PortfolioAlertKind,
+    pub threshold: f64,
+    /// Total value, or the drawdown percent for drawdown alerts
+    pub value: f64,
+    pub peak_value: f64,
+    pub currency: String,
+}
+
+impl PortfolioTrigger {
+    pub fn describe(&self) -> String {
+        match self.kind {
+            PortfolioAlertKind::ValueAbove => format!(
+                "Portfolio value {:.2} {} rose above {}",
+                self.value, self.currency, self.threshold
+            ),
+            PortfolioAlertKind::ValueBelow => format!(
+                "Portfolio value {:.2} {} fell below {}",
+                self.value, self.currency, self.threshold
+            ),
+            PortfolioAlertKind::Drawdown => format!(
+                "Portfolio is {:.2}% below its peak of {:.2} {} (threshold {}%)",
+                self.value, self.peak_value, self.currency, self.threshold
+            ),
+        }
+    }
+}
+
+/// Latest price of a symbol with its 24h change when known
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PriceQuote {
+    pub price: f64,
+    pub change_percent: Option<f64>,
+}
+
+/// One holding valued in the reporting currency
+#[derive(Debug, Clone, PartialEq)]
+pub struct HoldingValue {
+    pub holding: Holding,
+    /// Last price in the symbol's quote asset
+    pub price: Option<f64>,
+    pub value: Option<f64>,
+    pub cost: Option<f64>,
+    pub daily_change: Option<f64>,
+    /// Share of the valued total, in percent
+    pub allocation_percent: Option<f64>,
+    /// Why the holding cannot be converted into the reporting currency
+    pub conversion_error: Option<String>,
+}
+
+impl HoldingValue {
+    pub fn pnl(&self) -> Option<f64> {
+        Some(self.value? - self.cost?)
+    }
+
+    pub fn pnl_percent(&self) -> Option<f64> {
+        let cost = self.cost.filter(|cost| *cost > 0.0)?;
+        Some(self.pnl()? / cost * 100.0)
+    }
+}
+
+/// All holdings valued at the latest prices
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Valuation {
+    pub currency: String,
+    pub holdings: Vec<HoldingValue>,
+    /// Totals cover the holdings that have a price
+    pub total_value: f64,
+    pub total_cost: f64,
+    pub daily_change: f64,
+    /// Whether every holding has a price and conversion rate
+    pub complete: bool,
+}
+
+impl Valuation {
+    pub fn total_pnl(&self) -> f64 {
+        self.total_value - self.total_cost
+    }
+
+    pub fn pnl_percent(&self) -> Option<f64> {
+        (self.total_cost > 0.0).then(|| self.total_pnl() / self.total_cost * 100.0)
+    }
+
+    /// 24h change as a percent of the value a day ago
+    pub fn daily_change_percent(&self) -> Option<f64> {
+        let previous = self.total_value - self.daily_change;
+        (previous > 0.0).then(|| self.daily_change / previous * 100.0)
+    }
+
+    /// Percent below `peak`, 0 at or above it
+    pub fn drawdown_percent(&self, peak: Option<f64>) -> Option<f64> {
+        let peak = peak.filter(|peak| *peak > 0.0)?;
+        Some(((peak - self.total_value) / peak * 100.0).max(0.0))
+    }
+}
+
+/// Value holdings in `currency` with prices from `quote`
+///
+/// Cost and PnL are converted at the current rate. The daily change covers the
+/// holding's own price move, not the move of the conversion rate.
+pub fn value_holdings(
+    holdings: &[Holding],
+    currency: &str,
+    exchange_info: &ExchangeInfoCache,
+    quote: impl Fn(&str) -> Option<PriceQuote>,
+) -> Valuation {
+    let mut valuation = Valuation {
+        currency: currency.to_string(),
+        complete: true,
+        ..Valuation::default()
+    };
+
+    for holding in holdings {
+        let conversion = conversion(&holding.symbol, currency, exchange_info);
+        let rate = match &conversion {
+            Ok(conversion) => conversion.rate(&quote),
+            Err(_) => None,
+        };
+        let priced = quote(&holding.symbol);
+        let (value, cost, daily_change) = match (priced, rate) {
+            (Some(priced), Some(rate)) => {
+                let value = priced.price * holding.quantity * rate;
+                let daily_change = priced
+                    .change_percent
+                    .map(|pct| value - value / (1.0 + pct / 100.0));
+                (
+                    Some(value),
+                    Some(holding.cost_basis * holding.quantity * rate),
+                    daily_change,
+                )
+            }
+            _ => (None, None, None),
+        };
+
+        match (value, cost) {
+            (Some(value), Some(cost)) => {
+                valuation.total_value += value;
+                valuation.total_cost += cost;
+                valuation.daily_change += daily_change.unwrap_or(0.0);
+            }
+            _ => valuation.complete = false,
+        }
+        valuation.holdings.push(HoldingValue {
+            holding: holding.clone(),
+            price: priced.map(|q| q.price),
+            value,
+            cost,
+            daily_change,
+            allocation_percent: None,
+            conversion_error: conversion.err().map(|e| e.to_string()),
+        });
+    }
+
+    let total = valuation.total_value;
+    for entry in &mut valuation.holdings {
+        entry.allocation_percent = entry
+            .value
+            .filter(|_| total > 0.0)
+            .map(|value| value / total * 100.0);
+    }
+    valuation
+}
+
+/// How a holding's quote asset is converted into the reporting currency
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Conversion {
+    /// Already quoted in the reporting currency
+    Same,
+    /// `<quote><currency>`, multiplied by its price
+    Direct(String),
+    /// `<currency><quote>`, divided by its price
+    Inverse(String),
+}
+
+impl Conversion {
+    /// Pair whose price the conversion needs
+    pub fn symbol(&self) -> Option<&str> {
+        match self {
+            Conversion::Same => None,
+            Conversion::Direct(symbol) | Conversion::Inverse(symbol) => Some(symbol),
+        }
+    }
+
+    /// Rate from the quote asset to the reporting currency, once the pair has a price
+    fn rate(&self, quote: impl Fn(&str) -> Option<PriceQuote>) -> Option<f64> {
+        match self {
+            Conversion::Same => Some(1.0),
+            Conversion::Direct(symbol) => quote(symbol).map(|q| q.price),
+            Conversion::Inverse(symbol) => quote(symbol)
+                .map(|q| q.price)
+                .filter(|price| *price > 0.0)
+                .map(|price| 1.0 / price),
+        }
+    }
+}
+
+/// Pair that converts the holding's quote asset into `currency`
+///
+/// Exchange info decides the quote asset and whether `<quote><currency>` or
+/// `<currency><quote>` is listed. Before it loads the direct pair is assumed.
+pub fn conversion(
+    symbol: &str,
+    currency: &str,
+    exchange_info: &ExchangeInfoCache,
+) -> Result<Conversion> {
+    let info = exchange_info.get(symbol);
+    let (_, quote) = resolve_assets(symbol, info.as_ref())?;
+    if quote == currency {
+        return Ok(Conversion::Same);
+    }
+
+    let direct = format!("{}{}", quote, currency);
+    if !exchange_info.is_loaded() {
+        return Ok(Conversion::Direct(direct));
+    }
+    let listed = |pair: &str| {
+        exchange_info
+            .get(pair)
+            .is_some_and(|info| info.status != SymbolStatus::Delisted)
+    };
+    let inverse = format!("{}{}", currency, quote);
+    if listed(&direct) {
+        Ok(Conversion::Direct(direct))
+    } else if listed(&inverse) {
+        Ok(Conversion::Inverse(inverse))
+    } else {
+        anyhow::bail!(
+            "No {} or {} pair to convert {} into {}",
+            direct,
+            inverse,
+            quote,
+            currency
+        )
+    }
+}
+
+/// Everything the portfolio panel needs
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PortfolioSnapshot {
+    pub valuation: Valuation,
+    pub peak_value: Option<f64>,
+    pub alerts: PortfolioAlerts,
+}
+
+impl PortfolioSnapshot {
+    pub fn drawdown_percent(&self) -> Option<f64> {
+        self.valuation.drawdown_percent(self.peak_value)
+    }
+}
+
+/// Persisted portfolio document
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct PortfolioState {
+    holdings: Vec<Holding>,
+    #[serde(default)]
+    alerts: PortfolioAlerts,
+    /// Highest complete valuation since holdings last changed
+    #[serde(default)]
+    peak_value: Option<f64>,
+}
+
+impl PortfolioState {
+    fn fresh(config: &PortfolioConfig) -> Self {
+        Self {
+            holdings: config
+                .holdings
+                .iter()
+                .map(|holding| Holding {
+                    symbol: holding.symbol.to_ascii_uppercase(),
+                    ..holding.clone()
+                })
+                .collect(),
+            alerts: PortfolioAlerts {
+                value_above: config.value_alert_above,
+                value_below: config.value_alert_below,
+                drawdown_pct: config.drawdown_alert_pct,
+            },
+            peak_value: None,
+        }
+    }
+}
+
+/// Holdings, the latest prices and portfolio alert state
+#[derive(Debug, Clone)]
+pub struct PortfolioTracker {
+    config: PortfolioConfig,
+    state: PortfolioState,
+    path: Option<PathBuf>,
+    quotes: HashMap<String, PriceQuote>,
+    /// Base and quote assets of the held symbols
+    exchange_info: ExchangeInfoCache,
+    /// Alerts whose condition held at the last complete valuation
+    triggered: BTreeSet<PortfolioAlertKind>,
+}
+
+impl PortfolioTracker {
+    /// Create an in-memory portfolio with the configured holdings
+    pub fn new(config: &PortfolioConfig, exchange_info: ExchangeInfoCache) -> Self {
+        Self {
+            config: config.clone(),
+            state: PortfolioState::fresh(config),
+            path: None,
+            quotes: HashMap::new(),
+            exchange_info,
+            triggered: BTreeSet::new(),
+        }
+    }
+
+    /// Load the portfolio from `config.state_file`, seeding it from the
+    /// configuration when the file does not exist. An unreadable document is
+    /// moved aside to `<file>.bak`.
+    pub fn load(config: &PortfolioConfig, exchange_info: ExchangeInfoCache) -> Self {
+        let path = PathBuf::from(&config.state_file);
+        let mut tracker = Self::new(config, exchange_info);
+
+        match fs::read_to_string(&path) {
+            Ok(content) => match serde_json::from_str::<PortfolioState>(&content) {
+                Ok(state) => tracker.state = state,
+                Err(e) => {
+                    let backup = backup_path(&path);
+                    warn!(
+                        "Portfolio {} is unreadable ({}); starting from the configured holdings and keeping it as {}",
+                        path.display(),
+                        e,
+                        backup.display()
+                    );
+                    if let Err(e) = fs::rename(&path, &backup) {
+                        warn!("Failed to move aside {}: {}", path.display(), e);
+                    }
+                }
+            },
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => warn!("Failed to read portfolio {}: {}", path.display(), e),
+        }
+
+        tracker.path = Some(path);
+        tracker
+    }
+
+    /// Write the portfolio document, replacing the previous one atomically
+    pub fn save(&self) -> Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+
+        let content =
+            serde_json::to_vec_pretty(&self.state).context("Failed to serialize portfolio")?;
+        let tmp = path.with_extension("tmp");
+        fs::write(&tmp, content).with_context(|| format!("Failed to write {}", tmp.display()))?;
+        fs::rename(&tmp, path).with_context(|| format!("Failed to replace {}", path.display()))?;
+        Ok(())
+    }
+
+    pub fn currency(&self) -> &str {
+        &self.config.currency
+    }
+
+    pub fn holdings(&self) -> &[Holding] {
+        &self.state.holdings
+    }
+
+    /// Add to a holding; buying more of a symbol averages the cost basis
+    pub fn add(&mut self, symbol: &str, quantity: f64, cost_basis: f64) -> Result<Holding> {
+        let addition = Holding {
+            symbol: symbol.to_ascii_uppercase(),
+            quantity,
+            cost_basis,
+        };
+        addition.validate(self.exchange_info.get(&addition.symbol).as_ref())?;
+        conversion(&addition.symbol, self.currency(), &self.exchange_info)?;
+
+        let holding = match self
+            .state
+            .holdings
+            .iter_mut()
+            .find(|holding| holding.symbol == addition.symbol)
+        {
+            Some(holding) => {
+                let total = holding.quantity + addition.quantity;
+                holding.cost_basis = (holding.cost_basis * holding.quantity
+                    + addition.cost_basis * addition.quantity)
+                    / total;
+                holding.quantity = total;
+                holding.clone()
+            }
+            None => {
+                self.state.holdings.push(addition.clone());
+                addition
+            }
+        };
+        self.holdings_changed();
+        Ok(holding)
+    }
+
+    /// Drop a holding
+    pub fn remove(&mut self, symbol: &str) -> Result<Holding> {
+        let symbol = symbol.to_ascii_uppercase();
+        let index = self
+            .state
+            .holdings
+            .iter()
+            .position(|holding| holding.symbol == symbol)
+            .with_context(|| format!("No holding for {}", symbol))?;
+        let holding = self.state.holdings.remove(index);
+        self.holdings_changed();
+        Ok(holding)
+    }
+
+    /// Go back to the configured holdings and alert thresholds
+    pub fn reset(&mut self) {
+        self.state = PortfolioState::fresh(&self.config);
+        self.triggered.clear();
+    }
+
+    pub fn alerts(&self) -> PortfolioAlerts {
+        self.state.alerts
+    }
+
+    /// Set or clear one alert threshold; a changed threshold re-arms the alert
+    pub fn set_alert(&mut self, kind: PortfolioAlertKind, threshold: Option<f64>) -> Result<()> {
+        if let Some(threshold) = threshold {
+            kind.validate_threshold(threshold)?;
+        }
+        self.state.alerts.set(kind, threshold);
+        self.triggered.remove(&kind);
+        Ok(())
+    }
+
+    /// Symbols whose prices the valuation needs, conversion pairs included
+    pub fn required_symbols(&self) -> Vec<String> {
+        let mut symbols = BTreeSet::new();
+        for holding in &self.state.holdings {
+            symbols.insert(holding.symbol.clone());
+            match conversion(&holding.symbol, self.currency(), &self.exchange_info) {
+                Ok(conversion) => symbols.extend(conversion.symbol().map(str::to_string)),
+                Err(e) => warn!("Cannot value {}: {:#}", holding.symbol, e),
+            }
+        }
+        symbols.into_iter().collect()
+    }
+
+    /// Whether the valuation uses prices of `symbol`
+    pub fn tracks(&self, symbol: &str) -> bool {
+        self.state.holdings.iter().any(|holding| {
+            holding.symbol == symbol
+                || conversion(&holding.symbol, self.currency(), &self.exchange_info)
+                    .is_ok_and(|conversion| conversion.symbol() == Some(symbol))
+        })
+    }
+
+    /// Record a price and return the alerts it triggers
+    ///
+    /// Prices for symbols the portfolio does not use are ignored. Alerts are
+    /// only evaluated once every holding has a price.
+    pub fn on_price(
+        &mut self,
+        symbol: &str,
+        price: f64,
+        change_percent: Option<f64>,
+    ) -> Vec<PortfolioTrigger> {
+        if !price.is_finite() || price <= 0.0 || !self.tracks(symbol) {
+            return Vec::new();
+        }
+        let quote = self.quotes.entry(symbol.to_string()).or_insert(PriceQuote {
+            price,
+            change_percent: None,
+        });
+        quote.price = price;
+        if change_percent.is_some() {
+            quote.change_percent = change_percent;
+        }
+
+        let valuation = self.valuation();
+        if !valuation.complete || valuation.holdings.is_empty() {
+            return Vec::new();
+        }
+        let peak = self.state.peak_value.map_or(valuation.total_value, |peak| {
+            peak.max(valuation.total_value)
+        });
+        self.state.peak_value = Some(peak);
+        let drawdown = valuation.drawdown_percent(Some(peak)).unwrap_or(0.0);
+
+        let mut triggers = Vec::new();
+        for kind in PortfolioAlertKind::ALL {
+            let Some(threshold) = self.state.alerts.get(kind) else {
+                continue;
+            };
+            let (value, crossed) = match kind {
+                PortfolioAlertKind::ValueAbove => {
+                    (valuation.total_value, valuation.total_value >= threshold)
+                }
+                PortfolioAlertKind::ValueBelow => {
+                    (valuation.total_value, valuation.total_value <= threshold)
+                }
+                PortfolioAlertKind::Drawdown => (drawdown, drawdown >= threshold),
+            };
+            if !crossed {
+                // Re-arm once the condition clears
+                self.triggered.remove(&kind);
+            } else if self.triggered.insert(kind) {
+                triggers.push(PortfolioTrigger {
+                    kind,
+                    threshold,
+                    value,
+                    peak_value: peak,
+                    currency: self.config.currency.clone(),
+                });
+            }
+        }
+        triggers
+    }
+
+    /// Value the holdings at the latest recorded prices
+    pub fn valuation(&self) -> Valuation {
+        value_holdings(
+            &self.state.holdings,
+            self.currency(),
+            &self.exchange_info,
+            |symbol| self.quotes.get(symbol).copied(),
+        )
+    }
+
+    pub fn snapshot(&self) -> PortfolioSnapshot {
+        PortfolioSnapshot {
+            valuation: self.valuation(),
+            peak_value: self.state.peak_value,
+            alerts: self.state.alerts,
+        }
+    }
+
+    /// A different set of holdings starts a new peak
+    fn holdings_changed(&mut self) {
+        self.state.peak_value = None;
+        self.triggered.remove(&PortfolioAlertKind::Drawdown);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::binance::SymbolStatus;
+
+    fn config(holdings: Vec<Holding>) -> PortfolioConfig {
+        PortfolioConfig {
+            holdings,
+            ..PortfolioConfig::default()
+        }
+    }
+
+    fn holding(symbol: &str, quantity: f64, cost_basis: f64) -> Holding {
+        Holding {
+            symbol: symbol.to_string(),
+            quantity,
+            cost_basis,
+        }
+    }
+
+    fn close(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-6
+    }
+
+    #[test]
+    fn values_holdings_with_conversion_and_allocation() {
+        let holdings = [
+            holding("BTCUSDT", 0.5, 40_000.0),
+            holding("ETHBTC", 10.0, 0.04),
+        ];
+        let prices = HashMap::from([
+            (
+                "BTCUSDT",
+                PriceQuote {
+                    price: 50_000.0,
+                    change_percent: Some(25.0),
+                },
+            ),
+            (
+                "ETHBTC",
+                PriceQuote {
+                    price: 0.05,
+                    change_percent: None,
+                },
+            ),
+        ]);
+
+        let valuation = value_holdings(&holdings, "USDT", &ExchangeInfoCache::new(), |symbol| {
+            prices.get(symbol).copied()
+        });
+
+        assert!(valuation.complete);
+        // 0.5 BTC at 50k plus 10 ETH at 0.05 BTC converted at 50k
+        assert!(close(valuation.total_value, 50_000.0));
+        assert!(close(valuation.total_cost, 40_000.0));
+        assert!(close(valuation.pnl_percent().unwrap(), 25.0));
+        assert!(close(
+            valuation.holdings[0].allocation_percent.unwrap(),
+            50.0
+        ));
+        assert!(close(valuation.holdings[1].pnl().unwrap(), 5_000.0));
+        // BTC rose 25% from 20k to 25k
+        assert!(close(valuation.daily_change, 5_000.0));
+
+        let partial = value_holdings(&holdings, "USDT", &ExchangeInfoCache::new(), |symbol| {
+            (symbol == "ETHBTC").then(|| prices[symbol])
+        });
+        assert!(!partial.complete);
+        assert_eq!(partial.total_value, 0.0);
+    }
+
+    #[test]
+    fn adding_to_a_holding_averages_the_cost_basis() {
+        let mut tracker = PortfolioTracker::new(
+            &config(vec![holding("btcusdt", 1.0, 30_000.0)]),
+            ExchangeInfoCache::new(),
+        );
+        assert_eq!(tracker.holdings()[0].symbol, "BTCUSDT");
+
+        let merged = tracker.add("BTCUSDT", 1.0, 50_000.0).unwrap();
+        assert_eq!(merged.quantity, 2.0);
+        assert_eq!(merged.cost_basis, 40_000.0);
+        assert!(tracker.add("ETHUSDT", 0.0, 1.0).is_err());
+        assert!(tracker.add("NOTAPAIR", 1.0, 1.0).is_err());
+
+        tracker.remove("btcusdt").unwrap();
+        assert!(tracker.holdings().is_empty());
+        assert!(tracker.remove("BTCUSDT").is_err());
+
+        tracker.reset();
+        assert_eq!(tracker.holdings().len(), 1);
+    }
+
+    #[test]
+    fn conversion_uses_exchange_info_pairs_in_either_direction() {
+        let info = |base: &str, quote: &str| SymbolInfo {
+            symbol: format!("{}{}", base, quote),
+            base_asset: base.to_string(),
+            quote_asset: quote.to_string(),
+            status: SymbolStatus::Trading,
+            tick_size: None,
+            min_price: None,
+            max_price: None,
+            step_size: None,
+            min_qty: None,
+            max_qty: None,
+            min_notional: None,
+        };
+        let exchange_info = ExchangeInfoCache::new();
+        let mut tracker = PortfolioTracker::new(&config(vec![]), exchange_info.clone());
+        // BRL is not a quote asset the symbol suffix can be guessed from
+        assert!(tracker.add("SOLBRL", 1.0, 500.0).is_err());
+
+        // Binance lists USDTBRL, not BRLUSDT
+        exchange_info.replace(vec![
+            info("SOL", "BRL"),
+            info("USDT", "BRL"),
+            info("SOL", "ARS"),
+        ]);
+        tracker.add("SOLBRL", 1.0, 500.0).unwrap();
+        assert_eq!(tracker.required_symbols(), ["SOLBRL", "USDTBRL"]);
+        assert!(tracker.tracks("USDTBRL"));
+
+        tracker.on_price("SOLBRL", 1_000.0, None);
+        tracker.on_price("USDTBRL", 5.0, None);
+        let valuation = tracker.valuation();
+        assert!(valuation.complete);
+        assert!(close(valuation.total_value, 200.0));
+
+        let error = tracker.add("SOLARS", 1.0, 1.0).unwrap_err().to_string();
+        assert_eq!(error, "No ARSUSDT or USDTARS pair to convert ARS into USDT");
+
+        // A configured holding without a pair is reported instead of valued at 1:1
+        let valuation = value_holdings(
+            &[holding("SOLARS", 1.0, 1.0)],
+            "USDT",
+            &exchange_info,
+            |_| {
+                Some(PriceQuote {
+                    price: 1.0,
+                    change_percent: None,
+                })
+            },
+        );
+        assert!(!valuation.complete);
+        assert_eq!(
+            valuation.holdings[0].conversion_error.as_deref(),
+            Some("No ARSUSDT or USDTARS pair to convert ARS into USDT")
+        );
+    }
+
+    #[test]
+    fn value_and_drawdown_alerts_fire_once_per_crossing() {
+        let mut tracker = PortfolioTracker::new(
+            &config(vec![holding("BTCUSDT", 1.0, 0.0)]),
+            ExchangeInfoCache::new(),
+        );
+        tracker
+            .set_alert(PortfolioAlertKind::ValueBelow, Some(90.0))
+            .unwrap();
+        tracker
+            .set_alert(PortfolioAlertKind::Drawdown, Some(20.0))
+            .unwrap();
+        assert!(
+            tracker
+                .set_alert(PortfolioAlertKind::Drawdown, Some(150.0))
+                .is_err()
+        );
+
+        assert!(tracker.on_price("ETHUSDT", 1.0, None).is_empty());
+        assert!(tracker.on_price("BTCUSDT", 100.0, None).is_empty());
+
+        let triggers = tracker.on_price("BTCUSDT", 85.0, None);
+        assert_eq!(triggers.len(), 1);
+        assert_eq!(triggers[0].kind, PortfolioAlertKind::ValueBelow);
+        assert!(tracker.on_price("BTCUSDT", 84.0, None).is_empty());
+
+        let triggers = tracker.on_price("BTCUSDT", 75.0, None);
+        assert_eq!(triggers.len(), 1);
+        assert_eq!(triggers[0].kind, PortfolioAlertKind::Drawdown);
+        assert_eq!(triggers[0].value, 25.0);
+        assert_eq!(tracker.snapshot().peak_value, Some(100.0));
+
+        // Recovering re-arms the alert
+        tracker.on_price("BTCUSDT", 95.0, None);
+        assert_eq!(tracker.on_price("BTCUSDT", 89.0, None).len(), 1);
+    }
+}
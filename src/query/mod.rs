@@ -0,0 +1,600 @@
+//! One-shot market data queries for scripting
+//!
+//! Backs `xtrade quote`, `book`, `ticker` and `symbols`. Each command makes
+//! a single REST round trip, prints a table or `--json` to stdout and maps
+//! failures to distinct exit codes so shell scripts and cron jobs can branch
+//! on the outcome without starting the interactive session.
+
+use std::io::Write;
+
+use anyhow::{Result, anyhow};
+use serde::Serialize;
+
+use crate::binance::exchange_info::SymbolInfo;
+use crate::binance::rest::BinanceRestClient;
+use crate::binance::types::{RestApiError, RestTicker24hr};
+use crate::cli::QueryCommand;
+use crate::config::Config;
+
+/// Query printed its result
+pub const EXIT_OK: i32 = 0;
+/// Network, HTTP or parse failure
+pub const EXIT_FAILURE: i32 = 1;
+/// Invalid arguments; clap exits with the same code for malformed flags
+pub const EXIT_USAGE: i32 = 2;
+/// A requested symbol does not exist or a filter matched nothing
+pub const EXIT_NOT_FOUND: i32 = 3;
+
+/// Failures that map to a specific exit code
+#[derive(Debug, thiserror::Error)]
+pub enum QueryError {
+    #[error("{0}")]
+    Usage(String),
+    #[error("{0}")]
+    NotFound(String),
+}
+
+/// Run a query against the configured REST endpoint and return the process exit code
+pub async fn run(command: QueryCommand, config: &Config) -> i32 {
+    let client = BinanceRestClient::new(config.binance.rest_url.clone());
+    let mut stdout = std::io::stdout().lock();
+    match execute(&command, &client, &mut stdout).await {
+        Ok(()) => EXIT_OK,
+        Err(err) => {
+            eprintln!("error: {:#}", err);
+            exit_code(&err)
+        }
+    }
+}
+
+/// Exit code for a failed query
+pub fn exit_code(err: &anyhow::Error) -> i32 {
+    if let Some(query_error) = err.downcast_ref::<QueryError>() {
+        return match query_error {
+            QueryError::Usage(_) => EXIT_USAGE,
+            QueryError::NotFound(_) => EXIT_NOT_FOUND,
+        };
+    }
+    match err.downcast_ref::<RestApiError>() {
+        Some(RestApiError::InvalidSymbol(_)) => EXIT_NOT_FOUND,
+        _ => EXIT_FAILURE,
+    }
+}
+
+/// Execute a query, writing its output to `out`
+pub async fn execute<W: Write>(
+    command: &QueryCommand,
+    client: &BinanceRestClient,
+    out: &mut W,
+) -> Result<()> {
+    match command {
+        QueryCommand::Quote { symbols, output } => {
+            let symbols = normalize_symbols(symbols)?;
+            let tickers = fetch_tickers(client, &symbols).await?;
+            let rows: Vec<QuoteRow> = tickers.iter().map(QuoteRow::from_ticker).collect();
+            if output.json {
+                write_json(out, &rows)
+            } else {
+                let table = render_table(
+                    &["SYMBOL", "BID", "ASK", "SPREAD BPS", "LAST", "24H %"],
+                    rows.iter()
+                        .map(|row| {
+                            vec![
+                                row.symbol.clone(),
+                                format_number(row.bid),
+                                format_number(row.ask),
+                                format!("{:.2}", row.spread_bps),
+                                format_number(row.last),
+                                format!("{:+.2}", row.change_percent),
+                            ]
+                        })
+                        .collect(),
+                );
+                write!(out, "{}", table)?;
+                Ok(())
+            }
+        }
+        QueryCommand::Ticker { symbols, output } => {
+            let symbols = normalize_symbols(symbols)?;
+            let tickers = fetch_tickers(client, &symbols).await?;
+            let rows: Vec<TickerRow> = tickers.iter().map(TickerRow::from_ticker).collect();
+            if output.json {
+                write_json(out, &rows)
+            } else {
+                let table = render_table(
+                    &[
+                        "SYMBOL",
+                        "OPEN",
+                        "HIGH",
+                        "LOW",
+                        "LAST",
+                        "CHANGE",
+                        "24H %",
+                        "VOLUME",
+                        "QUOTE VOL",
+                        "TRADES",
+                    ],
+                    rows.iter()
+                        .map(|row| {
+                            vec![
+                                row.symbol.clone(),
+                                format_number(row.open),
+                                format_number(row.high),
+                                format_number(row.low),
+                                format_number(row.last),
+                                format_number(row.change),
+                                format!("{:+.2}", row.change_percent),
+                                format_number(row.volume),
+                                format!("{:.2}", row.quote_volume),
+                                row.trades.to_string(),
+                            ]
+                        })
+                        .collect(),
+                );
+                write!(out, "{}", table)?;
+                Ok(())
+            }
+        }
+        QueryCommand::Book {
+            symbol,
+            depth,
+            output,
+        } => {
+            let symbol = Config::normalize_symbol(symbol);
+            let snapshot = client
+                .get_depth_snapshot(&symbol, Some(u32::from(*depth)))
+                .await?;
+            let book = BookOutput {
+                symbol,
+                last_update_id: snapshot.last_update_id,
+                bids: book_levels(&snapshot.bids, usize::from(*depth))?,
+                asks: book_levels(&snapshot.asks, usize::from(*depth))?,
+            };
+            if output.json {
+                write_json(out, &book)
+            } else {
+                write!(out, "{}", render_book(&book))?;
+                Ok(())
+            }
+        }
+        QueryCommand::Symbols {
+            quote,
+            base,
+            all,
+            output,
+        } => {
+            let symbols: Vec<SymbolInfo> = client
+                .get_all_symbols()
+                .await?
+                .iter()
+                .map(SymbolInfo::from_symbol)
+                .collect();
+            let rows = filter_symbols(symbols, quote.as_deref(), base.as_deref(), *all);
+            if rows.is_empty() {
+                return Err(
+                    QueryError::NotFound("No symbols match the given filters".into()).into(),
+                );
+            }
+            if output.json {
+                write_json(out, &rows)
+            } else {
+                let table = render_table(
+                    &[
+                        "SYMBOL",
+                        "BASE",
+                        "QUOTE",
+                        "STATUS",
+                        "TICK",
+                        "STEP",
+                        "MIN NOTIONAL",
+                    ],
+                    rows.iter()
+                        .map(|row| {
+                            vec![
+                                row.symbol.clone(),
+                                row.base_asset.clone(),
+                                row.quote_asset.clone(),
+                                row.status.clone(),
+                                row.tick_size.map(format_number).unwrap_or_default(),
+                                row.step_size.map(format_number).unwrap_or_default(),
+                                row.min_notional.map(format_number).unwrap_or_default(),
+                            ]
+                        })
+                        .collect(),
+                );
+                write!(out, "{}", table)?;
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Quote row for `xtrade quote`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct QuoteRow {
+    pub symbol: String,
+    pub bid: f64,
+    pub ask: f64,
+    pub last: f64,
+    pub spread_bps: f64,
+    pub change_percent: f64,
+}
+
+impl QuoteRow {
+    fn from_ticker(ticker: &RestTicker24hr) -> Self {
+        let bid = parse_decimal(&ticker.bid_price);
+        let ask = parse_decimal(&ticker.ask_price);
+        let mid = (bid + ask) / 2.0;
+        let spread_bps = if mid > 0.0 {
+            (ask - bid) / mid * 10_000.0
+        } else {
+            0.0
+        };
+        Self {
+            symbol: ticker.symbol.clone(),
+            bid,
+            ask,
+            last: parse_decimal(&ticker.last_price),
+            spread_bps,
+            change_percent: parse_decimal(&ticker.price_change_percent),
+        }
+    }
+}
+
+/// 24h statistics row for `xtrade ticker`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TickerRow {
+    pub symbol: String,
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub last: f64,
+    pub change: f64,
+    pub change_percent: f64,
+    pub weighted_avg_price: f64,
+    pub volume: f64,
+    pub quote_volume: f64,
+    pub trades: u64,
+    pub open_time_ms: u64,
+    pub close_time_ms: u64,
+}
+
+impl TickerRow {
+    fn from_ticker(ticker: &RestTicker24hr) -> Self {
+        Self {
+            symbol: ticker.symbol.clone(),
+            open: parse_decimal(&ticker.open_price),
+            high: parse_decimal(&ticker.high_price),
+            low: parse_decimal(&ticker.low_price),
+            last: parse_decimal(&ticker.last_price),
+            change: parse_decimal(&ticker.price_change),
+            change_percent: parse_decimal(&ticker.price_change_percent),
+            weighted_avg_price: parse_decimal(&ticker.weighted_avg_price),
+            volume: parse_decimal(&ticker.volume),
+            quote_volume: parse_decimal(&ticker.quote_volume),
+            trades: ticker.count,
+            open_time_ms: ticker.open_time,
+            close_time_ms: ticker.close_time,
+        }
+    }
+}
+
+/// Order book snapshot for `xtrade book`, best levels first on both sides
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BookOutput {
+    pub symbol: String,
+    pub last_update_id: u64,
+    pub bids: Vec<BookLevel>,
+    pub asks: Vec<BookLevel>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BookLevel {
+    pub price: f64,
+    pub quantity: f64,
+    /// Quantity available from the best level down to this one
+    pub cumulative: f64,
+}
+
+/// Symbol row for `xtrade symbols`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SymbolRow {
+    pub symbol: String,
+    pub base_asset: String,
+    pub quote_asset: String,
+    pub status: String,
+    pub tick_size: Option<f64>,
+    pub step_size: Option<f64>,
+    pub min_notional: Option<f64>,
+}
+
+/// Normalize, de-duplicate and validate symbol arguments, keeping their order
+pub(crate) fn normalize_symbols(symbols: &[String]) -> Result<Vec<String>> {
+    let mut normalized: Vec<String> = Vec::with_capacity(symbols.len());
+    for symbol in symbols {
+        let symbol = Config::normalize_symbol(symbol.trim());
+        if symbol.is_empty() || !symbol.chars().all(|c| c.is_ascii_alphanumeric()) {
+            return Err(QueryError::Usage(format!("Invalid symbol argument '{}'", symbol)).into());
+        }
+        if !normalized.contains(&symbol) {
+            normalized.push(symbol);
+        }
+    }
+    Ok(normalized)
+}
+
+/// Fetch tickers and return them in the order the symbols were requested
+async fn fetch_tickers(
+    client: &BinanceRestClient,
+    symbols: &[String],
+) -> Result<Vec<RestTicker24hr>> {
+    let mut tickers = if symbols.len() == 1 {
+        vec![client.get_24hr_ticker(&symbols[0]).await?]
+    } else {
+        client.get_24hr_tickers(symbols).await?
+    };
+    tickers.sort_by_key(|ticker| {
+        symbols
+            .iter()
+            .position(|symbol| *symbol == ticker.symbol)
+            .unwrap_or(usize::MAX)
+    });
+
+    if let Some(missing) = symbols
+        .iter()
+        .find(|symbol| !tickers.iter().any(|ticker| &ticker.symbol == *symbol))
+    {
+        return Err(QueryError::NotFound(format!("No ticker returned for {}", missing)).into());
+    }
+    Ok(tickers)
+}
+
+fn book_levels(levels: &[[String; 2]], depth: usize) -> Result<Vec<BookLevel>> {
+    let mut cumulative = 0.0;
+    levels
+        .iter()
+        .take(depth)
+        .map(|[price, quantity]| {
+            let price = price
+                .parse::<f64>()
+                .map_err(|e| anyhow!("Invalid price '{}': {}", price, e))?;
+            let quantity = quantity
+                .parse::<f64>()
+                .map_err(|e| anyhow!("Invalid quantity '{}': {}", quantity, e))?;
+            cumulative += quantity;
+            Ok(BookLevel {
+                price,
+                quantity,
+                cumulative,
+            })
+        })
+        .collect()
+}
+
+fn filter_symbols(
+    symbols: Vec<SymbolInfo>,
+    quote: Option<&str>,
+    base: Option<&str>,
+    include_all: bool,
+) -> Vec<SymbolRow> {
+    let mut rows: Vec<SymbolRow> = symbols
+        .into_iter()
+        .filter(|info| include_all || info.status.is_trading())
+        .filter(|info| quote.map_or(true, |q| info.quote_asset.eq_ignore_ascii_case(q)))
+        .filter(|info| base.map_or(true, |b| info.base_asset.eq_ignore_ascii_case(b)))
+        .map(|info| SymbolRow {
+            status: info.status.to_string(),
+            symbol: info.symbol,
+            base_asset: info.base_asset,
+            quote_asset: info.quote_asset,
+            tick_size: info.tick_size,
+            step_size: info.step_size,
+            min_notional: info.min_notional,
+        })
+        .collect();
+    rows.sort_by(|a, b| a.symbol.cmp(&b.symbol));
+    rows
+}
+
+/// Asks from the furthest level down to the best, then bids from the best down
+fn render_book(book: &BookOutput) -> String {
+    let mut rows: Vec<Vec<String>> = book
+        .asks
+        .iter()
+        .rev()
+        .map(|level| book_row("ASK", level))
+        .collect();
+    rows.extend(book.bids.iter().map(|level| book_row("BID", level)));
+
+    let mut rendered = render_table(&["SIDE", "PRICE", "QTY", "CUM QTY"], rows);
+    if let (Some(bid), Some(ask)) = (book.bids.first(), book.asks.first()) {
+        rendered.push_str(&format!(
+            "{} spread {} ({:.2} bps), update id {}\n",
+            book.symbol,
+            format_number(ask.price - bid.price),
+            (ask.price - bid.price) / ((ask.price + bid.price) / 2.0) * 10_000.0,
+            book.last_update_id
+        ));
+    }
+    rendered
+}
+
+fn book_row(side: &str, level: &BookLevel) -> Vec<String> {
+    vec![
+        side.to_string(),
+        format_number(level.price),
+        format_number(level.quantity),
+        format_number(level.cumulative),
+    ]
+}
+
+/// Render an aligned plain-text table: first column left-aligned, the rest right-aligned
+pub fn render_table(headers: &[&str], rows: Vec<Vec<String>>) -> String {
+    let mut widths: Vec<usize> = headers.iter().map(|h| h.len()).collect();
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let format_row = |cells: Vec<&str>| {
+        let line = cells
+            .iter()
+            .zip(&widths)
+            .enumerate()
+            .map(|(index, (cell, width))| {
+                if index == 0 {
+                    format!("{:<width$}", cell, width = width)
+                } else {
+                    format!("{:>width$}", cell, width = width)
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("  ");
+        format!("{}\n", line.trim_end())
+    };
+
+    let mut rendered = format_row(headers.to_vec());
+    for row in &rows {
+        rendered.push_str(&format_row(row.iter().map(String::as_str).collect()));
+    }
+    rendered
+}
+
+fn write_json<W: Write, T: Serialize + ?Sized>(out: &mut W, value: &T) -> Result<()> {
+    serde_json::to_writer_pretty(&mut *out, value)?;
+    writeln!(out)?;
+    Ok(())
+}
+
+/// Shortest round-trip representation, without Binance's zero padding
+fn format_number(value: f64) -> String {
+    let rendered = format!("{}", value);
+    if rendered.contains('e') {
+        format!("{:.8}", value)
+            .trim_end_matches('0')
+            .trim_end_matches('.')
+            .to_string()
+    } else {
+        rendered
+    }
+}
+
+fn parse_decimal(value: &str) -> f64 {
+    value.parse().unwrap_or(0.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::binance::exchange_info::SymbolStatus;
+
+    fn ticker(symbol: &str, bid: &str, ask: &str) -> RestTicker24hr {
+        RestTicker24hr {
+            symbol: symbol.to_string(),
+            price_change: "10.00000000".into(),
+            price_change_percent: "1.250".into(),
+            weighted_avg_price: "805.0".into(),
+            last_price: "810.00000000".into(),
+            last_qty: "1.0".into(),
+            bid_price: bid.into(),
+            bid_qty: "2.0".into(),
+            ask_price: ask.into(),
+            ask_qty: "3.0".into(),
+            open_price: "800.0".into(),
+            high_price: "820.0".into(),
+            low_price: "790.0".into(),
+            volume: "1000.0".into(),
+            quote_volume: "805000.0".into(),
+            open_time: 1,
+            close_time: 2,
+            count: 42,
+        }
+    }
+
+    fn info(symbol: &str, base: &str, quote: &str, status: &str) -> SymbolInfo {
+        SymbolInfo {
+            symbol: symbol.into(),
+            base_asset: base.into(),
+            quote_asset: quote.into(),
+            status: SymbolStatus::parse(status),
+            tick_size: Some(0.01),
+            min_price: None,
+            max_price: None,
+            step_size: Some(0.001),
+            min_qty: None,
+            max_qty: None,
+            min_notional: Some(5.0),
+        }
+    }
+
+    #[test]
+    fn quote_row_computes_spread_in_bps() {
+        let row = QuoteRow::from_ticker(&ticker("BNBUSDT", "999.5", "1000.5"));
+        assert_eq!(row.bid, 999.5);
+        assert_eq!(row.last, 810.0);
+        assert!((row.spread_bps - 10.0).abs() < 1e-9);
+        assert!((row.change_percent - 1.25).abs() < 1e-9);
+    }
+
+    #[test]
+    fn table_aligns_columns() {
+        let table = render_table(
+            &["SYMBOL", "LAST"],
+            vec![
+                vec!["BTCUSDT".into(), "43000.5".into()],
+                vec!["ETHUSDT".into(), "2300".into()],
+            ],
+        );
+        assert_eq!(
+            table,
+            "SYMBOL      LAST\nBTCUSDT  43000.5\nETHUSDT     2300\n"
+        );
+    }
+
+    #[test]
+    fn symbol_filters_match_assets_and_status() {
+        let symbols = vec![
+            info("ETHBTC", "ETH", "BTC", "TRADING"),
+            info("BTCUSDT", "BTC", "USDT", "TRADING"),
+            info("OLDUSDT", "OLD", "USDT", "BREAK"),
+        ];
+
+        let rows = filter_symbols(symbols.clone(), Some("usdt"), None, false);
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].symbol, "BTCUSDT");
+
+        let rows = filter_symbols(symbols.clone(), Some("USDT"), None, true);
+        assert_eq!(rows.len(), 2);
+
+        let rows = filter_symbols(symbols, None, Some("ETH"), false);
+        assert_eq!(rows[0].symbol, "ETHBTC");
+    }
+
+    #[test]
+    fn exit_codes_distinguish_usage_missing_and_failures() {
+        assert_eq!(
+            exit_code(&QueryError::Usage("bad".into()).into()),
+            EXIT_USAGE
+        );
+        assert_eq!(
+            exit_code(&RestApiError::InvalidSymbol("Invalid symbol.".into()).into()),
+            EXIT_NOT_FOUND
+        );
+        assert_eq!(exit_code(&anyhow!("connection refused")), EXIT_FAILURE);
+        assert!(normalize_symbols(&["btc-usdt".into(), "BTCUSDT".into()]).unwrap() == ["BTCUSDT"]);
+        assert!(normalize_symbols(&["BTC/USDT".into()]).is_err());
+    }
+
+    #[test]
+    fn book_levels_accumulate_quantity() {
+        let levels = vec![
+            ["100.0".to_string(), "1.5".to_string()],
+            ["99.5".to_string(), "2.0".to_string()],
+            ["99.0".to_string(), "4.0".to_string()],
+        ];
+        let parsed = book_levels(&levels, 2).unwrap();
+        assert_eq!(parsed.len(), 2);
+        assert_eq!(parsed[1].cumulative, 3.5);
+    }
+}
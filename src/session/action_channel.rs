@@ -3,9 +3,13 @@
 use anyhow::Result;
 use tokio::sync::mpsc;
 
+use crate::account::AccountSnapshot;
 use crate::config::Config;
-use crate::market_data::MarketEvent;
+use crate::market_data::{ImpactRequest, MarketEvent};
 use crate::metrics::ConnectionMetrics;
+use crate::paper::PaperSnapshot;
+use crate::portfolio::PortfolioSnapshot;
+use crate::session::alert_history::AlertHistoryEntry;
 use crate::session::alert_manager::{Alert, AlertDirection, AlertOptions};
 use crate::session::command_router::InteractiveCommand;
 
@@ -32,7 +36,7 @@ pub enum SessionEvent {
         orderbook: Option<crate::binance::types::OrderBook>,
     },
     /// Configuration information
-    ConfigInfo { config: Config },
+    ConfigInfo { config: Box<Config> },
     /// Configuration reset
     ConfigReset,
     /// Configuration help
@@ -55,6 +59,40 @@ pub enum SessionEvent {
     AlertList { entries: Vec<String> },
     /// Full alert snapshot for UI surfaces
     AlertSnapshot { alerts: Vec<Alert> },
+    /// Full fired-alert history for UI surfaces
+    AlertHistorySnapshot { entries: Vec<AlertHistoryEntry> },
+    /// A fired alert appended to the history
+    AlertHistoryRecorded { entry: AlertHistoryEntry },
+    /// Open the alert history view, optionally filtered to a symbol
+    ShowAlertHistory { symbol: Option<String> },
+    /// Open or close the market-wide scanner view
+    ShowScanner { visible: bool },
+    /// Paper order placed, filled or cancelled
+    PaperNotification { message: String },
+    /// Paper balances, positions and orders for the positions panel
+    PaperSnapshot { snapshot: PaperSnapshot },
+    /// Open the paper trading panel
+    ShowPaper,
+    /// Exchange order executed or account stream state changed
+    AccountNotification { message: String },
+    /// Exchange balances, open orders and executions for the account panel
+    AccountSnapshot { snapshot: AccountSnapshot },
+    /// Open the account panel
+    ShowAccount,
+    /// Live order placed, cancelled or accepted by the test endpoint
+    OrderNotification { message: String },
+    /// Live order failed validation or was refused by the exchange
+    OrderRejected { message: String },
+    /// Order waiting for confirmation; `None` once it was sent or dropped
+    OrderStaged { summary: Option<String> },
+    /// Holding added, removed or portfolio alert changed
+    PortfolioNotification { message: String },
+    /// Valued holdings for the portfolio panel
+    PortfolioSnapshot { snapshot: PortfolioSnapshot },
+    /// Open the portfolio panel
+    ShowPortfolio,
+    /// Open the market impact panel for an order on the live book
+    ShowImpact { request: ImpactRequest },
     /// Add a price alert from UI
     AlertAdd {
         symbol: String,
@@ -128,6 +166,11 @@ impl ActionChannel {
         }
     }
 
+    /// Number of events waiting to be processed
+    pub fn pending_events(&self) -> usize {
+        self.event_rx.as_ref().map_or(0, |rx| rx.len())
+    }
+
     /// Get event sender for external use
     pub fn event_tx(&self) -> mpsc::UnboundedSender<SessionEvent> {
         self.event_tx.clone()
@@ -0,0 +1,339 @@
+//! Append-only journal of fired alerts with CSV export
+
+use anyhow::{Context, Result};
+use chrono::{TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use tracing::warn;
+
+use super::alert_manager::{AlertDirection, AlertInput, AlertTrigger};
+
+/// Default number of journal entries kept in memory
+pub const DEFAULT_ALERT_HISTORY_LIMIT: usize = 1_000;
+
+const CSV_HEADER: &str =
+    "timestamp,alert_key,alert_id,symbol,condition,threshold,price,best_bid,best_ask";
+
+/// A single fired alert as recorded in the journal
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AlertHistoryEntry {
+    pub alert_id: u64,
+    /// Creation time of the alert. Ids restart every session, so this tells
+    /// apart alerts that shared an id; 0 in journals written before it existed.
+    #[serde(default)]
+    pub alert_created_at_ms: u64,
+    pub symbol: String,
+    /// Journals written before trade flow alerts existed only hold price alerts
+    #[serde(default)]
+    pub input: AlertInput,
+    pub direction: AlertDirection,
+    pub threshold: f64,
+    /// Price (or trade flow value) that crossed the threshold
+    pub price: f64,
+    /// Best bid at trigger time, if the book was available
+    pub best_bid: Option<f64>,
+    /// Best ask at trigger time, if the book was available
+    pub best_ask: Option<f64>,
+    pub triggered_at_ms: u64,
+}
+
+impl AlertHistoryEntry {
+    /// Build an entry from a trigger and the top of book at that moment
+    pub fn from_trigger(
+        trigger: &AlertTrigger,
+        best_bid: Option<f64>,
+        best_ask: Option<f64>,
+        triggered_at_ms: u64,
+    ) -> Self {
+        Self {
+            alert_id: trigger.id,
+            alert_created_at_ms: trigger.created_at_ms,
+            symbol: trigger.symbol.clone(),
+            input: trigger.input,
+            direction: trigger.direction,
+            threshold: trigger.threshold,
+            price: trigger.price,
+            best_bid,
+            best_ask,
+            triggered_at_ms,
+        }
+    }
+
+    /// Key for the alert that stays unique across sessions, e.g. `1700000000000-7`
+    pub fn alert_key(&self) -> String {
+        format!("{}-{}", self.alert_created_at_ms, self.alert_id)
+    }
+
+    /// Human readable condition, e.g. `above 50000` or `cvd below -20`
+    pub fn condition(&self) -> String {
+        format!("{} {}", self.direction_label(), self.threshold)
+    }
+
+    /// Direction, prefixed with the input for trade flow alerts
+    fn direction_label(&self) -> String {
+        let direction = match self.direction {
+            AlertDirection::Above => "above",
+            AlertDirection::Below => "below",
+        };
+        match self.input {
+            AlertInput::Price => direction.to_string(),
+            input => format!("{} {}", input.label(), direction),
+        }
+    }
+
+    /// Trigger time formatted as RFC 3339 (UTC)
+    pub fn timestamp(&self) -> String {
+        Utc.timestamp_millis_opt(self.triggered_at_ms as i64)
+            .single()
+            .map(|ts| ts.to_rfc3339_opts(chrono::SecondsFormat::Millis, true))
+            .unwrap_or_else(|| self.triggered_at_ms.to_string())
+    }
+
+    /// Single CSV record matching the export header
+    pub fn to_csv_record(&self) -> String {
+        format!(
+            "{},{},{},{},{},{},{},{},{}",
+            self.timestamp(),
+            self.alert_key(),
+            self.alert_id,
+            self.symbol,
+            self.condition(),
+            self.threshold,
+            self.price,
+            self.best_bid.map(|v| v.to_string()).unwrap_or_default(),
+            self.best_ask.map(|v| v.to_string()).unwrap_or_default(),
+        )
+    }
+}
+
+/// Fired alerts, oldest first, backed by an append-only NDJSON file
+#[derive(Debug, Clone)]
+pub struct AlertHistory {
+    entries: VecDeque<AlertHistoryEntry>,
+    max_entries: usize,
+    path: Option<PathBuf>,
+}
+
+impl Default for AlertHistory {
+    fn default() -> Self {
+        Self::new(DEFAULT_ALERT_HISTORY_LIMIT)
+    }
+}
+
+impl AlertHistory {
+    /// Create an in-memory journal
+    pub fn new(max_entries: usize) -> Self {
+        Self {
+            entries: VecDeque::new(),
+            max_entries: max_entries.max(1),
+            path: None,
+        }
+    }
+
+    /// Load the newest `max_entries` records from a journal file.
+    /// Unreadable lines are skipped; a missing file is created on first record.
+    pub fn load(path: impl AsRef<Path>, max_entries: usize) -> Self {
+        let path = path.as_ref().to_path_buf();
+        let mut history = Self::new(max_entries);
+
+        match fs::read_to_string(&path) {
+            Ok(content) => {
+                let mut skipped = 0usize;
+                for line in content.lines().filter(|l| !l.trim().is_empty()) {
+                    match serde_json::from_str::<AlertHistoryEntry>(line) {
+                        Ok(entry) => history.push_in_memory(entry),
+                        Err(_) => skipped += 1,
+                    }
+                }
+                if skipped > 0 {
+                    warn!(
+                        "Skipped {} malformed alert history records in {}",
+                        skipped,
+                        path.display()
+                    );
+                }
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => warn!("Failed to read alert history {}: {}", path.display(), e),
+        }
+
+        history.path = Some(path);
+        history
+    }
+
+    /// Entries oldest first
+    pub fn entries(&self) -> impl Iterator<Item = &AlertHistoryEntry> {
+        self.entries.iter()
+    }
+
+    /// Number of entries held in memory
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether the journal is empty
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Record a fired alert and append it to the journal file
+    pub fn record(&mut self, entry: AlertHistoryEntry) {
+        self.append_to_file(&entry);
+        self.push_in_memory(entry);
+    }
+
+    /// Entries for a symbol (or all when `None`), oldest first
+    pub fn filtered(&self, symbol: Option<&str>) -> Vec<AlertHistoryEntry> {
+        self.entries
+            .iter()
+            .filter(|entry| symbol.map_or(true, |s| entry.symbol.eq_ignore_ascii_case(s)))
+            .cloned()
+            .collect()
+    }
+
+    fn push_in_memory(&mut self, entry: AlertHistoryEntry) {
+        self.entries.push_back(entry);
+        while self.entries.len() > self.max_entries {
+            self.entries.pop_front();
+        }
+    }
+
+    fn append_to_file(&self, entry: &AlertHistoryEntry) {
+        let Some(path) = &self.path else {
+            return;
+        };
+
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            if let Err(e) = fs::create_dir_all(parent) {
+                warn!(
+                    "Failed to create alert history directory {}: {}",
+                    parent.display(),
+                    e
+                );
+                return;
+            }
+        }
+
+        let result = serde_json::to_string(entry)
+            .map_err(std::io::Error::other)
+            .and_then(|line| {
+                OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(path)
+                    .and_then(|mut file| writeln!(file, "{}", line))
+            });
+        if let Err(e) = result {
+            warn!("Failed to write alert history {}: {}", path.display(), e);
+        }
+    }
+}
+
+/// Render entries as CSV with a header row
+pub fn to_csv<'a>(entries: impl IntoIterator<Item = &'a AlertHistoryEntry>) -> String {
+    let mut csv = String::from(CSV_HEADER);
+    csv.push('\n');
+    for entry in entries {
+        csv.push_str(&entry.to_csv_record());
+        csv.push('\n');
+    }
+    csv
+}
+
+/// Write entries to a CSV file, creating parent directories as needed
+pub fn export_csv<'a>(
+    path: impl AsRef<Path>,
+    entries: impl IntoIterator<Item = &'a AlertHistoryEntry>,
+) -> Result<()> {
+    let path = path.as_ref();
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create directory {}", parent.display()))?;
+    }
+    fs::write(path, to_csv(entries))
+        .with_context(|| format!("Failed to write alert history export {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn entry(id: u64, symbol: &str, price: f64) -> AlertHistoryEntry {
+        AlertHistoryEntry {
+            alert_id: id,
+            alert_created_at_ms: 1_699_999_000_000,
+            symbol: symbol.to_string(),
+            input: AlertInput::Price,
+            direction: AlertDirection::Above,
+            threshold: 100.0,
+            price,
+            best_bid: Some(price - 0.5),
+            best_ask: None,
+            triggered_at_ms: 1_700_000_000_000,
+        }
+    }
+
+    #[test]
+    fn journal_appends_and_reloads_newest_entries() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("alerts").join("history.ndjson");
+
+        let mut history = AlertHistory::load(&path, 2);
+        history.record(entry(1, "BTCUSDT", 101.0));
+        history.record(entry(2, "ETHUSDT", 102.0));
+        history.record(entry(3, "BTCUSDT", 103.0));
+        assert_eq!(history.len(), 2);
+
+        // The file keeps every record; only memory is bounded
+        let content = fs::read_to_string(&path).unwrap();
+        assert_eq!(content.lines().count(), 3);
+
+        fs::write(&path, format!("{}not json\n", content)).unwrap();
+        let reloaded = AlertHistory::load(&path, 2);
+        let ids: Vec<u64> = reloaded.entries().map(|e| e.alert_id).collect();
+        assert_eq!(ids, vec![2, 3]);
+    }
+
+    #[test]
+    fn filters_by_symbol_case_insensitively() {
+        let mut history = AlertHistory::new(10);
+        history.record(entry(1, "BTCUSDT", 101.0));
+        history.record(entry(2, "ETHUSDT", 102.0));
+
+        assert_eq!(history.filtered(Some("btcusdt")).len(), 1);
+        assert_eq!(history.filtered(None).len(), 2);
+    }
+
+    #[test]
+    fn csv_export_includes_top_of_book() {
+        let csv = to_csv(&[entry(7, "BTCUSDT", 101.0)]);
+        let mut lines = csv.lines();
+        assert_eq!(lines.next(), Some(CSV_HEADER));
+        assert_eq!(
+            lines.next(),
+            Some("2023-11-14T22:13:20.000Z,1699999000000-7,7,BTCUSDT,above 100,100,101,100.5,")
+        );
+
+        let flow = AlertHistoryEntry {
+            input: AlertInput::Cvd,
+            direction: AlertDirection::Below,
+            threshold: -20.0,
+            price: -25.0,
+            ..entry(8, "BTCUSDT", 0.0)
+        };
+        assert_eq!(flow.condition(), "cvd below -20");
+        assert!(flow.to_csv_record().contains(",cvd below -20,-20,-25,"));
+    }
+
+    #[test]
+    fn entries_without_input_load_as_price_alerts() {
+        let line = r#"{"alert_id":1,"symbol":"BTCUSDT","direction":"above","threshold":100.0,"price":101.0,"best_bid":null,"best_ask":null,"triggered_at_ms":1}"#;
+        let entry: AlertHistoryEntry = serde_json::from_str(line).unwrap();
+        assert_eq!(entry.input, AlertInput::Price);
+        assert_eq!(entry.alert_key(), "0-1");
+    }
+}
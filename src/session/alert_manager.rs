@@ -1,8 +1,11 @@
-//! In-memory alert manager for price threshold alerts
+//! In-memory alert manager for price and trade flow threshold alerts
 
 use anyhow::{Result, anyhow};
+use serde::{Deserialize, Serialize};
 use std::time::{SystemTime, UNIX_EPOCH};
 
+use crate::market_data::{Bar, BookTicker, MicrostructureSnapshot, TradeAnalyticsSnapshot};
+
 const MAX_ALERTS: usize = 50;
 const DEFAULT_ALERT_COOLDOWN_MS: u64 = 0;
 const DEFAULT_ALERT_HYSTERESIS_PCT: f64 = 0.0;
@@ -37,8 +40,174 @@ impl AlertOptions {
     }
 }
 
+/// Hysteresis given either as an absolute price distance or a percent of the threshold
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HysteresisSpec {
+    Absolute(f64),
+    Percent(f64),
+}
+
+impl HysteresisSpec {
+    /// Resolve to an absolute price distance for a threshold
+    pub fn resolve(self, threshold: f64) -> f64 {
+        match self {
+            Self::Absolute(value) => value,
+            Self::Percent(pct) => threshold.abs() * (pct / 100.0),
+        }
+    }
+}
+
+/// Market value an alert threshold is compared against
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertInput {
+    /// Last trade or ticker price
+    #[default]
+    Price,
+    /// Session cumulative volume delta in the base asset
+    Cvd,
+    /// Exchange fills per second over the trade analytics window
+    TradeRate,
+    /// Percent distance of the last price from the session VWAP
+    VwapDeviation,
+    /// Bid minus ask depth over their sum for the top book levels
+    DepthImbalance,
+    /// Order flow imbalance over the microstructure window in the base asset
+    OrderFlowImbalance,
+    /// Distance of the microprice from the mid in basis points
+    MicropriceOffset,
+    /// Best ask minus best bid in basis points of the mid
+    Spread,
+    /// Midpoint of the best bid and ask
+    Mid,
+}
+
+impl AlertInput {
+    /// Inputs derived from trade analytics rather than prices
+    pub const TRADE_FLOW: [AlertInput; 3] = [
+        AlertInput::Cvd,
+        AlertInput::TradeRate,
+        AlertInput::VwapDeviation,
+    ];
+
+    /// Inputs derived from order book microstructure
+    pub const MICROSTRUCTURE: [AlertInput; 3] = [
+        AlertInput::DepthImbalance,
+        AlertInput::OrderFlowImbalance,
+        AlertInput::MicropriceOffset,
+    ];
+
+    /// Inputs derived from the best bid/offer stream
+    pub const BOOK_TICKER: [AlertInput; 2] = [AlertInput::Spread, AlertInput::Mid];
+
+    /// Keyword used in commands and alert descriptions
+    pub fn label(self) -> &'static str {
+        match self {
+            AlertInput::Price => "price",
+            AlertInput::Cvd => "cvd",
+            AlertInput::TradeRate => "rate",
+            AlertInput::VwapDeviation => "vwap",
+            AlertInput::DepthImbalance => "imbalance",
+            AlertInput::OrderFlowImbalance => "ofi",
+            AlertInput::MicropriceOffset => "micro",
+            AlertInput::Spread => "spread",
+            AlertInput::Mid => "mid",
+        }
+    }
+
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw.to_ascii_lowercase().as_str() {
+            "price" => Some(AlertInput::Price),
+            "cvd" | "delta" => Some(AlertInput::Cvd),
+            "rate" | "tps" => Some(AlertInput::TradeRate),
+            "vwap" => Some(AlertInput::VwapDeviation),
+            "imbalance" | "imb" => Some(AlertInput::DepthImbalance),
+            "ofi" => Some(AlertInput::OrderFlowImbalance),
+            "micro" | "microprice" => Some(AlertInput::MicropriceOffset),
+            "spread" => Some(AlertInput::Spread),
+            "mid" => Some(AlertInput::Mid),
+            _ => None,
+        }
+    }
+
+    /// Current value of a trade flow input, `None` for other inputs or before any trade
+    pub fn value_from(self, analytics: &TradeAnalyticsSnapshot) -> Option<f64> {
+        match self {
+            AlertInput::Cvd => Some(analytics.cumulative_delta),
+            AlertInput::TradeRate => Some(analytics.trades_per_sec),
+            AlertInput::VwapDeviation => analytics.vwap_deviation_percent(),
+            _ => None,
+        }
+    }
+
+    /// Current value of a microstructure input, `None` for other inputs or a one-sided book
+    pub fn value_from_book(self, snapshot: &MicrostructureSnapshot) -> Option<f64> {
+        match self {
+            AlertInput::DepthImbalance => snapshot.depth_imbalance,
+            AlertInput::OrderFlowImbalance => Some(snapshot.order_flow_imbalance),
+            AlertInput::MicropriceOffset => snapshot.microprice_offset_bps(),
+            _ => None,
+        }
+    }
+
+    /// Current value of a best bid/offer input, `None` for other inputs or a one-sided quote
+    pub fn value_from_ticker(self, ticker: &BookTicker) -> Option<f64> {
+        if ticker.bid_price <= 0.0 || ticker.ask_price <= 0.0 {
+            return None;
+        }
+        match self {
+            AlertInput::Spread => Some(ticker.spread() / ticker.mid() * 10_000.0),
+            AlertInput::Mid => Some(ticker.mid()),
+            _ => None,
+        }
+    }
+
+    /// Prices, rates and spreads are positive; delta, VWAP distance and book signals may sit
+    /// on either side of zero, and depth imbalance is bounded by one
+    pub fn validate_threshold(self, threshold: f64) -> Result<()> {
+        if !threshold.is_finite() {
+            return Err(anyhow!("Threshold must be a finite number"));
+        }
+        if self == AlertInput::DepthImbalance && threshold.abs() >= 1.0 {
+            return Err(anyhow!(
+                "Depth imbalance threshold must lie between -1 and 1"
+            ));
+        }
+        let signed = matches!(
+            self,
+            AlertInput::Cvd
+                | AlertInput::VwapDeviation
+                | AlertInput::DepthImbalance
+                | AlertInput::OrderFlowImbalance
+                | AlertInput::MicropriceOffset
+        );
+        if !signed && threshold <= 0.0 {
+            return Err(anyhow!("Threshold must be a positive, finite number"));
+        }
+        Ok(())
+    }
+}
+
+/// Partial modification applied to an existing alert; `None` fields are left unchanged
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct AlertUpdate {
+    pub direction: Option<AlertDirection>,
+    pub threshold: Option<f64>,
+    pub repeat: Option<AlertRepeat>,
+    pub cooldown_ms: Option<u64>,
+    pub hysteresis: Option<HysteresisSpec>,
+}
+
+impl AlertUpdate {
+    /// Whether the update changes nothing
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
 /// Direction for price threshold comparison
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum AlertDirection {
     Above,
     Below,
@@ -49,6 +218,7 @@ pub enum AlertDirection {
 pub struct Alert {
     pub id: u64,
     pub symbol: String,
+    pub input: AlertInput,
     pub direction: AlertDirection,
     pub threshold: f64,
     pub triggered: bool,
@@ -58,15 +228,35 @@ pub struct Alert {
     pub cooldown_ms: u64,
     pub hysteresis: f64,
     pub last_notified_ms: Option<u64>,
+    pub paused: bool,
+    pub snoozed_until_ms: Option<u64>,
+    /// Trade time of the latest print or bar that reached the threshold
+    pub last_reached_ms: Option<u64>,
+}
+
+impl Alert {
+    /// Whether the alert is snoozed at the given time
+    pub fn is_snoozed(&self, now_ms: u64) -> bool {
+        self.snoozed_until_ms.is_some_and(|until| until > now_ms)
+    }
+
+    /// Whether the alert is currently evaluated against prices
+    pub fn is_active(&self, now_ms: u64) -> bool {
+        !self.paused && !self.is_snoozed(now_ms)
+    }
 }
 
 /// Trigger information returned when an alert fires
 #[derive(Debug, Clone, PartialEq)]
 pub struct AlertTrigger {
     pub id: u64,
+    /// Creation time of the alert; with `id` it identifies the alert across sessions
+    pub created_at_ms: u64,
     pub symbol: String,
+    pub input: AlertInput,
     pub direction: AlertDirection,
     pub threshold: f64,
+    /// Input value that crossed the threshold
     pub price: f64,
 }
 
@@ -95,13 +285,25 @@ impl AlertManager {
         self.add_alert_with_options(symbol, direction, threshold, options)
     }
 
-    /// Add a new alert with explicit options
+    /// Add a new price alert with explicit options
     pub fn add_alert_with_options(
         &mut self,
         symbol: impl Into<String>,
         direction: AlertDirection,
         threshold: f64,
         options: AlertOptions,
+    ) -> Result<Alert> {
+        self.add_input_alert(symbol, AlertInput::Price, direction, threshold, options)
+    }
+
+    /// Add a new alert on any input with explicit options
+    pub fn add_input_alert(
+        &mut self,
+        symbol: impl Into<String>,
+        input: AlertInput,
+        direction: AlertDirection,
+        threshold: f64,
+        options: AlertOptions,
     ) -> Result<Alert> {
         if self.alerts.len() >= MAX_ALERTS {
             return Err(anyhow!(
@@ -110,9 +312,7 @@ impl AlertManager {
             ));
         }
 
-        if !threshold.is_finite() || threshold <= 0.0 {
-            return Err(anyhow!("Threshold must be a positive, finite number"));
-        }
+        input.validate_threshold(threshold)?;
         if !options.hysteresis.is_finite() || options.hysteresis < 0.0 {
             return Err(anyhow!("Hysteresis must be a non-negative, finite number"));
         }
@@ -125,6 +325,7 @@ impl AlertManager {
         let alert = Alert {
             id: self.next_id,
             symbol,
+            input,
             direction,
             threshold,
             triggered: false,
@@ -134,6 +335,9 @@ impl AlertManager {
             cooldown_ms: options.cooldown_ms,
             hysteresis: options.hysteresis,
             last_notified_ms: None,
+            paused: false,
+            snoozed_until_ms: None,
+            last_reached_ms: None,
         };
         self.next_id += 1;
         self.alerts.push(alert.clone());
@@ -145,6 +349,73 @@ impl AlertManager {
         self.alerts.clone()
     }
 
+    /// Apply a partial update to an alert, re-arming it when the trigger condition changes
+    pub fn update_alert(&mut self, id: u64, update: AlertUpdate) -> Result<Alert> {
+        if update.is_empty() {
+            return Err(anyhow!("Nothing to update for alert #{}", id));
+        }
+
+        let alert = self.alert_mut(id)?;
+        let threshold = update.threshold.unwrap_or(alert.threshold);
+        alert.input.validate_threshold(threshold)?;
+        let hysteresis = update
+            .hysteresis
+            .map(|spec| spec.resolve(threshold))
+            .unwrap_or(alert.hysteresis);
+        if !hysteresis.is_finite() || hysteresis < 0.0 {
+            return Err(anyhow!("Hysteresis must be a non-negative, finite number"));
+        }
+
+        let condition_changed = update.threshold.is_some_and(|t| t != alert.threshold)
+            || update.direction.is_some_and(|d| d != alert.direction);
+
+        alert.threshold = threshold;
+        alert.hysteresis = hysteresis;
+        if let Some(direction) = update.direction {
+            alert.direction = direction;
+        }
+        if let Some(repeat) = update.repeat {
+            alert.repeat = repeat;
+        }
+        if let Some(cooldown_ms) = update.cooldown_ms {
+            alert.cooldown_ms = cooldown_ms;
+        }
+        if condition_changed {
+            alert.triggered = false;
+            alert.last_notified_ms = None;
+            alert.last_reached_ms = None;
+        }
+
+        Ok(alert.clone())
+    }
+
+    /// Pause or resume an alert
+    pub fn set_paused(&mut self, id: u64, paused: bool) -> Result<Alert> {
+        let alert = self.alert_mut(id)?;
+        alert.paused = paused;
+        if !paused {
+            alert.snoozed_until_ms = None;
+        }
+        Ok(alert.clone())
+    }
+
+    /// Suppress an alert for a duration
+    pub fn snooze(&mut self, id: u64, duration_ms: u64) -> Result<Alert> {
+        if duration_ms == 0 {
+            return Err(anyhow!("Snooze duration must be greater than 0"));
+        }
+        let alert = self.alert_mut(id)?;
+        alert.snoozed_until_ms = Some(now_ms().saturating_add(duration_ms));
+        Ok(alert.clone())
+    }
+
+    fn alert_mut(&mut self, id: u64) -> Result<&mut Alert> {
+        self.alerts
+            .iter_mut()
+            .find(|alert| alert.id == id)
+            .ok_or_else(|| anyhow!("Alert #{} not found", id))
+    }
+
     /// Clear a single alert by id
     pub fn clear_alert(&mut self, id: u64) -> bool {
         let len_before = self.alerts.len();
@@ -161,6 +432,106 @@ impl AlertManager {
 
     /// Evaluate alerts for a symbol against the latest price and return any triggers
     pub fn evaluate_price(&mut self, symbol: &str, price: f64) -> (Vec<AlertTrigger>, bool) {
+        self.evaluate(symbol, AlertInput::Price, price)
+    }
+
+    /// Whether any alert watches the given input, so callers can skip evaluation
+    pub fn has_input(&self, input: AlertInput) -> bool {
+        self.alerts.iter().any(|alert| alert.input == input)
+    }
+
+    /// Evaluate alerts on one input for a symbol and return any triggers
+    pub fn evaluate(
+        &mut self,
+        symbol: &str,
+        input: AlertInput,
+        price: f64,
+    ) -> (Vec<AlertTrigger>, bool) {
+        self.evaluate_at(symbol, input, price, None)
+    }
+
+    /// Evaluate price alerts against a trade print, remembering which thresholds it reached
+    pub fn evaluate_trade_price(
+        &mut self,
+        symbol: &str,
+        price: f64,
+        time_ms: u64,
+    ) -> (Vec<AlertTrigger>, bool) {
+        self.evaluate_at(symbol, AlertInput::Price, price, Some(time_ms))
+    }
+
+    /// Fire price alerts on a crossing inside a closed bar that no print reached
+    ///
+    /// `above` alerts are checked against the high and `below` alerts against the
+    /// low. Alerts a print reached during the bar are skipped, bars never re-arm
+    /// an alert and the last price stays that of the newest print.
+    pub fn evaluate_bar(&mut self, symbol: &str, bar: &Bar) -> (Vec<AlertTrigger>, bool) {
+        if !bar.is_closed || !bar.high.is_finite() || !bar.low.is_finite() {
+            return (Vec::new(), false);
+        }
+
+        let now = now_ms();
+        let mut triggers = Vec::new();
+        let mut state_changed = false;
+        for alert in self
+            .alerts
+            .iter_mut()
+            .filter(|a| a.symbol == symbol && a.input == AlertInput::Price)
+        {
+            if !alert.is_active(now) || alert.triggered {
+                continue;
+            }
+            if alert
+                .last_reached_ms
+                .is_some_and(|reached| reached >= bar.open_time_ms)
+            {
+                continue;
+            }
+            let (price, crossed) = match alert.direction {
+                AlertDirection::Above => (
+                    bar.high,
+                    bar.high >= alert.threshold
+                        && alert.last_price.map_or(true, |last| last < alert.threshold),
+                ),
+                AlertDirection::Below => (
+                    bar.low,
+                    bar.low <= alert.threshold
+                        && alert.last_price.map_or(true, |last| last > alert.threshold),
+                ),
+            };
+            if !crossed {
+                continue;
+            }
+
+            alert.triggered = true;
+            alert.last_reached_ms = Some(bar.close_time_ms);
+            alert.snoozed_until_ms = None;
+            state_changed = true;
+            if should_notify(alert, now) {
+                triggers.push(AlertTrigger {
+                    id: alert.id,
+                    created_at_ms: alert.created_at_ms,
+                    symbol: alert.symbol.clone(),
+                    input: alert.input,
+                    direction: alert.direction,
+                    threshold: alert.threshold,
+                    price,
+                });
+                alert.last_notified_ms = Some(now);
+            }
+        }
+
+        (triggers, state_changed)
+    }
+
+    /// Compare each alert with `price`; `reached_at` is the trade time of a print
+    fn evaluate_at(
+        &mut self,
+        symbol: &str,
+        input: AlertInput,
+        price: f64,
+        reached_at: Option<u64>,
+    ) -> (Vec<AlertTrigger>, bool) {
         if !price.is_finite() {
             return (Vec::new(), false);
         }
@@ -169,17 +540,23 @@ impl AlertManager {
         let mut triggers = Vec::new();
         let mut state_changed = false;
 
-        for alert in self.alerts.iter_mut().filter(|a| a.symbol == symbol) {
+        for alert in self
+            .alerts
+            .iter_mut()
+            .filter(|a| a.symbol == symbol && a.input == input)
+        {
+            if !alert.is_active(now) {
+                // Track the price so resuming does not fire on a stale crossing
+                alert.last_price = Some(price);
+                continue;
+            }
+            if alert.snoozed_until_ms.is_some() {
+                alert.snoozed_until_ms = None;
+                state_changed = true;
+            }
+
             let was_triggered = alert.triggered;
             let previous_price = alert.last_price;
-            let should_notify = |alert: &Alert| match alert.repeat {
-                AlertRepeat::Once => alert.last_notified_ms.is_none(),
-                AlertRepeat::Repeat => alert
-                    .last_notified_ms
-                    .map(|last| now.saturating_sub(last) >= alert.cooldown_ms)
-                    .unwrap_or(true),
-            };
-
             match alert.direction {
                 AlertDirection::Above => {
                     if alert.triggered {
@@ -192,10 +569,12 @@ impl AlertManager {
                         && previous_price.map_or(true, |prev| prev < alert.threshold)
                     {
                         alert.triggered = true;
-                        if should_notify(alert) {
+                        if should_notify(alert, now) {
                             triggers.push(AlertTrigger {
                                 id: alert.id,
+                                created_at_ms: alert.created_at_ms,
                                 symbol: alert.symbol.clone(),
+                                input: alert.input,
                                 direction: alert.direction,
                                 threshold: alert.threshold,
                                 price,
@@ -215,10 +594,12 @@ impl AlertManager {
                         && previous_price.map_or(true, |prev| prev > alert.threshold)
                     {
                         alert.triggered = true;
-                        if should_notify(alert) {
+                        if should_notify(alert, now) {
                             triggers.push(AlertTrigger {
                                 id: alert.id,
+                                created_at_ms: alert.created_at_ms,
                                 symbol: alert.symbol.clone(),
+                                input: alert.input,
                                 direction: alert.direction,
                                 threshold: alert.threshold,
                                 price,
@@ -230,6 +611,14 @@ impl AlertManager {
             }
 
             alert.last_price = Some(price);
+            let reached = match alert.direction {
+                AlertDirection::Above => price >= alert.threshold,
+                AlertDirection::Below => price <= alert.threshold,
+            };
+            if let Some(time_ms) = reached_at.filter(|_| reached) {
+                alert.last_reached_ms =
+                    Some(alert.last_reached_ms.map_or(time_ms, |t| t.max(time_ms)));
+            }
 
             if alert.triggered != was_triggered {
                 state_changed = true;
@@ -246,9 +635,226 @@ impl Default for AlertManager {
     }
 }
 
+/// Whether a firing alert may notify, given its repeat mode and cooldown
+fn should_notify(alert: &Alert, now: u64) -> bool {
+    match alert.repeat {
+        AlertRepeat::Once => alert.last_notified_ms.is_none(),
+        AlertRepeat::Repeat => alert
+            .last_notified_ms
+            .map(|last| now.saturating_sub(last) >= alert.cooldown_ms)
+            .unwrap_or(true),
+    }
+}
+
 fn now_ms() -> u64 {
     SystemTime::now()
         .duration_since(UNIX_EPOCH)
         .unwrap_or_default()
         .as_millis() as u64
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn update_rearms_alert_when_threshold_changes() {
+        let mut manager = AlertManager::new();
+        let alert = manager
+            .add_alert("btcusdt", AlertDirection::Above, 100.0)
+            .unwrap();
+
+        let (triggers, _) = manager.evaluate_price("BTCUSDT", 101.0);
+        assert_eq!(triggers.len(), 1);
+
+        let updated = manager
+            .update_alert(
+                alert.id,
+                AlertUpdate {
+                    threshold: Some(110.0),
+                    hysteresis: Some(HysteresisSpec::Percent(1.0)),
+                    ..AlertUpdate::default()
+                },
+            )
+            .unwrap();
+        assert!(!updated.triggered);
+        assert!((updated.hysteresis - 1.1).abs() < 1e-9);
+
+        let (triggers, _) = manager.evaluate_price("BTCUSDT", 111.0);
+        assert_eq!(triggers.len(), 1);
+        assert!(manager.update_alert(99, AlertUpdate::default()).is_err());
+    }
+
+    #[test]
+    fn closed_bar_fires_only_crossings_no_print_reached() {
+        let bar = |open_time_ms: u64, high: f64, low: f64, close: f64| Bar {
+            open_time_ms,
+            close_time_ms: open_time_ms + 999,
+            open: close,
+            high,
+            low,
+            close,
+            volume: 1.0,
+            buy_volume: 0.5,
+            trades: 2,
+            is_closed: true,
+        };
+        let mut manager = AlertManager::new();
+        manager
+            .add_alert("btcusdt", AlertDirection::Above, 105.0)
+            .unwrap();
+        manager
+            .add_alert("btcusdt", AlertDirection::Below, 95.0)
+            .unwrap();
+        manager.evaluate_trade_price("BTCUSDT", 100.0, 500);
+
+        // The wick through 105 was never printed on its own
+        let (triggers, _) = manager.evaluate_bar("BTCUSDT", &bar(0, 106.0, 99.0, 101.0));
+        assert_eq!(triggers.len(), 1);
+        assert_eq!(triggers[0].direction, AlertDirection::Above);
+        assert_eq!(triggers[0].price, 106.0);
+        // A longer bar over the same wick does not fire it again
+        let (triggers, _) = manager.evaluate_bar("BTCUSDT", &bar(0, 106.0, 99.0, 101.0));
+        assert!(triggers.is_empty());
+
+        // A print fires and a later one re-arms; the bar holding both stays quiet
+        manager.evaluate_trade_price("BTCUSDT", 104.0, 1_500);
+        assert_eq!(
+            manager
+                .evaluate_trade_price("BTCUSDT", 106.0, 2_100)
+                .0
+                .len(),
+            1
+        );
+        manager.evaluate_trade_price("BTCUSDT", 104.0, 2_200);
+        let (triggers, _) = manager.evaluate_bar("BTCUSDT", &bar(2_000, 106.0, 94.0, 104.0));
+        assert_eq!(triggers.len(), 1);
+        assert_eq!(triggers[0].direction, AlertDirection::Below);
+
+        // Bars arrive after newer prints and never replace the last price
+        assert!(
+            manager
+                .list_alerts()
+                .iter()
+                .all(|alert| alert.last_price == Some(104.0))
+        );
+    }
+
+    #[test]
+    fn paused_and_snoozed_alerts_do_not_fire() {
+        let mut manager = AlertManager::new();
+        let alert = manager
+            .add_alert("ETHUSDT", AlertDirection::Below, 50.0)
+            .unwrap();
+
+        manager.set_paused(alert.id, true).unwrap();
+        assert!(manager.evaluate_price("ETHUSDT", 49.0).0.is_empty());
+
+        manager.set_paused(alert.id, false).unwrap();
+        manager.snooze(alert.id, 60_000).unwrap();
+        assert!(manager.evaluate_price("ETHUSDT", 51.0).0.is_empty());
+        assert!(manager.evaluate_price("ETHUSDT", 48.0).0.is_empty());
+
+        // Resuming clears the snooze; the next fresh crossing fires
+        manager.set_paused(alert.id, false).unwrap();
+        manager.evaluate_price("ETHUSDT", 51.0);
+        assert_eq!(manager.evaluate_price("ETHUSDT", 49.0).0.len(), 1);
+    }
+
+    #[test]
+    fn trade_flow_alerts_only_see_their_input() {
+        let mut manager = AlertManager::new();
+        let options = AlertOptions::default_for_threshold(-50.0);
+        let alert = manager
+            .add_input_alert(
+                "BTCUSDT",
+                AlertInput::Cvd,
+                AlertDirection::Below,
+                -50.0,
+                options,
+            )
+            .unwrap();
+        assert!(manager.has_input(AlertInput::Cvd));
+        assert!(!manager.has_input(AlertInput::TradeRate));
+
+        // Prices never reach a CVD alert
+        assert!(manager.evaluate_price("BTCUSDT", -100.0).0.is_empty());
+
+        manager.evaluate("BTCUSDT", AlertInput::Cvd, 10.0);
+        let (triggers, _) = manager.evaluate("BTCUSDT", AlertInput::Cvd, -60.0);
+        assert_eq!(triggers.len(), 1);
+        assert_eq!(triggers[0].input, AlertInput::Cvd);
+        assert_eq!(triggers[0].id, alert.id);
+
+        // Signed thresholds only for inputs that can go negative
+        assert!(
+            manager
+                .add_input_alert(
+                    "BTCUSDT",
+                    AlertInput::TradeRate,
+                    AlertDirection::Above,
+                    -1.0,
+                    options,
+                )
+                .is_err()
+        );
+        assert!(
+            manager
+                .update_alert(
+                    alert.id,
+                    AlertUpdate {
+                        threshold: Some(-80.0),
+                        hysteresis: Some(HysteresisSpec::Percent(10.0)),
+                        ..AlertUpdate::default()
+                    },
+                )
+                .is_ok_and(|updated| (updated.hysteresis - 8.0).abs() < 1e-9)
+        );
+    }
+
+    #[test]
+    fn book_ticker_inputs_read_spread_and_mid() {
+        let ticker = BookTicker {
+            update_id: 1,
+            bid_price: 99.95,
+            bid_qty: 1.0,
+            ask_price: 100.05,
+            ask_qty: 1.0,
+        };
+        let spread = AlertInput::Spread.value_from_ticker(&ticker).unwrap();
+        assert!((spread - 10.0).abs() < 1e-6);
+        assert_eq!(AlertInput::Mid.value_from_ticker(&ticker), Some(100.0));
+        assert_eq!(AlertInput::Price.value_from_ticker(&ticker), None);
+        let one_sided = BookTicker {
+            bid_price: 0.0,
+            ..ticker
+        };
+        assert_eq!(AlertInput::Mid.value_from_ticker(&one_sided), None);
+
+        let mut manager = AlertManager::new();
+        manager
+            .add_input_alert(
+                "BTCUSDT",
+                AlertInput::Spread,
+                AlertDirection::Above,
+                8.0,
+                AlertOptions::default_for_threshold(8.0),
+            )
+            .unwrap();
+        assert!(AlertInput::Spread.validate_threshold(-1.0).is_err());
+        manager.evaluate("BTCUSDT", AlertInput::Spread, 5.0);
+        assert!(
+            manager
+                .evaluate("BTCUSDT", AlertInput::Mid, 100.0)
+                .0
+                .is_empty()
+        );
+        assert_eq!(
+            manager
+                .evaluate("BTCUSDT", AlertInput::Spread, spread)
+                .0
+                .len(),
+            1
+        );
+    }
+}
@@ -4,7 +4,14 @@ use anyhow::Result;
 use tokio::sync::mpsc;
 use tracing::{debug, info};
 
+use super::alert_manager::{
+    AlertDirection, AlertInput, AlertOptions, AlertRepeat, AlertUpdate, HysteresisSpec,
+};
 use crate::cli::{Cli, Commands};
+use crate::config::Config;
+use crate::market_data::{ImpactRequest, ImpactSize, TakerSide};
+use crate::paper::{OrderRequest, OrderSide, OrderType};
+use crate::portfolio::PortfolioAlertKind;
 use tracing::warn;
 
 /// Interactive commands for the terminal session
@@ -34,13 +41,116 @@ pub enum InteractiveCommand {
     Help,
     /// Manage price alerts
     Alert { action: AlertAction },
+    /// Open or close the market-wide scanner
+    Scanner { enabled: bool },
+    /// Paper trading orders and account
+    Paper { action: PaperAction },
+    /// Show the authenticated exchange account
+    Account,
+    /// Live orders on the exchange account
+    Order { action: OrderAction },
+    /// Manual portfolio holdings and alerts
+    Position { action: PositionAction },
+    /// Estimate the cost of a market order on the live book
+    Impact { request: ImpactRequest },
+}
+
+/// Manual portfolio subcommands
+#[derive(Debug, Clone)]
+pub enum PositionAction {
+    /// Show holdings valued at the latest prices
+    Show,
+    Add {
+        symbol: String,
+        quantity: f64,
+        cost_basis: f64,
+    },
+    Remove {
+        symbol: String,
+    },
+    /// Set or clear (`None`) a portfolio alert threshold
+    Alert {
+        kind: PortfolioAlertKind,
+        threshold: Option<f64>,
+    },
+    /// Restore the configured holdings and thresholds
+    Reset,
+}
+
+/// Live order subcommands
+#[derive(Debug, Clone)]
+pub enum OrderAction {
+    /// Validate an order and hold it for confirmation
+    Place {
+        request: OrderRequest,
+    },
+    /// Validate a new quantity and price for an open limit order and hold it for confirmation
+    Replace {
+        symbol: String,
+        order_id: u64,
+        quantity: f64,
+        price: f64,
+    },
+    Cancel {
+        symbol: String,
+        order_id: u64,
+    },
+    /// Send the order waiting for confirmation
+    Confirm,
+    /// Drop the order waiting for confirmation
+    Discard,
+}
+
+/// Paper trading subcommands
+#[derive(Debug, Clone)]
+pub enum PaperAction {
+    /// Show balances, positions and open orders
+    Show,
+    Order {
+        request: OrderRequest,
+    },
+    Cancel {
+        target: ClearTarget,
+    },
+    /// Restore the starting balances and drop all orders and positions
+    Reset,
 }
 
 /// Alert subcommands
 #[derive(Debug, Clone)]
 pub enum AlertAction {
     List,
-    Clear { target: ClearTarget },
+    Clear {
+        target: ClearTarget,
+    },
+    Add {
+        symbol: String,
+        input: AlertInput,
+        direction: AlertDirection,
+        threshold: f64,
+        options: AlertOptions,
+    },
+    Edit {
+        id: u64,
+        update: AlertUpdate,
+    },
+    Pause {
+        id: u64,
+    },
+    Resume {
+        id: u64,
+    },
+    Snooze {
+        id: u64,
+        duration_ms: u64,
+    },
+    History {
+        symbol: Option<String>,
+    },
+    Export {
+        symbol: Option<String>,
+        path: Option<String>,
+    },
 }
 
 /// Target for alert clear command
@@ -59,7 +169,7 @@ pub struct CommandInfo {
 }
 
 /// Static help descriptions used for interactive commands
-const HELP_LINES: [&str; 13] = [
+const HELP_LINES: [&str; 42] = [
     "XTrade Interactive Commands:",
     "  /add <symbol1> [symbol2] ...  - Subscribe to symbols",
     "  /remove <symbol1> [symbol2] ... - Unsubscribe from symbols",
@@ -71,12 +181,41 @@ const HELP_LINES: [&str; 13] = [
     "  /config [show|set|reset]      - Configuration management",
     "  /alert:list                   - List configured alerts",
     "  /alert:clear <id|all>         - Clear alerts",
+    "  /alert:add <symbol> [price|cvd|rate|vwap|imbalance|ofi|micro] <above|below> <value> [once|repeat] [cooldown=60s] [hysteresis=0.2%]",
+    "                                - Create a price, trade flow or microstructure alert",
+    "  /alert:edit <id> [above|below] [price] [once|repeat] [cooldown=..] [hysteresis=..]",
+    "                                - Modify an existing alert",
+    "  /alert:pause|resume <id>      - Pause or resume an alert",
+    "  /alert:snooze <id> <duration> - Silence an alert for e.g. 30m",
+    "  /alert:history [symbol]       - Show fired alerts",
+    "  /alert:export [symbol] [file.csv] - Export fired alerts to CSV",
+    "  /scanner [on|off]             - Rank all symbols by change, volume or volatility",
+    "  /paper                        - Show paper balances, positions and open orders",
+    "  /paper:buy|sell <symbol> <qty> [market|limit <price>|stop <price>]",
+    "                                - Place a simulated order against the live book",
+    "  /paper:cancel <id|all>        - Cancel open paper orders",
+    "  /paper:reset                  - Restore starting paper balances",
+    "  /account                      - Show live exchange balances, open orders and executions",
+    "  /order:buy|sell <symbol> <qty> [market|limit <price>|stop <price>]",
+    "                                - Stage a live order for confirmation",
+    "  /order:replace <symbol> <order id> <qty> <price>",
+    "                                - Stage new quantity and price for an open limit order",
+    "  /order:confirm | /order:discard - Send or drop the staged order",
+    "  /order:cancel <symbol> <order id> - Cancel an open order",
+    "  /position                     - Show holdings valued at live prices",
+    "  /position:add <symbol> <qty> <cost> - Add a holding bought at <cost> per unit",
+    "  /position:remove <symbol>     - Drop a holding",
+    "  /position:alert <above|below|drawdown> <value|off>",
+    "                                - Alert on portfolio value or drawdown from peak",
+    "  /position:reset               - Restore the configured holdings",
+    "  /impact <symbol> <buy|sell> <qty> [quote]",
+    "                                - Average fill, slippage and depth for a market order",
     "  /help                         - Show this help",
     "  /quit                         - Exit the application",
 ];
 
 /// Static list of interactive commands with descriptions for UI surfaces
-const COMMANDS: [CommandInfo; 12] = [
+const COMMANDS: [CommandInfo; 38] = [
     CommandInfo {
         trigger: "/add",
         usage: "/add <symbol1> [symbol2] ...",
@@ -127,6 +266,136 @@ const COMMANDS: [CommandInfo; 12] = [
         usage: "/alert:clear <id|all>",
         description: "Clear alerts",
     },
+    CommandInfo {
+        trigger: "/alert:add",
+        usage: "/alert:add <symbol> [price|cvd|rate|vwap|imbalance|ofi|micro|spread|mid] <above|below> <value> [once|repeat] [cooldown=60s] [hysteresis=0.2%]",
+        description: "Create an alert on price, trade flow (cvd, rate, vwap), book microstructure (imbalance, ofi, micro) or best bid/offer (spread in bps, mid)",
+    },
+    CommandInfo {
+        trigger: "/alert:edit",
+        usage: "/alert:edit <id> [above|below] [price] [once|repeat] [cooldown=..] [hysteresis=..]",
+        description: "Modify an existing alert",
+    },
+    CommandInfo {
+        trigger: "/alert:pause",
+        usage: "/alert:pause <id>",
+        description: "Pause an alert",
+    },
+    CommandInfo {
+        trigger: "/alert:resume",
+        usage: "/alert:resume <id>",
+        description: "Resume a paused or snoozed alert",
+    },
+    CommandInfo {
+        trigger: "/alert:snooze",
+        usage: "/alert:snooze <id> <duration>",
+        description: "Silence an alert for a duration (e.g. 30m)",
+    },
+    CommandInfo {
+        trigger: "/alert:history",
+        usage: "/alert:history [symbol]",
+        description: "Show fired alert history",
+    },
+    CommandInfo {
+        trigger: "/alert:export",
+        usage: "/alert:export [symbol] [file.csv]",
+        description: "Export fired alert history to CSV",
+    },
+    CommandInfo {
+        trigger: "/scanner",
+        usage: "/scanner [on|off]",
+        description: "Open the market-wide scanner",
+    },
+    CommandInfo {
+        trigger: "/paper",
+        usage: "/paper",
+        description: "Show paper balances, positions and open orders",
+    },
+    CommandInfo {
+        trigger: "/paper:buy",
+        usage: "/paper:buy <symbol> <qty> [market|limit <price>|stop <price>]",
+        description: "Place a simulated buy order",
+    },
+    CommandInfo {
+        trigger: "/paper:sell",
+        usage: "/paper:sell <symbol> <qty> [market|limit <price>|stop <price>]",
+        description: "Place a simulated sell order",
+    },
+    CommandInfo {
+        trigger: "/paper:cancel",
+        usage: "/paper:cancel <id|all>",
+        description: "Cancel open paper orders",
+    },
+    CommandInfo {
+        trigger: "/paper:reset",
+        usage: "/paper:reset",
+        description: "Restore starting paper balances",
+    },
+    CommandInfo {
+        trigger: "/account",
+        usage: "/account",
+        description: "Show live exchange balances and open orders",
+    },
+    CommandInfo {
+        trigger: "/order:buy",
+        usage: "/order:buy <symbol> <qty> [market|limit <price>|stop <price>]",
+        description: "Stage a live buy order for confirmation",
+    },
+    CommandInfo {
+        trigger: "/order:sell",
+        usage: "/order:sell <symbol> <qty> [market|limit <price>|stop <price>]",
+        description: "Stage a live sell order for confirmation",
+    },
+    CommandInfo {
+        trigger: "/order:replace",
+        usage: "/order:replace <symbol> <order id> <qty> <price>",
+        description: "Stage a new quantity and price for an open limit order",
+    },
+    CommandInfo {
+        trigger: "/order:confirm",
+        usage: "/order:confirm",
+        description: "Send the staged order",
+    },
+    CommandInfo {
+        trigger: "/order:discard",
+        usage: "/order:discard",
+        description: "Drop the staged order",
+    },
+    CommandInfo {
+        trigger: "/order:cancel",
+        usage: "/order:cancel <symbol> <order id>",
+        description: "Cancel an open exchange order",
+    },
+    CommandInfo {
+        trigger: "/position",
+        usage: "/position",
+        description: "Show holdings valued at live prices",
+    },
+    CommandInfo {
+        trigger: "/position:add",
+        usage: "/position:add <symbol> <qty> <cost>",
+        description: "Add a holding bought at <cost> per unit",
+    },
+    CommandInfo {
+        trigger: "/position:remove",
+        usage: "/position:remove <symbol>",
+        description: "Drop a holding",
+    },
+    CommandInfo {
+        trigger: "/position:alert",
+        usage: "/position:alert <above|below|drawdown> <value|off>",
+        description: "Alert on portfolio value or drawdown from peak",
+    },
+    CommandInfo {
+        trigger: "/position:reset",
+        usage: "/position:reset",
+        description: "Restore the configured holdings",
+    },
+    CommandInfo {
+        trigger: "/impact",
+        usage: "/impact <symbol> <buy|sell> <qty> [quote]",
+        description: "Estimate slippage of a market order on the live book",
+    },
     CommandInfo {
         trigger: "/help",
         usage: "/help",
@@ -322,6 +591,142 @@ impl CommandRouter {
                     action: AlertAction::Clear { target },
                 }))
             }
+            "/alert:add" => Ok(Some(InteractiveCommand::Alert {
+                action: parse_alert_add(&parts[1..])?,
+            })),
+            "/alert:edit" => {
+                let id = parse_alert_id(parts.get(1).copied(), "/alert:edit <id> ...")?;
+                let update = parse_alert_update(&parts[2..])?;
+                if update.is_empty() {
+                    return Err(anyhow::anyhow!(
+                        "Usage: /alert:edit <id> [above|below] [price] [once|repeat] [cooldown=60s] [hysteresis=0.2%]"
+                    ));
+                }
+                Ok(Some(InteractiveCommand::Alert {
+                    action: AlertAction::Edit { id, update },
+                }))
+            }
+            "/alert:pause" => Ok(Some(InteractiveCommand::Alert {
+                action: AlertAction::Pause {
+                    id: parse_alert_id(parts.get(1).copied(), "/alert:pause <id>")?,
+                },
+            })),
+            "/alert:resume" => Ok(Some(InteractiveCommand::Alert {
+                action: AlertAction::Resume {
+                    id: parse_alert_id(parts.get(1).copied(), "/alert:resume <id>")?,
+                },
+            })),
+            "/alert:snooze" => {
+                let id = parse_alert_id(parts.get(1).copied(), "/alert:snooze <id> <duration>")?;
+                let raw = parts.get(2).ok_or_else(|| {
+                    anyhow::anyhow!(
+                        "Usage: /alert:snooze <id> <duration>. Example: /alert:snooze 1 30m"
+                    )
+                })?;
+                let duration_ms = parse_duration_ms(raw)?;
+                if duration_ms == 0 {
+                    return Err(anyhow::anyhow!("Snooze duration must be greater than 0"));
+                }
+                Ok(Some(InteractiveCommand::Alert {
+                    action: AlertAction::Snooze { id, duration_ms },
+                }))
+            }
+            "/alert:history" => Ok(Some(InteractiveCommand::Alert {
+                action: AlertAction::History {
+                    symbol: parts.get(1).map(|s| Config::normalize_symbol(s)),
+                },
+            })),
+            "/alert:export" => Ok(Some(InteractiveCommand::Alert {
+                action: parse_alert_export(&parts[1..])?,
+            })),
+            "/scanner" => {
+                let enabled = match parts.get(1).map(|s| s.to_ascii_lowercase()).as_deref() {
+                    None | Some("on") => true,
+                    Some("off") => false,
+                    Some(_) => return Err(anyhow::anyhow!("Usage: /scanner [on|off]")),
+                };
+                Ok(Some(InteractiveCommand::Scanner { enabled }))
+            }
+            "/paper" => Ok(Some(InteractiveCommand::Paper {
+                action: PaperAction::Show,
+            })),
+            "/paper:buy" => Ok(Some(InteractiveCommand::Paper {
+                action: PaperAction::Order {
+                    request: parse_order_request("/paper", OrderSide::Buy, &parts[1..])?,
+                },
+            })),
+            "/paper:sell" => Ok(Some(InteractiveCommand::Paper {
+                action: PaperAction::Order {
+                    request: parse_order_request("/paper", OrderSide::Sell, &parts[1..])?,
+                },
+            })),
+            "/paper:cancel" => {
+                let token = parts.get(1).ok_or_else(|| {
+                    anyhow::anyhow!("Usage: /paper:cancel <id|all>. Example: /paper:cancel 3")
+                })?;
+                let target = if token.eq_ignore_ascii_case("all") {
+                    ClearTarget::All
+                } else {
+                    let id = token.trim_start_matches('#').parse::<u64>().map_err(|_| {
+                        anyhow::anyhow!("Invalid order id '{}'. Expected a number.", token)
+                    })?;
+                    ClearTarget::Id(id)
+                };
+                Ok(Some(InteractiveCommand::Paper {
+                    action: PaperAction::Cancel { target },
+                }))
+            }
+            "/paper:reset" => Ok(Some(InteractiveCommand::Paper {
+                action: PaperAction::Reset,
+            })),
+            "/account" => Ok(Some(InteractiveCommand::Account)),
+            "/order:buy" => Ok(Some(InteractiveCommand::Order {
+                action: OrderAction::Place {
+                    request: parse_order_request("/order", OrderSide::Buy, &parts[1..])?,
+                },
+            })),
+            "/order:sell" => Ok(Some(InteractiveCommand::Order {
+                action: OrderAction::Place {
+                    request: parse_order_request("/order", OrderSide::Sell, &parts[1..])?,
+                },
+            })),
+            "/order:replace" => Ok(Some(InteractiveCommand::Order {
+                action: parse_order_replace(&parts[1..])?,
+            })),
+            "/order:cancel" => {
+                const USAGE: &str =
+                    "Usage: /order:cancel <symbol> <order id>. Example: /order:cancel BTCUSDT 4821";
+                let [symbol, order_id] = &parts[1..] else {
+                    return Err(anyhow::anyhow!(USAGE));
+                };
+                Ok(Some(InteractiveCommand::Order {
+                    action: OrderAction::Cancel {
+                        symbol: Config::normalize_symbol(symbol),
+                        order_id: parse_order_id(order_id)?,
+                    },
+                }))
+            }
+            "/order:confirm" => Ok(Some(InteractiveCommand::Order {
+                action: OrderAction::Confirm,
+            })),
+            "/order:discard" => Ok(Some(InteractiveCommand::Order {
+                action: OrderAction::Discard,
+            })),
+            // `/position add ...` is accepted as well as `/position:add ...`
+            "/position" => Ok(Some(InteractiveCommand::Position {
+                action: match parts.get(1) {
+                    None => PositionAction::Show,
+                    Some(sub) => parse_position(sub, &parts[2..])?,
+                },
+            })),
+            trigger if trigger.starts_with("/position:") => {
+                Ok(Some(InteractiveCommand::Position {
+                    action: parse_position(&trigger["/position:".len()..], &parts[1..])?,
+                }))
+            }
+            "/impact" => Ok(Some(InteractiveCommand::Impact {
+                request: parse_impact(&parts[1..])?,
+            })),
             "/help" | "?" => Ok(Some(InteractiveCommand::Help)),
             "/logs" => Ok(Some(InteractiveCommand::Logs)),
             "/quit" | "/exit" | "/q" => Ok(Some(InteractiveCommand::Quit)),
@@ -352,3 +757,699 @@ impl CommandRouter {
         &COMMANDS
     }
 }
+
+/// Parse `/alert:add <symbol> [input] <above|below> <value> [options...]`
+///
+/// The input defaults to `price`; `cvd`, `rate` and `vwap` watch the trade
+/// flow analytics and `imbalance`, `ofi` and `micro` the book microstructure.
+pub(crate) fn parse_alert_add(args: &[&str]) -> Result<AlertAction> {
+    const USAGE: &str = "Usage: /alert:add <symbol> [price|cvd|rate|vwap|imbalance|ofi|micro|spread|mid] <above|below> <value> [once|repeat] [cooldown=60s] [hysteresis=0.2%]";
+
+    let [symbol, rest @ ..] = args else {
+        return Err(anyhow::anyhow!(USAGE));
+    };
+    let (input, rest) = match rest.first().and_then(|first| AlertInput::parse(first)) {
+        Some(input) => (input, &rest[1..]),
+        None => (AlertInput::Price, rest),
+    };
+    let (direction, value) = match rest {
+        [direction, value, ..] => (direction, value),
+        _ => return Err(anyhow::anyhow!(USAGE)),
+    };
+    let direction = parse_alert_direction(direction)
+        .ok_or_else(|| anyhow::anyhow!("Invalid direction '{}'. {}", direction, USAGE))?;
+    let threshold = match input {
+        AlertInput::Price | AlertInput::Mid => parse_alert_price(value)?,
+        _ => parse_alert_value(value)?,
+    };
+    input.validate_threshold(threshold)?;
+
+    let update = parse_alert_update(&rest[2..])?;
+    if update.direction.is_some() || update.threshold.is_some() {
+        return Err(anyhow::anyhow!(USAGE));
+    }
+
+    let defaults = AlertOptions::default_for_threshold(threshold);
+    let options = AlertOptions {
+        repeat: update.repeat.unwrap_or(defaults.repeat),
+        cooldown_ms: update.cooldown_ms.unwrap_or(defaults.cooldown_ms),
+        hysteresis: update
+            .hysteresis
+            .map(|spec| spec.resolve(threshold))
+            .unwrap_or(defaults.hysteresis),
+    };
+
+    Ok(AlertAction::Add {
+        symbol: Config::normalize_symbol(symbol),
+        input,
+        direction,
+        threshold,
+        options,
+    })
+}
+
+/// Parse `<prefix>:buy|sell <symbol> <qty> [market|limit <price>|stop <price>]`
+/// for paper and live orders; the order type defaults to market
+fn parse_order_request(prefix: &str, side: OrderSide, args: &[&str]) -> Result<OrderRequest> {
+    let usage = format!(
+        "Usage: {}:{} <symbol> <qty> [market|limit <price>|stop <price>]",
+        prefix,
+        side.label().to_ascii_lowercase()
+    );
+
+    let (symbol, quantity, rest) = match args {
+        [symbol, quantity, rest @ ..] => (symbol, quantity, rest),
+        _ => return Err(anyhow::anyhow!(usage)),
+    };
+    let quantity = quantity
+        .parse::<f64>()
+        .ok()
+        .filter(|qty| qty.is_finite() && *qty > 0.0)
+        .ok_or_else(|| anyhow::anyhow!("Invalid quantity '{}'. {}", quantity, usage))?;
+
+    let order_type = match rest {
+        [] => OrderType::Market,
+        [kind] if kind.eq_ignore_ascii_case("market") => OrderType::Market,
+        [kind, price] if kind.eq_ignore_ascii_case("limit") => OrderType::Limit {
+            price: parse_alert_price(price)?,
+        },
+        [kind, price] if kind.eq_ignore_ascii_case("stop") => OrderType::Stop {
+            trigger: parse_alert_price(price)?,
+        },
+        _ => return Err(anyhow::anyhow!(usage)),
+    };
+
+    Ok(OrderRequest {
+        symbol: Config::normalize_symbol(symbol),
+        side,
+        order_type,
+        quantity,
+    })
+}
+
+/// Parse `/order:replace <symbol> <order id> <qty> <price>`
+fn parse_order_replace(args: &[&str]) -> Result<OrderAction> {
+    const USAGE: &str = "Usage: /order:replace <symbol> <order id> <qty> <price>. Example: /order:replace BTCUSDT 4821 0.002 64500";
+
+    let [symbol, order_id, quantity, price] = args else {
+        return Err(anyhow::anyhow!(USAGE));
+    };
+    let quantity = quantity
+        .parse::<f64>()
+        .ok()
+        .filter(|qty| qty.is_finite() && *qty > 0.0)
+        .ok_or_else(|| anyhow::anyhow!("Invalid quantity '{}'. {}", quantity, USAGE))?;
+
+    Ok(OrderAction::Replace {
+        symbol: Config::normalize_symbol(symbol),
+        order_id: parse_order_id(order_id)?,
+        quantity,
+        price: parse_alert_price(price)?,
+    })
+}
+
+/// Parse the `/position` subcommand `sub` with its arguments
+fn parse_position(sub: &str, args: &[&str]) -> Result<PositionAction> {
+    match (sub.to_ascii_lowercase().as_str(), args) {
+        ("add", [symbol, quantity, cost]) => {
+            let quantity = quantity
+                .parse::<f64>()
+                .ok()
+                .filter(|qty| qty.is_finite() && *qty > 0.0)
+                .ok_or_else(|| anyhow::anyhow!("Invalid quantity '{}'", quantity))?;
+            let cost_basis = cost
+                .parse::<f64>()
+                .ok()
+                .filter(|cost| cost.is_finite() && *cost >= 0.0)
+                .ok_or_else(|| anyhow::anyhow!("Invalid cost '{}'", cost))?;
+            Ok(PositionAction::Add {
+                symbol: Config::normalize_symbol(symbol),
+                quantity,
+                cost_basis,
+            })
+        }
+        ("add", _) => Err(anyhow::anyhow!(
+            "Usage: /position:add <symbol> <qty> <cost>. Example: /position:add BTCUSDT 0.5 42000"
+        )),
+        ("remove", [symbol]) => Ok(PositionAction::Remove {
+            symbol: Config::normalize_symbol(symbol),
+        }),
+        ("remove", _) => Err(anyhow::anyhow!("Usage: /position:remove <symbol>")),
+        ("alert", [kind, value]) => {
+            const USAGE: &str = "Usage: /position:alert <above|below|drawdown> <value|off>";
+            let kind = PortfolioAlertKind::parse(kind)
+                .ok_or_else(|| anyhow::anyhow!("Invalid alert '{}'. {}", kind, USAGE))?;
+            let threshold = if value.eq_ignore_ascii_case("off") {
+                None
+            } else {
+                let threshold = parse_alert_value(value.trim_end_matches('%'))?;
+                kind.validate_threshold(threshold)?;
+                Some(threshold)
+            };
+            Ok(PositionAction::Alert { kind, threshold })
+        }
+        ("alert", _) => Err(anyhow::anyhow!(
+            "Usage: /position:alert <above|below|drawdown> <value|off>"
+        )),
+        ("reset", []) => Ok(PositionAction::Reset),
+        _ => Err(anyhow::anyhow!(
+            "Unknown position command '{}'. Use add, remove, alert or reset.",
+            sub
+        )),
+    }
+}
+
+/// Parse `/impact <symbol> <buy|sell> <qty> [quote]`; `quote` sizes the order by notional
+fn parse_impact(args: &[&str]) -> Result<ImpactRequest> {
+    const USAGE: &str =
+        "Usage: /impact <symbol> <buy|sell> <qty> [quote]. Example: /impact BTCUSDT buy 5";
+    let (symbol, side, amount, by_quote) = match args {
+        [symbol, side, amount] => (symbol, side, amount, false),
+        [symbol, side, amount, unit] if unit.eq_ignore_ascii_case("quote") => {
+            (symbol, side, amount, true)
+        }
+        _ => return Err(anyhow::anyhow!(USAGE)),
+    };
+    let side = match side.to_ascii_lowercase().as_str() {
+        "buy" => TakerSide::Buy,
+        "sell" => TakerSide::Sell,
+        _ => return Err(anyhow::anyhow!("Invalid side '{}'. {}", side, USAGE)),
+    };
+    let amount = parse_alert_value(amount)?;
+    if amount <= 0.0 {
+        return Err(anyhow::anyhow!("Order size must be greater than 0"));
+    }
+    Ok(ImpactRequest {
+        symbol: Config::normalize_symbol(symbol),
+        side,
+        size: if by_quote {
+            ImpactSize::Quote(amount)
+        } else {
+            ImpactSize::Base(amount)
+        },
+    })
+}
+
+/// Exchange order ids are plain numbers; a leading `#` is accepted
+fn parse_order_id(raw: &str) -> Result<u64> {
+    raw.trim_start_matches('#')
+        .parse::<u64>()
+        .map_err(|_| anyhow::anyhow!("Invalid order id '{}'. Expected a number.", raw))
+}
+
+/// Parse `/alert:export [symbol|all] [path]`; a token containing `.` or a path
+/// separator is taken as the output file
+fn parse_alert_export(args: &[&str]) -> Result<AlertAction> {
+    let mut symbol = None;
+    let mut path = None;
+
+    for arg in args {
+        let is_path = arg.contains(['.', '/', '\\']);
+        if is_path && path.is_none() {
+            path = Some((*arg).to_string());
+        } else if !is_path && symbol.is_none() {
+            if !arg.eq_ignore_ascii_case("all") {
+                symbol = Some(Config::normalize_symbol(arg));
+            }
+        } else {
+            return Err(anyhow::anyhow!(
+                "Usage: /alert:export [symbol] [file.csv]. Example: /alert:export BTCUSDT btc_alerts.csv"
+            ));
+        }
+    }
+
+    Ok(AlertAction::Export { symbol, path })
+}
+
+/// Parse alert option tokens shared by `/alert:add` and `/alert:edit`
+fn parse_alert_update(args: &[&str]) -> Result<AlertUpdate> {
+    let mut update = AlertUpdate::default();
+
+    for token in args {
+        let lower = token.to_ascii_lowercase();
+        if let Some(direction) = parse_alert_direction(&lower) {
+            update.direction = Some(direction);
+        } else if lower == "once" {
+            update.repeat = Some(AlertRepeat::Once);
+        } else if lower == "repeat" {
+            update.repeat = Some(AlertRepeat::Repeat);
+        } else if let Some(value) = lower.strip_prefix("cooldown=") {
+            update.cooldown_ms = Some(parse_duration_ms(value)?);
+        } else if let Some(value) = lower.strip_prefix("hysteresis=") {
+            update.hysteresis = Some(parse_hysteresis(value)?);
+        } else if lower.parse::<f64>().is_ok() {
+            // Sign rules depend on the alert input and are checked by the manager
+            update.threshold = Some(parse_alert_value(token)?);
+        } else {
+            return Err(anyhow::anyhow!("Unknown alert option '{}'", token));
+        }
+    }
+
+    Ok(update)
+}
+
+fn parse_alert_direction(raw: &str) -> Option<AlertDirection> {
+    match raw.to_ascii_lowercase().as_str() {
+        "above" | ">" | ">=" => Some(AlertDirection::Above),
+        "below" | "<" | "<=" => Some(AlertDirection::Below),
+        _ => None,
+    }
+}
+
+fn parse_alert_price(raw: &str) -> Result<f64> {
+    let price = raw
+        .parse::<f64>()
+        .map_err(|_| anyhow::anyhow!("Invalid price '{}'. Expected a number.", raw))?;
+    if !price.is_finite() || price <= 0.0 {
+        return Err(anyhow::anyhow!("Price must be a positive number"));
+    }
+    Ok(price)
+}
+
+fn parse_alert_value(raw: &str) -> Result<f64> {
+    let value = raw
+        .parse::<f64>()
+        .map_err(|_| anyhow::anyhow!("Invalid value '{}'. Expected a number.", raw))?;
+    if !value.is_finite() {
+        return Err(anyhow::anyhow!("Value must be a finite number"));
+    }
+    Ok(value)
+}
+
+fn parse_alert_id(raw: Option<&str>, usage: &str) -> Result<u64> {
+    let raw = raw.ok_or_else(|| anyhow::anyhow!("Usage: {}", usage))?;
+    raw.trim_start_matches('#')
+        .parse::<u64>()
+        .map_err(|_| anyhow::anyhow!("Invalid alert id '{}'. Expected a number.", raw))
+}
+
+fn parse_hysteresis(raw: &str) -> Result<HysteresisSpec> {
+    let (value, percent) = match raw.strip_suffix('%') {
+        Some(stripped) => (stripped, true),
+        None => (raw, false),
+    };
+    let value = value.parse::<f64>().map_err(|_| {
+        anyhow::anyhow!(
+            "Invalid hysteresis '{}'. Use a price distance or percent like 0.2%",
+            raw
+        )
+    })?;
+    if !value.is_finite() || value < 0.0 {
+        return Err(anyhow::anyhow!("Hysteresis must be non-negative"));
+    }
+    Ok(if percent {
+        HysteresisSpec::Percent(value)
+    } else {
+        HysteresisSpec::Absolute(value)
+    })
+}
+
+/// Parse durations like `90s`, `30m`, `2h`, `500ms`; a bare number is seconds
+pub fn parse_duration_ms(raw: &str) -> Result<u64> {
+    let raw = raw.trim().to_ascii_lowercase();
+    let split = raw
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(raw.len());
+    let (number, unit) = raw.split_at(split);
+
+    let value = number
+        .parse::<f64>()
+        .map_err(|_| anyhow::anyhow!("Invalid duration '{}'. Examples: 90s, 30m, 2h", raw))?;
+    let multiplier = match unit {
+        "ms" => 1.0,
+        "" | "s" | "sec" | "secs" => 1_000.0,
+        "m" | "min" | "mins" => 60_000.0,
+        "h" | "hr" | "hrs" => 3_600_000.0,
+        "d" => 86_400_000.0,
+        _ => {
+            return Err(anyhow::anyhow!(
+                "Invalid duration unit '{}'. Use ms, s, m, h or d",
+                unit
+            ));
+        }
+    };
+
+    Ok((value * multiplier).round() as u64)
+}
+
+/// Largest whole unit that [`parse_duration_ms`] reads back exactly, e.g. `90s` or `500ms`
+pub fn format_duration_ms(duration_ms: u64) -> String {
+    const UNITS: [(u64, &str); 4] = [
+        (86_400_000, "d"),
+        (3_600_000, "h"),
+        (60_000, "m"),
+        (1_000, "s"),
+    ];
+    UNITS
+        .iter()
+        .find(|(unit_ms, _)| duration_ms > 0 && duration_ms % unit_ms == 0)
+        .map(|(unit_ms, unit)| format!("{}{}", duration_ms / unit_ms, unit))
+        .unwrap_or_else(|| format!("{}ms", duration_ms))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(input: &str) -> Result<Option<InteractiveCommand>> {
+        CommandRouter::new().parse_interactive_command(input)
+    }
+
+    #[test]
+    fn parses_alert_add_with_options() {
+        let command = parse("/alert:add btc-usdt above 70000 once cooldown=60s hysteresis=0.2%")
+            .unwrap()
+            .unwrap();
+        let InteractiveCommand::Alert {
+            action:
+                AlertAction::Add {
+                    symbol,
+                    input,
+                    direction,
+                    threshold,
+                    options,
+                },
+        } = command
+        else {
+            panic!("expected alert add");
+        };
+
+        assert_eq!(symbol, "BTCUSDT");
+        assert_eq!(input, AlertInput::Price);
+        assert_eq!(direction, AlertDirection::Above);
+        assert_eq!(threshold, 70_000.0);
+        assert_eq!(options.repeat, AlertRepeat::Once);
+        assert_eq!(options.cooldown_ms, 60_000);
+        assert!((options.hysteresis - 140.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn alert_add_help_lists_every_input() {
+        let info = CommandRouter::commands()
+            .iter()
+            .find(|info| info.trigger == "/alert:add")
+            .unwrap();
+        let inputs = std::iter::once(AlertInput::Price)
+            .chain(AlertInput::TRADE_FLOW)
+            .chain(AlertInput::MICROSTRUCTURE)
+            .chain(AlertInput::BOOK_TICKER);
+        for input in inputs {
+            assert!(info.usage.contains(input.label()), "{}", input.label());
+            if input != AlertInput::Price {
+                assert!(
+                    info.description.contains(input.label()),
+                    "{}",
+                    input.label()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn parses_trade_flow_alerts() {
+        let command = parse("/alert:add ethusdt cvd below -250 once")
+            .unwrap()
+            .unwrap();
+        let InteractiveCommand::Alert {
+            action:
+                AlertAction::Add {
+                    input,
+                    direction,
+                    threshold,
+                    ..
+                },
+        } = command
+        else {
+            panic!("expected alert add");
+        };
+        assert_eq!(input, AlertInput::Cvd);
+        assert_eq!(direction, AlertDirection::Below);
+        assert_eq!(threshold, -250.0);
+
+        assert!(parse("/alert:add BTCUSDT rate above 50").is_ok());
+        assert!(parse("/alert:add BTCUSDT price above 50").is_ok());
+        assert!(parse("/alert:add BTCUSDT rate above -1").is_err());
+        assert!(parse("/alert:add BTCUSDT vwap above").is_err());
+        assert!(parse("/alert:add BTCUSDT imb above 0.4").is_ok());
+        assert!(parse("/alert:add BTCUSDT imbalance below -1.5").is_err());
+        assert!(parse("/alert:add BTCUSDT ofi below -20").is_ok());
+        assert!(parse("/alert:add BTCUSDT micro above 1.5").is_ok());
+        assert!(parse("/alert:add BTCUSDT spread above 5").is_ok());
+        assert!(parse("/alert:add BTCUSDT spread above 0").is_err());
+        assert!(parse("/alert:add BTCUSDT mid below 60000").is_ok());
+    }
+
+    #[test]
+    fn parses_alert_edit_and_snooze() {
+        let command = parse("/alert:edit 3 below 65000 hysteresis=25")
+            .unwrap()
+            .unwrap();
+        let InteractiveCommand::Alert {
+            action: AlertAction::Edit { id, update },
+        } = command
+        else {
+            panic!("expected alert edit");
+        };
+        assert_eq!(id, 3);
+        assert_eq!(update.direction, Some(AlertDirection::Below));
+        assert_eq!(update.threshold, Some(65_000.0));
+        assert_eq!(update.hysteresis, Some(HysteresisSpec::Absolute(25.0)));
+
+        let command = parse("/alert:snooze 3 30m").unwrap().unwrap();
+        assert!(matches!(
+            command,
+            InteractiveCommand::Alert {
+                action: AlertAction::Snooze {
+                    id: 3,
+                    duration_ms: 1_800_000
+                }
+            }
+        ));
+    }
+
+    #[test]
+    fn rejects_malformed_alert_commands() {
+        assert!(parse("/alert:add BTCUSDT sideways 1").is_err());
+        assert!(parse("/alert:add BTCUSDT above -5").is_err());
+        assert!(parse("/alert:edit 1").is_err());
+        assert!(parse("/alert:edit 1 cooldown=5y").is_err());
+        assert!(parse("/alert:pause x").is_err());
+        assert!(parse("/alert:snooze 1 0m").is_err());
+    }
+
+    #[test]
+    fn parses_alert_history_and_export() {
+        let command = parse("/alert:history btc-usdt").unwrap().unwrap();
+        let InteractiveCommand::Alert {
+            action: AlertAction::History { symbol },
+        } = command
+        else {
+            panic!("expected alert history");
+        };
+        assert_eq!(symbol.as_deref(), Some("BTCUSDT"));
+
+        let command = parse("/alert:export exports/alerts.csv ethusdt")
+            .unwrap()
+            .unwrap();
+        let InteractiveCommand::Alert {
+            action: AlertAction::Export { symbol, path },
+        } = command
+        else {
+            panic!("expected alert export");
+        };
+        assert_eq!(symbol.as_deref(), Some("ETHUSDT"));
+        assert_eq!(path.as_deref(), Some("exports/alerts.csv"));
+
+        assert!(matches!(
+            parse("/alert:export all").unwrap().unwrap(),
+            InteractiveCommand::Alert {
+                action: AlertAction::Export {
+                    symbol: None,
+                    path: None
+                }
+            }
+        ));
+        assert!(parse("/alert:export BTCUSDT ETHUSDT").is_err());
+    }
+
+    #[test]
+    fn parses_scanner_toggle() {
+        assert!(matches!(
+            parse("/scanner").unwrap(),
+            Some(InteractiveCommand::Scanner { enabled: true })
+        ));
+        assert!(matches!(
+            parse("/scanner OFF").unwrap(),
+            Some(InteractiveCommand::Scanner { enabled: false })
+        ));
+        assert!(parse("/scanner maybe").is_err());
+    }
+
+    #[test]
+    fn parses_account_command() {
+        assert!(matches!(
+            parse("/account").unwrap(),
+            Some(InteractiveCommand::Account)
+        ));
+    }
+
+    #[test]
+    fn parses_paper_orders() {
+        let Some(InteractiveCommand::Paper {
+            action: PaperAction::Order { request },
+        }) = parse("/paper:buy btc-usdt 0.5 limit 65000").unwrap()
+        else {
+            panic!("expected a paper order");
+        };
+        assert_eq!(request.symbol, "BTCUSDT");
+        assert_eq!(request.side, OrderSide::Buy);
+        assert_eq!(request.quantity, 0.5);
+        assert_eq!(request.order_type, OrderType::Limit { price: 65000.0 });
+
+        let Some(InteractiveCommand::Paper {
+            action: PaperAction::Order { request },
+        }) = parse("/paper:sell ETHUSDT 2").unwrap()
+        else {
+            panic!("expected a paper order");
+        };
+        assert_eq!(request.order_type, OrderType::Market);
+
+        assert!(parse("/paper:sell ETHUSDT 2 stop").is_err());
+        assert!(parse("/paper:buy ETHUSDT -1").is_err());
+        assert!(matches!(
+            parse("/paper:cancel #4").unwrap(),
+            Some(InteractiveCommand::Paper {
+                action: PaperAction::Cancel {
+                    target: ClearTarget::Id(4)
+                }
+            })
+        ));
+    }
+
+    #[test]
+    fn parses_live_order_commands() {
+        let Some(InteractiveCommand::Order {
+            action: OrderAction::Place { request },
+        }) = parse("/order:sell ethusdt 0.25 stop 3000").unwrap()
+        else {
+            panic!("expected a live order");
+        };
+        assert_eq!(request.symbol, "ETHUSDT");
+        assert_eq!(request.side, OrderSide::Sell);
+        assert_eq!(request.order_type, OrderType::Stop { trigger: 3000.0 });
+
+        assert!(matches!(
+            parse("/order:replace BTCUSDT #4821 0.002 64500").unwrap(),
+            Some(InteractiveCommand::Order {
+                action: OrderAction::Replace { order_id: 4821, .. }
+            })
+        ));
+        assert!(matches!(
+            parse("/order:cancel btcusdt 17").unwrap(),
+            Some(InteractiveCommand::Order {
+                action: OrderAction::Cancel { order_id: 17, .. }
+            })
+        ));
+        assert!(matches!(
+            parse("/order:confirm").unwrap(),
+            Some(InteractiveCommand::Order {
+                action: OrderAction::Confirm
+            })
+        ));
+        assert!(parse("/order:cancel 17").is_err());
+        assert!(parse("/order:replace BTCUSDT 1 0 100").is_err());
+    }
+
+    #[test]
+    fn parses_position_commands() {
+        let Some(InteractiveCommand::Position {
+            action:
+                PositionAction::Add {
+                    symbol,
+                    quantity,
+                    cost_basis,
+                },
+        }) = parse("/position add btc-usdt 0.5 42000").unwrap()
+        else {
+            panic!("expected a holding");
+        };
+        assert_eq!(symbol, "BTCUSDT");
+        assert_eq!(quantity, 0.5);
+        assert_eq!(cost_basis, 42_000.0);
+
+        assert!(matches!(
+            parse("/position:alert drawdown 10%").unwrap(),
+            Some(InteractiveCommand::Position {
+                action: PositionAction::Alert {
+                    kind: PortfolioAlertKind::Drawdown,
+                    threshold: Some(10.0)
+                }
+            })
+        ));
+        assert!(matches!(
+            parse("/position:alert below off").unwrap(),
+            Some(InteractiveCommand::Position {
+                action: PositionAction::Alert {
+                    threshold: None,
+                    ..
+                }
+            })
+        ));
+        assert!(matches!(
+            parse("/position").unwrap(),
+            Some(InteractiveCommand::Position {
+                action: PositionAction::Show
+            })
+        ));
+        assert!(parse("/position:add BTCUSDT 0.5").is_err());
+        assert!(parse("/position:alert drawdown 100").is_err());
+        assert!(parse("/position:sell BTCUSDT").is_err());
+    }
+
+    #[test]
+    fn parses_impact_commands() {
+        let Some(InteractiveCommand::Impact { request }) = parse("/impact btc-usdt buy 5").unwrap()
+        else {
+            panic!("expected an impact request");
+        };
+        assert_eq!(request.symbol, "BTCUSDT");
+        assert_eq!(request.side, TakerSide::Buy);
+        assert_eq!(request.size, ImpactSize::Base(5.0));
+
+        assert!(matches!(
+            parse("/impact ETHUSDT sell 25000 quote").unwrap(),
+            Some(InteractiveCommand::Impact {
+                request: ImpactRequest {
+                    side: TakerSide::Sell,
+                    size: ImpactSize::Quote(_),
+                    ..
+                }
+            })
+        ));
+        assert!(parse("/impact BTCUSDT buy 0").is_err());
+        assert!(parse("/impact BTCUSDT hold 5").is_err());
+        assert!(parse("/impact BTCUSDT buy 5 base").is_err());
+    }
+
+    #[test]
+    fn parses_durations() {
+        assert_eq!(parse_duration_ms("90").unwrap(), 90_000);
+        assert_eq!(parse_duration_ms("500ms").unwrap(), 500);
+        assert_eq!(parse_duration_ms("1.5h").unwrap(), 5_400_000);
+        assert!(parse_duration_ms("soon").is_err());
+    }
+
+    #[test]
+    fn formatted_durations_parse_back_exactly() {
+        assert_eq!(format_duration_ms(90_000), "90s");
+        assert_eq!(format_duration_ms(500), "500ms");
+        assert_eq!(format_duration_ms(1_500), "1500ms");
+        assert_eq!(format_duration_ms(7_200_000), "2h");
+        for duration_ms in [1, 500, 1_500, 60_000, 90_000, 5_400_000, 86_400_000] {
+            assert_eq!(
+                parse_duration_ms(&format_duration_ms(duration_ms)).unwrap(),
+                duration_ms
+            );
+        }
+    }
+}
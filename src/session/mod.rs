@@ -4,11 +4,13 @@
 //! including session lifecycle, command routing, and event processing.
 
 pub mod action_channel;
+pub mod alert_history;
 pub mod alert_manager;
 pub mod command_router;
 pub mod session_manager;
 
 pub use action_channel::{ActionChannel, SessionEvent, StatusInfo};
+pub use alert_history::{AlertHistory, AlertHistoryEntry};
 pub use alert_manager::{Alert, AlertDirection, AlertManager, AlertTrigger};
 pub use command_router::CommandRouter;
 pub use session_manager::{SessionConfig, SessionManager, SessionState, SessionStats};
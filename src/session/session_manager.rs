@@ -7,16 +7,36 @@ use std::time::{Duration, Instant};
 use tokio::sync::{Mutex, Semaphore, mpsc};
 use tracing::{debug, error, info, warn};
 
+use crate::account::{
+    AccountEvent, AccountState, AccountStreamHandle, Execution, StreamStatus, spawn_account_stream,
+};
+use crate::binance::{ApiCredentials, BinanceRestClient, ExchangeInfoCache, SymbolStatus};
 use crate::cli::Cli;
 use crate::config::Config;
-use crate::market_data::MarketDataManager;
-use crate::metrics::{ConnectionStatus as MetricsConnectionStatus, MetricsCollector};
+use crate::market_data::{
+    Bar, ImpactRequest, ImpactSize, MarketDataManager, MarketImpact, ScannerHandle, TakerSide,
+};
+use crate::metrics::{ClockEstimate, MetricsCollector, exporter};
 use crate::notify::SystemNotifier;
+use crate::paper::{
+    OrderRequest, OrderSide, OrderType, PaperEngine, PaperFill, PaperOrder, resolve_assets,
+};
+use crate::portfolio::{PortfolioAlertKind, PortfolioSnapshot, PortfolioTracker};
+use crate::storage::{HistoryHandle, HistoryStore};
+use crate::trading::{
+    self, CONFIRM_TIMEOUT, PendingAction, PendingOrder, ValidatedOrder, validate_order,
+};
 use crate::ui::ui_manager::UIManager;
 
 use super::action_channel::{ActionChannel, SessionEvent};
-use super::alert_manager::{AlertDirection, AlertManager, AlertOptions, AlertRepeat, AlertTrigger};
-use super::command_router::{AlertAction, ClearTarget, CommandRouter, InteractiveCommand};
+use super::alert_history::{self, AlertHistory, AlertHistoryEntry};
+use super::alert_manager::{
+    Alert, AlertDirection, AlertInput, AlertManager, AlertOptions, AlertRepeat, AlertTrigger,
+};
+use super::command_router::{
+    AlertAction, ClearTarget, CommandRouter, InteractiveCommand, OrderAction, PaperAction,
+    PositionAction,
+};
 
 /// Session state tracking
 #[derive(Debug, Clone, PartialEq)]
@@ -75,6 +95,9 @@ impl Default for SessionStats {
 
 const AUTO_SUBSCRIBE_MAX_CONCURRENCY: usize = 4;
 
+/// Recent paper fills shown in the positions panel
+const PAPER_PANEL_FILLS: usize = 20;
+
 /// Main session manager for interactive terminal
 pub struct SessionManager {
     /// Session configuration
@@ -89,14 +112,22 @@ pub struct SessionManager {
     stats: SessionStats,
     /// Market data manager
     market_manager: Arc<MarketDataManager>,
+    /// Exchange info refresh task handle
+    exchange_info_task: Option<tokio::task::JoinHandle<()>>,
+    /// Prometheus `/metrics` listener task handle
+    metrics_exporter_task: Option<tokio::task::JoinHandle<()>>,
+    /// Exchange clock sampling task handle
+    clock_sync_task: Option<tokio::task::JoinHandle<()>>,
+    /// Whether the current clock skew has already been reported
+    clock_skew_reported: bool,
+    /// All-market scanner stream, running while the scanner view is open
+    scanner: Option<ScannerHandle>,
     /// UI task handle (optional)
     ui_task: Option<tokio::task::JoinHandle<()>>,
     /// UI event sender (Session -> UI)
     ui_event_tx: Option<mpsc::UnboundedSender<SessionEvent>>,
     /// Metrics collector (optional)
     metrics_collector: Option<Arc<Mutex<MetricsCollector>>>,
-    /// Latest connection status for metrics reporting
-    metrics_status: MetricsConnectionStatus,
     /// Last time we emitted metrics to the UI
     metrics_last_emit: Instant,
     /// Minimum interval between metrics updates to UI
@@ -107,6 +138,26 @@ pub struct SessionManager {
     action_channel: ActionChannel,
     /// Price alert manager
     alert_manager: AlertManager,
+    /// Journal of fired alerts
+    alert_history: AlertHistory,
+    /// Simulated account; `None` when paper trading is disabled
+    paper: Option<PaperEngine>,
+    /// Signed REST client shared by the account stream and order entry
+    signed_rest: Option<BinanceRestClient>,
+    /// Authenticated exchange account; `None` unless account data is enabled
+    account: Option<AccountState>,
+    /// User data stream task feeding `account`
+    account_stream: Option<AccountStreamHandle>,
+    /// Updates from the user data stream task
+    account_event_rx: Option<mpsc::UnboundedReceiver<AccountEvent>>,
+    /// Live order waiting for `/order:confirm`
+    pending_order: Option<PendingOrder>,
+    /// Orders sent this session, used for client order ids
+    orders_sent: u64,
+    /// Manual holdings; `None` when the portfolio is disabled
+    portfolio: Option<PortfolioTracker>,
+    /// Last time the portfolio panel was refreshed from a price update
+    portfolio_last_emit: Instant,
     /// System notifier for desktop alerts
     system_notifier: SystemNotifier,
     /// Shutdown signal sender
@@ -124,7 +175,21 @@ impl SessionManager {
         let (shutdown_tx, shutdown_rx) = mpsc::channel(1);
 
         // Create market data manager
-        let market_manager = Arc::new(MarketDataManager::new());
+        let mut market_manager = MarketDataManager::new()
+            .with_trade_stream(app_config.binance.trade_stream)
+            .with_bar_specs(app_config.candles.bars.clone(), app_config.candles.history);
+        if app_config.paper.enabled {
+            // Resting paper orders and stops are matched against individual prints
+            market_manager = market_manager.with_trade_events();
+        }
+        if app_config.storage.enabled {
+            let store = HistoryStore::new(&app_config.storage.dir, app_config.storage.retention());
+            market_manager = market_manager.with_history(HistoryHandle::spawn(
+                store,
+                app_config.storage.record_trades,
+            ));
+        }
+        let market_manager = Arc::new(market_manager);
 
         // Create command router
         let command_router = CommandRouter::new();
@@ -135,6 +200,24 @@ impl SessionManager {
         // Create alert manager
         let alert_manager = AlertManager::new();
 
+        // Load fired alert journal
+        let alert_history = AlertHistory::load(
+            &app_config.alerts.history_file,
+            app_config.alerts.history_limit,
+        );
+
+        // Restore the paper account from the previous session
+        let paper = app_config
+            .paper
+            .enabled
+            .then(|| PaperEngine::load(&app_config.paper));
+
+        // Restore manual holdings from the previous session
+        let portfolio = app_config
+            .portfolio
+            .enabled
+            .then(|| PortfolioTracker::load(&app_config.portfolio, market_manager.exchange_info()));
+
         // Create system notifier (macOS implemented, extensible for Windows)
         let system_notifier = SystemNotifier::new(env!("CARGO_PKG_NAME"));
 
@@ -155,15 +238,29 @@ impl SessionManager {
             state: SessionState::Starting,
             stats: SessionStats::default(),
             market_manager,
+            exchange_info_task: None,
+            metrics_exporter_task: None,
+            clock_sync_task: None,
+            clock_skew_reported: false,
+            scanner: None,
             ui_task: None,
             ui_event_tx: None,
             metrics_collector: None,
-            metrics_status: MetricsConnectionStatus::Disconnected,
             metrics_last_emit: Instant::now(),
             metrics_emit_interval: metrics_interval,
             command_router,
             action_channel,
             alert_manager,
+            alert_history,
+            paper,
+            signed_rest: None,
+            account: None,
+            account_stream: None,
+            account_event_rx: None,
+            pending_order: None,
+            orders_sent: 0,
+            portfolio,
+            portfolio_last_emit: Instant::now(),
             system_notifier,
             shutdown_tx,
             shutdown_rx: Some(shutdown_rx),
@@ -174,6 +271,29 @@ impl SessionManager {
     pub async fn initialize(&mut self) -> Result<()> {
         info!("Initializing interactive session");
 
+        // Load exchange trading rules and keep them fresh in the background
+        let refresh_interval =
+            Duration::from_secs(self.app_config.binance.exchange_info_refresh_secs.max(1));
+        self.exchange_info_task = Some(
+            self.market_manager
+                .spawn_exchange_info_refresh(refresh_interval),
+        );
+
+        // Measure the exchange clock offset so latency metrics exclude local skew
+        if self.app_config.clock.sync_enabled {
+            let interval = Duration::from_secs(self.app_config.clock.sync_interval_secs.max(1));
+            self.clock_sync_task = Some(
+                self.market_manager
+                    .spawn_clock_sync(interval, self.app_config.clock.sync_samples),
+            );
+        }
+
+        // Expose Prometheus metrics before subscriptions start recording
+        if self.app_config.metrics.enabled {
+            let exporter = exporter::start_exporter(&self.app_config.metrics.bind_address).await?;
+            self.metrics_exporter_task = Some(exporter.task);
+        }
+
         // Initialize UI manager if enabled
         if self.config.enable_tui {
             self.initialize_ui().await?;
@@ -184,17 +304,35 @@ impl SessionManager {
             self.initialize_metrics().await?;
         }
 
-        // Auto-subscribe to symbols if configured
-        if self.config.auto_subscribe && !self.app_config.symbols.is_empty() {
+        // Auto-subscribe to configured symbols and those the portfolio is valued from
+        if self.config.auto_subscribe {
             self.spawn_auto_subscribe_symbols();
         }
 
+        if self.app_config.account.enabled || self.app_config.orders.enabled {
+            self.signed_rest = self.load_signed_client()?;
+        }
+        if self.app_config.account.enabled {
+            self.start_account_stream();
+        }
+        if self.app_config.orders.enabled && self.signed_rest.is_some() {
+            let mode = if self.orders_test_mode() {
+                "test mode: orders are validated, never executed"
+            } else {
+                "LIVE: confirmed orders trade on the exchange"
+            };
+            info!("Order entry enabled ({})", mode);
+        }
+
         if self.config.enable_tui {
             let help_lines = CommandRouter::help_messages()
                 .iter()
                 .map(|line| (*line).to_string())
                 .collect();
             self.forward_to_ui(SessionEvent::HelpInfo { lines: help_lines });
+            self.send_alert_history_snapshot();
+            self.send_paper_snapshot();
+            self.send_portfolio_snapshot();
         }
 
         self.state = SessionState::Running;
@@ -203,6 +341,51 @@ impl SessionManager {
         Ok(())
     }
 
+    /// Load API credentials into the signed REST client
+    ///
+    /// Missing or unreadable credentials are reported without stopping the session.
+    fn load_signed_client(&self) -> Result<Option<BinanceRestClient>> {
+        let credentials = match ApiCredentials::load(&self.app_config.account) {
+            Ok(credentials) => credentials,
+            Err(e) => {
+                let message = format!("Account data and orders disabled: {:#}", e);
+                self.action_channel
+                    .send_event(SessionEvent::Error { message })?;
+                return Ok(None);
+            }
+        };
+
+        info!("Loaded API credentials {:?}", credentials);
+        Ok(Some(
+            BinanceRestClient::new(self.app_config.binance.rest_url.clone())
+                .with_credentials(credentials)
+                .with_recv_window(self.app_config.account.recv_window_ms),
+        ))
+    }
+
+    /// Start streaming the exchange account with the signed REST client
+    fn start_account_stream(&mut self) {
+        let Some(rest) = self.signed_rest.clone() else {
+            return;
+        };
+
+        info!("Starting user data stream");
+        let (event_tx, event_rx) = mpsc::unbounded_channel();
+        self.account_stream = Some(spawn_account_stream(
+            rest,
+            self.app_config.binance.ws_url.clone(),
+            Duration::from_secs(self.app_config.account.keepalive_interval_secs),
+            event_tx,
+        ));
+        self.account_event_rx = Some(event_rx);
+        self.account = Some(AccountState::new());
+    }
+
+    /// Whether orders go to the test endpoint, from `orders.test_mode` or `--testnet`
+    fn orders_test_mode(&self) -> bool {
+        self.app_config.orders.test_mode || self.cli.testnet
+    }
+
     /// Start the session using the appropriate execution mode
     pub async fn start(&mut self) -> Result<()> {
         if self.cli.is_dry_run_mode() {
@@ -275,7 +458,8 @@ impl SessionManager {
     async fn initialize_metrics(&mut self) -> Result<()> {
         info!("Initializing metrics collector");
 
-        let metrics_collector = MetricsCollector::new(1000); // 1000 samples max
+        let mut metrics_collector = MetricsCollector::new();
+        metrics_collector.set_clock_skew_warning(self.app_config.clock.warn_skew_ms);
 
         self.metrics_collector = Some(Arc::new(Mutex::new(metrics_collector)));
 
@@ -284,7 +468,14 @@ impl SessionManager {
 
     /// Spawn background task to auto-subscribe to configured symbols with controlled parallelism
     fn spawn_auto_subscribe_symbols(&self) {
-        let symbols = self.app_config.symbols.clone();
+        let mut symbols = self.app_config.symbols.clone();
+        if let Some(portfolio) = &self.portfolio {
+            for symbol in portfolio.required_symbols() {
+                if !symbols.contains(&symbol) {
+                    symbols.push(symbol);
+                }
+            }
+        }
         let market_manager = self.market_manager.clone();
         let action_channel = self.action_channel.clone();
         let ui_event_tx = self.ui_event_tx.clone();
@@ -423,9 +614,19 @@ impl SessionManager {
 
                 // Handle events from action channel (including user commands)
                 Some(event) = self.action_channel.next_event() => {
+                    metrics::gauge!(
+                        exporter::CHANNEL_QUEUE_DEPTH,
+                        self.action_channel.pending_events() as f64,
+                        "channel" => "session_events"
+                    );
                     self.handle_event(event).await?;
                 }
 
+                // Handle updates from the user data stream
+                Some(event) = next_account_event(&mut self.account_event_rx) => {
+                    self.handle_account_event(event);
+                }
+
                 // Session timeout check
                 _ = tokio::time::sleep(std::time::Duration::from_millis(100)) => {
                     self.check_timeout().await?;
@@ -434,7 +635,13 @@ impl SessionManager {
                 // Handle market events without blocking other tasks
                 market_event = async {
                     let mut receiver = market_event_rx.lock().await;
-                    receiver.recv().await
+                    let event = receiver.recv().await;
+                    metrics::gauge!(
+                        exporter::CHANNEL_QUEUE_DEPTH,
+                        receiver.len() as f64,
+                        "channel" => "market_events"
+                    );
+                    event
                 } => {
                     match market_event {
                         Some(market_event) => {
@@ -472,9 +679,513 @@ impl SessionManager {
             InteractiveCommand::Logs => self.handle_logs().await,
             InteractiveCommand::Help => self.handle_help().await,
             InteractiveCommand::Alert { action } => self.handle_alert(action).await,
+            InteractiveCommand::Scanner { enabled } => self.handle_scanner(enabled).await,
+            InteractiveCommand::Paper { action } => self.handle_paper(action).await,
+            InteractiveCommand::Account => self.handle_account().await,
+            InteractiveCommand::Order { action } => self.handle_order(action).await,
+            InteractiveCommand::Position { action } => self.handle_position(action).await,
+            InteractiveCommand::Impact { request } => self.handle_impact(request).await,
+        }
+    }
+
+    /// Show the authenticated exchange account
+    async fn handle_account(&mut self) -> Result<()> {
+        let Some(account) = &self.account else {
+            let message =
+                "Account data is disabled (set account.enabled = true and provide API credentials)"
+                    .to_string();
+            self.action_channel
+                .send_event(SessionEvent::Error { message })?;
+            return Ok(());
+        };
+
+        if self.config.enable_tui {
+            self.send_account_snapshot();
+            self.forward_to_ui(SessionEvent::ShowAccount);
+        } else {
+            for line in describe_account(account) {
+                println!("{}", line);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Handle live order command
+    ///
+    /// New orders and replacements are validated and staged; nothing is sent
+    /// until `/order:confirm` arrives within [`CONFIRM_TIMEOUT`].
+    async fn handle_order(&mut self, action: OrderAction) -> Result<()> {
+        if !self.app_config.orders.enabled {
+            self.reject_order("Order entry is disabled (set orders.enabled = true)".into());
+            return Ok(());
+        }
+        let Some(rest) = self.signed_rest.clone() else {
+            self.reject_order(
+                "Order entry needs API credentials; see the Account Settings in the user guide"
+                    .into(),
+            );
+            return Ok(());
+        };
+
+        match action {
+            OrderAction::Place { request } => {
+                if let Some(order) = self.validate_live_order(&request).await {
+                    self.stage_order(PendingOrder::new(PendingAction::New, order));
+                }
+            }
+            OrderAction::Replace {
+                symbol,
+                order_id,
+                quantity,
+                price,
+            } => {
+                // Side and type come from the order being replaced
+                let open_orders = match rest.get_open_orders(Some(&symbol)).await {
+                    Ok(orders) => orders,
+                    Err(e) => {
+                        self.reject_order(format!("Failed to load open orders: {:#}", e));
+                        return Ok(());
+                    }
+                };
+                let Some(existing) = open_orders.iter().find(|order| order.order_id == order_id)
+                else {
+                    self.reject_order(format!("No open order {} on {}", order_id, symbol));
+                    return Ok(());
+                };
+                let side = match (existing.order_type.as_str(), existing.side.as_str()) {
+                    ("LIMIT", "BUY") => OrderSide::Buy,
+                    ("LIMIT", "SELL") => OrderSide::Sell,
+                    (order_type, _) => {
+                        self.reject_order(format!(
+                            "Only LIMIT orders can be replaced; order {} is {}",
+                            order_id, order_type
+                        ));
+                        return Ok(());
+                    }
+                };
+                let request = OrderRequest {
+                    symbol,
+                    side,
+                    order_type: OrderType::Limit { price },
+                    quantity,
+                };
+                if let Some(order) = self.validate_live_order(&request).await {
+                    self.stage_order(PendingOrder::new(
+                        PendingAction::Replace { order_id },
+                        order,
+                    ));
+                }
+            }
+            OrderAction::Cancel { symbol, order_id } => {
+                if self.orders_test_mode() {
+                    self.emit_order_notification(format!(
+                        "Test mode: cancel of order {} on {} not sent",
+                        order_id, symbol
+                    ));
+                    return Ok(());
+                }
+                match rest.cancel_order(&symbol, order_id).await {
+                    Ok(ack) => self.emit_order_notification(format!(
+                        "Order {} on {} cancelled ({})",
+                        ack.order_id, ack.symbol, ack.status
+                    )),
+                    Err(e) => self.reject_order(format!("Cancel failed: {:#}", e)),
+                }
+            }
+            OrderAction::Confirm => {
+                let Some(pending) = self.pending_order.take() else {
+                    self.reject_order("No order is waiting for confirmation".into());
+                    return Ok(());
+                };
+                self.forward_to_ui(SessionEvent::OrderStaged { summary: None });
+                if pending.is_expired() {
+                    self.reject_order(format!(
+                        "The staged order expired after {}s; enter it again",
+                        CONFIRM_TIMEOUT.as_secs()
+                    ));
+                    return Ok(());
+                }
+                // Market orders are valued again, the book may have moved since staging
+                let Some(order) = self.validate_live_order(&pending.order.request).await else {
+                    return Ok(());
+                };
+                self.send_order(&rest, pending.action, order).await;
+            }
+            OrderAction::Discard => {
+                if self.pending_order.take().is_some() {
+                    self.forward_to_ui(SessionEvent::OrderStaged { summary: None });
+                    self.emit_order_notification("Staged order discarded".into());
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Check an order against the exchange filters and `orders.max_notional`,
+    /// reporting the reason when it fails
+    async fn validate_live_order(&self, request: &OrderRequest) -> Option<ValidatedOrder> {
+        let info = self.market_manager.exchange_info().get(&request.symbol);
+        let book = self.market_manager.get_orderbook(&request.symbol).await;
+        match validate_order(
+            request,
+            info.as_ref(),
+            book.as_ref(),
+            &self.app_config.orders.max_notional,
+        ) {
+            Ok(order) => Some(order),
+            Err(e) => {
+                self.reject_order(e.to_string());
+                None
+            }
+        }
+    }
+
+    /// Hold a validated order until it is confirmed or discarded
+    fn stage_order(&mut self, pending: PendingOrder) {
+        let summary = pending.describe(self.orders_test_mode());
+        info!("Order staged: {}", summary);
+        self.pending_order = Some(pending);
+
+        if self.config.enable_tui {
+            self.forward_to_ui(SessionEvent::OrderStaged {
+                summary: Some(summary),
+            });
+        } else {
+            println!("{}", summary);
+            println!(
+                "Type /order:confirm within {}s to send it or /order:discard to drop it",
+                CONFIRM_TIMEOUT.as_secs()
+            );
+        }
+    }
+
+    /// Send a confirmed order, to the test endpoint in test mode
+    async fn send_order(
+        &mut self,
+        rest: &BinanceRestClient,
+        action: PendingAction,
+        order: ValidatedOrder,
+    ) {
+        self.orders_sent += 1;
+        let client_order_id = trading::client_order_id(now_ms(), self.orders_sent);
+        let params = order.params(&client_order_id);
+        let test = self.orders_test_mode();
+        info!(
+            "Sending {}order {}: {}",
+            if test { "test " } else { "" },
+            client_order_id,
+            order.describe()
+        );
+
+        let result = match action {
+            PendingAction::New => rest.place_order(&params, test).await,
+            // There is no test endpoint for cancel-replace; check the new leg only
+            PendingAction::Replace { .. } if test => rest.place_order(&params, true).await,
+            PendingAction::Replace { order_id } => {
+                rest.cancel_replace_order(order_id, &params).await.map(Some)
+            }
+        };
+
+        match result {
+            Ok(Some(ack)) => self.emit_order_notification(format!(
+                "Order {} placed: {} ({})",
+                ack.order_id,
+                order.describe(),
+                ack.status
+            )),
+            Ok(None) => self.emit_order_notification(format!(
+                "Test order accepted by Binance, nothing was traded: {}",
+                order.describe()
+            )),
+            Err(e) => self.reject_order(format!("Order failed: {:#}", e)),
         }
     }
 
+    /// Apply a user data stream event and refresh the account panel
+    fn handle_account_event(&mut self, event: AccountEvent) {
+        let Some(account) = self.account.as_mut() else {
+            return;
+        };
+
+        match event {
+            AccountEvent::Snapshot {
+                balances,
+                open_orders,
+                time_ms,
+            } => {
+                debug!(
+                    "Account snapshot: {} balances, {} open orders",
+                    balances.len(),
+                    open_orders.len()
+                );
+                account.apply_snapshot(balances, open_orders, time_ms);
+            }
+            AccountEvent::Update(update) => {
+                account.apply(&update);
+                if let crate::account::AccountUpdate::Execution(execution) = &update {
+                    self.emit_account_notification(describe_execution(execution));
+                }
+            }
+            AccountEvent::Status(status) => {
+                let changed = *account.status() != status;
+                account.set_status(status.clone());
+                match &status {
+                    StreamStatus::Live if changed => {
+                        self.emit_account_notification("Account stream connected".into());
+                    }
+                    StreamStatus::Failed { .. } => {
+                        self.emit_account_notification(format!(
+                            "Account stream {}",
+                            status.describe()
+                        ));
+                    }
+                    StreamStatus::Reconnecting { reason } if changed => {
+                        warn!("Account stream reconnecting: {}", reason);
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        self.send_account_snapshot();
+    }
+
+    /// Handle paper trading command
+    async fn handle_paper(&mut self, action: PaperAction) -> Result<()> {
+        if self.paper.is_none() {
+            let message = "Paper trading is disabled (set paper.enabled = true)".to_string();
+            self.action_channel
+                .send_event(SessionEvent::Error { message })?;
+            return Ok(());
+        }
+
+        match action {
+            PaperAction::Show => {
+                if self.config.enable_tui {
+                    self.send_paper_snapshot();
+                    self.forward_to_ui(SessionEvent::ShowPaper);
+                } else if let Some(paper) = &self.paper {
+                    for line in describe_paper_account(paper) {
+                        println!("{}", line);
+                    }
+                }
+            }
+            PaperAction::Order { request } => {
+                let symbol = request.symbol.clone();
+                if !self
+                    .market_manager
+                    .list_subscriptions()
+                    .await
+                    .contains(&symbol)
+                {
+                    let message = format!(
+                        "Paper orders need live data; subscribe first with /add {}",
+                        symbol
+                    );
+                    self.action_channel
+                        .send_event(SessionEvent::Error { message })?;
+                    return Ok(());
+                }
+
+                let info = self.market_manager.exchange_info().get(&symbol);
+                let book = self.market_manager.get_orderbook(&symbol).await;
+                let Some(paper) = self.paper.as_mut() else {
+                    return Ok(());
+                };
+                match paper.place_order(request, info.as_ref(), book.as_ref(), now_ms()) {
+                    Ok((order, fills)) => {
+                        self.emit_paper_notification(format!(
+                            "Paper order placed: {}",
+                            describe_paper_order(&order)
+                        ));
+                        self.report_paper_fills(&fills);
+                        self.save_paper();
+                    }
+                    Err(e) => {
+                        let message = format!("Paper order rejected: {}", e);
+                        self.action_channel
+                            .send_event(SessionEvent::Error { message })?;
+                    }
+                }
+            }
+            PaperAction::Cancel { target } => {
+                let Some(paper) = self.paper.as_mut() else {
+                    return Ok(());
+                };
+                match target {
+                    ClearTarget::All => {
+                        let cancelled = paper.cancel_all(now_ms());
+                        self.emit_paper_notification(format!(
+                            "Cancelled {} paper orders",
+                            cancelled.len()
+                        ));
+                        self.save_paper();
+                    }
+                    ClearTarget::Id(id) => match paper.cancel_order(id, now_ms()) {
+                        Ok(order) => {
+                            self.emit_paper_notification(format!(
+                                "Paper order cancelled: {}",
+                                describe_paper_order(&order)
+                            ));
+                            self.save_paper();
+                        }
+                        Err(e) => {
+                            self.action_channel.send_event(SessionEvent::Error {
+                                message: e.to_string(),
+                            })?;
+                        }
+                    },
+                }
+            }
+            PaperAction::Reset => {
+                if let Some(paper) = self.paper.as_mut() {
+                    paper.reset();
+                }
+                self.emit_paper_notification("Paper account reset to starting balances".into());
+                self.save_paper();
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Handle manual portfolio command
+    async fn handle_position(&mut self, action: PositionAction) -> Result<()> {
+        let Some(portfolio) = self.portfolio.as_mut() else {
+            let message = "Portfolio is disabled (set portfolio.enabled = true)".to_string();
+            self.action_channel
+                .send_event(SessionEvent::Error { message })?;
+            return Ok(());
+        };
+
+        let result = match action {
+            PositionAction::Show => {
+                if self.config.enable_tui {
+                    self.send_portfolio_snapshot();
+                    self.forward_to_ui(SessionEvent::ShowPortfolio);
+                } else {
+                    for line in describe_portfolio(&portfolio.snapshot()) {
+                        println!("{}", line);
+                    }
+                }
+                return Ok(());
+            }
+            PositionAction::Add {
+                symbol,
+                quantity,
+                cost_basis,
+            } => portfolio.add(&symbol, quantity, cost_basis).map(|holding| {
+                format!(
+                    "Holding {} is now {} at {} per unit",
+                    holding.symbol, holding.quantity, holding.cost_basis
+                )
+            }),
+            PositionAction::Remove { symbol } => portfolio
+                .remove(&symbol)
+                .map(|holding| format!("Removed holding {}", holding.symbol)),
+            PositionAction::Alert { kind, threshold } => {
+                portfolio.set_alert(kind, threshold).map(|()| {
+                    let unit = match kind {
+                        PortfolioAlertKind::Drawdown => "%".to_string(),
+                        _ => format!(" {}", portfolio.currency()),
+                    };
+                    match threshold {
+                        Some(threshold) => format!(
+                            "Portfolio {} alert set to {}{}",
+                            kind.label(),
+                            threshold,
+                            unit
+                        ),
+                        None => format!("Portfolio {} alert cleared", kind.label()),
+                    }
+                })
+            }
+            PositionAction::Reset => {
+                portfolio.reset();
+                Ok("Portfolio reset to the configured holdings".to_string())
+            }
+        };
+
+        match result {
+            Ok(message) => {
+                self.emit_portfolio_notification(message);
+                self.save_portfolio();
+                self.subscribe_portfolio_symbols().await?;
+            }
+            Err(e) => {
+                self.action_channel.send_event(SessionEvent::Error {
+                    message: e.to_string(),
+                })?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Estimate a market order on the live book; the TUI panel keeps it updated
+    async fn handle_impact(&mut self, request: ImpactRequest) -> Result<()> {
+        let Some(book) = self.market_manager.get_orderbook(&request.symbol).await else {
+            let message = format!(
+                "No order book for {}; subscribe first with /add {}",
+                request.symbol, request.symbol
+            );
+            self.action_channel
+                .send_event(SessionEvent::Error { message })?;
+            return Ok(());
+        };
+
+        if self.config.enable_tui {
+            self.forward_to_ui(SessionEvent::ShowImpact { request });
+        } else {
+            let impact = book.market_impact(request.side, request.size);
+            let exchange_info = self.market_manager.exchange_info();
+            for line in describe_impact(&request.symbol, &impact, &exchange_info) {
+                println!("{}", line);
+            }
+        }
+        Ok(())
+    }
+
+    /// Subscribe to the portfolio's symbols that are not streamed yet
+    async fn subscribe_portfolio_symbols(&mut self) -> Result<()> {
+        let Some(portfolio) = &self.portfolio else {
+            return Ok(());
+        };
+        let subscribed = self.market_manager.list_subscriptions().await;
+        let missing: Vec<String> = portfolio
+            .required_symbols()
+            .into_iter()
+            .filter(|symbol| !subscribed.contains(symbol))
+            .collect();
+        if missing.is_empty() {
+            return Ok(());
+        }
+        self.handle_subscribe(missing).await
+    }
+
+    /// Start or stop the all-market scanner stream
+    async fn handle_scanner(&mut self, enabled: bool) -> Result<()> {
+        if !self.config.enable_tui {
+            println!("The scanner view requires the TUI.");
+            return Ok(());
+        }
+
+        if enabled {
+            if self.scanner.is_none() {
+                info!("Starting market scanner");
+                self.scanner = Some(self.market_manager.spawn_scanner());
+            }
+        } else if let Some(scanner) = self.scanner.take() {
+            info!("Stopping market scanner");
+            scanner.stop();
+        }
+
+        self.forward_to_ui(SessionEvent::ShowScanner { visible: enabled });
+        Ok(())
+    }
+
     /// Handle subscribe command
     async fn handle_subscribe(&mut self, symbols: Vec<String>) -> Result<()> {
         for symbol in symbols {
@@ -502,6 +1213,9 @@ impl SessionManager {
             match self.market_manager.unsubscribe(&symbol).await {
                 Ok(()) => {
                     info!("Unsubscribed from symbol: {}", symbol);
+                    if let Some(metrics_collector) = &self.metrics_collector {
+                        metrics_collector.lock().await.remove_symbol(&symbol);
+                    }
                     self.action_channel
                         .send_event(SessionEvent::SubscriptionRemoved { symbol })?;
                 }
@@ -597,7 +1311,7 @@ impl SessionManager {
         match action {
             Some(crate::cli::ConfigAction::Show) => {
                 self.action_channel.send_event(SessionEvent::ConfigInfo {
-                    config: self.app_config.clone(),
+                    config: Box::new(self.app_config.clone()),
                 })?;
             }
             Some(crate::cli::ConfigAction::Set { key, value }) => {
@@ -610,7 +1324,7 @@ impl SessionManager {
                                 Duration::from_millis(self.app_config.refresh_rate_ms.max(50));
                             info!("Updated refresh_rate_ms to {}", parsed);
                             self.action_channel.send_event(SessionEvent::ConfigInfo {
-                                config: self.app_config.clone(),
+                                config: Box::new(self.app_config.clone()),
                             })?;
                         }
                         _ => {
@@ -625,7 +1339,7 @@ impl SessionManager {
                             self.app_config.orderbook_depth = parsed;
                             info!("Updated orderbook_depth to {}", parsed);
                             self.action_channel.send_event(SessionEvent::ConfigInfo {
-                                config: self.app_config.clone(),
+                                config: Box::new(self.app_config.clone()),
                             })?;
                         }
                         _ => {
@@ -640,7 +1354,7 @@ impl SessionManager {
                             self.app_config.ui.sparkline_points = parsed;
                             info!("Updated ui.sparkline_points to {}", parsed);
                             self.action_channel.send_event(SessionEvent::ConfigInfo {
-                                config: self.app_config.clone(),
+                                config: Box::new(self.app_config.clone()),
                             })?;
                         }
                         _ => {
@@ -666,7 +1380,7 @@ impl SessionManager {
                     Duration::from_millis(self.app_config.refresh_rate_ms.max(50));
                 self.action_channel.send_event(SessionEvent::ConfigReset)?;
                 self.action_channel.send_event(SessionEvent::ConfigInfo {
-                    config: self.app_config.clone(),
+                    config: Box::new(self.app_config.clone()),
                 })?;
             }
             None => {
@@ -747,7 +1461,7 @@ impl SessionManager {
                 price,
                 options,
             } => {
-                self.add_alert_from_ui(symbol, direction, price, options)?;
+                self.add_alert_from_ui(symbol, AlertInput::Price, direction, price, options)?;
             }
             SessionEvent::UserCommand { command } => {
                 self.handle_command(command).await?;
@@ -772,43 +1486,20 @@ impl SessionManager {
                 if alerts.is_empty() {
                     entries.push("No alerts configured.".to_string());
                 } else {
+                    let now = now_ms();
                     for alert in &alerts {
-                        let status = if alert.triggered {
-                            "triggered"
+                        let status = if alert.paused {
+                            "paused".to_string()
+                        } else if alert.is_snoozed(now) {
+                            let remaining_s =
+                                alert.snoozed_until_ms.unwrap_or(now).saturating_sub(now) / 1_000;
+                            format!("snoozed {}s", remaining_s)
+                        } else if alert.triggered {
+                            "triggered".to_string()
                         } else {
-                            "armed"
+                            "armed".to_string()
                         };
-                        let mode = match alert.repeat {
-                            AlertRepeat::Once => "once".to_string(),
-                            AlertRepeat::Repeat => {
-                                if alert.cooldown_ms > 0 {
-                                    format!("repeat/{}s", alert.cooldown_ms / 1_000)
-                                } else {
-                                    "repeat".to_string()
-                                }
-                            }
-                        };
-                        let cooldown = if alert.cooldown_ms > 0 {
-                            format!("{}s", alert.cooldown_ms / 1_000)
-                        } else {
-                            "0".to_string()
-                        };
-                        let hysteresis = if alert.hysteresis > 0.0 {
-                            format!("{:.4}", alert.hysteresis)
-                        } else {
-                            "0".to_string()
-                        };
-                        entries.push(format!(
-                            "#{} {} {:?} {} ({}, mode={}, cooldown={}, hysteresis={})",
-                            alert.id,
-                            alert.symbol,
-                            alert.direction,
-                            alert.threshold,
-                            status,
-                            mode,
-                            cooldown,
-                            hysteresis
-                        ));
+                        entries.push(format!("{} ({})", describe_alert(alert), status));
                     }
                 }
 
@@ -844,6 +1535,69 @@ impl SessionManager {
                     }
                 }
             },
+            AlertAction::Add {
+                symbol,
+                input,
+                direction,
+                threshold,
+                options,
+            } => {
+                self.add_alert_from_ui(symbol, input, direction, threshold, options)?;
+            }
+            AlertAction::Edit { id, update } => {
+                let result = self.alert_manager.update_alert(id, update);
+                self.report_alert_change(result, "updated")?;
+            }
+            AlertAction::Pause { id } => {
+                let result = self.alert_manager.set_paused(id, true);
+                self.report_alert_change(result, "paused")?;
+            }
+            AlertAction::Resume { id } => {
+                let result = self.alert_manager.set_paused(id, false);
+                self.report_alert_change(result, "resumed")?;
+            }
+            AlertAction::Snooze { id, duration_ms } => {
+                let result = self.alert_manager.snooze(id, duration_ms);
+                let verb = format!("snoozed for {}s", duration_ms / 1_000);
+                self.report_alert_change(result, &verb)?;
+            }
+            AlertAction::History { symbol } => {
+                let entries = self.alert_history.filtered(symbol.as_deref());
+                if self.config.enable_tui {
+                    self.send_alert_history_snapshot();
+                    self.forward_to_ui(SessionEvent::ShowAlertHistory { symbol });
+                } else if entries.is_empty() {
+                    println!("No alerts have fired yet.");
+                } else {
+                    for entry in entries.iter().rev() {
+                        println!("{}", describe_history_entry(entry));
+                    }
+                }
+            }
+            AlertAction::Export { symbol, path } => {
+                let entries = self.alert_history.filtered(symbol.as_deref());
+                let path = path.unwrap_or_else(|| {
+                    format!(
+                        "alert_history_{}.csv",
+                        chrono::Local::now().format("%Y%m%d-%H%M%S")
+                    )
+                });
+                match alert_history::export_csv(&path, &entries) {
+                    Ok(()) => {
+                        let message = format!(
+                            "Exported {} alert history entries to {}",
+                            entries.len(),
+                            path
+                        );
+                        self.emit_alert_notification(message);
+                    }
+                    Err(e) => {
+                        let message = format!("Failed to export alert history: {:#}", e);
+                        self.action_channel
+                            .send_event(SessionEvent::Error { message })?;
+                    }
+                }
+            }
         }
 
         Ok(())
@@ -852,45 +1606,17 @@ impl SessionManager {
     fn add_alert_from_ui(
         &mut self,
         symbol: String,
+        input: AlertInput,
         direction: AlertDirection,
         price: f64,
         options: AlertOptions,
     ) -> Result<()> {
         match self
             .alert_manager
-            .add_alert_with_options(symbol, direction, price, options)
+            .add_input_alert(symbol, input, direction, price, options)
         {
             Ok(alert) => {
-                let mode = match alert.repeat {
-                    AlertRepeat::Once => "once".to_string(),
-                    AlertRepeat::Repeat => {
-                        if alert.cooldown_ms > 0 {
-                            format!("repeat/{}s", alert.cooldown_ms / 1_000)
-                        } else {
-                            "repeat".to_string()
-                        }
-                    }
-                };
-                let cooldown = if alert.cooldown_ms > 0 {
-                    format!("{}s", alert.cooldown_ms / 1_000)
-                } else {
-                    "0".to_string()
-                };
-                let hysteresis = if alert.hysteresis > 0.0 {
-                    format!("{:.4}", alert.hysteresis)
-                } else {
-                    "0".to_string()
-                };
-                let message = format!(
-                    "Alert #{} added: {} {:?} {} (mode={}, cooldown={}, hysteresis={})",
-                    alert.id,
-                    alert.symbol,
-                    alert.direction,
-                    alert.threshold,
-                    mode,
-                    cooldown,
-                    hysteresis
-                );
+                let message = format!("Alert added: {}", describe_alert(&alert));
                 self.emit_alert_notification(message);
                 self.send_alert_snapshot();
             }
@@ -904,6 +1630,23 @@ impl SessionManager {
         Ok(())
     }
 
+    /// Report the outcome of an alert modification to UI/CLI
+    fn report_alert_change(&mut self, result: Result<Alert>, verb: &str) -> Result<()> {
+        match result {
+            Ok(alert) => {
+                let message = format!("Alert {}: {}", verb, describe_alert(&alert));
+                self.emit_alert_notification(message);
+                self.send_alert_snapshot();
+            }
+            Err(e) => {
+                let message = format!("Failed to modify alert: {}", e);
+                self.action_channel
+                    .send_event(SessionEvent::Error { message })?;
+            }
+        }
+        Ok(())
+    }
+
     /// Forward an event to the UI if the channel is available
     fn forward_to_ui(&self, event: SessionEvent) {
         if let Some(ui_event_tx) = &self.ui_event_tx {
@@ -932,14 +1675,224 @@ impl SessionManager {
             AlertDirection::Above => "above",
             AlertDirection::Below => "below",
         };
-        let title = format!("{} price alert", trigger.symbol);
-        let body = format!(
-            "Price {} {:.4} (last {})",
-            direction, trigger.threshold, trigger.price
-        );
+        let title = format!("{} {} alert", trigger.symbol, trigger.input.label());
+        let body = match trigger.input {
+            AlertInput::Price => format!(
+                "Price {} {:.4} (last {})",
+                direction, trigger.threshold, trigger.price
+            ),
+            input => format!(
+                "{} {} {:.4} (now {:.4})",
+                input.label().to_ascii_uppercase(),
+                direction,
+                trigger.threshold,
+                trigger.price
+            ),
+        };
         self.system_notifier.notify(title, body);
     }
 
+    /// Surface a trading status change for a watched symbol
+    fn notify_symbol_status_change(
+        &self,
+        symbol: &str,
+        previous: &SymbolStatus,
+        current: &SymbolStatus,
+    ) {
+        warn!(
+            "Symbol {} status changed: {} -> {}",
+            symbol, previous, current
+        );
+
+        let message = format!(
+            "Symbol {} status changed: {} -> {}",
+            symbol, previous, current
+        );
+        self.emit_alert_notification(message);
+        self.system_notifier.notify(
+            format!("{} status change", symbol),
+            format!("{} -> {}", previous, current),
+        );
+    }
+
+    /// Warn once when the local clock drifts past the threshold, and again after it recovers
+    fn check_clock_skew(&mut self, estimate: &ClockEstimate) {
+        let threshold = self.app_config.clock.warn_skew_ms;
+        let skewed = estimate.exceeds(threshold);
+
+        if skewed && !self.clock_skew_reported {
+            let direction = if estimate.offset_ms > 0 {
+                "behind"
+            } else {
+                "ahead of"
+            };
+            let message = format!(
+                "Local clock is {} ms {} Binance (threshold {} ms); latency figures are corrected but consider syncing NTP",
+                estimate.offset_ms.unsigned_abs(),
+                direction,
+                threshold
+            );
+            warn!("{}", message);
+            if self.config.enable_tui {
+                self.forward_to_ui(SessionEvent::Error { message });
+            } else {
+                println!("{}", message);
+            }
+        } else if !skewed && self.clock_skew_reported {
+            info!(
+                "Local clock back within {} ms of Binance (offset {} ms)",
+                threshold, estimate.offset_ms
+            );
+        }
+        self.clock_skew_reported = skewed;
+    }
+
+    /// Announce paper fills
+    fn report_paper_fills(&self, fills: &[PaperFill]) {
+        for fill in fills {
+            self.emit_paper_notification(describe_paper_fill(fill));
+        }
+    }
+
+    /// Persist the paper account and refresh the positions panel
+    fn save_paper(&self) {
+        if let Some(paper) = &self.paper {
+            if let Err(e) = paper.save() {
+                error!("Failed to save paper account: {:#}", e);
+            }
+        }
+        self.send_paper_snapshot();
+    }
+
+    /// Persist the portfolio and refresh the portfolio panel
+    fn save_portfolio(&mut self) {
+        if let Some(portfolio) = &self.portfolio {
+            if let Err(e) = portfolio.save() {
+                error!("Failed to save portfolio: {:#}", e);
+            }
+        }
+        self.send_portfolio_snapshot();
+    }
+
+    /// Emit a portfolio change to UI/logs or stdout for headless mode
+    fn emit_portfolio_notification(&self, message: String) {
+        info!("{}", message);
+
+        if self.config.enable_tui {
+            self.forward_to_ui(SessionEvent::PortfolioNotification { message });
+        } else {
+            println!("{}", message);
+        }
+    }
+
+    /// Emit a paper trading notification to UI/logs or stdout for headless mode
+    fn emit_paper_notification(&self, message: String) {
+        info!("{}", message);
+
+        if self.config.enable_tui {
+            self.forward_to_ui(SessionEvent::PaperNotification { message });
+        } else {
+            println!("{}", message);
+        }
+    }
+
+    /// Emit an account notification to UI/logs or stdout for headless mode
+    fn emit_account_notification(&self, message: String) {
+        info!("{}", message);
+
+        if self.config.enable_tui {
+            self.forward_to_ui(SessionEvent::AccountNotification { message });
+        } else {
+            println!("{}", message);
+        }
+    }
+
+    /// Emit a live order notification to UI/logs or stdout for headless mode
+    fn emit_order_notification(&self, message: String) {
+        info!("{}", message);
+
+        if self.config.enable_tui {
+            self.forward_to_ui(SessionEvent::OrderNotification { message });
+        } else {
+            println!("{}", message);
+        }
+    }
+
+    /// Report an order that failed validation or was refused
+    fn reject_order(&self, message: String) {
+        warn!("{}", message);
+
+        if self.config.enable_tui {
+            self.forward_to_ui(SessionEvent::OrderRejected { message });
+        } else {
+            println!("{}", message);
+        }
+    }
+
+    /// Send the exchange account to UI surfaces
+    fn send_account_snapshot(&self) {
+        if let (true, Some(account)) = (self.config.enable_tui, &self.account) {
+            self.forward_to_ui(SessionEvent::AccountSnapshot {
+                snapshot: account.snapshot(),
+            });
+        }
+    }
+
+    /// Send the paper account to UI surfaces
+    fn send_paper_snapshot(&self) {
+        if let (true, Some(paper)) = (self.config.enable_tui, &self.paper) {
+            self.forward_to_ui(SessionEvent::PaperSnapshot {
+                snapshot: paper.snapshot(PAPER_PANEL_FILLS),
+            });
+        }
+    }
+
+    /// Send the valued holdings to the portfolio panel
+    fn send_portfolio_snapshot(&mut self) {
+        if let Some(portfolio) = &self.portfolio {
+            if self.config.enable_tui {
+                self.forward_to_ui(SessionEvent::PortfolioSnapshot {
+                    snapshot: portfolio.snapshot(),
+                });
+            }
+            self.portfolio_last_emit = Instant::now();
+        }
+    }
+
+    /// Value the portfolio at a new price and report crossed thresholds
+    fn update_portfolio(&mut self, event: &crate::market_data::MarketEvent) {
+        let (symbol, price, change_percent) = match event {
+            crate::market_data::MarketEvent::PriceUpdate { symbol, price, .. } => {
+                (symbol, *price, None)
+            }
+            crate::market_data::MarketEvent::TickerUpdate {
+                symbol,
+                last_price,
+                price_change_percent,
+                ..
+            } => (symbol, *last_price, Some(*price_change_percent)),
+            _ => return,
+        };
+        let Some(portfolio) = self.portfolio.as_mut() else {
+            return;
+        };
+        if !portfolio.tracks(symbol) {
+            return;
+        }
+
+        for trigger in portfolio.on_price(symbol, price, change_percent) {
+            let message = trigger.describe();
+            self.emit_alert_notification(message.clone());
+            self.system_notifier
+                .notify("Portfolio alert".to_string(), message);
+        }
+
+        // Prices arrive far faster than the panel needs them
+        if self.portfolio_last_emit.elapsed() >= self.metrics_emit_interval {
+            self.send_portfolio_snapshot();
+        }
+    }
+
     /// Send the current alert snapshot to UI surfaces
     fn send_alert_snapshot(&self) {
         if self.config.enable_tui {
@@ -948,47 +1901,109 @@ impl SessionManager {
         }
     }
 
+    /// Send the fired alert history to UI surfaces
+    fn send_alert_history_snapshot(&self) {
+        if self.config.enable_tui {
+            let entries = self.alert_history.filtered(None);
+            self.forward_to_ui(SessionEvent::AlertHistorySnapshot { entries });
+        }
+    }
+
     /// Handle market event
     async fn handle_market_event(&mut self, event: crate::market_data::MarketEvent) -> Result<()> {
         debug!("Handling market event: {:?}", event);
 
-        if let Some((symbol, price)) = match &event {
-            crate::market_data::MarketEvent::PriceUpdate { symbol, price, .. } => {
-                Some((symbol.clone(), *price))
+        match &event {
+            crate::market_data::MarketEvent::PriceUpdate {
+                symbol,
+                price,
+                time,
+            } => {
+                let normalized = symbol.to_ascii_uppercase();
+                let (triggers, state_changed) =
+                    self.alert_manager
+                        .evaluate_trade_price(&normalized, *price, *time);
+                self.dispatch_alert_triggers(&normalized, triggers, state_changed)
+                    .await?;
             }
             crate::market_data::MarketEvent::TickerUpdate {
                 symbol, last_price, ..
-            } => Some((symbol.clone(), *last_price)),
-            _ => None,
-        } {
-            self.evaluate_alerts(&symbol, price)?;
+            } => {
+                self.evaluate_alerts(symbol, AlertInput::Price, *last_price)
+                    .await?;
+            }
+            _ => {}
         }
 
-        // Forward to UI if available
-        if let Some(ui_event_tx) = &self.ui_event_tx {
-            if let Err(e) = ui_event_tx.send(SessionEvent::MarketEvent(event.clone())) {
-                error!("Failed to send market event to UI: {}", e);
+        if let crate::market_data::MarketEvent::TradeAnalytics { symbol, analytics } = &event {
+            for input in AlertInput::TRADE_FLOW {
+                if !self.alert_manager.has_input(input) {
+                    continue;
+                }
+                if let Some(value) = input.value_from(analytics) {
+                    self.evaluate_alerts(symbol, input, value).await?;
+                }
             }
         }
 
-        if let crate::market_data::MarketEvent::ConnectionStatus { status, .. } = &event {
-            self.metrics_status = match status {
-                crate::binance::types::ConnectionStatus::Disconnected => {
-                    MetricsConnectionStatus::Disconnected
-                }
-                crate::binance::types::ConnectionStatus::Connecting => {
-                    MetricsConnectionStatus::Connecting
+        if let crate::market_data::MarketEvent::Bar { symbol, spec, bar } = &event {
+            // Bars catch crossings inside their range that no single print reported;
+            // the first configured spec is enough since nested bars cover the same trades
+            if bar.is_closed
+                && self.app_config.candles.bars.first() == Some(spec)
+                && self.alert_manager.has_input(AlertInput::Price)
+            {
+                self.evaluate_bar_alerts(symbol, bar).await?;
+            }
+        }
+
+        if let crate::market_data::MarketEvent::Microstructure { symbol, snapshot } = &event {
+            for input in AlertInput::MICROSTRUCTURE {
+                if !self.alert_manager.has_input(input) {
+                    continue;
                 }
-                crate::binance::types::ConnectionStatus::Connected => {
-                    MetricsConnectionStatus::Connected
+                if let Some(value) = input.value_from_book(snapshot) {
+                    self.evaluate_alerts(symbol, input, value).await?;
                 }
-                crate::binance::types::ConnectionStatus::Reconnecting => {
-                    MetricsConnectionStatus::Reconnecting
+            }
+        }
+
+        if let crate::market_data::MarketEvent::BookTicker { symbol, ticker } = &event {
+            for input in AlertInput::BOOK_TICKER {
+                if !self.alert_manager.has_input(input) {
+                    continue;
                 }
-                crate::binance::types::ConnectionStatus::Error(err) => {
-                    MetricsConnectionStatus::Error(err.clone())
+                if let Some(value) = input.value_from_ticker(ticker) {
+                    self.evaluate_alerts(symbol, input, value).await?;
                 }
-            };
+            }
+        }
+
+        if let crate::market_data::MarketEvent::SymbolStatusChanged {
+            symbol,
+            previous,
+            current,
+        } = &event
+        {
+            self.notify_symbol_status_change(symbol, previous, current);
+        }
+
+        if let crate::market_data::MarketEvent::ClockOffset { estimate } = &event {
+            self.check_clock_skew(estimate);
+            // Shared with the account stream's client
+            if let Some(rest) = &self.signed_rest {
+                rest.set_time_offset_ms(estimate.offset_ms);
+            }
+        }
+
+        self.match_paper_orders(&event).await;
+        self.update_portfolio(&event);
+
+        // Forward to UI if available
+        if let Some(ui_event_tx) = &self.ui_event_tx {
+            if let Err(e) = ui_event_tx.send(SessionEvent::MarketEvent(event.clone())) {
+                error!("Failed to send market event to UI: {}", e);
+            }
         }
 
         // Forward to metrics collector if available
@@ -1000,7 +2015,8 @@ impl SessionManager {
             let should_emit =
                 now.duration_since(self.metrics_last_emit) >= self.metrics_emit_interval;
             let metrics_snapshot = if should_emit {
-                Some(collector.get_connection_metrics(self.metrics_status.clone()))
+                let status = collector.overall_status();
+                Some(collector.get_connection_metrics(status))
             } else {
                 None
             };
@@ -1020,20 +2036,105 @@ impl SessionManager {
         Ok(())
     }
 
-    /// Evaluate alerts for a symbol and emit notifications
-    fn evaluate_alerts(&mut self, symbol: &str, price: f64) -> Result<()> {
+    /// Feed book updates and live prints to the paper engine
+    async fn match_paper_orders(&mut self, event: &crate::market_data::MarketEvent) {
+        let fills = match event {
+            crate::market_data::MarketEvent::OrderBookUpdate { symbol, orderbook } => {
+                match self.paper.as_mut() {
+                    Some(paper) => paper.on_book(symbol, orderbook, now_ms()),
+                    None => return,
+                }
+            }
+            crate::market_data::MarketEvent::Trade { symbol, trade } => {
+                let Some(paper) = self.paper.as_ref() else {
+                    return;
+                };
+                // Triggered stops walk the book
+                let book = if paper.has_open_orders(symbol) {
+                    self.market_manager.get_orderbook(symbol).await
+                } else {
+                    None
+                };
+                match self.paper.as_mut() {
+                    Some(paper) => paper.on_trade(symbol, trade, book.as_ref()),
+                    None => return,
+                }
+            }
+            _ => return,
+        };
+
+        if !fills.is_empty() {
+            self.report_paper_fills(&fills);
+            self.save_paper();
+        }
+    }
+
+    /// Evaluate alerts on one input for a symbol and emit notifications
+    async fn evaluate_alerts(&mut self, symbol: &str, input: AlertInput, value: f64) -> Result<()> {
         let normalized = symbol.to_ascii_uppercase();
-        let (triggers, state_changed) = self.alert_manager.evaluate_price(&normalized, price);
+        let (triggers, state_changed) = self.alert_manager.evaluate(&normalized, input, value);
+        self.dispatch_alert_triggers(&normalized, triggers, state_changed)
+            .await
+    }
+
+    /// Check price alerts against the range of a closed bar
+    async fn evaluate_bar_alerts(&mut self, symbol: &str, bar: &Bar) -> Result<()> {
+        let normalized = symbol.to_ascii_uppercase();
+        let (triggers, state_changed) = self.alert_manager.evaluate_bar(&normalized, bar);
+        self.dispatch_alert_triggers(&normalized, triggers, state_changed)
+            .await
+    }
+
+    /// Record, log and notify fired alerts
+    async fn dispatch_alert_triggers(
+        &mut self,
+        normalized: &str,
+        triggers: Vec<AlertTrigger>,
+        state_changed: bool,
+    ) -> Result<()> {
+        // Prefer the book ticker; the diff-depth book lags by up to 100ms
+        let top_of_book = if triggers.is_empty() {
+            None
+        } else if let Some(ticker) = self.market_manager.get_book_ticker(normalized).await {
+            Some((Some(ticker.bid_price), Some(ticker.ask_price)))
+        } else {
+            self.market_manager
+                .get_orderbook(normalized)
+                .await
+                .map(|book| (book.best_bid(), book.best_ask()))
+        };
+        let (best_bid, best_ask) = top_of_book.unwrap_or((None, None));
 
         for trigger in triggers {
+            metrics::counter!(
+                exporter::ALERT_TRIGGERS_TOTAL,
+                1,
+                "symbol" => trigger.symbol.clone()
+            );
+            let entry = AlertHistoryEntry::from_trigger(&trigger, best_bid, best_ask, now_ms());
+            self.alert_history.record(entry.clone());
+            self.forward_to_ui(SessionEvent::AlertHistoryRecorded { entry });
+
             let direction_str = match trigger.direction {
                 AlertDirection::Above => "above",
                 AlertDirection::Below => "below",
             };
-            let message = format!(
-                "Alert #{} triggered: {} {} {} (price {})",
-                trigger.id, trigger.symbol, direction_str, trigger.threshold, trigger.price
-            );
+            let message = match trigger.input {
+                AlertInput::Price => format!(
+                    "Alert #{} triggered: {} {} {} (price {})",
+                    trigger.id, trigger.symbol, direction_str, trigger.threshold, trigger.price
+                ),
+                input => format!(
+                    "Alert #{} triggered: {} {} {} {} ({} {:.4})",
+                    trigger.id,
+                    trigger.symbol,
+                    input.label(),
+                    direction_str,
+                    trigger.threshold,
+                    input.label(),
+                    trigger.price
+                ),
+            };
             self.emit_alert_notification(message);
             self.send_price_trigger_notification(&trigger);
         }
@@ -1071,6 +2172,57 @@ impl SessionManager {
 
         self.state = SessionState::ShuttingDown;
 
+        if let Some(task) = self.exchange_info_task.take() {
+            task.abort();
+        }
+
+        if let Some(task) = self.metrics_exporter_task.take() {
+            task.abort();
+        }
+
+        if let Some(task) = self.clock_sync_task.take() {
+            task.abort();
+        }
+
+        // Keep the latest marks for the next session
+        if let Some(paper) = &self.paper {
+            if let Err(e) = paper.save() {
+                error!("Failed to save paper account: {:#}", e);
+            }
+        }
+
+        // Keep the peak value for drawdown alerts
+        if let Some(portfolio) = &self.portfolio {
+            if let Err(e) = portfolio.save() {
+                error!("Failed to save portfolio: {:#}", e);
+            }
+        }
+
+        // Close the listen key so Binance does not keep the stream open
+        if let Some(stream) = self.account_stream.take() {
+            stream.stop();
+            let mut task = stream.task;
+            if tokio::time::timeout(Duration::from_secs(2), &mut task)
+                .await
+                .is_err()
+            {
+                warn!("Account stream did not stop in time");
+                task.abort();
+            }
+        }
+
+        if let Some(scanner) = self.scanner.take() {
+            scanner.stop();
+            let mut task = scanner.task;
+            if tokio::time::timeout(Duration::from_secs(2), &mut task)
+                .await
+                .is_err()
+            {
+                warn!("Scanner stream did not stop in time");
+                task.abort();
+            }
+        }
+
         // Notify UI to shutdown and wait for task completion
         if let Some(ui_event_tx) = self.ui_event_tx.take() {
             if let Err(e) = ui_event_tx.send(SessionEvent::ShutdownRequested) {
@@ -1131,3 +2283,364 @@ impl Drop for SessionManager {
         }
     }
 }
+
+/// One-line alert description shared by list and change notifications
+fn describe_alert(alert: &Alert) -> String {
+    let mode = match alert.repeat {
+        AlertRepeat::Once => "once".to_string(),
+        AlertRepeat::Repeat => {
+            if alert.cooldown_ms > 0 {
+                format!("repeat/{}s", alert.cooldown_ms / 1_000)
+            } else {
+                "repeat".to_string()
+            }
+        }
+    };
+    let cooldown = if alert.cooldown_ms > 0 {
+        format!("{}s", alert.cooldown_ms / 1_000)
+    } else {
+        "0".to_string()
+    };
+    let hysteresis = if alert.hysteresis > 0.0 {
+        format!("{:.4}", alert.hysteresis)
+    } else {
+        "0".to_string()
+    };
+    let input = match alert.input {
+        AlertInput::Price => String::new(),
+        input => format!(" {}", input.label()),
+    };
+    format!(
+        "#{} {}{} {:?} {} (mode={}, cooldown={}, hysteresis={})",
+        alert.id, alert.symbol, input, alert.direction, alert.threshold, mode, cooldown, hysteresis
+    )
+}
+
+/// One-line summary of a fired alert for headless output
+fn describe_history_entry(entry: &AlertHistoryEntry) -> String {
+    let book = match (entry.best_bid, entry.best_ask) {
+        (Some(bid), Some(ask)) => format!(" (bid {} / ask {})", bid, ask),
+        _ => String::new(),
+    };
+    format!(
+        "{} #{} {} {} @ {}{}",
+        entry.timestamp(),
+        entry.alert_id,
+        entry.symbol,
+        entry.condition(),
+        entry.price,
+        book
+    )
+}
+
+/// One-line paper order description, e.g. `#3 BUY 0.5 BTCUSDT limit 65000 (open)`
+fn describe_paper_order(order: &PaperOrder) -> String {
+    let mut description = format!(
+        "#{} {} {} {} {} ({}",
+        order.id,
+        order.side.label(),
+        order.quantity,
+        order.symbol,
+        order.order_type.describe(),
+        order.status.label()
+    );
+    if order.filled_qty > 0.0 {
+        description.push_str(&format!(
+            ", filled {} @ {:.8}",
+            order.filled_qty, order.avg_fill_price
+        ));
+    }
+    if let Some(note) = &order.note {
+        description.push_str(&format!(", {}", note));
+    }
+    description.push(')');
+    description
+}
+
+fn describe_paper_fill(fill: &PaperFill) -> String {
+    let mut description = format!(
+        "Paper fill #{}: {} {} {} @ {} ({}, fee {:.8} {})",
+        fill.order_id,
+        fill.side.label(),
+        fill.quantity,
+        fill.symbol,
+        fill.price,
+        fill.liquidity.label(),
+        fill.fee,
+        fill.quote_asset
+    );
+    if fill.realized_pnl != 0.0 {
+        description.push_str(&format!(
+            ", realized {:+.8} {}",
+            fill.realized_pnl, fill.quote_asset
+        ));
+    }
+    description
+}
+
+/// Balances, positions and open orders for headless output
+fn describe_paper_account(paper: &PaperEngine) -> Vec<String> {
+    let snapshot = paper.snapshot(0);
+    let mut lines = vec!["Paper balances:".to_string()];
+    for (asset, balance) in &snapshot.balances {
+        lines.push(format!(
+            "  {} free {:.8} locked {:.8}",
+            asset, balance.free, balance.locked
+        ));
+    }
+    lines.push("Paper positions:".to_string());
+    if snapshot.positions.is_empty() {
+        lines.push("  none".to_string());
+    }
+    for position in &snapshot.positions {
+        let unrealized = position
+            .mark_price
+            .map(|mark| format!("{:+.8}", position.unrealized_pnl(mark)))
+            .unwrap_or_else(|| "-".to_string());
+        lines.push(format!(
+            "  {} qty {} avg {:.8} realized {:+.8} unrealized {}",
+            position.symbol,
+            position.quantity,
+            position.avg_entry,
+            position.realized_pnl,
+            unrealized
+        ));
+    }
+    lines.push("Open paper orders:".to_string());
+    if snapshot.open_orders.is_empty() {
+        lines.push("  none".to_string());
+    }
+    for order in &snapshot.open_orders {
+        lines.push(format!("  {}", describe_paper_order(order)));
+    }
+    lines
+}
+
+/// Average fill, slippage and depth of a market order for headless output
+fn describe_impact(
+    symbol: &str,
+    impact: &MarketImpact,
+    exchange_info: &ExchangeInfoCache,
+) -> Vec<String> {
+    let (base, quote) = resolve_assets(symbol, exchange_info.get(symbol).as_ref())
+        .unwrap_or_else(|_| ("base".into(), "quote".into()));
+    let price = |price: f64| exchange_info.format_price(symbol, price);
+    let side = match impact.side {
+        TakerSide::Buy => "BUY",
+        TakerSide::Sell => "SELL",
+    };
+    let size = match impact.size {
+        ImpactSize::Base(quantity) => format!("{} {}", quantity, base),
+        ImpactSize::Quote(notional) => format!("{} {} of {}", notional, quote, base),
+    };
+    let bps = |value: Option<f64>| {
+        value
+            .map(|bps| format!(" ({:+.2} bps)", bps))
+            .unwrap_or_default()
+    };
+
+    let Some(average) = impact.average_price else {
+        return vec![format!(
+            "Market {} {} on {}: no liquidity on that side of the book",
+            side, size, symbol
+        )];
+    };
+    let mut lines = vec![
+        format!("Market {} {} on {}", side, size, symbol),
+        format!(
+            "  Average fill {}{}  worst {}{}  mid {}",
+            price(average),
+            bps(impact.slippage_bps()),
+            price(impact.worst_price.unwrap_or(average)),
+            bps(impact.worst_slippage_bps()),
+            impact
+                .mid_price
+                .map(price)
+                .unwrap_or_else(|| "-".to_string())
+        ),
+        format!(
+            "  Fills {} {} for {:.2} {} across {} levels",
+            impact.filled_quantity, base, impact.filled_notional, quote, impact.levels_consumed
+        ),
+    ];
+    if !impact.sufficient_depth() {
+        let unit = match impact.size {
+            ImpactSize::Base(_) => &base,
+            ImpactSize::Quote(_) => &quote,
+        };
+        lines.push(format!(
+            "  Not enough depth: {} {} left unfilled",
+            impact.unfilled, unit
+        ));
+    }
+    lines
+}
+
+/// Portfolio totals, holdings and alert thresholds for headless output
+fn describe_portfolio(snapshot: &PortfolioSnapshot) -> Vec<String> {
+    let valuation = &snapshot.valuation;
+    let currency = &valuation.currency;
+    let percent = |value: Option<f64>| {
+        value
+            .map(|pct| format!(" ({:+.2}%)", pct))
+            .unwrap_or_default()
+    };
+
+    let mut lines = vec![format!(
+        "Portfolio value {:.2} {}  PnL {:+.2}{}  24h {:+.2}{}",
+        valuation.total_value,
+        currency,
+        valuation.total_pnl(),
+        percent(valuation.pnl_percent()),
+        valuation.daily_change,
+        percent(valuation.daily_change_percent())
+    )];
+    if let (Some(drawdown), Some(peak)) = (snapshot.drawdown_percent(), snapshot.peak_value) {
+        lines.push(format!(
+            "Drawdown {:.2}% from peak {:.2} {}",
+            drawdown, peak, currency
+        ));
+    }
+    for entry in &valuation.holdings {
+        if let Some(error) = &entry.conversion_error {
+            lines.push(format!("Cannot value {}: {}", entry.holding.symbol, error));
+        }
+    }
+    if !valuation.complete {
+        lines.push("Waiting for prices; totals cover the holdings priced so far".to_string());
+    }
+
+    lines.push("Holdings:".to_string());
+    if valuation.holdings.is_empty() {
+        lines.push("  none".to_string());
+    }
+    for entry in &valuation.holdings {
+        let holding = &entry.holding;
+        let last = entry
+            .price
+            .map(|price| price.to_string())
+            .unwrap_or_else(|| "-".to_string());
+        let value = match (entry.value, entry.allocation_percent) {
+            (Some(value), Some(allocation)) => format!("{:.2} ({:.1}%)", value, allocation),
+            (Some(value), None) => format!("{:.2}", value),
+            _ => "-".to_string(),
+        };
+        let pnl = entry
+            .pnl()
+            .map(|pnl| format!("{:+.2}{}", pnl, percent(entry.pnl_percent())))
+            .unwrap_or_else(|| "-".to_string());
+        lines.push(format!(
+            "  {} qty {} cost {} last {} value {} PnL {}",
+            holding.symbol, holding.quantity, holding.cost_basis, last, value, pnl
+        ));
+    }
+
+    let alerts: Vec<String> = PortfolioAlertKind::ALL
+        .iter()
+        .filter_map(|kind| {
+            let threshold = snapshot.alerts.get(*kind)?;
+            Some(match kind {
+                PortfolioAlertKind::Drawdown => format!("{} {}%", kind.label(), threshold),
+                _ => format!("{} {}", kind.label(), threshold),
+            })
+        })
+        .collect();
+    lines.push(format!(
+        "Alerts: {}",
+        if alerts.is_empty() {
+            "none".to_string()
+        } else {
+            alerts.join(", ")
+        }
+    ));
+    lines
+}
+
+/// Wait for the next user data stream event; never resolves when account data is off
+async fn next_account_event(
+    receiver: &mut Option<mpsc::UnboundedReceiver<AccountEvent>>,
+) -> Option<AccountEvent> {
+    match receiver {
+        Some(receiver) => receiver.recv().await,
+        None => std::future::pending().await,
+    }
+}
+
+fn describe_execution(execution: &Execution) -> String {
+    match execution.execution_type.as_str() {
+        "TRADE" => format!(
+            "Order {} {} {} {} @ {} ({}, {} of {} filled)",
+            execution.order_id,
+            execution.side,
+            execution.last_qty,
+            execution.symbol,
+            execution.last_price,
+            execution.status.to_lowercase(),
+            execution.filled_qty,
+            execution.quantity
+        ),
+        "REJECTED" => format!(
+            "Order {} {} {} {} rejected: {}",
+            execution.order_id,
+            execution.side,
+            execution.order_type,
+            execution.symbol,
+            execution.reject_reason
+        ),
+        other => format!(
+            "Order {} {} {} {} {} @ {}: {}",
+            execution.order_id,
+            execution.side,
+            execution.order_type,
+            execution.quantity,
+            execution.symbol,
+            execution.price,
+            other.to_lowercase()
+        ),
+    }
+}
+
+fn describe_account(account: &AccountState) -> Vec<String> {
+    let snapshot = account.snapshot();
+    let mut lines = vec![format!("Account stream: {}", snapshot.status.describe())];
+
+    if snapshot.balances.is_empty() {
+        lines.push("Balances: none".to_string());
+    } else {
+        lines.push("Balances:".to_string());
+        for balance in &snapshot.balances {
+            lines.push(format!(
+                "  {:<8} free {:<16} locked {}",
+                balance.asset, balance.free, balance.locked
+            ));
+        }
+    }
+
+    if snapshot.open_orders.is_empty() {
+        lines.push("Open orders: none".to_string());
+    } else {
+        lines.push("Open orders:".to_string());
+        for order in &snapshot.open_orders {
+            lines.push(format!(
+                "  {} {} {} {} {} @ {} (filled {}, {})",
+                order.order_id,
+                order.symbol,
+                order.side,
+                order.order_type,
+                order.quantity,
+                order.price,
+                order.filled_qty,
+                order.status.to_lowercase()
+            ));
+        }
+    }
+
+    lines
+}
+
+fn now_ms() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
@@ -0,0 +1,585 @@
+//! Local history store for candles and trades
+//!
+//! Records are appended as NDJSON to segment files laid out as
+//! `<dir>/<SYMBOL>/candles_<interval>/<segment>.ndjson` and
+//! `<dir>/<SYMBOL>/trades/<segment>.ndjson`. Daily candles use one segment
+//! per UTC month, everything else one per UTC day, so retention only ever
+//! deletes whole files. Duplicate candles (a REST refresh of a bar already
+//! written live) are resolved on load, last write wins.
+
+use anyhow::{Context, Result};
+use chrono::{Datelike, NaiveDate, TimeZone, Utc};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use std::collections::HashMap;
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tracing::{debug, info, warn};
+
+use crate::market_data::{Bar, BarSpec, DailyCandle, TradePrint};
+
+/// Interval name daily exchange candles are stored under
+pub const DAILY_INTERVAL: &str = "1d";
+
+const FLUSH_INTERVAL: Duration = Duration::from_secs(1);
+const PRUNE_INTERVAL: Duration = Duration::from_secs(3_600);
+const DAY_MS: u64 = 86_400_000;
+
+/// How long each kind of data is kept; zero keeps it forever
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetentionPolicy {
+    pub daily_candle_days: u64,
+    pub bar_days: u64,
+    pub trade_days: u64,
+}
+
+/// Segment file store; writes are buffered until `flush`
+#[derive(Debug)]
+pub struct HistoryStore {
+    root: PathBuf,
+    retention: RetentionPolicy,
+    writers: HashMap<PathBuf, BufWriter<File>>,
+}
+
+impl HistoryStore {
+    pub fn new(root: impl Into<PathBuf>, retention: RetentionPolicy) -> Self {
+        Self {
+            root: root.into(),
+            retention,
+            writers: HashMap::new(),
+        }
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Append a closed candle or bar
+    pub fn append_candle(&mut self, symbol: &str, interval: &str, bar: &Bar) -> Result<()> {
+        let path = segment_path(
+            &candle_dir(&self.root, symbol, interval),
+            segment_name(interval, bar.open_time_ms),
+        );
+        self.append(path, bar)
+    }
+
+    pub fn append_trade(&mut self, symbol: &str, trade: &TradePrint) -> Result<()> {
+        let path = segment_path(
+            &trade_dir(&self.root, symbol),
+            segment_name("trades", trade.time_ms),
+        );
+        self.append(path, trade)
+    }
+
+    /// Write buffered records and close the segment files
+    pub fn flush(&mut self) -> Result<()> {
+        for (path, mut writer) in self.writers.drain() {
+            writer
+                .flush()
+                .with_context(|| format!("Failed to flush {}", path.display()))?;
+        }
+        Ok(())
+    }
+
+    /// Delete segments that fell out of retention, returning how many were removed
+    pub fn prune(&self, now_ms: u64) -> Result<usize> {
+        let symbols = match fs::read_dir(&self.root) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(0),
+            Err(e) => {
+                return Err(e).with_context(|| format!("Failed to read {}", self.root.display()));
+            }
+        };
+
+        let mut removed = 0;
+        for symbol_dir in symbols.flatten().filter(|entry| entry.path().is_dir()) {
+            for kind_dir in fs::read_dir(symbol_dir.path())?.flatten() {
+                let name = kind_dir.file_name().to_string_lossy().into_owned();
+                let days = match name.as_str() {
+                    "trades" => self.retention.trade_days,
+                    "candles_1d" => self.retention.daily_candle_days,
+                    other if other.starts_with("candles_") => self.retention.bar_days,
+                    _ => continue,
+                };
+                if days == 0 {
+                    continue;
+                }
+                let cutoff_ms = now_ms.saturating_sub(days * DAY_MS);
+                for segment in fs::read_dir(kind_dir.path())?.flatten() {
+                    let path = segment.path();
+                    let expired = path
+                        .file_stem()
+                        .and_then(|stem| segment_end_ms(&stem.to_string_lossy()))
+                        .is_some_and(|end_ms| end_ms <= cutoff_ms);
+                    if expired {
+                        fs::remove_file(&path)
+                            .with_context(|| format!("Failed to remove {}", path.display()))?;
+                        removed += 1;
+                    }
+                }
+            }
+        }
+        Ok(removed)
+    }
+
+    fn append<T: Serialize>(&mut self, path: PathBuf, record: &T) -> Result<()> {
+        let line = serde_json::to_string(record).context("Failed to serialize record")?;
+        let writer = match self.writers.entry(path) {
+            std::collections::hash_map::Entry::Occupied(entry) => entry.into_mut(),
+            std::collections::hash_map::Entry::Vacant(entry) => {
+                let path = entry.key();
+                if let Some(parent) = path.parent() {
+                    fs::create_dir_all(parent).with_context(|| {
+                        format!("Failed to create directory {}", parent.display())
+                    })?;
+                }
+                let file = OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(path)
+                    .with_context(|| format!("Failed to open {}", path.display()))?;
+                entry.insert(BufWriter::new(file))
+            }
+        };
+        writeln!(writer, "{}", line).context("Failed to append record")?;
+        Ok(())
+    }
+}
+
+/// Stored candles for one interval opened at or after `since_ms`, oldest first
+pub fn load_candles(root: &Path, symbol: &str, interval: &str, since_ms: u64) -> Result<Vec<Bar>> {
+    let mut bars: Vec<Bar> = read_records(&candle_dir(root, symbol, interval), since_ms)?
+        .into_iter()
+        .filter(|bar: &Bar| bar.open_time_ms >= since_ms)
+        .collect();
+    bars.sort_by_key(|bar| bar.open_time_ms);
+
+    // Time intervals have one bar per open time; volume and tick bars may share one
+    let time_based = interval == DAILY_INTERVAL
+        || BarSpec::parse(interval).is_ok_and(|spec| spec.is_time_based());
+    if time_based {
+        let mut deduped: Vec<Bar> = Vec::with_capacity(bars.len());
+        for bar in bars {
+            match deduped.last_mut() {
+                Some(last) if last.open_time_ms == bar.open_time_ms => *last = bar,
+                _ => deduped.push(bar),
+            }
+        }
+        bars = deduped;
+    }
+    Ok(bars)
+}
+
+/// Stored trades at or after `since_ms`, oldest first
+pub fn load_trades(root: &Path, symbol: &str, since_ms: u64) -> Result<Vec<TradePrint>> {
+    let mut trades: Vec<TradePrint> = read_records(&trade_dir(root, symbol), since_ms)?
+        .into_iter()
+        .filter(|trade: &TradePrint| trade.time_ms >= since_ms)
+        .collect();
+    trades.sort_by_key(|trade| trade.trade_id);
+    trades.dedup_by_key(|trade| trade.trade_id);
+    Ok(trades)
+}
+
+/// A stored bar as a daily candle
+pub fn bar_to_daily_candle(bar: &Bar) -> DailyCandle {
+    DailyCandle::new(
+        bar.open_time_ms,
+        bar.close_time_ms,
+        bar.open,
+        bar.high,
+        bar.low,
+        bar.close,
+        bar.volume,
+        bar.is_closed,
+    )
+}
+
+/// A daily candle in the stored bar layout; taker figures are unknown
+pub fn daily_candle_to_bar(candle: &DailyCandle) -> Bar {
+    Bar {
+        open_time_ms: candle.open_time_ms,
+        close_time_ms: candle.close_time_ms,
+        open: candle.open,
+        high: candle.high,
+        low: candle.low,
+        close: candle.close,
+        volume: candle.volume,
+        buy_volume: 0.0,
+        trades: 0,
+        is_closed: candle.is_closed,
+    }
+}
+
+enum HistoryRecord {
+    Candle {
+        symbol: String,
+        interval: String,
+        bar: Bar,
+    },
+    Trade {
+        symbol: String,
+        trade: TradePrint,
+    },
+}
+
+/// Cheap handle for recording into and reading from a store owned by a writer task
+#[derive(Debug, Clone)]
+pub struct HistoryHandle {
+    root: PathBuf,
+    record_trades: bool,
+    tx: mpsc::UnboundedSender<HistoryRecord>,
+}
+
+impl std::fmt::Debug for HistoryRecord {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HistoryRecord::Candle {
+                symbol, interval, ..
+            } => write!(f, "Candle({} {})", symbol, interval),
+            HistoryRecord::Trade { symbol, .. } => write!(f, "Trade({})", symbol),
+        }
+    }
+}
+
+impl HistoryHandle {
+    /// Move the store into a background task that flushes every second and prunes hourly.
+    /// The task exits, flushing first, once every handle is dropped.
+    pub fn spawn(mut store: HistoryStore, record_trades: bool) -> Self {
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let root = store.root().to_path_buf();
+
+        tokio::spawn(async move {
+            let mut flush = tokio::time::interval(FLUSH_INTERVAL);
+            let mut prune = tokio::time::interval(PRUNE_INTERVAL);
+            loop {
+                tokio::select! {
+                    record = rx.recv() => {
+                        let Some(record) = record else {
+                            break;
+                        };
+                        let result = match &record {
+                            HistoryRecord::Candle { symbol, interval, bar } => {
+                                store.append_candle(symbol, interval, bar)
+                            }
+                            HistoryRecord::Trade { symbol, trade } => {
+                                store.append_trade(symbol, trade)
+                            }
+                        };
+                        if let Err(e) = result {
+                            warn!("Failed to store {:?}: {}", record, e);
+                        }
+                    }
+                    _ = flush.tick() => {
+                        if let Err(e) = store.flush() {
+                            warn!("Failed to flush history store: {}", e);
+                        }
+                    }
+                    _ = prune.tick() => {
+                        match store.prune(now_ms()) {
+                            Ok(0) => {}
+                            Ok(removed) => info!("Pruned {} expired history segments", removed),
+                            Err(e) => warn!("Failed to prune history store: {}", e),
+                        }
+                    }
+                }
+            }
+            if let Err(e) = store.flush() {
+                warn!("Failed to flush history store on shutdown: {}", e);
+            }
+            debug!("History store writer stopped");
+        });
+
+        Self {
+            root,
+            record_trades,
+            tx,
+        }
+    }
+
+    pub fn record_candle(&self, symbol: &str, interval: &str, bar: &Bar) {
+        let _ = self.tx.send(HistoryRecord::Candle {
+            symbol: symbol.to_string(),
+            interval: interval.to_string(),
+            bar: *bar,
+        });
+    }
+
+    /// Record a trade when trade recording is enabled
+    pub fn record_trade(&self, symbol: &str, trade: &TradePrint) {
+        if self.record_trades {
+            let _ = self.tx.send(HistoryRecord::Trade {
+                symbol: symbol.to_string(),
+                trade: *trade,
+            });
+        }
+    }
+
+    /// Load stored candles on the blocking pool so segment reads do not stall the runtime
+    pub async fn load_candles(
+        &self,
+        symbol: &str,
+        interval: &str,
+        since_ms: u64,
+    ) -> Result<Vec<Bar>> {
+        let root = self.root.clone();
+        let (symbol, interval) = (symbol.to_string(), interval.to_string());
+        tokio::task::spawn_blocking(move || load_candles(&root, &symbol, &interval, since_ms))
+            .await
+            .context("Candle load task failed")?
+    }
+
+    /// Load stored trades on the blocking pool
+    pub async fn load_trades(&self, symbol: &str, since_ms: u64) -> Result<Vec<TradePrint>> {
+        let root = self.root.clone();
+        let symbol = symbol.to_string();
+        tokio::task::spawn_blocking(move || load_trades(&root, &symbol, since_ms))
+            .await
+            .context("Trade load task failed")?
+    }
+}
+
+fn candle_dir(root: &Path, symbol: &str, interval: &str) -> PathBuf {
+    root.join(symbol.to_uppercase())
+        .join(format!("candles_{}", interval))
+}
+
+fn trade_dir(root: &Path, symbol: &str) -> PathBuf {
+    root.join(symbol.to_uppercase()).join("trades")
+}
+
+fn segment_path(dir: &Path, name: String) -> PathBuf {
+    dir.join(format!("{}.ndjson", name))
+}
+
+/// `YYYY-MM` for daily candles, `YYYY-MM-DD` for everything else
+fn segment_name(interval: &str, time_ms: u64) -> String {
+    let datetime = Utc
+        .timestamp_millis_opt(time_ms as i64)
+        .single()
+        .unwrap_or_default();
+    if interval == DAILY_INTERVAL {
+        datetime.format("%Y-%m").to_string()
+    } else {
+        datetime.format("%Y-%m-%d").to_string()
+    }
+}
+
+/// First millisecond after the period a segment name covers
+fn segment_end_ms(name: &str) -> Option<u64> {
+    let next = if let Ok(day) = NaiveDate::parse_from_str(name, "%Y-%m-%d") {
+        day.succ_opt()?
+    } else {
+        let month = NaiveDate::parse_from_str(&format!("{}-01", name), "%Y-%m-%d").ok()?;
+        if month.month() == 12 {
+            NaiveDate::from_ymd_opt(month.year() + 1, 1, 1)?
+        } else {
+            NaiveDate::from_ymd_opt(month.year(), month.month() + 1, 1)?
+        }
+    };
+    let millis = next.and_hms_opt(0, 0, 0)?.and_utc().timestamp_millis();
+    u64::try_from(millis).ok()
+}
+
+/// Every record in a directory's segments that may hold times at or after
+/// `since_ms`; older segments are not opened and unreadable lines are skipped
+fn read_records<T: DeserializeOwned>(dir: &Path, since_ms: u64) -> Result<Vec<T>> {
+    let mut segments: Vec<PathBuf> = match fs::read_dir(dir) {
+        Ok(entries) => entries
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "ndjson"))
+            .filter(|path| {
+                path.file_stem()
+                    .and_then(|stem| segment_end_ms(&stem.to_string_lossy()))
+                    .map_or(true, |end_ms| end_ms > since_ms)
+            })
+            .collect(),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e).with_context(|| format!("Failed to read {}", dir.display())),
+    };
+    segments.sort();
+
+    let mut records = Vec::new();
+    let mut skipped = 0usize;
+    for path in segments {
+        let content = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        for line in content.lines().filter(|line| !line.trim().is_empty()) {
+            match serde_json::from_str(line) {
+                Ok(record) => records.push(record),
+                Err(_) => skipped += 1,
+            }
+        }
+    }
+    if skipped > 0 {
+        warn!(
+            "Skipped {} malformed history records in {}",
+            skipped,
+            dir.display()
+        );
+    }
+    Ok(records)
+}
+
+fn now_ms() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::market_data::TakerSide;
+    use tempfile::tempdir;
+
+    const RETENTION: RetentionPolicy = RetentionPolicy {
+        daily_candle_days: 0,
+        bar_days: 2,
+        trade_days: 1,
+    };
+
+    fn bar(open_time_ms: u64, close: f64) -> Bar {
+        Bar {
+            open_time_ms,
+            close_time_ms: open_time_ms + 4_999,
+            open: 100.0,
+            high: 101.0,
+            low: 99.0,
+            close,
+            volume: 1.0,
+            buy_volume: 0.4,
+            trades: 3,
+            is_closed: true,
+        }
+    }
+
+    fn trade(trade_id: u64, time_ms: u64) -> TradePrint {
+        TradePrint {
+            trade_id,
+            ..TradePrint::single_fill(time_ms, 100.0, 0.5, TakerSide::Sell)
+        }
+    }
+
+    #[test]
+    fn candles_round_trip_across_segments_and_dedupe() {
+        let dir = tempdir().unwrap();
+        let mut store = HistoryStore::new(dir.path(), RETENTION);
+
+        // Two UTC days, with a later rewrite of the first bar
+        store
+            .append_candle("btcusdt", "5s", &bar(0, 100.0))
+            .unwrap();
+        store
+            .append_candle("BTCUSDT", "5s", &bar(DAY_MS, 102.0))
+            .unwrap();
+        store
+            .append_candle("BTCUSDT", "5s", &bar(0, 100.5))
+            .unwrap();
+        store.flush().unwrap();
+
+        let bars = load_candles(dir.path(), "BTCUSDT", "5s", 0).unwrap();
+        assert_eq!(bars.len(), 2);
+        assert_eq!(bars[0].close, 100.5);
+        assert_eq!(bars[1].open_time_ms, DAY_MS);
+        assert!(
+            dir.path()
+                .join("BTCUSDT/candles_5s/1970-01-02.ndjson")
+                .exists()
+        );
+
+        let recent = load_candles(dir.path(), "BTCUSDT", "5s", 1).unwrap();
+        assert_eq!(recent.len(), 1);
+        assert!(
+            load_candles(dir.path(), "ETHUSDT", "5s", 0)
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn loads_skip_segments_that_end_before_since() {
+        let dir = tempdir().unwrap();
+        let mut store = HistoryStore::new(dir.path(), RETENTION);
+        store
+            .append_trade("BTCUSDT", &trade(7, DAY_MS + 5))
+            .unwrap();
+        store.flush().unwrap();
+        // An unreadable day-0 segment would fail the load if it were opened
+        fs::create_dir_all(dir.path().join("BTCUSDT/trades/1970-01-01.ndjson")).unwrap();
+
+        let trades = load_trades(dir.path(), "BTCUSDT", DAY_MS).unwrap();
+        assert_eq!(trades.len(), 1);
+        assert!(load_trades(dir.path(), "BTCUSDT", 0).is_err());
+    }
+
+    #[test]
+    fn trades_round_trip_without_duplicates() {
+        let dir = tempdir().unwrap();
+        let mut store = HistoryStore::new(dir.path(), RETENTION);
+        for (id, time) in [(2, 2_000), (1, 1_000), (2, 2_000)] {
+            store.append_trade("BTCUSDT", &trade(id, time)).unwrap();
+        }
+        store.flush().unwrap();
+
+        let trades = load_trades(dir.path(), "BTCUSDT", 0).unwrap();
+        assert_eq!(
+            trades.iter().map(|t| t.trade_id).collect::<Vec<_>>(),
+            vec![1, 2]
+        );
+        assert_eq!(trades[0].side, TakerSide::Sell);
+    }
+
+    #[test]
+    fn prune_applies_retention_per_data_type() {
+        let dir = tempdir().unwrap();
+        let mut store = HistoryStore::new(dir.path(), RETENTION);
+        store
+            .append_candle("BTCUSDT", "5s", &bar(0, 100.0))
+            .unwrap();
+        store
+            .append_candle("BTCUSDT", "5s", &bar(2 * DAY_MS, 100.0))
+            .unwrap();
+        store
+            .append_candle("BTCUSDT", DAILY_INTERVAL, &bar(0, 100.0))
+            .unwrap();
+        store
+            .append_trade("BTCUSDT", &trade(1, 2 * DAY_MS))
+            .unwrap();
+        store.flush().unwrap();
+
+        // Day 0 bars expire after two days; trades on day 2 survive a one-day policy
+        let removed = store.prune(3 * DAY_MS + 1).unwrap();
+        assert_eq!(removed, 1);
+        assert_eq!(
+            load_candles(dir.path(), "BTCUSDT", "5s", 0).unwrap().len(),
+            1
+        );
+        assert_eq!(load_trades(dir.path(), "BTCUSDT", 0).unwrap().len(), 1);
+        // Daily candles are kept forever
+        assert_eq!(
+            load_candles(dir.path(), "BTCUSDT", DAILY_INTERVAL, 0)
+                .unwrap()
+                .len(),
+            1
+        );
+
+        assert_eq!(store.prune(4 * DAY_MS).unwrap(), 1);
+        assert!(load_trades(dir.path(), "BTCUSDT", 0).unwrap().is_empty());
+    }
+
+    #[test]
+    fn segment_names_cover_days_and_months() {
+        assert_eq!(segment_name("5s", DAY_MS), "1970-01-02");
+        assert_eq!(segment_name(DAILY_INTERVAL, 40 * DAY_MS), "1970-02");
+        assert_eq!(segment_end_ms("1970-01-01"), Some(DAY_MS));
+        assert_eq!(segment_end_ms("1970-01"), Some(31 * DAY_MS));
+        assert_eq!(segment_end_ms("1970-12"), Some(365 * DAY_MS));
+        assert_eq!(segment_end_ms("notes"), None);
+    }
+}
@@ -0,0 +1,523 @@
+//! Order entry against the Binance spot account
+//!
+//! Orders go through two steps. [`validate_order`] checks an order against the
+//! symbol's exchange filters (tick size, lot size, minimum notional) and the
+//! notional cap configured for its quote asset, producing a [`ValidatedOrder`]
+//! with prices and quantities already formatted at the symbol's precision. The
+//! session keeps it as a [`PendingOrder`] until the user confirms, and only
+//! then sends it. In test mode orders go to `/api/v3/order/test`, where Binance
+//! checks them without placing anything.
+
+use std::collections::BTreeMap;
+use std::time::{Duration, Instant};
+
+use crate::binance::SymbolInfo;
+use crate::binance::types::OrderBook;
+use crate::market_data::{ImpactSize, TakerSide};
+use crate::paper::{OrderRequest, OrderSide, OrderType};
+
+/// How long a staged order waits for confirmation before it must be re-entered
+pub const CONFIRM_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Prefix of the client order ids this application assigns
+pub const CLIENT_ORDER_PREFIX: &str = "xtrade";
+
+/// Relative tolerance when checking that a value sits on a filter increment
+const INCREMENT_EPSILON: f64 = 1e-9;
+
+/// Why an order failed pre-trade validation
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum OrderRejection {
+    #[error("Trading rules for {0} are not loaded yet; try again shortly")]
+    UnknownSymbol(String),
+    #[error("{symbol} is not trading ({status})")]
+    NotTrading { symbol: String, status: String },
+    #[error(
+        "Price {price} is not a multiple of the {tick} tick size; nearest valid price is {suggested}"
+    )]
+    TickSize {
+        price: f64,
+        tick: f64,
+        suggested: String,
+    },
+    #[error("Price {price} is outside the allowed range {min} to {max}")]
+    PriceRange { price: f64, min: f64, max: f64 },
+    #[error(
+        "Quantity {quantity} is not a multiple of the {step} lot size; nearest valid quantity is {suggested}"
+    )]
+    LotSize {
+        quantity: f64,
+        step: f64,
+        suggested: String,
+    },
+    #[error("Quantity {quantity} is outside the allowed range {min} to {max}")]
+    QuantityRange { quantity: f64, min: f64, max: f64 },
+    #[error("Order value {notional:.8} {quote} is below the exchange minimum of {min} {quote}")]
+    MinNotional {
+        notional: f64,
+        min: f64,
+        quote: String,
+    },
+    #[error("Order value {notional:.8} {quote} exceeds orders.max_notional of {max} {quote}")]
+    MaxNotional {
+        notional: f64,
+        max: f64,
+        quote: String,
+    },
+    #[error("No orders.max_notional cap is set for {0}; add one to trade {0} pairs")]
+    NoNotionalCap(String),
+    #[error("No order book for {0} to value a market order; subscribe first with /add {0}")]
+    NoOrderBook(String),
+    #[error(
+        "The visible {symbol} book only fills {available} of {quantity}; reduce the quantity or use a limit order"
+    )]
+    InsufficientDepth {
+        symbol: String,
+        quantity: f64,
+        available: f64,
+    },
+}
+
+/// An order that passed validation, with values formatted for the REST API
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValidatedOrder {
+    pub request: OrderRequest,
+    pub quote_asset: String,
+    /// Quantity at the lot size precision
+    pub quantity: String,
+    /// Limit price at the tick precision
+    pub price: Option<String>,
+    /// Stop trigger at the tick precision
+    pub stop_price: Option<String>,
+    /// Estimated value in the quote asset
+    pub notional: f64,
+}
+
+impl ValidatedOrder {
+    /// Binance order type for the request
+    pub fn order_type(&self) -> &'static str {
+        match self.request.order_type {
+            OrderType::Market => "MARKET",
+            OrderType::Limit { .. } => "LIMIT",
+            OrderType::Stop { .. } => "STOP_LOSS",
+        }
+    }
+
+    /// Parameters for `POST /api/v3/order`, without the signature fields
+    pub fn params(&self, client_order_id: &str) -> Vec<(&'static str, String)> {
+        let mut params = vec![
+            ("symbol", self.request.symbol.clone()),
+            ("side", self.request.side.label().to_string()),
+            ("type", self.order_type().to_string()),
+            ("quantity", self.quantity.clone()),
+        ];
+        if let Some(price) = &self.price {
+            params.push(("timeInForce", "GTC".to_string()));
+            params.push(("price", price.clone()));
+        }
+        if let Some(stop_price) = &self.stop_price {
+            params.push(("stopPrice", stop_price.clone()));
+        }
+        params.push(("newClientOrderId", client_order_id.to_string()));
+        params
+    }
+
+    /// One-line summary such as `BUY 0.01 BTCUSDT LIMIT @ 65000.00 (≈650.00 USDT)`
+    pub fn describe(&self) -> String {
+        let mut description = format!(
+            "{} {} {} {}",
+            self.request.side.label(),
+            self.quantity,
+            self.request.symbol,
+            self.order_type()
+        );
+        if let Some(price) = &self.price {
+            description.push_str(&format!(" @ {}", price));
+        }
+        if let Some(stop_price) = &self.stop_price {
+            description.push_str(&format!(" stop {}", stop_price));
+        }
+        description.push_str(&format!(" (≈{:.2} {})", self.notional, self.quote_asset));
+        description
+    }
+}
+
+/// What a confirmed pending order does
+#[derive(Debug, Clone, PartialEq)]
+pub enum PendingAction {
+    /// Place a new order
+    New,
+    /// Cancel `order_id` and place the order in its place
+    Replace { order_id: u64 },
+}
+
+/// An order waiting for the user's confirmation
+#[derive(Debug, Clone)]
+pub struct PendingOrder {
+    pub action: PendingAction,
+    pub order: ValidatedOrder,
+    pub staged_at: Instant,
+}
+
+impl PendingOrder {
+    pub fn new(action: PendingAction, order: ValidatedOrder) -> Self {
+        Self {
+            action,
+            order,
+            staged_at: Instant::now(),
+        }
+    }
+
+    /// Whether the confirmation window has passed
+    pub fn is_expired(&self) -> bool {
+        self.staged_at.elapsed() > CONFIRM_TIMEOUT
+    }
+
+    /// Summary shown when asking for confirmation
+    pub fn describe(&self, test_mode: bool) -> String {
+        let mode = if test_mode { " [test]" } else { "" };
+        match self.action {
+            PendingAction::New => format!("{}{}", self.order.describe(), mode),
+            PendingAction::Replace { order_id } => {
+                format!(
+                    "Replace #{} with {}{}",
+                    order_id,
+                    self.order.describe(),
+                    mode
+                )
+            }
+        }
+    }
+}
+
+/// Check an order against the symbol's exchange filters and the notional cap
+///
+/// Market orders are valued by walking `book` level by level for the full
+/// quantity, so an order that sweeps several levels is valued at what it would
+/// pay rather than at the top of book. Limit and stop orders are valued at
+/// their own price. `max_notional` holds the cap per quote asset; orders on a
+/// quote asset without a cap are rejected.
+pub fn validate_order(
+    request: &OrderRequest,
+    info: Option<&SymbolInfo>,
+    book: Option<&OrderBook>,
+    max_notional: &BTreeMap<String, f64>,
+) -> Result<ValidatedOrder, OrderRejection> {
+    let info = info.ok_or_else(|| OrderRejection::UnknownSymbol(request.symbol.clone()))?;
+    if !info.status.is_trading() {
+        return Err(OrderRejection::NotTrading {
+            symbol: request.symbol.clone(),
+            status: info.status.to_string(),
+        });
+    }
+
+    let quantity = request.quantity;
+    if let Some(step) = info.step_size {
+        if !on_increment(quantity, step) {
+            return Err(OrderRejection::LotSize {
+                quantity,
+                step,
+                suggested: format!(
+                    "{:.*}",
+                    info.quantity_decimals(),
+                    info.round_quantity_to_step(quantity)
+                ),
+            });
+        }
+    }
+    let min_qty = info.min_qty.unwrap_or(0.0);
+    let max_qty = info.max_qty.unwrap_or(f64::INFINITY);
+    if quantity < min_qty || quantity > max_qty {
+        return Err(OrderRejection::QuantityRange {
+            quantity,
+            min: min_qty,
+            max: max_qty,
+        });
+    }
+
+    let (price, stop_price) = match request.order_type {
+        OrderType::Market => (None, None),
+        OrderType::Limit { price } => (Some(check_price(info, price)?), None),
+        OrderType::Stop { trigger } => (None, Some(check_price(info, trigger)?)),
+    };
+
+    let notional = match request.order_type {
+        OrderType::Market => market_notional(request, book)?,
+        OrderType::Limit { price } => quantity * price,
+        OrderType::Stop { trigger } => quantity * trigger,
+    };
+    if let Some(min) = info.min_notional.filter(|min| notional < *min) {
+        return Err(OrderRejection::MinNotional {
+            notional,
+            min,
+            quote: info.quote_asset.clone(),
+        });
+    }
+    let max = *max_notional
+        .get(&info.quote_asset)
+        .ok_or_else(|| OrderRejection::NoNotionalCap(info.quote_asset.clone()))?;
+    if notional > max {
+        return Err(OrderRejection::MaxNotional {
+            notional,
+            max,
+            quote: info.quote_asset.clone(),
+        });
+    }
+
+    Ok(ValidatedOrder {
+        request: request.clone(),
+        quote_asset: info.quote_asset.clone(),
+        quantity: format!("{:.*}", info.quantity_decimals(), quantity),
+        price,
+        stop_price,
+        notional,
+    })
+}
+
+/// Client order id unique within this process, e.g. `xtrade-1718000000000-3`
+pub fn client_order_id(now_ms: u64, sequence: u64) -> String {
+    format!("{}-{}-{}", CLIENT_ORDER_PREFIX, now_ms, sequence)
+}
+
+/// Quote value of a market order filled against the visible book
+fn market_notional(
+    request: &OrderRequest,
+    book: Option<&OrderBook>,
+) -> Result<f64, OrderRejection> {
+    let book = book.ok_or_else(|| OrderRejection::NoOrderBook(request.symbol.clone()))?;
+    let side = match request.side {
+        OrderSide::Buy => TakerSide::Buy,
+        OrderSide::Sell => TakerSide::Sell,
+    };
+    let impact = book.market_impact(side, ImpactSize::Base(request.quantity));
+    if impact.filled_quantity <= 0.0 {
+        return Err(OrderRejection::NoOrderBook(request.symbol.clone()));
+    }
+    if !impact.sufficient_depth() {
+        return Err(OrderRejection::InsufficientDepth {
+            symbol: request.symbol.clone(),
+            quantity: request.quantity,
+            available: impact.filled_quantity,
+        });
+    }
+    Ok(impact.filled_notional)
+}
+
+fn check_price(info: &SymbolInfo, price: f64) -> Result<String, OrderRejection> {
+    if let Some(tick) = info.tick_size {
+        if !on_increment(price, tick) {
+            return Err(OrderRejection::TickSize {
+                price,
+                tick,
+                suggested: info.format_price(info.round_price_to_tick(price)),
+            });
+        }
+    }
+    let min = info.min_price.unwrap_or(0.0);
+    let max = info.max_price.unwrap_or(f64::INFINITY);
+    if price < min || price > max {
+        return Err(OrderRejection::PriceRange { price, min, max });
+    }
+    Ok(info.format_price(price))
+}
+
+/// Whether `value` is a whole number of `increment`s, allowing for float noise
+fn on_increment(value: f64, increment: f64) -> bool {
+    let steps = value / increment;
+    (steps - steps.round()).abs() <= INCREMENT_EPSILON * steps.abs().max(1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::binance::SymbolStatus;
+
+    fn btcusdt() -> SymbolInfo {
+        SymbolInfo {
+            symbol: "BTCUSDT".to_string(),
+            base_asset: "BTC".to_string(),
+            quote_asset: "USDT".to_string(),
+            status: SymbolStatus::Trading,
+            tick_size: Some(0.01),
+            min_price: Some(0.01),
+            max_price: Some(1_000_000.0),
+            step_size: Some(0.00001),
+            min_qty: Some(0.00001),
+            max_qty: Some(9_000.0),
+            min_notional: Some(5.0),
+        }
+    }
+
+    fn caps() -> BTreeMap<String, f64> {
+        BTreeMap::from([("USDT".to_string(), 1_000.0)])
+    }
+
+    fn request(order_type: OrderType, quantity: f64) -> OrderRequest {
+        OrderRequest {
+            symbol: "BTCUSDT".to_string(),
+            side: OrderSide::Buy,
+            order_type,
+            quantity,
+        }
+    }
+
+    #[test]
+    fn accepts_orders_on_the_filter_increments() {
+        let order = validate_order(
+            &request(OrderType::Limit { price: 65_000.1 }, 0.0015),
+            Some(&btcusdt()),
+            None,
+            &caps(),
+        )
+        .unwrap();
+
+        assert_eq!(order.quantity, "0.00150");
+        assert_eq!(order.price.as_deref(), Some("65000.10"));
+        assert!((order.notional - 97.50015).abs() < 1e-9);
+        let params = order.params("xtrade-1-1");
+        assert!(params.contains(&("type", "LIMIT".to_string())));
+        assert!(params.contains(&("timeInForce", "GTC".to_string())));
+    }
+
+    #[test]
+    fn rejects_prices_and_quantities_off_the_increments() {
+        let info = btcusdt();
+        let err = validate_order(
+            &request(OrderType::Limit { price: 65_000.123 }, 0.001),
+            Some(&info),
+            None,
+            &caps(),
+        )
+        .unwrap_err();
+        assert!(
+            matches!(err, OrderRejection::TickSize { ref suggested, .. } if suggested == "65000.12")
+        );
+
+        let err = validate_order(
+            &request(OrderType::Market, 0.000015),
+            Some(&info),
+            Some(&OrderBook::from_levels("BTCUSDT", &[], &[(65_000.0, 1.0)])),
+            &caps(),
+        )
+        .unwrap_err();
+        assert!(matches!(err, OrderRejection::LotSize { .. }));
+    }
+
+    #[test]
+    fn enforces_minimum_and_configured_maximum_notional() {
+        let info = btcusdt();
+        let err = validate_order(
+            &request(OrderType::Market, 0.00005),
+            Some(&info),
+            Some(&OrderBook::from_levels("BTCUSDT", &[], &[(65_000.0, 1.0)])),
+            &caps(),
+        )
+        .unwrap_err();
+        assert!(matches!(err, OrderRejection::MinNotional { .. }));
+
+        let err = validate_order(
+            &request(OrderType::Market, 0.1),
+            Some(&info),
+            Some(&OrderBook::from_levels("BTCUSDT", &[], &[(65_000.0, 1.0)])),
+            &caps(),
+        )
+        .unwrap_err();
+        assert!(matches!(err, OrderRejection::MaxNotional { .. }));
+
+        let err = validate_order(
+            &request(OrderType::Market, 0.001),
+            Some(&info),
+            None,
+            &caps(),
+        )
+        .unwrap_err();
+        assert!(matches!(err, OrderRejection::NoOrderBook(_)));
+    }
+
+    #[test]
+    fn values_market_orders_by_walking_the_book() {
+        let info = btcusdt();
+        // 0.01 at the top level would be 650, but the order sweeps into 70000
+        let book = OrderBook::from_levels(
+            "BTCUSDT",
+            &[],
+            &[(65_000.0, 0.005), (70_000.0, 0.01), (200_000.0, 1.0)],
+        );
+        let order = validate_order(
+            &request(OrderType::Market, 0.01),
+            Some(&info),
+            Some(&book),
+            &caps(),
+        )
+        .unwrap();
+        assert!((order.notional - 675.0).abs() < 1e-9);
+
+        // 975 at the top level, 1025 once the book is walked
+
+        let err = validate_order(
+            &request(OrderType::Market, 0.015),
+            Some(&info),
+            Some(&book),
+            &caps(),
+        )
+        .unwrap_err();
+        assert!(matches!(err, OrderRejection::MaxNotional { .. }));
+
+        let err = validate_order(
+            &request(OrderType::Market, 0.01),
+            Some(&info),
+            Some(&OrderBook::from_levels(
+                "BTCUSDT",
+                &[],
+                &[(65_000.0, 0.005)],
+            )),
+            &caps(),
+        )
+        .unwrap_err();
+        assert!(matches!(err, OrderRejection::InsufficientDepth { .. }));
+    }
+
+    #[test]
+    fn caps_notional_per_quote_asset() {
+        let mut info = btcusdt();
+        info.symbol = "ETHBTC".to_string();
+        info.quote_asset = "BTC".to_string();
+        info.min_notional = None;
+        let err = validate_order(
+            &request(OrderType::Limit { price: 0.05 }, 1.0),
+            Some(&info),
+            None,
+            &caps(),
+        )
+        .unwrap_err();
+        assert_eq!(err, OrderRejection::NoNotionalCap("BTC".to_string()));
+
+        let caps = BTreeMap::from([("BTC".to_string(), 0.01)]);
+        let err = validate_order(
+            &request(OrderType::Limit { price: 0.05 }, 1.0),
+            Some(&info),
+            None,
+            &caps,
+        )
+        .unwrap_err();
+        assert!(matches!(err, OrderRejection::MaxNotional { max, .. } if max == 0.01));
+    }
+
+    #[test]
+    fn requires_loaded_trading_rules() {
+        let err =
+            validate_order(&request(OrderType::Market, 0.001), None, None, &caps()).unwrap_err();
+        assert_eq!(err, OrderRejection::UnknownSymbol("BTCUSDT".to_string()));
+
+        let mut info = btcusdt();
+        info.status = SymbolStatus::Halt;
+        let err = validate_order(
+            &request(OrderType::Limit { price: 1.0 }, 10.0),
+            Some(&info),
+            None,
+            &caps(),
+        )
+        .unwrap_err();
+        assert!(matches!(err, OrderRejection::NotTrading { .. }));
+    }
+}
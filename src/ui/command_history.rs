@@ -0,0 +1,271 @@
+//! Command palette history with recall, reverse search and file persistence
+
+use std::collections::VecDeque;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use tracing::warn;
+
+/// Default number of commands retained in history
+pub const DEFAULT_HISTORY_SIZE: usize = 500;
+
+/// Submitted commands, oldest first, with an optional backing file
+#[derive(Debug, Clone)]
+pub struct CommandHistory {
+    entries: VecDeque<String>,
+    max_entries: usize,
+    path: Option<PathBuf>,
+    /// Index into `entries` while browsing with Up/Down
+    cursor: Option<usize>,
+    /// Buffer contents before browsing started, restored when stepping past the newest entry
+    draft: String,
+}
+
+/// Active Ctrl-R reverse search
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HistorySearch {
+    pub query: String,
+    /// Index of the current match in history entries
+    pub match_index: Option<usize>,
+    /// Buffer contents when the search started, restored on cancel
+    pub original: String,
+}
+
+impl Default for CommandHistory {
+    fn default() -> Self {
+        Self::new(DEFAULT_HISTORY_SIZE)
+    }
+}
+
+impl CommandHistory {
+    /// Create an in-memory history
+    pub fn new(max_entries: usize) -> Self {
+        Self {
+            entries: VecDeque::new(),
+            max_entries: max_entries.max(1),
+            path: None,
+            cursor: None,
+            draft: String::new(),
+        }
+    }
+
+    /// Load history from a file, keeping the newest `max_entries` lines.
+    /// A missing file yields an empty history that will be created on first push.
+    pub fn load(path: impl AsRef<Path>, max_entries: usize) -> Self {
+        let path = path.as_ref().to_path_buf();
+        let mut history = Self::new(max_entries);
+
+        match fs::read_to_string(&path) {
+            Ok(content) => {
+                for line in content.lines() {
+                    history.record(line);
+                }
+                history.path = Some(path);
+                if content.lines().count() > history.entries.len() {
+                    // Compact the file so it does not grow without bound
+                    history.rewrite();
+                }
+            }
+            Err(e) => {
+                if e.kind() != std::io::ErrorKind::NotFound {
+                    warn!("Failed to read command history {}: {}", path.display(), e);
+                }
+                history.path = Some(path);
+            }
+        }
+
+        history
+    }
+
+    /// Commands oldest first
+    pub fn entries(&self) -> impl Iterator<Item = &str> {
+        self.entries.iter().map(String::as_str)
+    }
+
+    /// Number of stored commands
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether the history is empty
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Whether Up/Down browsing is in progress
+    pub fn is_browsing(&self) -> bool {
+        self.cursor.is_some()
+    }
+
+    /// Record a submitted command and append it to the history file
+    pub fn push(&mut self, command: &str) {
+        self.reset_browse();
+        if self.record(command) {
+            self.append_to_file(command.trim());
+        }
+    }
+
+    /// Step to an older entry, returning the text to show
+    pub fn older(&mut self, current: &str) -> Option<&str> {
+        if self.entries.is_empty() {
+            return None;
+        }
+
+        let next = match self.cursor {
+            None => {
+                self.draft = current.to_string();
+                self.entries.len() - 1
+            }
+            Some(0) => 0,
+            Some(idx) => idx - 1,
+        };
+        self.cursor = Some(next);
+        self.entries.get(next).map(String::as_str)
+    }
+
+    /// Step to a newer entry; past the newest entry the original draft is restored
+    pub fn newer(&mut self) -> Option<String> {
+        let idx = self.cursor?;
+        if idx + 1 < self.entries.len() {
+            self.cursor = Some(idx + 1);
+            self.entries.get(idx + 1).cloned()
+        } else {
+            self.cursor = None;
+            Some(std::mem::take(&mut self.draft))
+        }
+    }
+
+    /// Stop browsing without changing the buffer
+    pub fn reset_browse(&mut self) {
+        self.cursor = None;
+        self.draft.clear();
+    }
+
+    /// Find the newest entry containing `query` strictly older than `before`
+    pub fn search_backward(&self, query: &str, before: Option<usize>) -> Option<usize> {
+        let end = before.unwrap_or(self.entries.len()).min(self.entries.len());
+        if query.is_empty() {
+            return None;
+        }
+        (0..end)
+            .rev()
+            .find(|&idx| self.entries[idx].contains(query))
+    }
+
+    /// Entry at an index
+    pub fn get(&self, index: usize) -> Option<&str> {
+        self.entries.get(index).map(String::as_str)
+    }
+
+    fn record(&mut self, command: &str) -> bool {
+        let command = command.trim();
+        if command.is_empty() {
+            return false;
+        }
+        if self.entries.back().is_some_and(|last| last == command) {
+            return false;
+        }
+
+        self.entries.push_back(command.to_string());
+        while self.entries.len() > self.max_entries {
+            self.entries.pop_front();
+        }
+        true
+    }
+
+    fn append_to_file(&self, command: &str) {
+        let Some(path) = &self.path else {
+            return;
+        };
+
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            if let Err(e) = fs::create_dir_all(parent) {
+                warn!(
+                    "Failed to create history directory {}: {}",
+                    parent.display(),
+                    e
+                );
+                return;
+            }
+        }
+
+        let result = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .and_then(|mut file| writeln!(file, "{}", command));
+        if let Err(e) = result {
+            warn!("Failed to write command history {}: {}", path.display(), e);
+        }
+    }
+
+    fn rewrite(&self) {
+        let Some(path) = &self.path else {
+            return;
+        };
+
+        let mut content = self.entries.iter().cloned().collect::<Vec<_>>().join("\n");
+        content.push('\n');
+        if let Err(e) = fs::write(path, content) {
+            warn!(
+                "Failed to compact command history {}: {}",
+                path.display(),
+                e
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn browsing_restores_draft() {
+        let mut history = CommandHistory::new(10);
+        history.push("/add BTCUSDT");
+        history.push("/show BTCUSDT");
+        history.push("/show BTCUSDT");
+        assert_eq!(history.len(), 2);
+
+        assert_eq!(history.older("/rem"), Some("/show BTCUSDT"));
+        assert_eq!(history.older("ignored"), Some("/add BTCUSDT"));
+        assert_eq!(history.older("ignored"), Some("/add BTCUSDT"));
+        assert_eq!(history.newer().as_deref(), Some("/show BTCUSDT"));
+        assert_eq!(history.newer().as_deref(), Some("/rem"));
+        assert!(!history.is_browsing());
+    }
+
+    #[test]
+    fn reverse_search_walks_older_matches() {
+        let mut history = CommandHistory::new(10);
+        history.push("/add BTCUSDT ETHUSDT");
+        history.push("/status");
+        history.push("/add SOLUSDT");
+
+        let first = history.search_backward("/add", None).unwrap();
+        assert_eq!(history.get(first), Some("/add SOLUSDT"));
+        let second = history.search_backward("/add", Some(first)).unwrap();
+        assert_eq!(history.get(second), Some("/add BTCUSDT ETHUSDT"));
+        assert!(history.search_backward("/add", Some(second)).is_none());
+    }
+
+    #[test]
+    fn persists_and_trims_history_file() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("nested").join("history");
+
+        let mut history = CommandHistory::load(&path, 2);
+        history.push("/add BTCUSDT");
+        history.push("/status");
+        history.push("/logs");
+
+        let reloaded = CommandHistory::load(&path, 2);
+        let entries: Vec<&str> = reloaded.entries().collect();
+        assert_eq!(entries, vec!["/status", "/logs"]);
+
+        let content = fs::read_to_string(&path).unwrap();
+        assert_eq!(content.lines().count(), 2);
+    }
+}
@@ -0,0 +1,344 @@
+//! Argument completion for the command palette
+//!
+//! Resolves which kind of argument a command expects and ranks candidates
+//! (exchange symbols, watched symbols, alert ids) against the partial token.
+
+use crate::binance::SymbolInfo;
+use crate::session::alert_manager::{Alert, AlertDirection};
+
+/// Maximum number of argument suggestions shown in the palette
+pub const MAX_ARGUMENT_SUGGESTIONS: usize = 20;
+
+/// Quote assets ranked first when several pairs share a base asset
+const QUOTE_PRIORITY: [&str; 6] = ["USDT", "USDC", "FDUSD", "BTC", "ETH", "BNB"];
+
+/// Kind of argument a command position accepts
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArgumentKind {
+    /// Any tradable symbol from exchange info
+    ExchangeSymbol,
+    /// A symbol currently on the watch list
+    WatchedSymbol,
+    /// An alert id (or `all`)
+    AlertId,
+}
+
+/// A single argument completion candidate
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArgumentSuggestion {
+    pub value: String,
+    pub detail: String,
+}
+
+/// Partially typed command split into trigger, completed args and the token under the cursor
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArgumentContext<'a> {
+    pub trigger: &'a str,
+    pub position: usize,
+    pub previous: Vec<&'a str>,
+    pub token: &'a str,
+}
+
+impl<'a> ArgumentContext<'a> {
+    /// Parse the command buffer. Returns `None` while the trigger itself is still being typed.
+    pub fn parse(buffer: &'a str) -> Option<Self> {
+        let trimmed = buffer.trim_start();
+        let (trigger, rest) = trimmed.split_once(char::is_whitespace)?;
+
+        let mut previous: Vec<&str> = rest.split_whitespace().collect();
+        let token = if rest.ends_with(char::is_whitespace) || rest.is_empty() {
+            ""
+        } else {
+            previous.pop().unwrap_or("")
+        };
+
+        Some(Self {
+            trigger,
+            position: previous.len(),
+            previous,
+            token,
+        })
+    }
+
+    /// Argument kind expected at the current position, if the command takes completable args
+    pub fn kind(&self) -> Option<ArgumentKind> {
+        argument_kind(self.trigger, self.position)
+    }
+}
+
+/// Argument kind for a command trigger at the given argument position
+pub fn argument_kind(trigger: &str, position: usize) -> Option<ArgumentKind> {
+    match (trigger, position) {
+        ("/add", _) => Some(ArgumentKind::ExchangeSymbol),
+        ("/remove", _) => Some(ArgumentKind::WatchedSymbol),
+        ("/show", 0) => Some(ArgumentKind::WatchedSymbol),
+        ("/alert:add", 0) => Some(ArgumentKind::ExchangeSymbol),
+        ("/paper:buy" | "/paper:sell", 0) => Some(ArgumentKind::WatchedSymbol),
+        ("/position:add" | "/position:remove", 0) => Some(ArgumentKind::ExchangeSymbol),
+        ("/impact", 0) => Some(ArgumentKind::WatchedSymbol),
+        ("/alert:clear", 0) => Some(ArgumentKind::AlertId),
+        ("/alert:history" | "/alert:export", 0) => Some(ArgumentKind::WatchedSymbol),
+        ("/alert:edit" | "/alert:pause" | "/alert:resume" | "/alert:snooze", 0) => {
+            Some(ArgumentKind::AlertId)
+        }
+        _ => None,
+    }
+}
+
+/// Rank exchange symbols against a query such as `sol`, `sol/usdt` or `/btc`
+pub fn complete_exchange_symbols(
+    symbols: &[SymbolInfo],
+    watched: &[String],
+    query: &str,
+    exclude: &[&str],
+) -> Vec<ArgumentSuggestion> {
+    let query = query.trim().to_ascii_uppercase();
+    if query.is_empty() {
+        return Vec::new();
+    }
+
+    let (base_query, quote_query) = match query.split_once('/') {
+        Some((base, quote)) => (base, Some(quote)),
+        None => (query.as_str(), None),
+    };
+
+    let mut scored: Vec<(i64, &SymbolInfo)> = symbols
+        .iter()
+        .filter(|info| info.status.is_trading())
+        .filter(|info| {
+            !exclude
+                .iter()
+                .any(|ex| ex.eq_ignore_ascii_case(&info.symbol))
+        })
+        .filter(|info| quote_query.map_or(true, |quote| info.quote_asset.starts_with(quote)))
+        .filter_map(|info| {
+            let mut score = if base_query.is_empty() {
+                0
+            } else {
+                symbol_score(&info.symbol, &info.base_asset, base_query)?
+            };
+            score += quote_bonus(&info.quote_asset);
+            if watched.iter().any(|w| w == &info.symbol) {
+                score += 5;
+            }
+            Some((score, info))
+        })
+        .collect();
+
+    scored.sort_by(|(sa, a), (sb, b)| {
+        sb.cmp(sa)
+            .then_with(|| a.symbol.len().cmp(&b.symbol.len()))
+            .then_with(|| a.symbol.cmp(&b.symbol))
+    });
+
+    scored
+        .into_iter()
+        .take(MAX_ARGUMENT_SUGGESTIONS)
+        .map(|(_, info)| ArgumentSuggestion {
+            value: info.symbol.clone(),
+            detail: format!("{}/{}", info.base_asset, info.quote_asset),
+        })
+        .collect()
+}
+
+/// Rank watched symbols against a query; an empty query lists them all
+pub fn complete_watched_symbols(
+    watched: &[String],
+    query: &str,
+    exclude: &[&str],
+) -> Vec<ArgumentSuggestion> {
+    let query = query.trim().to_ascii_uppercase();
+
+    let mut scored: Vec<(i64, &String)> = watched
+        .iter()
+        .filter(|symbol| !exclude.iter().any(|ex| ex.eq_ignore_ascii_case(symbol)))
+        .filter_map(|symbol| {
+            if query.is_empty() {
+                Some((0, symbol))
+            } else {
+                symbol_score(symbol, symbol, &query).map(|score| (score, symbol))
+            }
+        })
+        .collect();
+
+    scored.sort_by(|(sa, a), (sb, b)| sb.cmp(sa).then_with(|| a.cmp(b)));
+
+    scored
+        .into_iter()
+        .take(MAX_ARGUMENT_SUGGESTIONS)
+        .map(|(_, symbol)| ArgumentSuggestion {
+            value: symbol.clone(),
+            detail: "watching".to_string(),
+        })
+        .collect()
+}
+
+/// Complete alert ids by id prefix or by fuzzy match on the alert's symbol
+pub fn complete_alert_ids(
+    alerts: &[Alert],
+    query: &str,
+    include_all: bool,
+) -> Vec<ArgumentSuggestion> {
+    let query = query.trim();
+    let upper = query.to_ascii_uppercase();
+
+    let mut suggestions: Vec<ArgumentSuggestion> = alerts
+        .iter()
+        .filter(|alert| {
+            query.is_empty()
+                || alert.id.to_string().starts_with(query)
+                || fuzzy_score(&alert.symbol, &upper).is_some()
+        })
+        .take(MAX_ARGUMENT_SUGGESTIONS)
+        .map(|alert| {
+            let direction = match alert.direction {
+                AlertDirection::Above => "above",
+                AlertDirection::Below => "below",
+            };
+            ArgumentSuggestion {
+                value: alert.id.to_string(),
+                detail: format!("{} {} {}", alert.symbol, direction, alert.threshold),
+            }
+        })
+        .collect();
+
+    if include_all && !alerts.is_empty() && "all".starts_with(&query.to_ascii_lowercase()) {
+        suggestions.push(ArgumentSuggestion {
+            value: "all".to_string(),
+            detail: "clear every alert".to_string(),
+        });
+    }
+
+    suggestions
+}
+
+fn symbol_score(symbol: &str, base_asset: &str, query: &str) -> Option<i64> {
+    if symbol == query {
+        return Some(1_000);
+    }
+    if base_asset == query {
+        return Some(900);
+    }
+    if symbol.starts_with(query) {
+        return Some(800 - (symbol.len() - query.len()) as i64);
+    }
+    if symbol.contains(query) {
+        return Some(500);
+    }
+    fuzzy_score(symbol, query)
+}
+
+/// Subsequence match score; tighter matches score higher
+fn fuzzy_score(candidate: &str, query: &str) -> Option<i64> {
+    if query.is_empty() {
+        return Some(0);
+    }
+
+    let mut gaps = 0i64;
+    let mut last_match: Option<usize> = None;
+    let mut chars = query.chars().peekable();
+
+    for (idx, c) in candidate.chars().enumerate() {
+        match chars.peek() {
+            Some(&q) if q == c => {
+                if let Some(last) = last_match {
+                    gaps += (idx - last - 1) as i64;
+                }
+                last_match = Some(idx);
+                chars.next();
+            }
+            Some(_) => {}
+            None => break,
+        }
+    }
+
+    if chars.peek().is_some() {
+        return None;
+    }
+
+    Some((100 - gaps * 5).max(1))
+}
+
+fn quote_bonus(quote_asset: &str) -> i64 {
+    QUOTE_PRIORITY
+        .iter()
+        .position(|quote| *quote == quote_asset)
+        .map(|idx| (QUOTE_PRIORITY.len() - idx) as i64 * 10)
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::binance::SymbolStatus;
+
+    fn symbol(base: &str, quote: &str) -> SymbolInfo {
+        SymbolInfo {
+            symbol: format!("{}{}", base, quote),
+            base_asset: base.to_string(),
+            quote_asset: quote.to_string(),
+            status: SymbolStatus::Trading,
+            tick_size: None,
+            min_price: None,
+            max_price: None,
+            step_size: None,
+            min_qty: None,
+            max_qty: None,
+            min_notional: None,
+        }
+    }
+
+    fn universe() -> Vec<SymbolInfo> {
+        vec![
+            symbol("BTC", "USDT"),
+            symbol("SOL", "BTC"),
+            symbol("SOL", "USDT"),
+            symbol("SOL", "EUR"),
+            symbol("ETH", "BTC"),
+            symbol("SOLV", "USDT"),
+        ]
+    }
+
+    #[test]
+    fn parses_argument_context() {
+        assert!(ArgumentContext::parse("/add").is_none());
+
+        let ctx = ArgumentContext::parse("/add BTCUSDT so").unwrap();
+        assert_eq!(ctx.trigger, "/add");
+        assert_eq!(ctx.previous, vec!["BTCUSDT"]);
+        assert_eq!(ctx.token, "so");
+        assert_eq!(ctx.position, 1);
+
+        let ctx = ArgumentContext::parse("/show ").unwrap();
+        assert_eq!(ctx.token, "");
+        assert_eq!(ctx.kind(), Some(ArgumentKind::WatchedSymbol));
+
+        let ctx = ArgumentContext::parse("/show BTCUSDT ").unwrap();
+        assert_eq!(ctx.kind(), None);
+    }
+
+    #[test]
+    fn base_asset_query_prefers_major_quotes() {
+        let results = complete_exchange_symbols(&universe(), &[], "sol", &[]);
+        let values: Vec<&str> = results.iter().map(|s| s.value.as_str()).collect();
+        assert_eq!(values, vec!["SOLUSDT", "SOLBTC", "SOLEUR", "SOLVUSDT"]);
+    }
+
+    #[test]
+    fn quote_filter_narrows_results() {
+        let results = complete_exchange_symbols(&universe(), &[], "/btc", &[]);
+        let values: Vec<&str> = results.iter().map(|s| s.value.as_str()).collect();
+        assert_eq!(values, vec!["ETHBTC", "SOLBTC"]);
+
+        let results = complete_exchange_symbols(&universe(), &[], "sol/us", &["SOLUSDT"]);
+        let values: Vec<&str> = results.iter().map(|s| s.value.as_str()).collect();
+        assert_eq!(values, vec!["SOLVUSDT"]);
+    }
+
+    #[test]
+    fn fuzzy_subsequence_matches() {
+        assert!(fuzzy_score("BTCUSDT", "BUSD").is_some());
+        assert!(fuzzy_score("BTCUSDT", "XRP").is_none());
+        assert!(fuzzy_score("ETHBTC", "EB").unwrap() > fuzzy_score("ETHBTC", "EC").unwrap());
+    }
+}
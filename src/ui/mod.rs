@@ -8,11 +8,30 @@ pub mod tui;
 /// UI Manager for interactive interface
 pub mod ui_manager;
 
+/// Command palette argument completion
+pub mod completion;
+
+/// Command palette history and reverse search
+pub mod command_history;
+
+use crate::account::AccountSnapshot;
+use crate::binance::ExchangeInfoCache;
 use crate::binance::types::OrderBook;
-use crate::market_data::DailyCandle;
-use crate::metrics::ConnectionMetrics;
+use crate::market_data::{
+    Bar, BarSpec, BookTicker, DEFAULT_BAR_HISTORY, DEFAULT_DAILY_CANDLE_LIMIT, DailyCandle,
+    HeatmapSettings, ImpactRequest, LiquidityHistory, MarketScanner, MicrostructureSnapshot,
+    ScannerFilter, ScannerRow, ScannerSort, ScannerTicker, TakerSide, TradeAnalyticsSnapshot,
+};
+use crate::metrics::{ConnectionMetrics, ConnectionStatus, SymbolMetrics};
+use crate::paper::{OrderSide, PaperOrder, PaperSnapshot};
+use crate::portfolio::PortfolioSnapshot;
+use crate::session::alert_history::{self, AlertHistoryEntry, DEFAULT_ALERT_HISTORY_LIMIT};
 use crate::session::alert_manager::{Alert, AlertDirection, AlertOptions, AlertRepeat};
-use crate::session::command_router::{CommandInfo, CommandRouter};
+use crate::session::command_router::{
+    CommandInfo, CommandRouter, format_duration_ms, parse_duration_ms,
+};
+use command_history::{CommandHistory, HistorySearch};
+use completion::{ArgumentContext, ArgumentKind, ArgumentSuggestion};
 use std::collections::{HashMap, VecDeque};
 use std::time::{Duration, Instant};
 
@@ -24,18 +43,56 @@ pub struct AppState {
     pub symbols: Vec<String>,
     pub market_data: HashMap<String, MarketDataState>,
     pub connection_metrics: ConnectionMetrics,
+    pub metrics_sort: MetricsSort,
+    /// Show one row per stream instead of one per symbol in the metrics table
+    pub metrics_show_streams: bool,
     pub paused: bool,
     pub log_messages: VecDeque<String>,
     pub log_scroll_offset: usize,
     pub notifications: VecDeque<String>,
     pub command_buffer: String,
+    /// Byte offset of the editing cursor within `command_buffer`
+    pub command_cursor: usize,
+    pub command_history: CommandHistory,
+    pub history_search: Option<HistorySearch>,
     pub input_mode: InputMode,
     pub available_commands: Vec<CommandInfo>,
     pub filtered_commands: Vec<CommandInfo>,
     pub selected_command_index: usize,
+    pub argument_suggestions: Vec<ArgumentSuggestion>,
     pub alert_form: AlertFormState,
+    pub order_form: OrderFormState,
     pub alerts: Vec<Alert>,
     pub selected_alert_index: usize,
+    pub alerts_tab: AlertsTab,
+    /// Fired alerts, oldest first
+    pub alert_history: Vec<AlertHistoryEntry>,
+    pub alert_history_limit: usize,
+    /// Symbol substring filter for the history tab
+    pub alert_history_filter: String,
+    pub editing_history_filter: bool,
+    pub selected_history_index: usize,
+    pub exchange_info: ExchangeInfoCache,
+    /// Latest ticker for every symbol while the scanner stream runs
+    pub scanner: MarketScanner,
+    pub scanner_sort: ScannerSort,
+    pub scanner_descending: bool,
+    pub scanner_filter: ScannerFilter,
+    pub selected_scanner_index: usize,
+    /// Paper balances, positions, open orders and recent fills
+    pub paper: PaperSnapshot,
+    pub selected_paper_order_index: usize,
+    /// Live exchange account: balances, open orders and executions
+    pub account: AccountSnapshot,
+    /// Manual holdings valued at the latest prices
+    pub portfolio: PortfolioSnapshot,
+    /// Market order estimated live in the impact panel
+    pub impact: Option<ImpactRequest>,
+    /// Local bar specs the subscriptions build, in chart cycling order
+    pub bar_specs: Vec<BarSpec>,
+    pub bar_history: usize,
+    /// Book sampling for the liquidity heatmap
+    pub heatmap: HeatmapSettings,
 }
 
 /// Market data state for a single symbol
@@ -48,10 +105,31 @@ pub struct MarketDataState {
     pub high_24h: f64,
     pub low_24h: f64,
     pub orderbook: Option<OrderBook>,
+    /// Real-time best bid/offer, ahead of the diff-depth book
+    pub book_ticker: Option<BookTicker>,
+    /// Rolling trade flow from the trade stream
+    pub trade_analytics: Option<TradeAnalyticsSnapshot>,
+    /// Order book microstructure samples, oldest first; the last is current
+    pub microstructure: Vec<MicrostructureSnapshot>,
+    /// Sampled top-of-book history behind the liquidity heatmap
+    pub liquidity: LiquidityHistory,
     pub price_history: Vec<PricePoint>,
     pub daily_candles: Vec<DailyCandle>,
     pub kline_render_cache: Option<KlineRenderCache>,
     pub last_kline_refresh: Option<Instant>,
+    /// Most recent REST backfill after a reconnect
+    pub last_backfill: Option<BackfillSummary>,
+    /// Locally aggregated bars, one series per configured spec
+    pub bar_series: Vec<BarSeries>,
+    /// Bars shown in the price trend chart; daily candles when unset
+    pub chart_bars: Option<BarSpec>,
+}
+
+/// Bars of one spec, oldest first; the last may still be building
+#[derive(Debug, Clone)]
+pub struct BarSeries {
+    pub spec: BarSpec,
+    pub bars: Vec<Bar>,
 }
 
 /// Historical price sample captured for trend chart
@@ -59,6 +137,19 @@ pub struct MarketDataState {
 pub struct PricePoint {
     pub timestamp_ms: u64,
     pub price: f64,
+    /// Recovered over REST rather than received live
+    pub backfilled: bool,
+}
+
+/// What the last reconnect backfill recovered for a symbol
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BackfillSummary {
+    pub trades: usize,
+    pub candles: usize,
+    /// Some missed trades could not be recovered
+    pub truncated: bool,
+    /// Wall-clock time the backfill arrived
+    pub received_ms: u64,
 }
 
 /// Cached candle samples prepared for rendering
@@ -91,6 +182,76 @@ pub enum InputMode {
     Command,
     AlertPopup,
     Alerts,
+    Scanner,
+    Paper,
+    Account,
+    OrderTicket,
+    Portfolio,
+    Impact,
+    Microstructure,
+    Heatmap,
+}
+
+/// Quote asset filters the scanner cycles through; `None` shows every market
+const SCANNER_QUOTE_ASSETS: [Option<&str>; 7] = [
+    None,
+    Some("USDT"),
+    Some("USDC"),
+    Some("FDUSD"),
+    Some("BTC"),
+    Some("ETH"),
+    Some("BNB"),
+];
+
+/// Minimum 24h quote volume steps the scanner cycles through
+const SCANNER_MIN_VOLUMES: [f64; 4] = [0.0, 1_000_000.0, 10_000_000.0, 100_000_000.0];
+
+/// Column the per-symbol metrics table is sorted by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MetricsSortColumn {
+    #[default]
+    Symbol,
+    Status,
+    Rate,
+    Latency,
+    Age,
+}
+
+impl MetricsSortColumn {
+    pub fn label(&self) -> &'static str {
+        match self {
+            MetricsSortColumn::Symbol => "symbol",
+            MetricsSortColumn::Status => "status",
+            MetricsSortColumn::Rate => "msg/s",
+            MetricsSortColumn::Latency => "p99",
+            MetricsSortColumn::Age => "age",
+        }
+    }
+
+    fn next(self) -> Self {
+        match self {
+            MetricsSortColumn::Symbol => MetricsSortColumn::Status,
+            MetricsSortColumn::Status => MetricsSortColumn::Rate,
+            MetricsSortColumn::Rate => MetricsSortColumn::Latency,
+            MetricsSortColumn::Latency => MetricsSortColumn::Age,
+            MetricsSortColumn::Age => MetricsSortColumn::Symbol,
+        }
+    }
+}
+
+/// Sort order for the per-symbol metrics table
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct MetricsSort {
+    pub column: MetricsSortColumn,
+    pub descending: bool,
+}
+
+/// Tab shown in the alerts overlay
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AlertsTab {
+    #[default]
+    Active,
+    History,
 }
 
 /// Active field in the alert popup form
@@ -115,6 +276,8 @@ pub struct AlertFormState {
     pub cooldown_input: String,
     pub hysteresis_input: String,
     pub active_field: AlertFormField,
+    /// Alert being edited; `None` when creating a new alert
+    pub editing_id: Option<u64>,
 }
 
 impl Default for AlertFormState {
@@ -129,6 +292,74 @@ impl Default for AlertFormState {
             cooldown_input: String::new(),
             hysteresis_input: String::new(),
             active_field: AlertFormField::Price,
+            editing_id: None,
+        }
+    }
+}
+
+/// Active field in the order ticket
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OrderFormField {
+    Side,
+    Type,
+    Quantity,
+    Price,
+}
+
+/// Order type selected in the order ticket
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OrderFormType {
+    #[default]
+    Limit,
+    Market,
+    Stop,
+}
+
+impl OrderFormType {
+    /// Keyword used by `/order:buy|sell`
+    pub fn keyword(self) -> &'static str {
+        match self {
+            OrderFormType::Limit => "limit",
+            OrderFormType::Market => "market",
+            OrderFormType::Stop => "stop",
+        }
+    }
+
+    fn next(self) -> Self {
+        match self {
+            OrderFormType::Limit => OrderFormType::Market,
+            OrderFormType::Market => OrderFormType::Stop,
+            OrderFormType::Stop => OrderFormType::Limit,
+        }
+    }
+}
+
+/// Order ticket popup state
+#[derive(Debug, Clone)]
+pub struct OrderFormState {
+    pub symbol: String,
+    pub side: OrderSide,
+    pub order_type: OrderFormType,
+    pub quantity_input: String,
+    /// Limit price or stop trigger
+    pub price_input: String,
+    pub active_field: OrderFormField,
+    pub error: Option<String>,
+    /// Summary of the order staged by the session, waiting for y/n
+    pub confirmation: Option<String>,
+}
+
+impl Default for OrderFormState {
+    fn default() -> Self {
+        Self {
+            symbol: String::new(),
+            side: OrderSide::Buy,
+            order_type: OrderFormType::default(),
+            quantity_input: String::new(),
+            price_input: String::new(),
+            active_field: OrderFormField::Quantity,
+            error: None,
+            confirmation: None,
         }
     }
 }
@@ -142,21 +373,61 @@ impl AppState {
             symbols,
             market_data: HashMap::new(),
             connection_metrics: ConnectionMetrics::default(),
+            metrics_sort: MetricsSort::default(),
+            metrics_show_streams: false,
             paused: false,
             log_messages: VecDeque::with_capacity(128),
             log_scroll_offset: 0,
             notifications: VecDeque::with_capacity(64),
             command_buffer: String::new(),
+            command_cursor: 0,
+            command_history: CommandHistory::default(),
+            history_search: None,
             input_mode: InputMode::Normal,
             available_commands: CommandRouter::commands().to_vec(),
             filtered_commands: CommandRouter::commands().to_vec(),
             selected_command_index: 0,
+            argument_suggestions: Vec::new(),
             alert_form: AlertFormState::default(),
+            order_form: OrderFormState::default(),
             alerts: Vec::new(),
             selected_alert_index: 0,
+            alerts_tab: AlertsTab::Active,
+            alert_history: Vec::new(),
+            alert_history_limit: DEFAULT_ALERT_HISTORY_LIMIT,
+            alert_history_filter: String::new(),
+            editing_history_filter: false,
+            selected_history_index: 0,
+            exchange_info: ExchangeInfoCache::default(),
+            scanner: MarketScanner::new(),
+            scanner_sort: ScannerSort::default(),
+            scanner_descending: true,
+            scanner_filter: ScannerFilter {
+                quote_asset: Some("USDT".to_string()),
+                min_quote_volume: 0.0,
+            },
+            selected_scanner_index: 0,
+            paper: PaperSnapshot::default(),
+            selected_paper_order_index: 0,
+            account: AccountSnapshot::default(),
+            portfolio: PortfolioSnapshot::default(),
+            impact: None,
+            bar_specs: Vec::new(),
+            bar_history: DEFAULT_BAR_HISTORY,
+            heatmap: HeatmapSettings::default(),
         }
     }
 
+    /// Decimal places used to display prices for a symbol
+    pub fn price_decimals(&self, symbol: &str) -> usize {
+        self.exchange_info.price_decimals(symbol)
+    }
+
+    /// Format a price with the symbol's tick size precision
+    pub fn format_price(&self, symbol: &str, price: f64) -> String {
+        self.exchange_info.format_price(symbol, price)
+    }
+
     /// Move to next tab
     pub fn next_tab(&mut self) {
         if !self.symbols.is_empty() {
@@ -180,6 +451,30 @@ impl AppState {
         self.symbols.get(self.selected_tab)
     }
 
+    /// Step the current symbol's chart through daily candles and each local bar spec
+    pub fn cycle_chart_bars(&mut self) -> Option<BarSpec> {
+        let symbol = self.current_symbol()?.clone();
+        let data = self
+            .market_data
+            .entry(symbol.clone())
+            .or_insert_with(|| MarketDataState {
+                symbol,
+                ..MarketDataState::default()
+            });
+        let next = match data.chart_bars {
+            None => self.bar_specs.first().copied(),
+            Some(current) => self
+                .bar_specs
+                .iter()
+                .position(|spec| *spec == current)
+                .and_then(|idx| self.bar_specs.get(idx + 1))
+                .copied(),
+        };
+        data.chart_bars = next;
+        data.invalidate_kline_cache();
+        next
+    }
+
     /// Toggle pause state
     pub fn toggle_pause(&mut self) {
         self.paused = !self.paused;
@@ -247,11 +542,392 @@ impl AppState {
         if matches!(self.input_mode, InputMode::Alerts) {
             self.input_mode = InputMode::Normal;
         }
+        self.editing_history_filter = false;
+    }
+
+    /// Sort the metrics table by the next column; numeric columns start descending
+    pub fn cycle_metrics_sort(&mut self) {
+        let column = self.metrics_sort.column.next();
+        self.metrics_sort = MetricsSort {
+            column,
+            descending: matches!(column, MetricsSortColumn::Rate | MetricsSortColumn::Latency),
+        };
+    }
+
+    /// Reverse the metrics table sort order
+    pub fn toggle_metrics_sort_direction(&mut self) {
+        self.metrics_sort.descending = !self.metrics_sort.descending;
+    }
+
+    /// Toggle between per-symbol and per-stream rows
+    pub fn toggle_metrics_streams(&mut self) {
+        self.metrics_show_streams = !self.metrics_show_streams;
+    }
+
+    /// Update one symbol's status ahead of the next metrics snapshot
+    pub fn set_symbol_connection_status(&mut self, symbol: &str, status: ConnectionStatus) {
+        if let Some(row) = self
+            .connection_metrics
+            .symbols
+            .iter_mut()
+            .find(|row| row.symbol == symbol)
+        {
+            row.status = status;
+        }
+    }
+
+    /// Per-symbol metrics in the current sort order
+    pub fn sorted_symbol_metrics(&self) -> Vec<&SymbolMetrics> {
+        let mut rows: Vec<&SymbolMetrics> = self.connection_metrics.symbols.iter().collect();
+        let sort = self.metrics_sort;
+        rows.sort_by(|a, b| {
+            let ordering = match sort.column {
+                MetricsSortColumn::Symbol => a.symbol.cmp(&b.symbol),
+                MetricsSortColumn::Status => status_rank(&a.status).cmp(&status_rank(&b.status)),
+                MetricsSortColumn::Rate => a.messages_per_second.total_cmp(&b.messages_per_second),
+                MetricsSortColumn::Latency => a.latency.p99.cmp(&b.latency.p99),
+                // Symbols that never received a message sort as the oldest
+                MetricsSortColumn::Age => a
+                    .last_message_age_ms
+                    .unwrap_or(u64::MAX)
+                    .cmp(&b.last_message_age_ms.unwrap_or(u64::MAX)),
+            };
+            let ordering = if sort.descending {
+                ordering.reverse()
+            } else {
+                ordering
+            };
+            ordering.then_with(|| a.symbol.cmp(&b.symbol))
+        });
+        rows
+    }
+
+    /// Open the scanner overlay
+    pub fn enter_scanner_view(&mut self) {
+        self.input_mode = InputMode::Scanner;
+        self.selected_scanner_index = 0;
+    }
+
+    /// Close the scanner overlay and drop its tickers
+    pub fn exit_scanner_view(&mut self) {
+        if matches!(self.input_mode, InputMode::Scanner) {
+            self.input_mode = InputMode::Normal;
+        }
+        self.scanner.clear();
+    }
+
+    /// Merge an all-market ticker update
+    pub fn apply_scanner_tickers(&mut self, tickers: &[ScannerTicker]) {
+        self.scanner.apply(tickers);
+        self.normalize_selected_scanner();
+    }
+
+    /// Scanner rows passing the filter, in the current sort order
+    pub fn scanner_rows(&self) -> Vec<ScannerRow> {
+        self.scanner.ranked(
+            &self.scanner_filter,
+            &self.exchange_info,
+            self.scanner_sort,
+            self.scanner_descending,
+        )
+    }
+
+    /// Rank the scanner by the next column, largest first
+    pub fn cycle_scanner_sort(&mut self) {
+        self.scanner_sort = self.scanner_sort.next();
+        self.scanner_descending = true;
+        self.selected_scanner_index = 0;
+    }
+
+    /// Reverse the scanner order, e.g. to see losers instead of gainers
+    pub fn toggle_scanner_sort_direction(&mut self) {
+        self.scanner_descending = !self.scanner_descending;
+        self.selected_scanner_index = 0;
+    }
+
+    /// Show the next quote asset market
+    pub fn cycle_scanner_quote(&mut self) {
+        let current = self.scanner_filter.quote_asset.as_deref();
+        let index = SCANNER_QUOTE_ASSETS
+            .iter()
+            .position(|quote| *quote == current)
+            .map_or(0, |i| (i + 1) % SCANNER_QUOTE_ASSETS.len());
+        self.scanner_filter.quote_asset = SCANNER_QUOTE_ASSETS[index].map(str::to_string);
+        self.selected_scanner_index = 0;
+    }
+
+    /// Raise the minimum quote volume, wrapping back to no minimum
+    pub fn cycle_scanner_min_volume(&mut self) {
+        let current = self.scanner_filter.min_quote_volume;
+        self.scanner_filter.min_quote_volume = SCANNER_MIN_VOLUMES
+            .iter()
+            .copied()
+            .find(|volume| *volume > current)
+            .unwrap_or(SCANNER_MIN_VOLUMES[0]);
+        self.selected_scanner_index = 0;
+    }
+
+    pub fn select_next_scanner_row(&mut self) {
+        self.selected_scanner_index += 1;
+        self.normalize_selected_scanner();
+    }
+
+    pub fn select_previous_scanner_row(&mut self) {
+        self.selected_scanner_index = self.selected_scanner_index.saturating_sub(1);
+    }
+
+    /// Symbol on the highlighted scanner row
+    pub fn selected_scanner_symbol(&self) -> Option<String> {
+        self.scanner_rows()
+            .into_iter()
+            .nth(self.selected_scanner_index)
+            .map(|row| row.ticker.symbol)
+    }
+
+    fn normalize_selected_scanner(&mut self) {
+        let len = self.scanner_rows().len();
+        self.selected_scanner_index = self.selected_scanner_index.min(len.saturating_sub(1));
+    }
+
+    /// Open the exchange account overlay
+    pub fn enter_account_view(&mut self) {
+        self.input_mode = InputMode::Account;
+    }
+
+    pub fn exit_account_view(&mut self) {
+        if matches!(self.input_mode, InputMode::Account) {
+            self.input_mode = InputMode::Normal;
+        }
+    }
+
+    /// Replace the account shown in the overlay
+    pub fn update_account(&mut self, snapshot: AccountSnapshot) {
+        self.account = snapshot;
+    }
+
+    /// Open the market impact overlay for an order on the live book
+    pub fn enter_impact_view(&mut self, request: ImpactRequest) {
+        self.impact = Some(request);
+        self.input_mode = InputMode::Impact;
+    }
+
+    pub fn exit_impact_view(&mut self) {
+        if matches!(self.input_mode, InputMode::Impact) {
+            self.input_mode = InputMode::Normal;
+        }
+    }
+
+    /// Estimate the same size on the other side of the book
+    pub fn toggle_impact_side(&mut self) {
+        if let Some(request) = self.impact.as_mut() {
+            request.side = match request.side {
+                TakerSide::Buy => TakerSide::Sell,
+                TakerSide::Sell => TakerSide::Buy,
+            };
+        }
+    }
+
+    /// Open the microstructure overlay for the selected symbol
+    pub fn enter_microstructure_view(&mut self) {
+        self.input_mode = InputMode::Microstructure;
+    }
+
+    pub fn exit_microstructure_view(&mut self) {
+        if matches!(self.input_mode, InputMode::Microstructure) {
+            self.input_mode = InputMode::Normal;
+        }
+    }
+
+    /// Open the liquidity heatmap for the selected symbol
+    pub fn enter_heatmap_view(&mut self) {
+        self.input_mode = InputMode::Heatmap;
+    }
+
+    pub fn exit_heatmap_view(&mut self) {
+        if matches!(self.input_mode, InputMode::Heatmap) {
+            self.input_mode = InputMode::Normal;
+        }
+    }
+
+    /// Open the portfolio overlay
+    pub fn enter_portfolio_view(&mut self) {
+        self.input_mode = InputMode::Portfolio;
+    }
+
+    pub fn exit_portfolio_view(&mut self) {
+        if matches!(self.input_mode, InputMode::Portfolio) {
+            self.input_mode = InputMode::Normal;
+        }
+    }
+
+    /// Replace the holdings shown in the overlay
+    pub fn update_portfolio(&mut self, snapshot: PortfolioSnapshot) {
+        self.portfolio = snapshot;
+    }
+
+    /// Open the paper trading overlay
+    pub fn enter_paper_view(&mut self) {
+        self.input_mode = InputMode::Paper;
+    }
+
+    pub fn exit_paper_view(&mut self) {
+        if matches!(self.input_mode, InputMode::Paper) {
+            self.input_mode = InputMode::Normal;
+        }
+    }
+
+    /// Replace the paper account shown in the overlay
+    pub fn update_paper(&mut self, snapshot: PaperSnapshot) {
+        self.paper = snapshot;
+        self.selected_paper_order_index = self
+            .selected_paper_order_index
+            .min(self.paper.open_orders.len().saturating_sub(1));
+    }
+
+    pub fn select_next_paper_order(&mut self) {
+        if self.selected_paper_order_index + 1 < self.paper.open_orders.len() {
+            self.selected_paper_order_index += 1;
+        }
+    }
+
+    pub fn select_previous_paper_order(&mut self) {
+        self.selected_paper_order_index = self.selected_paper_order_index.saturating_sub(1);
+    }
+
+    /// Open order highlighted in the paper overlay
+    pub fn selected_paper_order(&self) -> Option<&PaperOrder> {
+        self.paper.open_orders.get(self.selected_paper_order_index)
+    }
+
+    /// Price used to value a paper position: the live price when subscribed,
+    /// otherwise the last mark saved with the position
+    pub fn paper_mark_price(&self, symbol: &str, saved: Option<f64>) -> Option<f64> {
+        self.market_data
+            .get(symbol)
+            .map(|md| md.book_ticker.map_or(md.price, |ticker| ticker.mid()))
+            .filter(|price| *price > 0.0)
+            .or(saved)
+    }
+
+    /// Open the command palette with a paper order for the current symbol
+    pub fn start_paper_order(&mut self, side: &str) {
+        let command = match self.current_symbol() {
+            Some(symbol) => format!("/paper:{} {} ", side, symbol),
+            None => format!("/paper:{} ", side),
+        };
+        self.activate_command_mode(Some(&command));
+    }
+
+    /// Switch between the active alerts and history tabs
+    pub fn toggle_alerts_tab(&mut self) {
+        self.alerts_tab = match self.alerts_tab {
+            AlertsTab::Active => AlertsTab::History,
+            AlertsTab::History => AlertsTab::Active,
+        };
+        self.editing_history_filter = false;
+    }
+
+    /// Open the alerts overlay on the history tab, optionally filtered to a symbol
+    pub fn show_alert_history(&mut self, symbol: Option<String>) {
+        self.enter_alerts_view();
+        self.alerts_tab = AlertsTab::History;
+        self.alert_history_filter = symbol.unwrap_or_default();
+        self.selected_history_index = 0;
+    }
+
+    /// Replace the fired alert history
+    pub fn update_alert_history(&mut self, entries: Vec<AlertHistoryEntry>) {
+        self.alert_history = entries;
+        self.trim_alert_history();
+        self.normalize_selected_history();
+    }
+
+    /// Append a newly fired alert
+    pub fn push_alert_history(&mut self, entry: AlertHistoryEntry) {
+        self.alert_history.push(entry);
+        self.trim_alert_history();
+        // Keep the same row selected as newer entries arrive on top
+        if self.selected_history_index > 0 {
+            self.selected_history_index += 1;
+        }
+        self.normalize_selected_history();
+    }
+
+    /// History entries matching the filter, newest first
+    pub fn filtered_alert_history(&self) -> Vec<&AlertHistoryEntry> {
+        let filter = self.alert_history_filter.trim().to_ascii_uppercase();
+        self.alert_history
+            .iter()
+            .rev()
+            .filter(|entry| filter.is_empty() || entry.symbol.contains(&filter))
+            .collect()
+    }
+
+    /// Append a character to the history filter
+    pub fn push_history_filter_char(&mut self, c: char) {
+        self.alert_history_filter.push(c.to_ascii_uppercase());
+        self.selected_history_index = 0;
+    }
+
+    /// Remove the last character of the history filter
+    pub fn pop_history_filter_char(&mut self) {
+        self.alert_history_filter.pop();
+        self.selected_history_index = 0;
+    }
+
+    /// Select next (older) history entry
+    pub fn select_next_history(&mut self) {
+        let len = self.filtered_alert_history().len();
+        if self.selected_history_index + 1 < len {
+            self.selected_history_index += 1;
+        }
+    }
+
+    /// Select previous (newer) history entry
+    pub fn select_previous_history(&mut self) {
+        self.selected_history_index = self.selected_history_index.saturating_sub(1);
+    }
+
+    /// Export the filtered history to a timestamped CSV in the working directory
+    pub fn export_alert_history(&self) -> Result<String, String> {
+        let entries = self.filtered_alert_history();
+        if entries.is_empty() {
+            return Err("No alert history to export".to_string());
+        }
+
+        let path = format!(
+            "alert_history_{}.csv",
+            chrono::Local::now().format("%Y%m%d-%H%M%S")
+        );
+        // Oldest first, matching the journal order
+        alert_history::export_csv(&path, entries.into_iter().rev())
+            .map_err(|e| format!("Export failed: {:#}", e))?;
+        Ok(path)
+    }
+
+    fn trim_alert_history(&mut self) {
+        let limit = self.alert_history_limit.max(1);
+        if self.alert_history.len() > limit {
+            let overflow = self.alert_history.len() - limit;
+            self.alert_history.drain(0..overflow);
+        }
+    }
+
+    fn normalize_selected_history(&mut self) {
+        let len = self.filtered_alert_history().len();
+        if len == 0 {
+            self.selected_history_index = 0;
+        } else if self.selected_history_index >= len {
+            self.selected_history_index = len - 1;
+        }
     }
 
     /// Clear the command buffer
     pub fn clear_command(&mut self) {
         self.command_buffer.clear();
+        self.command_cursor = 0;
+        self.history_search = None;
+        self.command_history.reset_browse();
     }
 
     /// Enter command mode with optional preset buffer
@@ -259,12 +935,230 @@ impl AppState {
         self.input_mode = InputMode::Command;
         self.clear_command();
         if let Some(preset_value) = preset {
-            self.command_buffer.push_str(preset_value);
+            self.set_command_buffer(preset_value.to_string());
         }
         self.reset_command_suggestions();
         self.update_command_suggestions();
     }
 
+    /// Replace the command buffer and move the cursor to its end
+    pub fn set_command_buffer(&mut self, value: String) {
+        self.command_buffer = value;
+        self.command_cursor = self.command_buffer.len();
+    }
+
+    /// Record a submitted command in the persistent history
+    pub fn record_command(&mut self, command: &str) {
+        self.command_history.push(command);
+    }
+
+    /// Insert a character at the cursor
+    pub fn insert_command_char(&mut self, c: char) {
+        self.command_history.reset_browse();
+        self.command_buffer.insert(self.command_cursor, c);
+        self.command_cursor += c.len_utf8();
+        self.update_command_suggestions();
+    }
+
+    /// Delete the character before the cursor
+    pub fn delete_command_char_before(&mut self) {
+        if let Some(start) = self.prev_char_boundary(self.command_cursor) {
+            self.command_buffer.drain(start..self.command_cursor);
+            self.command_cursor = start;
+            self.after_command_edit();
+        }
+    }
+
+    /// Delete the character under the cursor
+    pub fn delete_command_char_at(&mut self) {
+        if let Some(end) = self.next_char_boundary(self.command_cursor) {
+            self.command_buffer.drain(self.command_cursor..end);
+            self.after_command_edit();
+        }
+    }
+
+    /// Delete the word before the cursor (Ctrl-W)
+    pub fn delete_command_word_before(&mut self) {
+        let start = self.word_start_before(self.command_cursor);
+        if start < self.command_cursor {
+            self.command_buffer.drain(start..self.command_cursor);
+            self.command_cursor = start;
+            self.after_command_edit();
+        }
+    }
+
+    /// Delete from the start of the line to the cursor (Ctrl-U)
+    pub fn kill_command_to_start(&mut self) {
+        if self.command_cursor > 0 {
+            self.command_buffer.drain(..self.command_cursor);
+            self.command_cursor = 0;
+            self.after_command_edit();
+        }
+    }
+
+    /// Delete from the cursor to the end of the line (Ctrl-K)
+    pub fn kill_command_to_end(&mut self) {
+        if self.command_cursor < self.command_buffer.len() {
+            self.command_buffer.truncate(self.command_cursor);
+            self.after_command_edit();
+        }
+    }
+
+    /// Move the cursor one character left
+    pub fn move_command_cursor_left(&mut self) {
+        if let Some(pos) = self.prev_char_boundary(self.command_cursor) {
+            self.command_cursor = pos;
+        }
+    }
+
+    /// Move the cursor one character right
+    pub fn move_command_cursor_right(&mut self) {
+        if let Some(pos) = self.next_char_boundary(self.command_cursor) {
+            self.command_cursor = pos;
+        }
+    }
+
+    /// Move the cursor to the start of the previous word
+    pub fn move_command_cursor_word_left(&mut self) {
+        self.command_cursor = self.word_start_before(self.command_cursor);
+    }
+
+    /// Move the cursor past the end of the next word
+    pub fn move_command_cursor_word_right(&mut self) {
+        let rest = &self.command_buffer[self.command_cursor..];
+        let skip_ws = rest.len() - rest.trim_start().len();
+        let word = rest[skip_ws..]
+            .find(char::is_whitespace)
+            .unwrap_or(rest.len() - skip_ws);
+        self.command_cursor += skip_ws + word;
+    }
+
+    /// Move the cursor to the start of the line
+    pub fn move_command_cursor_home(&mut self) {
+        self.command_cursor = 0;
+    }
+
+    /// Move the cursor to the end of the line
+    pub fn move_command_cursor_end(&mut self) {
+        self.command_cursor = self.command_buffer.len();
+    }
+
+    /// Number of characters before the cursor, used for terminal cursor placement
+    pub fn command_cursor_column(&self) -> usize {
+        self.command_buffer[..self.command_cursor].chars().count()
+    }
+
+    /// Recall the previous (older) history entry
+    pub fn recall_previous_command(&mut self) {
+        if let Some(entry) = self.command_history.older(&self.command_buffer) {
+            let entry = entry.to_string();
+            self.set_command_buffer(entry);
+            self.update_command_suggestions();
+        }
+    }
+
+    /// Recall the next (newer) history entry, restoring the draft at the end
+    pub fn recall_next_command(&mut self) {
+        if let Some(entry) = self.command_history.newer() {
+            self.set_command_buffer(entry);
+            self.update_command_suggestions();
+        }
+    }
+
+    /// Whether Up/Down should walk history rather than suggestions
+    pub fn history_navigation_active(&self) -> bool {
+        let trimmed = self.command_buffer.trim();
+        self.command_history.is_browsing() || trimmed.is_empty() || trimmed == "/"
+    }
+
+    /// Start Ctrl-R reverse search, or jump to the next older match if already searching
+    pub fn reverse_search(&mut self) {
+        match self.history_search.take() {
+            Some(mut search) => {
+                if let Some(idx) = self
+                    .command_history
+                    .search_backward(&search.query, search.match_index)
+                {
+                    search.match_index = Some(idx);
+                }
+                self.history_search = Some(search);
+            }
+            None => {
+                self.command_history.reset_browse();
+                self.history_search = Some(HistorySearch {
+                    original: self.command_buffer.clone(),
+                    ..HistorySearch::default()
+                });
+            }
+        }
+    }
+
+    /// Append a character to the reverse search query
+    pub fn push_search_char(&mut self, c: char) {
+        if let Some(search) = self.history_search.as_mut() {
+            search.query.push(c);
+            search.match_index = self.command_history.search_backward(&search.query, None);
+        }
+    }
+
+    /// Remove the last character from the reverse search query
+    pub fn pop_search_char(&mut self) {
+        if let Some(search) = self.history_search.as_mut() {
+            search.query.pop();
+            search.match_index = self.command_history.search_backward(&search.query, None);
+        }
+    }
+
+    /// Current reverse search match, if any
+    pub fn search_match(&self) -> Option<&str> {
+        let index = self.history_search.as_ref()?.match_index?;
+        self.command_history.get(index)
+    }
+
+    /// Leave reverse search, placing the match (if any) in the buffer for editing
+    pub fn accept_search(&mut self) {
+        if let Some(entry) = self.search_match().map(str::to_string) {
+            self.set_command_buffer(entry);
+        }
+        self.history_search = None;
+        self.update_command_suggestions();
+    }
+
+    /// Leave reverse search and restore the buffer from before the search
+    pub fn cancel_search(&mut self) {
+        if let Some(search) = self.history_search.take() {
+            self.set_command_buffer(search.original);
+            self.update_command_suggestions();
+        }
+    }
+
+    fn after_command_edit(&mut self) {
+        self.command_history.reset_browse();
+        self.update_command_suggestions();
+    }
+
+    fn prev_char_boundary(&self, pos: usize) -> Option<usize> {
+        self.command_buffer[..pos]
+            .char_indices()
+            .next_back()
+            .map(|(idx, _)| idx)
+    }
+
+    fn next_char_boundary(&self, pos: usize) -> Option<usize> {
+        self.command_buffer[pos..]
+            .chars()
+            .next()
+            .map(|c| pos + c.len_utf8())
+    }
+
+    fn word_start_before(&self, pos: usize) -> usize {
+        let before = self.command_buffer[..pos].trim_end();
+        before
+            .rfind(char::is_whitespace)
+            .map(|idx| idx + 1)
+            .unwrap_or(0)
+    }
+
     /// Enter alert popup mode using the current symbol and optional preset price
     pub fn activate_alert_popup(&mut self, preset_price: Option<f64>) -> Result<(), String> {
         let symbol = self
@@ -273,7 +1167,7 @@ impl AppState {
             .ok_or_else(|| "Select a symbol first".to_string())?;
 
         let price_string = preset_price
-            .map(|p| format!("{:.2}", p))
+            .map(|p| self.format_price(&symbol, p))
             .unwrap_or_default();
 
         self.alert_form = AlertFormState {
@@ -286,15 +1180,155 @@ impl AppState {
             cooldown_input: String::new(),
             hysteresis_input: String::new(),
             active_field: AlertFormField::Price,
+            editing_id: None,
         };
         self.input_mode = InputMode::AlertPopup;
         Ok(())
     }
 
-    /// Exit alert popup mode and clear state
+    /// Enter alert popup mode prefilled from the alert selected in the alerts view
+    pub fn activate_alert_edit_popup(&mut self) -> Result<(), String> {
+        let alert = self
+            .selected_alert()
+            .cloned()
+            .ok_or_else(|| "Select an alert first".to_string())?;
+
+        let hysteresis_input = if alert.hysteresis > 0.0 {
+            alert.hysteresis.to_string()
+        } else {
+            String::new()
+        };
+        let cooldown_input = if alert.cooldown_ms > 0 {
+            format_duration_ms(alert.cooldown_ms)
+        } else {
+            String::new()
+        };
+
+        self.alert_form = AlertFormState {
+            price_input: self.format_price(&alert.symbol, alert.threshold),
+            symbol: alert.symbol,
+            direction_above: alert.direction == AlertDirection::Above,
+            error: None,
+            price_dirty: false,
+            repeat: alert.repeat,
+            cooldown_input,
+            hysteresis_input,
+            active_field: AlertFormField::Price,
+            editing_id: Some(alert.id),
+        };
+        self.input_mode = InputMode::AlertPopup;
+        Ok(())
+    }
+
+    /// Open the order ticket for the current symbol, prefilling the price
+    pub fn open_order_ticket(&mut self, preset_price: Option<f64>) -> Result<(), String> {
+        let symbol = self
+            .current_symbol()
+            .cloned()
+            .ok_or_else(|| "Select a symbol first".to_string())?;
+
+        self.order_form = OrderFormState {
+            price_input: preset_price
+                .map(|price| self.format_price(&symbol, price))
+                .unwrap_or_default(),
+            symbol,
+            ..OrderFormState::default()
+        };
+        self.input_mode = InputMode::OrderTicket;
+        Ok(())
+    }
+
+    pub fn close_order_ticket(&mut self) {
+        self.order_form = OrderFormState::default();
+        if matches!(self.input_mode, InputMode::OrderTicket) {
+            self.input_mode = InputMode::Normal;
+        }
+    }
+
+    /// Cycle the active ticket field; market orders have no price
+    pub fn cycle_order_ticket_field(&mut self, reverse: bool) {
+        use OrderFormField::*;
+
+        let order: &[OrderFormField] = match self.order_form.order_type {
+            OrderFormType::Market => &[Side, Type, Quantity],
+            _ => &[Side, Type, Quantity, Price],
+        };
+        let len = order.len();
+        let idx = order
+            .iter()
+            .position(|field| *field == self.order_form.active_field)
+            .unwrap_or(0);
+        let next_idx = if reverse {
+            idx.checked_sub(1).unwrap_or(len - 1)
+        } else {
+            (idx + 1) % len
+        };
+        self.order_form.active_field = order[next_idx];
+    }
+
+    pub fn toggle_order_side(&mut self) {
+        self.order_form.side = match self.order_form.side {
+            OrderSide::Buy => OrderSide::Sell,
+            OrderSide::Sell => OrderSide::Buy,
+        };
+    }
+
+    pub fn cycle_order_type(&mut self) {
+        self.order_form.order_type = self.order_form.order_type.next();
+    }
+
+    /// `/order:buy|sell` command for the ticket, or why it is incomplete
+    pub fn order_ticket_command(&self) -> Result<String, String> {
+        let form = &self.order_form;
+        let quantity = form.quantity_input.trim();
+        if quantity.parse::<f64>().map_or(true, |qty| qty <= 0.0) {
+            return Err("Quantity must be a positive number".to_string());
+        }
+        let side = form.side.label().to_ascii_lowercase();
+        let mut command = format!("/order:{} {} {}", side, form.symbol, quantity);
+        match form.order_type {
+            OrderFormType::Market => command.push_str(" market"),
+            order_type => {
+                let price = form.price_input.trim();
+                if price.parse::<f64>().map_or(true, |price| price <= 0.0) {
+                    return Err("Price must be a positive number".to_string());
+                }
+                command.push_str(&format!(" {} {}", order_type.keyword(), price));
+            }
+        }
+        Ok(command)
+    }
+
+    /// Show the order the session staged, or close the ticket once it was sent or dropped
+    pub fn show_order_confirmation(&mut self, summary: Option<String>) {
+        match summary {
+            Some(summary) => {
+                self.order_form.error = None;
+                self.order_form.confirmation = Some(summary);
+                self.input_mode = InputMode::OrderTicket;
+            }
+            None if self.order_form.confirmation.is_some() => self.close_order_ticket(),
+            None => {}
+        }
+    }
+
+    /// Show why the ticket's order was refused
+    pub fn order_rejected(&mut self, message: String) {
+        if matches!(self.input_mode, InputMode::OrderTicket) {
+            self.order_form.confirmation = None;
+            self.order_form.error = Some(message);
+        }
+    }
+
+    /// Exit alert popup mode and clear state, returning to the alerts view after an edit
     pub fn deactivate_alert_popup(&mut self) {
+        let was_editing = self.alert_form.editing_id.is_some();
         self.alert_form = AlertFormState::default();
-        self.input_mode = InputMode::Normal;
+        self.input_mode = if was_editing {
+            InputMode::Alerts
+        } else {
+            InputMode::Normal
+        };
     }
 
     /// Cycle alert direction
@@ -352,13 +1386,8 @@ impl AppState {
         let cooldown_ms = if self.alert_form.cooldown_input.trim().is_empty() {
             0
         } else {
-            let seconds = self
-                .alert_form
-                .cooldown_input
-                .trim()
-                .parse::<u64>()
-                .map_err(|_| "Cooldown must be a number of seconds".to_string())?;
-            seconds.saturating_mul(1_000)
+            parse_duration_ms(&self.alert_form.cooldown_input)
+                .map_err(|e| format!("Cooldown: {}", e))?
         };
 
         let hysteresis = if self.alert_form.hysteresis_input.trim().is_empty() {
@@ -429,6 +1458,7 @@ impl AppState {
     /// Reset command suggestions to the full list
     pub fn reset_command_suggestions(&mut self) {
         self.filtered_commands = self.available_commands.clone();
+        self.argument_suggestions.clear();
         self.selected_command_index = 0;
     }
 
@@ -440,6 +1470,20 @@ impl AppState {
             return;
         }
 
+        if let Some(ctx) = ArgumentContext::parse(&self.command_buffer) {
+            self.filtered_commands = self
+                .available_commands
+                .iter()
+                .copied()
+                .filter(|cmd| cmd.trigger == ctx.trigger)
+                .collect();
+            self.argument_suggestions = self.complete_argument(&ctx);
+            self.clamp_suggestion_index();
+            return;
+        }
+
+        self.argument_suggestions.clear();
+
         let query = raw_query.trim_start_matches('/').to_ascii_lowercase();
         self.filtered_commands = self
             .available_commands
@@ -452,27 +1496,65 @@ impl AppState {
             })
             .collect();
 
-        if self.filtered_commands.is_empty() {
+        self.clamp_suggestion_index();
+    }
+
+    /// Compute argument completions for the token under the cursor
+    fn complete_argument(&self, ctx: &ArgumentContext<'_>) -> Vec<ArgumentSuggestion> {
+        match ctx.kind() {
+            Some(ArgumentKind::ExchangeSymbol) if self.exchange_info.is_loaded() => {
+                completion::complete_exchange_symbols(
+                    &self.exchange_info.symbols(),
+                    &self.symbols,
+                    ctx.token,
+                    &ctx.previous,
+                )
+            }
+            // Fall back to the watch list until exchange info has loaded
+            Some(ArgumentKind::ExchangeSymbol) | Some(ArgumentKind::WatchedSymbol) => {
+                completion::complete_watched_symbols(&self.symbols, ctx.token, &ctx.previous)
+            }
+            Some(ArgumentKind::AlertId) => completion::complete_alert_ids(
+                &self.alerts,
+                ctx.token,
+                ctx.trigger == "/alert:clear",
+            ),
+            None => Vec::new(),
+        }
+    }
+
+    /// Number of entries in the active suggestion list
+    fn suggestion_count(&self) -> usize {
+        if self.argument_suggestions.is_empty() {
+            self.filtered_commands.len()
+        } else {
+            self.argument_suggestions.len()
+        }
+    }
+
+    fn clamp_suggestion_index(&mut self) {
+        let count = self.suggestion_count();
+        if count == 0 {
             self.selected_command_index = 0;
-        } else if self.selected_command_index >= self.filtered_commands.len() {
-            self.selected_command_index = self.filtered_commands.len() - 1;
+        } else if self.selected_command_index >= count {
+            self.selected_command_index = count - 1;
         }
     }
 
     /// Select next command suggestion
     pub fn select_next_suggestion(&mut self) {
-        if self.filtered_commands.is_empty() {
+        let count = self.suggestion_count();
+        if count == 0 {
             return;
         }
-        let last = self.filtered_commands.len().saturating_sub(1);
-        if self.selected_command_index < last {
+        if self.selected_command_index < count - 1 {
             self.selected_command_index += 1;
         }
     }
 
     /// Select previous command suggestion
     pub fn select_previous_suggestion(&mut self) {
-        if self.filtered_commands.is_empty() {
+        if self.suggestion_count() == 0 {
             return;
         }
         if self.selected_command_index > 0 {
@@ -482,8 +1564,29 @@ impl AppState {
 
     /// Apply the selected suggestion to the command buffer
     pub fn apply_selected_suggestion(&mut self) {
+        if let Some(suggestion) = self.selected_argument() {
+            let value = suggestion.value.clone();
+            let keep = self
+                .command_buffer
+                .trim_end_matches(|c: char| !c.is_whitespace())
+                .len();
+            self.command_buffer.truncate(keep);
+            self.command_buffer.push_str(&value);
+            self.command_buffer.push(' ');
+            self.command_cursor = self.command_buffer.len();
+            self.selected_command_index = 0;
+            self.update_command_suggestions();
+            return;
+        }
+
+        // Arguments are being typed but nothing matched; keep the buffer as is
+        if ArgumentContext::parse(&self.command_buffer).is_some() {
+            return;
+        }
+
         if let Some(cmd) = self.selected_command() {
-            self.command_buffer = cmd.trigger.to_string();
+            let trigger = cmd.trigger.to_string();
+            self.set_command_buffer(trigger);
             self.reset_command_suggestions();
             self.update_command_suggestions();
         }
@@ -494,6 +1597,11 @@ impl AppState {
         self.filtered_commands.get(self.selected_command_index)
     }
 
+    /// Get the currently selected argument suggestion, if any
+    pub fn selected_argument(&self) -> Option<&ArgumentSuggestion> {
+        self.argument_suggestions.get(self.selected_command_index)
+    }
+
     /// Scroll logs up (toward older entries)
     pub fn scroll_logs_up(&mut self) {
         if self.log_scroll_offset + 1 < self.log_messages.len() {
@@ -515,6 +1623,115 @@ impl AppState {
 }
 
 impl MarketDataState {
+    /// Merge incremental candles by open time, returning whether a candle closed
+    pub fn merge_daily_candles(&mut self, candles: Vec<DailyCandle>) -> bool {
+        let mut appended_closed = false;
+        for candle in candles {
+            if let Some(existing) = self
+                .daily_candles
+                .iter_mut()
+                .find(|existing| existing.open_time_ms == candle.open_time_ms)
+            {
+                if candle.is_closed && !existing.is_closed {
+                    appended_closed = true;
+                }
+                *existing = candle;
+            } else {
+                if candle.is_closed {
+                    appended_closed = true;
+                }
+                self.daily_candles.push(candle);
+            }
+        }
+
+        if self.daily_candles.len() > DEFAULT_DAILY_CANDLE_LIMIT {
+            let overflow = self.daily_candles.len() - DEFAULT_DAILY_CANDLE_LIMIT;
+            self.daily_candles.drain(0..overflow);
+        }
+
+        self.daily_candles.sort_by_key(|candle| candle.open_time_ms);
+        appended_closed
+    }
+
+    /// Record a bar update, returning whether the chart is showing its spec
+    pub fn apply_bar(&mut self, spec: BarSpec, bar: Bar, limit: usize) -> bool {
+        let series = match self
+            .bar_series
+            .iter()
+            .position(|series| series.spec == spec)
+        {
+            Some(idx) => &mut self.bar_series[idx],
+            None => {
+                self.bar_series.push(BarSeries {
+                    spec,
+                    bars: Vec::new(),
+                });
+                self.bar_series.last_mut().expect("series pushed above")
+            }
+        };
+
+        // Updates to the building bar replace it until it closes
+        match series.bars.last_mut() {
+            Some(last) if !last.is_closed && last.open_time_ms == bar.open_time_ms => *last = bar,
+            _ => series.bars.push(bar),
+        }
+        if series.bars.len() > limit.max(1) {
+            let overflow = series.bars.len() - limit.max(1);
+            series.bars.drain(0..overflow);
+        }
+
+        let charted = self.chart_bars == Some(spec);
+        if charted {
+            self.invalidate_kline_cache();
+        }
+        charted
+    }
+
+    /// Seed a series with stored bars, keeping any live bars newer than them
+    pub fn load_bar_history(&mut self, spec: BarSpec, mut bars: Vec<Bar>, limit: usize) {
+        if let Some(idx) = self
+            .bar_series
+            .iter()
+            .position(|series| series.spec == spec)
+        {
+            let series = self.bar_series.remove(idx);
+            let newest_stored = bars.last().map_or(0, |bar| bar.close_time_ms);
+            bars.extend(
+                series
+                    .bars
+                    .into_iter()
+                    .filter(|bar| bar.open_time_ms > newest_stored),
+            );
+        }
+        if bars.len() > limit.max(1) {
+            bars.drain(0..bars.len() - limit.max(1));
+        }
+        self.bar_series.push(BarSeries { spec, bars });
+        if self.chart_bars == Some(spec) {
+            self.invalidate_kline_cache();
+        }
+    }
+
+    /// Slot backfilled prints into the price history in time order, keeping the newest `max_points`
+    pub fn merge_backfilled_prices(&mut self, points: &[PricePoint], max_points: usize) {
+        self.price_history.extend_from_slice(points);
+        // Stable sort keeps live samples ahead of backfilled ones at the same timestamp
+        self.price_history.sort_by_key(|point| point.timestamp_ms);
+        if self.price_history.len() > max_points {
+            let overflow = self.price_history.len() - max_points;
+            self.price_history.drain(0..overflow);
+        }
+    }
+
+    /// Append a microstructure sample, keeping the newest `max_points`
+    pub fn push_microstructure(&mut self, snapshot: MicrostructureSnapshot, max_points: usize) {
+        self.microstructure.push(snapshot);
+        if self.microstructure.len() > max_points {
+            let overflow = self.microstructure.len() - max_points;
+            self.microstructure.drain(0..overflow);
+        }
+    }
+
     /// Invalidate the cached kline render data
     pub fn invalidate_kline_cache(&mut self) {
         self.kline_render_cache = None;
@@ -540,21 +1757,34 @@ impl MarketDataState {
     }
 
     fn rebuild_kline_cache(&mut self, width: u16) {
-        if width < 4 || self.daily_candles.is_empty() {
+        let max_candles = std::cmp::max(1, width as usize);
+        let samples: Vec<CandleSample> = match self.chart_bars {
+            None => {
+                let start = self.daily_candles.len().saturating_sub(max_candles);
+                self.daily_candles[start..]
+                    .iter()
+                    .map(CandleSample::from)
+                    .collect()
+            }
+            Some(spec) => self
+                .bar_series
+                .iter()
+                .find(|series| series.spec == spec)
+                .map(|series| {
+                    let start = series.bars.len().saturating_sub(max_candles);
+                    series.bars[start..]
+                        .iter()
+                        .map(CandleSample::from)
+                        .collect()
+                })
+                .unwrap_or_default(),
+        };
+
+        if width < 4 || samples.is_empty() {
             self.kline_render_cache = None;
             return;
         }
 
-        let max_candles = std::cmp::max(1, width as usize);
-        let len = self.daily_candles.len();
-        let take = len.min(max_candles);
-        let start = len - take;
-
-        let samples: Vec<CandleSample> = self.daily_candles[start..]
-            .iter()
-            .map(CandleSample::from)
-            .collect();
-
         self.kline_render_cache = KlineRenderCache::from_samples(width, samples);
     }
 
@@ -624,6 +1854,20 @@ impl From<&DailyCandle> for CandleSample {
     }
 }
 
+impl From<&Bar> for CandleSample {
+    fn from(value: &Bar) -> Self {
+        Self {
+            open_time_ms: value.open_time_ms,
+            close_time_ms: value.close_time_ms,
+            open: value.open,
+            high: value.high,
+            low: value.low,
+            close: value.close,
+            is_closed: value.is_closed,
+        }
+    }
+}
+
 impl Default for MarketDataState {
     fn default() -> Self {
         Self {
@@ -634,10 +1878,17 @@ impl Default for MarketDataState {
             high_24h: 0.0,
             low_24h: 0.0,
             orderbook: None,
+            book_ticker: None,
+            trade_analytics: None,
+            microstructure: Vec::new(),
+            liquidity: LiquidityHistory::default(),
             price_history: Vec::new(),
             daily_candles: Vec::new(),
             kline_render_cache: None,
             last_kline_refresh: None,
+            last_backfill: None,
+            bar_series: Vec::new(),
+            chart_bars: None,
         }
     }
 }
@@ -675,9 +1926,21 @@ pub fn display_welcome_page() -> Result<(), std::io::Error> {
     Ok(())
 }
 
+/// Ordering used when sorting by status: healthy first
+fn status_rank(status: &ConnectionStatus) -> u8 {
+    match status {
+        ConnectionStatus::Connected => 0,
+        ConnectionStatus::Connecting => 1,
+        ConnectionStatus::Reconnecting => 2,
+        ConnectionStatus::Disconnected => 3,
+        ConnectionStatus::Error(_) => 4,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::binance::{SymbolInfo, SymbolStatus};
 
     #[test]
     fn test_app_state_navigation() {
@@ -694,6 +1957,158 @@ mod tests {
         assert_eq!(app.selected_tab, 1);
     }
 
+    #[test]
+    fn symbol_metrics_sort_by_column_and_direction() {
+        let mut app = AppState::new(vec![]);
+        let row = |symbol: &str, rate: f64, p99: u64, age: Option<u64>| SymbolMetrics {
+            symbol: symbol.to_string(),
+            status: ConnectionStatus::Connected,
+            total_messages: 0,
+            messages_per_second: rate,
+            latency: crate::metrics::LatencySummary {
+                p99,
+                ..Default::default()
+            },
+            last_message_age_ms: age,
+            reconnect_count: 0,
+            error_count: 0,
+            streams: Vec::new(),
+        };
+        app.connection_metrics.symbols = vec![
+            row("ETHUSDT", 4.0, 30, Some(100)),
+            row("BTCUSDT", 9.0, 10, None),
+            row("SOLUSDT", 1.0, 50, Some(5)),
+        ];
+        let order = |app: &AppState| -> Vec<String> {
+            app.sorted_symbol_metrics()
+                .iter()
+                .map(|m| m.symbol.clone())
+                .collect()
+        };
+
+        assert_eq!(order(&app), vec!["BTCUSDT", "ETHUSDT", "SOLUSDT"]);
+
+        app.cycle_metrics_sort(); // status
+        app.cycle_metrics_sort(); // rate
+        assert_eq!(app.metrics_sort.column, MetricsSortColumn::Rate);
+        assert_eq!(order(&app), vec!["BTCUSDT", "ETHUSDT", "SOLUSDT"]);
+        app.toggle_metrics_sort_direction();
+        assert_eq!(order(&app), vec!["SOLUSDT", "ETHUSDT", "BTCUSDT"]);
+
+        app.metrics_sort = MetricsSort {
+            column: MetricsSortColumn::Age,
+            descending: false,
+        };
+        assert_eq!(order(&app), vec!["SOLUSDT", "ETHUSDT", "BTCUSDT"]);
+    }
+
+    #[test]
+    fn alert_history_filter_lists_newest_first() {
+        let mut app = AppState::new(vec![]);
+        app.alert_history_limit = 3;
+        for (id, symbol) in [
+            (1, "BTCUSDT"),
+            (2, "ETHUSDT"),
+            (3, "ETHBTC"),
+            (4, "BTCUSDT"),
+        ] {
+            app.push_alert_history(AlertHistoryEntry {
+                alert_id: id,
+                alert_created_at_ms: 0,
+                symbol: symbol.to_string(),
+                input: Default::default(),
+                direction: AlertDirection::Above,
+                threshold: 1.0,
+                price: 1.5,
+                best_bid: None,
+                best_ask: None,
+                triggered_at_ms: id * 1_000,
+            });
+        }
+        assert_eq!(app.alert_history.len(), 3);
+
+        app.show_alert_history(None);
+        for c in "eth".chars() {
+            app.push_history_filter_char(c);
+        }
+        let ids: Vec<u64> = app
+            .filtered_alert_history()
+            .iter()
+            .map(|e| e.alert_id)
+            .collect();
+        assert_eq!(ids, vec![3, 2]);
+
+        app.select_next_history();
+        app.select_next_history();
+        assert_eq!(app.selected_history_index, 1);
+    }
+
+    #[test]
+    fn scanner_filters_and_selects_symbols() {
+        let ticker = |symbol: &str, last: f64, quote_volume: f64| ScannerTicker {
+            symbol: symbol.to_string(),
+            last_price: last,
+            open_price: 100.0,
+            high_price: last.max(100.0),
+            low_price: last.min(100.0),
+            volume: 1.0,
+            quote_volume,
+            event_time: 0,
+        };
+        let info = |base: &str, quote: &str| SymbolInfo {
+            symbol: format!("{}{}", base, quote),
+            base_asset: base.to_string(),
+            quote_asset: quote.to_string(),
+            status: SymbolStatus::Trading,
+            tick_size: None,
+            min_price: None,
+            max_price: None,
+            step_size: None,
+            min_qty: None,
+            max_qty: None,
+            min_notional: None,
+        };
+        let mut app = AppState::new(vec![]);
+        app.exchange_info.replace(vec![
+            info("BTC", "USDT"),
+            info("ETH", "USDT"),
+            info("ETH", "BTC"),
+        ]);
+        app.enter_scanner_view();
+        app.apply_scanner_tickers(&[
+            ticker("BTCUSDT", 110.0, 5_000_000.0),
+            ticker("ETHUSDT", 95.0, 50_000_000.0),
+            ticker("ETHBTC", 130.0, 10.0),
+        ]);
+
+        // Defaults to USDT markets ranked by 24h change
+        assert_eq!(app.scanner_rows().len(), 2);
+        assert_eq!(app.selected_scanner_symbol().as_deref(), Some("BTCUSDT"));
+        app.select_next_scanner_row();
+        app.select_next_scanner_row();
+        assert_eq!(app.selected_scanner_symbol().as_deref(), Some("ETHUSDT"));
+
+        app.toggle_scanner_sort_direction();
+        assert_eq!(app.selected_scanner_symbol().as_deref(), Some("ETHUSDT"));
+
+        app.cycle_scanner_min_volume();
+        app.cycle_scanner_min_volume();
+        assert_eq!(app.scanner_filter.min_quote_volume, 10_000_000.0);
+        assert_eq!(app.scanner_rows().len(), 1);
+
+        // USDC, FDUSD, BTC
+        for _ in 0..3 {
+            app.cycle_scanner_quote();
+        }
+        app.scanner_filter.min_quote_volume = 0.0;
+        assert_eq!(app.scanner_filter.quote_asset.as_deref(), Some("BTC"));
+        assert_eq!(app.selected_scanner_symbol().as_deref(), Some("ETHBTC"));
+
+        app.exit_scanner_view();
+        assert!(matches!(app.input_mode, InputMode::Normal));
+        assert!(app.scanner.is_empty());
+    }
+
     #[test]
     fn test_toggle_pause() {
         let mut app = AppState::new(vec![]);
@@ -707,9 +2122,151 @@ mod tests {
     }
 
     #[test]
-    fn kline_cache_limits_samples_by_width() {
+    fn command_palette_completes_symbol_arguments() {
+        let mut app = AppState::new(vec!["BTCUSDT".to_string(), "SOLUSDT".to_string()]);
+        app.activate_command_mode(Some("/remove so"));
+
+        assert_eq!(app.argument_suggestions.len(), 1);
+        assert_eq!(app.argument_suggestions[0].value, "SOLUSDT");
+
+        app.apply_selected_suggestion();
+        assert_eq!(app.command_buffer, "/remove SOLUSDT ");
+        // Already-typed symbols are not suggested again
+        assert!(
+            app.argument_suggestions
+                .iter()
+                .all(|s| s.value != "SOLUSDT")
+        );
+    }
+
+    #[test]
+    fn command_line_editing_moves_cursor_and_deletes_words() {
+        let mut app = AppState::new(vec![]);
+        app.activate_command_mode(Some("/add BTCUSDT ETHUSDT"));
+
+        app.move_command_cursor_word_left();
+        assert_eq!(app.command_cursor, "/add BTCUSDT ".len());
+        app.delete_command_word_before();
+        assert_eq!(app.command_buffer, "/add ETHUSDT");
+
+        app.move_command_cursor_home();
+        app.move_command_cursor_word_right();
+        app.insert_command_char('!');
+        assert_eq!(app.command_buffer, "/add! ETHUSDT");
+        app.delete_command_char_before();
+        app.kill_command_to_end();
+        assert_eq!(app.command_buffer, "/add");
+    }
+
+    #[test]
+    fn reverse_search_recalls_matching_command() {
+        let mut app = AppState::new(vec![]);
+        app.record_command("/add BTCUSDT ETHUSDT");
+        app.record_command("/status");
+        app.activate_command_mode(Some("/"));
+
+        app.reverse_search();
+        for c in "add".chars() {
+            app.push_search_char(c);
+        }
+        assert_eq!(app.search_match(), Some("/add BTCUSDT ETHUSDT"));
+
+        app.accept_search();
+        assert!(app.history_search.is_none());
+        assert_eq!(app.command_buffer, "/add BTCUSDT ETHUSDT");
+        assert_eq!(app.command_cursor, app.command_buffer.len());
+    }
+
+    #[test]
+    fn chart_cycles_through_local_bars() {
+        let five_seconds = BarSpec::Time { interval_ms: 5_000 };
+        let mut app = AppState::new(vec!["BTCUSDT".to_string()]);
+        app.bar_specs = vec![five_seconds, BarSpec::Tick { trades: 10 }];
+
+        let bar = |open_time_ms, close, is_closed| Bar {
+            open_time_ms,
+            close_time_ms: open_time_ms + 4_999,
+            open: 100.0,
+            high: 101.0,
+            low: 99.0,
+            close,
+            volume: 1.0,
+            buy_volume: 0.5,
+            trades: 2,
+            is_closed,
+        };
+
+        assert_eq!(app.cycle_chart_bars(), Some(five_seconds));
+        let data = app.market_data.get_mut("BTCUSDT").unwrap();
+        assert!(data.apply_bar(five_seconds, bar(0, 100.5, false), 2));
+        assert!(data.apply_bar(five_seconds, bar(0, 100.8, true), 2));
+        assert!(data.apply_bar(five_seconds, bar(5_000, 100.2, false), 2));
+        assert!(data.apply_bar(five_seconds, bar(10_000, 100.1, false), 2));
+
+        let bars = &data.bar_series[0].bars;
+        assert_eq!(bars.len(), 2);
+        assert_eq!(bars[0].open_time_ms, 5_000);
+
+        let cache = data.ensure_kline_cache(10).expect("bars should render");
+        assert_eq!(cache.samples.len(), 2);
+
+        assert_eq!(app.cycle_chart_bars(), Some(BarSpec::Tick { trades: 10 }));
+        assert_eq!(app.cycle_chart_bars(), None);
+    }
+
+    #[test]
+    fn stored_bars_precede_live_bars() {
+        let spec = BarSpec::Time { interval_ms: 1_000 };
+        let bar = |open_time_ms| Bar {
+            open_time_ms,
+            close_time_ms: open_time_ms + 999,
+            open: 1.0,
+            high: 1.0,
+            low: 1.0,
+            close: 1.0,
+            volume: 1.0,
+            buy_volume: 0.0,
+            trades: 1,
+            is_closed: true,
+        };
+        let mut state = MarketDataState::default();
+        state.apply_bar(spec, bar(5_000), 10);
+
+        state.load_bar_history(spec, vec![bar(1_000), bar(2_000), bar(5_000)], 3);
+
+        let opens: Vec<u64> = state.bar_series[0]
+            .bars
+            .iter()
+            .map(|bar| bar.open_time_ms)
+            .collect();
+        assert_eq!(opens, vec![1_000, 2_000, 5_000]);
+        assert_eq!(state.bar_series.len(), 1);
+    }
+
+    #[test]
+    fn backfilled_prices_merge_in_time_order() {
         let mut state = MarketDataState::default();
-        state.symbol = "TESTUSDT".to_string();
+        let point = |timestamp_ms, backfilled| PricePoint {
+            timestamp_ms,
+            price: 100.0,
+            backfilled,
+        };
+        state.price_history = vec![point(1_000, false), point(4_000, false)];
+
+        state.merge_backfilled_prices(&[point(2_000, true), point(3_000, true)], 3);
+
+        let times: Vec<u64> = state.price_history.iter().map(|p| p.timestamp_ms).collect();
+        assert_eq!(times, vec![2_000, 3_000, 4_000]);
+        assert!(state.price_history[0].backfilled);
+        assert!(!state.price_history[2].backfilled);
+    }
+
+    #[test]
+    fn kline_cache_limits_samples_by_width() {
+        let mut state = MarketDataState {
+            symbol: "TESTUSDT".to_string(),
+            ..MarketDataState::default()
+        };
 
         for idx in 0..20 {
             state.daily_candles.push(DailyCandle::new(
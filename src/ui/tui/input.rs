@@ -1,7 +1,8 @@
 use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
 
 use super::UiAction;
-use crate::ui::{AlertFormField, AppState, InputMode};
+use crate::session::alert_manager::{AlertUpdate, HysteresisSpec};
+use crate::ui::{AlertFormField, AlertsTab, AppState, InputMode, OrderFormField};
 
 /// Handle keyboard events for TUI, returning actions for the session manager
 pub fn handle_key_event(app: &mut AppState, key_event: KeyEvent) -> UiAction {
@@ -16,7 +17,7 @@ pub fn handle_key_event(app: &mut AppState, key_event: KeyEvent) -> UiAction {
                 let preset = app
                     .current_symbol()
                     .and_then(|sym| app.market_data.get(sym))
-                    .map(|md| md.price);
+                    .map(|md| md.book_ticker.map_or(md.price, |ticker| ticker.mid()));
                 if let Err(e) = app.activate_alert_popup(preset) {
                     app.push_notification(e);
                 }
@@ -45,6 +46,14 @@ pub fn handle_key_event(app: &mut AppState, key_event: KeyEvent) -> UiAction {
         InputMode::Command => handle_command_mode_keys(app, key_event),
         InputMode::AlertPopup => handle_alert_popup_keys(app, key_event),
         InputMode::Alerts => handle_alerts_mode_keys(app, key_event),
+        InputMode::Scanner => handle_scanner_mode_keys(app, key_event),
+        InputMode::Paper => handle_paper_mode_keys(app, key_event),
+        InputMode::Account => handle_account_mode_keys(app, key_event),
+        InputMode::Portfolio => handle_portfolio_mode_keys(app, key_event),
+        InputMode::Impact => handle_impact_mode_keys(app, key_event),
+        InputMode::Microstructure => handle_microstructure_mode_keys(app, key_event),
+        InputMode::Heatmap => handle_heatmap_mode_keys(app, key_event),
+        InputMode::OrderTicket => handle_order_ticket_keys(app, key_event),
     }
 }
 
@@ -92,6 +101,32 @@ fn handle_normal_mode_keys(app: &mut AppState, key_event: KeyEvent) -> UiAction
             app.activate_command_mode(Some("/status"));
             UiAction::None
         }
+        KeyCode::Char('m') => {
+            app.cycle_metrics_sort();
+            UiAction::None
+        }
+        KeyCode::Char('M') if key_event.modifiers.contains(KeyModifiers::SHIFT) => {
+            app.toggle_metrics_sort_direction();
+            UiAction::None
+        }
+        KeyCode::Char('t') => {
+            app.toggle_metrics_streams();
+            UiAction::None
+        }
+        KeyCode::Char('i') => {
+            app.cycle_chart_bars();
+            UiAction::None
+        }
+        KeyCode::Char('o') => {
+            let preset = app
+                .current_symbol()
+                .and_then(|sym| app.market_data.get(sym))
+                .map(|md| md.book_ticker.map_or(md.price, |ticker| ticker.mid()));
+            if let Err(e) = app.open_order_ticket(preset) {
+                app.push_notification(e);
+            }
+            UiAction::None
+        }
         KeyCode::Char('L') if key_event.modifiers.contains(KeyModifiers::SHIFT) => {
             app.activate_command_mode(Some("/logs"));
             UiAction::None
@@ -100,12 +135,43 @@ fn handle_normal_mode_keys(app: &mut AppState, key_event: KeyEvent) -> UiAction
             app.enter_alerts_view();
             UiAction::SubmitCommand("/alert:list".to_string())
         }
+        KeyCode::Char('S') if key_event.modifiers.contains(KeyModifiers::SHIFT) => {
+            app.enter_scanner_view();
+            UiAction::SubmitCommand("/scanner on".to_string())
+        }
+        KeyCode::Char('P') if key_event.modifiers.contains(KeyModifiers::SHIFT) => {
+            app.enter_paper_view();
+            UiAction::SubmitCommand("/paper".to_string())
+        }
+        KeyCode::Char('B') if key_event.modifiers.contains(KeyModifiers::SHIFT) => {
+            app.enter_account_view();
+            UiAction::SubmitCommand("/account".to_string())
+        }
+        KeyCode::Char('H') if key_event.modifiers.contains(KeyModifiers::SHIFT) => {
+            app.enter_portfolio_view();
+            UiAction::SubmitCommand("/position".to_string())
+        }
+        KeyCode::Char('D') if key_event.modifiers.contains(KeyModifiers::SHIFT) => {
+            app.enter_microstructure_view();
+            UiAction::None
+        }
+        KeyCode::Char('V') if key_event.modifiers.contains(KeyModifiers::SHIFT) => {
+            app.enter_heatmap_view();
+            UiAction::None
+        }
         KeyCode::Enter => UiAction::None,
         _ => UiAction::None,
     }
 }
 
 fn handle_command_mode_keys(app: &mut AppState, key_event: KeyEvent) -> UiAction {
+    if app.history_search.is_some() {
+        return handle_history_search_keys(app, key_event);
+    }
+
+    let ctrl = key_event.modifiers.contains(KeyModifiers::CONTROL);
+    let alt = key_event.modifiers.contains(KeyModifiers::ALT);
+
     match key_event.code {
         KeyCode::Esc => {
             app.input_mode = InputMode::Normal;
@@ -113,48 +179,153 @@ fn handle_command_mode_keys(app: &mut AppState, key_event: KeyEvent) -> UiAction
             app.reset_command_suggestions();
             UiAction::None
         }
-        KeyCode::Enter => {
-            let command = app.command_buffer.trim().to_string();
-            app.input_mode = InputMode::Normal;
-            app.clear_command();
-            app.reset_command_suggestions();
-            if command.is_empty() {
-                UiAction::None
-            } else {
-                UiAction::SubmitCommand(command)
-            }
+        KeyCode::Enter => submit_command_buffer(app),
+        KeyCode::Backspace if ctrl || alt => {
+            app.delete_command_word_before();
+            UiAction::None
         }
         KeyCode::Backspace => {
-            app.command_buffer.pop();
-            app.update_command_suggestions();
+            app.delete_command_char_before();
+            UiAction::None
+        }
+        KeyCode::Delete => {
+            app.delete_command_char_at();
             UiAction::None
         }
         KeyCode::Up => {
-            app.select_previous_suggestion();
+            if app.history_navigation_active() {
+                app.recall_previous_command();
+            } else {
+                app.select_previous_suggestion();
+            }
             UiAction::None
         }
         KeyCode::Down => {
-            app.select_next_suggestion();
+            if app.command_history.is_browsing() {
+                app.recall_next_command();
+            } else {
+                app.select_next_suggestion();
+            }
             UiAction::None
         }
         KeyCode::Tab => {
             app.apply_selected_suggestion();
             UiAction::None
         }
-        KeyCode::Char(c) => {
-            if !key_event.modifiers.contains(KeyModifiers::CONTROL) {
-                app.command_buffer.push(c);
-                app.update_command_suggestions();
+        KeyCode::Left if ctrl || alt => {
+            app.move_command_cursor_word_left();
+            UiAction::None
+        }
+        KeyCode::Right if ctrl || alt => {
+            app.move_command_cursor_word_right();
+            UiAction::None
+        }
+        KeyCode::Left => {
+            app.move_command_cursor_left();
+            UiAction::None
+        }
+        KeyCode::Right => {
+            app.move_command_cursor_right();
+            UiAction::None
+        }
+        KeyCode::Home => {
+            app.move_command_cursor_home();
+            UiAction::None
+        }
+        KeyCode::End => {
+            app.move_command_cursor_end();
+            UiAction::None
+        }
+        KeyCode::Char(c) if ctrl => {
+            match c {
+                'a' => app.move_command_cursor_home(),
+                'e' => app.move_command_cursor_end(),
+                'b' => app.move_command_cursor_left(),
+                'f' => app.move_command_cursor_right(),
+                'w' | 'h' => app.delete_command_word_before(),
+                'u' => app.kill_command_to_start(),
+                'k' => app.kill_command_to_end(),
+                'r' => app.reverse_search(),
+                'n' => app.recall_next_command(),
+                _ => {}
+            }
+            UiAction::None
+        }
+        KeyCode::Char(c) if alt => {
+            match c {
+                'b' => app.move_command_cursor_word_left(),
+                'f' => app.move_command_cursor_word_right(),
+                _ => {}
             }
             UiAction::None
         }
-        KeyCode::Left | KeyCode::Right => UiAction::None,
+        KeyCode::Char(c) => {
+            app.insert_command_char(c);
+            UiAction::None
+        }
         _ => UiAction::None,
     }
 }
 
+fn handle_history_search_keys(app: &mut AppState, key_event: KeyEvent) -> UiAction {
+    let ctrl = key_event.modifiers.contains(KeyModifiers::CONTROL);
+
+    match key_event.code {
+        KeyCode::Esc => {
+            app.cancel_search();
+            UiAction::None
+        }
+        KeyCode::Char('g') if ctrl => {
+            app.cancel_search();
+            UiAction::None
+        }
+        KeyCode::Char('r') if ctrl => {
+            app.reverse_search();
+            UiAction::None
+        }
+        KeyCode::Enter => {
+            app.accept_search();
+            submit_command_buffer(app)
+        }
+        KeyCode::Backspace => {
+            app.pop_search_char();
+            UiAction::None
+        }
+        KeyCode::Char(c) if !ctrl => {
+            app.push_search_char(c);
+            UiAction::None
+        }
+        _ => {
+            // Any other key leaves search with the match ready for editing
+            app.accept_search();
+            UiAction::None
+        }
+    }
+}
+
+fn submit_command_buffer(app: &mut AppState) -> UiAction {
+    let command = app.command_buffer.trim().to_string();
+    app.input_mode = InputMode::Normal;
+    app.clear_command();
+    app.reset_command_suggestions();
+    if command.is_empty() {
+        UiAction::None
+    } else {
+        app.record_command(&command);
+        UiAction::SubmitCommand(command)
+    }
+}
+
 fn handle_alerts_mode_keys(app: &mut AppState, key_event: KeyEvent) -> UiAction {
+    if app.alerts_tab == AlertsTab::History {
+        return handle_alert_history_keys(app, key_event);
+    }
+
     match key_event.code {
+        KeyCode::Tab => {
+            app.toggle_alerts_tab();
+            UiAction::None
+        }
         KeyCode::Esc => {
             app.exit_alerts_view();
             UiAction::None
@@ -177,6 +348,23 @@ fn handle_alerts_mode_keys(app: &mut AppState, key_event: KeyEvent) -> UiAction
         KeyCode::Char('C') if key_event.modifiers.contains(KeyModifiers::SHIFT) => {
             UiAction::SubmitCommand("/alert:clear all".to_string())
         }
+        KeyCode::Char('e') | KeyCode::Enter => {
+            if let Err(e) = app.activate_alert_edit_popup() {
+                app.push_notification(e);
+            }
+            UiAction::None
+        }
+        KeyCode::Char('p') => match app.selected_alert() {
+            Some(alert) if alert.paused => {
+                UiAction::SubmitCommand(format!("/alert:resume {}", alert.id))
+            }
+            Some(alert) => UiAction::SubmitCommand(format!("/alert:pause {}", alert.id)),
+            None => UiAction::None,
+        },
+        KeyCode::Char('z') => match app.selected_alert() {
+            Some(alert) => UiAction::SubmitCommand(format!("/alert:snooze {} 30m", alert.id)),
+            None => UiAction::None,
+        },
         KeyCode::Char('r') => UiAction::SubmitCommand("/alert:list".to_string()),
         KeyCode::Char('q') => {
             app.exit_alerts_view();
@@ -186,6 +374,259 @@ fn handle_alerts_mode_keys(app: &mut AppState, key_event: KeyEvent) -> UiAction
     }
 }
 
+fn handle_scanner_mode_keys(app: &mut AppState, key_event: KeyEvent) -> UiAction {
+    match key_event.code {
+        KeyCode::Esc | KeyCode::Char('q') => {
+            app.exit_scanner_view();
+            UiAction::SubmitCommand("/scanner off".to_string())
+        }
+        KeyCode::Up | KeyCode::Char('k') => {
+            app.select_previous_scanner_row();
+            UiAction::None
+        }
+        KeyCode::Down | KeyCode::Char('j') => {
+            app.select_next_scanner_row();
+            UiAction::None
+        }
+        KeyCode::Tab | KeyCode::Char('s') => {
+            app.cycle_scanner_sort();
+            UiAction::None
+        }
+        KeyCode::Char('r') => {
+            app.toggle_scanner_sort_direction();
+            UiAction::None
+        }
+        KeyCode::Char('f') => {
+            app.cycle_scanner_quote();
+            UiAction::None
+        }
+        KeyCode::Char('v') => {
+            app.cycle_scanner_min_volume();
+            UiAction::None
+        }
+        KeyCode::Enter | KeyCode::Char('a') => match app.selected_scanner_symbol() {
+            Some(symbol) if app.symbols.contains(&symbol) => {
+                app.push_notification(format!("{} is already on the watchlist", symbol));
+                UiAction::None
+            }
+            Some(symbol) => UiAction::SubmitCommand(format!("/add {}", symbol)),
+            None => UiAction::None,
+        },
+        _ => UiAction::None,
+    }
+}
+
+fn handle_paper_mode_keys(app: &mut AppState, key_event: KeyEvent) -> UiAction {
+    match key_event.code {
+        KeyCode::Esc | KeyCode::Char('q') => {
+            app.exit_paper_view();
+            UiAction::None
+        }
+        KeyCode::Up | KeyCode::Char('k') => {
+            app.select_previous_paper_order();
+            UiAction::None
+        }
+        KeyCode::Down | KeyCode::Char('j') => {
+            app.select_next_paper_order();
+            UiAction::None
+        }
+        KeyCode::Char('b') => {
+            app.start_paper_order("buy");
+            UiAction::None
+        }
+        KeyCode::Char('s') => {
+            app.start_paper_order("sell");
+            UiAction::None
+        }
+        KeyCode::Char('c') | KeyCode::Delete => match app.selected_paper_order() {
+            Some(order) => UiAction::SubmitCommand(format!("/paper:cancel {}", order.id)),
+            None => UiAction::None,
+        },
+        KeyCode::Char('C') if key_event.modifiers.contains(KeyModifiers::SHIFT) => {
+            UiAction::SubmitCommand("/paper:cancel all".to_string())
+        }
+        _ => UiAction::None,
+    }
+}
+
+fn handle_account_mode_keys(app: &mut AppState, key_event: KeyEvent) -> UiAction {
+    match key_event.code {
+        KeyCode::Esc | KeyCode::Char('q') => {
+            app.exit_account_view();
+            UiAction::None
+        }
+        KeyCode::Char('r') => UiAction::SubmitCommand("/account".to_string()),
+        _ => UiAction::None,
+    }
+}
+
+fn handle_portfolio_mode_keys(app: &mut AppState, key_event: KeyEvent) -> UiAction {
+    match key_event.code {
+        KeyCode::Esc | KeyCode::Char('q') => {
+            app.exit_portfolio_view();
+            UiAction::None
+        }
+        KeyCode::Char('a') => {
+            app.activate_command_mode(Some("/position:add "));
+            UiAction::None
+        }
+        _ => UiAction::None,
+    }
+}
+
+fn handle_impact_mode_keys(app: &mut AppState, key_event: KeyEvent) -> UiAction {
+    match key_event.code {
+        KeyCode::Esc | KeyCode::Char('q') => {
+            app.exit_impact_view();
+            UiAction::None
+        }
+        KeyCode::Tab => {
+            app.toggle_impact_side();
+            UiAction::None
+        }
+        _ => UiAction::None,
+    }
+}
+
+fn handle_microstructure_mode_keys(app: &mut AppState, key_event: KeyEvent) -> UiAction {
+    match key_event.code {
+        KeyCode::Esc | KeyCode::Char('q') => {
+            app.exit_microstructure_view();
+            UiAction::None
+        }
+        KeyCode::Left | KeyCode::Up => {
+            app.previous_tab();
+            UiAction::None
+        }
+        KeyCode::Right | KeyCode::Down => {
+            app.next_tab();
+            UiAction::None
+        }
+        _ => UiAction::None,
+    }
+}
+
+fn handle_heatmap_mode_keys(app: &mut AppState, key_event: KeyEvent) -> UiAction {
+    match key_event.code {
+        KeyCode::Esc | KeyCode::Char('q') => {
+            app.exit_heatmap_view();
+            UiAction::None
+        }
+        KeyCode::Left | KeyCode::Up => {
+            app.previous_tab();
+            UiAction::None
+        }
+        KeyCode::Right | KeyCode::Down => {
+            app.next_tab();
+            UiAction::None
+        }
+        _ => UiAction::None,
+    }
+}
+
+fn handle_order_ticket_keys(app: &mut AppState, key_event: KeyEvent) -> UiAction {
+    // A staged order only takes a yes or no
+    if app.order_form.confirmation.is_some() {
+        return match key_event.code {
+            KeyCode::Char('y') | KeyCode::Char('Y') => {
+                app.close_order_ticket();
+                UiAction::SubmitCommand("/order:confirm".to_string())
+            }
+            KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
+                app.close_order_ticket();
+                UiAction::SubmitCommand("/order:discard".to_string())
+            }
+            _ => UiAction::None,
+        };
+    }
+
+    match key_event.code {
+        KeyCode::Esc => {
+            app.close_order_ticket();
+            UiAction::None
+        }
+        KeyCode::Up => {
+            app.cycle_order_ticket_field(true);
+            UiAction::None
+        }
+        KeyCode::Down => {
+            app.cycle_order_ticket_field(false);
+            UiAction::None
+        }
+        KeyCode::Tab => {
+            match app.order_form.active_field {
+                OrderFormField::Side => app.toggle_order_side(),
+                OrderFormField::Type => app.cycle_order_type(),
+                _ => {}
+            }
+            UiAction::None
+        }
+        KeyCode::Enter => match app.order_ticket_command() {
+            Ok(command) => {
+                app.order_form.error = None;
+                UiAction::SubmitCommand(command)
+            }
+            Err(e) => {
+                app.order_form.error = Some(e);
+                UiAction::None
+            }
+        },
+        KeyCode::Backspace => {
+            match app.order_form.active_field {
+                OrderFormField::Quantity => {
+                    app.order_form.quantity_input.pop();
+                }
+                OrderFormField::Price => {
+                    app.order_form.price_input.pop();
+                }
+                _ => {}
+            }
+            app.order_form.error = None;
+            UiAction::None
+        }
+        KeyCode::Char(c) if c.is_ascii_digit() || c == '.' => {
+            match app.order_form.active_field {
+                OrderFormField::Quantity => app.order_form.quantity_input.push(c),
+                OrderFormField::Price => app.order_form.price_input.push(c),
+                _ => {}
+            }
+            app.order_form.error = None;
+            UiAction::None
+        }
+        _ => UiAction::None,
+    }
+}
+
+fn handle_alert_history_keys(app: &mut AppState, key_event: KeyEvent) -> UiAction {
+    if app.editing_history_filter {
+        match key_event.code {
+            KeyCode::Enter | KeyCode::Esc => app.editing_history_filter = false,
+            KeyCode::Backspace => app.pop_history_filter_char(),
+            KeyCode::Char(c) if !c.is_whitespace() => app.push_history_filter_char(c),
+            _ => {}
+        }
+        return UiAction::None;
+    }
+
+    match key_event.code {
+        KeyCode::Tab => app.toggle_alerts_tab(),
+        KeyCode::Esc | KeyCode::Char('q') => app.exit_alerts_view(),
+        KeyCode::Up | KeyCode::Char('k') => app.select_previous_history(),
+        KeyCode::Down | KeyCode::Char('j') => app.select_next_history(),
+        KeyCode::Char('/') | KeyCode::Char('f') => app.editing_history_filter = true,
+        KeyCode::Backspace => {
+            app.alert_history_filter.clear();
+            app.selected_history_index = 0;
+        }
+        KeyCode::Char('x') => match app.export_alert_history() {
+            Ok(path) => app.push_notification(format!("Alert history exported to {}", path)),
+            Err(e) => app.push_notification(e),
+        },
+        _ => {}
+    }
+    UiAction::None
+}
+
 fn handle_alert_popup_keys(app: &mut AppState, key_event: KeyEvent) -> UiAction {
     match key_event.code {
         KeyCode::Esc => {
@@ -228,7 +669,24 @@ fn handle_alert_popup_keys(app: &mut AppState, key_event: KeyEvent) -> UiAction
             };
             let symbol = app.alert_form.symbol.clone();
             let direction = app.alert_direction();
+            let editing_id = app.alert_form.editing_id;
+            // The prefilled price is rounded for display, so only send it when retyped
+            let threshold = app.alert_form.price_dirty.then_some(price);
             app.deactivate_alert_popup();
+
+            if let Some(id) = editing_id {
+                return UiAction::SubmitAlertEdit {
+                    id,
+                    update: AlertUpdate {
+                        direction: Some(direction),
+                        threshold,
+                        repeat: Some(options.repeat),
+                        cooldown_ms: Some(options.cooldown_ms),
+                        hysteresis: Some(HysteresisSpec::Absolute(options.hysteresis)),
+                    },
+                };
+            }
+
             UiAction::SubmitAlert {
                 symbol,
                 direction,
@@ -260,27 +718,21 @@ fn handle_alert_popup_keys(app: &mut AppState, key_event: KeyEvent) -> UiAction
         }
         KeyCode::Char(c) => {
             match app.alert_form.active_field {
-                AlertFormField::Price => {
-                    if c.is_ascii_digit() || c == '.' || c == '-' || c == '+' {
-                        if !app.alert_form.price_dirty {
-                            app.alert_form.price_input.clear();
-                            app.alert_form.price_dirty = true;
-                        }
-                        app.alert_form.price_input.push(c);
-                        app.alert_form.error = None;
+                AlertFormField::Price if c.is_ascii_digit() || c == '.' || c == '-' || c == '+' => {
+                    if !app.alert_form.price_dirty {
+                        app.alert_form.price_input.clear();
+                        app.alert_form.price_dirty = true;
                     }
+                    app.alert_form.price_input.push(c);
+                    app.alert_form.error = None;
                 }
-                AlertFormField::Cooldown => {
-                    if c.is_ascii_digit() {
-                        app.alert_form.cooldown_input.push(c);
-                        app.alert_form.error = None;
-                    }
+                AlertFormField::Cooldown if c.is_ascii_alphanumeric() || c == '.' => {
+                    app.alert_form.cooldown_input.push(c);
+                    app.alert_form.error = None;
                 }
-                AlertFormField::Hysteresis => {
-                    if c.is_ascii_digit() || c == '.' || c == '%' {
-                        app.alert_form.hysteresis_input.push(c);
-                        app.alert_form.error = None;
-                    }
+                AlertFormField::Hysteresis if c.is_ascii_digit() || c == '.' || c == '%' => {
+                    app.alert_form.hysteresis_input.push(c);
+                    app.alert_form.error = None;
                 }
                 _ => {}
             }
@@ -17,7 +17,7 @@ use ratatui::{Terminal, backend::CrosstermBackend};
 
 use super::AppState;
 use crate::AppResult;
-use crate::session::alert_manager::{AlertDirection, AlertOptions};
+use crate::session::alert_manager::{AlertDirection, AlertOptions, AlertUpdate};
 use crate::session::session_manager::SessionStats;
 use crate::ui::ui_manager::RenderState;
 
@@ -34,6 +34,10 @@ pub enum UiAction {
         price: f64,
         options: AlertOptions,
     },
+    SubmitAlertEdit {
+        id: u64,
+        update: AlertUpdate,
+    },
     QuitRequested,
 }
 
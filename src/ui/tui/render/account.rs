@@ -0,0 +1,235 @@
+use chrono::{Local, TimeZone};
+use ratatui::Frame;
+use ratatui::layout::{Alignment, Constraint, Direction, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::widgets::{Block, Borders, Cell, Clear, Paragraph, Row, Table};
+
+use crate::account::StreamStatus;
+use crate::ui::AppState;
+
+use super::format_amount;
+use super::layout::centered_rect;
+
+pub(super) fn render_account_overlay(frame: &mut Frame<'_>, app: &AppState) {
+    let overlay_area = centered_rect(85, 75, frame.size());
+    frame.render_widget(Clear, overlay_area);
+
+    let block = Block::default()
+        .title(" Account ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .style(Style::default().bg(Color::Black));
+    frame.render_widget(block.clone(), overlay_area);
+
+    let inner = block.inner(overlay_area);
+    let balance_rows = app.account.balances.len().clamp(1, 8) as u16 + 1;
+    let layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1),
+            Constraint::Length(balance_rows + 1),
+            Constraint::Min(4),
+            Constraint::Length(7),
+            Constraint::Length(1),
+        ])
+        .margin(1)
+        .split(inner);
+
+    render_status(frame, app, layout[0]);
+    render_balances(frame, app, layout[1]);
+    render_open_orders(frame, app, layout[2]);
+    render_executions(frame, app, layout[3]);
+
+    let hints = Paragraph::new("r refresh   Esc close").style(Style::default().fg(Color::Gray));
+    frame.render_widget(hints, layout[4]);
+}
+
+fn render_status(frame: &mut Frame<'_>, app: &AppState, area: Rect) {
+    let color = match app.account.status {
+        StreamStatus::Live => Color::Green,
+        StreamStatus::Connecting | StreamStatus::Reconnecting { .. } => Color::Yellow,
+        StreamStatus::Failed { .. } => Color::Red,
+    };
+    let updated = if app.account.updated_ms == 0 {
+        "-".to_string()
+    } else {
+        format_time(app.account.updated_ms)
+    };
+    let status = Paragraph::new(format!(
+        "Stream: {}   Updated: {}",
+        app.account.status.describe(),
+        updated
+    ))
+    .style(Style::default().fg(color));
+    frame.render_widget(status, area);
+}
+
+fn render_balances(frame: &mut Frame<'_>, app: &AppState, area: Rect) {
+    if app.account.balances.is_empty() {
+        let placeholder = Paragraph::new("No balances")
+            .style(Style::default().fg(Color::Gray))
+            .block(section_block(" Balances "));
+        frame.render_widget(placeholder, area);
+        return;
+    }
+
+    let visible = (area.height as usize).saturating_sub(2);
+    let rows: Vec<Row> = app
+        .account
+        .balances
+        .iter()
+        .take(visible)
+        .map(|balance| {
+            Row::new(vec![
+                Cell::from(balance.asset.clone()),
+                Cell::from(format_amount(balance.free)),
+                Cell::from(format_amount(balance.locked)),
+                Cell::from(format_amount(balance.total())),
+            ])
+        })
+        .collect();
+
+    let widths = [
+        Constraint::Length(10),
+        Constraint::Length(16),
+        Constraint::Length(16),
+        Constraint::Length(16),
+    ];
+    let table = Table::new(rows, widths)
+        .header(header_row(&["Asset", "Free", "Locked", "Total"]))
+        .block(section_block(" Balances "))
+        .column_spacing(1);
+    frame.render_widget(table, area);
+}
+
+fn render_open_orders(frame: &mut Frame<'_>, app: &AppState, area: Rect) {
+    let title = format!(" Open Orders ({}) ", app.account.open_orders.len());
+    let block = section_block(&title);
+    if app.account.open_orders.is_empty() {
+        let placeholder = Paragraph::new("No open orders")
+            .style(Style::default().fg(Color::Gray))
+            .alignment(Alignment::Center)
+            .block(block);
+        frame.render_widget(placeholder, area);
+        return;
+    }
+
+    let visible = (area.height as usize).saturating_sub(2);
+    let rows: Vec<Row> = app
+        .account
+        .open_orders
+        .iter()
+        .take(visible)
+        .map(|order| {
+            let price = if order.price > 0.0 {
+                app.format_price(&order.symbol, order.price)
+            } else {
+                "-".to_string()
+            };
+            Row::new(vec![
+                Cell::from(order.order_id.to_string()),
+                Cell::from(order.side.clone()).style(side_style(&order.side)),
+                Cell::from(order.symbol.clone()),
+                Cell::from(order.order_type.clone()),
+                Cell::from(price),
+                Cell::from(format_amount(order.quantity)),
+                Cell::from(format_amount(order.filled_qty)),
+                Cell::from(order.status.clone()),
+            ])
+        })
+        .collect();
+
+    let widths = [
+        Constraint::Length(12),
+        Constraint::Length(5),
+        Constraint::Length(12),
+        Constraint::Length(18),
+        Constraint::Length(12),
+        Constraint::Length(12),
+        Constraint::Length(12),
+        Constraint::Length(16),
+    ];
+    let table = Table::new(rows, widths)
+        .header(header_row(&[
+            "Id", "Side", "Symbol", "Type", "Price", "Qty", "Filled", "Status",
+        ]))
+        .block(block)
+        .column_spacing(1);
+    frame.render_widget(table, area);
+}
+
+fn render_executions(frame: &mut Frame<'_>, app: &AppState, area: Rect) {
+    let visible = (area.height as usize).saturating_sub(3);
+    let rows: Vec<Row> = app
+        .account
+        .executions
+        .iter()
+        .take(visible)
+        .map(|execution| {
+            let price = if execution.last_qty > 0.0 {
+                app.format_price(&execution.symbol, execution.last_price)
+            } else {
+                "-".to_string()
+            };
+            Row::new(vec![
+                Cell::from(format_time(execution.time_ms)),
+                Cell::from(execution.order_id.to_string()),
+                Cell::from(execution.side.clone()).style(side_style(&execution.side)),
+                Cell::from(execution.symbol.clone()),
+                Cell::from(execution.execution_type.clone()),
+                Cell::from(price),
+                Cell::from(format_amount(execution.last_qty)),
+                Cell::from(execution.status.clone()),
+            ])
+        })
+        .collect();
+
+    let widths = [
+        Constraint::Length(9),
+        Constraint::Length(12),
+        Constraint::Length(5),
+        Constraint::Length(12),
+        Constraint::Length(10),
+        Constraint::Length(12),
+        Constraint::Length(12),
+        Constraint::Length(16),
+    ];
+    let table = Table::new(rows, widths)
+        .header(header_row(&[
+            "Time", "Order", "Side", "Symbol", "Exec", "Price", "Qty", "Status",
+        ]))
+        .block(section_block(" Recent Executions "))
+        .column_spacing(1);
+    frame.render_widget(table, area);
+}
+
+fn section_block(title: &str) -> Block<'_> {
+    Block::default()
+        .title(title)
+        .borders(Borders::TOP)
+        .border_style(Style::default().fg(Color::DarkGray))
+}
+
+fn header_row<'a>(titles: &[&'a str]) -> Row<'a> {
+    Row::new(titles.to_vec()).style(
+        Style::default()
+            .fg(Color::Cyan)
+            .add_modifier(Modifier::BOLD),
+    )
+}
+
+fn side_style(side: &str) -> Style {
+    match side {
+        "BUY" => Style::default().fg(Color::Green),
+        "SELL" => Style::default().fg(Color::Red),
+        _ => Style::default(),
+    }
+}
+
+fn format_time(time_ms: u64) -> String {
+    Local
+        .timestamp_millis_opt(time_ms as i64)
+        .single()
+        .map(|ts| ts.format("%H:%M:%S").to_string())
+        .unwrap_or_default()
+}
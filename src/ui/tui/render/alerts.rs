@@ -1,11 +1,13 @@
+use chrono::{Local, TimeZone};
 use ratatui::Frame;
 use ratatui::layout::{Alignment, Constraint, Direction, Layout, Rect};
 use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, Cell, Clear, Paragraph, Row, Table, Wrap};
 
-use crate::session::alert_manager::{AlertDirection, AlertRepeat};
-use crate::ui::{AlertFormField, AppState};
+use crate::session::alert_manager::{AlertDirection, AlertInput, AlertRepeat};
+use crate::session::command_router::format_duration_ms;
+use crate::ui::{AlertFormField, AlertsTab, AppState};
 
 use super::layout::centered_rect;
 
@@ -24,6 +26,7 @@ pub(super) fn render_alerts_overlay(frame: &mut Frame<'_>, app: &AppState) {
     let layout = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
+            Constraint::Length(1),
             Constraint::Length(1),
             Constraint::Min(3),
             Constraint::Length(1),
@@ -31,26 +34,62 @@ pub(super) fn render_alerts_overlay(frame: &mut Frame<'_>, app: &AppState) {
         .margin(1)
         .split(inner);
 
+    let tab_style = |tab: AlertsTab| {
+        if app.alerts_tab == tab {
+            Style::default()
+                .fg(Color::Black)
+                .bg(Color::LightCyan)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(Color::Gray)
+        }
+    };
+    let tabs = Line::from(vec![
+        Span::styled(" Active ", tab_style(AlertsTab::Active)),
+        Span::raw("  "),
+        Span::styled(
+            format!(" History ({}) ", app.alert_history.len()),
+            tab_style(AlertsTab::History),
+        ),
+    ]);
+    frame.render_widget(Paragraph::new(tabs), layout[0]);
+
+    match app.alerts_tab {
+        AlertsTab::Active => render_active_alerts(frame, app, &layout[1..]),
+        AlertsTab::History => render_alert_history(frame, app, &layout[1..]),
+    }
+}
+
+fn render_active_alerts(frame: &mut Frame<'_>, app: &AppState, areas: &[Rect]) {
+    let now_ms = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64;
     let total = app.alerts.len();
-    let triggered = app.alerts.iter().filter(|a| a.triggered).count();
-    let armed = total.saturating_sub(triggered);
+    let inactive = app.alerts.iter().filter(|a| !a.is_active(now_ms)).count();
+    let triggered = app
+        .alerts
+        .iter()
+        .filter(|a| a.is_active(now_ms) && a.triggered)
+        .count();
+    let armed = total.saturating_sub(triggered + inactive);
     let summary = Paragraph::new(format!(
-        "Total: {}  | Armed: {}  | Triggered: {}",
-        total, armed, triggered
+        "Total: {}  | Armed: {}  | Triggered: {}  | Paused/Snoozed: {}",
+        total, armed, triggered, inactive
     ))
     .style(Style::default().fg(Color::Gray));
-    frame.render_widget(summary, layout[0]);
+    frame.render_widget(summary, areas[0]);
 
     if app.alerts.is_empty() {
         let placeholder = Paragraph::new("No alerts configured")
             .style(Style::default().fg(Color::Gray))
             .alignment(Alignment::Center);
-        frame.render_widget(placeholder, layout[1]);
+        frame.render_widget(placeholder, areas[1]);
     } else {
         let widths = [
             Constraint::Length(6),
             Constraint::Length(10),
-            Constraint::Length(6),
+            Constraint::Length(11),
             Constraint::Length(12),
             Constraint::Length(12),
             Constraint::Length(10),
@@ -64,25 +103,26 @@ pub(super) fn render_alerts_overlay(frame: &mut Frame<'_>, app: &AppState) {
             .iter()
             .enumerate()
             .map(|(idx, alert)| {
-                let direction = match alert.direction {
-                    AlertDirection::Above => "Above",
-                    AlertDirection::Below => "Below",
-                };
-                let status = if alert.triggered {
+                let direction = direction_label(alert.input, alert.direction);
+                let status = if alert.paused {
+                    "Paused"
+                } else if alert.is_snoozed(now_ms) {
+                    "Snoozed"
+                } else if alert.triggered {
                     "Triggered"
                 } else {
                     "Armed"
                 };
                 let last_price = alert
                     .last_price
-                    .map(|p| format!("{:.4}", p))
+                    .map(|p| format_value(app, &alert.symbol, alert.input, p))
                     .unwrap_or_else(|| "-".to_string());
                 let mode = match alert.repeat {
                     AlertRepeat::Once => "Once",
                     AlertRepeat::Repeat => "Repeat",
                 };
                 let cooldown = if alert.cooldown_ms > 0 {
-                    format!("{}s", alert.cooldown_ms / 1_000)
+                    format_duration_ms(alert.cooldown_ms)
                 } else {
                     "-".to_string()
                 };
@@ -96,7 +136,12 @@ pub(super) fn render_alerts_overlay(frame: &mut Frame<'_>, app: &AppState) {
                     Cell::from(format!("#{}", alert.id)),
                     Cell::from(alert.symbol.clone()),
                     Cell::from(direction),
-                    Cell::from(format!("{:.4}", alert.threshold)),
+                    Cell::from(format_value(
+                        app,
+                        &alert.symbol,
+                        alert.input,
+                        alert.threshold,
+                    )),
                     Cell::from(last_price),
                     Cell::from(status),
                     Cell::from(mode),
@@ -140,19 +185,154 @@ pub(super) fn render_alerts_overlay(frame: &mut Frame<'_>, app: &AppState) {
             )
             .column_spacing(1);
 
-        frame.render_widget(table, layout[1]);
+        frame.render_widget(table, areas[1]);
     }
 
-    let hints = Paragraph::new("↑/↓ move   d delete   Shift+C clear all   r refresh   Esc close")
+    let hints = Paragraph::new(
+        "↑/↓ move   e edit   p pause/resume   z snooze 30m   d delete   Shift+C clear all   r refresh   Tab history   Esc close",
+    )
         .style(Style::default().fg(Color::Gray));
-    frame.render_widget(hints, layout[2]);
+    frame.render_widget(hints, areas[2]);
+}
+
+fn render_alert_history(frame: &mut Frame<'_>, app: &AppState, areas: &[Rect]) {
+    let entries = app.filtered_alert_history();
+
+    let filter_label = if app.editing_history_filter {
+        format!("{}_", app.alert_history_filter)
+    } else if app.alert_history_filter.is_empty() {
+        "all".to_string()
+    } else {
+        app.alert_history_filter.clone()
+    };
+    let filter_style = if app.editing_history_filter {
+        Style::default()
+            .fg(Color::Black)
+            .bg(Color::LightCyan)
+            .add_modifier(Modifier::BOLD)
+    } else {
+        Style::default().fg(Color::White)
+    };
+    let summary = Line::from(vec![
+        Span::styled(
+            format!(
+                "Fired: {} of {}  | Filter: ",
+                entries.len(),
+                app.alert_history.len()
+            ),
+            Style::default().fg(Color::Gray),
+        ),
+        Span::styled(filter_label, filter_style),
+    ]);
+    frame.render_widget(Paragraph::new(summary), areas[0]);
+
+    if entries.is_empty() {
+        let placeholder = Paragraph::new("No alerts have fired")
+            .style(Style::default().fg(Color::Gray))
+            .alignment(Alignment::Center);
+        frame.render_widget(placeholder, areas[1]);
+    } else {
+        let widths = [
+            Constraint::Length(19),
+            Constraint::Length(6),
+            Constraint::Length(10),
+            Constraint::Length(18),
+            Constraint::Length(12),
+            Constraint::Length(12),
+            Constraint::Length(12),
+        ];
+
+        // Keep the selected row visible in the available height
+        let visible = areas[1].height.saturating_sub(1).max(1) as usize;
+        let offset = app.selected_history_index.saturating_sub(visible - 1);
+
+        let rows: Vec<Row> = entries
+            .iter()
+            .enumerate()
+            .skip(offset)
+            .take(visible)
+            .map(|(idx, entry)| {
+                let time = Local
+                    .timestamp_millis_opt(entry.triggered_at_ms as i64)
+                    .single()
+                    .map(|ts| ts.format("%Y-%m-%d %H:%M:%S").to_string())
+                    .unwrap_or_else(|| "-".to_string());
+                let direction = direction_label(entry.input, entry.direction);
+                let book_price = |price: Option<f64>| {
+                    price
+                        .map(|p| app.format_price(&entry.symbol, p))
+                        .unwrap_or_else(|| "-".to_string())
+                };
+
+                let mut row = Row::new(vec![
+                    Cell::from(time),
+                    Cell::from(format!("#{}", entry.alert_id)),
+                    Cell::from(entry.symbol.clone()),
+                    Cell::from(format!(
+                        "{} {}",
+                        direction,
+                        format_value(app, &entry.symbol, entry.input, entry.threshold)
+                    )),
+                    Cell::from(format_value(app, &entry.symbol, entry.input, entry.price)),
+                    Cell::from(book_price(entry.best_bid)),
+                    Cell::from(book_price(entry.best_ask)),
+                ]);
+
+                if idx == app.selected_history_index {
+                    row = row.style(
+                        Style::default()
+                            .fg(Color::White)
+                            .bg(Color::Blue)
+                            .add_modifier(Modifier::BOLD),
+                    );
+                }
+
+                row
+            })
+            .collect();
+
+        let table = Table::new(rows, widths)
+            .header(
+                Row::new(vec![
+                    "Time",
+                    "ID",
+                    "Symbol",
+                    "Condition",
+                    "Price",
+                    "Bid",
+                    "Ask",
+                ])
+                .style(
+                    Style::default()
+                        .fg(Color::Cyan)
+                        .add_modifier(Modifier::BOLD),
+                ),
+            )
+            .column_spacing(1);
+
+        frame.render_widget(table, areas[1]);
+    }
+
+    let hint_text = if app.editing_history_filter {
+        "type symbol filter   Backspace delete   Enter/Esc done"
+    } else {
+        "↑/↓ move   / filter   Backspace clear filter   x export CSV   Tab active alerts   Esc close"
+    };
+    frame.render_widget(
+        Paragraph::new(hint_text).style(Style::default().fg(Color::Gray)),
+        areas[2],
+    );
 }
 
 pub(super) fn render_alert_popup(frame: &mut Frame<'_>, _area: Rect, app: &AppState) {
     // Centered box occupying a portion of the screen
     let popup_area = centered_rect(62, 32, frame.size());
+    let title = match app.alert_form.editing_id {
+        Some(id) => format!(" Edit Alert #{} ", id),
+        None => " Add Alert ".to_string(),
+    };
     let block = Block::default()
-        .title(" Add Alert ")
+        .title(title)
         .borders(Borders::ALL)
         .border_style(Style::default().fg(Color::Cyan))
         .style(Style::default().bg(Color::Magenta));
@@ -310,7 +490,7 @@ pub(super) fn render_alert_popup(frame: &mut Frame<'_>, _area: Rect, app: &AppSt
         value_style
     };
     let cooldown_line = Line::from(vec![
-        Span::styled("Cooldown(s): ", cooldown_label_style),
+        Span::styled("Cooldown: ", cooldown_label_style),
         Span::styled(cooldown_value, cooldown_value_style),
     ]);
     frame.render_widget(
@@ -359,8 +539,34 @@ pub(super) fn render_alert_popup(frame: &mut Frame<'_>, _area: Rect, app: &AppSt
 
     // Hint row
     frame.render_widget(
-        Paragraph::new("Hint: hysteresis supports % (e.g. 0.2%), cooldown in seconds")
+        Paragraph::new("Hint: hysteresis supports % (e.g. 0.2%), cooldown takes 90s, 500ms, 5m")
             .style(Style::default().fg(Color::Gray)),
         inner_layout[8],
     );
 }
+
+/// Direction column, prefixed with the input for trade flow alerts
+fn direction_label(input: AlertInput, direction: AlertDirection) -> String {
+    let direction = match direction {
+        AlertDirection::Above => "Above",
+        AlertDirection::Below => "Below",
+    };
+    match input {
+        AlertInput::Price => direction.to_string(),
+        input => format!("{} {}", input.label(), direction),
+    }
+}
+
+/// Prices and mids use the symbol's tick size; trade flow and book values are plain numbers
+fn format_value(app: &AppState, symbol: &str, input: AlertInput, value: f64) -> String {
+    match input {
+        AlertInput::Price | AlertInput::Mid => app.format_price(symbol, value),
+        AlertInput::Spread => format!("{:.2} bps", value),
+        AlertInput::VwapDeviation => format!("{:+.2}%", value),
+        AlertInput::MicropriceOffset => format!("{:+.2} bps", value),
+        AlertInput::DepthImbalance => format!("{:+.3}", value),
+        AlertInput::Cvd | AlertInput::TradeRate | AlertInput::OrderFlowImbalance => {
+            format!("{:.2}", value)
+        }
+    }
+}
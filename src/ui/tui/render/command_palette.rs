@@ -25,6 +25,14 @@ pub(super) fn render_command_palette(
         InputMode::Command => " Command Entry ",
         InputMode::AlertPopup => " Command Hints ",
         InputMode::Alerts => " Alerts ",
+        InputMode::Scanner => " Scanner ",
+        InputMode::Paper => " Paper Trading ",
+        InputMode::Account => " Account ",
+        InputMode::OrderTicket => " Order Ticket ",
+        InputMode::Portfolio => " Portfolio ",
+        InputMode::Impact => " Market Impact ",
+        InputMode::Microstructure => " Book Microstructure ",
+        InputMode::Heatmap => " Liquidity Heatmap ",
     };
 
     let block = Block::default().title(title).borders(Borders::ALL);
@@ -37,26 +45,67 @@ pub(super) fn render_command_palette(
             .constraints([Constraint::Length(1), Constraint::Min(1)])
             .split(inner);
 
-        let input_line = Paragraph::new(Text::from(vec![Line::from(vec![
-            Span::styled(">", Style::default().fg(Color::Cyan)),
-            Span::raw(" "),
-            Span::raw(app.command_buffer.clone()),
-        ])]))
-        .wrap(Wrap { trim: true });
+        let (input_spans, cursor_column) = match &app.history_search {
+            Some(search) => {
+                let prompt = format!("(reverse-i-search)`{}': ", search.query);
+                let column = prompt.chars().count() - 3;
+                let matched = app.search_match().unwrap_or_default().to_string();
+                let prompt_style = if search.match_index.is_some() || search.query.is_empty() {
+                    Style::default().fg(Color::Cyan)
+                } else {
+                    Style::default().fg(Color::Red)
+                };
+                (
+                    vec![Span::styled(prompt, prompt_style), Span::raw(matched)],
+                    column,
+                )
+            }
+            None => (
+                vec![
+                    Span::styled(">", Style::default().fg(Color::Cyan)),
+                    Span::raw(" "),
+                    Span::raw(app.command_buffer.clone()),
+                ],
+                2 + app.command_cursor_column(), // ">" plus trailing space
+            ),
+        };
+
+        let input_line =
+            Paragraph::new(Text::from(vec![Line::from(input_spans)])).wrap(Wrap { trim: true });
         frame.render_widget(input_line, layout[0]);
 
-        let prompt_offset = 2u16; // ">" plus trailing space
         let max_cursor_x = layout[0]
             .x
             .saturating_add(layout[0].width.saturating_sub(1));
         let cursor_x = layout[0]
             .x
-            .saturating_add(prompt_offset)
-            .saturating_add(app.command_buffer.len() as u16)
+            .saturating_add(cursor_column as u16)
             .min(max_cursor_x);
         frame.set_cursor(cursor_x, layout[0].y);
 
-        let suggestion_items: Vec<ListItem> = if app.filtered_commands.is_empty() {
+        let suggestion_count = if app.argument_suggestions.is_empty() {
+            app.filtered_commands.len()
+        } else {
+            app.argument_suggestions.len()
+        };
+
+        let suggestion_items: Vec<ListItem> = if !app.argument_suggestions.is_empty() {
+            app.argument_suggestions
+                .iter()
+                .map(|suggestion| {
+                    ListItem::new(Line::from(vec![
+                        Span::styled(
+                            suggestion.value.clone(),
+                            Style::default()
+                                .fg(Color::Cyan)
+                                .add_modifier(Modifier::BOLD),
+                        ),
+                        Span::raw("  "),
+                        Span::styled(suggestion.detail.clone(), Style::default().fg(Color::Gray)),
+                    ]))
+                })
+                .collect()
+        } else if app.filtered_commands.is_empty() {
             vec![ListItem::new(Span::styled(
                 "No matching commands",
                 Style::default().fg(Color::DarkGray),
@@ -82,10 +131,10 @@ pub(super) fn render_command_palette(
         };
 
         let mut state = ListState::default();
-        if !app.filtered_commands.is_empty() {
+        if suggestion_count > 0 {
             state.select(Some(
                 app.selected_command_index
-                    .min(app.filtered_commands.len().saturating_sub(1)),
+                    .min(suggestion_count.saturating_sub(1)),
             ));
         }
 
@@ -105,12 +154,28 @@ pub(super) fn render_command_palette(
             Span::raw(": Switch symbol   "),
             Span::styled("j/k", Style::default().fg(Color::Cyan)),
             Span::raw(": Scroll logs   "),
+            Span::styled("i", Style::default().fg(Color::Cyan)),
+            Span::raw(": Chart bars   "),
             Span::styled("a", Style::default().fg(Color::Cyan)),
             Span::raw(": Alert popup   "),
+            Span::styled("o", Style::default().fg(Color::Cyan)),
+            Span::raw(": Order ticket   "),
             Span::styled("Shift+A", Style::default().fg(Color::Cyan)),
             Span::raw(": Alerts view   "),
+            Span::styled("Shift+S", Style::default().fg(Color::Cyan)),
+            Span::raw(": Scanner   "),
+            Span::styled("Shift+P", Style::default().fg(Color::Cyan)),
+            Span::raw(": Paper   "),
+            Span::styled("Shift+B", Style::default().fg(Color::Cyan)),
+            Span::raw(": Account   "),
+            Span::styled("Shift+H", Style::default().fg(Color::Cyan)),
+            Span::raw(": Holdings   "),
+            Span::styled("Shift+D", Style::default().fg(Color::Cyan)),
+            Span::raw(": Book microstructure   "),
+            Span::styled("Shift+V", Style::default().fg(Color::Cyan)),
+            Span::raw(": Liquidity heatmap   "),
             Span::styled("/", Style::default().fg(Color::Cyan)),
-            Span::raw(": Command palette   "),
+            Span::raw(": Command palette (↑ history, Ctrl+R search)   "),
             Span::styled("Space", Style::default().fg(Color::Cyan)),
             Span::raw(": Pause   "),
             Span::styled("q", Style::default().fg(Color::Cyan)),
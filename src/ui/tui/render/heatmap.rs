@@ -0,0 +1,134 @@
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Direction, Layout};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+
+use crate::market_data::{HeatCell, HeatmapGrid};
+use crate::ui::AppState;
+
+use super::layout::centered_rect;
+
+/// Marker drawn in the cell holding the last trade price
+const TRADE_MARKER: &str = "•";
+
+pub(super) fn render_heatmap_overlay(frame: &mut Frame<'_>, app: &AppState) {
+    let symbol = app.current_symbol().cloned().unwrap_or_default();
+    let popup_area = centered_rect(90, 80, frame.size());
+    let block = Block::default()
+        .title(format!(" Liquidity Heatmap: {} ", symbol))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .style(Style::default().bg(Color::Black));
+    frame.render_widget(Clear, popup_area);
+    frame.render_widget(block.clone(), popup_area);
+
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(4),
+            Constraint::Length(1),
+            Constraint::Length(1),
+        ])
+        .margin(1)
+        .split(block.inner(popup_area));
+
+    frame.render_widget(
+        Paragraph::new("←/→ switch symbol   Esc close").style(Style::default().fg(Color::Gray)),
+        rows[2],
+    );
+
+    let history = app.market_data.get(&symbol).map(|state| &state.liquidity);
+    let settings = &app.heatmap;
+    let gutter = 12u16.min(rows[0].width / 3);
+    let heat_width = rows[0].width.saturating_sub(gutter) as usize;
+    let height = rows[0].height as usize;
+    let Some(grid) = history.and_then(|history| history.grid(heat_width, height)) else {
+        frame.render_widget(
+            Paragraph::new(format!("Waiting for {} order book samples", symbol))
+                .style(Style::default().fg(Color::Gray)),
+            rows[0],
+        );
+        return;
+    };
+
+    let label_rows = [0, height / 2, height.saturating_sub(1)];
+    let lines: Vec<Line> = (0..height)
+        .map(|row| {
+            let label = if label_rows.contains(&row) {
+                app.format_price(&symbol, grid.row_price(row, height))
+            } else {
+                String::new()
+            };
+            let mut spans = vec![
+                Span::styled(
+                    format!(
+                        "{:>width$} ",
+                        label,
+                        width = gutter.saturating_sub(1) as usize
+                    ),
+                    Style::default().fg(Color::Gray),
+                ),
+                // Newest sample sits against the right edge
+                Span::raw(" ".repeat(heat_width.saturating_sub(grid.columns.len()))),
+            ];
+            spans.extend(grid.columns.iter().map(|column| {
+                let style = cell_style(column.cells[row], &grid);
+                if column.trade_row == Some(row) {
+                    Span::styled(
+                        TRADE_MARKER,
+                        style.fg(Color::Yellow).add_modifier(Modifier::BOLD),
+                    )
+                } else {
+                    Span::styled(" ", style)
+                }
+            }));
+            Line::from(spans)
+        })
+        .collect();
+    frame.render_widget(Paragraph::new(lines), rows[0]);
+
+    let samples = history.map_or(0, |history| history.len());
+    let legend = Line::from(vec![
+        Span::styled("  ", Style::default().bg(bid_color(1.0))),
+        Span::raw(" bids  "),
+        Span::styled("  ", Style::default().bg(ask_color(1.0))),
+        Span::raw(" asks  "),
+        Span::styled(TRADE_MARKER, Style::default().fg(Color::Yellow)),
+        Span::raw(" last trade   "),
+        Span::styled(
+            format!(
+                "{} samples every {} ms, top {} levels, brightest {:.4}",
+                samples, settings.interval_ms, settings.levels, grid.max_size
+            ),
+            Style::default().fg(Color::Gray),
+        ),
+    ]);
+    frame.render_widget(Paragraph::new(legend), rows[1]);
+}
+
+/// Background shade for a bucket; square root scaling keeps thin levels visible next to walls
+fn cell_style(cell: HeatCell, grid: &HeatmapGrid) -> Style {
+    if grid.max_size <= 0.0 || (cell.bid <= 0.0 && cell.ask <= 0.0) {
+        return Style::default();
+    }
+    let intensity = |size: f64| (size / grid.max_size).sqrt();
+    let color = if cell.bid >= cell.ask {
+        bid_color(intensity(cell.bid))
+    } else {
+        ask_color(intensity(cell.ask))
+    };
+    Style::default().bg(color)
+}
+
+fn bid_color(intensity: f64) -> Color {
+    Color::Rgb(0, shade(intensity), shade(intensity) / 2)
+}
+
+fn ask_color(intensity: f64) -> Color {
+    Color::Rgb(shade(intensity), 0, shade(intensity) / 3)
+}
+
+fn shade(intensity: f64) -> u8 {
+    (40.0 + 215.0 * intensity.clamp(0.0, 1.0)) as u8
+}
@@ -0,0 +1,153 @@
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Direction, Layout};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+
+use crate::market_data::{ImpactSize, TakerSide};
+use crate::paper::resolve_assets;
+use crate::ui::AppState;
+
+use super::format_amount;
+use super::layout::centered_rect;
+
+/// Slippage above this is highlighted as expensive
+const HIGH_SLIPPAGE_BPS: f64 = 10.0;
+
+pub(super) fn render_impact_overlay(frame: &mut Frame<'_>, app: &AppState) {
+    let Some(request) = &app.impact else {
+        return;
+    };
+    let popup_area = centered_rect(60, 40, frame.size());
+    let block = Block::default()
+        .title(format!(" Market Impact: {} ", request.symbol))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .style(Style::default().bg(Color::Black));
+    frame.render_widget(Clear, popup_area);
+    frame.render_widget(block.clone(), popup_area);
+
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(1), Constraint::Length(1)])
+        .margin(1)
+        .split(block.inner(popup_area));
+
+    let info = app.exchange_info.get(&request.symbol);
+    let (base, quote) = resolve_assets(&request.symbol, info.as_ref())
+        .unwrap_or_else(|_| ("base".to_string(), "quote".to_string()));
+    let (side, side_color) = match request.side {
+        TakerSide::Buy => ("BUY", Color::Green),
+        TakerSide::Sell => ("SELL", Color::Red),
+    };
+    let size = match request.size {
+        ImpactSize::Base(quantity) => format!("{} {}", quantity, base),
+        ImpactSize::Quote(notional) => format!("{} {} of {}", notional, quote, base),
+    };
+    let label = Style::default().fg(Color::Cyan);
+    let value = Style::default()
+        .fg(Color::White)
+        .add_modifier(Modifier::BOLD);
+
+    let mut lines = vec![Line::from(vec![
+        Span::styled("Market ", label),
+        Span::styled(
+            side,
+            Style::default().fg(side_color).add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(format!(" {}", size), value),
+    ])];
+
+    let book = app
+        .market_data
+        .get(&request.symbol)
+        .and_then(|state| state.orderbook.as_ref());
+    match book.map(|book| book.market_impact(request.side, request.size)) {
+        None => lines.push(Line::styled(
+            format!("Waiting for the {} order book", request.symbol),
+            Style::default().fg(Color::Gray),
+        )),
+        Some(impact) if impact.average_price.is_none() => lines.push(Line::styled(
+            "No liquidity on that side of the book",
+            Style::default().fg(Color::LightRed),
+        )),
+        Some(impact) => {
+            let price = |price: Option<f64>| {
+                price
+                    .map(|price| app.format_price(&request.symbol, price))
+                    .unwrap_or_else(|| "-".to_string())
+            };
+            let bps = |bps: Option<f64>| match bps {
+                Some(bps) => Span::styled(format!("  {:+.2} bps", bps), slippage_style(bps)),
+                None => Span::raw(""),
+            };
+
+            lines.push(Line::from(vec![
+                Span::styled("Mid:          ", label),
+                Span::styled(price(impact.mid_price), value),
+            ]));
+            lines.push(Line::from(vec![
+                Span::styled("Average fill: ", label),
+                Span::styled(price(impact.average_price), value),
+                bps(impact.slippage_bps()),
+            ]));
+            lines.push(Line::from(vec![
+                Span::styled("Worst price:  ", label),
+                Span::styled(price(impact.worst_price), value),
+                bps(impact.worst_slippage_bps()),
+            ]));
+            lines.push(Line::from(vec![
+                Span::styled("Levels:       ", label),
+                Span::styled(impact.levels_consumed.to_string(), value),
+            ]));
+            lines.push(Line::from(vec![
+                Span::styled("Fills:        ", label),
+                Span::styled(
+                    format!(
+                        "{} {} for {:.2} {}",
+                        format_amount(impact.filled_quantity),
+                        base,
+                        impact.filled_notional,
+                        quote
+                    ),
+                    value,
+                ),
+            ]));
+            if impact.sufficient_depth() {
+                lines.push(Line::styled(
+                    "Visible depth covers the whole order",
+                    Style::default().fg(Color::Green),
+                ));
+            } else {
+                let unit = match request.size {
+                    ImpactSize::Base(_) => &base,
+                    ImpactSize::Quote(_) => &quote,
+                };
+                lines.push(Line::styled(
+                    format!(
+                        "Not enough depth: {} {} left unfilled",
+                        format_amount(impact.unfilled),
+                        unit
+                    ),
+                    Style::default()
+                        .fg(Color::Yellow)
+                        .add_modifier(Modifier::BOLD),
+                ));
+            }
+        }
+    }
+    frame.render_widget(Paragraph::new(lines), rows[0]);
+
+    frame.render_widget(
+        Paragraph::new("Tab switch side   Esc close").style(Style::default().fg(Color::Gray)),
+        rows[1],
+    );
+}
+
+fn slippage_style(bps: f64) -> Style {
+    if bps >= HIGH_SLIPPAGE_BPS {
+        Style::default().fg(Color::LightRed)
+    } else {
+        Style::default().fg(Color::Gray)
+    }
+}
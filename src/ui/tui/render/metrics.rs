@@ -2,18 +2,164 @@ use ratatui::Frame;
 use ratatui::layout::{Constraint, Direction, Layout, Rect};
 use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::{Line, Span};
-use ratatui::widgets::{Block, Borders, Gauge, Paragraph, Wrap};
+use ratatui::widgets::{Block, Borders, Cell, Gauge, Paragraph, Row, Table, Wrap};
 
+use crate::metrics::ConnectionStatus;
 use crate::ui::AppState;
 
+/// Maximum height of the per-symbol table including borders and header
+const MAX_SYMBOL_TABLE_HEIGHT: u16 = 12;
+
 pub(super) fn render_metrics(frame: &mut Frame<'_>, area: Rect, app: &mut AppState) {
+    let row_count = if app.metrics_show_streams {
+        app.connection_metrics
+            .symbols
+            .iter()
+            .map(|s| s.streams.len().max(1))
+            .sum::<usize>()
+    } else {
+        app.connection_metrics.symbols.len()
+    };
+    let table_height = (row_count as u16 + 3).clamp(4, MAX_SYMBOL_TABLE_HEIGHT);
+
     let chunks = Layout::default()
         .direction(Direction::Vertical)
-        .constraints([Constraint::Length(7), Constraint::Min(4)])
+        .constraints([
+            Constraint::Length(8),
+            Constraint::Length(table_height),
+            Constraint::Min(4),
+        ])
         .split(area);
 
     render_latency_gauges(frame, chunks[0], app);
-    render_price_trend(frame, chunks[1], app);
+    render_symbol_table(frame, chunks[1], app);
+    render_price_trend(frame, chunks[2], app);
+}
+
+fn render_symbol_table(frame: &mut Frame<'_>, area: Rect, app: &AppState) {
+    let sort = app.metrics_sort;
+    let title = format!(
+        " {} · {} {} (m/M/t) ",
+        if app.metrics_show_streams {
+            "Streams"
+        } else {
+            "Symbols"
+        },
+        sort.column.label(),
+        if sort.descending { "↓" } else { "↑" }
+    );
+    let block = Block::default().title(title).borders(Borders::ALL);
+
+    let rows: Vec<Row> = app
+        .sorted_symbol_metrics()
+        .into_iter()
+        .flat_map(|symbol| {
+            let status_style = status_style(&symbol.status);
+            if app.metrics_show_streams && !symbol.streams.is_empty() {
+                symbol
+                    .streams
+                    .iter()
+                    .map(|stream| {
+                        Row::new(vec![
+                            Cell::from(format!("{}:{}", symbol.symbol, stream.kind.as_str())),
+                            Cell::from(status_label(&symbol.status)).style(status_style),
+                            Cell::from(format!("{:.1}", stream.messages_per_second)),
+                            Cell::from(format_latency(stream.latency.p50)),
+                            Cell::from(format_latency(stream.latency.p99)),
+                            Cell::from(format_latency(stream.latency.p999)),
+                            Cell::from(format_latency(stream.latency.max)),
+                            Cell::from(format_age(stream.last_message_age_ms)),
+                        ])
+                    })
+                    .collect::<Vec<_>>()
+            } else {
+                vec![Row::new(vec![
+                    Cell::from(symbol.symbol.clone()),
+                    Cell::from(status_label(&symbol.status)).style(status_style),
+                    Cell::from(format!("{:.1}", symbol.messages_per_second)),
+                    Cell::from(format_latency(symbol.latency.p50)),
+                    Cell::from(format_latency(symbol.latency.p99)),
+                    Cell::from(format_latency(symbol.latency.p999)),
+                    Cell::from(format_latency(symbol.latency.max)),
+                    Cell::from(format_age(symbol.last_message_age_ms)),
+                ])]
+            }
+        })
+        .collect();
+
+    if rows.is_empty() {
+        frame.render_widget(
+            Paragraph::new("No symbol metrics yet")
+                .style(Style::default().fg(Color::Gray))
+                .block(block),
+            area,
+        );
+        return;
+    }
+
+    let widths = [
+        Constraint::Min(10),
+        Constraint::Length(5),
+        Constraint::Length(6),
+        Constraint::Length(6),
+        Constraint::Length(6),
+        Constraint::Length(6),
+        Constraint::Length(6),
+        Constraint::Length(5),
+    ];
+    let table = Table::new(rows, widths)
+        .header(
+            Row::new(vec![
+                "Symbol", "Conn", "Msg/s", "P50", "P99", "P99.9", "Max", "Age",
+            ])
+            .style(
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+            ),
+        )
+        .block(block)
+        .column_spacing(1);
+    frame.render_widget(table, area);
+}
+
+fn status_label(status: &ConnectionStatus) -> &'static str {
+    match status {
+        ConnectionStatus::Connected => "OK",
+        ConnectionStatus::Connecting => "CONN",
+        ConnectionStatus::Reconnecting => "RECON",
+        ConnectionStatus::Disconnected => "DOWN",
+        ConnectionStatus::Error(_) => "ERR",
+    }
+}
+
+fn status_style(status: &ConnectionStatus) -> Style {
+    match status {
+        ConnectionStatus::Connected => Style::default().fg(Color::Green),
+        ConnectionStatus::Connecting | ConnectionStatus::Reconnecting => {
+            Style::default().fg(Color::Yellow)
+        }
+        ConnectionStatus::Disconnected | ConnectionStatus::Error(_) => {
+            Style::default().fg(Color::Red)
+        }
+    }
+}
+
+fn format_latency(latency_ms: u64) -> String {
+    if latency_ms == 0 {
+        "-".to_string()
+    } else {
+        format!("{}ms", latency_ms)
+    }
+}
+
+fn format_age(age_ms: Option<u64>) -> String {
+    match age_ms {
+        None => "-".to_string(),
+        Some(ms) if ms < 1_000 => format!("{}ms", ms),
+        Some(ms) if ms < 60_000 => format!("{}s", ms / 1_000),
+        Some(ms) => format!("{}m", ms / 60_000),
+    }
 }
 
 fn render_latency_gauges(frame: &mut Frame<'_>, area: Rect, app: &AppState) {
@@ -34,15 +180,43 @@ fn render_latency_gauges(frame: &mut Frame<'_>, area: Rect, app: &AppState) {
         Span::raw("    "),
         Span::raw(format!("Msg/s: {:.1}", metrics.messages_per_second)),
     ]));
+    lines.push(Line::from(
+        metrics
+            .latency_windows
+            .iter()
+            .map(|(window, summary)| {
+                Span::raw(format!(
+                    "{} p99/p99.9/max: {}/{}/{} ms   ",
+                    window.label(),
+                    summary.p99,
+                    summary.p999,
+                    summary.max
+                ))
+            })
+            .collect::<Vec<_>>(),
+    ));
     lines.push(Line::from(vec![
         Span::raw(format!("Reconnects: {}", metrics.reconnect_count)),
         Span::raw("    "),
         Span::raw(format!("Errors: {}", metrics.error_count)),
     ]));
+    let clock = match &metrics.clock {
+        Some(clock) => Span::styled(
+            format!("Clock: {:+} ms (rtt {} ms)", clock.offset_ms, clock.rtt_ms),
+            if metrics.clock_skewed {
+                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            },
+        ),
+        None => Span::styled("Clock: unsynced", Style::default().fg(Color::Gray)),
+    };
     lines.push(Line::from(vec![
         Span::raw(format!("Quality: {:?}", metrics.connection_quality)),
         Span::raw("    "),
         Span::raw(format!("Uptime: {}s", metrics.uptime_seconds)),
+        Span::raw("    "),
+        clock,
     ]));
 
     let gauge_value = (metrics.messages_per_second / 1200.0).min(1.0);
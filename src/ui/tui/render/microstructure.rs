@@ -0,0 +1,257 @@
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::symbols::Marker;
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Axis, Block, Borders, Chart, Clear, Dataset, GraphType, Paragraph};
+
+use crate::market_data::MicrostructureSnapshot;
+use crate::ui::AppState;
+
+use super::layout::centered_rect;
+
+/// Imbalance beyond this share of the top levels is highlighted
+const STRONG_IMBALANCE: f64 = 0.3;
+
+pub(super) fn render_microstructure_overlay(frame: &mut Frame<'_>, app: &AppState) {
+    let symbol = app.current_symbol().cloned().unwrap_or_default();
+    let popup_area = centered_rect(80, 70, frame.size());
+    let block = Block::default()
+        .title(format!(" Book Microstructure: {} ", symbol))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .style(Style::default().bg(Color::Black));
+    frame.render_widget(Clear, popup_area);
+    frame.render_widget(block.clone(), popup_area);
+
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(6),
+            Constraint::Min(6),
+            Constraint::Length(1),
+        ])
+        .margin(1)
+        .split(block.inner(popup_area));
+
+    frame.render_widget(
+        Paragraph::new("←/→ switch symbol   Esc close").style(Style::default().fg(Color::Gray)),
+        rows[2],
+    );
+
+    let history = app
+        .market_data
+        .get(&symbol)
+        .map(|state| state.microstructure.as_slice())
+        .unwrap_or_default();
+    let Some(latest) = history.last() else {
+        frame.render_widget(
+            Paragraph::new(format!("Waiting for {} depth updates", symbol))
+                .style(Style::default().fg(Color::Gray)),
+            rows[0],
+        );
+        return;
+    };
+
+    render_figures(frame, app, &symbol, latest, rows[0]);
+
+    let charts = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(rows[1]);
+    let imbalance: Vec<(f64, f64)> = history
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, sample)| sample.depth_imbalance.map(|value| (idx as f64, value)))
+        .collect();
+    let flow: Vec<(f64, f64)> = history
+        .iter()
+        .enumerate()
+        .map(|(idx, sample)| (idx as f64, sample.order_flow_imbalance))
+        .collect();
+    let flow_bound = flow
+        .iter()
+        .map(|(_, value)| value.abs())
+        .fold(0.0, f64::max)
+        .max(f64::EPSILON);
+
+    render_series(
+        frame,
+        charts[0],
+        &format!(" Depth imbalance, top {} ", latest.levels),
+        &imbalance,
+        history.len(),
+        1.0,
+        Color::Yellow,
+    );
+    render_series(
+        frame,
+        charts[1],
+        &format!(" Order flow imbalance, {}s ", latest.window_ms / 1_000),
+        &flow,
+        history.len(),
+        flow_bound,
+        Color::Magenta,
+    );
+}
+
+fn render_figures(
+    frame: &mut Frame<'_>,
+    app: &AppState,
+    symbol: &str,
+    snapshot: &MicrostructureSnapshot,
+    area: Rect,
+) {
+    let label = Style::default().fg(Color::Cyan);
+    let value = Style::default()
+        .fg(Color::White)
+        .add_modifier(Modifier::BOLD);
+    let price = |price: Option<f64>| {
+        price
+            .map(|price| app.format_price(symbol, price))
+            .unwrap_or_else(|| "-".to_string())
+    };
+
+    let mut microprice = vec![
+        Span::styled("Microprice:   ", label),
+        Span::styled(price(snapshot.microprice), value),
+    ];
+    if let Some(bps) = snapshot.microprice_offset_bps() {
+        microprice.push(Span::styled(
+            format!("  {:+.2} bps", bps),
+            signed_style(bps),
+        ));
+    }
+    let imbalance = snapshot
+        .depth_imbalance
+        .map(|imbalance| {
+            let style = if imbalance.abs() >= STRONG_IMBALANCE {
+                signed_style(imbalance).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::Gray)
+            };
+            Span::styled(format!("{:+.3}", imbalance), style)
+        })
+        .unwrap_or_else(|| Span::raw("-"));
+
+    let resilience = &snapshot.resilience;
+    let mut recovery = format!("{} large trades", resilience.large_trades);
+    if let Some(last) = resilience.last_recovery_ms {
+        recovery.push_str(&format!(", last refill {} ms", last));
+    }
+    if let Some(average) = resilience.average_recovery_ms {
+        recovery.push_str(&format!(", avg {:.0} ms", average));
+    }
+    if resilience.unrecovered > 0 {
+        recovery.push_str(&format!(", {} unrecovered", resilience.unrecovered));
+    }
+    if resilience.recovering {
+        recovery.push_str(", refilling");
+    }
+
+    let lines = vec![
+        Line::from(vec![
+            Span::styled("Mid:          ", label),
+            Span::styled(price(snapshot.mid), value),
+            Span::styled("   Weighted mid: ", label),
+            Span::styled(price(snapshot.weighted_mid), value),
+        ]),
+        Line::from(microprice),
+        Line::from(vec![
+            Span::styled("Imbalance:    ", label),
+            imbalance,
+            Span::styled(
+                format!(
+                    "   bid {:.4} / ask {:.4}",
+                    snapshot.bid_depth, snapshot.ask_depth
+                ),
+                Style::default().fg(Color::Gray),
+            ),
+            Span::styled("   OFI: ", label),
+            Span::styled(
+                format!("{:+.4}", snapshot.order_flow_imbalance),
+                signed_style(snapshot.order_flow_imbalance),
+            ),
+        ]),
+        Line::from(vec![
+            Span::styled("Bid add/cxl:  ", label),
+            Span::styled(
+                format!(
+                    "{:.4} / {:.4} per s",
+                    snapshot.bid_add_rate, snapshot.bid_cancel_rate
+                ),
+                value,
+            ),
+            Span::styled("   Ask add/cxl: ", label),
+            Span::styled(
+                format!(
+                    "{:.4} / {:.4} per s",
+                    snapshot.ask_add_rate, snapshot.ask_cancel_rate
+                ),
+                value,
+            ),
+        ]),
+        Line::from(vec![
+            Span::styled("Resilience:   ", label),
+            Span::styled(recovery, Style::default().fg(Color::Gray)),
+        ]),
+    ];
+    frame.render_widget(Paragraph::new(lines), area);
+}
+
+fn render_series(
+    frame: &mut Frame<'_>,
+    area: Rect,
+    title: &str,
+    points: &[(f64, f64)],
+    samples: usize,
+    bound: f64,
+    color: Color,
+) {
+    let block = Block::default()
+        .title(title.to_string())
+        .borders(Borders::TOP)
+        .border_style(Style::default().fg(Color::DarkGray));
+    let dataset = Dataset::default()
+        .marker(Marker::Braille)
+        .graph_type(GraphType::Line)
+        .style(Style::default().fg(color))
+        .data(points);
+    let axis_style = Style::default().fg(Color::Gray);
+    let chart = Chart::new(vec![dataset])
+        .block(block)
+        .x_axis(
+            Axis::default()
+                .style(axis_style)
+                .bounds([0.0, samples.saturating_sub(1).max(1) as f64]),
+        )
+        .y_axis(
+            Axis::default()
+                .style(axis_style)
+                .bounds([-bound, bound])
+                .labels(vec![
+                    Span::raw(format_bound(-bound)),
+                    Span::raw("0"),
+                    Span::raw(format_bound(bound)),
+                ]),
+        );
+    frame.render_widget(chart, area);
+}
+
+fn signed_style(value: f64) -> Style {
+    if value > 0.0 {
+        Style::default().fg(Color::Green)
+    } else if value < 0.0 {
+        Style::default().fg(Color::Red)
+    } else {
+        Style::default().fg(Color::Gray)
+    }
+}
+
+fn format_bound(value: f64) -> String {
+    if value.abs() >= 100.0 {
+        format!("{:+.0}", value)
+    } else {
+        format!("{:+.2}", value)
+    }
+}
@@ -1,12 +1,21 @@
+mod account;
 mod alerts;
 mod command_palette;
 mod header;
+mod heatmap;
+mod impact;
 mod layout;
 mod logs;
 mod metrics;
+mod microstructure;
+mod order_ticket;
 mod orderbook;
 mod overview;
+mod paper;
+mod portfolio;
 mod price_trend;
+mod scanner;
+mod trade_flow;
 
 use ratatui::Frame;
 use ratatui::layout::{Constraint, Direction, Layout};
@@ -15,13 +24,22 @@ use crate::session::session_manager::SessionStats;
 use crate::ui::ui_manager::RenderState;
 use crate::ui::{AppState, InputMode};
 
+use self::account::render_account_overlay;
 use self::alerts::render_alerts_overlay;
 use self::command_palette::render_command_palette;
 use self::header::render_header;
+use self::heatmap::render_heatmap_overlay;
+use self::impact::render_impact_overlay;
 use self::logs::render_logs;
 use self::metrics::render_metrics;
+use self::microstructure::render_microstructure_overlay;
+use self::order_ticket::render_order_ticket;
 use self::orderbook::render_orderbook;
 use self::overview::render_symbol_overview;
+use self::paper::render_paper_overlay;
+use self::portfolio::render_portfolio_overlay;
+use self::scanner::render_scanner_overlay;
+use self::trade_flow::render_trade_flow;
 
 pub(super) fn render_root(
     frame: &mut Frame<'_>,
@@ -52,13 +70,38 @@ pub(super) fn render_root(
         .split(chunks[1]);
 
     render_symbol_overview(frame, body_chunks[0], app);
-    render_orderbook(frame, body_chunks[1], app, orderbook_depth);
+    let book_chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(6), Constraint::Length(9)])
+        .split(body_chunks[1]);
+    render_orderbook(frame, book_chunks[0], app, orderbook_depth);
+    render_trade_flow(frame, book_chunks[1], app);
     render_metrics(frame, body_chunks[2], app);
 
     render_logs(frame, chunks[2], app, render_state);
     render_command_palette(frame, chunks[3], app, render_state);
 
-    if matches!(app.input_mode, InputMode::Alerts) {
-        render_alerts_overlay(frame, app);
+    match app.input_mode {
+        InputMode::Alerts => render_alerts_overlay(frame, app),
+        InputMode::Scanner => render_scanner_overlay(frame, app),
+        InputMode::Paper => render_paper_overlay(frame, app),
+        InputMode::Account => render_account_overlay(frame, app),
+        InputMode::OrderTicket => render_order_ticket(frame, app),
+        InputMode::Portfolio => render_portfolio_overlay(frame, app),
+        InputMode::Impact => render_impact_overlay(frame, app),
+        InputMode::Microstructure => render_microstructure_overlay(frame, app),
+        InputMode::Heatmap => render_heatmap_overlay(frame, app),
+        _ => {}
+    }
+}
+
+/// Compact quantity without trailing zeros, e.g. `0.015` or `10000`
+fn format_amount(value: f64) -> String {
+    let formatted = format!("{:.8}", value);
+    let trimmed = formatted.trim_end_matches('0').trim_end_matches('.');
+    if trimmed == "-0" {
+        "0".to_string()
+    } else {
+        trimmed.to_string()
     }
 }
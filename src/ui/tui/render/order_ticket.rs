@@ -0,0 +1,145 @@
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Direction, Layout};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph, Wrap};
+
+use crate::paper::OrderSide;
+use crate::ui::{AppState, OrderFormField, OrderFormType};
+
+use super::layout::centered_rect;
+
+pub(super) fn render_order_ticket(frame: &mut Frame<'_>, app: &AppState) {
+    let popup_area = centered_rect(62, 36, frame.size());
+    let form = &app.order_form;
+    let border = if form.confirmation.is_some() {
+        Color::Yellow
+    } else {
+        Color::Cyan
+    };
+    let block = Block::default()
+        .title(format!(" Order Ticket: {} ", form.symbol))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(border))
+        .style(Style::default().bg(Color::Black));
+    frame.render_widget(Clear, popup_area);
+    frame.render_widget(block.clone(), popup_area);
+
+    let inner_area = block.inner(popup_area);
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Length(3),
+            Constraint::Length(1),
+        ])
+        .margin(1)
+        .split(inner_area);
+
+    let label_style = |field: OrderFormField| {
+        if form.confirmation.is_none() && form.active_field == field {
+            Style::default()
+                .fg(Color::Black)
+                .bg(Color::LightCyan)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(Color::Cyan)
+        }
+    };
+    let choice_style = |selected: bool| {
+        if selected {
+            Style::default()
+                .fg(Color::White)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(Color::DarkGray)
+        }
+    };
+    let value_style = Style::default()
+        .fg(Color::White)
+        .add_modifier(Modifier::BOLD);
+
+    let side_line = Line::from(vec![
+        Span::styled("Side: ", label_style(OrderFormField::Side)),
+        Span::styled(
+            "Buy",
+            choice_style(form.side == OrderSide::Buy).fg(Color::Green),
+        ),
+        Span::raw("  "),
+        Span::styled(
+            "Sell",
+            choice_style(form.side == OrderSide::Sell).fg(Color::Red),
+        ),
+    ]);
+    frame.render_widget(Paragraph::new(side_line), rows[0]);
+
+    let type_line = Line::from(vec![
+        Span::styled("Type: ", label_style(OrderFormField::Type)),
+        Span::styled(
+            "Limit",
+            choice_style(form.order_type == OrderFormType::Limit),
+        ),
+        Span::raw("  "),
+        Span::styled(
+            "Market",
+            choice_style(form.order_type == OrderFormType::Market),
+        ),
+        Span::raw("  "),
+        Span::styled("Stop", choice_style(form.order_type == OrderFormType::Stop)),
+    ]);
+    frame.render_widget(Paragraph::new(type_line), rows[1]);
+
+    let quantity_line = Line::from(vec![
+        Span::styled("Quantity: ", label_style(OrderFormField::Quantity)),
+        Span::styled(form.quantity_input.clone(), value_style),
+    ]);
+    frame.render_widget(Paragraph::new(quantity_line), rows[2]);
+
+    let price_label = match form.order_type {
+        OrderFormType::Stop => "Trigger: ",
+        _ => "Price: ",
+    };
+    let price_value = match form.order_type {
+        OrderFormType::Market => "at market".to_string(),
+        _ => form.price_input.clone(),
+    };
+    let price_line = Line::from(vec![
+        Span::styled(price_label, label_style(OrderFormField::Price)),
+        Span::styled(price_value, value_style),
+    ]);
+    frame.render_widget(Paragraph::new(price_line), rows[3]);
+
+    let (status, status_style) = match (&form.confirmation, &form.error) {
+        (Some(summary), _) => (
+            format!("Send {}?", summary),
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        ),
+        (None, Some(error)) => (error.clone(), Style::default().fg(Color::LightRed)),
+        (None, None) => (
+            "Enter checks the order against the exchange rules before anything is sent".to_string(),
+            Style::default().fg(Color::Gray),
+        ),
+    };
+    frame.render_widget(
+        Paragraph::new(status)
+            .style(status_style)
+            .wrap(Wrap { trim: true }),
+        rows[5],
+    );
+
+    let hints = if form.confirmation.is_some() {
+        "y confirm and send   n/Esc discard"
+    } else {
+        "Up/Down switch fields, Tab toggles options, Enter to review, Esc to close"
+    };
+    frame.render_widget(
+        Paragraph::new(hints).style(Style::default().fg(Color::Gray)),
+        rows[6],
+    );
+}
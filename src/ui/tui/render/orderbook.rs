@@ -1,10 +1,11 @@
 use ordered_float::OrderedFloat;
 use ratatui::Frame;
-use ratatui::layout::{Constraint, Rect};
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
 use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, Cell, Paragraph, Row, Table};
 
-use crate::ui::AppState;
+use crate::ui::{AppState, MarketDataState};
 
 pub(super) fn render_orderbook(
     frame: &mut Frame<'_>,
@@ -25,6 +26,14 @@ pub(super) fn render_orderbook(
     frame.render_widget(block, area);
 
     if let Some(data) = market_data {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(1), Constraint::Min(1)])
+            .split(inner_area);
+        let decimals = app.price_decimals(&symbol);
+        frame.render_widget(Paragraph::new(top_of_book_line(&data, decimals)), chunks[0]);
+        let inner_area = chunks[1];
+
         let orderbook = data
             .orderbook
             .unwrap_or_else(|| crate::binance::types::OrderBook::new(symbol.clone()));
@@ -56,8 +65,8 @@ pub(super) fn render_orderbook(
                 let ask_price = ask.0.into_inner();
                 Row::new(vec![
                     Cell::from(format!("{:>10.4}", bid.1)),
-                    Cell::from(format!("{:>10.2}", bid_price)),
-                    Cell::from(format!("{:>10.2}", ask_price)),
+                    Cell::from(format!("{:>10.*}", decimals, bid_price)),
+                    Cell::from(format!("{:>10.*}", decimals, ask_price)),
                     Cell::from(format!("{:>10.4}", ask.1)),
                 ])
                 .style(
@@ -90,3 +99,36 @@ pub(super) fn render_orderbook(
         frame.render_widget(placeholder, inner_area);
     }
 }
+
+/// Best bid/offer line from the book ticker, which leads the diff-depth ladder
+fn top_of_book_line(data: &MarketDataState, decimals: usize) -> Line<'static> {
+    let Some(ticker) = data.book_ticker else {
+        return Line::from(Span::styled(
+            "BBO: waiting for book ticker",
+            Style::default().fg(Color::Gray),
+        ));
+    };
+
+    Line::from(vec![
+        Span::styled(
+            format!("{:.4} @ {:.*}", ticker.bid_qty, decimals, ticker.bid_price),
+            Style::default().fg(Color::Green),
+        ),
+        Span::raw("  |  "),
+        Span::styled(
+            format!("{:.*} x {:.4}", decimals, ticker.ask_price, ticker.ask_qty),
+            Style::default().fg(Color::Red),
+        ),
+        Span::styled(
+            format!(
+                "   mid {:.*}  spread {:.*} ({:.1}bp)",
+                decimals + 1,
+                ticker.mid(),
+                decimals,
+                ticker.spread(),
+                ticker.spread_bps()
+            ),
+            Style::default().fg(Color::Gray),
+        ),
+    ])
+}
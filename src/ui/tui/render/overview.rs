@@ -13,9 +13,10 @@ pub(super) fn render_symbol_overview(frame: &mut Frame<'_>, area: Rect, app: &Ap
         Constraint::Length(12),
         Constraint::Length(10),
         Constraint::Length(10),
+        Constraint::Length(8),
     ];
 
-    let header = Row::new(["Symbol", "Price", "Δ%", "Volume"]).style(
+    let header = Row::new(["Symbol", "Price", "Δ%", "Volume", "Spread"]).style(
         Style::default()
             .fg(Color::Gray)
             .add_modifier(Modifier::BOLD),
@@ -42,9 +43,14 @@ pub(super) fn render_symbol_overview(frame: &mut Frame<'_>, area: Rect, app: &Ap
 
             let mut row = Row::new(vec![
                 Cell::from(symbol.clone()),
-                Cell::from(format!("{:.2}", data.price)),
+                Cell::from(app.format_price(symbol, data.price)),
                 Cell::from(format!("{:+.2}", data.change_percent)).style(change_style),
                 Cell::from(format!("{:.2}k", data.volume_24h / 1000.0)),
+                Cell::from(
+                    data.book_ticker
+                        .map(|ticker| format!("{:.1}bp", ticker.spread_bps()))
+                        .unwrap_or_else(|| "-".to_string()),
+                ),
             ]);
 
             if app.selected_tab == idx {
@@ -0,0 +1,324 @@
+use std::collections::BTreeMap;
+
+use chrono::{Local, TimeZone};
+use ratatui::Frame;
+use ratatui::layout::{Alignment, Constraint, Direction, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::widgets::{Block, Borders, Cell, Clear, Paragraph, Row, Table};
+
+use crate::paper::{OrderSide, OrderType};
+use crate::ui::AppState;
+
+use super::format_amount;
+use super::layout::centered_rect;
+
+pub(super) fn render_paper_overlay(frame: &mut Frame<'_>, app: &AppState) {
+    let overlay_area = centered_rect(85, 75, frame.size());
+    frame.render_widget(Clear, overlay_area);
+
+    let block = Block::default()
+        .title(" Paper Trading ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .style(Style::default().bg(Color::Black));
+    frame.render_widget(block.clone(), overlay_area);
+
+    let inner = block.inner(overlay_area);
+    let position_rows = app.paper.positions.len().max(1) as u16 + 1;
+    let layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(2),
+            Constraint::Length(position_rows + 1),
+            Constraint::Min(4),
+            Constraint::Length(7),
+            Constraint::Length(1),
+        ])
+        .margin(1)
+        .split(inner);
+
+    render_account_summary(frame, app, layout[0]);
+    render_positions(frame, app, layout[1]);
+    render_open_orders(frame, app, layout[2]);
+    render_recent_fills(frame, app, layout[3]);
+
+    let hints = Paragraph::new(
+        "↑/↓ select order   b buy   s sell   c cancel selected   Shift+C cancel all   Esc close",
+    )
+    .style(Style::default().fg(Color::Gray));
+    frame.render_widget(hints, layout[4]);
+}
+
+fn render_account_summary(frame: &mut Frame<'_>, app: &AppState, area: Rect) {
+    let balances = if app.paper.balances.is_empty() {
+        "none".to_string()
+    } else {
+        app.paper
+            .balances
+            .iter()
+            .map(|(asset, balance)| {
+                if balance.locked > 0.0 {
+                    format!(
+                        "{} {} ({} locked)",
+                        asset,
+                        format_amount(balance.total()),
+                        format_amount(balance.locked)
+                    )
+                } else {
+                    format!("{} {}", asset, format_amount(balance.total()))
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("  | ")
+    };
+
+    // Positions in different quote assets are summed separately
+    let mut pnl: BTreeMap<&str, (f64, f64)> = BTreeMap::new();
+    for position in &app.paper.positions {
+        let entry = pnl.entry(position.quote_asset.as_str()).or_default();
+        entry.0 += position.realized_pnl;
+        if let Some(mark) = app.paper_mark_price(&position.symbol, position.mark_price) {
+            entry.1 += position.unrealized_pnl(mark);
+        }
+    }
+    let pnl = if pnl.is_empty() {
+        "PnL: -".to_string()
+    } else {
+        pnl.iter()
+            .map(|(quote, (realized, unrealized))| {
+                format!(
+                    "PnL {}: realized {:+.2}  unrealized {:+.2}",
+                    quote, realized, unrealized
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("  | ")
+    };
+
+    let summary = Paragraph::new(format!("Balances: {}\n{}", balances, pnl))
+        .style(Style::default().fg(Color::Gray));
+    frame.render_widget(summary, area);
+}
+
+fn render_positions(frame: &mut Frame<'_>, app: &AppState, area: Rect) {
+    let rows: Vec<Row> = app
+        .paper
+        .positions
+        .iter()
+        .map(|position| {
+            let mark = app.paper_mark_price(&position.symbol, position.mark_price);
+            let unrealized = mark.map(|mark| position.unrealized_pnl(mark));
+            Row::new(vec![
+                Cell::from(position.symbol.clone()),
+                Cell::from(format_amount(position.quantity)),
+                Cell::from(app.format_price(&position.symbol, position.avg_entry)),
+                Cell::from(
+                    mark.map(|mark| app.format_price(&position.symbol, mark))
+                        .unwrap_or_else(|| "-".to_string()),
+                ),
+                Cell::from(
+                    unrealized
+                        .map(|pnl| format!("{:+.2}", pnl))
+                        .unwrap_or_else(|| "-".to_string()),
+                )
+                .style(pnl_style(unrealized.unwrap_or(0.0))),
+                Cell::from(format!("{:+.2}", position.realized_pnl))
+                    .style(pnl_style(position.realized_pnl)),
+                Cell::from(format!("{:.4}", position.fees_paid)),
+            ])
+        })
+        .collect();
+
+    if rows.is_empty() {
+        let placeholder = Paragraph::new("No paper positions")
+            .style(Style::default().fg(Color::Gray))
+            .block(section_block(" Positions "));
+        frame.render_widget(placeholder, area);
+        return;
+    }
+
+    let widths = [
+        Constraint::Length(12),
+        Constraint::Length(12),
+        Constraint::Length(12),
+        Constraint::Length(12),
+        Constraint::Length(12),
+        Constraint::Length(12),
+        Constraint::Length(10),
+    ];
+    let table = Table::new(rows, widths)
+        .header(header_row(&[
+            "Symbol",
+            "Qty",
+            "Avg Entry",
+            "Mark",
+            "Unrealized",
+            "Realized",
+            "Fees",
+        ]))
+        .block(section_block(" Positions "))
+        .column_spacing(1);
+    frame.render_widget(table, area);
+}
+
+fn render_open_orders(frame: &mut Frame<'_>, app: &AppState, area: Rect) {
+    let title = format!(" Open Orders ({}) ", app.paper.open_orders.len());
+    let block = section_block(&title);
+    if app.paper.open_orders.is_empty() {
+        let placeholder = Paragraph::new("No open paper orders — press b or s to place one")
+            .style(Style::default().fg(Color::Gray))
+            .alignment(Alignment::Center)
+            .block(block);
+        frame.render_widget(placeholder, area);
+        return;
+    }
+
+    let visible = (area.height as usize).saturating_sub(2).max(1);
+    let offset = app.selected_paper_order_index.saturating_sub(visible - 1);
+    let rows: Vec<Row> = app
+        .paper
+        .open_orders
+        .iter()
+        .enumerate()
+        .skip(offset)
+        .take(visible)
+        .map(|(idx, order)| {
+            let price = match order.order_type {
+                OrderType::Market => "-".to_string(),
+                OrderType::Limit { price } | OrderType::Stop { trigger: price } => {
+                    app.format_price(&order.symbol, price)
+                }
+            };
+            let kind = match order.order_type {
+                OrderType::Market => "MARKET",
+                OrderType::Limit { .. } => "LIMIT",
+                OrderType::Stop { .. } => "STOP",
+            };
+            let queue = match order.order_type {
+                OrderType::Limit { .. } => format_amount(order.queue_ahead),
+                _ => "-".to_string(),
+            };
+            let mut row = Row::new(vec![
+                Cell::from(format!("#{}", order.id)),
+                Cell::from(order.side.label()).style(side_style(order.side)),
+                Cell::from(order.symbol.clone()),
+                Cell::from(kind),
+                Cell::from(price),
+                Cell::from(format_amount(order.quantity)),
+                Cell::from(format_amount(order.filled_qty)),
+                Cell::from(queue),
+            ]);
+            if idx == app.selected_paper_order_index {
+                row = row.style(
+                    Style::default()
+                        .fg(Color::White)
+                        .bg(Color::Blue)
+                        .add_modifier(Modifier::BOLD),
+                );
+            }
+            row
+        })
+        .collect();
+
+    let widths = [
+        Constraint::Length(6),
+        Constraint::Length(5),
+        Constraint::Length(12),
+        Constraint::Length(7),
+        Constraint::Length(12),
+        Constraint::Length(12),
+        Constraint::Length(12),
+        Constraint::Length(12),
+    ];
+    let table = Table::new(rows, widths)
+        .header(header_row(&[
+            "Id",
+            "Side",
+            "Symbol",
+            "Type",
+            "Price",
+            "Qty",
+            "Filled",
+            "Queue Ahead",
+        ]))
+        .block(block)
+        .column_spacing(1);
+    frame.render_widget(table, area);
+}
+
+fn render_recent_fills(frame: &mut Frame<'_>, app: &AppState, area: Rect) {
+    let visible = (area.height as usize).saturating_sub(3);
+    let rows: Vec<Row> = app
+        .paper
+        .recent_fills
+        .iter()
+        .take(visible)
+        .map(|fill| {
+            let time = Local
+                .timestamp_millis_opt(fill.time_ms as i64)
+                .single()
+                .map(|ts| ts.format("%H:%M:%S").to_string())
+                .unwrap_or_default();
+            Row::new(vec![
+                Cell::from(time),
+                Cell::from(format!("#{}", fill.order_id)),
+                Cell::from(fill.side.label()).style(side_style(fill.side)),
+                Cell::from(fill.symbol.clone()),
+                Cell::from(app.format_price(&fill.symbol, fill.price)),
+                Cell::from(format_amount(fill.quantity)),
+                Cell::from(fill.liquidity.label()),
+                Cell::from(format!("{:.4}", fill.fee)),
+            ])
+        })
+        .collect();
+
+    let widths = [
+        Constraint::Length(9),
+        Constraint::Length(6),
+        Constraint::Length(5),
+        Constraint::Length(12),
+        Constraint::Length(12),
+        Constraint::Length(12),
+        Constraint::Length(6),
+        Constraint::Length(10),
+    ];
+    let table = Table::new(rows, widths)
+        .header(header_row(&[
+            "Time", "Order", "Side", "Symbol", "Price", "Qty", "Liq", "Fee",
+        ]))
+        .block(section_block(" Recent Fills "))
+        .column_spacing(1);
+    frame.render_widget(table, area);
+}
+
+fn section_block(title: &str) -> Block<'_> {
+    Block::default()
+        .title(title)
+        .borders(Borders::TOP)
+        .border_style(Style::default().fg(Color::DarkGray))
+}
+
+fn header_row<'a>(titles: &[&'a str]) -> Row<'a> {
+    Row::new(titles.to_vec()).style(
+        Style::default()
+            .fg(Color::Cyan)
+            .add_modifier(Modifier::BOLD),
+    )
+}
+
+fn side_style(side: OrderSide) -> Style {
+    match side {
+        OrderSide::Buy => Style::default().fg(Color::Green),
+        OrderSide::Sell => Style::default().fg(Color::Red),
+    }
+}
+
+fn pnl_style(value: f64) -> Style {
+    if value > 0.0 {
+        Style::default().fg(Color::Green)
+    } else if value < 0.0 {
+        Style::default().fg(Color::Red)
+    } else {
+        Style::default()
+    }
+}
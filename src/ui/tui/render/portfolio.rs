@@ -0,0 +1,206 @@
+use ratatui::Frame;
+use ratatui::layout::{Alignment, Constraint, Direction, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::widgets::{Block, Borders, Cell, Clear, Paragraph, Row, Table};
+
+use crate::portfolio::PortfolioAlertKind;
+use crate::ui::AppState;
+
+use super::format_amount;
+use super::layout::centered_rect;
+
+pub(super) fn render_portfolio_overlay(frame: &mut Frame<'_>, app: &AppState) {
+    let overlay_area = centered_rect(85, 75, frame.size());
+    frame.render_widget(Clear, overlay_area);
+
+    let block = Block::default()
+        .title(" Portfolio ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .style(Style::default().bg(Color::Black));
+    frame.render_widget(block.clone(), overlay_area);
+
+    let inner = block.inner(overlay_area);
+    let layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Min(4),
+            Constraint::Length(1),
+        ])
+        .margin(1)
+        .split(inner);
+
+    render_summary(frame, app, layout[0]);
+    render_holdings(frame, app, layout[1]);
+
+    let hints =
+        Paragraph::new("a add holding   /position:alert above|below|drawdown <n>   Esc close")
+            .style(Style::default().fg(Color::Gray));
+    frame.render_widget(hints, layout[2]);
+}
+
+fn render_summary(frame: &mut Frame<'_>, app: &AppState, area: Rect) {
+    let snapshot = &app.portfolio;
+    let valuation = &snapshot.valuation;
+    let currency = valuation.currency.as_str();
+
+    let mut value_line = format!(
+        "Value: {:.2} {}   PnL: {:+.2}",
+        valuation.total_value,
+        currency,
+        valuation.total_pnl()
+    );
+    if let Some(pct) = valuation.pnl_percent() {
+        value_line.push_str(&format!(" ({:+.2}%)", pct));
+    }
+    value_line.push_str(&format!("   24h: {:+.2}", valuation.daily_change));
+    if let Some(pct) = valuation.daily_change_percent() {
+        value_line.push_str(&format!(" ({:+.2}%)", pct));
+    }
+
+    let drawdown_line = match (snapshot.peak_value, snapshot.drawdown_percent()) {
+        (Some(peak), Some(drawdown)) => {
+            format!(
+                "Peak: {:.2} {}   Drawdown: {:.2}%",
+                peak, currency, drawdown
+            )
+        }
+        _ => "Peak: -   Drawdown: -".to_string(),
+    };
+
+    let alerts = PortfolioAlertKind::ALL
+        .iter()
+        .filter_map(|kind| {
+            snapshot.alerts.get(*kind).map(|threshold| match kind {
+                PortfolioAlertKind::Drawdown => format!("drawdown {}%", threshold),
+                _ => format!("{} {}", kind.label(), threshold),
+            })
+        })
+        .collect::<Vec<_>>();
+    let alerts_line = if alerts.is_empty() {
+        "Alerts: none".to_string()
+    } else {
+        format!("Alerts: {}", alerts.join("  | "))
+    };
+
+    let summary = Paragraph::new(format!(
+        "{}\n{}\n{}",
+        value_line, drawdown_line, alerts_line
+    ))
+    .style(Style::default().fg(Color::Gray));
+    frame.render_widget(summary, area);
+}
+
+fn render_holdings(frame: &mut Frame<'_>, app: &AppState, area: Rect) {
+    let valuation = &app.portfolio.valuation;
+    let conversion_error = valuation
+        .holdings
+        .iter()
+        .find_map(|entry| entry.conversion_error.as_deref());
+    let title = if valuation.complete || valuation.holdings.is_empty() {
+        format!(" Holdings ({}) ", valuation.holdings.len())
+    } else if let Some(error) = conversion_error {
+        format!(" Holdings ({}) — {} ", valuation.holdings.len(), error)
+    } else {
+        format!(
+            " Holdings ({}) — waiting for prices ",
+            valuation.holdings.len()
+        )
+    };
+    let block = section_block(&title);
+
+    if valuation.holdings.is_empty() {
+        let placeholder =
+            Paragraph::new("No holdings — press a or use /position:add SYMBOL QTY COST")
+                .style(Style::default().fg(Color::Gray))
+                .alignment(Alignment::Center)
+                .block(block);
+        frame.render_widget(placeholder, area);
+        return;
+    }
+
+    let rows: Vec<Row> = valuation
+        .holdings
+        .iter()
+        .map(|entry| {
+            let symbol = entry.holding.symbol.as_str();
+            let pnl = entry.pnl();
+            Row::new(vec![
+                Cell::from(symbol.to_string()),
+                Cell::from(format_amount(entry.holding.quantity)),
+                Cell::from(app.format_price(symbol, entry.holding.cost_basis)),
+                Cell::from(
+                    entry
+                        .price
+                        .map(|price| app.format_price(symbol, price))
+                        .unwrap_or_else(|| "-".to_string()),
+                ),
+                Cell::from(format_optional(entry.value, |value| {
+                    format!("{:.2}", value)
+                })),
+                Cell::from(format_optional(entry.allocation_percent, |pct| {
+                    format!("{:.1}%", pct)
+                })),
+                Cell::from(format_optional(pnl, |pnl| format!("{:+.2}", pnl)))
+                    .style(pnl_style(pnl.unwrap_or(0.0))),
+                Cell::from(format_optional(entry.pnl_percent(), |pct| {
+                    format!("{:+.2}%", pct)
+                }))
+                .style(pnl_style(pnl.unwrap_or(0.0))),
+                Cell::from(format_optional(entry.daily_change, |change| {
+                    format!("{:+.2}", change)
+                }))
+                .style(pnl_style(entry.daily_change.unwrap_or(0.0))),
+            ])
+        })
+        .collect();
+
+    let widths = [
+        Constraint::Length(12),
+        Constraint::Length(12),
+        Constraint::Length(12),
+        Constraint::Length(12),
+        Constraint::Length(12),
+        Constraint::Length(7),
+        Constraint::Length(12),
+        Constraint::Length(9),
+        Constraint::Length(10),
+    ];
+    let table = Table::new(rows, widths)
+        .header(header_row(&[
+            "Symbol", "Qty", "Cost", "Last", "Value", "Alloc", "PnL", "PnL%", "24h",
+        ]))
+        .block(block)
+        .column_spacing(1);
+    frame.render_widget(table, area);
+}
+
+fn section_block(title: &str) -> Block<'_> {
+    Block::default()
+        .title(title)
+        .borders(Borders::TOP)
+        .border_style(Style::default().fg(Color::DarkGray))
+}
+
+fn header_row<'a>(titles: &[&'a str]) -> Row<'a> {
+    Row::new(titles.to_vec()).style(
+        Style::default()
+            .fg(Color::Cyan)
+            .add_modifier(Modifier::BOLD),
+    )
+}
+
+fn pnl_style(value: f64) -> Style {
+    if value > 0.0 {
+        Style::default().fg(Color::Green)
+    } else if value < 0.0 {
+        Style::default().fg(Color::Red)
+    } else {
+        Style::default()
+    }
+}
+
+fn format_optional(value: Option<f64>, format: impl Fn(f64) -> String) -> String {
+    value.map(format).unwrap_or_else(|| "-".to_string())
+}
@@ -7,9 +7,23 @@ use ratatui::widgets::{Block, Borders, Paragraph};
 use crate::ui::{AppState, CandleSample};
 
 pub(super) fn render_price_trend(frame: &mut Frame<'_>, area: Rect, app: &mut AppState) {
-    let block = Block::default()
-        .title(" Price Trend ")
-        .borders(Borders::ALL);
+    let data = app
+        .current_symbol()
+        .and_then(|symbol| app.market_data.get(symbol));
+    let chart_bars = data.and_then(|data| data.chart_bars);
+    // Flag daily charts that include candles recovered after a reconnect
+    let backfill = data
+        .and_then(|data| data.last_backfill)
+        .filter(|summary| summary.candles > 0 && chart_bars.is_none());
+    let title = match (chart_bars, backfill) {
+        (Some(spec), _) => format!(" Price Trend · {} bars ", spec),
+        (None, Some(summary)) => format!(
+            " Price Trend · backfilled {} ",
+            format_clock_time(summary.received_ms)
+        ),
+        (None, None) => " Price Trend ".to_string(),
+    };
+    let block = Block::default().title(title).borders(Borders::ALL);
     let inner = block.inner(area);
     frame.render_widget(block, area);
 
@@ -23,6 +37,8 @@ pub(super) fn render_price_trend(frame: &mut Frame<'_>, area: Rect, app: &mut Ap
         return;
     };
 
+    let price_decimals = app.price_decimals(&symbol);
+
     let Some(data) = app.market_data.get_mut(&symbol) else {
         render_price_trend_placeholder(frame, inner);
         return;
@@ -73,9 +89,10 @@ pub(super) fn render_price_trend(frame: &mut Frame<'_>, area: Rect, app: &mut Ap
     {
         let buffer = frame.buffer_mut();
         let label_style = Style::default().fg(Color::Gray);
-        let top_label = format_price_label(max_price, price_axis_area.width);
-        let mid_label = format_price_label((min_price + max_price) / 2.0, price_axis_area.width);
-        let bottom_label = format_price_label(min_price, price_axis_area.width);
+        let width = price_axis_area.width;
+        let top_label = format_price_label(max_price, price_decimals, width);
+        let mid_label = format_price_label((min_price + max_price) / 2.0, price_decimals, width);
+        let bottom_label = format_price_label(min_price, price_decimals, width);
 
         buffer.set_string(price_axis_area.x, price_axis_area.y, top_label, label_style);
 
@@ -89,12 +106,23 @@ pub(super) fn render_price_trend(frame: &mut Frame<'_>, area: Rect, app: &mut Ap
     }
 
     // Render time axis summary
-    let time_text = match (cache.samples.first(), cache.samples.last()) {
-        (Some(first), Some(last)) => {
+    let time_text = match (cache.samples.first(), cache.samples.last(), chart_bars) {
+        (Some(first), Some(last), None) => {
             let start = format_candle_date(first.open_time_ms);
             let end = format_candle_date(last.close_time_ms);
             format!("{} → {} ({} candles)", start, end, cache.samples.len())
         }
+        (Some(first), Some(last), Some(spec)) => {
+            let start = format_clock_time(first.open_time_ms);
+            let end = format_clock_time(last.close_time_ms);
+            format!(
+                "{} → {} ({} {} bars)",
+                start,
+                end,
+                cache.samples.len(),
+                spec
+            )
+        }
         _ => "Daily candle data unavailable".to_string(),
     };
 
@@ -183,8 +211,8 @@ fn within(area: Rect, x: u16, y: u16) -> bool {
     x >= area.x && x < area.x + area.width && y >= area.y && y < area.y + area.height
 }
 
-fn format_price_label(value: f64, width: u16) -> String {
-    let mut label = format!("{:.2}", value);
+fn format_price_label(value: f64, decimals: usize, width: u16) -> String {
+    let mut label = format!("{:.*}", decimals, value);
     let max_len = width as usize;
     if max_len > 0 && label.len() > max_len {
         label.truncate(max_len);
@@ -201,6 +229,12 @@ fn format_candle_date(timestamp_ms: u64) -> String {
     }
 }
 
+fn format_clock_time(timestamp_ms: u64) -> String {
+    DateTime::<Utc>::from_timestamp_millis(timestamp_ms as i64)
+        .map(|datetime| datetime.format("%H:%M:%S").to_string())
+        .unwrap_or_else(|| "-".to_string())
+}
+
 fn render_price_trend_placeholder(frame: &mut Frame<'_>, area: Rect) {
     frame.render_widget(
         Paragraph::new("Collecting price data...")
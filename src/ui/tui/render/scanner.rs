@@ -0,0 +1,149 @@
+use ratatui::Frame;
+use ratatui::layout::{Alignment, Constraint, Direction, Layout};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::widgets::{Block, Borders, Cell, Clear, Paragraph, Row, Table};
+
+use crate::ui::AppState;
+
+use super::layout::centered_rect;
+
+pub(super) fn render_scanner_overlay(frame: &mut Frame<'_>, app: &AppState) {
+    let overlay_area = centered_rect(80, 70, frame.size());
+    frame.render_widget(Clear, overlay_area);
+
+    let rows = app.scanner_rows();
+    let block = Block::default()
+        .title(format!(
+            " Scanner · {} {} ",
+            app.scanner_sort.label(),
+            if app.scanner_descending { "↓" } else { "↑" }
+        ))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .style(Style::default().bg(Color::Black));
+    frame.render_widget(block.clone(), overlay_area);
+
+    let inner = block.inner(overlay_area);
+    let layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1),
+            Constraint::Min(3),
+            Constraint::Length(1),
+        ])
+        .margin(1)
+        .split(inner);
+
+    let summary = Paragraph::new(format!(
+        "Quote: {}  | Min volume: {}  | Showing {} of {} symbols",
+        app.scanner_filter.quote_asset.as_deref().unwrap_or("all"),
+        format_volume(app.scanner_filter.min_quote_volume),
+        rows.len(),
+        app.scanner.len()
+    ))
+    .style(Style::default().fg(Color::Gray));
+    frame.render_widget(summary, layout[0]);
+
+    if rows.is_empty() {
+        let message = if app.scanner.is_empty() {
+            "Waiting for the all-market ticker stream..."
+        } else {
+            "No symbols match the current filter"
+        };
+        let placeholder = Paragraph::new(message)
+            .style(Style::default().fg(Color::Gray))
+            .alignment(Alignment::Center);
+        frame.render_widget(placeholder, layout[1]);
+    } else {
+        // Scroll so the highlighted row stays visible below the header
+        let visible = (layout[1].height as usize).saturating_sub(1).max(1);
+        let offset = app.selected_scanner_index.saturating_sub(visible - 1);
+
+        let table_rows: Vec<Row> = rows
+            .iter()
+            .enumerate()
+            .skip(offset)
+            .take(visible)
+            .map(|(idx, row)| {
+                let ticker = &row.ticker;
+                let change_style = if row.change_percent >= 0.0 {
+                    Style::default().fg(Color::Green)
+                } else {
+                    Style::default().fg(Color::Red)
+                };
+                let watched = app.symbols.contains(&ticker.symbol);
+
+                let mut table_row = Row::new(vec![
+                    Cell::from(format!("{}", idx + 1)),
+                    Cell::from(if watched {
+                        format!("{} *", ticker.symbol)
+                    } else {
+                        ticker.symbol.clone()
+                    }),
+                    Cell::from(app.format_price(&ticker.symbol, ticker.last_price)),
+                    Cell::from(format!("{:+.2}%", row.change_percent)).style(change_style),
+                    Cell::from(format_volume(ticker.quote_volume)),
+                    Cell::from(format!("{:.2}%", row.range_percent)),
+                    Cell::from(format!("{:.2}%", row.volatility_percent)),
+                ]);
+
+                if idx == app.selected_scanner_index {
+                    table_row = table_row.style(
+                        Style::default()
+                            .fg(Color::White)
+                            .bg(Color::Blue)
+                            .add_modifier(Modifier::BOLD),
+                    );
+                }
+                table_row
+            })
+            .collect();
+
+        let widths = [
+            Constraint::Length(5),
+            Constraint::Length(16),
+            Constraint::Length(14),
+            Constraint::Length(9),
+            Constraint::Length(10),
+            Constraint::Length(8),
+            Constraint::Length(8),
+        ];
+        let table = Table::new(table_rows, widths)
+            .header(
+                Row::new(vec![
+                    "#",
+                    "Symbol",
+                    "Last",
+                    "24h %",
+                    "Quote Vol",
+                    "Range",
+                    "Vol",
+                ])
+                .style(
+                    Style::default()
+                        .fg(Color::Cyan)
+                        .add_modifier(Modifier::BOLD),
+                ),
+            )
+            .column_spacing(1);
+        frame.render_widget(table, layout[1]);
+    }
+
+    let hints = Paragraph::new(
+        "↑/↓ move   s sort   r reverse   f quote   v min volume   Enter add to watchlist   Esc close",
+    )
+    .style(Style::default().fg(Color::Gray));
+    frame.render_widget(hints, layout[2]);
+}
+
+fn format_volume(value: f64) -> String {
+    if value >= 1_000_000_000.0 {
+        format!("{:.2}B", value / 1_000_000_000.0)
+    } else if value >= 1_000_000.0 {
+        format!("{:.1}M", value / 1_000_000.0)
+    } else if value >= 1_000.0 {
+        format!("{:.1}K", value / 1_000.0)
+    } else {
+        format!("{:.0}", value)
+    }
+}
@@ -0,0 +1,141 @@
+use ratatui::Frame;
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Paragraph};
+
+use crate::market_data::{TakerSide, TradeAnalyticsSnapshot};
+use crate::ui::AppState;
+
+pub(super) fn render_trade_flow(frame: &mut Frame<'_>, area: Rect, app: &AppState) {
+    let symbol = app.current_symbol().cloned().unwrap_or_default();
+    let analytics = app
+        .market_data
+        .get(&symbol)
+        .and_then(|data| data.trade_analytics.as_ref());
+
+    let title = match analytics {
+        Some(analytics) => format!(" Trade Flow · {}s ", analytics.window_ms / 1_000),
+        None => " Trade Flow ".to_string(),
+    };
+    let block = Block::default().title(title).borders(Borders::ALL);
+    let inner_area = block.inner(area);
+    frame.render_widget(block, area);
+
+    let Some(analytics) = analytics else {
+        let placeholder = Paragraph::new("Waiting for trades...")
+            .style(Style::default().fg(Color::Gray))
+            .alignment(ratatui::layout::Alignment::Center);
+        frame.render_widget(placeholder, inner_area);
+        return;
+    };
+
+    let lines = trade_flow_lines(app, &symbol, analytics, inner_area.height as usize);
+    frame.render_widget(Paragraph::new(lines), inner_area);
+}
+
+fn trade_flow_lines(
+    app: &AppState,
+    symbol: &str,
+    analytics: &TradeAnalyticsSnapshot,
+    height: usize,
+) -> Vec<Line<'static>> {
+    let gray = Style::default().fg(Color::Gray);
+    let delta = analytics.window_delta();
+
+    let mut lines = vec![
+        Line::from(vec![
+            Span::styled(
+                format!("Buy {:.4}", analytics.buy_volume),
+                Style::default().fg(Color::Green),
+            ),
+            Span::raw("  "),
+            Span::styled(
+                format!("Sell {:.4}", analytics.sell_volume),
+                Style::default().fg(Color::Red),
+            ),
+            Span::raw("  "),
+            Span::styled(format!("Δ {:+.4}", delta), signed_style(delta)),
+            Span::styled(
+                analytics
+                    .buy_ratio_percent()
+                    .map(|ratio| format!(" ({:.0}% buy)", ratio))
+                    .unwrap_or_default(),
+                gray,
+            ),
+        ]),
+        Line::from(vec![
+            Span::styled("CVD ", gray),
+            Span::styled(
+                format!("{:+.4}", analytics.cumulative_delta),
+                signed_style(analytics.cumulative_delta),
+            ),
+            Span::styled("  VWAP ", gray),
+            Span::raw(
+                analytics
+                    .session_vwap
+                    .map(|vwap| app.format_price(symbol, vwap))
+                    .unwrap_or_else(|| "-".to_string()),
+            ),
+            Span::styled(
+                analytics
+                    .vwap_deviation_percent()
+                    .map(|deviation| format!(" ({:+.2}%)", deviation))
+                    .unwrap_or_default(),
+                gray,
+            ),
+        ]),
+        Line::from(vec![
+            Span::styled(
+                format!(
+                    "{:.1} trades/s ({} in window)",
+                    analytics.trades_per_sec, analytics.trade_count
+                ),
+                gray,
+            ),
+            Span::styled(
+                if analytics.backfilled_trades > 0 {
+                    format!(" · {} backfilled", analytics.backfilled_trades)
+                } else {
+                    String::new()
+                },
+                Style::default().fg(Color::Yellow),
+            ),
+        ]),
+    ];
+
+    let remaining = height.saturating_sub(lines.len() + 1);
+    if remaining > 0 && !analytics.largest_trades.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "Largest",
+            gray.add_modifier(Modifier::BOLD),
+        )));
+        for trade in analytics.largest_trades.iter().take(remaining) {
+            let (label, color) = match trade.side {
+                TakerSide::Buy => ("B", Color::Green),
+                TakerSide::Sell => ("S", Color::Red),
+            };
+            lines.push(Line::from(vec![
+                Span::styled(format!("{} ", label), Style::default().fg(color)),
+                Span::raw(format!(
+                    "{:.4} @ {}",
+                    trade.quantity,
+                    app.format_price(symbol, trade.price)
+                )),
+                Span::styled(format!("  {:.0}", trade.notional()), gray),
+            ]));
+        }
+    }
+
+    lines
+}
+
+fn signed_style(value: f64) -> Style {
+    if value > 0.0 {
+        Style::default().fg(Color::Green)
+    } else if value < 0.0 {
+        Style::default().fg(Color::Red)
+    } else {
+        Style::default()
+    }
+}
@@ -11,12 +11,14 @@ use crossterm::event::{self, Event};
 use crate::cli::Cli;
 use crate::config::Config;
 use crate::market_data::{DEFAULT_DAILY_CANDLE_LIMIT, MarketDataManager, MarketEvent};
-use crate::metrics::ConnectionStatus as MetricsConnectionStatus;
+use crate::metrics::exporter;
 use crate::session::action_channel::{SessionEvent, StatusInfo};
+use crate::session::command_router::{AlertAction, InteractiveCommand};
 use crate::session::session_manager::SessionStats;
 
+use super::command_history::CommandHistory;
 use super::tui::{Tui, UiAction, handle_key_event};
-use super::{AppState, PricePoint};
+use super::{AppState, InputMode, PricePoint};
 
 /// UI Manager for managing the terminal interface
 pub struct UIManager {
@@ -94,13 +96,22 @@ impl UIManager {
 
         let refresh_interval = Duration::from_millis(config.refresh_rate_ms.clamp(16, 1000));
 
+        let mut app_state = AppState::new(config.symbols.clone());
+        app_state.exchange_info = market_manager.exchange_info();
+        app_state.command_history =
+            CommandHistory::load(&config.ui.history_file, config.ui.history_size);
+        app_state.alert_history_limit = config.alerts.history_limit;
+        app_state.bar_specs = config.candles.bars.clone();
+        app_state.bar_history = config.candles.history;
+        app_state.heatmap = config.ui.heatmap_settings();
+
         Self {
             market_manager,
             session_event_tx,
             ui_event_tx,
             event_rx: Some(ui_event_rx),
             market_event_rx: Some(market_event_rx),
-            app_state: AppState::new(config.symbols.clone()),
+            app_state,
             render_state: RenderState::default(),
             dry_run: false,
             config,
@@ -360,6 +371,19 @@ impl UIManager {
                                 self.app_state.push_log(message);
                             }
                         }
+                        UiAction::SubmitAlertEdit { id, update } => {
+                            let command = InteractiveCommand::Alert {
+                                action: AlertAction::Edit { id, update },
+                            };
+                            if let Err(e) = self
+                                .session_event_tx
+                                .send(SessionEvent::UserCommand { command })
+                            {
+                                let message = format!("Failed to edit alert from UI: {}", e);
+                                self.render_state.error_message = Some(message.clone());
+                                self.app_state.push_log(message);
+                            }
+                        }
                         UiAction::SubmitCommand(cmd) => {
                             if let Err(e) = self.process_user_command(&cmd) {
                                 let message = format!("Command error: {}", e);
@@ -399,6 +423,11 @@ impl UIManager {
         // Process session events
         let mut events_to_process = Vec::new();
         if let Some(event_rx) = &mut self.event_rx {
+            metrics::gauge!(
+                exporter::CHANNEL_QUEUE_DEPTH,
+                event_rx.len() as f64,
+                "channel" => "ui_events"
+            );
             while let Ok(event) = event_rx.try_recv() {
                 events_to_process.push(event);
             }
@@ -572,7 +601,7 @@ impl UIManager {
                     .push_log(format!("UI mode changed: TUI {}", enable_tui));
             }
             SessionEvent::ConfigInfo { config } => {
-                self.config = config.clone();
+                self.config = *config;
                 self.refresh_interval = Duration::from_millis(self.config.refresh_rate_ms.max(16));
                 let message = format!(
                     "Config updated → refresh {}ms, depth {}",
@@ -625,6 +654,77 @@ impl UIManager {
             SessionEvent::AlertSnapshot { alerts } => {
                 self.app_state.update_alerts(alerts);
             }
+            SessionEvent::AlertHistorySnapshot { entries } => {
+                self.app_state.update_alert_history(entries);
+            }
+            SessionEvent::AlertHistoryRecorded { entry } => {
+                self.app_state.push_alert_history(entry);
+            }
+            SessionEvent::ShowAlertHistory { symbol } => {
+                self.app_state.show_alert_history(symbol);
+            }
+            SessionEvent::ShowScanner { visible } => {
+                if visible {
+                    if !matches!(self.app_state.input_mode, InputMode::Scanner) {
+                        self.app_state.enter_scanner_view();
+                    }
+                } else {
+                    self.app_state.exit_scanner_view();
+                }
+            }
+            SessionEvent::PaperNotification { message } => {
+                self.render_state.queue_message(message.clone());
+                self.app_state.push_log(format!("[paper] {}", message));
+                self.app_state.push_notification(message);
+            }
+            SessionEvent::PaperSnapshot { snapshot } => {
+                self.app_state.update_paper(snapshot);
+            }
+            SessionEvent::ShowPaper => {
+                self.app_state.enter_paper_view();
+            }
+            SessionEvent::AccountNotification { message } => {
+                self.render_state.queue_message(message.clone());
+                self.app_state.push_log(format!("[account] {}", message));
+                self.app_state.push_notification(message);
+            }
+            SessionEvent::AccountSnapshot { snapshot } => {
+                self.app_state.update_account(snapshot);
+            }
+            SessionEvent::ShowAccount => {
+                self.app_state.enter_account_view();
+            }
+            SessionEvent::PortfolioNotification { message } => {
+                self.render_state.queue_message(message.clone());
+                self.app_state.push_log(format!("[portfolio] {}", message));
+                self.app_state.push_notification(message);
+            }
+            SessionEvent::PortfolioSnapshot { snapshot } => {
+                self.app_state.update_portfolio(snapshot);
+            }
+            SessionEvent::ShowPortfolio => {
+                self.app_state.enter_portfolio_view();
+            }
+            SessionEvent::ShowImpact { request } => {
+                self.app_state.enter_impact_view(request);
+            }
+            SessionEvent::OrderNotification { message } => {
+                self.render_state.queue_message(message.clone());
+                self.app_state.push_log(format!("[order] {}", message));
+                self.app_state.push_notification(message);
+            }
+            SessionEvent::OrderRejected { message } => {
+                self.render_state.queue_message(message.clone());
+                self.app_state.push_log(format!("[order] {}", message));
+                self.app_state.order_rejected(message);
+            }
+            SessionEvent::OrderStaged { summary } => {
+                if let Some(summary) = &summary {
+                    self.app_state
+                        .push_log(format!("[order] Awaiting confirmation: {}", summary));
+                }
+                self.app_state.show_order_confirmation(summary);
+            }
             SessionEvent::AlertList { entries } => {
                 if entries.is_empty() {
                     self.render_state
@@ -667,6 +767,7 @@ impl UIManager {
                     market_data.price_history.push(PricePoint {
                         timestamp_ms: time,
                         price,
+                        backfilled: false,
                     });
 
                     // Keep history size manageable
@@ -688,13 +789,21 @@ impl UIManager {
                             high_24h: 0.0,
                             low_24h: 0.0,
                             orderbook: None,
+                            book_ticker: None,
+                            trade_analytics: None,
+                            microstructure: Vec::new(),
+                            liquidity: Default::default(),
                             price_history: vec![PricePoint {
                                 timestamp_ms: time,
                                 price,
+                                backfilled: false,
                             }],
                             daily_candles: Vec::new(),
                             kline_render_cache: None,
                             last_kline_refresh: None,
+                            last_backfill: None,
+                            bar_series: Vec::new(),
+                            chart_bars: None,
                         },
                     );
                     self.render_state
@@ -733,10 +842,49 @@ impl UIManager {
             MarketEvent::OrderBookUpdate { symbol, orderbook } => {
                 // Update orderbook
                 if let Some(market_data) = self.app_state.market_data.get_mut(&symbol) {
+                    let now_ms = std::time::SystemTime::now()
+                        .duration_since(std::time::UNIX_EPOCH)
+                        .unwrap_or_default()
+                        .as_millis() as u64;
+                    market_data.liquidity.record(
+                        &orderbook,
+                        Some(market_data.price),
+                        now_ms,
+                        self.app_state.heatmap,
+                    );
                     market_data.orderbook = Some(orderbook);
                     should_redraw = true;
                 }
             }
+            MarketEvent::BookTicker { symbol, ticker } => {
+                use std::collections::hash_map::Entry;
+
+                let market_data = match self.app_state.market_data.entry(symbol.clone()) {
+                    Entry::Occupied(occupied) => occupied.into_mut(),
+                    Entry::Vacant(vacant) => vacant.insert(super::MarketDataState {
+                        symbol,
+                        ..Default::default()
+                    }),
+                };
+                market_data.book_ticker = Some(ticker);
+                should_redraw = true;
+            }
+            MarketEvent::Trade { .. } => {
+                // The TUI draws trades from price updates and the analytics snapshot
+            }
+            MarketEvent::TradeAnalytics { symbol, analytics } => {
+                if let Some(market_data) = self.app_state.market_data.get_mut(&symbol) {
+                    market_data.trade_analytics = Some(analytics);
+                    should_redraw = true;
+                }
+            }
+            MarketEvent::Microstructure { symbol, snapshot } => {
+                if let Some(market_data) = self.app_state.market_data.get_mut(&symbol) {
+                    let max_points = self.config.ui.sparkline_points.max(2);
+                    market_data.push_microstructure(snapshot, max_points);
+                    should_redraw = self.app_state.input_mode == InputMode::Microstructure;
+                }
+            }
             MarketEvent::ConnectionStatus { symbol, status } => {
                 debug!("Connection status for {}: {:?}", symbol, status);
                 if !matches!(status, crate::binance::types::ConnectionStatus::Connected) {
@@ -746,28 +894,26 @@ impl UIManager {
                         .push_log(format!("Connection status for {}: {:?}", symbol, status));
                 }
 
-                self.app_state.connection_metrics.status = match status {
-                    crate::binance::types::ConnectionStatus::Disconnected => {
-                        MetricsConnectionStatus::Disconnected
-                    }
-                    crate::binance::types::ConnectionStatus::Connecting => {
-                        MetricsConnectionStatus::Connecting
-                    }
-                    crate::binance::types::ConnectionStatus::Connected => {
-                        MetricsConnectionStatus::Connected
-                    }
-                    crate::binance::types::ConnectionStatus::Reconnecting => {
-                        MetricsConnectionStatus::Reconnecting
-                    }
-                    crate::binance::types::ConnectionStatus::Error(err) => {
-                        MetricsConnectionStatus::Error(err)
-                    }
-                };
+                // Global status comes from the next metrics snapshot; only touch this symbol's row
+                self.app_state
+                    .set_symbol_connection_status(&symbol, status.into());
+                should_redraw = true;
+            }
+            MarketEvent::SymbolStatusChanged {
+                symbol,
+                previous,
+                current,
+            } => {
+                // Notification text arrives separately via AlertNotification
+                debug!(
+                    "Symbol {} status changed: {} -> {}",
+                    symbol, previous, current
+                );
                 should_redraw = true;
             }
             MarketEvent::DailyCandleUpdate {
                 symbol,
-                mut candles,
+                candles,
                 is_snapshot,
             } => {
                 use std::collections::hash_map::Entry;
@@ -781,9 +927,7 @@ impl UIManager {
                     }),
                 };
 
-                let mut appended_closed = false;
-
-                if is_snapshot {
+                let appended_closed = if is_snapshot {
                     market_data.daily_candles = candles;
                     if market_data.daily_candles.len() > DEFAULT_DAILY_CANDLE_LIMIT {
                         let overflow = market_data.daily_candles.len() - DEFAULT_DAILY_CANDLE_LIMIT;
@@ -799,35 +943,10 @@ impl UIManager {
                         market_data.daily_candles.len(),
                         symbol
                     ));
-                    appended_closed = true; // force redraw on fresh snapshot
+                    true // force redraw on fresh snapshot
                 } else {
-                    for candle in candles.drain(..) {
-                        if let Some(existing) = market_data
-                            .daily_candles
-                            .iter_mut()
-                            .find(|existing| existing.open_time_ms == candle.open_time_ms)
-                        {
-                            if candle.is_closed && !existing.is_closed {
-                                appended_closed = true;
-                            }
-                            *existing = candle;
-                        } else {
-                            if candle.is_closed {
-                                appended_closed = true;
-                            }
-                            market_data.daily_candles.push(candle);
-                        }
-                    }
-
-                    if market_data.daily_candles.len() > DEFAULT_DAILY_CANDLE_LIMIT {
-                        let overflow = market_data.daily_candles.len() - DEFAULT_DAILY_CANDLE_LIMIT;
-                        market_data.daily_candles.drain(0..overflow);
-                    }
-
-                    market_data
-                        .daily_candles
-                        .sort_by_key(|candle| candle.open_time_ms);
-                }
+                    market_data.merge_daily_candles(candles)
+                };
 
                 market_data.invalidate_kline_cache();
 
@@ -843,6 +962,86 @@ impl UIManager {
                     should_redraw = true;
                 }
             }
+            MarketEvent::Bar { symbol, spec, bar } => {
+                if let Some(market_data) = self.app_state.market_data.get_mut(&symbol) {
+                    let charted = market_data.apply_bar(spec, bar, self.app_state.bar_history);
+                    let is_current = self.app_state.current_symbol() == Some(&symbol);
+                    should_redraw = charted && is_current;
+                }
+            }
+            MarketEvent::BarHistory { symbol, spec, bars } => {
+                let market_data = self
+                    .app_state
+                    .market_data
+                    .entry(symbol.clone())
+                    .or_insert_with(|| super::MarketDataState {
+                        symbol,
+                        ..Default::default()
+                    });
+                market_data.load_bar_history(spec, bars, self.app_state.bar_history);
+                should_redraw = true;
+            }
+            MarketEvent::Backfill {
+                symbol,
+                trades,
+                candles,
+                truncated,
+            } => {
+                let Some(market_data) = self.app_state.market_data.get_mut(&symbol) else {
+                    return Ok(());
+                };
+
+                let summary = super::BackfillSummary {
+                    trades: trades.len(),
+                    candles: candles.len(),
+                    truncated,
+                    received_ms: chrono::Utc::now().timestamp_millis().max(0) as u64,
+                };
+
+                let points: Vec<PricePoint> = trades
+                    .iter()
+                    .map(|trade| PricePoint {
+                        timestamp_ms: trade.time_ms,
+                        price: trade.price,
+                        backfilled: true,
+                    })
+                    .collect();
+                market_data
+                    .merge_backfilled_prices(&points, self.config.ui.sparkline_points.max(2));
+
+                if !candles.is_empty() {
+                    market_data.merge_daily_candles(candles);
+                    market_data.invalidate_kline_cache();
+                }
+                market_data.last_backfill = Some(summary);
+
+                let message = format!(
+                    "Backfilled {} trades and {} candles for {} after reconnect{}",
+                    summary.trades,
+                    summary.candles,
+                    symbol,
+                    if truncated {
+                        " (gap only partly recovered)"
+                    } else {
+                        ""
+                    }
+                );
+                self.render_state.queue_message(message.clone());
+                self.app_state.push_log(message);
+                should_redraw = true;
+            }
+            MarketEvent::ClockOffset { estimate } => {
+                // Skew warnings arrive separately; keep the panel current between snapshots
+                self.app_state.connection_metrics.clock = Some(estimate);
+                should_redraw = true;
+            }
+            MarketEvent::ScannerTickers { tickers } => {
+                // A late batch can arrive after the view was closed
+                if matches!(self.app_state.input_mode, InputMode::Scanner) {
+                    self.app_state.apply_scanner_tickers(&tickers);
+                    should_redraw = true;
+                }
+            }
             MarketEvent::Error { symbol, error } => {
                 let message = format!("Market error for {}: {}", symbol, error);
                 self.render_state.error_message = Some(message.clone());
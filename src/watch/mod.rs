@@ -0,0 +1,1017 @@
+//! Streaming, non-interactive output of market events
+//!
+//! Backs `xtrade watch`: subscribes like the TUI does, but flattens each
+//! [`MarketEvent`] into a [`WatchRecord`] and writes it to stdout as NDJSON,
+//! CSV or an aligned text line, flushing after every record so the stream
+//! can feed `jq`, log shippers or other programs. Alert triggers are written
+//! as records of their own.
+
+use std::io::{self, Write};
+
+use anyhow::{Context, Result, bail};
+use chrono::{Local, TimeZone};
+use serde::Serialize;
+use tracing::{debug, info, warn};
+
+use crate::binance::types::{ConnectionStatus, OrderBook};
+use crate::cli::WatchArgs;
+use crate::config::Config;
+use crate::market_data::{BarSpec, MarketDataManager, MarketEvent, TakerSide};
+use crate::query::{EXIT_OK, QueryError, exit_code, normalize_symbols};
+use crate::session::alert_manager::{AlertDirection, AlertInput, AlertManager};
+use crate::session::command_router::{AlertAction, parse_alert_add};
+
+/// Output encoding for `xtrade watch`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum WatchFormat {
+    /// One JSON object per line
+    Ndjson,
+    /// Aligned, human-readable lines
+    Table,
+    /// Comma separated values with a fixed header
+    Csv,
+}
+
+/// Event families that can be selected with `--events`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum WatchEventKind {
+    /// 24h rolling ticker updates
+    Ticker,
+    /// Individual trade prints
+    Trade,
+    /// Best bid/offer from the book ticker stream
+    Book,
+    /// Top levels of the local order book
+    Depth,
+    /// Closed local bars of the configured candle specs
+    Bar,
+    /// Rolling trade flow analytics
+    Flow,
+    /// Connection, error and trading status changes
+    Status,
+}
+
+/// Default `--events` selection
+pub const DEFAULT_WATCH_EVENTS: [WatchEventKind; 3] = [
+    WatchEventKind::Ticker,
+    WatchEventKind::Trade,
+    WatchEventKind::Book,
+];
+
+const CSV_HEADER: &str = "ts,type,symbol,price,quantity,bid,bid_qty,ask,ask_qty,info";
+
+/// One normalized output line
+///
+/// `ts` is the local receive time in milliseconds; exchange timestamps are
+/// kept in their own fields where the event carries one.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum WatchRecord {
+    Ticker {
+        ts: u64,
+        symbol: String,
+        last: f64,
+        change_percent: f64,
+        high: f64,
+        low: f64,
+        volume: f64,
+    },
+    Trade {
+        ts: u64,
+        symbol: String,
+        time: u64,
+        price: f64,
+        quantity: f64,
+        side: TakerSide,
+        trade_id: u64,
+    },
+    Book {
+        ts: u64,
+        symbol: String,
+        update_id: u64,
+        bid: f64,
+        bid_qty: f64,
+        ask: f64,
+        ask_qty: f64,
+    },
+    Depth {
+        ts: u64,
+        symbol: String,
+        update_id: u64,
+        /// `[price, quantity]`, best first
+        bids: Vec<[f64; 2]>,
+        asks: Vec<[f64; 2]>,
+    },
+    Bar {
+        ts: u64,
+        symbol: String,
+        spec: String,
+        open_time: u64,
+        close_time: u64,
+        open: f64,
+        high: f64,
+        low: f64,
+        close: f64,
+        volume: f64,
+        trades: u64,
+    },
+    Flow {
+        ts: u64,
+        symbol: String,
+        trades_per_sec: f64,
+        window_delta: f64,
+        cumulative_delta: f64,
+        vwap: Option<f64>,
+    },
+    Status {
+        ts: u64,
+        symbol: String,
+        status: String,
+    },
+    Alert {
+        ts: u64,
+        symbol: String,
+        alert_id: u64,
+        input: AlertInput,
+        direction: AlertDirection,
+        threshold: f64,
+        value: f64,
+    },
+}
+
+impl WatchRecord {
+    /// Flatten a market event, or `None` when its family is not selected
+    pub fn from_event(
+        event: &MarketEvent,
+        kinds: &[WatchEventKind],
+        depth: usize,
+        ts: u64,
+    ) -> Option<Self> {
+        let wants = |kind| kinds.contains(&kind);
+        let record = match event {
+            MarketEvent::TickerUpdate {
+                symbol,
+                last_price,
+                price_change_percent,
+                high_price,
+                low_price,
+                volume,
+            } if wants(WatchEventKind::Ticker) => WatchRecord::Ticker {
+                ts,
+                symbol: symbol.clone(),
+                last: *last_price,
+                change_percent: *price_change_percent,
+                high: *high_price,
+                low: *low_price,
+                volume: *volume,
+            },
+            MarketEvent::Trade { symbol, trade } if wants(WatchEventKind::Trade) => {
+                WatchRecord::Trade {
+                    ts,
+                    symbol: symbol.clone(),
+                    time: trade.time_ms,
+                    price: trade.price,
+                    quantity: trade.quantity,
+                    side: trade.side,
+                    trade_id: trade.trade_id,
+                }
+            }
+            MarketEvent::BookTicker { symbol, ticker } if wants(WatchEventKind::Book) => {
+                WatchRecord::Book {
+                    ts,
+                    symbol: symbol.clone(),
+                    update_id: ticker.update_id,
+                    bid: ticker.bid_price,
+                    bid_qty: ticker.bid_qty,
+                    ask: ticker.ask_price,
+                    ask_qty: ticker.ask_qty,
+                }
+            }
+            MarketEvent::OrderBookUpdate { symbol, orderbook } if wants(WatchEventKind::Depth) => {
+                let (bids, asks) = top_levels(orderbook, depth);
+                WatchRecord::Depth {
+                    ts,
+                    symbol: symbol.clone(),
+                    update_id: orderbook.last_update_id,
+                    bids,
+                    asks,
+                }
+            }
+            MarketEvent::Bar { symbol, spec, bar } if wants(WatchEventKind::Bar) => {
+                if !bar.is_closed {
+                    return None;
+                }
+                WatchRecord::Bar {
+                    ts,
+                    symbol: symbol.clone(),
+                    spec: spec.label(),
+                    open_time: bar.open_time_ms,
+                    close_time: bar.close_time_ms,
+                    open: bar.open,
+                    high: bar.high,
+                    low: bar.low,
+                    close: bar.close,
+                    volume: bar.volume,
+                    trades: bar.trades,
+                }
+            }
+            MarketEvent::TradeAnalytics { symbol, analytics } if wants(WatchEventKind::Flow) => {
+                WatchRecord::Flow {
+                    ts,
+                    symbol: symbol.clone(),
+                    trades_per_sec: analytics.trades_per_sec,
+                    window_delta: analytics.window_delta(),
+                    cumulative_delta: analytics.cumulative_delta,
+                    vwap: analytics.session_vwap,
+                }
+            }
+            MarketEvent::ConnectionStatus { symbol, status } if wants(WatchEventKind::Status) => {
+                WatchRecord::Status {
+                    ts,
+                    symbol: symbol.clone(),
+                    status: match status {
+                        ConnectionStatus::Error(detail) => format!("error: {}", detail),
+                        other => format!("{:?}", other).to_lowercase(),
+                    },
+                }
+            }
+            MarketEvent::Error { symbol, error } if wants(WatchEventKind::Status) => {
+                WatchRecord::Status {
+                    ts,
+                    symbol: symbol.clone(),
+                    status: format!("error: {}", error),
+                }
+            }
+            MarketEvent::SymbolStatusChanged {
+                symbol,
+                previous,
+                current,
+            } if wants(WatchEventKind::Status) => WatchRecord::Status {
+                ts,
+                symbol: symbol.clone(),
+                status: format!("trading status {} -> {}", previous, current),
+            },
+            _ => return None,
+        };
+        Some(record)
+    }
+
+    fn csv_row(&self) -> String {
+        let num = |value: f64| value.to_string();
+        let (ts, kind, symbol, price, quantity, bid, bid_qty, ask, ask_qty, info) = match self {
+            WatchRecord::Ticker {
+                ts,
+                symbol,
+                last,
+                change_percent,
+                high,
+                low,
+                volume,
+            } => (
+                ts,
+                "ticker",
+                symbol,
+                num(*last),
+                num(*volume),
+                String::new(),
+                String::new(),
+                String::new(),
+                String::new(),
+                format!("change={}% high={} low={}", change_percent, high, low),
+            ),
+            WatchRecord::Trade {
+                ts,
+                symbol,
+                time,
+                price,
+                quantity,
+                side,
+                trade_id,
+            } => (
+                ts,
+                "trade",
+                symbol,
+                num(*price),
+                num(*quantity),
+                String::new(),
+                String::new(),
+                String::new(),
+                String::new(),
+                format!("side={} time={} id={}", side_label(*side), time, trade_id),
+            ),
+            WatchRecord::Book {
+                ts,
+                symbol,
+                update_id,
+                bid,
+                bid_qty,
+                ask,
+                ask_qty,
+            } => (
+                ts,
+                "book",
+                symbol,
+                String::new(),
+                String::new(),
+                num(*bid),
+                num(*bid_qty),
+                num(*ask),
+                num(*ask_qty),
+                format!("update_id={}", update_id),
+            ),
+            WatchRecord::Depth {
+                ts,
+                symbol,
+                update_id,
+                bids,
+                asks,
+            } => (
+                ts,
+                "depth",
+                symbol,
+                String::new(),
+                String::new(),
+                bids.first().map(|l| num(l[0])).unwrap_or_default(),
+                bids.first().map(|l| num(l[1])).unwrap_or_default(),
+                asks.first().map(|l| num(l[0])).unwrap_or_default(),
+                asks.first().map(|l| num(l[1])).unwrap_or_default(),
+                format!(
+                    "update_id={} bids={} asks={}",
+                    update_id,
+                    format_levels(bids),
+                    format_levels(asks)
+                ),
+            ),
+            WatchRecord::Bar {
+                ts,
+                symbol,
+                spec,
+                open_time,
+                open,
+                high,
+                low,
+                close,
+                volume,
+                trades,
+                ..
+            } => (
+                ts,
+                "bar",
+                symbol,
+                num(*close),
+                num(*volume),
+                String::new(),
+                String::new(),
+                String::new(),
+                String::new(),
+                format!(
+                    "spec={} open_time={} open={} high={} low={} trades={}",
+                    spec, open_time, open, high, low, trades
+                ),
+            ),
+            WatchRecord::Flow {
+                ts,
+                symbol,
+                trades_per_sec,
+                window_delta,
+                cumulative_delta,
+                vwap,
+            } => (
+                ts,
+                "flow",
+                symbol,
+                vwap.map(num).unwrap_or_default(),
+                num(*window_delta),
+                String::new(),
+                String::new(),
+                String::new(),
+                String::new(),
+                format!("tps={:.2} cvd={}", trades_per_sec, cumulative_delta),
+            ),
+            WatchRecord::Status { ts, symbol, status } => (
+                ts,
+                "status",
+                symbol,
+                String::new(),
+                String::new(),
+                String::new(),
+                String::new(),
+                String::new(),
+                String::new(),
+                status.clone(),
+            ),
+            WatchRecord::Alert {
+                ts,
+                symbol,
+                alert_id,
+                input,
+                direction,
+                threshold,
+                value,
+            } => (
+                ts,
+                "alert",
+                symbol,
+                num(*value),
+                String::new(),
+                String::new(),
+                String::new(),
+                String::new(),
+                String::new(),
+                format!(
+                    "id={} {} {} {}",
+                    alert_id,
+                    input.label(),
+                    direction_label(*direction),
+                    threshold
+                ),
+            ),
+        };
+        format!(
+            "{},{},{},{},{},{},{},{},{},{}",
+            ts,
+            kind,
+            symbol,
+            price,
+            quantity,
+            bid,
+            bid_qty,
+            ask,
+            ask_qty,
+            csv_escape(&info)
+        )
+    }
+
+    fn table_row(&self) -> String {
+        let (ts, symbol, kind, detail) = match self {
+            WatchRecord::Ticker {
+                ts,
+                symbol,
+                last,
+                change_percent,
+                high,
+                low,
+                volume,
+            } => (
+                ts,
+                symbol,
+                "ticker",
+                format!(
+                    "last {}  {:+.2}%  high {}  low {}  vol {}",
+                    last, change_percent, high, low, volume
+                ),
+            ),
+            WatchRecord::Trade {
+                ts,
+                symbol,
+                price,
+                quantity,
+                side,
+                ..
+            } => (
+                ts,
+                symbol,
+                "trade",
+                format!("{} x {} {}", price, quantity, side_label(*side)),
+            ),
+            WatchRecord::Book {
+                ts,
+                symbol,
+                bid,
+                bid_qty,
+                ask,
+                ask_qty,
+                ..
+            } => (
+                ts,
+                symbol,
+                "book",
+                format!("{} x {}  |  {} x {}", bid, bid_qty, ask, ask_qty),
+            ),
+            WatchRecord::Depth {
+                ts,
+                symbol,
+                bids,
+                asks,
+                ..
+            } => (
+                ts,
+                symbol,
+                "depth",
+                format!(
+                    "bids {}  |  asks {}",
+                    format_levels(bids),
+                    format_levels(asks)
+                ),
+            ),
+            WatchRecord::Bar {
+                ts,
+                symbol,
+                spec,
+                open,
+                high,
+                low,
+                close,
+                volume,
+                ..
+            } => (
+                ts,
+                symbol,
+                "bar",
+                format!(
+                    "{} O {} H {} L {} C {} V {}",
+                    spec, open, high, low, close, volume
+                ),
+            ),
+            WatchRecord::Flow {
+                ts,
+                symbol,
+                trades_per_sec,
+                window_delta,
+                cumulative_delta,
+                vwap,
+            } => (
+                ts,
+                symbol,
+                "flow",
+                format!(
+                    "{:.2} tps  delta {:+}  cvd {:+}  vwap {}",
+                    trades_per_sec,
+                    window_delta,
+                    cumulative_delta,
+                    vwap.map(|v| v.to_string()).unwrap_or_else(|| "-".into())
+                ),
+            ),
+            WatchRecord::Status { ts, symbol, status } => (ts, symbol, "status", status.clone()),
+            WatchRecord::Alert {
+                ts,
+                symbol,
+                alert_id,
+                input,
+                direction,
+                threshold,
+                value,
+            } => (
+                ts,
+                symbol,
+                "ALERT",
+                format!(
+                    "#{} {} {} {} (value {})",
+                    alert_id,
+                    input.label(),
+                    direction_label(*direction),
+                    threshold,
+                    value
+                ),
+            ),
+        };
+        format!(
+            "{}  {:<10} {:<6}  {}",
+            format_clock(*ts),
+            symbol,
+            kind,
+            detail
+        )
+    }
+}
+
+/// Writes records in the selected format, flushing after each one
+pub struct RecordWriter<W: Write> {
+    out: W,
+    format: WatchFormat,
+    header_written: bool,
+}
+
+impl<W: Write> RecordWriter<W> {
+    pub fn new(out: W, format: WatchFormat) -> Self {
+        Self {
+            out,
+            format,
+            header_written: false,
+        }
+    }
+
+    pub fn write(&mut self, record: &WatchRecord) -> io::Result<()> {
+        match self.format {
+            WatchFormat::Ndjson => {
+                serde_json::to_writer(&mut self.out, record)?;
+                self.out.write_all(b"\n")?;
+            }
+            WatchFormat::Csv => {
+                if !self.header_written {
+                    writeln!(self.out, "{}", CSV_HEADER)?;
+                    self.header_written = true;
+                }
+                writeln!(self.out, "{}", record.csv_row())?;
+            }
+            WatchFormat::Table => writeln!(self.out, "{}", record.table_row())?,
+        }
+        self.out.flush()
+    }
+}
+
+/// Alerts given with `--alert`, evaluated against the event stream
+pub struct WatchAlerts {
+    manager: AlertManager,
+    /// Closed bars of this spec catch crossings between prints
+    bar_spec: Option<BarSpec>,
+}
+
+impl WatchAlerts {
+    /// Parse `--alert` specs written like `/alert:add` arguments,
+    /// e.g. `BTCUSDT above 70000` or `ETHUSDT cvd below -250 repeat`
+    pub fn parse(specs: &[String], symbols: &[String]) -> Result<Self> {
+        let mut manager = AlertManager::new();
+        for spec in specs {
+            let tokens: Vec<&str> = spec.split_whitespace().collect();
+            let action = parse_alert_add(&tokens)
+                .map_err(|e| QueryError::Usage(format!("Invalid --alert '{}': {}", spec, e)))?;
+            let AlertAction::Add {
+                symbol,
+                input,
+                direction,
+                threshold,
+                options,
+            } = action
+            else {
+                bail!(QueryError::Usage(format!("Invalid --alert '{}'", spec)));
+            };
+            if !symbols.contains(&symbol) {
+                bail!(QueryError::Usage(format!(
+                    "--alert '{}' refers to {}, which is not being watched",
+                    spec, symbol
+                )));
+            }
+            manager
+                .add_input_alert(symbol, input, direction, threshold, options)
+                .map_err(|e| QueryError::Usage(format!("Invalid --alert '{}': {}", spec, e)))?;
+        }
+        Ok(Self {
+            manager,
+            bar_spec: None,
+        })
+    }
+
+    /// Also check price alerts against closed bars of `spec`
+    pub fn with_bar_spec(mut self, spec: Option<BarSpec>) -> Self {
+        self.bar_spec = spec;
+        self
+    }
+
+    /// Alert records triggered by this event
+    pub fn evaluate(&mut self, event: &MarketEvent, ts: u64) -> Vec<WatchRecord> {
+        let mut triggers = Vec::new();
+        match event {
+            MarketEvent::PriceUpdate {
+                symbol,
+                price,
+                time,
+            } => {
+                triggers.extend(self.manager.evaluate_trade_price(symbol, *price, *time).0);
+            }
+            MarketEvent::TickerUpdate {
+                symbol,
+                last_price: price,
+                ..
+            } => {
+                triggers.extend(self.manager.evaluate(symbol, AlertInput::Price, *price).0);
+            }
+            MarketEvent::TradeAnalytics { symbol, analytics } => {
+                for input in AlertInput::TRADE_FLOW {
+                    if !self.manager.has_input(input) {
+                        continue;
+                    }
+                    if let Some(value) = input.value_from(analytics) {
+                        triggers.extend(self.manager.evaluate(symbol, input, value).0);
+                    }
+                }
+            }
+            MarketEvent::Bar { symbol, spec, bar } if self.bar_spec == Some(*spec) => {
+                triggers.extend(self.manager.evaluate_bar(symbol, bar).0);
+            }
+            MarketEvent::Microstructure { symbol, snapshot } => {
+                for input in AlertInput::MICROSTRUCTURE {
+                    if !self.manager.has_input(input) {
+                        continue;
+                    }
+                    if let Some(value) = input.value_from_book(snapshot) {
+                        triggers.extend(self.manager.evaluate(symbol, input, value).0);
+                    }
+                }
+            }
+            MarketEvent::BookTicker { symbol, ticker } => {
+                for input in AlertInput::BOOK_TICKER {
+                    if !self.manager.has_input(input) {
+                        continue;
+                    }
+                    if let Some(value) = input.value_from_ticker(ticker) {
+                        triggers.extend(self.manager.evaluate(symbol, input, value).0);
+                    }
+                }
+            }
+            _ => {}
+        }
+
+        triggers
+            .into_iter()
+            .map(|trigger| WatchRecord::Alert {
+                ts,
+                symbol: trigger.symbol,
+                alert_id: trigger.id,
+                input: trigger.input,
+                direction: trigger.direction,
+                threshold: trigger.threshold,
+                value: trigger.price,
+            })
+            .collect()
+    }
+}
+
+/// Run `xtrade watch` against stdout and return the process exit code
+pub async fn run(args: WatchArgs, config: &Config) -> i32 {
+    let stdout = io::stdout().lock();
+    match watch(&args, config, stdout).await {
+        Ok(()) => EXIT_OK,
+        Err(err) => {
+            eprintln!("error: {:#}", err);
+            exit_code(&err)
+        }
+    }
+}
+
+/// Subscribe to the requested symbols and stream records into `out` until
+/// Ctrl-C, SIGTERM, a closed pipe or `--limit` records
+pub async fn watch<W: Write>(args: &WatchArgs, config: &Config, out: W) -> Result<()> {
+    let symbols = normalize_symbols(&args.symbols)?;
+    let mut alerts = WatchAlerts::parse(&args.alerts, &symbols)?;
+    let kinds = args.events.clone();
+
+    let mut manager = MarketDataManager::new().with_trade_stream(config.binance.trade_stream);
+    if kinds.contains(&WatchEventKind::Trade) {
+        manager = manager.with_trade_events();
+    }
+    if kinds.contains(&WatchEventKind::Bar) {
+        manager = manager.with_bar_specs(config.candles.bars.clone(), 0);
+        alerts = alerts.with_bar_spec(config.candles.bars.first().copied());
+    }
+
+    for symbol in &symbols {
+        manager
+            .subscribe(symbol.clone())
+            .await
+            .with_context(|| format!("Failed to subscribe to {}", symbol))?;
+    }
+    info!(
+        "Watching {} with {} alerts",
+        symbols.join(", "),
+        args.alerts.len()
+    );
+
+    let mut writer = RecordWriter::new(out, args.format);
+    let receiver = manager.event_receiver();
+    let mut receiver = receiver.lock().await;
+    let mut written: u64 = 0;
+    let shutdown = shutdown_signal();
+    tokio::pin!(shutdown);
+
+    let result = 'stream: loop {
+        let event = tokio::select! {
+            _ = &mut shutdown => {
+                debug!("Watch interrupted");
+                break 'stream Ok(());
+            }
+            event = receiver.recv() => match event {
+                Some(event) => event,
+                None => break 'stream Err(anyhow::anyhow!("Market event stream closed")),
+            },
+        };
+        manager.process_market_event(&event).await;
+
+        let ts = now_ms();
+        let mut records = Vec::new();
+        records.extend(WatchRecord::from_event(&event, &kinds, args.depth, ts));
+        records.extend(alerts.evaluate(&event, ts));
+
+        for record in &records {
+            if let Err(e) = writer.write(record) {
+                if e.kind() == io::ErrorKind::BrokenPipe {
+                    debug!("Output closed; stopping watch");
+                    break 'stream Ok(());
+                }
+                break 'stream Err(anyhow::Error::new(e).context("Failed to write output"));
+            }
+            written += 1;
+            if args.limit.is_some_and(|limit| written >= limit) {
+                break 'stream Ok(());
+            }
+        }
+    };
+
+    drop(receiver);
+    if let Err(e) = manager.batch_unsubscribe(symbols).await {
+        warn!("Failed to unsubscribe cleanly: {}", e);
+    }
+    result
+}
+
+/// Resolves on Ctrl-C, or SIGTERM on Unix
+async fn shutdown_signal() {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{SignalKind, signal};
+        match signal(SignalKind::terminate()) {
+            Ok(mut terminate) => {
+                tokio::select! {
+                    _ = tokio::signal::ctrl_c() => {}
+                    _ = terminate.recv() => {}
+                }
+            }
+            Err(_) => {
+                let _ = tokio::signal::ctrl_c().await;
+            }
+        }
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = tokio::signal::ctrl_c().await;
+    }
+}
+
+fn top_levels(orderbook: &OrderBook, depth: usize) -> (Vec<[f64; 2]>, Vec<[f64; 2]>) {
+    let bids = orderbook
+        .bids
+        .iter()
+        .rev()
+        .take(depth)
+        .map(|(price, qty)| [price.0, *qty])
+        .collect();
+    let asks = orderbook
+        .asks
+        .iter()
+        .take(depth)
+        .map(|(price, qty)| [price.0, *qty])
+        .collect();
+    (bids, asks)
+}
+
+fn format_levels(levels: &[[f64; 2]]) -> String {
+    levels
+        .iter()
+        .map(|[price, qty]| format!("{}x{}", price, qty))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn side_label(side: TakerSide) -> &'static str {
+    match side {
+        TakerSide::Buy => "buy",
+        TakerSide::Sell => "sell",
+    }
+}
+
+fn direction_label(direction: AlertDirection) -> &'static str {
+    match direction {
+        AlertDirection::Above => "above",
+        AlertDirection::Below => "below",
+    }
+}
+
+fn csv_escape(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn format_clock(ts: u64) -> String {
+    Local
+        .timestamp_millis_opt(ts as i64)
+        .single()
+        .map(|dt| dt.format("%H:%M:%S%.3f").to_string())
+        .unwrap_or_else(|| ts.to_string())
+}
+
+fn now_ms() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::market_data::{BookTicker, TradePrint};
+
+    fn trade_event(price: f64) -> MarketEvent {
+        MarketEvent::Trade {
+            symbol: "BTCUSDT".into(),
+            trade: TradePrint {
+                trade_id: 7,
+                ..TradePrint::single_fill(1_000, price, 0.5, TakerSide::Buy)
+            },
+        }
+    }
+
+    #[test]
+    fn events_outside_the_selection_are_skipped() {
+        let kinds = [WatchEventKind::Book];
+        assert!(WatchRecord::from_event(&trade_event(100.0), &kinds, 5, 1).is_none());
+
+        let book = MarketEvent::BookTicker {
+            symbol: "BTCUSDT".into(),
+            ticker: BookTicker {
+                update_id: 9,
+                bid_price: 99.5,
+                bid_qty: 1.0,
+                ask_price: 100.5,
+                ask_qty: 2.0,
+            },
+        };
+        let record = WatchRecord::from_event(&book, &kinds, 5, 1).unwrap();
+        assert!(matches!(record, WatchRecord::Book { update_id: 9, .. }));
+    }
+
+    #[test]
+    fn ndjson_records_are_tagged_single_lines() {
+        let record =
+            WatchRecord::from_event(&trade_event(100.25), &[WatchEventKind::Trade], 5, 42).unwrap();
+        let mut writer = RecordWriter::new(Vec::new(), WatchFormat::Ndjson);
+        writer.write(&record).unwrap();
+        writer.write(&record).unwrap();
+
+        let output = String::from_utf8(writer.out).unwrap();
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines.len(), 2);
+        let value: serde_json::Value = serde_json::from_str(lines[0]).unwrap();
+        assert_eq!(value["type"], "trade");
+        assert_eq!(value["symbol"], "BTCUSDT");
+        assert_eq!(value["price"], 100.25);
+        assert_eq!(value["side"], "buy");
+        assert_eq!(value["ts"], 42);
+    }
+
+    #[test]
+    fn csv_output_has_one_header_and_fixed_columns() {
+        let record =
+            WatchRecord::from_event(&trade_event(100.0), &[WatchEventKind::Trade], 5, 42).unwrap();
+        let mut writer = RecordWriter::new(Vec::new(), WatchFormat::Csv);
+        writer.write(&record).unwrap();
+        writer.write(&record).unwrap();
+
+        let output = String::from_utf8(writer.out).unwrap();
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0], CSV_HEADER);
+        let header_columns = CSV_HEADER.split(',').count();
+        assert_eq!(lines[1].split(',').count(), header_columns);
+        assert!(lines[1].starts_with("42,trade,BTCUSDT,100,0.5,"));
+    }
+
+    #[test]
+    fn broken_pipe_surfaces_as_an_io_error() {
+        struct ClosedPipe;
+        impl Write for ClosedPipe {
+            fn write(&mut self, _: &[u8]) -> io::Result<usize> {
+                Err(io::ErrorKind::BrokenPipe.into())
+            }
+            fn flush(&mut self) -> io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let record =
+            WatchRecord::from_event(&trade_event(100.0), &[WatchEventKind::Trade], 5, 1).unwrap();
+        let err = RecordWriter::new(ClosedPipe, WatchFormat::Table)
+            .write(&record)
+            .unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::BrokenPipe);
+    }
+
+    #[test]
+    fn alert_specs_are_parsed_and_trigger_records() {
+        let symbols = vec!["BTCUSDT".to_string()];
+        let mut alerts = WatchAlerts::parse(&["btcusdt above 100".to_string()], &symbols).unwrap();
+
+        let price_update = |price| MarketEvent::PriceUpdate {
+            symbol: "BTCUSDT".into(),
+            price,
+            time: 2,
+        };
+        assert!(alerts.evaluate(&price_update(99.0), 1).is_empty());
+        let records = alerts.evaluate(&price_update(101.0), 2);
+        assert_eq!(records.len(), 1);
+        assert!(matches!(
+            records[0],
+            WatchRecord::Alert {
+                alert_id: 1,
+                direction: AlertDirection::Above,
+                ..
+            }
+        ));
+
+        assert!(WatchAlerts::parse(&["ETHUSDT above 1".to_string()], &symbols).is_err());
+        assert!(WatchAlerts::parse(&["BTCUSDT sideways 1".to_string()], &symbols).is_err());
+    }
+}
@@ -0,0 +1,430 @@
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use futures_util::{SinkExt, StreamExt};
+use serde_json::{Value, json};
+use tokio::net::TcpListener;
+use tokio::sync::mpsc;
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::tungstenite::handshake::server::{Request as WsRequest, Response};
+use wiremock::matchers::{header, method, path, query_param};
+use wiremock::{Mock, MockServer, Request, ResponseTemplate};
+use xtrade::account::{AccountEvent, AccountState, AccountUpdate, StreamStatus};
+use xtrade::binance::types::RestApiError;
+use xtrade::binance::{ApiCredentials, BinanceRestClient};
+
+const API_KEY: &str = "test-api-key";
+const API_SECRET: &str = "test-api-secret";
+
+fn credentials() -> ApiCredentials {
+    ApiCredentials::new(API_KEY, API_SECRET).unwrap()
+}
+
+fn signed_client(server: &MockServer) -> BinanceRestClient {
+    BinanceRestClient::new(server.uri()).with_credentials(credentials())
+}
+
+/// Matches requests whose `signature` is the HMAC of the rest of the query string
+fn correctly_signed(request: &Request) -> bool {
+    let Some(query) = request.url.query() else {
+        return false;
+    };
+    let Some((payload, signature)) = query.rsplit_once("&signature=") else {
+        return false;
+    };
+    credentials().sign(payload) == signature
+}
+
+fn account_json() -> Value {
+    json!({
+        "makerCommission": 10,
+        "takerCommission": 10,
+        "canTrade": true,
+        "canWithdraw": true,
+        "canDeposit": true,
+        "updateTime": 1_700_000_000_000u64,
+        "accountType": "SPOT",
+        "balances": [
+            {"asset": "BTC", "free": "0.50000000", "locked": "0.10000000"},
+            {"asset": "USDT", "free": "1200.00000000", "locked": "0.00000000"}
+        ],
+        "permissions": ["SPOT"]
+    })
+}
+
+fn open_orders_json() -> Value {
+    json!([{
+        "symbol": "BTCUSDT",
+        "orderId": 11,
+        "orderListId": -1,
+        "clientOrderId": "resting",
+        "price": "60000.00",
+        "origQty": "0.10000000",
+        "executedQty": "0.00000000",
+        "cummulativeQuoteQty": "0.00000000",
+        "status": "NEW",
+        "timeInForce": "GTC",
+        "type": "LIMIT",
+        "side": "SELL",
+        "stopPrice": "0.00",
+        "icebergQty": "0.00",
+        "time": 1_700_000_000_000u64,
+        "updateTime": 1_700_000_000_000u64,
+        "isWorking": true
+    }])
+}
+
+fn execution_report(order_id: u64, status: &str) -> String {
+    json!({
+        "e": "executionReport",
+        "E": 1_700_000_001_000u64,
+        "s": "BTCUSDT",
+        "c": "resting",
+        "C": "",
+        "S": "SELL",
+        "o": "LIMIT",
+        "f": "GTC",
+        "q": "0.10000000",
+        "p": "60000.00",
+        "P": "0.00",
+        "x": "TRADE",
+        "X": status,
+        "r": "NONE",
+        "i": order_id,
+        "l": "0.10000000",
+        "z": "0.10000000",
+        "L": "60000.00",
+        "n": "6.00",
+        "N": "USDT",
+        "T": 1_700_000_001_000u64,
+        "t": 99,
+        "O": 1_700_000_000_000u64,
+        "m": true
+    })
+    .to_string()
+}
+
+async fn mount_account_endpoints(server: &MockServer) {
+    Mock::given(method("GET"))
+        .and(path("/api/v3/account"))
+        .and(header("X-MBX-APIKEY", API_KEY))
+        .and(correctly_signed)
+        .respond_with(ResponseTemplate::new(200).set_body_json(account_json()))
+        .mount(server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/v3/openOrders"))
+        .and(header("X-MBX-APIKEY", API_KEY))
+        .and(correctly_signed)
+        .respond_with(ResponseTemplate::new(200).set_body_json(open_orders_json()))
+        .mount(server)
+        .await;
+    Mock::given(method("PUT"))
+        .and(path("/api/v3/userDataStream"))
+        .and(header("X-MBX-APIKEY", API_KEY))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({})))
+        .mount(server)
+        .await;
+    Mock::given(method("DELETE"))
+        .and(path("/api/v3/userDataStream"))
+        .and(header("X-MBX-APIKEY", API_KEY))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({})))
+        .mount(server)
+        .await;
+}
+
+async fn mount_listen_key(server: &MockServer, listen_key: &str) {
+    Mock::given(method("POST"))
+        .and(path("/api/v3/userDataStream"))
+        .and(header("X-MBX-APIKEY", API_KEY))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "listenKey": listen_key })))
+        .up_to_n_times(1)
+        .mount(server)
+        .await;
+}
+
+/// WebSocket server that sends `scripts[n]` on the n-th connection and records request paths
+async fn spawn_stream_server(scripts: Vec<Vec<String>>) -> (String, Arc<Mutex<Vec<String>>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("ws://{}", listener.local_addr().unwrap());
+    let paths = Arc::new(Mutex::new(Vec::new()));
+    let recorded = paths.clone();
+
+    tokio::spawn(async move {
+        for script in scripts {
+            let (stream, _) = listener.accept().await.unwrap();
+            let paths = recorded.clone();
+            tokio::spawn(async move {
+                // The error type is fixed by tungstenite's handshake callback
+                #[allow(clippy::result_large_err)]
+                let callback = |request: &WsRequest, response: Response| {
+                    paths.lock().unwrap().push(request.uri().path().to_string());
+                    Ok(response)
+                };
+                let mut ws = tokio_tungstenite::accept_hdr_async(stream, callback)
+                    .await
+                    .unwrap();
+                for message in script {
+                    ws.send(Message::Text(message)).await.unwrap();
+                }
+                while let Some(Ok(message)) = ws.next().await {
+                    if message.is_close() {
+                        break;
+                    }
+                }
+            });
+        }
+    });
+
+    (url, paths)
+}
+
+async fn next_event(events: &mut mpsc::UnboundedReceiver<AccountEvent>) -> AccountEvent {
+    tokio::time::timeout(Duration::from_secs(5), events.recv())
+        .await
+        .expect("account event within 5s")
+        .expect("account stream open")
+}
+
+#[tokio::test]
+async fn signed_requests_carry_api_key_window_and_signature() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/v3/account"))
+        .and(header("X-MBX-APIKEY", API_KEY))
+        .and(query_param("recvWindow", "7000"))
+        .and(correctly_signed)
+        .respond_with(ResponseTemplate::new(200).set_body_json(account_json()))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = signed_client(&server).with_recv_window(7_000);
+    let account = client.get_account().await.unwrap();
+
+    assert!(account.can_trade);
+    assert_eq!(account.balances.len(), 2);
+    assert_eq!(account.balances[0].asset, "BTC");
+}
+
+#[tokio::test]
+async fn signed_timestamps_follow_the_exchange_clock_offset() {
+    let server = MockServer::start().await;
+    mount_account_endpoints(&server).await;
+
+    let client = signed_client(&server);
+    client.set_time_offset_ms(-3_600_000);
+    client.get_open_orders(Some("BTCUSDT")).await.unwrap();
+
+    let requests = server.received_requests().await.unwrap();
+    let url = &requests[0].url;
+    let timestamp: u64 = url
+        .query_pairs()
+        .find(|(key, _)| key == "timestamp")
+        .map(|(_, value)| value.parse().unwrap())
+        .unwrap();
+    let local_ms = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_millis() as u64;
+    assert!(local_ms - timestamp >= 3_590_000);
+    assert!(url.query().unwrap().starts_with("symbol=BTCUSDT&"));
+}
+
+#[tokio::test]
+async fn rejected_credentials_surface_as_unauthorized() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/v3/account"))
+        .respond_with(ResponseTemplate::new(401).set_body_json(json!({
+            "code": -2015,
+            "msg": "Invalid API-key, IP, or permissions for action."
+        })))
+        .mount(&server)
+        .await;
+
+    let err = signed_client(&server).get_account().await.unwrap_err();
+
+    assert!(matches!(
+        err.downcast_ref::<RestApiError>(),
+        Some(RestApiError::Unauthorized(msg)) if msg.contains("Invalid API-key")
+    ));
+}
+
+#[tokio::test]
+async fn account_requests_need_credentials() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(account_json()))
+        .expect(0)
+        .mount(&server)
+        .await;
+
+    let client = BinanceRestClient::new(server.uri());
+    let err = client.get_account().await.unwrap_err();
+
+    assert!(matches!(
+        err.downcast_ref::<RestApiError>(),
+        Some(RestApiError::MissingCredentials)
+    ));
+}
+
+#[tokio::test]
+async fn listen_key_is_created_kept_alive_and_closed() {
+    let server = MockServer::start().await;
+    mount_listen_key(&server, "key-1").await;
+    for verb in ["PUT", "DELETE"] {
+        Mock::given(method(verb))
+            .and(path("/api/v3/userDataStream"))
+            .and(header("X-MBX-APIKEY", API_KEY))
+            .and(query_param("listenKey", "key-1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({})))
+            .expect(1)
+            .mount(&server)
+            .await;
+    }
+
+    let client = signed_client(&server);
+    let listen_key = client.create_listen_key().await.unwrap();
+    assert_eq!(listen_key, "key-1");
+    client.keepalive_listen_key(&listen_key).await.unwrap();
+    client.close_listen_key(&listen_key).await.unwrap();
+
+    // Listen key requests are not signed
+    let requests = server.received_requests().await.unwrap();
+    assert!(
+        requests
+            .iter()
+            .all(|request| !request.url.as_str().contains("signature="))
+    );
+}
+
+#[tokio::test]
+async fn stream_loads_snapshot_then_applies_updates_and_keeps_key_alive() {
+    let server = MockServer::start().await;
+    mount_listen_key(&server, "key-1").await;
+    mount_account_endpoints(&server).await;
+    let (ws_url, paths) = spawn_stream_server(vec![vec![execution_report(11, "FILLED")]]).await;
+
+    let (tx, mut events) = mpsc::unbounded_channel();
+    let handle = xtrade::account::spawn_account_stream(
+        signed_client(&server),
+        ws_url,
+        Duration::from_millis(200),
+        tx,
+    );
+
+    let mut state = AccountState::new();
+    let AccountEvent::Snapshot {
+        balances,
+        open_orders,
+        time_ms,
+    } = next_event(&mut events).await
+    else {
+        panic!("expected the REST snapshot first");
+    };
+    state.apply_snapshot(balances, open_orders, time_ms);
+    assert_eq!(state.balance("BTC").unwrap().locked, 0.1);
+    assert_eq!(state.open_orders().count(), 1);
+
+    assert_eq!(
+        next_event(&mut events).await,
+        AccountEvent::Status(StreamStatus::Live)
+    );
+
+    let AccountEvent::Update(update) = next_event(&mut events).await else {
+        panic!("expected an execution report");
+    };
+    assert!(matches!(update, AccountUpdate::Execution(_)));
+    state.apply(&update);
+    assert_eq!(state.open_orders().count(), 0);
+    assert_eq!(paths.lock().unwrap().as_slice(), ["/ws/key-1"]);
+
+    tokio::time::sleep(Duration::from_millis(500)).await;
+    handle.stop();
+    tokio::time::timeout(Duration::from_secs(5), handle.task)
+        .await
+        .expect("stream stops")
+        .unwrap();
+
+    let requests = server.received_requests().await.unwrap();
+    let count = |verb: &str| {
+        requests
+            .iter()
+            .filter(|request| request.method.to_string() == verb)
+            .count()
+    };
+    assert!(count("PUT") >= 1, "listen key kept alive");
+    assert_eq!(count("DELETE"), 1, "listen key closed on stop");
+}
+
+#[tokio::test]
+async fn expired_listen_key_is_replaced() {
+    let server = MockServer::start().await;
+    mount_listen_key(&server, "key-1").await;
+    mount_listen_key(&server, "key-2").await;
+    mount_account_endpoints(&server).await;
+    let expired = json!({"e": "listenKeyExpired", "E": 1_700_000_000_000u64, "listenKey": "key-1"});
+    let (ws_url, paths) = spawn_stream_server(vec![vec![expired.to_string()], vec![]]).await;
+
+    let (tx, mut events) = mpsc::unbounded_channel();
+    let handle = xtrade::account::spawn_account_stream(
+        signed_client(&server),
+        ws_url,
+        Duration::from_secs(60),
+        tx,
+    );
+
+    let mut statuses = Vec::new();
+    let mut snapshots = 0;
+    while snapshots < 2 {
+        match next_event(&mut events).await {
+            AccountEvent::Snapshot { .. } => snapshots += 1,
+            AccountEvent::Status(status) => statuses.push(status),
+            AccountEvent::Update(update) => panic!("unexpected update {:?}", update),
+        }
+    }
+
+    assert!(statuses.contains(&StreamStatus::Reconnecting {
+        reason: "listen key expired".to_string()
+    }));
+    assert_eq!(paths.lock().unwrap().as_slice(), ["/ws/key-1", "/ws/key-2"]);
+
+    handle.stop();
+    tokio::time::timeout(Duration::from_secs(5), handle.task)
+        .await
+        .expect("stream stops")
+        .unwrap();
+}
+
+#[tokio::test]
+async fn rejected_credentials_stop_the_stream() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/api/v3/userDataStream"))
+        .respond_with(ResponseTemplate::new(401).set_body_json(json!({
+            "code": -2014,
+            "msg": "API-key format invalid."
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let (tx, mut events) = mpsc::unbounded_channel();
+    let handle = xtrade::account::spawn_account_stream(
+        signed_client(&server),
+        "ws://127.0.0.1:9",
+        Duration::from_secs(60),
+        tx,
+    );
+
+    let AccountEvent::Status(StreamStatus::Failed { reason }) = next_event(&mut events).await
+    else {
+        panic!("expected the stream to give up");
+    };
+    assert!(reason.contains("API-key format invalid"));
+    tokio::time::timeout(Duration::from_secs(5), handle.task)
+        .await
+        .expect("stream exits")
+        .unwrap();
+}
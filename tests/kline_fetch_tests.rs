@@ -0,0 +1,195 @@
+use std::path::Path;
+
+use serde_json::{Value, json};
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, Request, ResponseTemplate};
+use xtrade::binance::rest::BinanceRestClient;
+use xtrade::fetch::{ExportFormat, KlineFetchRequest, fetch_klines};
+
+const MINUTE_MS: u64 = 60_000;
+const START_MS: u64 = 1_704_067_200_000;
+
+/// Serve 1m klines from `START_MS` the way Binance does: rows with open time
+/// in `[startTime, endTime]`, at most `limit` of them, up to `available` rows
+fn kline_pages(available: u64) -> impl Fn(&Request) -> ResponseTemplate + Send + Sync {
+    move |request: &Request| {
+        let param = |name: &str| {
+            request
+                .url
+                .query_pairs()
+                .find(|(key, _)| key == name)
+                .and_then(|(_, value)| value.parse::<u64>().ok())
+        };
+        let start = param("startTime").unwrap_or(START_MS);
+        let end = param("endTime").unwrap_or(u64::MAX);
+        let limit = param("limit").unwrap_or(500);
+
+        let first_index = start.saturating_sub(START_MS).div_ceil(MINUTE_MS);
+        let rows: Vec<Value> = (first_index..available)
+            .map(|i| START_MS + i * MINUTE_MS)
+            .take_while(|open| *open <= end)
+            .take(limit as usize)
+            .map(|open| {
+                json!([
+                    open,
+                    "100.0",
+                    "101.0",
+                    "99.0",
+                    "100.5",
+                    "2.0",
+                    open + MINUTE_MS - 1,
+                    "201.0",
+                    12,
+                    "1.0",
+                    "100.5",
+                    "0"
+                ])
+            })
+            .collect();
+
+        ResponseTemplate::new(200)
+            .insert_header("x-mbx-used-weight-1m", "10")
+            .set_body_json(rows)
+    }
+}
+
+fn request(out: &Path, minutes: u64, resume: bool) -> KlineFetchRequest {
+    KlineFetchRequest {
+        symbol: "BTCUSDT".to_string(),
+        interval: "1m".to_string(),
+        start_ms: START_MS,
+        end_ms: START_MS + minutes * MINUTE_MS,
+        out: out.to_path_buf(),
+        format: ExportFormat::Csv,
+        resume,
+        force: false,
+    }
+}
+
+fn csv_open_times(out: &Path) -> Vec<u64> {
+    std::fs::read_to_string(out)
+        .unwrap()
+        .lines()
+        .skip(1)
+        .map(|line| line.split(',').next().unwrap().parse().unwrap())
+        .collect()
+}
+
+#[tokio::test]
+async fn fetch_paginates_past_the_row_limit() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/v3/klines"))
+        .respond_with(kline_pages(5_000))
+        .mount(&server)
+        .await;
+
+    let dir = tempfile::tempdir().unwrap();
+    let out = dir.path().join("btc.csv");
+    let client = BinanceRestClient::new(server.uri());
+
+    let mut pages_seen = 0;
+    let summary = fetch_klines(&client, &request(&out, 2_500, false), |_| pages_seen += 1)
+        .await
+        .unwrap();
+
+    assert_eq!(summary.rows_written, 2_500);
+    assert_eq!(summary.pages, 3);
+    assert_eq!(pages_seen, 3);
+
+    let open_times = csv_open_times(&out);
+    assert_eq!(open_times.len(), 2_500);
+    assert_eq!(open_times[0], START_MS);
+    assert!(open_times.windows(2).all(|w| w[1] - w[0] == MINUTE_MS));
+    assert_eq!(
+        open_times.last().copied(),
+        Some(START_MS + 2_499 * MINUTE_MS)
+    );
+}
+
+#[tokio::test]
+async fn fetch_resumes_after_the_last_written_row() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/v3/klines"))
+        .respond_with(kline_pages(5_000))
+        .mount(&server)
+        .await;
+
+    let dir = tempfile::tempdir().unwrap();
+    let out = dir.path().join("btc.csv");
+    let client = BinanceRestClient::new(server.uri());
+
+    fetch_klines(&client, &request(&out, 1_200, false), |_| {})
+        .await
+        .unwrap();
+    // A second run without --resume or --force must not clobber the file.
+    assert!(
+        fetch_klines(&client, &request(&out, 2_000, false), |_| {})
+            .await
+            .is_err()
+    );
+
+    let summary = fetch_klines(&client, &request(&out, 2_000, true), |_| {})
+        .await
+        .unwrap();
+    assert_eq!(summary.resumed_after_ms, Some(START_MS + 1_199 * MINUTE_MS));
+    assert_eq!(summary.rows_written, 800);
+
+    let open_times = csv_open_times(&out);
+    assert_eq!(open_times.len(), 2_000);
+    assert!(open_times.windows(2).all(|w| w[1] - w[0] == MINUTE_MS));
+}
+
+#[tokio::test]
+async fn fetch_waits_out_rate_limit_responses() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/v3/klines"))
+        .respond_with(ResponseTemplate::new(429).insert_header("retry-after", "0"))
+        .up_to_n_times(1)
+        .with_priority(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/v3/klines"))
+        .respond_with(kline_pages(100))
+        .with_priority(2)
+        .mount(&server)
+        .await;
+
+    let dir = tempfile::tempdir().unwrap();
+    let out = dir.path().join("btc.ndjson");
+    let client = BinanceRestClient::new(server.uri());
+    let mut fetch = request(&out, 100, false);
+    fetch.format = ExportFormat::Ndjson;
+
+    let summary = fetch_klines(&client, &fetch, |_| {}).await.unwrap();
+
+    assert_eq!(summary.rows_written, 100);
+    assert_eq!(server.received_requests().await.unwrap().len(), 2);
+    let lines = std::fs::read_to_string(&out).unwrap();
+    let first: Value = serde_json::from_str(lines.lines().next().unwrap()).unwrap();
+    assert_eq!(first["open_time_ms"], json!(START_MS));
+    assert_eq!(first["trades"], json!(12));
+}
+
+#[tokio::test]
+async fn fetch_stops_when_the_ip_is_banned() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/v3/klines"))
+        .respond_with(ResponseTemplate::new(418).insert_header("retry-after", "120"))
+        .mount(&server)
+        .await;
+
+    let dir = tempfile::tempdir().unwrap();
+    let out = dir.path().join("btc.csv");
+    let client = BinanceRestClient::new(server.uri());
+
+    let err = fetch_klines(&client, &request(&out, 100, false), |_| {})
+        .await
+        .unwrap_err();
+    assert!(format!("{:#}", err).contains("IP banned"));
+    assert_eq!(server.received_requests().await.unwrap().len(), 1);
+}
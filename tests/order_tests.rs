@@ -0,0 +1,182 @@
+use serde_json::json;
+use wiremock::matchers::{header, method, path, query_param};
+use wiremock::{Mock, MockServer, Request, ResponseTemplate};
+use xtrade::binance::types::RestApiError;
+use xtrade::binance::{ApiCredentials, BinanceRestClient};
+
+const API_KEY: &str = "test-api-key";
+const API_SECRET: &str = "test-api-secret";
+
+fn credentials() -> ApiCredentials {
+    ApiCredentials::new(API_KEY, API_SECRET).unwrap()
+}
+
+fn signed_client(server: &MockServer) -> BinanceRestClient {
+    BinanceRestClient::new(server.uri()).with_credentials(credentials())
+}
+
+/// Matches requests whose `signature` is the HMAC of the rest of the query string
+fn correctly_signed(request: &Request) -> bool {
+    let Some(query) = request.url.query() else {
+        return false;
+    };
+    let Some((payload, signature)) = query.rsplit_once("&signature=") else {
+        return false;
+    };
+    credentials().sign(payload) == signature
+}
+
+fn limit_params() -> Vec<(&'static str, String)> {
+    vec![
+        ("symbol", "BTCUSDT".to_string()),
+        ("side", "BUY".to_string()),
+        ("type", "LIMIT".to_string()),
+        ("quantity", "0.01000".to_string()),
+        ("timeInForce", "GTC".to_string()),
+        ("price", "65000.00".to_string()),
+        ("newClientOrderId", "xtrade-1-1".to_string()),
+    ]
+}
+
+fn order_ack(order_id: u64) -> serde_json::Value {
+    json!({
+        "symbol": "BTCUSDT",
+        "orderId": order_id,
+        "orderListId": -1,
+        "clientOrderId": "xtrade-1-1",
+        "transactTime": 1_700_000_000_000u64,
+        "price": "65000.00",
+        "origQty": "0.01000",
+        "executedQty": "0.00000",
+        "status": "NEW",
+        "timeInForce": "GTC",
+        "type": "LIMIT",
+        "side": "BUY"
+    })
+}
+
+#[tokio::test]
+async fn test_orders_only_reach_the_test_endpoint() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/api/v3/order/test"))
+        .and(header("X-MBX-APIKEY", API_KEY))
+        .and(query_param("price", "65000.00"))
+        .and(correctly_signed)
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({})))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/api/v3/order"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(order_ack(1)))
+        .expect(0)
+        .mount(&server)
+        .await;
+
+    let ack = signed_client(&server)
+        .place_order(&limit_params(), true)
+        .await
+        .unwrap();
+
+    assert!(ack.is_none());
+}
+
+#[tokio::test]
+async fn live_orders_return_the_exchange_acknowledgement() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/api/v3/order"))
+        .and(query_param("newClientOrderId", "xtrade-1-1"))
+        .and(correctly_signed)
+        .respond_with(ResponseTemplate::new(200).set_body_json(order_ack(42)))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let ack = signed_client(&server)
+        .place_order(&limit_params(), false)
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(ack.order_id, 42);
+    assert_eq!(ack.client_order_id, "xtrade-1-1");
+    assert_eq!(ack.status, "NEW");
+}
+
+#[tokio::test]
+async fn exchange_rejections_carry_code_and_message() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/api/v3/order"))
+        .respond_with(ResponseTemplate::new(400).set_body_json(json!({
+            "code": -2010,
+            "msg": "Account has insufficient balance for requested action."
+        })))
+        .mount(&server)
+        .await;
+
+    let err = signed_client(&server)
+        .place_order(&limit_params(), false)
+        .await
+        .unwrap_err();
+
+    assert!(matches!(
+        err.downcast_ref::<RestApiError>(),
+        Some(RestApiError::OrderRejected { code: -2010, msg }) if msg.contains("insufficient balance")
+    ));
+}
+
+#[tokio::test]
+async fn cancel_sends_symbol_and_order_id() {
+    let server = MockServer::start().await;
+    Mock::given(method("DELETE"))
+        .and(path("/api/v3/order"))
+        .and(query_param("symbol", "BTCUSDT"))
+        .and(query_param("orderId", "42"))
+        .and(correctly_signed)
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "symbol": "BTCUSDT",
+            "orderId": 42,
+            "origClientOrderId": "xtrade-1-1",
+            "status": "CANCELED"
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let ack = signed_client(&server)
+        .cancel_order("BTCUSDT", 42)
+        .await
+        .unwrap();
+
+    assert_eq!(ack.order_id, 42);
+    assert_eq!(ack.status, "CANCELED");
+}
+
+#[tokio::test]
+async fn replace_cancels_and_places_in_one_request() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/api/v3/order/cancelReplace"))
+        .and(query_param("cancelReplaceMode", "STOP_ON_FAILURE"))
+        .and(query_param("cancelOrderId", "42"))
+        .and(correctly_signed)
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "cancelResult": "SUCCESS",
+            "newOrderResult": "SUCCESS",
+            "cancelResponse": {"symbol": "BTCUSDT", "orderId": 42, "status": "CANCELED"},
+            "newOrderResponse": order_ack(43)
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let ack = signed_client(&server)
+        .cancel_replace_order(42, &limit_params())
+        .await
+        .unwrap();
+
+    assert_eq!(ack.order_id, 43);
+}
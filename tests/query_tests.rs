@@ -0,0 +1,149 @@
+use clap::Parser;
+use serde_json::{Value, json};
+use wiremock::matchers::{method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+use xtrade::binance::rest::BinanceRestClient;
+use xtrade::cli::{Cli, Commands, QueryCommand};
+use xtrade::query::{EXIT_FAILURE, EXIT_NOT_FOUND, execute, exit_code};
+
+fn ticker_json(symbol: &str, last: &str) -> Value {
+    json!({
+        "symbol": symbol,
+        "priceChange": "150.00000000",
+        "priceChangePercent": "0.350",
+        "weightedAvgPrice": "43010.12000000",
+        "prevClosePrice": "42850.00000000",
+        "lastPrice": last,
+        "lastQty": "0.01000000",
+        "bidPrice": "43000.00000000",
+        "bidQty": "1.20000000",
+        "askPrice": "43000.10000000",
+        "askQty": "0.80000000",
+        "openPrice": "42850.00000000",
+        "highPrice": "43200.00000000",
+        "lowPrice": "42700.00000000",
+        "volume": "12345.67800000",
+        "quoteVolume": "530000000.12000000",
+        "openTime": 1_704_000_000_000u64,
+        "closeTime": 1_704_086_399_999u64,
+        "firstId": 1,
+        "lastId": 1000,
+        "count": 999
+    })
+}
+
+fn query_command(args: &[&str]) -> QueryCommand {
+    let cli = Cli::try_parse_from(std::iter::once("xtrade").chain(args.iter().copied()))
+        .expect("arguments should parse");
+    match cli.command {
+        Some(Commands::Query(command)) => command,
+        other => panic!("expected a query command, got {:?}", other),
+    }
+}
+
+async fn run(server: &MockServer, args: &[&str]) -> (anyhow::Result<()>, String) {
+    let client = BinanceRestClient::new(server.uri());
+    let mut out = Vec::new();
+    let result = execute(&query_command(args), &client, &mut out).await;
+    (result, String::from_utf8(out).unwrap())
+}
+
+#[tokio::test]
+async fn quote_prints_requested_symbols_in_order() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/v3/ticker/24hr"))
+        .and(query_param("symbols", r#"["ETHUSDT","BTCUSDT"]"#))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+            ticker_json("BTCUSDT", "43000.05000000"),
+            ticker_json("ETHUSDT", "2300.00000000"),
+        ])))
+        .mount(&server)
+        .await;
+
+    let (result, table) = run(&server, &["quote", "eth-usdt", "BTCUSDT"]).await;
+    result.unwrap();
+
+    let lines: Vec<&str> = table.lines().collect();
+    assert!(lines[0].starts_with("SYMBOL"));
+    assert!(lines[1].starts_with("ETHUSDT"));
+    assert!(lines[2].starts_with("BTCUSDT"));
+    assert!(lines[2].contains("43000.05"));
+
+    let (result, json) = run(&server, &["quote", "ETHUSDT", "BTCUSDT", "--json"]).await;
+    result.unwrap();
+    let rows: Value = serde_json::from_str(&json).unwrap();
+    assert_eq!(rows[0]["symbol"], "ETHUSDT");
+    assert_eq!(rows[1]["last"], json!(43000.05));
+    assert_eq!(rows[1]["bid"], json!(43000.0));
+}
+
+#[tokio::test]
+async fn unknown_symbol_exits_with_not_found() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/v3/ticker/24hr"))
+        .respond_with(
+            ResponseTemplate::new(400)
+                .set_body_json(json!({"code": -1121, "msg": "Invalid symbol."})),
+        )
+        .mount(&server)
+        .await;
+
+    let (result, out) = run(&server, &["ticker", "NOPE"]).await;
+    let err = result.unwrap_err();
+    assert_eq!(exit_code(&err), EXIT_NOT_FOUND);
+    assert!(out.is_empty());
+}
+
+#[tokio::test]
+async fn server_errors_exit_with_failure() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/v3/depth"))
+        .respond_with(ResponseTemplate::new(503))
+        .mount(&server)
+        .await;
+
+    let (result, _) = run(&server, &["book", "BTCUSDT"]).await;
+    assert_eq!(exit_code(&result.unwrap_err()), EXIT_FAILURE);
+}
+
+#[tokio::test]
+async fn book_reports_levels_with_cumulative_quantity() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/v3/depth"))
+        .and(query_param("limit", "2"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "lastUpdateId": 77,
+            "bids": [["100.00", "1.0"], ["99.90", "2.5"]],
+            "asks": [["100.10", "0.5"], ["100.20", "3.0"]]
+        })))
+        .mount(&server)
+        .await;
+
+    let (result, json) = run(&server, &["book", "BTCUSDT", "--depth", "2", "--json"]).await;
+    result.unwrap();
+    let book: Value = serde_json::from_str(&json).unwrap();
+    assert_eq!(book["last_update_id"], 77);
+    assert_eq!(book["bids"][1]["cumulative"], json!(3.5));
+    assert_eq!(book["asks"][0]["price"], json!(100.1));
+
+    let (result, table) = run(&server, &["book", "BTCUSDT", "--depth", "2"]).await;
+    result.unwrap();
+    let sides: Vec<&str> = table
+        .lines()
+        .skip(1)
+        .take(4)
+        .map(|line| line.split_whitespace().next().unwrap())
+        .collect();
+    assert_eq!(sides, ["ASK", "ASK", "BID", "BID"]);
+    assert!(table.contains("update id 77"));
+}
+
+#[test]
+fn depth_outside_the_api_range_is_a_usage_error() {
+    let err = Cli::try_parse_from(["xtrade", "book", "BTCUSDT", "--depth", "0"]).unwrap_err();
+    assert_eq!(err.exit_code(), 2);
+}